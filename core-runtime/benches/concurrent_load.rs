@@ -4,7 +4,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use gg_core::engine::InferenceParams;
+use gg_core::engine::{InferenceParams, OutputEncoding};
 use gg_core::scheduler::{Priority, PriorityQueue, QueuedRequest};
 
 fn create_request(id: u64, token_count: usize) -> QueuedRequest {
@@ -19,8 +19,18 @@ fn create_request(id: u64, token_count: usize) -> QueuedRequest {
             temperature: 0.7,
             top_p: 1.0,
             top_k: 50,
+            min_p: None,
             stream: false,
             timeout_ms: None,
+            max_cpu_ms: None,
+            output_encoding: OutputEncoding::Utf8,
+            no_cache: false,
+            logit_bias: Default::default(),
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: Default::default(),
         },
     )
 }
@@ -13,6 +13,7 @@ fn create_generation_result(token_count: usize) -> GenerationResult {
         text,
         tokens_generated: token_count as u32,
         finish_reason: FinishReason::MaxTokens,
+        raw_bytes: None,
     }
 }
 
@@ -57,6 +58,7 @@ fn bench_finish_reason_matching(c: &mut Criterion) {
         FinishReason::MaxTokens,
         FinishReason::Timeout,
         FinishReason::ContentFiltered,
+        FinishReason::Length,
     ];
 
     group.bench_function("pattern_match", |b| {
@@ -67,6 +69,7 @@ fn bench_finish_reason_matching(c: &mut Criterion) {
                     FinishReason::MaxTokens => 1,
                     FinishReason::Timeout => 2,
                     FinishReason::ContentFiltered => 3,
+                    FinishReason::Length => 4,
                 });
             }
         })
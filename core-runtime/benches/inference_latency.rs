@@ -4,7 +4,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use gg_core::engine::{ChatMessage, ChatRole, InferenceInput, InferenceParams};
+use gg_core::engine::{ChatMessage, ChatRole, InferenceInput, InferenceParams, OutputEncoding};
 
 fn create_text_input(length: usize) -> InferenceInput {
     let text = "a".repeat(length);
@@ -62,8 +62,18 @@ fn bench_params_creation(c: &mut Criterion) {
                 temperature: black_box(0.7),
                 top_p: black_box(1.0),
                 top_k: black_box(50),
+                min_p: None,
                 stream: false,
                 timeout_ms: None,
+                max_cpu_ms: None,
+                output_encoding: OutputEncoding::Utf8,
+                no_cache: false,
+                logit_bias: Default::default(),
+                repetition_penalty: 1.0,
+                no_repeat_ngram_size: 0,
+                seed: None,
+                stop_sequences: Vec::new(),
+                sampler: Default::default(),
             }
         })
     });
@@ -3,7 +3,7 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use std::fs;
 
-use gg_core::engine::InferenceParams;
+use gg_core::engine::{InferenceParams, OutputEncoding};
 use gg_core::ipc::protocol::{
     decode_message, decode_message_binary, encode_message, encode_message_binary,
     InferenceRequest, IpcMessage, RequestId,
@@ -32,9 +32,22 @@ fn fixture_to_request(fixture: &serde_json::Value, request_id: u64) -> Inference
             temperature: params["temperature"].as_f64().unwrap() as f32,
             top_p: 1.0,
             top_k: 50,
+            min_p: None,
             stream: false,
             timeout_ms: None,
+            max_cpu_ms: None,
+            output_encoding: OutputEncoding::Utf8,
+            no_cache: false,
+            logit_bias: Default::default(),
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: Default::default(),
         },
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     }
 }
 
@@ -0,0 +1,34 @@
+//! Per-step sampling benchmarks.
+//!
+//! Compares top-k/top-p sampling over a large-vocab logit vector with and
+//! without the candidate cap.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use gg_core::engine::sampling::{sample_top_k_top_p, DEFAULT_CANDIDATE_CAP};
+
+const VOCAB_SIZE: usize = 128_000;
+
+fn synthetic_logits(vocab: usize) -> Vec<f32> {
+    (0..vocab)
+        .map(|i| ((i as u64 * 2_654_435_761) % 10_007) as f32 / 1_000.0)
+        .collect()
+}
+
+fn bench_candidate_cap(c: &mut Criterion) {
+    let logits = synthetic_logits(VOCAB_SIZE);
+    let mut group = c.benchmark_group("sampling_step_128k_vocab");
+
+    for (name, cap) in [("uncapped", 0), ("cap_1000", DEFAULT_CANDIDATE_CAP)] {
+        group.bench_with_input(
+            BenchmarkId::new("top_k0_top_p0.9", name),
+            &cap,
+            |b, &cap| b.iter(|| sample_top_k_top_p(black_box(&logits), 0, 0.9, 0.8, cap, 0.42)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_candidate_cap);
+criterion_main!(benches);
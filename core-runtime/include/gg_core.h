@@ -88,6 +88,56 @@ extern "C" {
 
 #define EXIT_UNHEALTHY 1
 
+/**
+ * Build feature flag: ONNX backend (`onnx`)
+ */
+#define CORE_FEATURE_ONNX (1 << 0)
+
+/**
+ * Build feature flag: GGUF backend (`gguf`)
+ */
+#define CORE_FEATURE_GGUF (1 << 1)
+
+/**
+ * Build feature flag: CUDA GPU support (`cuda`)
+ */
+#define CORE_FEATURE_CUDA (1 << 2)
+
+/**
+ * Build feature flag: Metal GPU support (`metal`)
+ */
+#define CORE_FEATURE_METAL (1 << 3)
+
+/**
+ * Build feature flag: Python bindings (`python`)
+ */
+#define CORE_FEATURE_PYTHON (1 << 4)
+
+/**
+ * Build feature flag: C FFI (`ffi`)
+ */
+#define CORE_FEATURE_FFI (1 << 5)
+
+/**
+ * Capability flag: text generation
+ */
+#define CORE_CAPABILITY_TEXT_GENERATION (1 << 0)
+
+/**
+ * Capability flag: token streaming
+ */
+#define CORE_CAPABILITY_STREAMING (1 << 1)
+
+/**
+ * Capability flag: text classification
+ */
+#define CORE_CAPABILITY_CLASSIFICATION (1 << 2)
+
+/**
+ * Capability flag: embeddings
+ */
+#define CORE_CAPABILITY_EMBEDDING (1 << 3)
+
 /**
  * Error codes for FFI functions
  */
@@ -108,6 +158,7 @@ enum CoreErrorCode {
   CORE_ERROR_CODE_SHUTTING_DOWN = -13,
   CORE_ERROR_CODE_TIMEOUT = -14,
   CORE_ERROR_CODE_CANCELLED = -15,
+  CORE_ERROR_CODE_RESOURCE_EXHAUSTED = -16,
   CORE_ERROR_CODE_INTERNAL = -99,
 };
 typedef int32_t CoreErrorCode;
@@ -170,6 +221,20 @@ typedef struct CoreHealthReport {
   uint64_t uptime_secs;
 } CoreHealthReport;
 
+/**
+ * Additive bias for one token's logit
+ */
+typedef struct CoreLogitBias {
+  /**
+   * Token ID to bias
+   */
+  uint32_t token_id;
+  /**
+   * Added to the raw logit (-INFINITY bans the token)
+   */
+  float bias;
+} CoreLogitBias;
+
 /**
  * Inference parameters (matches InferenceParams)
  */
@@ -198,6 +263,18 @@ typedef struct CoreInferenceParams {
    * Timeout in milliseconds (0 = no timeout)
    */
   uint64_t timeout_ms;
+  /**
+   * Per-token logit biases (NULL = none)
+   */
+  const struct CoreLogitBias *logit_bias;
+  /**
+   * Number of entries in logit_bias
+   */
+  uint32_t logit_bias_count;
+  /**
+   * Sampling seed for reproducible output (0 = random per call)
+   */
+  uint64_t seed;
 } CoreInferenceParams;
 
 /**
@@ -266,12 +343,57 @@ typedef struct CoreConfig {
   uint64_t shutdown_timeout_secs;
 } CoreConfig;
 
+/**
+ * Library build information
+ */
+typedef struct CoreBuildInfo {
+  /**
+   * Version string (static, do not free)
+   */
+  const char *version;
+  /**
+   * Major version
+   */
+  uint32_t version_major;
+  /**
+   * Minor version
+   */
+  uint32_t version_minor;
+  /**
+   * Patch version
+   */
+  uint32_t version_patch;
+  /**
+   * Enabled build features (CORE_FEATURE_* bitset)
+   */
+  uint32_t features;
+  /**
+   * Supported capabilities (CORE_CAPABILITY_* bitset)
+   */
+  uint32_t capabilities;
+  /**
+   * Minimum supported IPC protocol version
+   */
+  uint32_t min_protocol_version;
+  /**
+   * Maximum supported IPC protocol version
+   */
+  uint32_t max_protocol_version;
+} CoreBuildInfo;
+
 /**
  * Streaming callback signature
  * Return false to cancel streaming
  */
 typedef bool (*CoreStreamCallback)(void *user_data, uint32_t token, bool is_final, const char *error);
 
+/**
+ * Text streaming callback signature
+ * Receives each decoded piece as a null-terminated UTF-8 string that is
+ * freed once the callback returns. Return false to cancel generation.
+ */
+typedef bool (*CoreTextStreamCallback)(const char *token_utf8, void *user_data);
+
 
 
 
@@ -298,6 +420,11 @@ void core_session_release(struct CoreSession *session);
  */
 const char *core_session_id(const struct CoreSession *session);
 
+/**
+ * Query library version, build features, and capabilities
+ */
+CoreErrorCode core_build_info(struct CoreBuildInfo *out_info);
+
 /**
  * Get the last error message (C API)
  */
@@ -421,6 +548,22 @@ CoreErrorCode core_infer_streaming(struct CoreRuntime *runtime,
                                    CoreStreamCallback callback,
                                    void *user_data);
 
+/**
+ * Stream the decoded text of a prompt's completion to `callback`
+ * (blocks until complete/cancelled)
+ *
+ * The callback runs on the calling thread. Returning false from it cancels
+ * the request the same way an IPC `CancelRequest` does, and this function
+ * returns `Cancelled` once generation has stopped.
+ */
+CoreErrorCode core_infer_stream(struct CoreRuntime *runtime,
+                                struct CoreSession *session,
+                                const char *model_id,
+                                const char *prompt,
+                                const struct CoreInferenceParams *params,
+                                CoreTextStreamCallback callback,
+                                void *user_data);
+
 /**
  * Free string allocated by core functions
  */
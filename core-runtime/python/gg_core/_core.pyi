@@ -97,13 +97,26 @@ class Session:
         model_id: int,
         tokens: List[int],
         params: Optional[InferenceParams] = None,
-    ) -> Iterator[StreamingResult]:
+    ) -> TokenIterator:
         """Run streaming inference.
 
         Yields tokens as they are generated.
         """
         ...
 
+    def infer_stream(
+        self,
+        model_id: str,
+        prompt: str,
+        params: Optional[InferenceParams] = None,
+    ) -> TextStreamIterator:
+        """Stream the decoded text of a completion.
+
+        Yields text pieces as tokens are generated. Stopping iteration
+        early cancels the request.
+        """
+        ...
+
     def __enter__(self) -> Session:
         ...
 
@@ -174,6 +187,27 @@ class StreamingResult:
         """Check if this result indicates an error."""
         ...
 
+class TokenIterator(Iterator[StreamingResult]):
+    """Iterator over the tokens of a finished generation."""
+
+    def __next__(self) -> StreamingResult:
+        ...
+
+    def __len__(self) -> int:
+        ...
+
+# Former name of TokenIterator, kept for existing callers.
+StreamingIterator = TokenIterator
+
+class TextStreamIterator(Iterator[str]):
+    """Iterator over the decoded text of a live generation.
+
+    Dropping it before it is exhausted cancels the request.
+    """
+
+    def __next__(self) -> str:
+        ...
+
 class ModelInfo:
     """Information about a loaded model."""
 
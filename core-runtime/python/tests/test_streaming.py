@@ -0,0 +1,38 @@
+"""Tests for the streaming iterators of gg_core.Session."""
+
+import pytest
+
+gg_core = pytest.importorskip("gg_core")
+_core = gg_core._core
+
+AUTH_TOKEN = "test-token"
+
+
+@pytest.fixture
+def session():
+    runtime = gg_core.Runtime(auth_token=AUTH_TOKEN)
+    with runtime.session() as session:
+        yield session
+
+
+def test_streaming_iterator_is_token_iterator():
+    assert _core.StreamingIterator is _core.TokenIterator
+
+
+def test_infer_stream_returns_text_iterator(session):
+    stream = session.infer_stream("missing-model", "Hello")
+    assert isinstance(stream, _core.TextStreamIterator)
+    assert iter(stream) is stream
+
+
+def test_infer_stream_raises_stream_errors(session):
+    with pytest.raises(gg_core.InferenceError):
+        list(session.infer_stream("missing-model", "Hello"))
+
+
+def test_dropping_text_stream_early_is_safe(session):
+    stream = session.infer_stream("missing-model", "Hello")
+    del stream
+    # The session stays usable after the request is cancelled
+    with pytest.raises(gg_core.InferenceError):
+        list(session.infer_stream("missing-model", "Hello"))
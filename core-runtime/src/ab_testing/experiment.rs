@@ -0,0 +1,242 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Two-arm experiments that split one model's traffic by session.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ab_testing::metrics::{VariantMetrics, VariantStatsSnapshot};
+use crate::ab_testing::traffic::{TrafficConfig, TrafficError, TrafficSplitter};
+use crate::ab_testing::variant::VariantLabel;
+
+/// Sends `variant_percent` of the sessions asking for `control_model` to
+/// `variant_model` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbExperiment {
+    /// Model requests name; its traffic is split.
+    pub control_model: String,
+    /// Model serving the variant arm.
+    pub variant_model: String,
+    /// Share of sessions assigned to the variant (0-100).
+    pub variant_percent: u8,
+}
+
+impl AbExperiment {
+    fn traffic_config(&self) -> TrafficConfig {
+        let mut weights = BTreeMap::new();
+        weights.insert(VariantLabel::control(), 100 - self.variant_percent);
+        weights.insert(VariantLabel::treatment(), self.variant_percent);
+        TrafficConfig {
+            weights,
+            sticky_sessions: true,
+        }
+    }
+}
+
+/// Per-arm outcome of an experiment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmResults {
+    /// `control` or `treatment`.
+    pub arm: VariantLabel,
+    pub model_id: String,
+    pub stats: VariantStatsSnapshot,
+}
+
+/// An experiment and the outcomes recorded for each arm so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentResults {
+    pub experiment: AbExperiment,
+    /// Control first, then treatment.
+    pub arms: Vec<ArmResults>,
+}
+
+/// A running experiment: assigns requests and collects per-arm metrics.
+pub struct ActiveExperiment {
+    experiment: AbExperiment,
+    splitter: TrafficSplitter,
+    metrics: VariantMetrics,
+}
+
+impl ActiveExperiment {
+    /// Start `experiment` with empty results.
+    pub fn new(experiment: AbExperiment) -> Result<Self, TrafficError> {
+        if experiment.variant_percent > 100 {
+            return Err(TrafficError::InvalidWeights(experiment.variant_percent.into()));
+        }
+        let splitter = TrafficSplitter::new(experiment.traffic_config())?;
+        let metrics = VariantMetrics::new();
+        for arm in [VariantLabel::control(), VariantLabel::treatment()] {
+            metrics.get_or_create(&arm);
+        }
+        Ok(Self {
+            experiment,
+            splitter,
+            metrics,
+        })
+    }
+
+    pub fn experiment(&self) -> &AbExperiment {
+        &self.experiment
+    }
+
+    /// Assign a request for `model_id` to an arm and count it there.
+    ///
+    /// The same client always lands on the same arm; requests without a
+    /// client ID are assigned at random. Returns `None` for models other
+    /// than the control model.
+    pub fn assign(
+        self: &Arc<Self>,
+        model_id: &str,
+        client_id: Option<&str>,
+    ) -> Option<ExperimentAssignment> {
+        if model_id != self.experiment.control_model {
+            return None;
+        }
+        let arm = self.splitter.select(client_id).clone();
+        self.metrics.get_or_create(&arm).record_request();
+        Some(ExperimentAssignment {
+            model_id: self.model_for(&arm).to_string(),
+            arm,
+            experiment: Arc::clone(self),
+        })
+    }
+
+    /// Outcomes recorded so far, control arm first.
+    pub fn results(&self) -> ExperimentResults {
+        let mut snapshots = self.metrics.all_snapshots();
+        let arms = [VariantLabel::control(), VariantLabel::treatment()]
+            .into_iter()
+            .filter_map(|arm| {
+                let stats = snapshots.remove(&arm)?;
+                Some(ArmResults {
+                    model_id: self.model_for(&arm).to_string(),
+                    arm,
+                    stats,
+                })
+            })
+            .collect();
+        ExperimentResults {
+            experiment: self.experiment.clone(),
+            arms,
+        }
+    }
+
+    fn model_for(&self, arm: &VariantLabel) -> &str {
+        if *arm == VariantLabel::treatment() {
+            &self.experiment.variant_model
+        } else {
+            &self.experiment.control_model
+        }
+    }
+}
+
+/// Where one request was routed; records its outcome against that arm.
+pub struct ExperimentAssignment {
+    pub arm: VariantLabel,
+    /// Model that serves the request.
+    pub model_id: String,
+    experiment: Arc<ActiveExperiment>,
+}
+
+impl ExperimentAssignment {
+    pub fn record_success(&self, latency: Duration, tokens: u64) {
+        self.experiment
+            .metrics
+            .get_or_create(&self.arm)
+            .record_success(latency, tokens);
+    }
+
+    pub fn record_failure(&self) {
+        self.experiment.metrics.get_or_create(&self.arm).record_failure();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment(variant_percent: u8) -> Arc<ActiveExperiment> {
+        Arc::new(
+            ActiveExperiment::new(AbExperiment {
+                control_model: "chat".into(),
+                variant_model: "chat-v2".into(),
+                variant_percent,
+            })
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_even_split_within_tolerance() {
+        let experiment = experiment(50);
+        let sessions = 10_000;
+        let variant = (0..sessions)
+            .filter(|i| {
+                let session = format!("session-{i}");
+                experiment.assign("chat", Some(&session)).unwrap().model_id == "chat-v2"
+            })
+            .count();
+
+        let share = variant as f64 / sessions as f64;
+        assert!((0.45..=0.55).contains(&share), "variant share {share}");
+
+        let results = experiment.results();
+        assert_eq!(results.arms[0].stats.requests + results.arms[1].stats.requests, 10_000);
+        assert_eq!(results.arms[1].stats.requests, variant as u64);
+    }
+
+    #[test]
+    fn test_session_always_maps_to_same_arm() {
+        let experiment = experiment(30);
+        for i in 0..200 {
+            let session = format!("session-{i}");
+            let first = experiment.assign("chat", Some(&session)).unwrap().arm;
+            for _ in 0..5 {
+                assert_eq!(experiment.assign("chat", Some(&session)).unwrap().arm, first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_control_model_is_split() {
+        let experiment = experiment(100);
+        assert!(experiment.assign("embed", Some("session-1")).is_none());
+
+        let assignment = experiment.assign("chat", Some("session-1")).unwrap();
+        assert_eq!(assignment.arm, VariantLabel::treatment());
+        assert_eq!(assignment.model_id, "chat-v2");
+    }
+
+    #[test]
+    fn test_outcomes_recorded_per_arm() {
+        let experiment = experiment(0);
+        let assignment = experiment.assign("chat", Some("session-1")).unwrap();
+        assignment.record_success(Duration::from_millis(40), 12);
+        experiment.assign("chat", Some("session-2")).unwrap().record_failure();
+
+        let results = experiment.results();
+        let control = &results.arms[0];
+        assert_eq!(control.arm, VariantLabel::control());
+        assert_eq!(control.model_id, "chat");
+        assert_eq!(
+            (control.stats.requests, control.stats.successes, control.stats.failures),
+            (2, 1, 1)
+        );
+        assert_eq!(control.stats.avg_latency_ms, 40.0);
+        assert_eq!(results.arms[1].stats.requests, 0);
+    }
+
+    #[test]
+    fn test_percent_over_100_rejected() {
+        let result = ActiveExperiment::new(AbExperiment {
+            control_model: "chat".into(),
+            variant_model: "chat-v2".into(),
+            variant_percent: 101,
+        });
+        assert!(matches!(result, Err(TrafficError::InvalidWeights(101))));
+    }
+}
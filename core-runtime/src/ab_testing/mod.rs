@@ -6,10 +6,14 @@
 //! Provides traffic splitting, variant management, and per-variant metrics
 //! for comparing model performance in production.
 
+pub mod experiment;
 pub mod metrics;
 pub mod traffic;
 pub mod variant;
 
+pub use experiment::{
+    AbExperiment, ActiveExperiment, ArmResults, ExperimentAssignment, ExperimentResults,
+};
 pub use metrics::{VariantMetrics, VariantStats, VariantStatsSnapshot};
 pub use traffic::{TrafficConfig, TrafficSplitter};
 pub use variant::{Variant, VariantLabel};
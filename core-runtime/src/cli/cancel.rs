@@ -0,0 +1,29 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `cancel-all` command.
+//!
+//! Cancels every request pending on the running server, for shutdown or
+//! emergencies. Requires the server's auth token.
+
+use super::ipc_client::{CliError, CliIpcClient};
+
+/// Cancel all pending requests on the running server.
+///
+/// Exit codes: 0 = cancelled (possibly none), 1 = rejected, 3 = connection error.
+pub async fn run_cancel_all(socket_path: &str, auth_token: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.cancel_all(auth_token).await {
+        Ok(cancelled) => {
+            println!("Cancelled {} pending request(s)", cancelled);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error cancelling requests: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
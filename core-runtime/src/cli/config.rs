@@ -0,0 +1,42 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `config show` command.
+//!
+//! Prints the effective configuration as JSON, either as resolved locally
+//! from the environment or as reported by the running server (`--remote`).
+//! The auth token is always redacted.
+
+use super::ipc_client::{CliError, CliIpcClient};
+use crate::ipc::EffectiveConfig;
+
+/// Print a configuration as pretty JSON. Returns exit code.
+pub fn print_config(config: &EffectiveConfig) -> i32 {
+    match serde_json::to_string_pretty(config) {
+        Ok(json) => {
+            println!("{}", json);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error serializing configuration: {}", e);
+            1
+        }
+    }
+}
+
+/// Fetch and print the running server's effective configuration.
+///
+/// Exit codes: 0 = success, 1 = request failed, 3 = connection error.
+pub async fn run_config_show_remote(socket_path: &str, auth_token: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.get_config(auth_token).await {
+        Ok(config) => print_config(&config),
+        Err(e) => {
+            eprintln!("Error fetching configuration: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
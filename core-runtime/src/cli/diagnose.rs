@@ -0,0 +1,101 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models diagnose` command.
+//!
+//! Asks the running server why a model is or is not servable and prints one
+//! line per check, with a remediation hint under each failure.
+
+use super::ipc_client::{CliError, CliIpcClient};
+use crate::engine::{CheckStatus, ModelDiagnostic};
+
+/// Render a diagnostic report as human-readable text.
+pub fn format_report(report: &ModelDiagnostic) -> String {
+    let mut out = format!("Diagnosing model '{}':\n", report.model_id);
+    for check in &report.checks {
+        let status = match check.status {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        };
+        out.push_str(&format!(
+            "  [{}] {}: {}\n",
+            status, check.name, check.detail
+        ));
+        if let Some(ref hint) = check.remediation {
+            out.push_str(&format!("         fix: {}\n", hint));
+        }
+    }
+    let verdict = if report.is_servable() {
+        "servable"
+    } else {
+        "NOT servable"
+    };
+    out.push_str(&format!("Result: {}\n", verdict));
+    out
+}
+
+/// Diagnose a model on the running server.
+///
+/// Exit codes: 0 = servable, 1 = a check failed, 3 = connection error.
+pub async fn run_models_diagnose(socket_path: &str, auth_token: &str, model_id: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.diagnose_model(auth_token, model_id).await {
+        Ok(report) => {
+            print!("{}", format_report(&report));
+            if report.is_servable() {
+                0
+            } else {
+                1
+            }
+        }
+        Err(e) => {
+            eprintln!("Error diagnosing model: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::DiagnosticCheck;
+
+    #[test]
+    fn test_format_report_shows_remediation_for_failures() {
+        let report = ModelDiagnostic {
+            model_id: "phi".into(),
+            checks: vec![
+                DiagnosticCheck {
+                    name: "tokenizer".into(),
+                    status: CheckStatus::Fail,
+                    detail: "no tokenizer attached to the model".into(),
+                    remediation: Some("ship the tokenizer".into()),
+                },
+                DiagnosticCheck {
+                    name: "context".into(),
+                    status: CheckStatus::Skip,
+                    detail: "unknown".into(),
+                    remediation: None,
+                },
+            ],
+        };
+
+        let text = format_report(&report);
+        assert!(text.contains("[FAIL] tokenizer: no tokenizer attached"));
+        assert!(text.contains("fix: ship the tokenizer"));
+        assert!(text.contains("[SKIP] context"));
+        assert!(text.ends_with("Result: NOT servable\n"));
+    }
+
+    #[tokio::test]
+    async fn test_run_models_diagnose_connection_failure() {
+        assert_eq!(
+            run_models_diagnose("/nonexistent/socket.sock", "token", "phi").await,
+            3
+        );
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models drain` command.
+//!
+//! Stops the running server routing new requests to a model and waits for
+//! its in-flight requests, ahead of an unload. Requires the server's auth token.
+
+use std::time::Duration;
+
+use super::ipc_client::{CliError, CliIpcClient};
+use crate::ipc::DrainResponse;
+
+/// Drain timeout when `--timeout-ms` is not given.
+pub const DEFAULT_DRAIN_TIMEOUT_MS: u64 = 30_000;
+
+/// Extra time the client waits past the drain timeout for the response.
+const RESPONSE_MARGIN: Duration = Duration::from_secs(5);
+
+/// Render a drain outcome as one human-readable line.
+pub fn format_drain(response: &DrainResponse) -> String {
+    if response.drained {
+        format!("Model '{}' drained", response.model_id)
+    } else {
+        format!(
+            "Model '{}' not drained: {} request(s) still in flight",
+            response.model_id, response.in_flight_remaining
+        )
+    }
+}
+
+/// Drain a model on the running server.
+///
+/// Exit codes: 0 = drained, 1 = requests remain or rejected, 3 = connection error.
+pub async fn run_models_drain(
+    socket_path: &str,
+    auth_token: &str,
+    model_id: &str,
+    timeout_ms: u64,
+) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string())
+        .with_timeout(Duration::from_millis(timeout_ms) + RESPONSE_MARGIN);
+    match client.drain_model(auth_token, model_id, timeout_ms).await {
+        Ok(response) => {
+            println!("{}", format_drain(&response));
+            if response.drained {
+                0
+            } else {
+                1
+            }
+        }
+        Err(e) => {
+            eprintln!("Error draining model: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_drain_reports_remaining_requests() {
+        let mut response = DrainResponse {
+            model_id: "phi".into(),
+            drained: false,
+            in_flight_remaining: 2,
+        };
+        assert_eq!(
+            format_drain(&response),
+            "Model 'phi' not drained: 2 request(s) still in flight"
+        );
+        response.drained = true;
+        response.in_flight_remaining = 0;
+        assert_eq!(format_drain(&response), "Model 'phi' drained");
+    }
+}
@@ -33,13 +33,16 @@ pub async fn run_readiness(socket_path: &str) -> i32 {
 async fn run_check(socket_path: &str, check_type: HealthCheckType, name: &str) -> i32 {
     let client = CliIpcClient::new(socket_path.to_string());
 
-    match client.check_health(check_type).await {
-        Ok(true) => {
+    match client.get_health(check_type).await {
+        Ok(response) if response.ok => {
             eprintln!("{} check: OK", name);
             EXIT_HEALTHY
         }
-        Ok(false) => {
-            eprintln!("{} check: FAILED", name);
+        Ok(response) => {
+            match response.reason {
+                Some(reason) => eprintln!("{} check: FAILED ({})", name, reason),
+                None => eprintln!("{} check: FAILED", name),
+            }
             EXIT_UNHEALTHY
         }
         Err(e) => {
@@ -59,6 +62,9 @@ pub async fn run_health_verbose(socket_path: &str) -> i32 {
                 eprintln!("Health Report:");
                 eprintln!("  State: {:?}", health.state);
                 eprintln!("  Ready: {}", health.ready);
+                if let Some(reason) = report.reason {
+                    eprintln!("  Not ready: {}", reason);
+                }
                 eprintln!("  Accepting: {}", health.accepting_requests);
                 eprintln!("  Models: {}", health.models_loaded);
                 eprintln!("  Memory: {} bytes", health.memory_used_bytes);
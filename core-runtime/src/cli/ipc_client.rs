@@ -12,12 +12,14 @@ use thiserror::Error;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::time::timeout;
 
-use crate::engine::InferenceParams;
+use crate::engine::{ChatMessage, InferenceParams, ModelDiagnostic};
 use crate::ipc::protocol::{
-    decode_message, encode_message, HealthCheckResponse, HealthCheckType, InferenceRequest,
-    IpcMessage, ModelsListResponse, RequestId,
+    decode_message, encode_message, ActiveRequestInfo, ChatRequest, DiagnoseModelRequest, DrainResponse, EffectiveConfig, HealthCheckResponse,
+    HealthCheckType, InferenceRequest, InferenceResponse, IpcMessage, LoadModelResponse, ModelInfo,
+    ModelStats, ModelsListResponse, RequestId, UnloadModelResponse,
 };
-use crate::telemetry::MetricsSnapshot;
+use crate::scheduler::{RequestIdAllocator, RequestOrigin};
+use crate::telemetry::{ExportableSpan, MetricsSnapshot};
 
 /// CLI client errors.
 #[derive(Error, Debug)]
@@ -38,6 +40,11 @@ pub enum CliError {
     Unhealthy,
 }
 
+/// Allocate a wire request ID for a CLI-originated request.
+fn next_request_id() -> RequestId {
+    RequestId(RequestIdAllocator::global().allocate(RequestOrigin::Cli))
+}
+
 /// IPC client for CLI health probe commands.
 pub struct CliIpcClient {
     socket_path: String,
@@ -65,6 +72,14 @@ impl CliIpcClient {
         Ok(response.ok)
     }
 
+    /// Perform a health check via IPC, keeping the not-ready reason.
+    pub async fn get_health(
+        &self,
+        check_type: HealthCheckType,
+    ) -> Result<HealthCheckResponse, CliError> {
+        self.send_health_request(check_type).await
+    }
+
     /// Get full health report via IPC.
     pub async fn get_health_report(&self) -> Result<HealthCheckResponse, CliError> {
         self.send_health_request(HealthCheckType::Full).await
@@ -88,6 +103,22 @@ impl CliIpcClient {
         }
     }
 
+    /// Get metrics in Prometheus text exposition format via IPC.
+    pub async fn get_prometheus_metrics(&self) -> Result<String, CliError> {
+        let request_bytes = encode_message(&IpcMessage::PrometheusMetricsRequest)
+            .map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::PrometheusMetricsResponse { text } => Ok(text),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
     /// Get loaded models list via IPC.
     pub async fn get_models(&self) -> Result<ModelsListResponse, CliError> {
         let message = IpcMessage::ModelsRequest;
@@ -106,6 +137,243 @@ impl CliIpcClient {
         }
     }
 
+    /// Get one loaded model's status via IPC.
+    pub async fn model_info(&self, model_id: &str) -> Result<ModelInfo, CliError> {
+        let message = IpcMessage::ModelInfoRequest {
+            model_id: model_id.to_string(),
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::ModelInfoResponse(info) => Ok(info),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Get one model's live request and memory stats via IPC.
+    pub async fn model_stats(&self, model_id: &str) -> Result<ModelStats, CliError> {
+        let message = IpcMessage::ModelStatsRequest {
+            model_id: model_id.to_string(),
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::ModelStatsResponse(stats) => Ok(stats),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Register `models/<model_id>.gguf` on the server.
+    ///
+    /// Requires authentication; handshakes with `auth_token` first.
+    pub async fn load_model(
+        &self,
+        auth_token: &str,
+        model_id: &str,
+    ) -> Result<LoadModelResponse, CliError> {
+        let message = IpcMessage::LoadModelRequest {
+            model_id: model_id.to_string(),
+            path: None,
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::LoadModelResponse(response) => Ok(response),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Remove a model from the server.
+    ///
+    /// Requires authentication; handshakes with `auth_token` first.
+    pub async fn unload_model(
+        &self,
+        auth_token: &str,
+        model_id: &str,
+    ) -> Result<UnloadModelResponse, CliError> {
+        let message = IpcMessage::UnloadModelRequest {
+            model_id: model_id.to_string(),
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::UnloadModelResponse(response) => Ok(response),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Run servability diagnostics for one model via IPC.
+    pub async fn diagnose_model(
+        &self,
+        auth_token: &str,
+        model_id: &str,
+    ) -> Result<ModelDiagnostic, CliError> {
+        let message = IpcMessage::DiagnoseModelRequest(DiagnoseModelRequest {
+            model_id: model_id.to_string(),
+        });
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::DiagnoseModelResponse(report) => Ok(report),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Get the buffered spans recorded for one request via IPC.
+    pub async fn get_trace(
+        &self,
+        request_id: RequestId,
+        max_count: usize,
+    ) -> Result<Vec<ExportableSpan>, CliError> {
+        let message = IpcMessage::SpansRequest {
+            max_count,
+            request_id: Some(request_id),
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::SpansResponse { spans } => Ok(spans),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Get the server's effective configuration (secrets redacted).
+    ///
+    /// Requires authentication; handshakes with `auth_token` first.
+    pub async fn get_config(&self, auth_token: &str) -> Result<EffectiveConfig, CliError> {
+        let request_bytes = encode_message(&IpcMessage::ConfigRequest)
+            .map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::ConfigResponse(config) => Ok(config),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Cancel every pending request on the server. Returns how many were cancelled.
+    ///
+    /// Requires authentication; handshakes with `auth_token` first.
+    pub async fn cancel_all(&self, auth_token: &str) -> Result<usize, CliError> {
+        let request_bytes = encode_message(&IpcMessage::CancelAllRequest)
+            .map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::CancelAllResponse { cancelled } => Ok(cancelled),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Drain a model: stop routing new requests to it and wait up to
+    /// `timeout_ms` for in-flight ones.
+    ///
+    /// Requires authentication; handshakes with `auth_token` first.
+    pub async fn drain_model(
+        &self,
+        auth_token: &str,
+        model_id: &str,
+        timeout_ms: u64,
+    ) -> Result<DrainResponse, CliError> {
+        let message = IpcMessage::DrainModel {
+            model_id: model_id.to_string(),
+            timeout_ms,
+        };
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::DrainResponse(response) => Ok(response),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Requests executing on the server right now. Requires the auth token.
+    pub async fn active_requests(
+        &self,
+        auth_token: &str,
+    ) -> Result<Vec<ActiveRequestInfo>, CliError> {
+        let request_bytes = encode_message(&IpcMessage::ActiveRequestsRequest)
+            .map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self
+            .send_receive_authenticated(auth_token, &request_bytes)
+            .await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::ActiveRequestsResponse { requests } => Ok(requests),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
     /// Send inference request and return response text.
     pub async fn send_inference(
         &self,
@@ -113,11 +381,26 @@ impl CliIpcClient {
         prompt: &str,
         params: &InferenceParams,
     ) -> Result<String, CliError> {
+        self.send_inference_response(model_id, prompt, params)
+            .await
+            .map(|resp| resp.output)
+    }
+
+    /// Send inference request and return the full response, including timing.
+    pub async fn send_inference_response(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+    ) -> Result<InferenceResponse, CliError> {
         let request = InferenceRequest {
-            request_id: RequestId(1),
+            request_id: next_request_id(),
             model_id: model_id.to_string(),
             prompt: prompt.to_string(),
             parameters: params.clone(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
         let message = IpcMessage::InferenceRequest(request);
         let request_bytes =
@@ -128,7 +411,38 @@ impl CliIpcClient {
             decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
 
         match response {
-            IpcMessage::InferenceResponse(resp) => Ok(resp.output),
+            IpcMessage::InferenceResponse(resp) => Ok(resp),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
+    /// Send a chat request, rendered server-side with the model's template.
+    pub async fn send_chat(
+        &self,
+        model_id: &str,
+        messages: &[ChatMessage],
+        params: &InferenceParams,
+    ) -> Result<InferenceResponse, CliError> {
+        let request = ChatRequest {
+            request_id: next_request_id(),
+            model_id: model_id.to_string(),
+            messages: messages.to_vec(),
+            parameters: params.clone(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
+        };
+        let message = IpcMessage::ChatRequest(request);
+        let request_bytes =
+            encode_message(&message).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        let response_bytes = self.send_receive(&request_bytes).await?;
+        let response =
+            decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))?;
+
+        match response {
+            IpcMessage::InferenceResponse(resp) => Ok(resp),
             IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
             _ => Err(CliError::Protocol("Unexpected response type".to_string())),
         }
@@ -145,10 +459,13 @@ impl CliIpcClient {
         params.stream = true;
 
         let request = InferenceRequest {
-            request_id: RequestId(1),
+            request_id: next_request_id(),
             model_id: model_id.to_string(),
             prompt: prompt.to_string(),
             parameters: params,
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
         let message = IpcMessage::InferenceRequest(request);
         let request_bytes =
@@ -222,6 +539,12 @@ impl CliIpcClient {
                         break;
                     }
                 }
+                IpcMessage::StreamBatch(batch) => {
+                    if batch.is_final {
+                        println!();
+                        break;
+                    }
+                }
                 IpcMessage::Error { message, .. } => {
                     return Err(CliError::Protocol(message));
                 }
@@ -280,6 +603,64 @@ impl CliIpcClient {
         self.exchange_data(&mut pipe, request).await
     }
 
+    #[cfg(unix)]
+    async fn send_receive_authenticated(
+        &self,
+        auth_token: &str,
+        request: &[u8],
+    ) -> Result<Vec<u8>, CliError> {
+        use tokio::net::UnixStream;
+
+        let connect_future = UnixStream::connect(&self.socket_path);
+        let mut stream = timeout(self.timeout_duration, connect_future)
+            .await
+            .map_err(|_| CliError::Timeout)?
+            .map_err(|e| CliError::ConnectionFailed(e.to_string()))?;
+
+        self.handshake(&mut stream, auth_token).await?;
+        self.exchange_data(&mut stream, request).await
+    }
+
+    #[cfg(windows)]
+    async fn send_receive_authenticated(
+        &self,
+        auth_token: &str,
+        request: &[u8],
+    ) -> Result<Vec<u8>, CliError> {
+        use tokio::net::windows::named_pipe::ClientOptions;
+
+        let connect_future = ClientOptions::new().open(&self.socket_path);
+        let mut pipe = timeout(self.timeout_duration, async { connect_future })
+            .await
+            .map_err(|_| CliError::Timeout)?
+            .map_err(|e| CliError::ConnectionFailed(e.to_string()))?;
+
+        self.handshake(&mut pipe, auth_token).await?;
+        self.exchange_data(&mut pipe, request).await
+    }
+
+    /// Authenticate the connection; the server binds the session to it.
+    async fn handshake<S>(&self, stream: &mut S, auth_token: &str) -> Result<(), CliError>
+    where
+        S: AsyncReadExt + AsyncWriteExt + Unpin,
+    {
+        let handshake = IpcMessage::Handshake {
+            token: auth_token.to_string(),
+            protocol_version: None,
+            compression: None,
+            strict_version: false,
+        };
+        let request_bytes =
+            encode_message(&handshake).map_err(|e| CliError::Protocol(e.to_string()))?;
+        let response_bytes = self.exchange_data(stream, &request_bytes).await?;
+
+        match decode_message(&response_bytes).map_err(|e| CliError::Protocol(e.to_string()))? {
+            IpcMessage::HandshakeAck { .. } => Ok(()),
+            IpcMessage::Error { message, .. } => Err(CliError::Protocol(message)),
+            _ => Err(CliError::Protocol("Unexpected response type".to_string())),
+        }
+    }
+
     async fn exchange_data<S>(&self, stream: &mut S, request: &[u8]) -> Result<Vec<u8>, CliError>
     where
         S: AsyncReadExt + AsyncWriteExt + Unpin,
@@ -378,6 +759,13 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_get_config_connection_failure() {
+        let client = CliIpcClient::new("/nonexistent/socket".to_string());
+        let result = client.get_config("token").await;
+        assert!(matches!(result, Err(CliError::ConnectionFailed(_))));
+    }
+
     #[tokio::test]
     async fn test_get_health_report_connection_failure() {
         let client = CliIpcClient::new("/nonexistent/socket".to_string());
@@ -0,0 +1,83 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `metrics` command.
+//!
+//! Dumps the running server's metrics to stdout, either as the Prometheus
+//! text exposition (for piping into a textfile collector) or as the JSON
+//! `MetricsSnapshot` (for scripts). Neither request needs the auth token.
+
+use super::ipc_client::{CliError, CliIpcClient};
+
+/// Output format for the `metrics` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetricsFormat {
+    /// Prometheus text exposition format, printed verbatim.
+    Prometheus,
+    /// Pretty-printed JSON `MetricsSnapshot`.
+    #[default]
+    Json,
+}
+
+impl MetricsFormat {
+    /// Parse a `--prometheus` / `--json` flag.
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "--prometheus" => Some(Self::Prometheus),
+            "--json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Fetch the server's metrics rendered in `format`, exactly as printed.
+pub async fn fetch_metrics(socket_path: &str, format: MetricsFormat) -> Result<String, CliError> {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match format {
+        MetricsFormat::Prometheus => client.get_prometheus_metrics().await,
+        MetricsFormat::Json => {
+            let snapshot = client.get_metrics().await?;
+            let json = serde_json::to_string_pretty(&snapshot)
+                .map_err(|e| CliError::Protocol(e.to_string()))?;
+            Ok(json + "\n")
+        }
+    }
+}
+
+/// Print the server's metrics to stdout.
+///
+/// Exit codes: 0 = printed, 1 = request failed, 3 = connection error.
+pub async fn run_metrics(socket_path: &str, format: MetricsFormat) -> i32 {
+    match fetch_metrics(socket_path, format).await {
+        Ok(body) => {
+            print!("{}", body);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error fetching metrics: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_flag() {
+        assert_eq!(
+            MetricsFormat::from_flag("--prometheus"),
+            Some(MetricsFormat::Prometheus)
+        );
+        assert_eq!(
+            MetricsFormat::from_flag("--json"),
+            Some(MetricsFormat::Json)
+        );
+        assert_eq!(MetricsFormat::from_flag("--yaml"), None);
+        assert_eq!(MetricsFormat::default(), MetricsFormat::Json);
+    }
+}
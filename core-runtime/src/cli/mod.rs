@@ -13,15 +13,43 @@
 //! GG-CORE live     # Liveness probe, exits 0 if alive
 //! GG-CORE ready    # Readiness probe, exits 0 if ready
 //! GG-CORE status   # Show system status and statistics
+//! GG-CORE status --active         # List requests executing right now
+//! GG-CORE metrics [--prometheus|--json]  # Dump server metrics
+//! GG-CORE config show [--remote]  # Show effective configuration
+//! GG-CORE models load <name>      # Load models/<name>.gguf
+//! GG-CORE models unload <name>    # Unload a model
+//! GG-CORE models info <name>      # Show one model's status
+//! GG-CORE models stats <name> [--json]  # Live in-flight, request and memory stats
+//! GG-CORE models diagnose <name>  # Explain why a model is not servable
+//! GG-CORE models drain <name>     # Stop routing to a model, await in-flight
+//! GG-CORE trace <request_id>      # Show spans recorded for one request
+//! GG-CORE cancel-all              # Cancel every pending request
+//! GG-CORE infer --validate-only --model m --prompt p  # Scan prompt only
 //! ```
 
+pub mod cancel;
+pub mod config;
+pub mod diagnose;
+pub mod drain;
 pub mod health;
 pub mod ipc_client;
+pub mod metrics;
+pub mod models_cmd;
 pub mod status;
+pub mod trace;
+pub mod validate;
 
+pub use cancel::run_cancel_all;
+pub use config::{print_config, run_config_show_remote};
+pub use diagnose::run_models_diagnose;
+pub use drain::{run_models_drain, DEFAULT_DRAIN_TIMEOUT_MS};
 pub use health::{run_health, run_liveness, run_readiness};
 pub use ipc_client::{CliError, CliIpcClient};
-pub use status::{run_status, SystemStatus};
+pub use metrics::{fetch_metrics, run_metrics, MetricsFormat};
+pub use models_cmd::{run_info, run_load, run_stats, run_unload};
+pub use status::{run_active_requests, run_scale_hint, run_status, SystemStatus};
+pub use trace::run_trace;
+pub use validate::run_validate_only;
 
 /// Default socket path for IPC communication.
 #[cfg(unix)]
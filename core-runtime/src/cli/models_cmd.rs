@@ -0,0 +1,173 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `models load`, `models unload`, `models info` and `models stats` commands.
+//!
+//! Load and unload change what every caller can use, so they require the
+//! server's auth token; info and stats are read-only like `models list`.
+
+use super::ipc_client::{CliError, CliIpcClient};
+use crate::ipc::{ModelInfo, ModelStats};
+
+/// Render one model's status as human-readable text.
+pub fn format_info(info: &ModelInfo) -> String {
+    format!(
+        "Model '{}' (handle {})\n  format:    {}\n  size:      {} bytes\n  memory:    {} bytes\n  state:     {}\n  requests:  {} (avg {:.1} ms)\n  loaded at: {}\n",
+        info.name,
+        info.handle_id,
+        info.format,
+        info.size_bytes,
+        info.memory_bytes,
+        info.state,
+        info.request_count,
+        info.avg_latency_ms,
+        info.loaded_at
+    )
+}
+
+/// Render one model's live stats as human-readable text.
+pub fn format_stats(stats: &ModelStats) -> String {
+    format!(
+        "Model '{}'\n  in flight: {}\n  requests:  {} (avg {:.1} ms)\n  memory:    {} bytes\n  circuit:   {}\n",
+        stats.model_id,
+        stats.in_flight,
+        stats.total_requests,
+        stats.avg_latency_ms,
+        stats.memory_bytes,
+        stats.circuit_state.as_str()
+    )
+}
+
+fn exit_code(e: &CliError) -> i32 {
+    match e {
+        CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+        _ => 1,
+    }
+}
+
+/// Load `models/<name>.gguf` on the running server.
+///
+/// Exit codes: 0 = loaded, 1 = rejected, 3 = connection error.
+pub async fn run_load(socket_path: &str, auth_token: &str, name: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.load_model(auth_token, name).await {
+        Ok(response) => {
+            println!(
+                "Model '{}' loaded (handle {}, {} bytes)",
+                response.model_id, response.handle_id, response.size_bytes
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Error loading model '{}': {}", name, e);
+            exit_code(&e)
+        }
+    }
+}
+
+/// Unload a model from the running server.
+///
+/// Exit codes: 0 = unloaded, 1 = not loaded or rejected, 3 = connection error.
+pub async fn run_unload(socket_path: &str, auth_token: &str, name: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.unload_model(auth_token, name).await {
+        Ok(response) => {
+            println!(
+                "Model '{}' unloaded ({} bytes freed)",
+                response.model_id, response.freed_bytes
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Error unloading model '{}': {}", name, e);
+            exit_code(&e)
+        }
+    }
+}
+
+/// Show one model's status on the running server.
+///
+/// Exit codes: 0 = found, 1 = not loaded, 3 = connection error.
+pub async fn run_info(socket_path: &str, name: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.model_info(name).await {
+        Ok(info) => {
+            print!("{}", format_info(&info));
+            0
+        }
+        Err(e) => {
+            eprintln!("Error getting model info for '{}': {}", name, e);
+            exit_code(&e)
+        }
+    }
+}
+
+/// Show one model's live stats on the running server, as text or JSON.
+///
+/// Exit codes: 0 = found, 1 = not loaded, 3 = connection error.
+pub async fn run_stats(socket_path: &str, name: &str, json: bool) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.model_stats(name).await {
+        Ok(stats) if json => match serde_json::to_string_pretty(&stats) {
+            Ok(text) => {
+                println!("{}", text);
+                0
+            }
+            Err(e) => {
+                eprintln!("Error encoding stats for '{}': {}", name, e);
+                1
+            }
+        },
+        Ok(stats) => {
+            print!("{}", format_stats(&stats));
+            0
+        }
+        Err(e) => {
+            eprintln!("Error getting model stats for '{}': {}", name, e);
+            exit_code(&e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CircuitState;
+
+    #[test]
+    fn test_format_info_lists_fields() {
+        let info = ModelInfo {
+            handle_id: 7,
+            name: "phi".into(),
+            format: "gguf".into(),
+            size_bytes: 1024,
+            memory_bytes: 0,
+            state: "ready".into(),
+            request_count: 2,
+            avg_latency_ms: 12.5,
+            loaded_at: "2026-01-01T00:00:00Z".into(),
+        };
+        let text = format_info(&info);
+        assert!(text.starts_with("Model 'phi' (handle 7)\n"));
+        assert!(text.contains("size:      1024 bytes"));
+        assert!(text.contains("requests:  2 (avg 12.5 ms)"));
+    }
+
+    #[test]
+    fn test_format_stats_lists_fields() {
+        let stats = ModelStats {
+            model_id: "phi".into(),
+            in_flight: 1,
+            total_requests: 4,
+            avg_latency_ms: 20.25,
+            memory_bytes: 2048,
+            circuit_state: CircuitState::Open,
+        };
+        let text = format_stats(&stats);
+        assert!(text.starts_with("Model 'phi'\n"));
+        assert!(text.contains("in flight: 1"));
+        assert!(text.contains("requests:  4 (avg 20.2 ms)"));
+        assert!(text.contains("memory:    2048 bytes"));
+        assert!(text.contains("circuit:   open"));
+    }
+}
@@ -13,6 +13,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::ipc_client::{CliError, CliIpcClient};
+use crate::engine::Accelerations;
+use crate::ipc::protocol::{ActiveRequestInfo, RequestPhase};
 
 /// System status response from the runtime.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +35,15 @@ pub struct SystemStatus {
     pub scheduler: SchedulerStatus,
     /// GPU information (if available)
     pub gpus: Option<Vec<GpuStatus>>,
+    /// CPU accelerations in use (if reported)
+    #[serde(default)]
+    pub accelerations: Option<Accelerations>,
+    /// CPU instruction set extensions detected by the server
+    #[serde(default)]
+    pub cpu_features: Vec<String>,
+    /// Whether the tokenizer runs its vectorized path (if reported)
+    #[serde(default)]
+    pub simd_tokenizer: Option<bool>,
     /// Recent events (last 10)
     pub recent_events: Vec<Event>,
 }
@@ -196,6 +207,75 @@ pub async fn run_status(socket_path: &str, json_output: bool) -> i32 {
     }
 }
 
+/// Run `status --scale-hint`: print only the load factor for autoscalers.
+pub async fn run_scale_hint(socket_path: &str) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.get_metrics().await {
+        Ok(metrics) => {
+            let load_factor = metrics.gauges.get("core_load_factor").copied().unwrap_or(0.0);
+            println!("{:.3}", load_factor);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error fetching metrics: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+/// Run `status --active`: list requests executing on the server.
+///
+/// Exit codes: 0 = listed (possibly none), 1 = rejected, 3 = connection error.
+pub async fn run_active_requests(socket_path: &str, auth_token: &str, json_output: bool) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client.active_requests(auth_token).await {
+        Ok(requests) => {
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&requests).unwrap());
+            } else {
+                print_active_requests(&requests);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Error fetching active requests: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+/// Print active requests as a table, oldest first.
+fn print_active_requests(requests: &[ActiveRequestInfo]) {
+    if requests.is_empty() {
+        println!("No active requests");
+        return;
+    }
+    println!(
+        "{:<12} {:<24} {:<8} {:>10} {:<8}",
+        "REQUEST", "MODEL", "PHASE", "AGE", "SESSION"
+    );
+    for request in requests {
+        let phase = match request.phase {
+            RequestPhase::Prefill => "prefill",
+            RequestPhase::Decode => "decode",
+        };
+        println!(
+            "{:<12} {:<24} {:<8} {:>10} {:<8}",
+            request.request_id.0,
+            truncate(&request.model_id, 24),
+            phase,
+            format!("{:.1}s", request.age_ms as f64 / 1000.0),
+            request.session_prefix.as_deref().unwrap_or("-"),
+        );
+    }
+}
+
 /// Fetch status from the IPC server.
 async fn fetch_status(socket_path: &str) -> Result<SystemStatus, CliError> {
     let client = CliIpcClient::new(socket_path.to_string());
@@ -338,6 +418,9 @@ async fn fetch_status(socket_path: &str) -> Result<SystemStatus, CliError> {
         },
         // DEFERRED v0.7.0: GPU metrics require cuda/metal feature
         gpus: None,
+        accelerations: report.as_ref().map(|r| r.accelerations),
+        cpu_features: report.as_ref().map(|r| r.cpu_features.clone()).unwrap_or_default(),
+        simd_tokenizer: report.as_ref().map(|r| r.simd_tokenizer),
         // DEFERRED v0.7.0: Event log requires telemetry event buffer
         recent_events: vec![]
     };
@@ -425,6 +508,20 @@ fn print_status_human(status: &SystemStatus) {
         "│ CPU: {:>5.1}%    Threads: {:>3}                                     │",
         status.resources.cpu_utilization_percent, status.resources.active_threads
     );
+    if let Some(accel) = status.accelerations {
+        println!(
+            "│ Accel: SIMD {:8}  Flash attention {:8}                    │",
+            if accel.simd { "on" } else { "scalar" },
+            if accel.flash_attention { "on" } else { "scalar" }
+        );
+    }
+    if let Some(tokenizer) = status.simd_tokenizer {
+        println!(
+            "│ Tokenizer: {:8}  CPU features: {:33} │",
+            if tokenizer { "simd" } else { "scalar" },
+            status.cpu_features.join(" ")
+        );
+    }
     println!("└─────────────────────────────────────────────────────────────────┘");
 
     // GPU status (if available)
@@ -615,6 +712,9 @@ mod tests {
                 avg_batch_size: 4.5,
             },
             gpus: None,
+            accelerations: None,
+            cpu_features: vec![],
+            simd_tokenizer: None,
             recent_events: vec![],
         };
 
@@ -0,0 +1,120 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `trace` command.
+//!
+//! Pulls the spans the running server buffered for one request ID (the ID a
+//! client logged) and prints them as a timeline relative to the request start.
+
+use super::ipc_client::{CliError, CliIpcClient};
+use crate::ipc::protocol::RequestId;
+use crate::telemetry::{ExportableSpan, SpanStatus};
+
+/// Upper bound on spans fetched for one request.
+const MAX_TRACE_SPANS: usize = 100;
+
+/// Render a request's spans as human-readable text, root span first.
+pub fn format_trace(request_id: u64, spans: &[ExportableSpan]) -> String {
+    if spans.is_empty() {
+        return format!("No spans buffered for request {}\n", request_id);
+    }
+    let mut ordered: Vec<&ExportableSpan> = spans.iter().collect();
+    ordered.sort_by_key(|s| (s.parent_span_id.is_some(), s.start_time_unix_ns));
+    let origin = ordered
+        .iter()
+        .map(|s| s.start_time_unix_ns)
+        .min()
+        .unwrap_or(0);
+
+    let mut out = format!(
+        "Trace for request {} (trace_id {}):\n",
+        request_id, ordered[0].trace_id
+    );
+    for span in ordered {
+        let status = match span.status {
+            SpanStatus::Ok => "ok",
+            SpanStatus::Error => "error",
+            SpanStatus::Unset => "unset",
+        };
+        let indent = if span.parent_span_id.is_some() {
+            "    "
+        } else {
+            "  "
+        };
+        out.push_str(&format!(
+            "{}{:<10} +{:.3}ms  {:.3}ms  {}\n",
+            indent,
+            span.name,
+            ns_to_ms(span.start_time_unix_ns.saturating_sub(origin)),
+            ns_to_ms(
+                span.end_time_unix_ns
+                    .saturating_sub(span.start_time_unix_ns)
+            ),
+            status
+        ));
+    }
+    out
+}
+
+fn ns_to_ms(ns: u64) -> f64 {
+    ns as f64 / 1_000_000.0
+}
+
+/// Print the trace for one request from the running server.
+///
+/// Exit codes: 0 = spans found, 1 = none buffered or error, 3 = connection error.
+pub async fn run_trace(socket_path: &str, request_id: u64) -> i32 {
+    let client = CliIpcClient::new(socket_path.to_string());
+    match client
+        .get_trace(RequestId(request_id), MAX_TRACE_SPANS)
+        .await
+    {
+        Ok(spans) => {
+            print!("{}", format_trace(request_id, &spans));
+            if spans.is_empty() {
+                1
+            } else {
+                0
+            }
+        }
+        Err(e) => {
+            eprintln!("Error fetching trace: {}", e);
+            match e {
+                CliError::ConnectionFailed(_) | CliError::Timeout => 3,
+                _ => 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::span_export::now_unix_ns;
+    use crate::telemetry::RequestTrace;
+
+    #[test]
+    fn test_format_trace_lists_root_then_phases() {
+        let trace = RequestTrace::start(42, "phi");
+        let queue = trace.phase("queue", now_unix_ns(), true);
+        let generate = trace.phase("generate", now_unix_ns(), false);
+        let root = trace.finish("inference", false);
+
+        let text = format_trace(42, &[queue, generate, root]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("Trace for request 42"));
+        assert!(lines[1].starts_with("  inference"));
+        assert!(lines[2].starts_with("    queue"));
+        assert!(lines[3].starts_with("    generate") && lines[3].ends_with("error"));
+    }
+
+    #[test]
+    fn test_format_trace_empty() {
+        assert_eq!(format_trace(7, &[]), "No spans buffered for request 7\n");
+    }
+
+    #[tokio::test]
+    async fn test_run_trace_connection_failure() {
+        assert_eq!(run_trace("/nonexistent/socket.sock", 1).await, 3);
+    }
+}
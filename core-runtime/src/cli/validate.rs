@@ -0,0 +1,48 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `infer --validate-only` command.
+//!
+//! Runs the prompt security scan locally and reports what would happen,
+//! without contacting the server or invoking the model:
+//! - Exit 0: Prompt passes
+//! - Exit 2: Prompt would be blocked
+
+use crate::security::{scan_prompt, SecurityConfig, SecurityScanResult};
+
+/// Exit codes for prompt validation.
+pub const EXIT_PROMPT_PASSED: i32 = 0;
+pub const EXIT_PROMPT_BLOCKED: i32 = 2;
+
+/// Scan `prompt` with the default security policy and print the result.
+pub fn run_validate_only(model_id: &str, prompt: &str) -> i32 {
+    let result = scan_prompt(prompt, &SecurityConfig::default());
+    print_scan_result(model_id, &result);
+    if result.passed {
+        EXIT_PROMPT_PASSED
+    } else {
+        EXIT_PROMPT_BLOCKED
+    }
+}
+
+fn print_scan_result(model_id: &str, result: &SecurityScanResult) {
+    let verdict = if result.passed { "PASS" } else { "BLOCKED" };
+    println!("Model:      {}", model_id);
+    println!("Verdict:    {}", verdict);
+    println!("Risk score: {}/100", result.risk_score);
+    if result.issues.is_empty() {
+        println!("Issues:     none");
+        return;
+    }
+    println!("Issues:");
+    for issue in &result.issues {
+        let location = issue
+            .location
+            .map(|offset| format!(" at byte {}", offset))
+            .unwrap_or_default();
+        println!(
+            "  - {:?} (severity {}){}: {}",
+            issue.issue_type, issue.severity, location, issue.description
+        );
+    }
+}
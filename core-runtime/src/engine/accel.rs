@@ -0,0 +1,318 @@
+//! CPU acceleration setup with scalar fallback.
+//!
+//! SIMD kernels and flash attention are verified against scalar reference
+//! implementations at startup. An acceleration that panics during init or
+//! disagrees with its reference is disabled with a warning, and callers
+//! routed through this module get the scalar path instead. Startup never
+//! aborts because an exotic CPU lacks (or misreports) a feature.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::flash_attn::{FlashAttn, FlashAttnConfig};
+use super::simd_matmul;
+
+/// Relative tolerance for self-test comparisons against scalar references.
+const SELF_TEST_TOLERANCE: f32 = 1e-3;
+
+static SIMD_ACTIVE: AtomicBool = AtomicBool::new(false);
+static FLASH_ATTN_ACTIVE: AtomicBool = AtomicBool::new(false);
+static STARTUP: OnceLock<Accelerations> = OnceLock::new();
+static CPU_FEATURES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Why an acceleration could not be enabled.
+#[derive(Debug, Error)]
+pub enum AccelInitError {
+    #[error("{0} initialization panicked")]
+    Panicked(&'static str),
+
+    #[error("{feature} self-test mismatch: expected {expected}, got {actual}")]
+    SelfTestMismatch {
+        feature: &'static str,
+        expected: f32,
+        actual: f32,
+    },
+
+    #[error("invalid flash attention config: {0}")]
+    InvalidConfig(String),
+}
+
+/// Which accelerations are active; false means the scalar path is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Accelerations {
+    pub simd: bool,
+    pub flash_attention: bool,
+}
+
+/// Try each acceleration and report which ones may be used.
+///
+/// Failures are logged as warnings; nothing is activated until [`activate`].
+pub fn probe(
+    simd: impl FnOnce() -> Result<(), AccelInitError>,
+    flash_attention: impl FnOnce() -> Result<(), AccelInitError>,
+) -> Accelerations {
+    Accelerations {
+        simd: check("simd", simd),
+        flash_attention: check("flash_attention", flash_attention),
+    }
+}
+
+fn check(name: &str, init: impl FnOnce() -> Result<(), AccelInitError>) -> bool {
+    match init() {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(
+                acceleration = name,
+                error = %e,
+                "acceleration unavailable, falling back to scalar implementation"
+            );
+            false
+        }
+    }
+}
+
+/// Route kernels through the given accelerations from now on.
+pub fn activate(accel: &Accelerations) {
+    SIMD_ACTIVE.store(accel.simd, Ordering::Relaxed);
+    FLASH_ATTN_ACTIVE.store(accel.flash_attention, Ordering::Relaxed);
+}
+
+/// Accelerations currently in use.
+pub fn active() -> Accelerations {
+    Accelerations {
+        simd: SIMD_ACTIVE.load(Ordering::Relaxed),
+        flash_attention: FLASH_ATTN_ACTIVE.load(Ordering::Relaxed),
+    }
+}
+
+/// Probe and activate all accelerations once per process.
+///
+/// Later calls return the startup result without re-probing.
+pub fn init_accelerations() -> Accelerations {
+    *STARTUP.get_or_init(|| {
+        tracing::info!(cpu_features = ?cpu_features(), "detected CPU features");
+        let accel = probe(try_init_simd, || {
+            try_init_flash_attn(&FlashAttnConfig::default())
+        });
+        activate(&accel);
+        accel
+    })
+}
+
+/// Instruction set extensions relevant to the kernels, detected once per process.
+pub fn cpu_features() -> &'static [String] {
+    CPU_FEATURES.get_or_init(detect_cpu_features)
+}
+
+fn detect_cpu_features() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut features = Vec::new();
+    #[cfg(target_arch = "x86_64")]
+    macro_rules! detect {
+        ($($feature:tt),*) => {
+            $(if is_x86_feature_detected!($feature) {
+                features.push($feature.to_string());
+            })*
+        };
+    }
+    #[cfg(target_arch = "aarch64")]
+    macro_rules! detect {
+        ($($feature:tt),*) => {
+            $(if std::arch::is_aarch64_feature_detected!($feature) {
+                features.push($feature.to_string());
+            })*
+        };
+    }
+    #[cfg(target_arch = "x86_64")]
+    detect!("sse4.2", "avx", "avx2", "fma", "avx512f", "aes");
+    #[cfg(target_arch = "aarch64")]
+    detect!("neon", "aes");
+    features
+}
+
+/// Initialize the SIMD kernels and check them against the scalar reference.
+pub fn try_init_simd() -> Result<(), AccelInitError> {
+    panic::catch_unwind(simd_matmul::init_simd).map_err(|_| AccelInitError::Panicked("simd"))?;
+
+    let q_data: Vec<u8> = (0..67u8).map(|i| i.wrapping_mul(37)).collect();
+    let input: Vec<f32> = (0..134).map(|i| (i as f32 * 0.37).sin()).collect();
+    let scale = 0.05;
+
+    let q8 = panic::catch_unwind(|| simd_matmul::dot_q8(&q_data, &input[..67], scale))
+        .map_err(|_| AccelInitError::Panicked("simd"))?;
+    compare(
+        "simd dot_q8",
+        scalar_dot_q8(&q_data, &input[..67], scale),
+        q8,
+    )?;
+
+    let q4 = panic::catch_unwind(|| simd_matmul::dot_q4(&q_data, &input, scale))
+        .map_err(|_| AccelInitError::Panicked("simd"))?;
+    compare("simd dot_q4", scalar_dot_q4(&q_data, &input, scale), q4)
+}
+
+/// Build flash attention for `config` and check it against naive attention.
+pub fn try_init_flash_attn(config: &FlashAttnConfig) -> Result<(), AccelInitError> {
+    let attn = FlashAttn::try_new(config.clone())?;
+    let head_dim = config.head_dim;
+    // Span several tiles so the online-softmax rescaling is exercised
+    let seq_len = config.block_size * 2 + 1;
+
+    let query: Vec<f32> = (0..head_dim).map(|i| (i as f32 * 0.11).cos()).collect();
+    let keys: Vec<f32> = (0..seq_len * head_dim)
+        .map(|i| (i as f32 * 0.07).sin() * 0.5)
+        .collect();
+    let values: Vec<f32> = (0..seq_len * head_dim)
+        .map(|i| (i as f32 * 0.13).cos())
+        .collect();
+
+    let mut expected = vec![0.0; head_dim];
+    naive_attention(&query, &keys, &values, seq_len, head_dim, &mut expected);
+    let mut actual = vec![0.0; head_dim];
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        attn.forward(&query, &keys, &values, seq_len, &mut actual)
+    }))
+    .map_err(|_| AccelInitError::Panicked("flash_attention"))?;
+
+    for (&e, &a) in expected.iter().zip(&actual) {
+        compare("flash_attention", e, a)?;
+    }
+    Ok(())
+}
+
+fn compare(feature: &'static str, expected: f32, actual: f32) -> Result<(), AccelInitError> {
+    let tolerance = SELF_TEST_TOLERANCE * expected.abs().max(1.0);
+    if (expected - actual).abs() <= tolerance {
+        Ok(())
+    } else {
+        Err(AccelInitError::SelfTestMismatch {
+            feature,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Q8 dot product, SIMD when active.
+pub fn dot_q8(q_data: &[u8], input: &[f32], scale: f32) -> f32 {
+    if SIMD_ACTIVE.load(Ordering::Relaxed) {
+        simd_matmul::dot_q8(q_data, input, scale)
+    } else {
+        scalar_dot_q8(q_data, input, scale)
+    }
+}
+
+/// Q4 dot product, SIMD when active.
+pub fn dot_q4(q_data: &[u8], input: &[f32], scale: f32) -> f32 {
+    if SIMD_ACTIVE.load(Ordering::Relaxed) {
+        simd_matmul::dot_q4(q_data, input, scale)
+    } else {
+        scalar_dot_q4(q_data, input, scale)
+    }
+}
+
+/// Single-query attention, tiled flash attention when active.
+pub fn attention(
+    config: &FlashAttnConfig,
+    query: &[f32],
+    keys: &[f32],
+    values: &[f32],
+    seq_len: usize,
+    output: &mut [f32],
+) {
+    if FLASH_ATTN_ACTIVE.load(Ordering::Relaxed) && config.block_size > 0 {
+        FlashAttn::new(config.clone()).forward(query, keys, values, seq_len, output);
+    } else {
+        naive_attention(query, keys, values, seq_len, config.head_dim, output);
+    }
+}
+
+/// Scalar reference for [`dot_q8`].
+pub fn scalar_dot_q8(q_data: &[u8], input: &[f32], scale: f32) -> f32 {
+    q_data
+        .iter()
+        .zip(input)
+        .map(|(&q, &x)| (q as i8 as f32) * x)
+        .sum::<f32>()
+        * scale
+}
+
+/// Scalar reference for [`dot_q4`]: two 4-bit values per byte, low nibble first.
+pub fn scalar_dot_q4(q_data: &[u8], input: &[f32], scale: f32) -> f32 {
+    let mut sum = 0.0f32;
+    for (i, &byte) in q_data.iter().enumerate() {
+        if let Some(&x) = input.get(i * 2) {
+            sum += ((byte & 0x0F) as i8 - 8) as f32 * x;
+        }
+        if let Some(&x) = input.get(i * 2 + 1) {
+            sum += ((byte >> 4) as i8 - 8) as f32 * x;
+        }
+    }
+    sum * scale
+}
+
+/// Untiled softmax attention with the same scoring as [`FlashAttn`].
+pub fn naive_attention(
+    query: &[f32],
+    keys: &[f32],
+    values: &[f32],
+    seq_len: usize,
+    head_dim: usize,
+    output: &mut [f32],
+) {
+    if seq_len == 0 {
+        return;
+    }
+    let scores: Vec<f32> = (0..seq_len)
+        .map(|pos| {
+            let key = &keys[pos * head_dim..(pos + 1) * head_dim];
+            query.iter().zip(key).map(|(&q, &k)| q * k).sum()
+        })
+        .collect();
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let weights: Vec<f32> = scores.iter().map(|&s| (s - max).exp()).collect();
+    let total: f32 = weights.iter().sum();
+
+    for (j, out) in output.iter_mut().enumerate().take(head_dim) {
+        *out = weights
+            .iter()
+            .enumerate()
+            .map(|(pos, &w)| w * values[pos * head_dim + j])
+            .sum::<f32>()
+            / total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_probe_falls_back_to_scalar() {
+        let accel = probe(|| Err(AccelInitError::Panicked("simd")), || Ok(()));
+        assert_eq!(
+            accel,
+            Accelerations {
+                simd: false,
+                flash_attention: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_flash_attn_self_test_rejects_zero_block_size() {
+        let config = FlashAttnConfig {
+            block_size: 0,
+            head_dim: 8,
+        };
+        assert!(matches!(
+            try_init_flash_attn(&config),
+            Err(AccelInitError::InvalidConfig(_))
+        ));
+        assert!(try_init_flash_attn(&FlashAttnConfig::default()).is_ok());
+    }
+}
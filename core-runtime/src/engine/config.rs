@@ -2,7 +2,17 @@
 //!
 //! All fields have safe defaults. Configuration is validated before use.
 
+use tokio_util::sync::CancellationToken;
+
+use super::decode_valve::{DecodeSignal, DEFAULT_ABSOLUTE_MAX_DECODE_STEPS};
 use super::error::InferenceError;
+use super::grammar::GrammarConstraint;
+use super::history::DEFAULT_HISTORY_WINDOW;
+use super::sampler::SamplerKind;
+use super::sampling::DEFAULT_CANDIDATE_CAP;
+
+/// Default lower bound applied to a non-zero `top_p` before sampling.
+pub const DEFAULT_TOP_P_FLOOR: f32 = 0.01;
 
 /// Per-call inference configuration.
 #[derive(Debug, Clone)]
@@ -15,12 +25,47 @@ pub struct InferenceConfig {
     pub top_p: f32,
     /// Top-k sampling limit (0 = disabled)
     pub top_k: u32,
+    /// Min-p threshold relative to the most likely token; overrides `top_p` when set
+    pub min_p: Option<f32>,
+    /// Highest-logit candidates kept before top-k/top-p (0 = full vocabulary).
+    /// Bounds per-step sampling cost on large vocabularies.
+    pub candidate_cap: usize,
     /// Repetition penalty (1.0 = none, >1.0 = penalize repeats)
     pub repetition_penalty: f32,
+    /// Ban tokens that would repeat an n-gram of this size (0 = disabled)
+    pub no_repeat_ngram_size: usize,
+    /// Recent tokens considered for repetition penalty and stop sequences.
+    /// Bounds per-request memory; repeats older than this are not penalized.
+    pub history_window: usize,
     /// Hard timeout in milliseconds — inference killed after this
     pub timeout_ms: u64,
+    /// CPU time cap in milliseconds, checked between decode steps. Distinct
+    /// from `timeout_ms`: waiting for a worker does not count. None = no cap.
+    pub max_cpu_ms: Option<u64>,
     /// Maximum memory allowed for this call (bytes). None = use global limit.
     pub max_memory_bytes: Option<usize>,
+    /// Hard cap on decode steps regardless of `max_tokens`, EOS or timeouts.
+    /// Set by the engine, not the client; see [`super::DecodeValve`].
+    pub absolute_max_decode_steps: u32,
+    /// Marked by the model when prefill ends; detached unless the caller
+    /// tracks request phases.
+    pub decode_signal: DecodeSignal,
+    /// Checked between decode steps; once cancelled, streaming generation
+    /// stops after the current token. Never cancelled by default.
+    pub cancel: CancellationToken,
+    /// `(token, bias)` pairs added to raw logits before filtering, sorted by
+    /// token. A bias of `f32::NEG_INFINITY` bans the token.
+    pub logit_bias: Vec<(u32, f32)>,
+    /// Structure the output must follow; disallowed tokens are masked
+    /// before sampling. None = unconstrained.
+    pub grammar: Option<GrammarConstraint>,
+    /// Seed for the sampling RNG. None = seeded from the OS per call.
+    pub seed: Option<u64>,
+    /// Generation halts once the decoded output contains any of these;
+    /// see [`super::StopMatcher`].
+    pub stop_sequences: Vec<String>,
+    /// Strategy picking each token; see [`super::Sampler`].
+    pub sampler: SamplerKind,
 }
 
 impl Default for InferenceConfig {
@@ -30,9 +75,22 @@ impl Default for InferenceConfig {
             temperature: 0.7,
             top_p: 0.9,
             top_k: 40,
+            min_p: None,
+            candidate_cap: DEFAULT_CANDIDATE_CAP,
             repetition_penalty: 1.1,
+            no_repeat_ngram_size: 0,
+            history_window: DEFAULT_HISTORY_WINDOW,
             timeout_ms: 30_000,
+            max_cpu_ms: None,
             max_memory_bytes: Some(1024 * 1024 * 1024), // 1GB
+            absolute_max_decode_steps: DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            decode_signal: DecodeSignal::default(),
+            cancel: CancellationToken::new(),
+            logit_bias: Vec::new(),
+            grammar: None,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: SamplerKind::Default,
         }
     }
 }
@@ -50,19 +108,54 @@ impl InferenceConfig {
                 "top_p must be in range (0.0, 1.0]".into(),
             ));
         }
+        if let Some(min_p) = self.min_p {
+            if !(min_p > 0.0 && min_p <= 1.0) {
+                return Err(InferenceError::InputValidation(
+                    "min_p must be in range (0.0, 1.0]".into(),
+                ));
+            }
+        }
         if self.repetition_penalty < 1.0 {
             return Err(InferenceError::InputValidation(
                 "repetition_penalty must be >= 1.0".into(),
             ));
         }
+        if self.history_window == 0 {
+            return Err(InferenceError::InputValidation(
+                "history_window must be > 0".into(),
+            ));
+        }
         if self.timeout_ms == 0 {
             return Err(InferenceError::InputValidation(
                 "timeout_ms must be > 0".into(),
             ));
         }
+        self.sampler.validate()?;
         Ok(())
     }
 
+    /// Normalize sampling parameters so samplers always have candidates.
+    ///
+    /// - `top_k == 0` means disabled: all candidates are considered.
+    /// - A set `min_p` takes precedence and is checked first: `top_p` is
+    ///   disabled (`1.0`), so even `top_p == 0.0` keeps min-p sampling.
+    /// - Otherwise `top_p <= 0.0` falls back to greedy decoding
+    ///   (`top_k = 1`, `temperature = 0.0`, `top_p = 1.0`).
+    /// - Other `top_p` values are clamped to `[top_p_floor, 1.0]`.
+    pub fn normalize_sampling(&mut self, top_p_floor: f32) {
+        if self.min_p.is_some() {
+            self.top_p = 1.0;
+            return;
+        }
+        if self.top_p.is_nan() || self.top_p <= 0.0 {
+            self.top_k = 1;
+            self.temperature = 0.0;
+            self.top_p = 1.0;
+            return;
+        }
+        self.top_p = self.top_p.clamp(top_p_floor.clamp(f32::EPSILON, 1.0), 1.0);
+    }
+
     /// Create a config for deterministic classification (no sampling).
     pub fn for_classification() -> Self {
         Self {
@@ -70,9 +163,22 @@ impl InferenceConfig {
             temperature: 0.0,
             top_p: 1.0,
             top_k: 0,
+            min_p: None,
+            candidate_cap: DEFAULT_CANDIDATE_CAP,
             repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            history_window: DEFAULT_HISTORY_WINDOW,
             timeout_ms: 5_000,
+            max_cpu_ms: None,
             max_memory_bytes: Some(512 * 1024 * 1024), // 512MB
+            absolute_max_decode_steps: DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            decode_signal: DecodeSignal::default(),
+            cancel: CancellationToken::new(),
+            logit_bias: Vec::new(),
+            grammar: None,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: SamplerKind::Default,
         }
     }
 
@@ -83,9 +189,22 @@ impl InferenceConfig {
             temperature: 0.0,
             top_p: 1.0,
             top_k: 0,
+            min_p: None,
+            candidate_cap: DEFAULT_CANDIDATE_CAP,
             repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            history_window: DEFAULT_HISTORY_WINDOW,
             timeout_ms: 2_000,
+            max_cpu_ms: None,
             max_memory_bytes: Some(256 * 1024 * 1024), // 256MB
+            absolute_max_decode_steps: DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            decode_signal: DecodeSignal::default(),
+            cancel: CancellationToken::new(),
+            logit_bias: Vec::new(),
+            grammar: None,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: SamplerKind::Default,
         }
     }
 }
@@ -0,0 +1,85 @@
+//! Cooperative per-request CPU time accounting.
+//!
+//! The sandbox caps CPU for the whole process; this caps a single request.
+//! Decode loops call [`CpuBudget::check`] once per step, so a runaway
+//! request stops at the next step boundary instead of starving the worker.
+//! CPU time is measured on the calling thread, which is the thread running
+//! the decode loop; time spent blocked or waiting does not count.
+
+use std::time::Duration;
+
+use super::error::InferenceError;
+
+/// CPU time budget for one request, started when the request begins decoding.
+#[derive(Debug, Clone, Copy)]
+pub struct CpuBudget {
+    limit: Option<Duration>,
+    started: Option<Duration>,
+}
+
+impl CpuBudget {
+    /// Start accounting against `limit_ms`. `None` disables the check.
+    pub fn start(limit_ms: Option<u64>) -> Self {
+        let limit = limit_ms.map(Duration::from_millis);
+        let started = limit.and_then(|_| thread_cpu_time());
+        Self { limit, started }
+    }
+
+    /// CPU time used on this thread since `start`, if measurable.
+    pub fn used(&self) -> Option<Duration> {
+        let started = self.started?;
+        thread_cpu_time().map(|now| now.saturating_sub(started))
+    }
+
+    /// Fail with `CpuLimitExceeded` once the budget is spent.
+    pub fn check(&self) -> Result<(), InferenceError> {
+        let (Some(limit), Some(used)) = (self.limit, self.used()) else {
+            return Ok(());
+        };
+        if used > limit {
+            return Err(InferenceError::CpuLimitExceeded {
+                used_ms: used.as_millis() as u64,
+                limit_ms: limit.as_millis() as u64,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// CPU time consumed by the calling thread.
+#[cfg(unix)]
+pub fn thread_cpu_time() -> Option<Duration> {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: ts is a valid, writable timespec for the duration of the call.
+    let rc = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+    if rc != 0 {
+        return None;
+    }
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// CPU time consumed by the calling thread.
+#[cfg(not(unix))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_budget_never_trips() {
+        let budget = CpuBudget::start(None);
+        assert!(budget.used().is_none());
+        assert!(budget.check().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sleeping_does_not_consume_budget() {
+        let budget = CpuBudget::start(Some(20));
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(budget.check().is_ok());
+    }
+}
@@ -2,7 +2,14 @@
 //!
 //! Generates tokens sequentially with minimal latency per step.
 
-use crate::engine::{FinishReason, InferenceError, SpeculativeConfig};
+use crate::engine::sampler::{DefaultSampler, Sampler};
+use crate::engine::sampling::{
+    apply_logit_bias, apply_repetition_penalty, ban_tokens, banned_ngram_tokens,
+};
+use crate::engine::{
+    FinishReason, GrammarState, InferenceConfig, InferenceError, SpeculativeConfig, StopMatcher,
+    TokenHistory, DEFAULT_HISTORY_WINDOW,
+};
 use crate::memory::paged::{PageTable, PAGE_TOKENS};
 
 /// Result from a single decode step.
@@ -25,6 +32,16 @@ pub struct DecodeConfig {
     pub eos_token: u32,
     /// Enable speculative decoding.
     pub speculative: Option<SpeculativeConfig>,
+    /// Repetition penalty over recent prompt and generated tokens (1.0 = none).
+    pub repetition_penalty: f32,
+    /// Ban tokens that would repeat an n-gram of this size (0 = disabled).
+    pub no_repeat_ngram_size: usize,
+    /// Recent tokens considered for the repetition penalty and n-gram ban.
+    pub history_window: usize,
+    /// Seed for the sampling RNG. None = seeded from the OS.
+    pub seed: Option<u64>,
+    /// Picks each token once the logits are adjusted.
+    pub sampler: Box<dyn Sampler>,
 }
 
 impl Default for DecodeConfig {
@@ -33,6 +50,25 @@ impl Default for DecodeConfig {
             hidden_dim: 768,
             eos_token: 2, // Common EOS token ID
             speculative: None,
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            history_window: DEFAULT_HISTORY_WINDOW,
+            seed: None,
+            sampler: Box::new(DefaultSampler::default()),
+        }
+    }
+}
+
+impl DecodeConfig {
+    /// Repetition controls, seed and the requested sampler from `config`.
+    pub fn from_inference(config: &InferenceConfig) -> Self {
+        Self {
+            repetition_penalty: config.repetition_penalty,
+            no_repeat_ngram_size: config.no_repeat_ngram_size,
+            history_window: config.history_window,
+            seed: config.seed,
+            sampler: config.sampler.build(config),
+            ..Default::default()
         }
     }
 }
@@ -43,22 +79,124 @@ pub struct DecodeExecutor {
     config: DecodeConfig,
     current_pos: usize,
     tokens_generated: usize,
+    /// Recent prompt and generated tokens, for repetition control.
+    history: TokenHistory,
+    /// Output constraint consulted before every sample.
+    grammar: Option<GrammarState>,
+    /// Decoded output tail checked for stop sequences by `accept`.
+    stop: StopMatcher,
+    /// Decoded output recorded by `accept`, trimmed at a stop sequence.
+    output: Vec<u8>,
 }
 
 impl DecodeExecutor {
     /// Create a new decode executor.
-    pub fn new(config: DecodeConfig) -> Self {
+    pub fn new(mut config: DecodeConfig) -> Self {
+        config.sampler.reset(config.seed);
         Self {
+            history: TokenHistory::new(config.history_window),
             config,
             current_pos: 0,
             tokens_generated: 0,
+            grammar: None,
+            stop: StopMatcher::default(),
+            output: Vec::new(),
         }
     }
 
+    /// Finish generation once the decoded output contains any of `stops`.
+    pub fn with_stop_sequences(mut self, stops: &[String]) -> Self {
+        self.stop = StopMatcher::new(stops);
+        self
+    }
+
+    /// Constrain generated tokens to `grammar`.
+    pub fn with_grammar(mut self, grammar: GrammarState) -> Self {
+        self.grammar = Some(grammar);
+        self
+    }
+
     /// Initialize decoder with prefill position.
     pub fn init(&mut self, prefill_len: usize) {
         self.current_pos = prefill_len;
         self.tokens_generated = 0;
+        self.history = TokenHistory::new(self.config.history_window);
+        self.config.sampler.reset(self.config.seed);
+        self.stop.reset();
+        self.output.clear();
+    }
+
+    /// Initialize decoder after prefilling `prompt`, which seeds the
+    /// repetition history.
+    pub fn init_with_prompt(&mut self, prompt: &[u32]) {
+        self.init(prompt.len());
+        for &token in prompt {
+            self.history.push(token);
+        }
+    }
+
+    /// Apply the grammar mask, the n-gram ban, then the repetition penalty
+    /// to raw logits (after any logit bias, before top-k/top-p).
+    pub fn adjust_logits(&self, logits: &mut [f32]) {
+        if let Some(grammar) = &self.grammar {
+            grammar.mask_logits(logits);
+        }
+        let recent: Vec<u32> = self.history.iter().collect();
+        let banned = banned_ngram_tokens(&recent, self.config.no_repeat_ngram_size);
+        ban_tokens(logits, &banned);
+        apply_repetition_penalty(logits, &recent, self.config.repetition_penalty, recent.len());
+    }
+
+    /// Pick the next token from raw `logits`: `config.logit_bias`,
+    /// `adjust_logits`, then the configured sampler at `config`'s top-k,
+    /// top-p and temperature. Returns None for empty logits.
+    pub fn sample(&mut self, logits: &mut [f32], config: &InferenceConfig) -> Option<u32> {
+        apply_logit_bias(logits, &config.logit_bias);
+        self.adjust_logits(logits);
+        let recent: Vec<u32> = self.history.iter().collect();
+        self.config.sampler.configure(config);
+        self.config.sampler.sample(logits, &recent)
+    }
+
+    /// Record a sampled `token` whose decoded bytes are `piece`.
+    ///
+    /// EOS finishes generation without being counted or output. A stop
+    /// sequence, even one straddling several tokens, finishes it too: the
+    /// stop string and anything after it are trimmed from `output`, and
+    /// `tokens_generated` drops to the tokens still contributing to it.
+    pub fn accept(&mut self, token: u32, piece: &[u8]) -> Result<DecodeStepResult, InferenceError> {
+        if let Some(grammar) = &mut self.grammar {
+            grammar.accept(token)?;
+        }
+        self.current_pos += 1;
+        self.history.push(token);
+
+        let finished = |finish_reason| DecodeStepResult {
+            token: Some(token),
+            finished: true,
+            finish_reason: Some(finish_reason),
+        };
+        if token == self.config.eos_token {
+            return Ok(finished(FinishReason::Stop));
+        }
+
+        self.tokens_generated += 1;
+        self.output.extend_from_slice(piece);
+        if let Some(stop) = self.stop.push(piece) {
+            self.output.truncate(stop.output_len);
+            self.tokens_generated = stop.tokens;
+            return Ok(finished(FinishReason::Stop));
+        }
+        Ok(DecodeStepResult {
+            token: Some(token),
+            finished: false,
+            finish_reason: None,
+        })
+    }
+
+    /// Output recorded by `accept`, without any stop sequence.
+    pub fn output(&self) -> &[u8] {
+        &self.output
     }
 
     /// Generate a single token with minimal latency.
@@ -82,12 +220,16 @@ impl DecodeExecutor {
 
         // Simulate token generation (actual model would sample here)
         let token = self.sample_token()?;
+        if let Some(grammar) = &mut self.grammar {
+            grammar.accept(token)?;
+        }
 
         // Write KV for generated position
         self.write_kv(page_table)?;
 
         self.current_pos += 1;
         self.tokens_generated += 1;
+        self.history.push(token);
 
         // Check for EOS
         if token == self.config.eos_token {
@@ -133,3 +275,130 @@ impl DecodeExecutor {
     pub fn tokens_generated(&self) -> usize { self.tokens_generated }
     pub fn current_pos(&self) -> usize { self.current_pos }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjust_logits_applies_grammar_mask() {
+        use crate::engine::GrammarConstraint;
+
+        let vocab: std::sync::Arc<[String]> = ["{", "}", "x"].map(String::from).into();
+        let grammar = GrammarState::new(&GrammarConstraint::Json, vocab, 3).unwrap();
+        let exec = DecodeExecutor::new(DecodeConfig::default()).with_grammar(grammar);
+
+        let mut logits = vec![1.0, 9.0, 9.0, 9.0];
+        exec.adjust_logits(&mut logits);
+        assert_eq!(logits, vec![1.0, f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY]);
+    }
+
+    #[test]
+    fn adjust_logits_bans_ngram_and_penalizes_prompt_tokens() {
+        let mut exec = DecodeExecutor::new(DecodeConfig {
+            repetition_penalty: 2.0,
+            no_repeat_ngram_size: 2,
+            ..Default::default()
+        });
+        exec.init_with_prompt(&[1, 3, 1]);
+        assert_eq!(exec.current_pos(), 3);
+
+        let mut logits = vec![1.0, 4.0, 1.0, 5.0];
+        exec.adjust_logits(&mut logits);
+
+        // "1 3" occurred, so 3 after a trailing 1 is banned; 1 is penalized
+        assert_eq!(logits[3], f32::NEG_INFINITY);
+        assert_eq!(logits[1], 2.0);
+        assert_eq!(logits[0], 1.0);
+    }
+
+    #[test]
+    fn sample_is_reproducible_per_seed_and_reset_by_init() {
+        let config = InferenceConfig {
+            temperature: 1.0,
+            top_p: 1.0,
+            top_k: 0,
+            repetition_penalty: 1.0,
+            ..Default::default()
+        };
+        let draw = |exec: &mut DecodeExecutor| -> Vec<u32> {
+            (0..32)
+                .map(|_| exec.sample(&mut [0.0; 8], &config).unwrap())
+                .collect()
+        };
+        let seeded = |seed| DecodeExecutor::new(DecodeConfig {
+            seed: Some(seed),
+            ..Default::default()
+        });
+
+        let mut exec = seeded(1);
+        let first = draw(&mut exec);
+        assert_eq!(first, draw(&mut seeded(1)));
+        assert_ne!(first, draw(&mut seeded(2)));
+
+        exec.init(0);
+        assert_eq!(first, draw(&mut exec));
+    }
+
+    #[test]
+    fn sample_honors_per_call_sampling_settings() {
+        let mut exec = DecodeExecutor::new(DecodeConfig::default());
+        exec.init(0);
+        let greedy = InferenceConfig {
+            top_k: 1,
+            repetition_penalty: 1.0,
+            ..Default::default()
+        };
+        for _ in 0..16 {
+            let mut logits = vec![0.5, 0.4, 0.6, 0.1];
+            assert_eq!(exec.sample(&mut logits, &greedy), Some(2));
+        }
+        assert_eq!(exec.sample(&mut [], &greedy), None);
+    }
+
+    #[test]
+    fn sample_with_default_sampler_reproduces_bias_adjust_top_k_top_p() {
+        use crate::engine::sampling::sample_top_k_top_p;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let config = InferenceConfig {
+            temperature: 0.8,
+            top_k: 6,
+            top_p: 0.95,
+            repetition_penalty: 1.3,
+            logit_bias: vec![(0, -2.0), (5, 1.0)],
+            seed: Some(1234),
+            ..Default::default()
+        };
+        let mut exec = DecodeExecutor::new(DecodeConfig::from_inference(&config));
+        exec.init_with_prompt(&[3, 4]);
+        let mut reference = DecodeExecutor::new(DecodeConfig::from_inference(&config));
+        reference.init_with_prompt(&[3, 4]);
+        let mut rng = StdRng::seed_from_u64(1234);
+
+        for step in 0..48u32 {
+            let raw: Vec<f32> = (0..12).map(|i| ((i * 5 + step) % 7) as f32 * 0.4).collect();
+
+            // The pre-trait pipeline: bias, adjust, then top-k/top-p with a
+            // draw from an RNG seeded like the executor's
+            let mut expected_logits = raw.clone();
+            apply_logit_bias(&mut expected_logits, &config.logit_bias);
+            reference.adjust_logits(&mut expected_logits);
+            let expected = sample_top_k_top_p(
+                &expected_logits,
+                config.top_k as usize,
+                config.top_p,
+                config.temperature,
+                config.candidate_cap,
+                rng.gen(),
+            )
+            .unwrap();
+
+            let token = exec.sample(&mut raw.clone(), &config).unwrap();
+            assert_eq!(token, expected, "step {step}");
+            exec.accept(token, b"x").unwrap();
+            reference.accept(token, b"x").unwrap();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Absolute decode-step safety valve.
+//!
+//! `max_tokens`, EOS, timeouts and CPU budgets should always end a decode
+//! loop first. This cap exists for when they don't (a sampler that never
+//! reaches EOS while a limit is misread), so tripping it is logged as a
+//! critical event: it means there is a bug, not a slow request.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+use super::error::InferenceError;
+use crate::telemetry::{log_security_event, SecurityEvent};
+
+/// Default hard cap on decode steps for a single request.
+pub const DEFAULT_ABSOLUTE_MAX_DECODE_STEPS: u32 = 65_536;
+
+/// Counts decode steps for one request against the absolute cap.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeValve {
+    limit: u32,
+    steps: u32,
+}
+
+impl DecodeValve {
+    /// Start counting against `limit` steps.
+    pub fn new(limit: u32) -> Self {
+        Self { limit, steps: 0 }
+    }
+
+    /// Steps taken so far.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Account for one decode step; fails once the cap would be exceeded.
+    pub fn step(&mut self) -> Result<(), InferenceError> {
+        if self.steps >= self.limit {
+            let limit = self.limit.to_string();
+            log_security_event(
+                SecurityEvent::DecodeStepLimit,
+                "Absolute decode step limit reached; decode loop did not terminate",
+                &[("limit", limit.as_str())],
+            );
+            return Err(InferenceError::DecodeStepLimit { limit: self.limit });
+        }
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+/// Set by a model once prefill ends and decoding starts.
+///
+/// Lets callers report a running request's phase without seeing inside the
+/// model. The default signal is detached: marking it does nothing.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeSignal(Option<Arc<OnceLock<Instant>>>);
+
+impl DecodeSignal {
+    /// A signal the caller can observe with [`is_decoding`](Self::is_decoding).
+    pub fn attached() -> Self {
+        Self(Some(Arc::new(OnceLock::new())))
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Record that decoding has started. Only the first mark counts.
+    pub fn mark(&self) {
+        if let Some(started) = &self.0 {
+            started.get_or_init(Instant::now);
+        }
+    }
+
+    pub fn is_decoding(&self) -> bool {
+        self.decode_started().is_some()
+    }
+
+    /// When decoding started, if it has.
+    pub fn decode_started(&self) -> Option<Instant> {
+        self.0.as_ref().and_then(|started| started.get().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valve_allows_exactly_limit_steps() {
+        let mut valve = DecodeValve::new(3);
+        for _ in 0..3 {
+            assert!(valve.step().is_ok());
+        }
+        let err = valve.step().unwrap_err();
+        assert!(matches!(err, InferenceError::DecodeStepLimit { limit: 3 }));
+        assert_eq!(valve.steps(), 3);
+    }
+}
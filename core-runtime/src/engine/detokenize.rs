@@ -0,0 +1,142 @@
+//! Pluggable detokenizers for vocabularies with special spacing rules.
+//!
+//! Byte-level BPE (GPT-2 style) vocabularies store every byte as a printable
+//! character, so a space is `Ġ` and a newline is `Ċ`. Pieces must be mapped
+//! back to raw bytes before UTF-8 decoding, otherwise spaces are dropped or
+//! doubled and multi-byte characters come out garbled.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::tokenizer::TokenizerError;
+
+/// Converts token IDs into the raw bytes they represent.
+pub trait Detokenizer: Send + Sync {
+    fn decode_bytes(&self, tokens: &[u32]) -> Result<Vec<u8>, TokenizerError>;
+}
+
+/// Controls post-processing applied to decoded text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Keep (or add) a single leading space. When false, the space encoded
+    /// by the first token's `Ġ` prefix is stripped.
+    pub add_leading_space: bool,
+}
+
+impl DecodeOptions {
+    /// Apply the leading-space policy to decoded text.
+    pub fn apply(&self, text: String) -> String {
+        match (self.add_leading_space, text.starts_with(' ')) {
+            (true, false) if !text.is_empty() => format!(" {text}"),
+            (false, true) => text[1..].to_string(),
+            _ => text,
+        }
+    }
+}
+
+/// GPT-2 style byte-level BPE detokenizer over a piece vocabulary.
+pub struct ByteLevelBpe {
+    pieces: Vec<String>,
+}
+
+impl ByteLevelBpe {
+    /// `pieces[id]` is the vocabulary string for token `id`.
+    pub fn new(pieces: Vec<String>) -> Self {
+        Self { pieces }
+    }
+
+    pub fn vocab_size(&self) -> usize {
+        self.pieces.len()
+    }
+}
+
+impl Detokenizer for ByteLevelBpe {
+    fn decode_bytes(&self, tokens: &[u32]) -> Result<Vec<u8>, TokenizerError> {
+        let decoder = byte_decoder();
+        let mut bytes = Vec::with_capacity(tokens.len() * 4);
+        for &token in tokens {
+            let piece = self
+                .pieces
+                .get(token as usize)
+                .ok_or(TokenizerError::InvalidToken(token))?;
+            for c in piece.chars() {
+                let byte = decoder.get(&c).ok_or_else(|| {
+                    TokenizerError::DecodingFailed(format!(
+                        "token {token} contains non byte-level char {c:?}"
+                    ))
+                })?;
+                bytes.push(*byte);
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// Inverse of GPT-2's `bytes_to_unicode`: printable bytes map to themselves,
+/// the remaining 68 bytes map to U+0100 onward in byte order.
+fn byte_decoder() -> &'static HashMap<char, u8> {
+    static DECODER: OnceLock<HashMap<char, u8>> = OnceLock::new();
+    DECODER.get_or_init(|| {
+        let mut map = HashMap::with_capacity(256);
+        let mut shifted = 0u32;
+        for byte in 0..=255u8 {
+            let printable = matches!(byte, b'!'..=b'~' | 0xA1..=0xAC | 0xAE..=0xFF);
+            let c = if printable {
+                char::from(byte)
+            } else {
+                let c = char::from_u32(0x100 + shifted).expect("valid code point");
+                shifted += 1;
+                c
+            };
+            map.insert(c, byte);
+        }
+        map
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bpe(pieces: &[&str]) -> ByteLevelBpe {
+        ByteLevelBpe::new(pieces.iter().map(|p| p.to_string()).collect())
+    }
+
+    #[test]
+    fn test_space_and_newline_markers() {
+        let decoder = byte_decoder();
+        assert_eq!(decoder.get(&'Ġ'), Some(&b' '));
+        assert_eq!(decoder.get(&'Ċ'), Some(&b'\n'));
+        assert_eq!(decoder.get(&'a'), Some(&b'a'));
+        assert_eq!(decoder.len(), 256);
+    }
+
+    #[test]
+    fn test_multibyte_pieces_reassemble() {
+        // "é" is bytes C3 A9, stored as the pieces for those two bytes
+        let vocab = bpe(&["caf", "Ã©"]);
+        let bytes = vocab.decode_bytes(&[0, 1]).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "café");
+    }
+
+    #[test]
+    fn test_unknown_token_rejected() {
+        let vocab = bpe(&["a"]);
+        assert!(matches!(
+            vocab.decode_bytes(&[1]),
+            Err(TokenizerError::InvalidToken(1))
+        ));
+    }
+
+    #[test]
+    fn test_leading_space_policy() {
+        let strip = DecodeOptions::default();
+        let keep = DecodeOptions {
+            add_leading_space: true,
+        };
+        assert_eq!(strip.apply(" the".into()), "the");
+        assert_eq!(keep.apply(" the".into()), " the");
+        assert_eq!(keep.apply("the".into()), " the");
+        assert_eq!(keep.apply(String::new()), "");
+    }
+}
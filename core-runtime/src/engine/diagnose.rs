@@ -0,0 +1,168 @@
+//! "Model not servable" diagnostics.
+//!
+//! A model can register successfully and still fail every request. These
+//! checks pinpoint why and suggest a fix, one result per check.
+
+use serde::{Deserialize, Serialize};
+
+use super::inference::InferenceEngine;
+
+/// Text used for the tokenizer round-trip check.
+const ROUND_TRIP_TEXT: &str = "Hello, world!";
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The model does not expose what the check needs.
+    Skip,
+}
+
+/// One diagnostic check with remediation for failures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub remediation: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, remediation: &str) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+
+    fn skip(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+}
+
+/// Per-check report for one model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDiagnostic {
+    pub model_id: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl ModelDiagnostic {
+    /// True if no check failed.
+    pub fn is_servable(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+
+    /// Look up a check by name.
+    pub fn check(&self, name: &str) -> Option<&DiagnosticCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+impl InferenceEngine {
+    /// Run all servability checks against a registered model.
+    pub async fn diagnose(&self, model_id: &str) -> ModelDiagnostic {
+        let model = self.model(model_id).await;
+        let Some(model) = model else {
+            return ModelDiagnostic {
+                model_id: model_id.to_string(),
+                checks: vec![DiagnosticCheck::fail(
+                    "registered",
+                    format!("model '{}' is not registered", model_id),
+                    "Load the model first, or check the name against the server's model list.",
+                )],
+            };
+        };
+
+        let tokenizer = match model.tokenizer_round_trip(ROUND_TRIP_TEXT) {
+            None => DiagnosticCheck::fail(
+                "tokenizer",
+                "no tokenizer attached to the model",
+                "Ship the tokenizer with the model (GGUF with embedded vocab, or tokenizer.json \
+                 under tokenizers/).",
+            ),
+            Some(Err(e)) => DiagnosticCheck::fail(
+                "tokenizer",
+                format!("tokenizer failed: {}", e),
+                "The tokenizer does not match the weights; re-export the model with its own \
+                 vocabulary.",
+            ),
+            Some(Ok(text)) if text.trim_start() != ROUND_TRIP_TEXT => DiagnosticCheck::fail(
+                "tokenizer",
+                format!(
+                    "round-trip mismatch: {:?} decoded as {:?}",
+                    ROUND_TRIP_TEXT, text
+                ),
+                "Check the tokenizer type (byte-level BPE vs SentencePiece) and its \
+                 detokenizer configuration.",
+            ),
+            Some(Ok(_)) => DiagnosticCheck::pass("tokenizer", "encode/decode round-trips"),
+        };
+
+        let forward = match self.ping(model_id).await {
+            Ok(latency_ms) => {
+                DiagnosticCheck::pass("forward_step", format!("completed in {}ms", latency_ms))
+            }
+            Err(e) => DiagnosticCheck::fail(
+                "forward_step",
+                format!("forward step failed: {}", e),
+                "Check the quantization is supported by this build and that weights are not \
+                 truncated (verify the sha256).",
+            ),
+        };
+
+        let eos = match model.eos_token() {
+            Some(token) => DiagnosticCheck::pass("eos_token", format!("EOS token id {}", token)),
+            None => DiagnosticCheck::fail(
+                "eos_token",
+                "EOS token not defined",
+                "Set tokenizer.ggml.eos_token_id in the GGUF metadata; without it generation \
+                 only stops at max_tokens.",
+            ),
+        };
+
+        let context = match model.context_size() {
+            None => DiagnosticCheck::skip("context", "model does not report its context size"),
+            Some(n_ctx) if (n_ctx as usize) < self.max_context_length() => DiagnosticCheck::fail(
+                "context",
+                format!(
+                    "runtime max_context_length {} exceeds model context {}",
+                    self.max_context_length(),
+                    n_ctx
+                ),
+                "Lower max_context_length or load the model with a larger n_ctx.",
+            ),
+            Some(n_ctx) => DiagnosticCheck::pass(
+                "context",
+                format!(
+                    "model context {} covers max_context_length {}",
+                    n_ctx,
+                    self.max_context_length()
+                ),
+            ),
+        };
+
+        ModelDiagnostic {
+            model_id: model_id.to_string(),
+            checks: vec![tokenizer, forward, eos, context],
+        }
+    }
+}
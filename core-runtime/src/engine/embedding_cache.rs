@@ -0,0 +1,210 @@
+//! Embedding cache for duplicate texts.
+//!
+//! Bulk embedding pipelines re-embed the same strings (boilerplate headers,
+//! repeated chunks). Embeddings are deterministic per model, so vectors are
+//! cached by model handle and text hash within a TTL and size bound.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::output::EmbeddingResult;
+use crate::models::ModelHandle;
+
+/// Counter names in the metrics store.
+pub const EMBEDDING_CACHE_HITS_TOTAL: &str = "core_embedding_cache_hits_total";
+pub const EMBEDDING_CACHE_MISSES_TOTAL: &str = "core_embedding_cache_misses_total";
+/// Gauge name for the hit rate in the metrics store.
+pub const EMBEDDING_CACHE_HIT_RATE: &str = "core_embedding_cache_hit_rate";
+
+/// Configuration for the embedding cache.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingCacheConfig {
+    pub ttl: Duration,
+    /// Maximum cached vectors; 0 disables the cache.
+    pub max_entries: usize,
+}
+
+impl Default for EmbeddingCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(300),
+            max_entries: 10_000,
+        }
+    }
+}
+
+/// Point-in-time cache statistics.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl EmbeddingCacheStats {
+    /// Fraction of lookups served from cache.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+type CacheKey = (u64, [u8; 32]);
+
+struct CachedEmbedding {
+    result: EmbeddingResult,
+    cached_at: Instant,
+}
+
+/// Embedding vectors keyed by `(model_handle, text_hash)`.
+pub struct EmbeddingCache {
+    entries: Mutex<HashMap<CacheKey, CachedEmbedding>>,
+    ttl: Duration,
+    max_entries: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl EmbeddingCache {
+    pub fn new(config: EmbeddingCacheConfig) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl: config.ttl,
+            max_entries: config.max_entries,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Compute the text half of the cache key.
+    pub fn text_hash(text: &str) -> [u8; 32] {
+        Sha256::digest(text.as_bytes()).into()
+    }
+
+    /// Cached embedding of `text` under `handle`, if within TTL.
+    pub fn get(&self, handle: ModelHandle, text: &str) -> Option<EmbeddingResult> {
+        if self.max_entries == 0 {
+            return None;
+        }
+        let key = (handle.id(), Self::text_hash(text));
+        let mut entries = self.entries.lock().unwrap();
+        let hit = match entries.get(&key) {
+            Some(entry) if entry.cached_at.elapsed() <= self.ttl => Some(entry.result.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+        let counter = if hit.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        hit
+    }
+
+    /// Store the embedding of `text` under `handle`.
+    pub fn insert(&self, handle: ModelHandle, text: &str, result: EmbeddingResult) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let key = (handle.id(), Self::text_hash(text));
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let ttl = self.ttl;
+            entries.retain(|_, entry| entry.cached_at.elapsed() <= ttl);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.cached_at)
+                    .map(|(k, _)| *k);
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CachedEmbedding {
+                result,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop every vector cached for `handle` (model unloaded or replaced).
+    pub fn invalidate_model(&self, handle: ModelHandle) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|(id, _), _| *id != handle.id());
+    }
+
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn embedding(value: f32) -> EmbeddingResult {
+        EmbeddingResult {
+            vector: vec![value; 4],
+            dimensions: 4,
+        }
+    }
+
+    #[test]
+    fn test_entries_are_per_model_and_expire() {
+        let cache = EmbeddingCache::new(EmbeddingCacheConfig {
+            ttl: Duration::from_millis(20),
+            max_entries: 10,
+        });
+        cache.insert(ModelHandle::new(1), "text", embedding(1.0));
+
+        assert!(cache.get(ModelHandle::new(2), "text").is_none());
+        assert_eq!(
+            cache.get(ModelHandle::new(1), "text").unwrap().vector,
+            vec![1.0; 4]
+        );
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get(ModelHandle::new(1), "text").is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 0));
+    }
+
+    #[test]
+    fn test_full_cache_evicts_oldest() {
+        let cache = EmbeddingCache::new(EmbeddingCacheConfig {
+            ttl: Duration::from_secs(60),
+            max_entries: 2,
+        });
+        let handle = ModelHandle::new(1);
+        cache.insert(handle, "a", embedding(1.0));
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert(handle, "b", embedding(2.0));
+        cache.insert(handle, "c", embedding(3.0));
+
+        assert!(cache.get(handle, "a").is_none());
+        assert!(cache.get(handle, "b").is_some());
+        assert!(cache.get(handle, "c").is_some());
+    }
+}
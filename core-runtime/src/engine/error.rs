@@ -16,6 +16,9 @@ pub enum InferenceError {
     #[error("Inference timeout after {0}ms")]
     Timeout(u64),
 
+    #[error("CPU limit exceeded: used {used_ms}ms, limit {limit_ms}ms")]
+    CpuLimitExceeded { used_ms: u64, limit_ms: u64 },
+
     #[error("Memory limit exceeded: used {used} bytes, limit {limit} bytes")]
     MemoryExceeded { used: usize, limit: usize },
 
@@ -43,19 +46,94 @@ pub enum InferenceError {
 
     #[error("Invalid model format: {0}")]
     InvalidFormat(String),
+
+    #[error("Invalid token id {id}: model vocab size is {vocab_size}")]
+    InvalidToken { id: u32, vocab_size: u32 },
+
+    #[error("Memory pressure: {available} bytes available, floor is {floor} bytes")]
+    MemoryPressure { available: u64, floor: u64 },
+
+    #[error("Decode step safety limit reached: {limit} steps")]
+    DecodeStepLimit { limit: u32 },
+}
+
+/// Coarse failure class used to split error metrics and error codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The request itself was invalid (bad params, unknown model, too long).
+    Client,
+    /// The model failed to produce an acceptable result.
+    Model,
+    /// The runtime could not serve the request (limits, overload, timeout).
+    Infra,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Model => "model",
+            Self::Infra => "infra",
+        }
+    }
+
+    /// Metrics counter incremented for each failure in this category.
+    pub fn counter_name(&self) -> &'static str {
+        match self {
+            Self::Client => "core_errors_client_total",
+            Self::Model => "core_errors_model_total",
+            Self::Infra => "core_errors_infra_total",
+        }
+    }
+
+    /// HTTP-style status code reported in IPC error responses.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Client => 400,
+            Self::Model => 502,
+            Self::Infra => 503,
+        }
+    }
 }
 
 impl InferenceError {
+    /// Classify this error for metrics and error codes.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ModelNotLoaded(_)
+            | Self::InputValidation(_)
+            | Self::CapabilityNotSupported(_)
+            | Self::InvalidToken { .. } => ErrorCategory::Client,
+            Self::OutputFiltered { .. }
+            | Self::ModelError(_)
+            | Self::HashMismatch { .. }
+            | Self::InvalidFormat(_)
+            | Self::DecodeStepLimit { .. } => ErrorCategory::Model,
+            Self::Timeout(_)
+            | Self::CpuLimitExceeded { .. }
+            | Self::MemoryExceeded { .. }
+            | Self::MemoryPressure { .. }
+            | Self::RateLimited
+            | Self::QueueFull { .. } => ErrorCategory::Infra,
+        }
+    }
+
     /// Returns true if this error should be logged as a warning.
     pub fn is_warning(&self) -> bool {
-        matches!(self, Self::RateLimited | Self::QueueFull { .. })
+        matches!(
+            self,
+            Self::RateLimited | Self::QueueFull { .. } | Self::MemoryPressure { .. }
+        )
     }
 
     /// Returns true if this error indicates a security concern.
     pub fn is_security_concern(&self) -> bool {
         matches!(
             self,
-            Self::HashMismatch { .. } | Self::InputValidation(_) | Self::OutputFiltered { .. }
+            Self::HashMismatch { .. }
+                | Self::InputValidation(_)
+                | Self::OutputFiltered { .. }
+                | Self::InvalidToken { .. }
         )
     }
 }
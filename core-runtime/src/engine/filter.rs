@@ -4,13 +4,13 @@
 //! Uses NFC Unicode normalization to prevent bypass through decomposed characters.
 
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use unicode_normalization::UnicodeNormalization;
 
-use crate::engine::InferenceError;
+use crate::engine::{FinishReason, GenerationResult, InferenceError};
 
 /// Configuration for output filtering.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct FilterConfig {
     /// List of substrings to block (case-insensitive).
     #[serde(default)]
@@ -21,6 +21,10 @@ pub struct FilterConfig {
     /// Maximum output length in characters (0 = unlimited).
     #[serde(default)]
     pub max_output_chars: usize,
+    /// Hard cap on output bytes, whatever `max_tokens` allows. Output past
+    /// it is dropped and generation reports `FinishReason::Length`.
+    #[serde(default)]
+    pub max_output_bytes: Option<usize>,
     /// Replacement text for filtered content.
     #[serde(default = "default_replacement")]
     pub replacement: String,
@@ -67,6 +71,48 @@ impl OutputFilter {
     /// Filter the output text, returning filtered version or error if blocked.
     /// Applies NFC normalization before blocklist comparison.
     pub fn filter(&self, text: &str) -> Result<String, InferenceError> {
+        let mut result = self.rewrite(text)?;
+        self.limit_bytes(&mut result);
+        Ok(result)
+    }
+
+    /// Filter a finished generation in place.
+    ///
+    /// Sets `FinishReason::Length` if the output had to be cut to
+    /// `max_output_bytes`.
+    pub fn apply(&self, result: &mut GenerationResult) -> Result<(), InferenceError> {
+        result.text = self.rewrite(&result.text)?;
+        let mut truncated = self.limit_bytes(&mut result.text);
+        if let (Some(max), Some(raw)) = (self.config.max_output_bytes, &mut result.raw_bytes) {
+            if raw.len() > max {
+                raw.truncate(utf8_floor(raw, max));
+                truncated = true;
+            }
+        }
+        if truncated {
+            result.finish_reason = FinishReason::Length;
+        }
+        Ok(())
+    }
+
+    /// Byte limiter for output emitted piece by piece, e.g. streamed tokens.
+    pub fn limiter(&self) -> OutputLimiter {
+        OutputLimiter::new(self.config.max_output_bytes)
+    }
+
+    /// Truncate `text` to `max_output_bytes`; true if anything was cut.
+    fn limit_bytes(&self, text: &mut String) -> bool {
+        match self.config.max_output_bytes {
+            Some(max) if text.len() > max => {
+                text.truncate(text.floor_char_boundary(max));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Apply blocklist, regex patterns and the character limit.
+    fn rewrite(&self, text: &str) -> Result<String, InferenceError> {
         let mut result = text.to_string();
 
         // Normalize input for comparison (NFC handles composed/decomposed equivalence)
@@ -89,7 +135,7 @@ impl OutputFilter {
 
         // Apply length limit
         if self.config.max_output_chars > 0 && result.len() > self.config.max_output_chars {
-            result.truncate(self.config.max_output_chars);
+            result.truncate(result.floor_char_boundary(self.config.max_output_chars));
         }
 
         Ok(result)
@@ -124,3 +170,89 @@ impl Default for OutputFilter {
         }
     }
 }
+
+/// Enforces `max_output_bytes` across output emitted piece by piece.
+///
+/// The piece that crosses the limit is cut at the last whole character and
+/// everything after it is dropped, so a client never receives a partial
+/// multibyte sequence.
+#[derive(Debug, Clone, Default)]
+pub struct OutputLimiter {
+    /// Bytes still allowed, or `None` for no limit.
+    remaining: Option<usize>,
+    exceeded: bool,
+}
+
+impl OutputLimiter {
+    /// Create a limiter allowing `max_output_bytes` in total.
+    pub fn new(max_output_bytes: Option<usize>) -> Self {
+        Self {
+            remaining: max_output_bytes,
+            exceeded: false,
+        }
+    }
+
+    /// Admit the next piece, returning the part that fits.
+    pub fn push<'a>(&mut self, piece: &'a str) -> &'a str {
+        if self.exceeded {
+            return "";
+        }
+        let Some(remaining) = self.remaining.as_mut() else {
+            return piece;
+        };
+        if piece.len() <= *remaining {
+            *remaining -= piece.len();
+            return piece;
+        }
+        self.exceeded = true;
+        let end = piece.floor_char_boundary(*remaining);
+        *remaining = 0;
+        &piece[..end]
+    }
+
+    /// Admit a piece of `len` bytes that cannot be cut, e.g. a streamed
+    /// token. Returns false, and drops everything after, if it does not fit.
+    pub fn admit(&mut self, len: usize) -> bool {
+        match self.remaining.as_mut() {
+            _ if self.exceeded => false,
+            None => true,
+            Some(remaining) if len <= *remaining => {
+                *remaining -= len;
+                true
+            }
+            Some(remaining) => {
+                *remaining = 0;
+                self.exceeded = true;
+                false
+            }
+        }
+    }
+
+    /// Whether a byte limit is set at all.
+    pub fn is_limited(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Check if output went past the limit; emission should stop.
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded
+    }
+
+    /// `FinishReason::Length` once the limit was exceeded.
+    pub fn finish_reason(&self) -> Option<FinishReason> {
+        self.exceeded.then_some(FinishReason::Length)
+    }
+}
+
+/// Largest length `<= max` that does not split a UTF-8 sequence in `bytes`.
+fn utf8_floor(bytes: &[u8], max: usize) -> usize {
+    let mut end = max.min(bytes.len());
+    // At most three continuation bytes follow a lead byte
+    for _ in 0..3 {
+        if end == 0 || end == bytes.len() || bytes[end] & 0xC0 != 0x80 {
+            break;
+        }
+        end -= 1;
+    }
+    end
+}
@@ -3,6 +3,8 @@
 //! Tiled attention that computes softmax in blocks to reduce peak memory
 //! from O(n^2) to O(n). Uses online softmax algorithm for numerical stability.
 
+use super::accel::AccelInitError;
+
 /// Configuration for Flash Attention.
 #[derive(Debug, Clone)]
 pub struct FlashAttnConfig {
@@ -33,6 +35,17 @@ impl FlashAttn {
         Self { config }
     }
 
+    /// Create a new instance, rejecting configs `forward` cannot run with.
+    pub fn try_new(config: FlashAttnConfig) -> Result<Self, AccelInitError> {
+        if config.block_size == 0 || config.head_dim == 0 {
+            return Err(AccelInitError::InvalidConfig(format!(
+                "block_size {} and head_dim {} must be non-zero",
+                config.block_size, config.head_dim
+            )));
+        }
+        Ok(Self { config })
+    }
+
     /// Compute attention output using tiled algorithm.
     ///
     /// # Arguments
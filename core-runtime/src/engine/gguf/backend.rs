@@ -5,18 +5,23 @@
 
 use std::num::NonZeroU32;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
 use llama_cpp_2::context::params::LlamaContextParams;
 use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
-use llama_cpp_2::model::{AddBos, LlamaModel};
+use llama_cpp_2::model::{AddBos, LlamaModel, Special};
 use llama_cpp_2::sampling::LlamaSampler;
+use llama_cpp_2::token::logit_bias::LlamaLogitBias;
 use llama_cpp_2::token::LlamaToken;
+use rand::RngCore;
 
+use super::constrain::{sample_next, token_vocab};
 use crate::engine::{
-    FinishReason, GenerationResult, InferenceConfig, InferenceError,
+    CpuBudget, DecodeValve, FinishReason, GenerationResult, GrammarState, InferenceConfig,
+    InferenceError, SamplerKind, StopMatcher, TokenHistory, TokenVocab, validate_token_ids,
 };
 
 /// Holds the loaded llama-cpp-2 model and backend.
@@ -25,6 +30,10 @@ pub struct LlamaBackendInner {
     model: LlamaModel,
     n_ctx: u32,
     n_threads: i32,
+    /// Vocabulary size read at load; token IDs at or above it are rejected.
+    vocab_size: u32,
+    /// Token bytes indexed for grammar masking, built on first use.
+    grammar_vocab: OnceLock<Arc<TokenVocab>>,
 }
 
 // SAFETY: LlamaModel and LlamaBackend are Send+Sync in llama-cpp-2.
@@ -45,11 +54,22 @@ impl LlamaBackendInner {
         let model = LlamaModel::load_from_file(&backend, path, &model_params)
             .map_err(|e| InferenceError::ModelError(format!("load: {e}")))?;
         let n_threads = resolve_threads(config.n_threads);
-        Ok(Self { backend, model, n_ctx: config.n_ctx, n_threads })
+        let vocab_size = u32::try_from(model.n_vocab()).unwrap_or(0);
+        Ok(Self {
+            backend,
+            model,
+            n_ctx: config.n_ctx,
+            n_threads,
+            vocab_size,
+            grammar_vocab: OnceLock::new(),
+        })
     }
 
     pub fn model_size(&self) -> usize { self.model.size() as usize }
 
+    /// Number of tokens in the model vocabulary.
+    pub fn vocab_size(&self) -> u32 { self.vocab_size }
+
     /// Generate text from a prompt using llama-cpp-2.
     pub fn generate(
         &self,
@@ -59,11 +79,14 @@ impl LlamaBackendInner {
         let tokens = self.tokenize(prompt)?;
         let max_tok = config.max_tokens.unwrap_or(256);
         let mut ctx = self.create_context()?;
-        let (out_tokens, reason) =
+        let (out_tokens, reason, stop_len) =
             self.sample_loop(&mut ctx, &tokens, max_tok, config)?;
-        let text = self.detokenize(&out_tokens)?;
+        let mut bytes = self.detokenize_bytes(&out_tokens)?;
+        if let Some(len) = stop_len {
+            bytes.truncate(len);
+        }
         let count = u32::try_from(out_tokens.len()).unwrap_or(u32::MAX);
-        Ok(GenerationResult { text, tokens_generated: count, finish_reason: reason })
+        Ok(GenerationResult::from_bytes(bytes, count, reason))
     }
 
     /// Stream tokens one at a time through a channel.
@@ -79,15 +102,33 @@ impl LlamaBackendInner {
         let mut batch = LlamaBatch::new(tokens.len(), 1);
         add_seq(&mut batch, &tokens)?;
         decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(config);
+        config.decode_signal.mark();
+        let mut sampler = build_sampler(config, self.model.n_vocab());
         sampler.accept_many(tokens.iter().copied());
+        let mut history = seed_history(config, &tokens);
+        let mut grammar = self.grammar_state(config)?;
         let mut pos = tokens.len() as i32;
         let rt = tokio::runtime::Handle::current();
+        let budget = CpuBudget::start(config.max_cpu_ms);
+        let mut valve = DecodeValve::new(config.absolute_max_decode_steps);
+        let mut stop = StopMatcher::new(&config.stop_sequences);
         for i in 0..max_tok {
-            // Use -1 to sample from the last token that had logits computed
-            let tok = sampler.sample(&ctx, -1);
-            sampler.accept(tok);
+            if config.cancel.is_cancelled() {
+                break;
+            }
+            budget.check()?;
+            valve.step()?;
+            let tok = sample_next(&mut sampler, &ctx, &history, config, grammar.as_ref());
+            accept(&mut sampler, &mut history, grammar.as_mut(), tok)?;
             let eog = self.model.is_eog_token(tok);
+            // Tokens already sent cannot be recalled, so only the token
+            // completing a stop sequence is withheld
+            if !eog && !stop.is_empty() {
+                let piece = self.detokenize_bytes(&[tok])?;
+                if stop.push(&piece).is_some() {
+                    break;
+                }
+            }
             let is_final = eog || i + 1 == max_tok;
             if rt.block_on(sender.send(tok.0 as u32, is_final)).is_err() {
                 break;
@@ -107,17 +148,20 @@ impl LlamaBackendInner {
         context: &[u32],
         count: usize,
     ) -> Result<Vec<u32>, InferenceError> {
+        validate_token_ids(context, self.vocab_size)?;
         let tokens: Vec<LlamaToken> = context.iter().map(|&t| LlamaToken(t as i32)).collect();
         let config = InferenceConfig::default();
         let mut ctx = self.create_context()?;
         let mut batch = LlamaBatch::new(tokens.len().max(1), 1);
         add_seq(&mut batch, &tokens)?;
         decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(&config);
+        let mut sampler = build_sampler(&config, self.model.n_vocab());
         sampler.accept_many(tokens.iter().copied());
         let mut out = Vec::with_capacity(count);
         let mut pos = tokens.len() as i32;
+        let mut valve = DecodeValve::new(config.absolute_max_decode_steps);
         for _ in 0..count {
+            valve.step()?;
             let tok = sampler.sample(&ctx, -1);
             sampler.accept(tok);
             if self.model.is_eog_token(tok) { break; }
@@ -137,6 +181,8 @@ impl LlamaBackendInner {
         draft: &[u32],
     ) -> Result<crate::engine::speculative::VerifyResult, InferenceError> {
         use crate::engine::speculative::VerifyResult;
+        validate_token_ids(context, self.vocab_size)?;
+        validate_token_ids(draft, self.vocab_size)?;
         let all_tokens: Vec<LlamaToken> = context.iter()
             .chain(draft.iter())
             .map(|&t| LlamaToken(t as i32))
@@ -153,7 +199,7 @@ impl LlamaBackendInner {
                 .map_err(|e| InferenceError::ModelError(format!("batch: {e}")))?;
         }
         decode(&mut ctx, &mut batch)?;
-        let mut sampler = build_sampler(&config);
+        let mut sampler = build_sampler(&config, self.model.n_vocab());
         // Verify each draft token
         for (i, &draft_tok) in draft.iter().enumerate() {
             let logit_idx = (ctx_len - 1 + i) as i32;
@@ -166,9 +212,22 @@ impl LlamaBackendInner {
         Ok(VerifyResult::accept_all(draft.len()))
     }
 
-    /// Get EOS token ID.
+    /// Get EOS token ID. `None` if the GGUF metadata does not define one.
     pub fn eos_token(&self) -> Option<u32> {
-        Some(self.model.token_eos().0 as u32)
+        u32::try_from(self.model.token_eos().0).ok()
+    }
+
+    /// Query and KV head counts from the `<arch>.attention.*` metadata.
+    /// Models without `head_count_kv` use one KV head per query head.
+    pub fn attention_heads(&self) -> Option<super::AttentionHeads> {
+        let arch = self.model.meta_val_str("general.architecture").ok()?;
+        let count = |key: &str| -> Option<usize> {
+            let key = format!("{arch}.attention.{key}");
+            self.model.meta_val_str(&key).ok()?.trim().parse().ok()
+        };
+        let heads = count("head_count")?;
+        let kv_heads = count("head_count_kv").unwrap_or(heads);
+        Some(super::AttentionHeads { heads, kv_heads })
     }
 
     /// Tokenize a prompt string.
@@ -190,6 +249,32 @@ impl LlamaBackendInner {
         Ok(out)
     }
 
+    /// Concatenate raw token pieces without UTF-8 decoding.
+    pub fn detokenize_bytes(&self, tokens: &[LlamaToken]) -> Result<Vec<u8>, InferenceError> {
+        let mut out = Vec::new();
+        for &t in tokens {
+            let piece = self.model.token_to_bytes(t, Special::Plaintext)
+                .map_err(|e| InferenceError::ModelError(format!("detok: {e}")))?;
+            out.extend_from_slice(&piece);
+        }
+        Ok(out)
+    }
+
+    /// Grammar state for `config.grammar`, over this model's vocabulary.
+    fn grammar_state(
+        &self,
+        config: &InferenceConfig,
+    ) -> Result<Option<GrammarState>, InferenceError> {
+        let Some(constraint) = &config.grammar else {
+            return Ok(None);
+        };
+        let vocab = self
+            .grammar_vocab
+            .get_or_init(|| Arc::new(token_vocab(&self.model)));
+        let eos = self.eos_token().unwrap_or(u32::MAX);
+        GrammarState::with_vocab(constraint, Arc::clone(vocab), eos).map(Some)
+    }
+
     fn create_context(&self) -> Result<LlamaContext<'_>, InferenceError> {
         // Use same thread count for both - simpler and avoids cache contention
         // llama.cpp internally optimizes based on workload
@@ -207,28 +292,43 @@ impl LlamaBackendInner {
         tokens: &[LlamaToken],
         max_tok: u32,
         config: &InferenceConfig,
-    ) -> Result<(Vec<LlamaToken>, FinishReason), InferenceError> {
+    ) -> Result<(Vec<LlamaToken>, FinishReason, Option<usize>), InferenceError> {
         let mut batch = LlamaBatch::new(tokens.len(), 1);
         add_seq(&mut batch, tokens)?;
         decode(ctx, &mut batch)?;
-        let mut sampler = build_sampler(config);
+        config.decode_signal.mark();
+        let mut sampler = build_sampler(config, self.model.n_vocab());
         sampler.accept_many(tokens.iter().copied());
+        let mut history = seed_history(config, tokens);
+        let mut grammar = self.grammar_state(config)?;
         let mut out = Vec::new();
         let mut pos = tokens.len() as i32;
+        let budget = CpuBudget::start(config.max_cpu_ms);
+        let mut valve = DecodeValve::new(config.absolute_max_decode_steps);
+        let mut stop = StopMatcher::new(&config.stop_sequences);
         for _ in 0..max_tok {
-            // Use -1 to sample from the last token that had logits computed
-            let tok = sampler.sample(ctx, -1);
-            sampler.accept(tok);
+            budget.check()?;
+            valve.step()?;
+            let tok = sample_next(&mut sampler, ctx, &history, config, grammar.as_ref());
+            accept(&mut sampler, &mut history, grammar.as_mut(), tok)?;
             if self.model.is_eog_token(tok) {
-                return Ok((out, FinishReason::Stop));
+                return Ok((out, FinishReason::Stop, None));
             }
             out.push(tok);
+            if !stop.is_empty() {
+                let piece = self.detokenize_bytes(&[tok])?;
+                if let Some(found) = stop.push(&piece) {
+                    // Keep tokens overlapping the output; the caller trims bytes
+                    out.truncate(found.tokens);
+                    return Ok((out, FinishReason::Stop, Some(found.output_len)));
+                }
+            }
             batch.clear();
             add_one(&mut batch, tok, pos)?;
             decode(ctx, &mut batch)?;
             pos += 1;
         }
-        Ok((out, FinishReason::MaxTokens))
+        Ok((out, FinishReason::MaxTokens, None))
     }
 }
 
@@ -256,20 +356,75 @@ fn decode(ctx: &mut LlamaContext<'_>, batch: &mut LlamaBatch) -> Result<(), Infe
     ctx.decode(batch).map_err(|e| InferenceError::ModelError(format!("decode: {e}")))
 }
 
-fn build_sampler(config: &InferenceConfig) -> LlamaSampler {
+/// Record a sampled token with the sampler chain, history and grammar.
+fn accept(
+    sampler: &mut LlamaSampler,
+    history: &mut TokenHistory,
+    grammar: Option<&mut GrammarState>,
+    tok: LlamaToken,
+) -> Result<(), InferenceError> {
+    sampler.accept(tok);
+    history.push(tok.0 as u32);
+    match grammar {
+        Some(grammar) => grammar.accept(tok.0 as u32),
+        None => Ok(()),
+    }
+}
+
+/// Recent prompt tokens, for the n-gram ban.
+fn seed_history(config: &InferenceConfig, prompt: &[LlamaToken]) -> TokenHistory {
+    let mut history = TokenHistory::new(config.history_window);
+    for tok in prompt {
+        history.push(tok.0 as u32);
+    }
+    history
+}
+
+fn build_sampler(config: &InferenceConfig, n_vocab: i32) -> LlamaSampler {
     let mut s = Vec::new();
+    // Bias raw logits before any penalty or filter sees them
+    if !config.logit_bias.is_empty() {
+        let biases: Vec<LlamaLogitBias> = config
+            .logit_bias
+            .iter()
+            .map(|&(token, bias)| LlamaLogitBias::new(LlamaToken(token as i32), bias))
+            .collect();
+        s.push(LlamaSampler::logit_bias(n_vocab, &biases));
+    }
     if config.repetition_penalty > 1.0 {
-        s.push(LlamaSampler::penalties(64, config.repetition_penalty, 0.0, 0.0));
+        let last_n = i32::try_from(config.history_window).unwrap_or(i32::MAX);
+        s.push(LlamaSampler::penalties(last_n, config.repetition_penalty, 0.0, 0.0));
+    }
+    // Mirostat does its own truncation and draw in place of top-k/top-p/temp
+    if let SamplerKind::Mirostat { tau, eta } = config.sampler {
+        s.push(LlamaSampler::mirostat_v2(sampler_seed(config), tau, eta));
+        return LlamaSampler::chain_simple(s);
+    }
+    // Partial-select the strongest candidates first unless top_k is already tighter
+    let cap = i32::try_from(config.candidate_cap).unwrap_or(i32::MAX);
+    if cap > 0 && (config.top_k == 0 || config.top_k as i32 > cap) {
+        s.push(LlamaSampler::top_k(cap));
     }
     if config.top_k > 0 {
         s.push(LlamaSampler::top_k(config.top_k as i32));
     }
-    s.push(LlamaSampler::top_p(config.top_p as f32, 1));
+    match config.min_p {
+        Some(min_p) => s.push(LlamaSampler::min_p(min_p, 1)),
+        None => s.push(LlamaSampler::top_p(config.top_p as f32, 1)),
+    }
     s.push(LlamaSampler::temp(config.temperature));
-    s.push(LlamaSampler::dist(42));
+    s.push(LlamaSampler::dist(sampler_seed(config)));
     LlamaSampler::chain_simple(s)
 }
 
+/// The request's seed folded to 32 bits, or a fresh seed from the OS.
+fn sampler_seed(config: &InferenceConfig) -> u32 {
+    match config.seed {
+        Some(seed) => (seed ^ (seed >> 32)) as u32,
+        None => rand::rngs::OsRng.next_u32(),
+    }
+}
+
 fn resolve_threads(n: u32) -> i32 {
     if n == 0 {
         // LLM inference is memory-bound, hyperthreads help hide latency
@@ -0,0 +1,51 @@
+//! Token masking applied before the llama.cpp sampler chain.
+
+use llama_cpp_2::context::LlamaContext;
+use llama_cpp_2::model::{LlamaModel, Special};
+use llama_cpp_2::sampling::LlamaSampler;
+use llama_cpp_2::token::LlamaToken;
+
+use crate::engine::sampling::banned_ngram_tokens;
+use crate::engine::{GrammarState, InferenceConfig, TokenHistory, TokenVocab};
+
+/// `model`'s token bytes, indexed for grammar masking.
+pub(super) fn token_vocab(model: &LlamaModel) -> TokenVocab {
+    let pieces = (0..model.n_vocab().max(0)).map(|id| {
+        model
+            .token_to_bytes(LlamaToken(id), Special::Plaintext)
+            .unwrap_or_default()
+    });
+    TokenVocab::new(pieces)
+}
+
+/// Sample from the last computed logits. Tokens that would repeat an
+/// n-gram in `history` (when `config.no_repeat_ngram_size > 0`) or break
+/// `grammar` are banned before the sampler chain runs.
+pub(super) fn sample_next(
+    sampler: &mut LlamaSampler,
+    ctx: &LlamaContext<'_>,
+    history: &TokenHistory,
+    config: &InferenceConfig,
+    grammar: Option<&GrammarState>,
+) -> LlamaToken {
+    let ngram = config.no_repeat_ngram_size;
+    let recent: Vec<u32> = if ngram > 0 { history.iter().collect() } else { Vec::new() };
+    let banned = banned_ngram_tokens(&recent, ngram);
+    if banned.is_empty() && grammar.is_none() {
+        // Use -1 to sample from the last token that had logits computed
+        return sampler.sample(ctx, -1);
+    }
+    let mut data = ctx.token_data_array_ith(-1);
+    let allowed = grammar.map(|grammar| grammar.allowed(data.data.len()));
+    for candidate in data.data.iter_mut() {
+        let id = candidate.id().0 as u32;
+        let breaks_grammar = allowed
+            .as_ref()
+            .is_some_and(|allowed| !allowed.get(id as usize).copied().unwrap_or(false));
+        if breaks_grammar || banned.binary_search(&id).is_ok() {
+            candidate.set_logit(f32::NEG_INFINITY);
+        }
+    }
+    data.apply_sampler(sampler);
+    data.selected_token().unwrap_or_else(|| sampler.sample(ctx, -1))
+}
@@ -13,7 +13,6 @@ use crate::engine::{
 pub struct GgufGenerator {
     model_id: String,
     memory_bytes: AtomicUsize,
-    #[allow(dead_code)]
     context_size: u32,
     #[cfg(feature = "gguf")]
     inner: Option<super::backend::LlamaBackendInner>,
@@ -114,6 +113,17 @@ impl GgufGenerator {
         Err(InferenceError::ModelError("no model loaded".into()))
     }
 
+    /// Concatenate the raw pieces of `tokens` (may end mid UTF-8 sequence).
+    #[cfg(feature = "gguf")]
+    pub fn detokenize_bytes(&self, tokens: &[u32]) -> Result<Vec<u8>, InferenceError> {
+        use llama_cpp_2::token::LlamaToken;
+        if let Some(inner) = &self.inner {
+            let tokens: Vec<LlamaToken> = tokens.iter().map(|&t| LlamaToken(t as i32)).collect();
+            return inner.detokenize_bytes(&tokens);
+        }
+        Err(InferenceError::ModelError("no model loaded".into()))
+    }
+
     /// Get EOS token ID (for speculative decoding).
     #[cfg(feature = "gguf")]
     pub fn eos_token_id(&self) -> Option<u32> {
@@ -125,19 +135,7 @@ impl GgufGenerator {
         &self,
         messages: &[crate::engine::ChatMessage],
     ) -> Result<String, InferenceError> {
-        let mut prompt = String::new();
-        for msg in messages {
-            let tag = match msg.role {
-                crate::engine::ChatRole::System => "<|system|>",
-                crate::engine::ChatRole::User => "<|user|>",
-                crate::engine::ChatRole::Assistant => "<|assistant|>",
-            };
-            prompt.push_str(tag);
-            prompt.push_str(&msg.content);
-            prompt.push_str("<|end|>\n");
-        }
-        prompt.push_str("<|assistant|>");
-        Ok(prompt)
+        Ok(crate::engine::ChatTemplate::default().apply(messages))
     }
 }
 
@@ -190,6 +188,57 @@ impl super::GgufModel for GgufGenerator {
         Ok(())
     }
 
+    fn tokenizer_round_trip(&self, text: &str) -> Option<Result<String, InferenceError>> {
+        #[cfg(feature = "gguf")]
+        {
+            let inner = self.inner.as_ref()?;
+            Some(inner.tokenize(text).and_then(|tokens| inner.detokenize(&tokens)))
+        }
+        #[cfg(not(feature = "gguf"))]
+        {
+            let _ = text;
+            None
+        }
+    }
+
+    fn detokenize_bytes(&self, tokens: &[u32]) -> Option<Result<Vec<u8>, InferenceError>> {
+        #[cfg(feature = "gguf")]
+        {
+            Some(GgufGenerator::detokenize_bytes(self, tokens))
+        }
+        #[cfg(not(feature = "gguf"))]
+        {
+            let _ = tokens;
+            None
+        }
+    }
+
+    fn eos_token(&self) -> Option<u32> {
+        #[cfg(feature = "gguf")]
+        {
+            self.eos_token_id()
+        }
+        #[cfg(not(feature = "gguf"))]
+        {
+            None
+        }
+    }
+
+    fn context_size(&self) -> Option<u32> {
+        Some(self.context_size)
+    }
+
+    fn attention_heads(&self) -> Option<super::AttentionHeads> {
+        #[cfg(feature = "gguf")]
+        {
+            self.inner.as_ref().and_then(|i| i.attention_heads())
+        }
+        #[cfg(not(feature = "gguf"))]
+        {
+            None
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
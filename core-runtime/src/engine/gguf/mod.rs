@@ -4,6 +4,8 @@
 
 #[cfg(feature = "gguf")]
 pub mod backend;
+#[cfg(feature = "gguf")]
+mod constrain;
 mod generator;
 #[cfg(feature = "gguf")]
 pub mod speculative;
@@ -41,6 +43,15 @@ impl Default for GgufConfig {
     }
 }
 
+/// Attention head layout read from a model's metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttentionHeads {
+    /// Query heads.
+    pub heads: usize,
+    /// Key/value heads; fewer than `heads` under grouped-query attention.
+    pub kv_heads: usize,
+}
+
 /// Shared trait for GGUF models.
 #[async_trait::async_trait]
 pub trait GgufModel: Send + Sync {
@@ -56,6 +67,33 @@ pub trait GgufModel: Send + Sync {
 
     async fn unload(&mut self) -> Result<(), InferenceError>;
 
+    /// Encode then decode `text` with the model's tokenizer, for diagnostics.
+    /// `None` if the model has no tokenizer.
+    fn tokenizer_round_trip(&self, _text: &str) -> Option<Result<String, InferenceError>> {
+        None
+    }
+
+    /// Raw bytes of `tokens`' pieces, which may end partway through a UTF-8
+    /// sequence. `None` if the model has no tokenizer.
+    fn detokenize_bytes(&self, _tokens: &[u32]) -> Option<Result<Vec<u8>, InferenceError>> {
+        None
+    }
+
+    /// End-of-sequence token, if the model defines one.
+    fn eos_token(&self) -> Option<u32> {
+        None
+    }
+
+    /// Context window in tokens, if known.
+    fn context_size(&self) -> Option<u32> {
+        None
+    }
+
+    /// Attention head layout, if the model's metadata records it.
+    fn attention_heads(&self) -> Option<AttentionHeads> {
+        None
+    }
+
     /// Downcast support for streaming access to concrete type.
     fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -93,6 +131,15 @@ pub fn load_gguf_model(
     ))
 }
 
+/// Builds an engine model for `model_id` from a validated model file.
+pub type ModelFactory =
+    Arc<dyn Fn(&Path, &str) -> Result<Arc<dyn GgufModel>, InferenceError> + Send + Sync>;
+
+/// Factory loading GGUF files with `config`.
+pub fn gguf_model_factory(config: GgufConfig) -> ModelFactory {
+    Arc::new(move |path, model_id| load_gguf_model(path, model_id, &config))
+}
+
 /// Validate that a file has the GGUF magic bytes.
 pub fn is_valid_gguf(path: &Path) -> Result<bool, std::io::Error> {
     use std::fs::File;
@@ -0,0 +1,78 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// Licensed under the Apache License, Version 2.0
+
+//! Discovering the GPU devices present on this machine
+
+use super::GpuDevice;
+#[cfg(any(feature = "cuda", all(feature = "metal", target_os = "macos")))]
+use super::GpuError;
+
+/// Lists the GPU devices present on this machine
+pub trait DeviceEnumerator: Send + Sync {
+    /// GPU devices found; the CPU fallback is added by `GpuManager`
+    fn devices(&self) -> Vec<GpuDevice>;
+}
+
+/// Enumerates devices through the compiled-in CUDA and Metal backends
+pub struct SystemDevices;
+
+impl DeviceEnumerator for SystemDevices {
+    fn devices(&self) -> Vec<GpuDevice> {
+        #[allow(unused_mut)]
+        let mut devices = Vec::new();
+
+        // Detect CUDA devices using the cuda backend module
+        #[cfg(feature = "cuda")]
+        {
+            if let Ok(cuda_devices) = detect_cuda_devices() {
+                devices.extend(cuda_devices);
+            }
+        }
+
+        // Detect Metal devices (macOS only)
+        #[cfg(all(feature = "metal", target_os = "macos"))]
+        {
+            if let Ok(metal_devices) = detect_metal_devices() {
+                devices.extend(metal_devices);
+            }
+        }
+
+        devices
+    }
+}
+
+/// Detect CUDA devices using cudarc
+#[cfg(feature = "cuda")]
+fn detect_cuda_devices() -> Result<Vec<GpuDevice>, GpuError> {
+    use crate::engine::cuda::CudaBackend;
+
+    match CudaBackend::new() {
+        Ok(cuda_backend) => {
+            let devices: Vec<GpuDevice> = cuda_backend
+                .devices()
+                .iter()
+                .map(|info| info.device.clone())
+                .collect();
+            Ok(devices)
+        }
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Detect Metal devices using metal crate
+#[cfg(all(feature = "metal", target_os = "macos"))]
+fn detect_metal_devices() -> Result<Vec<GpuDevice>, GpuError> {
+    use crate::engine::metal::MetalBackend;
+
+    match MetalBackend::new() {
+        Ok(metal_backend) => {
+            let devices: Vec<GpuDevice> = metal_backend
+                .devices()
+                .iter()
+                .map(|info| info.device.clone())
+                .collect();
+            Ok(devices)
+        }
+        Err(_) => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// Licensed under the Apache License, Version 2.0
+
+//! Device discovery, selection and placement
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::{
+    DeviceEnumerator, DeviceSelection, GpuBackend, GpuConfig, GpuDevice, GpuError, GpuMemory,
+    SystemDevices,
+};
+
+/// GPU Manager - Handles device detection and memory management
+pub struct GpuManager {
+    /// Available devices
+    devices: Vec<GpuDevice>,
+    /// Current configuration
+    config: GpuConfig,
+    /// Source of `devices`
+    enumerator: Box<dyn DeviceEnumerator>,
+    /// Active device
+    active_device: Mutex<Option<Arc<GpuDevice>>>,
+    /// Next device for `DeviceSelection::RoundRobin`
+    next_round_robin: AtomicUsize,
+}
+
+impl GpuManager {
+    /// Create a new GPU manager
+    pub fn new(config: GpuConfig) -> Result<Self, GpuError> {
+        Self::with_enumerator(config, Box::new(SystemDevices))
+    }
+
+    /// Create a GPU manager that discovers devices through `enumerator`
+    pub fn with_enumerator(
+        config: GpuConfig,
+        enumerator: Box<dyn DeviceEnumerator>,
+    ) -> Result<Self, GpuError> {
+        let mut manager = Self {
+            devices: Vec::new(),
+            config,
+            enumerator,
+            active_device: Mutex::new(None),
+            next_round_robin: AtomicUsize::new(0),
+        };
+
+        manager.detect_devices()?;
+        manager.select_device()?;
+
+        Ok(manager)
+    }
+
+    /// Detect available GPU devices
+    pub fn detect_devices(&mut self) -> Result<(), GpuError> {
+        self.devices.clear();
+
+        // Always add CPU as fallback
+        self.devices.push(GpuDevice::cpu());
+        self.devices.extend(self.enumerator.devices());
+
+        if self.devices.len() == 1 && self.config.backend != GpuBackend::Cpu {
+            return Err(GpuError::NoDevicesAvailable);
+        }
+
+        Ok(())
+    }
+
+    /// Select the active device based on configuration
+    pub fn select_device(&mut self) -> Result<(), GpuError> {
+        let candidates = self.candidates();
+        let device = match self.config.device_selection {
+            DeviceSelection::Index(index) => {
+                let device = candidates.iter().find(|d| d.index == index);
+                if device.is_none() && !candidates.is_empty() {
+                    return Err(GpuError::DeviceNotFound(index));
+                }
+                device
+            }
+            // Ties go to the lowest index
+            DeviceSelection::MostFreeMemory => candidates
+                .iter()
+                .rev()
+                .max_by_key(|d| d.available_memory),
+            DeviceSelection::RoundRobin => {
+                self.next_round_robin.store(0, Ordering::Relaxed);
+                candidates.first()
+            }
+        }
+        .map(|d| (*d).clone());
+
+        let device = match device {
+            Some(d) => d,
+            // Fall back to CPU if requested backend not available
+            None if self.config.backend != GpuBackend::Cpu => GpuDevice::cpu(),
+            None => {
+                let index = match self.config.device_selection {
+                    DeviceSelection::Index(index) => index,
+                    _ => 0,
+                };
+                return Err(GpuError::DeviceNotFound(index));
+            }
+        };
+        self.bind(device);
+        Ok(())
+    }
+
+    /// Device to place the next model load on
+    ///
+    /// With `DeviceSelection::RoundRobin` each call takes the next device
+    /// of the configured backend; otherwise the active device is returned.
+    /// The active device is left unchanged either way.
+    pub fn next_device(&self) -> GpuDevice {
+        self.placement()
+            .map(|d| (*d).clone())
+            .unwrap_or_else(GpuDevice::cpu)
+    }
+
+    /// Get the active device
+    pub fn active_device(&self) -> GpuDevice {
+        self.active()
+            .map(|d| (*d).clone())
+            .unwrap_or_else(GpuDevice::cpu)
+    }
+
+    /// Devices of the configured backend, by index
+    fn candidates(&self) -> Vec<&GpuDevice> {
+        let mut candidates: Vec<&GpuDevice> = self
+            .devices
+            .iter()
+            .filter(|d| d.backend == self.config.backend)
+            .collect();
+        candidates.sort_by_key(|d| d.index);
+        candidates
+    }
+
+    /// Device the next load or allocation goes to
+    fn placement(&self) -> Option<Arc<GpuDevice>> {
+        if self.config.device_selection == DeviceSelection::RoundRobin {
+            let candidates = self.candidates();
+            if !candidates.is_empty() {
+                let turn = self.next_round_robin.fetch_add(1, Ordering::Relaxed);
+                return Some(Arc::new(candidates[turn % candidates.len()].clone()));
+            }
+        }
+        self.active()
+    }
+
+    fn active(&self) -> Option<Arc<GpuDevice>> {
+        self.active_device
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn bind(&self, device: GpuDevice) {
+        *self.active_device.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(device));
+    }
+
+    /// Get all available devices
+    pub fn available_devices(&self) -> &[GpuDevice] {
+        &self.devices
+    }
+
+    /// Check if GPU is available
+    pub fn is_gpu_available(&self) -> bool {
+        self.devices.iter().any(|d| d.backend != GpuBackend::Cpu)
+    }
+
+    /// Get available GPU backends
+    pub fn available_backends(&self) -> Vec<GpuBackend> {
+        self.devices
+            .iter()
+            .map(|d| d.backend)
+            .filter(|b| *b != GpuBackend::Cpu)
+            .collect()
+    }
+
+    /// Allocate GPU memory on the device `next_device` would return
+    pub fn allocate_memory(&self, size: u64) -> Result<GpuMemory, GpuError> {
+        let device = self.placement().ok_or(GpuError::NoDevicesAvailable)?;
+
+        if !device.has_memory(size) {
+            return Err(GpuError::OutOfMemory {
+                required: size,
+                available: device.available_memory,
+            });
+        }
+
+        // Actual allocation would happen here with CUDA/Metal bindings
+        Ok(GpuMemory {
+            size,
+            device,
+            ptr: std::ptr::null_mut(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_manager_cpu_only() {
+        let config = GpuConfig::cpu();
+        let manager = GpuManager::new(config).unwrap();
+
+        assert_eq!(manager.active_device().backend, GpuBackend::Cpu);
+    }
+}
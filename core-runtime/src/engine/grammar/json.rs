@@ -0,0 +1,212 @@
+//! Byte-level pushdown automaton over RFC 8259 JSON.
+//!
+//! Structure is ASCII, so the automaton steps one byte at a time; bytes of
+//! multi-byte UTF-8 characters are only valid inside strings.
+
+use super::MAX_JSON_DEPTH;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
+}
+
+/// Position inside a number literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Num {
+    Minus,
+    Zero,
+    Int,
+    Dot,
+    Frac,
+    Exp,
+    ExpSign,
+    ExpInt,
+}
+
+impl Num {
+    fn next(self, b: u8) -> Option<Num> {
+        let digit = b.is_ascii_digit();
+        match (self, b) {
+            (Num::Minus, b'0') => Some(Num::Zero),
+            (Num::Minus, _) if digit => Some(Num::Int),
+            (Num::Int, _) if digit => Some(Num::Int),
+            (Num::Zero | Num::Int, b'.') => Some(Num::Dot),
+            (Num::Dot | Num::Frac, _) if digit => Some(Num::Frac),
+            (Num::Zero | Num::Int | Num::Frac, b'e' | b'E') => Some(Num::Exp),
+            (Num::Exp, b'+' | b'-') => Some(Num::ExpSign),
+            (Num::Exp | Num::ExpSign | Num::ExpInt, _) if digit => Some(Num::ExpInt),
+            _ => None,
+        }
+    }
+
+    fn is_terminal(self) -> bool {
+        matches!(self, Num::Zero | Num::Int | Num::Frac | Num::ExpInt)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Json {
+    /// Expecting a value; `close` also allows `]` right after `[`.
+    Value { close: bool },
+    /// Expecting an object key; `close` also allows `}` right after `{`.
+    Key { close: bool },
+    /// Inside a string. `escape`: 0 none, 1 after `\`, 2..=5 hex digits left + 1.
+    Str { key: bool, escape: u8 },
+    Colon,
+    AfterValue,
+    Number(Num),
+    Literal { word: &'static [u8], at: usize },
+    Done,
+}
+
+fn is_ws(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct JsonMatcher {
+    stack: Vec<Container>,
+    state: Json,
+}
+
+impl JsonMatcher {
+    pub(super) fn new() -> Self {
+        Self {
+            stack: Vec::new(),
+            state: Json::Value { close: false },
+        }
+    }
+
+    /// Step over `b`; false if no valid document continues with it.
+    pub(super) fn feed(&mut self, b: u8) -> bool {
+        match self.state {
+            Json::Value { close } => self.value(b, close),
+            Json::Key { close } => match b {
+                _ if is_ws(b) => true,
+                b'"' => self.set(Json::Str { key: true, escape: 0 }),
+                b'}' if close => self.close(Container::Object),
+                _ => false,
+            },
+            Json::Str { key, escape } => self.string(b, key, escape),
+            Json::Colon => match b {
+                _ if is_ws(b) => true,
+                b':' => self.set(Json::Value { close: false }),
+                _ => false,
+            },
+            Json::AfterValue => self.after_value(b),
+            Json::Number(num) => match num.next(b) {
+                Some(next) => self.set(Json::Number(next)),
+                None if num.is_terminal() => {
+                    self.state = self.value_done();
+                    self.feed(b)
+                }
+                None => false,
+            },
+            Json::Literal { word, at } => self.literal(b, word, at),
+            Json::Done => is_ws(b),
+        }
+    }
+
+    pub(super) fn is_complete(&self) -> bool {
+        match self.state {
+            Json::Done => true,
+            Json::Number(num) => self.stack.is_empty() && num.is_terminal(),
+            _ => false,
+        }
+    }
+
+    fn value(&mut self, b: u8, close: bool) -> bool {
+        let literal = |word| Json::Literal { word, at: 1 };
+        match b {
+            _ if is_ws(b) => true,
+            b'{' => self.open(Container::Object, Json::Key { close: true }),
+            b'[' => self.open(Container::Array, Json::Value { close: true }),
+            b'"' => self.set(Json::Str { key: false, escape: 0 }),
+            b'-' => self.set(Json::Number(Num::Minus)),
+            b'0' => self.set(Json::Number(Num::Zero)),
+            b'1'..=b'9' => self.set(Json::Number(Num::Int)),
+            b't' => self.set(literal(b"true")),
+            b'f' => self.set(literal(b"false")),
+            b'n' => self.set(literal(b"null")),
+            b']' if close => self.close(Container::Array),
+            _ => false,
+        }
+    }
+
+    fn string(&mut self, b: u8, key: bool, escape: u8) -> bool {
+        match (escape, b) {
+            (0, b'"') => {
+                self.state = if key { Json::Colon } else { self.value_done() };
+                true
+            }
+            (0, b'\\') => self.set(Json::Str { key, escape: 1 }),
+            (0, b) => b >= 0x20,
+            (1, b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                self.set(Json::Str { key, escape: 0 })
+            }
+            (1, b'u') => self.set(Json::Str { key, escape: 5 }),
+            // Reject U+D000..U+DFFF so lone surrogates can never appear
+            (5, b'd' | b'D') => false,
+            (2..=5, b) if b.is_ascii_hexdigit() => {
+                let escape = if escape == 2 { 0 } else { escape - 1 };
+                self.set(Json::Str { key, escape })
+            }
+            _ => false,
+        }
+    }
+
+    fn after_value(&mut self, b: u8) -> bool {
+        match (b, self.stack.last()) {
+            _ if is_ws(b) => true,
+            (b',', Some(Container::Object)) => self.set(Json::Key { close: false }),
+            (b',', Some(Container::Array)) => self.set(Json::Value { close: false }),
+            (b'}', _) => self.close(Container::Object),
+            (b']', _) => self.close(Container::Array),
+            _ => false,
+        }
+    }
+
+    fn literal(&mut self, b: u8, word: &'static [u8], at: usize) -> bool {
+        if word[at] != b {
+            return false;
+        }
+        self.state = if at + 1 == word.len() {
+            self.value_done()
+        } else {
+            Json::Literal { word, at: at + 1 }
+        };
+        true
+    }
+
+    fn set(&mut self, state: Json) -> bool {
+        self.state = state;
+        true
+    }
+
+    fn open(&mut self, container: Container, state: Json) -> bool {
+        if self.stack.len() >= MAX_JSON_DEPTH {
+            return false;
+        }
+        self.stack.push(container);
+        self.set(state)
+    }
+
+    fn close(&mut self, container: Container) -> bool {
+        if self.stack.last() != Some(&container) {
+            return false;
+        }
+        self.stack.pop();
+        self.state = self.value_done();
+        true
+    }
+
+    /// State after a complete value at the current depth.
+    fn value_done(&self) -> Json {
+        if self.stack.is_empty() {
+            Json::Done
+        } else {
+            Json::AfterValue
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Grammar-constrained decoding.
+//!
+//! A [`GrammarState`] tracks how much of a [`GrammarConstraint`] the output
+//! has satisfied and masks every token whose bytes would break it, so the
+//! sampler can only pick structurally valid continuations. Token bytes come
+//! from the tokenizer vocabulary, indexed once as a [`TokenVocab`] trie; EOS
+//! is allowed only once the output is a complete match.
+//!
+//! The JSON matcher is a byte-level pushdown automaton over RFC 8259
+//! (`json`). Regex constraints compile to an anchored DFA and must match the
+//! whole output (`pattern`).
+
+mod json;
+mod pattern;
+mod vocab;
+
+use std::sync::Arc;
+
+use super::error::InferenceError;
+use json::JsonMatcher;
+use pattern::RegexMatcher;
+
+pub use vocab::TokenVocab;
+
+/// Maximum JSON nesting depth (serde_json's default recursion limit).
+pub const MAX_JSON_DEPTH: usize = 128;
+
+/// Structure the generated text must follow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarConstraint {
+    /// A single JSON value, optionally surrounded by whitespace.
+    Json,
+    /// Text fully matching this regular expression.
+    Regex(String),
+}
+
+/// Decoding state for a [`GrammarConstraint`] over a fixed vocabulary.
+///
+/// Masking looks one token ahead, so the vocabulary must be able to finish
+/// any valid prefix; vocabularies with single-character tokens always can.
+#[derive(Clone)]
+pub struct GrammarState {
+    matcher: Matcher,
+    vocab: Arc<TokenVocab>,
+    eos_token: u32,
+}
+
+impl GrammarState {
+    /// Start matching `constraint`. `vocab[id]` is the text of token `id`.
+    pub fn new(
+        constraint: &GrammarConstraint,
+        vocab: Arc<[String]>,
+        eos_token: u32,
+    ) -> Result<Self, InferenceError> {
+        let vocab = Arc::new(TokenVocab::new(vocab.iter()));
+        Self::with_vocab(constraint, vocab, eos_token)
+    }
+
+    /// Start matching `constraint` over a prebuilt vocabulary trie.
+    pub fn with_vocab(
+        constraint: &GrammarConstraint,
+        vocab: Arc<TokenVocab>,
+        eos_token: u32,
+    ) -> Result<Self, InferenceError> {
+        let matcher = match constraint {
+            GrammarConstraint::Json => Matcher::Json(JsonMatcher::new()),
+            GrammarConstraint::Regex(pattern) => Matcher::Regex(RegexMatcher::new(pattern)?),
+        };
+        Ok(Self {
+            matcher,
+            vocab,
+            eos_token,
+        })
+    }
+
+    /// Whether sampling `token` next keeps the output valid.
+    pub fn allows(&self, token: u32) -> bool {
+        if token == self.eos_token {
+            return self.matcher.is_complete();
+        }
+        match self.vocab.piece(token) {
+            Some(piece) if !piece.is_empty() => {
+                let mut matcher = self.matcher.clone();
+                piece.iter().all(|&b| matcher.feed(b))
+            }
+            _ => false,
+        }
+    }
+
+    /// Which of the first `n` token IDs may be sampled next.
+    ///
+    /// Walks the vocabulary trie once, stepping a copy of the matcher per
+    /// viable edge, so tokens sharing a prefix share its work and a rejected
+    /// prefix prunes every token below it.
+    pub fn allowed(&self, n: usize) -> Vec<bool> {
+        let mut allowed = vec![false; n];
+        let mut pending = vec![(0u32, self.matcher.clone())];
+        while let Some((node, matcher)) = pending.pop() {
+            for &(byte, child) in self.vocab.children(node) {
+                let mut next = matcher.clone();
+                if !next.feed(byte) {
+                    continue;
+                }
+                for &token in self.vocab.tokens(child) {
+                    if let Some(slot) = allowed.get_mut(token as usize) {
+                        *slot = true;
+                    }
+                }
+                pending.push((child, next));
+            }
+        }
+        if let Some(slot) = allowed.get_mut(self.eos_token as usize) {
+            *slot = self.matcher.is_complete();
+        }
+        allowed
+    }
+
+    /// Set every disallowed token's logit to `f32::NEG_INFINITY`.
+    /// Returns how many tokens remain allowed.
+    pub fn mask_logits(&self, logits: &mut [f32]) -> usize {
+        let allowed = self.allowed(logits.len());
+        for (logit, allowed) in logits.iter_mut().zip(&allowed) {
+            if !allowed {
+                *logit = f32::NEG_INFINITY;
+            }
+        }
+        allowed.iter().filter(|&&allowed| allowed).count()
+    }
+
+    /// Advance past a sampled token. Fails if the token is not allowed,
+    /// which only happens when masking left nothing to sample.
+    pub fn accept(&mut self, token: u32) -> Result<(), InferenceError> {
+        if !self.allows(token) {
+            return Err(InferenceError::ModelError(format!(
+                "token {} violates grammar constraint",
+                token
+            )));
+        }
+        if let Some(piece) = self.vocab.piece(token).filter(|_| token != self.eos_token) {
+            for &b in piece {
+                self.matcher.feed(b);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the output so far is a complete match.
+    pub fn is_complete(&self) -> bool {
+        self.matcher.is_complete()
+    }
+}
+
+impl std::fmt::Debug for GrammarState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrammarState")
+            .field("vocab_size", &self.vocab.len())
+            .field("eos_token", &self.eos_token)
+            .field("complete", &self.is_complete())
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+enum Matcher {
+    Json(JsonMatcher),
+    Regex(RegexMatcher),
+}
+
+impl Matcher {
+    fn feed(&mut self, b: u8) -> bool {
+        match self {
+            Self::Json(m) => m.feed(b),
+            Self::Regex(m) => m.feed(b),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        match self {
+            Self::Json(m) => m.is_complete(),
+            Self::Regex(m) => m.is_complete(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_state_masks_tokens_and_gates_eos() {
+        let vocab: Arc<[String]> = ["a", "b", "ab", "c", ""].map(String::from).into();
+        let eos = 5;
+        let mut state =
+            GrammarState::new(&GrammarConstraint::Regex("a+b".into()), vocab, eos).unwrap();
+
+        let mut logits = vec![0.0; 6];
+        assert_eq!(state.mask_logits(&mut logits), 2);
+        assert_eq!(logits[1], f32::NEG_INFINITY);
+        assert!(!state.allows(eos));
+
+        state.accept(2).unwrap();
+        assert!(state.is_complete());
+        assert!(state.allows(eos));
+        assert!(!state.allows(0));
+        assert!(state.accept(3).is_err());
+    }
+
+    #[test]
+    fn trie_mask_matches_per_token_checks() {
+        let vocab: Arc<[String]> = ["{", "{\"", "\"a\"", "\"a\":", ":", "1", "}", "1}", "é"]
+            .map(String::from)
+            .into();
+        let eos = vocab.len() as u32;
+        let mut state = GrammarState::new(&GrammarConstraint::Json, vocab, eos).unwrap();
+        for token in [1, 8] {
+            let allowed = state.allowed(eos as usize + 1);
+            for (id, &ok) in allowed.iter().enumerate() {
+                assert_eq!(ok, state.allows(id as u32), "token {}", id);
+            }
+            state.accept(token).unwrap();
+        }
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        let vocab: Arc<[String]> = Vec::new().into();
+        let err = GrammarState::new(&GrammarConstraint::Regex("(".into()), vocab, 0).unwrap_err();
+        assert!(err.to_string().contains("invalid grammar regex"), "{}", err);
+    }
+}
@@ -0,0 +1,109 @@
+//! Anchored-DFA matcher for regex constraints.
+//!
+//! DFA states that can no longer reach a match are found up front, since
+//! the DFA itself only reports a failed match one byte late.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use regex_automata::dfa::{dense, Automaton, StartKind};
+use regex_automata::util::primitives::StateID;
+use regex_automata::util::start;
+use regex_automata::{Anchored, MatchKind};
+
+use crate::engine::error::InferenceError;
+
+/// Cap on compiled regex DFA size, in bytes.
+const MAX_REGEX_DFA_BYTES: usize = 4 << 20;
+
+#[derive(Clone)]
+pub(super) struct RegexMatcher {
+    dfa: Arc<dense::DFA<Vec<u32>>>,
+    /// States from which some continuation fully matches.
+    live: Arc<HashSet<StateID>>,
+    state: StateID,
+}
+
+impl RegexMatcher {
+    pub(super) fn new(pattern: &str) -> Result<Self, InferenceError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            InferenceError::InputValidation(format!("invalid grammar regex: {}", e))
+        };
+        let dfa = dense::DFA::builder()
+            .configure(
+                dense::DFA::config()
+                    .start_kind(StartKind::Anchored)
+                    .match_kind(MatchKind::All)
+                    .dfa_size_limit(Some(MAX_REGEX_DFA_BYTES)),
+            )
+            .build(pattern)
+            .map_err(|e| invalid(&e))?;
+        let state = dfa
+            .start_state(&start::Config::new().anchored(Anchored::Yes))
+            .map_err(|e| invalid(&e))?;
+        let live = live_states(&dfa, state);
+        Ok(Self {
+            dfa: Arc::new(dfa),
+            live: Arc::new(live),
+            state,
+        })
+    }
+
+    /// Step over `byte`; false once no continuation can match.
+    pub(super) fn feed(&mut self, byte: u8) -> bool {
+        self.state = self.dfa.next_state(self.state, byte);
+        self.live.contains(&self.state)
+    }
+
+    pub(super) fn is_complete(&self) -> bool {
+        self.dfa.is_match_state(self.dfa.next_eoi_state(self.state))
+    }
+}
+
+/// States reachable from `start` that can still reach a full match.
+fn live_states(dfa: &dense::DFA<Vec<u32>>, start: StateID) -> HashSet<StateID> {
+    let (states, incoming) = reachable(dfa, start);
+    let mut live: Vec<bool> = states
+        .iter()
+        .map(|&s| dfa.is_match_state(dfa.next_eoi_state(s)))
+        .collect();
+    let mut pending: Vec<usize> = (0..states.len()).filter(|&i| live[i]).collect();
+    while let Some(to) = pending.pop() {
+        for &from in &incoming[to] {
+            if !live[from] {
+                live[from] = true;
+                pending.push(from);
+            }
+        }
+    }
+    states
+        .into_iter()
+        .zip(live)
+        .filter_map(|(state, live)| live.then_some(state))
+        .collect()
+}
+
+/// States reachable from `start`, and for each the indices of the states
+/// with a transition into it.
+fn reachable(dfa: &dense::DFA<Vec<u32>>, start: StateID) -> (Vec<StateID>, Vec<Vec<usize>>) {
+    let mut index = HashMap::from([(start, 0usize)]);
+    let mut states = vec![start];
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new()];
+    let mut next = 0;
+    while next < states.len() {
+        let from = states[next];
+        if !dfa.is_dead_state(from) && !dfa.is_quit_state(from) {
+            for byte in 0..=255u8 {
+                let to = dfa.next_state(from, byte);
+                let to = *index.entry(to).or_insert_with(|| {
+                    states.push(to);
+                    incoming.push(Vec::new());
+                    states.len() - 1
+                });
+                incoming[to].push(next);
+            }
+        }
+        next += 1;
+    }
+    (states, incoming)
+}
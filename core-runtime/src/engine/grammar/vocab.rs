@@ -0,0 +1,104 @@
+//! Byte trie over a tokenizer vocabulary.
+//!
+//! Masking walks the trie instead of every token: a byte shared by many
+//! tokens is fed to the matcher once, and a prefix the grammar rejects
+//! prunes every token below it.
+
+/// One trie node: outgoing byte edges and the tokens spelled by its path.
+#[derive(Debug, Default)]
+struct Node {
+    /// `(byte, child)` edges, sorted by byte.
+    children: Vec<(u8, u32)>,
+    tokens: Vec<u32>,
+}
+
+/// A vocabulary's token bytes, indexed as a trie. Build once per model and
+/// share it across requests.
+#[derive(Debug)]
+pub struct TokenVocab {
+    pieces: Vec<Box<[u8]>>,
+    nodes: Vec<Node>,
+}
+
+impl TokenVocab {
+    /// Index `pieces`, where `pieces[id]` is the bytes of token `id`.
+    pub fn new<I, P>(pieces: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let pieces: Vec<Box<[u8]>> = pieces.into_iter().map(|p| p.as_ref().into()).collect();
+        let mut nodes = vec![Node::default()];
+        for (token, piece) in pieces.iter().enumerate() {
+            // Empty tokens would never advance the grammar; leave them out
+            if piece.is_empty() {
+                continue;
+            }
+            let mut node = 0;
+            for &byte in piece.iter() {
+                node = child(&mut nodes, node, byte);
+            }
+            nodes[node].tokens.push(token as u32);
+        }
+        Self { pieces, nodes }
+    }
+
+    /// Number of tokens in the vocabulary.
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// Bytes of `token`, or None past the end of the vocabulary.
+    pub fn piece(&self, token: u32) -> Option<&[u8]> {
+        self.pieces.get(token as usize).map(|p| &p[..])
+    }
+
+    /// Edges out of `node`.
+    pub(super) fn children(&self, node: u32) -> &[(u8, u32)] {
+        &self.nodes[node as usize].children
+    }
+
+    /// Tokens spelled by the path to `node`.
+    pub(super) fn tokens(&self, node: u32) -> &[u32] {
+        &self.nodes[node as usize].tokens
+    }
+}
+
+/// Child of `node` along `byte`, created if missing.
+fn child(nodes: &mut Vec<Node>, node: usize, byte: u8) -> usize {
+    let edges = &nodes[node].children;
+    match edges.binary_search_by_key(&byte, |&(b, _)| b) {
+        Ok(i) => edges[i].1 as usize,
+        Err(i) => {
+            let id = nodes.len();
+            nodes[node].children.insert(i, (byte, id as u32));
+            nodes.push(Node::default());
+            id
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_prefixes_share_nodes() {
+        let vocab = TokenVocab::new(["ab", "a", "abc", "", "b"]);
+        assert_eq!(vocab.len(), 5);
+        assert_eq!(vocab.piece(2), Some(&b"abc"[..]));
+        assert_eq!(vocab.piece(5), None);
+
+        let root = vocab.children(0);
+        assert_eq!(root.iter().map(|&(b, _)| b).collect::<Vec<_>>(), b"ab");
+        let a = root[0].1;
+        assert_eq!(vocab.tokens(a), &[1]);
+        let ab = vocab.children(a)[0].1;
+        assert_eq!(vocab.tokens(ab), &[0]);
+        assert!(vocab.tokens(0).is_empty(), "empty tokens are never indexed");
+    }
+}
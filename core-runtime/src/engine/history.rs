@@ -0,0 +1,143 @@
+//! Bounded token history for long generations.
+//!
+//! Repetition penalty and stop-sequence matching only need recent tokens.
+//! `TokenHistory` keeps the last `window` tokens in a fixed-capacity ring,
+//! so memory stays flat no matter how many tokens are generated.
+//!
+//! Tokens older than the window are forgotten: repetition penalty will not
+//! discourage a phrase whose previous occurrence fell out of the window.
+
+use std::collections::VecDeque;
+
+/// Default number of recent tokens retained (matches llama.cpp's default).
+pub const DEFAULT_HISTORY_WINDOW: usize = 64;
+
+/// Sliding window over the most recent generated tokens.
+#[derive(Debug, Clone)]
+pub struct TokenHistory {
+    window: usize,
+    tokens: VecDeque<u32>,
+}
+
+impl TokenHistory {
+    /// Create a history retaining at most `window` tokens (minimum 1).
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            tokens: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Record a token, evicting the oldest if the window is full.
+    pub fn push(&mut self, token: u32) {
+        if self.tokens.len() == self.window {
+            self.tokens.pop_front();
+        }
+        self.tokens.push_back(token);
+    }
+
+    /// Maximum number of tokens retained.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Number of tokens currently retained.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Check if no tokens have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Allocated capacity in tokens (bounded by the window).
+    pub fn capacity(&self) -> usize {
+        self.tokens.capacity()
+    }
+
+    /// Iterate retained tokens from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.tokens.iter().copied()
+    }
+
+    /// Count occurrences of `token` within the window.
+    pub fn count(&self, token: u32) -> usize {
+        self.tokens.iter().filter(|&&t| t == token).count()
+    }
+
+    /// Check whether the retained tokens end with `suffix`.
+    ///
+    /// Suffixes longer than the window never match.
+    pub fn ends_with(&self, suffix: &[u32]) -> bool {
+        if suffix.len() > self.tokens.len() {
+            return false;
+        }
+        let start = self.tokens.len() - suffix.len();
+        self.tokens.range(start..).copied().eq(suffix.iter().copied())
+    }
+
+    /// Forget all retained tokens, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.tokens.clear();
+    }
+}
+
+impl Default for TokenHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_memory_stays_bounded() {
+        let mut history = TokenHistory::new(128);
+        let initial_capacity = history.capacity();
+
+        for i in 0..1_000_000u32 {
+            history.push(i);
+        }
+
+        assert_eq!(history.len(), 128);
+        assert_eq!(history.capacity(), initial_capacity);
+        assert_eq!(history.iter().next(), Some(1_000_000 - 128));
+        assert_eq!(history.iter().last(), Some(999_999));
+    }
+
+    #[test]
+    fn history_evicts_oldest() {
+        let mut history = TokenHistory::new(3);
+        for t in [1, 2, 3, 4] {
+            history.push(t);
+        }
+        assert_eq!(history.iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(history.count(1), 0);
+    }
+
+    #[test]
+    fn history_ends_with() {
+        let mut history = TokenHistory::new(4);
+        for t in [5, 6, 7, 8, 9] {
+            history.push(t);
+        }
+        assert!(history.ends_with(&[8, 9]));
+        assert!(history.ends_with(&[]));
+        assert!(!history.ends_with(&[7, 9]));
+        // Longer than the window: cannot match
+        assert!(!history.ends_with(&[5, 6, 7, 8, 9]));
+    }
+
+    #[test]
+    fn history_zero_window_clamped() {
+        let mut history = TokenHistory::new(0);
+        history.push(1);
+        history.push(2);
+        assert_eq!(history.window(), 1);
+        assert_eq!(history.iter().collect::<Vec<_>>(), vec![2]);
+    }
+}
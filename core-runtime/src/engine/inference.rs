@@ -2,14 +2,22 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
 use crate::engine::gguf::GgufModel;
-use crate::engine::{InferenceConfig, InferenceInput, InferenceOutput};
+use crate::engine::config::DEFAULT_TOP_P_FLOOR;
+use crate::engine::detokenize::Detokenizer;
+use crate::engine::embedding_cache::{EmbeddingCache, EmbeddingCacheConfig};
+use crate::engine::ping::PingCache;
+use crate::engine::{
+    ChatMessage, ChatTemplate, DecodeSignal, EmbeddingResult, ErrorCategory, InferenceConfig, InferenceInput,
+    InferenceOutput, OutputEncoding, SamplerKind, MAX_STOP_SEQUENCES, MAX_STOP_SEQUENCE_BYTES,
+};
 use crate::models::ModelHandle;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum InferenceError {
     #[error("Model not loaded: {0}")]
     ModelNotLoaded(String),
@@ -22,21 +30,121 @@ pub enum InferenceError {
 
     #[error("Context length exceeded: max {max}, got {got}")]
     ContextExceeded { max: usize, got: usize },
+
+    #[error("Resource limit: {0}")]
+    ResourceLimit(String),
+}
+
+impl InferenceError {
+    /// Classify this error for metrics and error codes.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ModelNotLoaded(_) | Self::InvalidParams(_) | Self::ContextExceeded { .. } => {
+                ErrorCategory::Client
+            }
+            Self::ExecutionFailed(_) => ErrorCategory::Model,
+            Self::ResourceLimit(_) => ErrorCategory::Infra,
+        }
+    }
 }
 
+/// Preserve the category of errors raised by the model itself.
+impl From<crate::engine::InferenceError> for InferenceError {
+    fn from(err: crate::engine::InferenceError) -> Self {
+        match err.category() {
+            ErrorCategory::Client => Self::InvalidParams(err.to_string()),
+            ErrorCategory::Model => Self::ExecutionFailed(err.to_string()),
+            ErrorCategory::Infra => Self::ResourceLimit(err.to_string()),
+        }
+    }
+}
+
+/// Fixed single-token input used by [`InferenceEngine::ping`].
+const PING_PROMPT: &str = "a";
+
 /// Parameters controlling inference behavior (IPC protocol).
+///
+/// `top_k == 0` disables top-k filtering. `top_p == 0.0` selects greedy
+/// decoding; other `top_p` values are raised to the engine's floor.
+/// When `min_p` is set it replaces `top_p` filtering.
+///
+/// Logits are adjusted in this order before filtering: `logit_bias`, the
+/// `no_repeat_ngram_size` ban, then `repetition_penalty`. Top-k, min-p/top-p
+/// and temperature then see the adjusted logits, so a banned token can never
+/// be selected.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InferenceParams {
     pub max_tokens: usize,
     pub temperature: f32,
     pub top_p: f32,
     pub top_k: usize,
+    /// Min-p sampling: keep tokens with probability >= `min_p * max_prob`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f32>,
     /// Enable token-by-token streaming response.
     #[serde(default)]
     pub stream: bool,
     /// Request timeout in milliseconds. None = no timeout.
     #[serde(default)]
     pub timeout_ms: Option<u64>,
+    /// Per-request CPU time cap in milliseconds. None = no cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_ms: Option<u64>,
+    /// How output is returned: lossy UTF-8 text (default) or exact bytes.
+    #[serde(default, skip_serializing_if = "OutputEncoding::is_utf8")]
+    pub output_encoding: OutputEncoding,
+    /// Bypass result caches for this request (always run the model).
+    #[serde(default)]
+    pub no_cache: bool,
+    /// Added to raw logits by token ID before top-k/top-p filtering.
+    /// `f32::NEG_INFINITY` (JSON `null`) bans the token.
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        deserialize_with = "deserialize_logit_bias"
+    )]
+    pub logit_bias: HashMap<u32, f32>,
+    /// Divides positive (multiplies negative) logits of tokens already seen in
+    /// the prompt or output. 1.0 disables the penalty.
+    #[serde(default = "default_repetition_penalty")]
+    pub repetition_penalty: f32,
+    /// Ban any token that would repeat an n-gram of this size from the prompt
+    /// or output. 0 disables the ban.
+    #[serde(default)]
+    pub no_repeat_ngram_size: usize,
+    /// Seed for the sampling RNG. The same seed, prompt, params and model
+    /// produce identical tokens; None draws a fresh seed per request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Halt once the output contains any of these strings. The matched
+    /// string and anything after it are not returned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+    /// Token sampling strategy. Defaults to top-k/top-p/temperature.
+    #[serde(default, skip_serializing_if = "SamplerKind::is_default")]
+    pub sampler: SamplerKind,
+}
+
+fn default_repetition_penalty() -> f32 {
+    1.0
+}
+
+/// Read `logit_bias`, mapping JSON `null` (how serde_json writes infinities)
+/// back to a ban. Keys arrive as strings once buffered by the tagged
+/// `IpcMessage` enum, so they are parsed here.
+fn deserialize_logit_bias<'de, D>(deserializer: D) -> Result<HashMap<u32, f32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: HashMap<String, Option<f32>> = serde::Deserialize::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(token, bias)| {
+            let token = token.parse::<u32>().map_err(|_| {
+                serde::de::Error::custom(format!("invalid logit_bias token id: {}", token))
+            })?;
+            Ok((token, bias.unwrap_or(f32::NEG_INFINITY)))
+        })
+        .collect()
 }
 
 impl Default for InferenceParams {
@@ -46,8 +154,18 @@ impl Default for InferenceParams {
             temperature: 0.7,
             top_p: 0.9,
             top_k: 40,
+            min_p: None,
             stream: false,
             timeout_ms: None,
+            max_cpu_ms: None,
+            output_encoding: OutputEncoding::Utf8,
+            no_cache: false,
+            logit_bias: HashMap::new(),
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: SamplerKind::Default,
         }
     }
 }
@@ -60,23 +178,92 @@ impl InferenceParams {
         if self.temperature < 0.0 {
             return Err(InferenceError::InvalidParams("temperature must be >= 0".into()));
         }
-        if self.top_p <= 0.0 || self.top_p > 1.0 {
-            return Err(InferenceError::InvalidParams("top_p must be in (0, 1]".into()));
+        if !(0.0..=1.0).contains(&self.top_p) {
+            return Err(InferenceError::InvalidParams("top_p must be in [0, 1]".into()));
+        }
+        if let Some(min_p) = self.min_p {
+            if !(min_p > 0.0 && min_p <= 1.0) {
+                return Err(InferenceError::InvalidParams("min_p must be in (0, 1]".into()));
+            }
+        }
+        if self.max_cpu_ms == Some(0) {
+            return Err(InferenceError::InvalidParams("max_cpu_ms must be > 0".into()));
+        }
+        if self
+            .logit_bias
+            .values()
+            .any(|bias| bias.is_nan() || *bias == f32::INFINITY)
+        {
+            return Err(InferenceError::InvalidParams(
+                "logit_bias values must be finite or -inf".into(),
+            ));
+        }
+        if !(self.repetition_penalty >= 1.0 && self.repetition_penalty.is_finite()) {
+            return Err(InferenceError::InvalidParams(
+                "repetition_penalty must be finite and >= 1.0".into(),
+            ));
+        }
+        if self.stop_sequences.len() > MAX_STOP_SEQUENCES {
+            return Err(InferenceError::InvalidParams(format!(
+                "at most {} stop_sequences allowed",
+                MAX_STOP_SEQUENCES
+            )));
         }
+        if self
+            .stop_sequences
+            .iter()
+            .any(|s| s.is_empty() || s.len() > MAX_STOP_SEQUENCE_BYTES)
+        {
+            return Err(InferenceError::InvalidParams(format!(
+                "stop_sequences must be non-empty and at most {} bytes",
+                MAX_STOP_SEQUENCE_BYTES
+            )));
+        }
+        self.sampler.validate()?;
         Ok(())
     }
 
-    /// Convert to internal InferenceConfig format.
+    /// Whether identical requests with these params always produce the same
+    /// output. Mirostat adapts its truncation as it samples, so only a seed
+    /// makes it reproducible.
+    pub fn is_reproducible(&self) -> bool {
+        let greedy = self.sampler.is_default() && self.temperature == 0.0;
+        !self.no_cache && (self.seed.is_some() || greedy)
+    }
+
+    /// Convert to internal InferenceConfig format with the default top-p floor.
     pub fn to_config(&self) -> InferenceConfig {
-        InferenceConfig {
+        self.to_config_with_floor(DEFAULT_TOP_P_FLOOR)
+    }
+
+    /// Convert to internal InferenceConfig format, normalizing sampling
+    /// parameters against `top_p_floor`.
+    pub fn to_config_with_floor(&self, top_p_floor: f32) -> InferenceConfig {
+        let mut config = InferenceConfig {
             max_tokens: Some(self.max_tokens as u32),
             temperature: self.temperature,
             top_p: self.top_p,
             top_k: self.top_k as u32,
-            repetition_penalty: 1.1,
+            min_p: self.min_p,
+            candidate_cap: crate::engine::sampling::DEFAULT_CANDIDATE_CAP,
+            repetition_penalty: self.repetition_penalty,
+            no_repeat_ngram_size: self.no_repeat_ngram_size,
+            history_window: crate::engine::DEFAULT_HISTORY_WINDOW,
             timeout_ms: self.timeout_ms.unwrap_or(30_000),
+            max_cpu_ms: self.max_cpu_ms,
             max_memory_bytes: None,
-        }
+            absolute_max_decode_steps: crate::engine::DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            decode_signal: Default::default(),
+            cancel: Default::default(),
+            logit_bias: self.logit_bias.iter().map(|(&id, &bias)| (id, bias)).collect(),
+            grammar: None,
+            seed: self.seed,
+            stop_sequences: self.stop_sequences.clone(),
+            sampler: self.sampler,
+        };
+        config.logit_bias.sort_unstable_by_key(|&(id, _)| id);
+        config.normalize_sampling(top_p_floor);
+        config
     }
 }
 
@@ -85,28 +272,90 @@ impl InferenceParams {
 pub struct InferenceResult {
     /// Generated text output.
     pub output: String,
+    /// Exact output bytes when `output` is a lossy UTF-8 conversion.
+    pub raw_output: Option<Vec<u8>>,
     pub tokens_generated: usize,
     pub finished: bool,
+    /// Time spent queued before the model ran (0 unless set by the caller).
+    pub queue_wait_ms: f64,
+    /// Time from model start until decoding began. Models that never mark
+    /// their decode signal report all model time here.
+    pub prefill_ms: f64,
+    /// Time spent decoding after prefill.
+    pub decode_ms: f64,
 }
 
 /// Executes model inference by delegating to registered models.
 pub struct InferenceEngine {
     max_context_length: usize,
+    /// Lower bound applied to non-zero top_p values.
+    top_p_floor: f32,
+    /// Hard cap on decode steps per request, whatever the params say.
+    absolute_max_decode_steps: u32,
+    /// Recent tokens the repetition penalty and n-gram ban look back over.
+    history_window: usize,
     /// Models indexed by model_id for lookup.
     models: Arc<RwLock<HashMap<String, Arc<dyn GgufModel>>>>,
     /// ModelHandle to model_id mapping.
     handle_to_id: Arc<RwLock<HashMap<u64, String>>>,
+    /// Vectors for recently embedded texts, per model.
+    embedding_cache: EmbeddingCache,
+    /// Chat templates set from model manifests, by model_id.
+    chat_templates: Arc<RwLock<HashMap<String, ChatTemplate>>>,
+    /// Detokenizers replacing the model's own decoding, by model_id.
+    detokenizers: Arc<RwLock<HashMap<String, Arc<dyn Detokenizer>>>>,
+    /// Recent `ping` outcomes, by model_id.
+    pings: PingCache,
 }
 
 impl InferenceEngine {
     pub fn new(max_context_length: usize) -> Self {
         Self {
             max_context_length,
+            top_p_floor: DEFAULT_TOP_P_FLOOR,
+            absolute_max_decode_steps: crate::engine::DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            history_window: crate::engine::DEFAULT_HISTORY_WINDOW,
             models: Arc::new(RwLock::new(HashMap::new())),
             handle_to_id: Arc::new(RwLock::new(HashMap::new())),
+            embedding_cache: EmbeddingCache::new(EmbeddingCacheConfig::default()),
+            chat_templates: Arc::new(RwLock::new(HashMap::new())),
+            detokenizers: Arc::new(RwLock::new(HashMap::new())),
+            pings: PingCache::default(),
         }
     }
 
+    /// Set the lower bound applied to non-zero top_p values.
+    pub fn with_top_p_floor(mut self, top_p_floor: f32) -> Self {
+        self.top_p_floor = top_p_floor;
+        self
+    }
+
+    /// Set the absolute decode-step cap applied to every request.
+    pub fn with_absolute_max_decode_steps(mut self, steps: u32) -> Self {
+        self.absolute_max_decode_steps = steps;
+        self
+    }
+
+    /// Set how many recent tokens repetition controls look back over.
+    pub fn with_history_window(mut self, history_window: usize) -> Self {
+        self.history_window = history_window;
+        self
+    }
+
+    /// Replace the embedding cache with one using `config`.
+    pub fn with_embedding_cache(mut self, config: EmbeddingCacheConfig) -> Self {
+        self.embedding_cache = EmbeddingCache::new(config);
+        self
+    }
+
+    /// Internal config for `params` under this engine's limits.
+    pub fn config_for(&self, params: &InferenceParams) -> InferenceConfig {
+        let mut config = params.to_config_with_floor(self.top_p_floor);
+        config.absolute_max_decode_steps = self.absolute_max_decode_steps;
+        config.history_window = self.history_window;
+        config
+    }
+
     /// Register a model for inference.
     pub async fn register_model(
         &self,
@@ -114,15 +363,90 @@ impl InferenceEngine {
         handle: ModelHandle,
         model: Arc<dyn GgufModel>,
     ) {
+        self.pings.invalidate(&model_id);
         self.models.write().await.insert(model_id.clone(), model);
         self.handle_to_id.write().await.insert(handle.id(), model_id);
     }
 
+    /// Render chat requests for `model_id` with `template` from now on.
+    ///
+    /// Set from the model's manifest when it is loaded or swapped in; kept
+    /// across swaps without one and cleared when the model is unregistered.
+    pub async fn set_chat_template(&self, model_id: &str, template: ChatTemplate) {
+        self.chat_templates
+            .write()
+            .await
+            .insert(model_id.to_string(), template);
+    }
+
+    /// Decode `model_id`'s tokens with `detokenizer` from now on, for
+    /// vocabularies such as byte-level BPE whose spacing the model's own
+    /// decoding gets wrong. Cleared when the model is unregistered.
+    pub async fn set_detokenizer(&self, model_id: &str, detokenizer: Arc<dyn Detokenizer>) {
+        self.detokenizers
+            .write()
+            .await
+            .insert(model_id.to_string(), detokenizer);
+    }
+
+    /// Render `messages` into the prompt `model_id` expects, using the
+    /// default role tags when no template was set for it.
+    pub async fn render_chat(
+        &self,
+        model_id: &str,
+        messages: &[ChatMessage],
+    ) -> Result<String, InferenceError> {
+        crate::engine::input::validate_messages(messages)?;
+        let templates = self.chat_templates.read().await;
+        Ok(match templates.get(model_id) {
+            Some(template) => template.apply(messages),
+            None => ChatTemplate::default().apply(messages),
+        })
+    }
+
     /// Unregister a model.
     pub async fn unregister_model(&self, model_id: &str) {
         self.models.write().await.remove(model_id);
+        self.chat_templates.write().await.remove(model_id);
+        self.detokenizers.write().await.remove(model_id);
+        self.pings.invalidate(model_id);
         let mut handles = self.handle_to_id.write().await;
-        handles.retain(|_, v| v != model_id);
+        handles.retain(|&handle, v| {
+            let keep = v != model_id;
+            if !keep {
+                self.embedding_cache.invalidate_model(ModelHandle::new(handle));
+            }
+            keep
+        });
+    }
+
+    /// Atomically replace the model served as `model_id` with `model`
+    /// under `new_handle`, returning the handle it replaced.
+    ///
+    /// Requests only hold the model map while looking a model up, so this
+    /// never waits for a generation: requests already running finish on
+    /// the old model and every lookup after this returns sees the new one.
+    pub async fn swap_model(
+        &self,
+        model_id: &str,
+        new_handle: ModelHandle,
+        model: Arc<dyn GgufModel>,
+    ) -> Option<ModelHandle> {
+        // Both maps change together so no lookup sees a half-done swap
+        let mut handles = self.handle_to_id.write().await;
+        let mut models = self.models.write().await;
+        let old_handle = handles
+            .iter()
+            .find(|(_, id)| id.as_str() == model_id)
+            .map(|(&handle, _)| ModelHandle::new(handle));
+        if let Some(old) = old_handle {
+            handles.remove(&old.id());
+            self.embedding_cache.invalidate_model(old);
+        }
+        handles.insert(new_handle.id(), model_id.to_string());
+        models.insert(model_id.to_string(), model);
+        self.pings.invalidate(model_id);
+        old_handle
     }
 
     /// Run inference on text prompt using the specified model.
@@ -131,14 +455,27 @@ impl InferenceEngine {
         model_id: &str,
         prompt: &str,
         params: &InferenceParams,
+    ) -> Result<InferenceResult, InferenceError> {
+        self.run_with_signal(model_id, prompt, params, DecodeSignal::default())
+            .await
+    }
+
+    /// Like [`run`](Self::run); the model marks `decode_signal` when
+    /// prefill ends.
+    pub async fn run_with_signal(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: &InferenceParams,
+        decode_signal: DecodeSignal,
     ) -> Result<InferenceResult, InferenceError> {
         params.validate()?;
 
-        // Look up model by ID
-        let models = self.models.read().await;
-        let model = models.get(model_id).ok_or_else(|| {
-            InferenceError::ModelNotLoaded(model_id.to_string())
-        })?;
+        // Hold the model, not the map, so a swap need not wait for this run
+        let model = self
+            .model(model_id)
+            .await
+            .ok_or_else(|| InferenceError::ModelNotLoaded(model_id.to_string()))?;
 
         // Check context length (approximate by bytes)
         if prompt.len() > self.max_context_length {
@@ -148,21 +485,39 @@ impl InferenceEngine {
             });
         }
 
+        // Attach a signal when the caller didn't, so prefill can be timed
+        let decode_signal = if decode_signal.is_attached() {
+            decode_signal
+        } else {
+            DecodeSignal::attached()
+        };
+
         // Convert params to internal config
-        let config = params.to_config();
+        let config = InferenceConfig {
+            decode_signal: decode_signal.clone(),
+            ..self.config_for(params)
+        };
         let input = InferenceInput::Text(prompt.to_string());
 
         // Delegate to actual model
-        let output = model.infer(&input, &config).await.map_err(|e| {
-            InferenceError::ExecutionFailed(e.to_string())
-        })?;
+        let started = Instant::now();
+        let output = model.infer(&input, &config).await?;
+        let elapsed = started.elapsed();
+        let prefill = decode_signal
+            .decode_started()
+            .map_or(elapsed, |decode_start| decode_start.saturating_duration_since(started))
+            .min(elapsed);
 
         // Extract generation result
         match output {
             InferenceOutput::Generation(gen) => Ok(InferenceResult {
                 output: gen.text,
+                raw_output: gen.raw_bytes,
                 tokens_generated: gen.tokens_generated as usize,
                 finished: true,
+                queue_wait_ms: 0.0,
+                prefill_ms: prefill.as_secs_f64() * 1000.0,
+                decode_ms: (elapsed - prefill).as_secs_f64() * 1000.0,
             }),
             _ => Err(InferenceError::ExecutionFailed(
                 "Model returned non-generation output".into(),
@@ -170,6 +525,60 @@ impl InferenceEngine {
         }
     }
 
+    /// Embed `text` with the specified model.
+    ///
+    /// Duplicate texts within the cache TTL are served from the embedding
+    /// cache without running the model.
+    pub async fn embed(&self, model_id: &str, text: &str) -> Result<EmbeddingResult, InferenceError> {
+        self.embed_with_params(model_id, text, &InferenceParams::default())
+            .await
+    }
+
+    /// Like [`embed`](Self::embed); `params.no_cache` skips the embedding cache.
+    pub async fn embed_with_params(
+        &self,
+        model_id: &str,
+        text: &str,
+        params: &InferenceParams,
+    ) -> Result<EmbeddingResult, InferenceError> {
+        if text.len() > self.max_context_length {
+            return Err(InferenceError::ContextExceeded {
+                max: self.max_context_length,
+                got: text.len(),
+            });
+        }
+        let model = self
+            .model(model_id)
+            .await
+            .ok_or_else(|| InferenceError::ModelNotLoaded(model_id.to_string()))?;
+        let handle = self
+            .get_handle(model_id)
+            .await
+            .filter(|_| !params.no_cache);
+        if let Some(cached) = handle.and_then(|h| self.embedding_cache.get(h, text)) {
+            return Ok(cached);
+        }
+
+        let input = InferenceInput::Text(text.to_string());
+        let output = model.infer(&input, &InferenceConfig::default()).await?;
+        match output {
+            InferenceOutput::Embedding(result) => {
+                if let Some(handle) = handle {
+                    self.embedding_cache.insert(handle, text, result.clone());
+                }
+                Ok(result)
+            }
+            _ => Err(InferenceError::ExecutionFailed(
+                "Model returned non-embedding output".into(),
+            )),
+        }
+    }
+
+    /// Embedding cache shared by all `embed` calls.
+    pub fn embedding_cache(&self) -> &EmbeddingCache {
+        &self.embedding_cache
+    }
+
     /// Run inference by handle (legacy API compatibility).
     pub async fn run_by_handle(
         &self,
@@ -177,22 +586,76 @@ impl InferenceEngine {
         prompt: &str,
         params: &InferenceParams,
     ) -> Result<InferenceResult, InferenceError> {
-        let handles = self.handle_to_id.read().await;
-        let model_id = handles.get(&handle.id()).ok_or_else(|| {
-            InferenceError::ModelNotLoaded(format!("handle {}", handle.id()))
-        })?;
-        self.run(model_id, prompt, params).await
+        let model_id = self
+            .model_id_for(handle)
+            .await
+            .ok_or_else(|| InferenceError::ModelNotLoaded(format!("handle {}", handle.id())))?;
+        self.run(&model_id, prompt, params).await
+    }
+
+    /// The model_id served under `handle`.
+    pub async fn model_id_for(&self, handle: ModelHandle) -> Option<String> {
+        self.handle_to_id.read().await.get(&handle.id()).cloned()
     }
 
     pub fn max_context_length(&self) -> usize {
         self.max_context_length
     }
 
+    pub fn top_p_floor(&self) -> f32 {
+        self.top_p_floor
+    }
+
+    pub fn absolute_max_decode_steps(&self) -> u32 {
+        self.absolute_max_decode_steps
+    }
+
+    pub fn history_window(&self) -> usize {
+        self.history_window
+    }
+
+    /// Run one forward step on a fixed 1-token input, discarding the output.
+    ///
+    /// Proves tokenizer, weights, and sampler are servable without the
+    /// multi-token cost of warmup. Returns elapsed milliseconds. Outcomes are
+    /// cached, so a model is stepped at most once per `DEFAULT_PING_TTL`.
+    pub async fn ping(&self, model_id: &str) -> Result<u64, InferenceError> {
+        // Cloned out so the step runs without holding the model map
+        let model = self
+            .model(model_id)
+            .await
+            .ok_or_else(|| InferenceError::ModelNotLoaded(model_id.to_string()))?;
+        self.pings
+            .get_or_probe(model_id, async move {
+                let config = InferenceConfig {
+                    max_tokens: Some(1),
+                    temperature: 0.0,
+                    ..Default::default()
+                };
+                let start = std::time::Instant::now();
+                model
+                    .infer(&InferenceInput::Text(PING_PROMPT.to_string()), &config)
+                    .await?;
+                Ok(start.elapsed().as_millis() as u64)
+            })
+            .await
+    }
+
+    /// IDs of all registered models.
+    pub async fn model_ids(&self) -> Vec<String> {
+        self.models.read().await.keys().cloned().collect()
+    }
+
     /// Check if a model is registered.
     pub async fn has_model(&self, model_id: &str) -> bool {
         self.models.read().await.contains_key(model_id)
     }
 
+    /// Look up a registered model by ID.
+    pub(crate) async fn model(&self, model_id: &str) -> Option<Arc<dyn GgufModel>> {
+        self.models.read().await.get(model_id).cloned()
+    }
+
     /// Get the ModelHandle for a model_id (for metrics attribution).
     pub async fn get_handle(&self, model_id: &str) -> Option<ModelHandle> {
         let handles = self.handle_to_id.read().await;
@@ -204,6 +667,32 @@ impl InferenceEngine {
         None
     }
 
+    /// Raw bytes of `tokens` as produced by `model_id`'s vocabulary.
+    ///
+    /// Streaming clients decode incrementally from this, since a single
+    /// token can end partway through a UTF-8 sequence. A detokenizer set
+    /// for the model takes precedence over the model's own decoding.
+    pub async fn detokenize_bytes(
+        &self,
+        model_id: &str,
+        tokens: &[u32],
+    ) -> Result<Vec<u8>, InferenceError> {
+        if let Some(detokenizer) = self.detokenizers.read().await.get(model_id) {
+            return detokenizer
+                .decode_bytes(tokens)
+                .map_err(|e| InferenceError::ExecutionFailed(e.to_string()));
+        }
+        let model = self
+            .model(model_id)
+            .await
+            .ok_or_else(|| InferenceError::ModelNotLoaded(model_id.to_string()))?;
+        model.detokenize_bytes(tokens).unwrap_or_else(|| {
+            Err(InferenceError::ExecutionFailed(
+                "model does not support detokenization".into(),
+            ))
+        })
+    }
+
     /// Run streaming inference, sending tokens to the provided sender.
     ///
     /// This method looks up the model, downcasts to GgufGenerator, and calls
@@ -220,8 +709,7 @@ impl InferenceEngine {
 
         // Get runtime handle for async model lookup
         let rt = tokio::runtime::Handle::current();
-        let models = rt.block_on(self.models.read());
-        let model = models.get(model_id).ok_or_else(|| {
+        let model = rt.block_on(self.model(model_id)).ok_or_else(|| {
             InferenceError::ModelNotLoaded(model_id.to_string())
         })?;
 
@@ -231,7 +719,7 @@ impl InferenceEngine {
         })?;
 
         generator.generate_stream(prompt, config, sender)
-            .map_err(|e| InferenceError::ExecutionFailed(e.to_string()))
+            .map_err(InferenceError::from)
     }
 }
 
@@ -263,10 +751,33 @@ mod tests {
         assert!(params.validate().is_err());
     }
 
+    #[test]
+    fn logit_bias_ban_round_trips_through_json() {
+        let params = InferenceParams {
+            logit_bias: HashMap::from([(2, f32::NEG_INFINITY), (7, 1.5)]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        assert!(json.contains(r#""2":null"#), "{}", json);
+
+        let decoded: InferenceParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.logit_bias, params.logit_bias);
+        assert_eq!(
+            decoded.to_config().logit_bias,
+            vec![(2, f32::NEG_INFINITY), (7, 1.5)]
+        );
+
+        let invalid = InferenceParams {
+            logit_bias: HashMap::from([(2, f32::NAN)]),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
     #[test]
     fn inference_params_rejects_invalid_top_p() {
         let params = InferenceParams {
-            top_p: 0.0,
+            top_p: -0.1,
             ..Default::default()
         };
         assert!(params.validate().is_err());
@@ -276,6 +787,104 @@ mod tests {
             ..Default::default()
         };
         assert!(params.validate().is_err());
+
+        let params = InferenceParams {
+            top_p: f32::NAN,
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn inference_params_stop_sequences_are_bounded() {
+        let with = |stop_sequences: Vec<String>| InferenceParams {
+            stop_sequences,
+            ..Default::default()
+        };
+        let params = with(vec!["\n\nUser:".into()]);
+        assert!(params.validate().is_ok());
+        assert_eq!(params.to_config().stop_sequences, params.stop_sequences);
+
+        assert!(with(vec![String::new()]).validate().is_err());
+        assert!(with(vec!["x".repeat(MAX_STOP_SEQUENCE_BYTES + 1)]).validate().is_err());
+        assert!(with(vec!["x".into(); MAX_STOP_SEQUENCES + 1]).validate().is_err());
+    }
+
+    #[test]
+    fn inference_params_top_k_zero_disables_top_k() {
+        let params = InferenceParams {
+            top_k: 0,
+            ..Default::default()
+        };
+        assert!(params.validate().is_ok());
+        let config = params.to_config();
+        assert_eq!(config.top_k, 0);
+        assert_eq!(config.top_p, params.top_p);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn inference_params_top_p_zero_falls_back_to_greedy() {
+        let params = InferenceParams {
+            top_p: 0.0,
+            ..Default::default()
+        };
+        assert!(params.validate().is_ok());
+        let config = params.to_config();
+        assert_eq!(config.top_k, 1);
+        assert_eq!(config.temperature, 0.0);
+        assert_eq!(config.top_p, 1.0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn inference_params_top_p_one_is_unchanged() {
+        let params = InferenceParams {
+            top_p: 1.0,
+            ..Default::default()
+        };
+        let config = params.to_config();
+        assert_eq!(config.top_p, 1.0);
+        assert_eq!(config.top_k, 40);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn inference_params_tiny_top_p_raised_to_floor() {
+        let params = InferenceParams {
+            top_p: 1e-6,
+            ..Default::default()
+        };
+        assert_eq!(params.to_config().top_p, DEFAULT_TOP_P_FLOOR);
+        assert_eq!(params.to_config_with_floor(0.2).top_p, 0.2);
+    }
+
+    #[test]
+    fn inference_params_min_p_takes_precedence_over_top_p() {
+        let params = InferenceParams {
+            top_p: 0.0,
+            min_p: Some(0.05),
+            ..Default::default()
+        };
+        assert!(params.validate().is_ok());
+        let config = params.to_config();
+        assert_eq!(config.min_p, Some(0.05));
+        assert_eq!(config.top_p, 1.0);
+        // top_p == 0 does not force greedy when min_p is set
+        assert_eq!(config.top_k, 40);
+        assert_eq!(config.temperature, params.temperature);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn inference_params_rejects_invalid_min_p() {
+        for min_p in [0.0, -0.1, 1.5, f32::NAN] {
+            let params = InferenceParams {
+                min_p: Some(min_p),
+                ..Default::default()
+            };
+            assert!(params.validate().is_err(), "min_p {} should be rejected", min_p);
+        }
     }
 
     #[tokio::test]
@@ -3,6 +3,8 @@
 //! All inputs are validated before reaching the model. Invalid inputs are
 //! rejected, not truncated — fail-closed security.
 
+use serde::{Deserialize, Serialize};
+
 use super::error::InferenceError;
 
 /// Maximum text input size in bytes (64KB).
@@ -26,14 +28,15 @@ pub enum InferenceInput {
 }
 
 /// A single message in a chat conversation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: ChatRole,
     pub content: String,
 }
 
 /// Typed chat roles — prevents invalid role strings at compile time.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ChatRole {
     System,
     User,
@@ -60,7 +63,7 @@ impl InferenceInput {
     }
 }
 
-fn validate_text(text: &str) -> Result<(), InferenceError> {
+pub(crate) fn validate_text(text: &str) -> Result<(), InferenceError> {
     if text.is_empty() {
         return Err(InferenceError::InputValidation("text cannot be empty".into()));
     }
@@ -93,7 +96,7 @@ fn validate_batch(batch: &[String]) -> Result<(), InferenceError> {
     Ok(())
 }
 
-fn validate_messages(messages: &[ChatMessage]) -> Result<(), InferenceError> {
+pub(crate) fn validate_messages(messages: &[ChatMessage]) -> Result<(), InferenceError> {
     if messages.is_empty() {
         return Err(InferenceError::InputValidation("messages cannot be empty".into()));
     }
@@ -3,25 +3,38 @@
 //! Handles tokenization, inference execution, and token streaming.
 //! Provides the `InferenceModel` trait and supporting types.
 
+pub mod accel;
 pub mod config;
+pub mod cpu_budget;
 pub mod decode;
+pub mod decode_valve;
+pub mod diagnose;
+pub mod embedding_cache;
+pub mod detokenize;
 pub mod error;
 pub mod filter;
 pub mod flash_attn;
 pub mod flash_attn_gpu;
 pub mod gguf;
 pub mod gpu;
+pub mod grammar;
+pub mod history;
 pub mod input;
 pub mod onnx;
 pub mod output;
+pub mod ping;
 pub mod prefill;
 pub mod quantize;
+pub mod sampler;
+pub mod sampling;
 pub mod simd_matmul;
 mod simd_neon;
 pub mod simd_tokenizer;
 pub mod simd_tokenizer_v2;
 pub mod speculative;
 pub mod speculative_v2;
+pub mod stop;
+pub mod template;
 
 // GPU backend modules (conditionally compiled)
 #[cfg(feature = "cuda")]
@@ -35,21 +48,32 @@ pub mod inference;
 mod streaming;
 mod tokenizer;
 
+pub use accel::{AccelInitError, Accelerations};
 pub use config::InferenceConfig;
+pub use cpu_budget::CpuBudget;
 pub use decode::{DecodeConfig, DecodeExecutor, DecodeStepResult};
-pub use error::InferenceError;
-pub use filter::{FilterConfig, OutputFilter};
+pub use decode_valve::{DecodeSignal, DecodeValve, DEFAULT_ABSOLUTE_MAX_DECODE_STEPS};
+pub use diagnose::{CheckStatus, DiagnosticCheck, ModelDiagnostic};
+pub use embedding_cache::{EmbeddingCache, EmbeddingCacheConfig, EmbeddingCacheStats};
+pub use detokenize::{ByteLevelBpe, DecodeOptions, Detokenizer};
+pub use error::{ErrorCategory, InferenceError};
+pub use filter::{FilterConfig, OutputFilter, OutputLimiter};
 pub use flash_attn::{FlashAttn, FlashAttnConfig};
 pub use flash_attn_gpu::{FlashAttnGpuConfig, FlashAttnGpuError, FlashAttnGpuKernel};
+pub use grammar::{GrammarConstraint, GrammarState, TokenVocab};
+pub use history::{TokenHistory, DEFAULT_HISTORY_WINDOW};
 pub use inference::{InferenceEngine, InferenceParams, InferenceResult};
 pub use input::{ChatMessage, ChatRole, InferenceInput};
 pub use input::{MAX_BATCH_SIZE, MAX_INPUT_TOKENS, MAX_TEXT_BYTES};
 pub use output::{ClassificationResult, EmbeddingResult, EntityResult};
-pub use output::{FinishReason, GenerationResult, InferenceOutput};
-pub use prefill::{PrefillConfig, PrefillExecutor, PrefillResult};
+pub use output::{FinishReason, GenerationResult, InferenceOutput, OutputEncoding};
+pub use ping::{PingCache, DEFAULT_PING_TTL};
+pub use prefill::{validate_token_ids, PrefillConfig, PrefillExecutor, PrefillResult};
 pub use quantize::{QuantFormat, QuantizedTensor, QUANT_BLOCK_SIZE};
 pub use simd_matmul::{dot_q4, dot_q8, init_simd};
 pub use simd_tokenizer::SimdTokenizer;
+pub use sampler::{DefaultSampler, MirostatSampler, Sampler, SamplerKind};
+pub use stop::{StopMatch, StopMatcher, MAX_STOP_SEQUENCES, MAX_STOP_SEQUENCE_BYTES};
 pub use simd_tokenizer_v2::{
     SimdTokenizer as SimdTokenizerV2, TokenizerError as TokenizerV2Error, TokenizerStats,
 };
@@ -60,14 +84,20 @@ pub use speculative_v2::{
     SpeculativeConfig as SpeculativeV2Config, SpeculativeDecoder as SpeculativeV2Decoder,
     SpeculativeStats,
 };
-pub use streaming::{StreamingOutput, TokenStream, TokenStreamSender};
+pub use streaming::{StreamTextDecoder, StreamingOutput, TokenStream, TokenStreamSender};
+pub use template::{ChatTemplate, TemplateError};
 pub use tokenizer::{TokenizerError, TokenizerWrapper};
 
 // Backend re-exports
-pub use gguf::{GgufConfig, GgufGenerator, GgufModel};
+pub use gguf::{
+    gguf_model_factory, AttentionHeads, GgufConfig, GgufGenerator, GgufModel, ModelFactory,
+};
 #[cfg(feature = "gguf")]
 pub use gguf::LlamaBackendInner;
-pub use gpu::{GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager, GpuMemory, GpuMemoryPool};
+pub use gpu::{
+    DeviceEnumerator, DeviceSelection, GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager,
+    GpuMemory, GpuMemoryPool, SystemDevices,
+};
 pub use onnx::{OnnxClassifier, OnnxConfig, OnnxEmbedder, OnnxModel};
 
 // CUDA backend re-exports
@@ -1,19 +1,28 @@
 //! ONNX-based embedding model.
 //!
 //! Wraps Candle ONNX runtime for generating text embeddings.
+//!
+//! Batches are padded to their longest input in one flat ids/mask
+//! allocation, run through a single forward pass, then mean-pooled over
+//! each row's unpadded positions, so padding never changes an embedding.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use super::OnnxConfig;
+use crate::engine::input::validate_text;
 use crate::engine::{
     EmbeddingResult, InferenceCapability, InferenceConfig, InferenceError,
-    InferenceInput, InferenceOutput,
+    InferenceInput, InferenceOutput, MAX_BATCH_SIZE,
 };
 
+/// Token ID used to fill padded positions (masked out before pooling).
+const PAD_TOKEN: u32 = 0;
+
 /// ONNX embedding model using Candle.
 pub struct OnnxEmbedder {
     model_id: String,
-    #[allow(dead_code)]
     embedding_dim: usize,
+    max_batch_size: usize,
     memory_bytes: AtomicUsize,
     #[cfg(feature = "onnx")]
     _model: Option<()>, // Placeholder for candle model
@@ -25,23 +34,150 @@ impl OnnxEmbedder {
         Self {
             model_id,
             embedding_dim,
+            max_batch_size: OnnxConfig::default().max_batch_size,
             memory_bytes: AtomicUsize::new(0),
             #[cfg(feature = "onnx")]
             _model: None,
         }
     }
 
+    /// Apply batching limits from `config`.
+    pub fn with_config(mut self, config: &OnnxConfig) -> Self {
+        self.max_batch_size = config.max_batch_size;
+        self
+    }
+
+    /// Embed up to `min(max_batch_size, MAX_BATCH_SIZE)` texts in one
+    /// forward pass. Results are in input order and match embedding each
+    /// text on its own.
+    pub fn embed_batch(
+        &self,
+        inputs: &[&str],
+        _config: &InferenceConfig,
+    ) -> Result<Vec<EmbeddingResult>, InferenceError> {
+        let limit = self.max_batch_size.min(MAX_BATCH_SIZE);
+        if inputs.is_empty() {
+            return Err(InferenceError::InputValidation("batch cannot be empty".into()));
+        }
+        if inputs.len() > limit {
+            return Err(InferenceError::InputValidation(format!(
+                "batch exceeds maximum size: {} > {}",
+                inputs.len(),
+                limit
+            )));
+        }
+        for (i, text) in inputs.iter().enumerate() {
+            validate_text(text).map_err(|e| {
+                InferenceError::InputValidation(format!("batch item {}: {}", i, e))
+            })?;
+        }
+        let rows = inputs
+            .iter()
+            .map(|text| self.tokenize(text))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.embed_rows(&rows, |batch| self.forward(batch))
+    }
+
     /// Generate embedding for a single text input.
-    fn embed_text(&self, _text: &str) -> Result<EmbeddingResult, InferenceError> {
+    fn embed_text(&self, text: &str) -> Result<EmbeddingResult, InferenceError> {
+        let mut results = self.embed_batch(&[text], &InferenceConfig::for_embedding())?;
+        results.pop().ok_or_else(|| InferenceError::ModelError("empty embedding batch".into()))
+    }
+
+    /// Pad `rows`, run `forward` once, and pool its `[rows, seq_len, dim]`
+    /// hidden states back into one embedding per row.
+    fn embed_rows(
+        &self,
+        rows: &[Vec<u32>],
+        forward: impl FnOnce(&PaddedBatch) -> Result<Vec<f32>, InferenceError>,
+    ) -> Result<Vec<EmbeddingResult>, InferenceError> {
+        let batch = PaddedBatch::new(rows);
+        let hidden = forward(&batch)?;
+        mean_pool(&hidden, &batch, self.embedding_dim)
+    }
+
+    fn tokenize(&self, _text: &str) -> Result<Vec<u32>, InferenceError> {
+        Err(self.not_loaded())
+    }
+
+    fn forward(&self, _batch: &PaddedBatch) -> Result<Vec<f32>, InferenceError> {
+        Err(self.not_loaded())
+    }
+
+    fn not_loaded(&self) -> InferenceError {
         // ONNX model not loaded - fail rather than return mock data
         // Real implementation requires candle-onnx with loaded model
-        Err(InferenceError::ModelError(format!(
+        InferenceError::ModelError(format!(
             "ONNX model '{}' not loaded - enable 'onnx' feature and load model",
             self.model_id
-        )))
+        ))
+    }
+}
+
+/// Token rows padded to a common length, flattened row-major.
+struct PaddedBatch {
+    /// Model input; read by the forward pass once a model is loaded.
+    #[allow(dead_code)]
+    ids: Vec<u32>,
+    /// 1 for real tokens, 0 for padding.
+    mask: Vec<u8>,
+    rows: usize,
+    seq_len: usize,
+}
+
+impl PaddedBatch {
+    fn new(rows: &[Vec<u32>]) -> Self {
+        let seq_len = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut ids = Vec::with_capacity(rows.len() * seq_len);
+        let mut mask = Vec::with_capacity(rows.len() * seq_len);
+        for row in rows {
+            ids.extend_from_slice(row);
+            ids.resize(ids.len() + seq_len - row.len(), PAD_TOKEN);
+            mask.resize(mask.len() + row.len(), 1);
+            mask.resize(mask.len() + seq_len - row.len(), 0);
+        }
+        Self { ids, mask, rows: rows.len(), seq_len }
     }
 }
 
+/// Average each row's hidden states over its unmasked positions.
+fn mean_pool(
+    hidden: &[f32],
+    batch: &PaddedBatch,
+    dim: usize,
+) -> Result<Vec<EmbeddingResult>, InferenceError> {
+    if hidden.len() != batch.rows * batch.seq_len * dim {
+        return Err(InferenceError::ModelError(format!(
+            "hidden state size mismatch: {} != {} x {} x {}",
+            hidden.len(),
+            batch.rows,
+            batch.seq_len,
+            dim
+        )));
+    }
+    let mut results = Vec::with_capacity(batch.rows);
+    for row in 0..batch.rows {
+        let mut vector = vec![0.0f32; dim];
+        let mut count = 0usize;
+        for pos in 0..batch.seq_len {
+            let idx = row * batch.seq_len + pos;
+            if batch.mask[idx] == 0 {
+                continue;
+            }
+            count += 1;
+            let state = &hidden[idx * dim..(idx + 1) * dim];
+            for (acc, &value) in vector.iter_mut().zip(state) {
+                *acc += value;
+            }
+        }
+        if count > 0 {
+            vector.iter_mut().for_each(|v| *v /= count as f32);
+        }
+        results.push(EmbeddingResult { vector, dimensions: dim });
+    }
+    Ok(results)
+}
+
 #[async_trait::async_trait]
 impl super::OnnxModel for OnnxEmbedder {
     fn model_id(&self) -> &str {
@@ -91,3 +227,64 @@ impl super::OnnxModel for OnnxEmbedder {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic stand-in for a model: each position's state depends on
+    /// its token only, and padding gets a large value that must be masked.
+    fn fake_forward(batch: &PaddedBatch, dim: usize) -> Result<Vec<f32>, InferenceError> {
+        let mut hidden = Vec::with_capacity(batch.ids.len() * dim);
+        for (&id, &mask) in batch.ids.iter().zip(&batch.mask) {
+            for d in 0..dim {
+                let value = if mask == 0 { 1e6 } else { (id as f32 * 0.37 + d as f32).sin() };
+                hidden.push(value);
+            }
+        }
+        Ok(hidden)
+    }
+
+    #[test]
+    fn batched_embeddings_match_single_calls() {
+        let embedder = OnnxEmbedder::new("e".into(), 8);
+        let rows = vec![vec![5, 9, 2], vec![7], vec![1, 2, 3, 4, 5]];
+
+        let batched = embedder.embed_rows(&rows, |b| fake_forward(b, 8)).unwrap();
+        assert_eq!(batched.len(), rows.len());
+        for (row, result) in rows.iter().zip(&batched) {
+            let single = embedder
+                .embed_rows(std::slice::from_ref(row), |b| fake_forward(b, 8))
+                .unwrap();
+            assert_eq!(result.dimensions, 8);
+            for (a, b) in result.vector.iter().zip(&single[0].vector) {
+                assert!((a - b).abs() < 1e-6, "{} vs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn embed_batch_enforces_batch_limit() {
+        let config = InferenceConfig::for_embedding();
+        let embedder = OnnxEmbedder::new("e".into(), 8)
+            .with_config(&OnnxConfig { max_batch_size: 2, ..Default::default() });
+
+        let err = embedder.embed_batch(&["a", "b", "c"], &config).unwrap_err();
+        assert!(err.to_string().contains("batch exceeds maximum size: 3 > 2"), "{}", err);
+
+        // MAX_BATCH_SIZE caps a larger configured limit
+        let embedder = OnnxEmbedder::new("e".into(), 8)
+            .with_config(&OnnxConfig { max_batch_size: 1000, ..Default::default() });
+        let inputs = vec!["a"; MAX_BATCH_SIZE + 1];
+        assert!(matches!(
+            embedder.embed_batch(&inputs, &config),
+            Err(InferenceError::InputValidation(_))
+        ));
+
+        // Within limits, the missing model is reported
+        assert!(matches!(
+            embedder.embed_batch(&["a"], &config),
+            Err(InferenceError::ModelError(_))
+        ));
+    }
+}
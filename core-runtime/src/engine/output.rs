@@ -31,6 +31,51 @@ pub struct GenerationResult {
     pub tokens_generated: u32,
     /// Reason generation stopped.
     pub finish_reason: FinishReason,
+    /// Exact output bytes when they are not valid UTF-8 (`text` is then a
+    /// lossy conversion). `None` means `text` is exact.
+    pub raw_bytes: Option<Vec<u8>>,
+}
+
+impl GenerationResult {
+    /// Build a result from raw model output, keeping the bytes only if they
+    /// are not valid UTF-8.
+    pub fn from_bytes(bytes: Vec<u8>, tokens_generated: u32, finish_reason: FinishReason) -> Self {
+        let (text, raw_bytes) = match String::from_utf8(bytes) {
+            Ok(text) => (text, None),
+            Err(e) => {
+                let bytes = e.into_bytes();
+                (String::from_utf8_lossy(&bytes).into_owned(), Some(bytes))
+            }
+        };
+        Self {
+            text,
+            tokens_generated,
+            finish_reason,
+            raw_bytes,
+        }
+    }
+
+    /// Exact output bytes.
+    pub fn bytes(&self) -> &[u8] {
+        self.raw_bytes.as_deref().unwrap_or(self.text.as_bytes())
+    }
+}
+
+/// How generated output is returned to clients.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputEncoding {
+    /// UTF-8 text; invalid sequences are replaced with U+FFFD.
+    #[default]
+    Utf8,
+    /// Exact output bytes, base64-encoded in JSON.
+    Bytes,
+}
+
+impl OutputEncoding {
+    pub fn is_utf8(&self) -> bool {
+        *self == Self::Utf8
+    }
 }
 
 /// Result of embedding generation.
@@ -68,6 +113,8 @@ pub enum FinishReason {
     Timeout,
     /// Content filter triggered.
     ContentFiltered,
+    /// Hit the output byte limit.
+    Length,
 }
 
 impl InferenceOutput {
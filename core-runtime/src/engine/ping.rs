@@ -0,0 +1,76 @@
+//! Cached model liveness probes.
+//!
+//! Readiness checks and `PingModel` are unauthenticated and bypass the
+//! request queue, so each model runs its forward step at most once per TTL
+//! and one probe at a time; callers in between get the cached outcome.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::inference::InferenceError;
+
+/// How long a probe outcome is served before the model is probed again.
+pub const DEFAULT_PING_TTL: Duration = Duration::from_secs(10);
+
+type Outcome = Result<u64, InferenceError>;
+
+/// Last probe outcome per model, reused within a TTL.
+pub struct PingCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Outcome)>>,
+    /// Held while a probe runs, so concurrent callers wait for its outcome.
+    probe: tokio::sync::Mutex<()>,
+}
+
+impl PingCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            probe: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// The cached outcome for `model_id`, or the outcome of running `probe`
+    /// once no fresh one exists.
+    pub async fn get_or_probe<F>(&self, model_id: &str, probe: F) -> Outcome
+    where
+        F: std::future::Future<Output = Outcome>,
+    {
+        if let Some(outcome) = self.fresh(model_id) {
+            return outcome;
+        }
+        let _probing = self.probe.lock().await;
+        // Another caller may have probed while this one waited
+        if let Some(outcome) = self.fresh(model_id) {
+            return outcome;
+        }
+        let outcome = probe.await;
+        self.lock()
+            .insert(model_id.to_string(), (Instant::now(), outcome.clone()));
+        outcome
+    }
+
+    /// Forget `model_id`'s outcome, e.g. after it was replaced.
+    pub fn invalidate(&self, model_id: &str) {
+        self.lock().remove(model_id);
+    }
+
+    fn fresh(&self, model_id: &str) -> Option<Outcome> {
+        self.lock()
+            .get(model_id)
+            .filter(|(at, _)| at.elapsed() < self.ttl)
+            .map(|(_, outcome)| outcome.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Outcome)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for PingCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PING_TTL)
+    }
+}
@@ -2,8 +2,12 @@
 //!
 //! Processes prompt tokens in chunks with batch-parallel execution.
 
+use std::sync::Arc;
+
 use crate::engine::InferenceError;
+use crate::memory::{ArenaPool, ArenaSlice};
 use crate::memory::paged::{PageTable, PAGE_TOKENS};
+use crate::memory::prompt_cache::PromptCache;
 
 /// Result from prefill phase.
 #[derive(Debug, Clone)]
@@ -12,6 +16,8 @@ pub struct PrefillResult {
     pub kv_len: usize,
     /// Number of chunks processed.
     pub chunks_processed: usize,
+    /// Leading prompt tokens whose KV was restored from the prompt cache.
+    pub reused_tokens: usize,
 }
 
 /// Configuration for prefill execution.
@@ -21,6 +27,11 @@ pub struct PrefillConfig {
     pub chunk_size: usize,
     /// Hidden dimension for KV storage.
     pub hidden_dim: usize,
+    /// Vocab size of the loaded model; prompt token IDs must be below it.
+    pub vocab_size: Option<u32>,
+    /// Draw per-request scratch buffers from the executor's arena pool
+    /// (see [`PrefillExecutor::with_arena_pool`]) instead of the heap.
+    pub use_arena: bool,
 }
 
 impl Default for PrefillConfig {
@@ -28,6 +39,8 @@ impl Default for PrefillConfig {
         Self {
             chunk_size: 512,
             hidden_dim: 768,
+            vocab_size: None,
+            use_arena: false,
         }
     }
 }
@@ -36,12 +49,21 @@ impl Default for PrefillConfig {
 #[derive(Debug)]
 pub struct PrefillExecutor {
     config: PrefillConfig,
+    arena_pool: Option<Arc<ArenaPool>>,
 }
 
 impl PrefillExecutor {
     /// Create a new prefill executor.
     pub fn new(config: PrefillConfig) -> Self {
-        Self { config }
+        Self { config, arena_pool: None }
+    }
+
+    /// Take scratch buffers from `pool` when `use_arena` is set. Each request
+    /// holds one arena and returns it reset, so a warm pool serves repeated
+    /// prefills without allocating.
+    pub fn with_arena_pool(mut self, pool: Arc<ArenaPool>) -> Self {
+        self.arena_pool = Some(pool);
+        self
     }
 
     /// Process prompt tokens and populate KV-cache.
@@ -50,24 +72,145 @@ impl PrefillExecutor {
         tokens: &[u32],
         page_table: &mut PageTable,
     ) -> Result<PrefillResult, InferenceError> {
+        self.validate(tokens)?;
+        let chunks_processed = self.with_scratch(tokens.len(), |scratch| {
+            self.prefill_from(tokens, 0, page_table, scratch)
+        })?;
+
+        Ok(PrefillResult {
+            kv_len: tokens.len(),
+            chunks_processed,
+            reused_tokens: 0,
+        })
+    }
+
+    /// Process prompt tokens, starting after the longest prefix found in
+    /// `cache`, then cache the KV of the whole prompt.
+    ///
+    /// The last prompt token is always recomputed so the model has fresh
+    /// logits to sample from, even when the full prompt is cached.
+    pub fn execute_with_cache(
+        &self,
+        tokens: &[u32],
+        page_table: &mut PageTable,
+        cache: &mut PromptCache,
+    ) -> Result<PrefillResult, InferenceError> {
+        self.validate(tokens)?;
+
+        let (reused_tokens, chunks_processed) = self.with_scratch(tokens.len(), |scratch| {
+            let reused_tokens = match cache.find_prefix(tokens) {
+                Some((len, entry)) => {
+                    let len = len.min(tokens.len() - 1);
+                    self.restore_kv(entry.kv_data(), len, page_table, scratch)?
+                }
+                None => 0,
+            };
+            let chunks = self.prefill_from(tokens, reused_tokens, page_table, scratch)?;
+            Ok::<_, InferenceError>((reused_tokens, chunks))
+        })?;
+        cache.insert(tokens, self.serialize_kv(tokens.len(), page_table), tokens.len());
+
+        Ok(PrefillResult {
+            kv_len: tokens.len(),
+            chunks_processed,
+            reused_tokens,
+        })
+    }
+
+    fn validate(&self, tokens: &[u32]) -> Result<(), InferenceError> {
         if tokens.is_empty() {
             return Err(InferenceError::InputValidation(
                 "prefill requires non-empty prompt".into(),
             ));
         }
+        if let Some(vocab_size) = self.config.vocab_size {
+            validate_token_ids(tokens, vocab_size)?;
+        }
+        Ok(())
+    }
 
-        let chunks: Vec<_> = tokens.chunks(self.config.chunk_size).collect();
-        let mut pos = 0;
+    /// Run `f` with scratch for a `prompt_len`-token prompt: one chunk of
+    /// embeddings, or one position's keys and values. The contents are
+    /// unspecified; callers overwrite what they use.
+    fn with_scratch<R>(&self, prompt_len: usize, f: impl FnOnce(&mut [f32]) -> R) -> R {
+        let len = self.config.chunk_size.min(prompt_len).max(2) * self.config.hidden_dim;
+        if let Some(pool) = self.arena_pool.as_ref().filter(|_| self.config.use_arena) {
+            // Returned to the pool on drop, even if `f` panics
+            let arena = pool.lease();
+            if let Some(mut slice) = ArenaSlice::<f32>::new(&arena, len) {
+                return f(slice.as_mut_slice());
+            }
+            // Arena too small for this prompt: fall back to the heap
+        }
+        f(&mut vec![0.0; len])
+    }
 
-        for chunk in &chunks {
-            self.process_chunk(chunk, pos, page_table)?;
+    /// Prefill `tokens[start..]` in chunks. Returns the number of chunks.
+    fn prefill_from(
+        &self,
+        tokens: &[u32],
+        start: usize,
+        page_table: &mut PageTable,
+        scratch: &mut [f32],
+    ) -> Result<usize, InferenceError> {
+        let mut pos = start;
+        let mut chunks_processed = 0;
+        for chunk in tokens[start..].chunks(self.config.chunk_size) {
+            self.process_chunk(chunk, pos, page_table, scratch)?;
             pos += chunk.len();
+            chunks_processed += 1;
         }
+        Ok(chunks_processed)
+    }
 
-        Ok(PrefillResult {
-            kv_len: tokens.len(),
-            chunks_processed: chunks.len(),
-        })
+    /// Bytes of serialized KV per sequence position (keys then values, f32 LE).
+    fn kv_stride(&self) -> usize {
+        2 * self.config.hidden_dim * std::mem::size_of::<f32>()
+    }
+
+    /// Serialize the KV of positions `0..len` for the prompt cache.
+    fn serialize_kv(&self, len: usize, page_table: &PageTable) -> Vec<u8> {
+        let mut data = Vec::with_capacity(len * self.kv_stride());
+        for seq_pos in 0..len {
+            if let Some(page) = page_table.get(seq_pos) {
+                let slot = PageTable::slot_in_page(seq_pos);
+                for &x in page.read_keys(slot).iter().chain(page.read_values(slot)) {
+                    data.extend_from_slice(&x.to_le_bytes());
+                }
+            }
+        }
+        data
+    }
+
+    /// Write up to `len` cached positions into `page_table`. Returns how many
+    /// were restored (0 if the entry was cached with another hidden size).
+    fn restore_kv(
+        &self,
+        kv_data: &[u8],
+        len: usize,
+        page_table: &mut PageTable,
+        scratch: &mut [f32],
+    ) -> Result<usize, InferenceError> {
+        let stride = self.kv_stride();
+        if stride == 0 || !kv_data.len().is_multiple_of(stride) || kv_data.len() / stride < len {
+            return Ok(0);
+        }
+
+        for (seq_pos, bytes) in kv_data.chunks_exact(stride).take(len).enumerate() {
+            page_table.allocate(seq_pos).ok_or_else(|| {
+                InferenceError::MemoryExceeded { used: seq_pos, limit: seq_pos }
+            })?;
+            let floats = &mut scratch[..2 * self.config.hidden_dim];
+            for (x, b) in floats.iter_mut().zip(bytes.chunks_exact(4)) {
+                *x = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            }
+            let (keys, values) = floats.split_at(self.config.hidden_dim);
+            let slot = PageTable::slot_in_page(seq_pos);
+            if let Some(page) = page_table.get_mut(seq_pos) {
+                page.write(slot, keys, values);
+            }
+        }
+        Ok(len)
     }
 
     /// Process a single chunk of tokens.
@@ -76,8 +219,14 @@ impl PrefillExecutor {
         tokens: &[u32],
         start_pos: usize,
         page_table: &mut PageTable,
+        scratch: &mut [f32],
     ) -> Result<(), InferenceError> {
-        for (i, _token) in tokens.iter().enumerate() {
+        let hidden_dim = self.config.hidden_dim;
+        // Placeholder embeddings (actual transformer would compute here)
+        let embeddings = &mut scratch[..tokens.len() * hidden_dim];
+        embeddings.fill(0.0);
+
+        for i in 0..tokens.len() {
             let seq_pos = start_pos + i;
 
             // Allocate page if needed
@@ -85,12 +234,11 @@ impl PrefillExecutor {
                 InferenceError::MemoryExceeded { used: seq_pos, limit: seq_pos }
             })?;
 
-            // Write placeholder KV (actual transformer would compute here)
+            // Write placeholder KV projected from the token's embedding
             let slot = PageTable::slot_in_page(seq_pos);
             if let Some(page) = page_table.get_mut(seq_pos) {
-                let keys = vec![0.0f32; self.config.hidden_dim];
-                let values = vec![0.0f32; self.config.hidden_dim];
-                page.write(slot, &keys, &values);
+                let row = &embeddings[i * hidden_dim..(i + 1) * hidden_dim];
+                page.write(slot, row, row);
             }
         }
         Ok(())
@@ -103,3 +251,52 @@ impl PrefillExecutor {
 
     pub fn config(&self) -> &PrefillConfig { &self.config }
 }
+
+/// Reject token IDs outside the model vocab before they reach the embedding lookup.
+pub fn validate_token_ids(tokens: &[u32], vocab_size: u32) -> Result<(), InferenceError> {
+    match tokens.iter().find(|&&id| id >= vocab_size) {
+        Some(&id) => Err(InferenceError::InvalidToken { id, vocab_size }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn executor(vocab_size: Option<u32>) -> PrefillExecutor {
+        PrefillExecutor::new(PrefillConfig {
+            hidden_dim: 8,
+            vocab_size,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_out_of_range_token_is_rejected_before_prefill() {
+        let mut page_table = PageTable::new(8, 4);
+        let result = executor(Some(100)).execute(&[1, 2, 100, 3], &mut page_table);
+
+        assert!(matches!(
+            result,
+            Err(InferenceError::InvalidToken { id: 100, vocab_size: 100 })
+        ));
+        // Nothing was written for the rejected prompt
+        assert!(page_table.get(0).is_none());
+    }
+
+    #[test]
+    fn test_in_range_tokens_prefill() {
+        let mut page_table = PageTable::new(8, 4);
+        let result = executor(Some(100)).execute(&[0, 42, 99], &mut page_table).unwrap();
+        assert_eq!(result.kv_len, 3);
+    }
+
+    #[test]
+    fn test_validate_token_ids_reports_first_offender() {
+        assert!(validate_token_ids(&[], 10).is_ok());
+        let err = validate_token_ids(&[3, 12, 11], 10).unwrap_err();
+        assert!(err.to_string().contains("12"));
+        assert!(err.is_security_concern());
+    }
+}
@@ -0,0 +1,318 @@
+//! Pluggable token samplers for `DecodeExecutor`.
+//!
+//! A `Sampler` picks the next token from logits that already carry the
+//! logit bias, grammar mask and repetition controls. `DefaultSampler` is
+//! the top-k/top-p/temperature chain; `MirostatSampler` instead steers the
+//! surprise of each sampled token towards a target.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::config::InferenceConfig;
+use super::error::InferenceError;
+use super::sampling::{
+    sample_top_k_top_p, softmax_with_temperature, top_candidates, DEFAULT_CANDIDATE_CAP,
+};
+
+/// Picks the next token from adjusted logits.
+pub trait Sampler: Send + Sync + std::fmt::Debug {
+    /// Pick a token from `logits`, or None if there is none to pick.
+    /// `history` holds recent prompt and generated tokens, oldest first.
+    fn sample(&mut self, logits: &mut [f32], history: &[u32]) -> Option<u32>;
+
+    /// Adopt the per-call settings in `config` (top-k, top-p, temperature,
+    /// candidate cap), keeping adaptive state and the RNG position.
+    fn configure(&mut self, _config: &InferenceConfig) {}
+
+    /// Start a new generation, reseeding any RNG from `seed` (None = OS).
+    fn reset(&mut self, seed: Option<u64>);
+
+    /// Clone into a new box, so configs holding a sampler stay `Clone`.
+    fn clone_box(&self) -> Box<dyn Sampler>;
+}
+
+impl Clone for Box<dyn Sampler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Sampling RNG for `seed`, or from OS entropy when None.
+pub(crate) fn sampling_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Sampling strategy requested for a generation.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SamplerKind {
+    /// Top-k, top-p and temperature.
+    #[default]
+    Default,
+    /// Mirostat 2.0: keeps the average surprise near `tau` bits, adapting
+    /// its truncation at rate `eta`. Ignores top-k, top-p and temperature.
+    Mirostat { tau: f32, eta: f32 },
+}
+
+impl SamplerKind {
+    pub fn is_default(&self) -> bool {
+        *self == Self::Default
+    }
+
+    pub fn validate(&self) -> Result<(), InferenceError> {
+        if let Self::Mirostat { tau, eta } = *self {
+            if !(tau > 0.0 && tau.is_finite()) {
+                return Err(InferenceError::InputValidation(
+                    "mirostat tau must be finite and > 0".into(),
+                ));
+            }
+            if !(eta > 0.0 && eta <= 1.0) {
+                return Err(InferenceError::InputValidation(
+                    "mirostat eta must be in (0, 1]".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the sampler for `config`, seeded from `config.seed`.
+    pub fn build(&self, config: &InferenceConfig) -> Box<dyn Sampler> {
+        match *self {
+            Self::Default => Box::new(DefaultSampler::new(config)),
+            Self::Mirostat { tau, eta } => Box::new(
+                MirostatSampler::new(tau, eta, config.seed).with_candidate_cap(config.candidate_cap),
+            ),
+        }
+    }
+}
+
+/// Top-k/top-p sampling at a temperature, with a draw from its own RNG.
+#[derive(Debug, Clone)]
+pub struct DefaultSampler {
+    top_k: usize,
+    top_p: f32,
+    temperature: f32,
+    candidate_cap: usize,
+    rng: StdRng,
+}
+
+impl DefaultSampler {
+    /// Sample with `config`'s top-k, top-p, temperature and candidate cap.
+    pub fn new(config: &InferenceConfig) -> Self {
+        let mut sampler = Self {
+            top_k: 0,
+            top_p: 1.0,
+            temperature: 1.0,
+            candidate_cap: DEFAULT_CANDIDATE_CAP,
+            rng: sampling_rng(config.seed),
+        };
+        sampler.configure(config);
+        sampler
+    }
+}
+
+impl Default for DefaultSampler {
+    fn default() -> Self {
+        Self::new(&InferenceConfig::default())
+    }
+}
+
+impl Sampler for DefaultSampler {
+    fn sample(&mut self, logits: &mut [f32], _history: &[u32]) -> Option<u32> {
+        sample_top_k_top_p(
+            logits,
+            self.top_k,
+            self.top_p,
+            self.temperature,
+            self.candidate_cap,
+            self.rng.gen(),
+        )
+    }
+
+    fn configure(&mut self, config: &InferenceConfig) {
+        self.top_k = config.top_k as usize;
+        self.top_p = config.top_p;
+        self.temperature = config.temperature;
+        self.candidate_cap = config.candidate_cap;
+    }
+
+    fn reset(&mut self, seed: Option<u64>) {
+        self.rng = sampling_rng(seed);
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/// Mirostat 2.0 (Basu et al., 2020).
+///
+/// Tokens whose surprise `-log2(p)` exceeds `mu` are dropped before the
+/// draw; after each draw `mu` moves by `eta` times the gap between the
+/// token's surprise and `tau`. Mean surprise, and so perplexity
+/// (`2^surprise`), settles at the target.
+#[derive(Debug, Clone)]
+pub struct MirostatSampler {
+    tau: f32,
+    eta: f32,
+    mu: f32,
+    candidate_cap: usize,
+    rng: StdRng,
+}
+
+impl MirostatSampler {
+    /// Target `tau` bits of surprise per token, adapting at rate `eta`.
+    pub fn new(tau: f32, eta: f32, seed: Option<u64>) -> Self {
+        Self {
+            tau,
+            eta,
+            mu: 2.0 * tau,
+            candidate_cap: DEFAULT_CANDIDATE_CAP,
+            rng: sampling_rng(seed),
+        }
+    }
+
+    /// Consider only the `cap` highest logits (0 = full vocabulary).
+    pub fn with_candidate_cap(mut self, cap: usize) -> Self {
+        self.candidate_cap = cap;
+        self
+    }
+
+    /// Current maximum surprise admitted, in bits.
+    pub fn mu(&self) -> f32 {
+        self.mu
+    }
+}
+
+impl Sampler for MirostatSampler {
+    fn sample(&mut self, logits: &mut [f32], _history: &[u32]) -> Option<u32> {
+        let candidates = top_candidates(logits, self.candidate_cap);
+        if candidates.is_empty() {
+            return None;
+        }
+        let scores: Vec<f32> = candidates.iter().map(|&(_, logit)| logit).collect();
+        let probs = softmax_with_temperature(&scores, 1.0);
+
+        // Candidates are sorted, so admitted tokens form a prefix; the most
+        // likely token is always admitted
+        let admitted = probs
+            .iter()
+            .take_while(|&&p| -p.log2() <= self.mu)
+            .count()
+            .max(1);
+        let target = self.rng.gen::<f32>() * probs[..admitted].iter().sum::<f32>();
+        let mut chosen = admitted - 1;
+        let mut acc = 0.0f32;
+        for (i, p) in probs[..admitted].iter().enumerate() {
+            acc += p;
+            if acc > target {
+                chosen = i;
+                break;
+            }
+        }
+
+        let surprise = -probs[chosen].log2();
+        if surprise.is_finite() {
+            self.mu -= self.eta * (surprise - self.tau);
+        }
+        Some(candidates[chosen].0)
+    }
+
+    fn configure(&mut self, config: &InferenceConfig) {
+        self.candidate_cap = config.candidate_cap;
+    }
+
+    fn reset(&mut self, seed: Option<u64>) {
+        self.mu = 2.0 * self.tau;
+        self.rng = sampling_rng(seed);
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_kind_validation_and_serde() {
+        assert!(SamplerKind::Default.validate().is_ok());
+        assert!(SamplerKind::Mirostat { tau: 5.0, eta: 0.1 }.validate().is_ok());
+        assert!(SamplerKind::Mirostat { tau: 0.0, eta: 0.1 }.validate().is_err());
+        assert!(SamplerKind::Mirostat { tau: 5.0, eta: 1.5 }.validate().is_err());
+
+        let json = r#"{"type":"mirostat","tau":5.0,"eta":0.1}"#;
+        let kind: SamplerKind = serde_json::from_str(json).unwrap();
+        assert_eq!(kind, SamplerKind::Mirostat { tau: 5.0, eta: 0.1 });
+    }
+
+    #[test]
+    fn test_default_sampler_matches_top_k_top_p_draws() {
+        let config = InferenceConfig {
+            temperature: 0.9,
+            top_k: 5,
+            top_p: 0.8,
+            seed: Some(42),
+            ..Default::default()
+        };
+        let mut sampler = DefaultSampler::new(&config);
+        let mut rng = StdRng::seed_from_u64(42);
+        let logits: Vec<f32> = (0..32).map(|i| ((i * 7) % 11) as f32 * 0.3).collect();
+
+        for _ in 0..64 {
+            let expected =
+                sample_top_k_top_p(&logits, 5, 0.8, 0.9, config.candidate_cap, rng.gen());
+            assert_eq!(sampler.sample(&mut logits.clone(), &[]), expected);
+        }
+    }
+
+    #[test]
+    fn test_mirostat_holds_perplexity_near_target() {
+        const TAU: f32 = 4.0;
+        let mut sampler = MirostatSampler::new(TAU, 0.1, Some(3));
+        let mut noise = StdRng::seed_from_u64(11);
+
+        // Zipf-like logits over 256 tokens with per-step jitter: the top
+        // token carries ~2.6 bits of surprise, the full distribution ~6
+        let mut surprises = Vec::new();
+        for _ in 0..3000 {
+            let logits: Vec<f32> = (0..256)
+                .map(|i| -((i + 1) as f32).ln() + noise.gen_range(-0.2..0.2))
+                .collect();
+            let probs = softmax_with_temperature(&logits, 1.0);
+            let token = sampler.sample(&mut logits.clone(), &[]).unwrap();
+            surprises.push(-probs[token as usize].log2());
+        }
+
+        // Skip the warm-up while mu settles from 2 * tau
+        let settled = &surprises[500..];
+        let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+        let perplexity = mean.exp2();
+        assert!(
+            (TAU - 0.25..=TAU + 0.25).contains(&mean),
+            "mean surprise {mean} bits, perplexity {perplexity}"
+        );
+        assert!((TAU - 0.25).exp2() <= perplexity && perplexity <= (TAU + 0.25).exp2());
+    }
+
+    #[test]
+    fn test_mirostat_reset_restores_mu_and_draws() {
+        let mut sampler = MirostatSampler::new(3.0, 0.1, Some(9));
+        let logits: Vec<f32> = (0..64).map(|i| -(i as f32) * 0.1).collect();
+        let draw = |s: &mut MirostatSampler| -> Vec<u32> {
+            (0..16).map(|_| s.sample(&mut logits.clone(), &[]).unwrap()).collect()
+        };
+
+        let first = draw(&mut sampler);
+        assert_ne!(sampler.mu(), 6.0);
+        sampler.reset(Some(9));
+        assert_eq!(sampler.mu(), 6.0);
+        assert_eq!(draw(&mut sampler), first);
+    }
+}
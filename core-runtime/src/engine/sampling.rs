@@ -0,0 +1,272 @@
+//! Sampling filters applied to token probability distributions.
+//!
+//! Mirrors the backend sampler chain so filter behavior can be verified
+//! without a loaded model.
+//!
+//! Sampling first keeps only the `candidate_cap` highest logits using a
+//! partial selection (O(n)), so top-k/top-p sort a bounded candidate set
+//! instead of the full vocabulary. Tokens outside the top 1000 carry
+//! negligible probability mass for typical temperatures.
+
+use std::cmp::Ordering;
+
+/// Default number of candidates kept before top-k/top-p.
+pub const DEFAULT_CANDIDATE_CAP: usize = 1000;
+
+/// The `cap` highest logits as `(token, logit)`, sorted descending.
+///
+/// `cap == 0` or `cap >= logits.len()` keeps (and sorts) every token.
+pub fn top_candidates(logits: &[f32], cap: usize) -> Vec<(u32, f32)> {
+    let mut candidates: Vec<(u32, f32)> = logits
+        .iter()
+        .enumerate()
+        .map(|(id, &logit)| (id as u32, logit))
+        .collect();
+    if cap > 0 && cap < candidates.len() {
+        candidates.select_nth_unstable_by(cap - 1, by_logit_desc);
+        candidates.truncate(cap);
+    }
+    candidates.sort_unstable_by(by_logit_desc);
+    candidates
+}
+
+/// Add each `(token, bias)` to that token's logit. A bias of
+/// `f32::NEG_INFINITY` bans the token; IDs outside the vocabulary are ignored.
+pub fn apply_logit_bias(logits: &mut [f32], bias: &[(u32, f32)]) {
+    for &(token, bias) in bias {
+        if let Some(logit) = logits.get_mut(token as usize) {
+            *logit += bias;
+        }
+    }
+}
+
+/// Penalize every token seen in the last `window` entries of `history`
+/// (prompt plus generated tokens): positive logits are divided by `penalty`,
+/// negative ones multiplied, once per distinct token. `penalty <= 1.0` is a no-op.
+pub fn apply_repetition_penalty(logits: &mut [f32], history: &[u32], penalty: f32, window: usize) {
+    if penalty <= 1.0 {
+        return;
+    }
+    let recent = &history[history.len().saturating_sub(window)..];
+    let mut seen = std::collections::HashSet::new();
+    for &token in recent {
+        if !seen.insert(token) {
+            continue;
+        }
+        if let Some(logit) = logits.get_mut(token as usize) {
+            if *logit > 0.0 {
+                *logit /= penalty;
+            } else {
+                *logit *= penalty;
+            }
+        }
+    }
+}
+
+/// Tokens that would complete an `n`-gram already present in `history`.
+///
+/// `n == 0` disables the ban; `n == 1` bans every token seen so far.
+pub fn banned_ngram_tokens(history: &[u32], n: usize) -> Vec<u32> {
+    if n == 0 || history.len() < n.saturating_sub(1) {
+        return Vec::new();
+    }
+    let prefix = &history[history.len() + 1 - n..];
+    let mut banned: Vec<u32> = history
+        .windows(n)
+        .filter(|gram| gram[..n - 1] == *prefix)
+        .map(|gram| gram[n - 1])
+        .collect();
+    banned.sort_unstable();
+    banned.dedup();
+    banned
+}
+
+/// Set the logit of every token in `banned` to `f32::NEG_INFINITY`.
+pub fn ban_tokens(logits: &mut [f32], banned: &[u32]) {
+    for &token in banned {
+        if let Some(logit) = logits.get_mut(token as usize) {
+            *logit = f32::NEG_INFINITY;
+        }
+    }
+}
+
+/// Pick a token with candidate cap, top-k, temperature, then top-p.
+///
+/// `uniform` is a random draw in `[0, 1)`; temperature 0 is greedy.
+pub fn sample_top_k_top_p(
+    logits: &[f32],
+    top_k: usize,
+    top_p: f32,
+    temperature: f32,
+    candidate_cap: usize,
+    uniform: f32,
+) -> Option<u32> {
+    let mut candidates = top_candidates(logits, candidate_cap);
+    if top_k > 0 {
+        candidates.truncate(top_k);
+    }
+    let (best, _) = *candidates.first()?;
+    if temperature <= 0.0 {
+        return Some(best);
+    }
+
+    let scaled: Vec<f32> = candidates.iter().map(|&(_, logit)| logit).collect();
+    let probs = softmax_with_temperature(&scaled, temperature);
+
+    // Smallest prefix whose mass reaches top_p
+    let mut nucleus = probs.len();
+    let mut mass = 0.0f32;
+    for (i, p) in probs.iter().enumerate() {
+        mass += p;
+        if mass >= top_p {
+            nucleus = i + 1;
+            break;
+        }
+    }
+
+    let target = uniform.clamp(0.0, 1.0) * probs[..nucleus].iter().sum::<f32>();
+    let mut acc = 0.0f32;
+    for (&(token, _), p) in candidates[..nucleus].iter().zip(&probs) {
+        acc += p;
+        if acc > target {
+            return Some(token);
+        }
+    }
+    Some(candidates[nucleus - 1].0)
+}
+
+fn by_logit_desc(a: &(u32, f32), b: &(u32, f32)) -> Ordering {
+    b.1.total_cmp(&a.1)
+}
+
+/// Min-p filter: zero tokens below `min_p * max_prob`, then renormalize.
+///
+/// Returns the number of tokens kept. The most likely token is always kept.
+pub fn apply_min_p(probs: &mut [f32], min_p: f32) -> usize {
+    let max_prob = probs.iter().copied().fold(0.0f32, f32::max);
+    if max_prob <= 0.0 {
+        return 0;
+    }
+
+    let threshold = min_p.clamp(0.0, 1.0) * max_prob;
+    let mut kept = 0;
+    let mut total = 0.0f32;
+    for p in probs.iter_mut() {
+        if *p >= threshold {
+            kept += 1;
+            total += *p;
+        } else {
+            *p = 0.0;
+        }
+    }
+    for p in probs.iter_mut() {
+        *p /= total;
+    }
+    kept
+}
+
+/// Softmax over logits scaled by `temperature` (> 0).
+pub fn softmax_with_temperature(logits: &[f32], temperature: f32) -> Vec<f32> {
+    let max_logit = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = logits
+        .iter()
+        .map(|&l| ((l - max_logit) / temperature).exp())
+        .collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|e| e / sum).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_p_prunes_low_probability_tokens() {
+        let mut probs = vec![0.50, 0.30, 0.12, 0.05, 0.03];
+        // threshold = 0.2 * 0.5 = 0.1
+        let kept = apply_min_p(&mut probs, 0.2);
+
+        assert_eq!(kept, 3);
+        assert_eq!(&probs[3..], &[0.0, 0.0]);
+        let sum: f32 = probs.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6);
+        assert!((probs[0] - 0.50 / 0.92).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_min_p_one_keeps_only_the_top_token() {
+        let mut probs = vec![0.1, 0.6, 0.3];
+        assert_eq!(apply_min_p(&mut probs, 1.0), 1);
+        assert_eq!(probs, vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_top_candidates_partial_selection_matches_full_sort() {
+        let logits: Vec<f32> = (0..64).map(|i| ((i * 37) % 64) as f32).collect();
+        let capped = top_candidates(&logits, 8);
+        let full = top_candidates(&logits, 0);
+
+        assert_eq!(capped.len(), 8);
+        assert_eq!(capped, full[..8].to_vec());
+        assert_eq!(capped[0].1, 63.0);
+    }
+
+    #[test]
+    fn test_logit_bias_bans_and_boosts_before_filtering() {
+        let mut logits = vec![5.0, 1.0, 0.5, 4.0];
+        apply_logit_bias(&mut logits, &[(0, f32::NEG_INFINITY), (2, 4.0), (99, 1.0)]);
+        assert_eq!(sample_top_k_top_p(&logits, 1, 1.0, 0.0, 0, 0.0), Some(2));
+
+        let kept = top_candidates(&logits, 2);
+        assert!(kept.iter().all(|&(token, _)| token != 0));
+    }
+
+    #[test]
+    fn test_repetition_penalty_scales_seen_tokens_once() {
+        let mut logits = vec![2.6, -1.0, 1.0];
+        apply_repetition_penalty(&mut logits, &[0, 0, 1, 0], 1.3, 64);
+        assert!((logits[0] - 2.0).abs() < 1e-6);
+        assert!((logits[1] + 1.3).abs() < 1e-6);
+        assert_eq!(logits[2], 1.0);
+
+        // Tokens older than the window are not penalized
+        let mut logits = vec![2.6, 1.0];
+        apply_repetition_penalty(&mut logits, &[0, 1], 1.3, 1);
+        assert_eq!(logits[0], 2.6);
+    }
+
+    #[test]
+    fn test_banned_ngram_tokens() {
+        // "1 2" was followed by 3, so a trailing "1 2" bans 3
+        assert_eq!(banned_ngram_tokens(&[1, 2, 3, 4, 1, 2], 3), vec![3]);
+        assert_eq!(banned_ngram_tokens(&[5, 6, 5], 2), vec![6]);
+        assert_eq!(banned_ngram_tokens(&[5, 6, 5], 1), vec![5, 6]);
+        assert!(banned_ngram_tokens(&[5, 6, 5], 0).is_empty());
+        assert!(banned_ngram_tokens(&[5], 3).is_empty());
+    }
+
+    #[test]
+    fn test_sample_greedy_and_nucleus() {
+        let logits = [0.0, 5.0, 1.0, 4.9];
+        assert_eq!(sample_top_k_top_p(&logits, 0, 1.0, 0.0, 2, 0.5), Some(1));
+        // top_p small enough that only the best token is in the nucleus
+        assert_eq!(sample_top_k_top_p(&logits, 0, 0.1, 1.0, 2, 0.99), Some(1));
+        // Upper draw with a wide nucleus reaches the runner-up
+        assert_eq!(sample_top_k_top_p(&logits, 0, 1.0, 1.0, 2, 0.99), Some(3));
+        assert_eq!(sample_top_k_top_p(&[], 0, 1.0, 1.0, 2, 0.5), None);
+    }
+
+    #[test]
+    fn test_min_p_composes_with_temperature() {
+        let logits = [2.0, 1.0, 0.0, -1.0];
+
+        // Higher temperature flattens the distribution, so more tokens survive
+        let mut cool = softmax_with_temperature(&logits, 0.5);
+        let mut warm = softmax_with_temperature(&logits, 2.0);
+        let kept_cool = apply_min_p(&mut cool, 0.1);
+        let kept_warm = apply_min_p(&mut warm, 0.1);
+
+        assert_eq!(kept_cool, 2);
+        assert_eq!(kept_warm, 4);
+    }
+}
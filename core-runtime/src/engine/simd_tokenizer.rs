@@ -105,11 +105,23 @@ impl SimdTokenizer {
             .collect()
     }
 
+    /// Whether [`find_whitespace`](Self::find_whitespace) takes the AVX2 path on this CPU.
+    pub fn is_vectorized() -> bool {
+        #[cfg(target_arch = "x86_64")]
+        {
+            is_x86_feature_detected!("avx2")
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            false
+        }
+    }
+
     /// Find whitespace with automatic dispatch.
     pub fn find_whitespace(text: &[u8]) -> Vec<usize> {
         #[cfg(target_arch = "x86_64")]
         {
-            if is_x86_feature_detected!("avx2") {
+            if Self::is_vectorized() {
                 // SAFETY: AVX2 feature is detected before calling
                 return unsafe { Self::find_whitespace_avx2(text) };
             }
@@ -0,0 +1,165 @@
+//! Stop-sequence matching over decoded output.
+//!
+//! Generation halts once the output contains any stop string. Output arrives
+//! one token's decoded bytes at a time, so a stop string can straddle several
+//! tokens. `StopMatcher` keeps only the last `longest - 1` output bytes
+//! between tokens, which is enough to catch any straddling match while memory
+//! stays flat over long generations.
+
+/// Maximum stop sequences accepted per request.
+pub const MAX_STOP_SEQUENCES: usize = 16;
+
+/// Maximum length of a single stop sequence in bytes.
+pub const MAX_STOP_SEQUENCE_BYTES: usize = 256;
+
+/// Where a stop sequence was found in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopMatch {
+    /// Output length in bytes once the stop sequence and anything after it
+    /// are trimmed.
+    pub output_len: usize,
+    /// Tokens contributing at least one byte to the trimmed output.
+    pub tokens: usize,
+}
+
+/// Finds the first stop sequence in output fed token by token.
+#[derive(Debug, Clone, Default)]
+pub struct StopMatcher {
+    stops: Vec<Vec<u8>>,
+    longest: usize,
+    /// Most recent output bytes, at most `longest - 1` between pushes.
+    tail: Vec<u8>,
+    /// Output offset of `tail[0]`.
+    tail_start: usize,
+    /// Output offset at which each pushed token starts.
+    token_starts: Vec<usize>,
+}
+
+impl StopMatcher {
+    /// Create a matcher for `stops`. Empty strings are ignored.
+    pub fn new(stops: &[String]) -> Self {
+        let stops: Vec<Vec<u8>> = stops
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.as_bytes().to_vec())
+            .collect();
+        let longest = stops.iter().map(Vec::len).max().unwrap_or(0);
+        Self {
+            stops,
+            longest,
+            ..Default::default()
+        }
+    }
+
+    /// Check if there is nothing to match.
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Forget all output, keeping the stop sequences.
+    pub fn reset(&mut self) {
+        self.tail.clear();
+        self.tail_start = 0;
+        self.token_starts.clear();
+    }
+
+    /// Feed the next token's decoded bytes.
+    ///
+    /// Returns the earliest stop sequence completed by `piece`, if any.
+    pub fn push(&mut self, piece: &[u8]) -> Option<StopMatch> {
+        self.token_starts.push(self.tail_start + self.tail.len());
+        if self.stops.is_empty() {
+            return None;
+        }
+
+        let searched = self.tail.len();
+        self.tail.extend_from_slice(piece);
+        // Earlier pushes found nothing, so only windows reaching into
+        // `piece` can match
+        let found = self
+            .stops
+            .iter()
+            .filter_map(|stop| {
+                let from = searched.saturating_sub(stop.len() - 1);
+                self.tail[from..]
+                    .windows(stop.len())
+                    .position(|window| window == stop.as_slice())
+                    .map(|i| from + i)
+            })
+            .min();
+
+        if let Some(pos) = found {
+            let output_len = self.tail_start + pos;
+            let tokens = self.token_starts.partition_point(|&start| start < output_len);
+            return Some(StopMatch { output_len, tokens });
+        }
+
+        let keep = self.longest - 1;
+        if self.tail.len() > keep {
+            let excess = self.tail.len() - keep;
+            self.tail.drain(..excess);
+            self.tail_start += excess;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(stops: &[&str]) -> StopMatcher {
+        StopMatcher::new(&stops.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_single_token_stop() {
+        let mut m = matcher(&["###"]);
+        assert_eq!(m.push(b"Hello"), None);
+        assert_eq!(m.push(b" world"), None);
+        assert_eq!(
+            m.push(b"###"),
+            Some(StopMatch { output_len: 11, tokens: 2 })
+        );
+    }
+
+    #[test]
+    fn test_stop_straddling_tokens_trims_partial_token() {
+        let mut m = matcher(&["\n\nUser:"]);
+        assert_eq!(m.push(b"Sure"), None);
+        assert_eq!(m.push(b".\n"), None);
+        assert_eq!(m.push(b"\nUs"), None);
+        // "Sure." is kept; ".\n" contributes a byte so it still counts
+        assert_eq!(
+            m.push(b"er: hi"),
+            Some(StopMatch { output_len: 5, tokens: 2 })
+        );
+    }
+
+    #[test]
+    fn test_earliest_of_several_stops_wins() {
+        let mut m = matcher(&["c", "ab"]);
+        assert_eq!(m.push(b"xa"), None);
+        assert_eq!(
+            m.push(b"bc"),
+            Some(StopMatch { output_len: 1, tokens: 1 })
+        );
+    }
+
+    #[test]
+    fn test_tail_stays_bounded_and_reset_forgets_output() {
+        let mut m = matcher(&["abcd"]);
+        for _ in 0..1000 {
+            assert_eq!(m.push(b"xyz"), None);
+        }
+        assert!(m.tail.len() <= 3);
+        assert_eq!(
+            m.push(b"abcd"),
+            Some(StopMatch { output_len: 3000, tokens: 1000 })
+        );
+
+        m.reset();
+        assert_eq!(m.push(b"abcd"), Some(StopMatch { output_len: 0, tokens: 0 }));
+        assert!(matcher(&[""]).is_empty());
+    }
+}
@@ -2,6 +2,8 @@
 
 use tokio::sync::mpsc;
 
+use super::{InferenceEngine, InferenceError};
+
 /// A single streamed token output.
 #[derive(Debug, Clone)]
 pub struct StreamingOutput {
@@ -59,6 +61,46 @@ impl TokenStreamSender {
     }
 }
 
+/// Turns streamed tokens into text.
+///
+/// Only the tokens since the last complete UTF-8 boundary are detokenized
+/// again, so pieces that split a sequence decode correctly without
+/// re-reading the whole stream.
+#[derive(Debug, Default)]
+pub struct StreamTextDecoder {
+    /// Tokens whose text ends in an unfinished UTF-8 sequence.
+    pending: Vec<u32>,
+    /// Bytes of the pending tokens' text already returned.
+    emitted: usize,
+}
+
+impl StreamTextDecoder {
+    /// Text completed by `tokens`, holding back a trailing partial UTF-8
+    /// sequence until its last byte arrives or the stream is `is_final`.
+    pub async fn push(
+        &mut self,
+        engine: &InferenceEngine,
+        model_id: &str,
+        tokens: &[u32],
+        is_final: bool,
+    ) -> Result<String, InferenceError> {
+        self.pending.extend_from_slice(tokens);
+        let bytes = engine.detokenize_bytes(model_id, &self.pending).await?;
+        let complete = match std::str::from_utf8(&bytes) {
+            Err(e) if !is_final && e.error_len().is_none() => e.valid_up_to(),
+            _ => bytes.len(),
+        };
+        let text = String::from_utf8_lossy(&bytes[self.emitted.min(complete)..complete]);
+        if complete == bytes.len() {
+            self.pending.clear();
+            self.emitted = 0;
+        } else {
+            self.emitted = complete;
+        }
+        Ok(text.into_owned())
+    }
+}
+
 #[derive(Debug)]
 pub struct StreamSendError;
 
@@ -0,0 +1,224 @@
+//! Minijinja-style chat template parsing.
+
+use super::{ChatTemplate, TemplateError};
+
+/// Parse the single-loop template form described in the parent module.
+pub(super) fn parse(source: &str) -> Result<ChatTemplate, TemplateError> {
+    let tokens = tokenize(source)?;
+    let loop_start = tokens
+        .iter()
+        .position(|t| matches!(t, Token::Stmt(s) if is_message_loop(s)))
+        .ok_or_else(|| TemplateError::Unsupported("no loop over messages".into()))?;
+    let loop_end = tokens
+        .iter()
+        .position(|t| matches!(t, Token::Stmt(s) if s == "endfor"))
+        .filter(|&end| end > loop_start)
+        .ok_or_else(|| TemplateError::Unsupported("messages loop is not closed".into()))?;
+
+    if !literal_text(&tokens[..loop_start])?.is_empty() {
+        return Err(TemplateError::Unsupported("text before the messages loop".into()));
+    }
+    let body = &tokens[loop_start + 1..loop_end];
+    let content = body
+        .iter()
+        .position(|t| matches!(t, Token::Expr(e) if e == "message.content"))
+        .ok_or_else(|| TemplateError::Unsupported("loop never emits message.content".into()))?;
+
+    let prefix = |role: &str| role_text(&body[..content], role);
+    let assistant_prefix = prefix("assistant")?;
+    let generation_prompt = literal_text(&tokens[loop_end + 1..])?;
+    if !generation_prompt.is_empty() && generation_prompt != assistant_prefix {
+        return Err(TemplateError::Unsupported(format!(
+            "generation prompt {:?} differs from assistant prefix {:?}",
+            generation_prompt, assistant_prefix
+        )));
+    }
+
+    Ok(ChatTemplate {
+        system_prefix: prefix("system")?,
+        user_prefix: prefix("user")?,
+        assistant_prefix,
+        message_suffix: literal_text(&body[content + 1..])?,
+    })
+}
+
+/// A piece of template source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    /// `{{ ... }}` contents, whitespace removed.
+    Expr(String),
+    /// `{% ... %}` contents, whitespace collapsed.
+    Stmt(String),
+}
+
+/// Split `source` into text and tags, applying `-` whitespace control.
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    let mut trim_next = false;
+    loop {
+        let open = match (rest.find("{{"), rest.find("{%")) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let Some(open) = open else {
+            push_text(&mut tokens, rest, trim_next);
+            return Ok(tokens);
+        };
+        let is_expr = rest[open..].starts_with("{{");
+        let close_tag = if is_expr { "}}" } else { "%}" };
+        let inner_start = open + 2;
+        let close = rest[inner_start..]
+            .find(close_tag)
+            .map(|i| inner_start + i)
+            .ok_or_else(|| TemplateError::Unterminated(rest[open..].to_string()))?;
+
+        let mut inner = &rest[inner_start..close];
+        let mut text = &rest[..open];
+        if let Some(stripped) = inner.strip_prefix('-') {
+            inner = stripped;
+            text = text.trim_end();
+        }
+        push_text(&mut tokens, text, trim_next);
+        trim_next = false;
+        if let Some(stripped) = inner.strip_suffix('-') {
+            inner = stripped;
+            trim_next = true;
+        }
+
+        tokens.push(if is_expr {
+            Token::Expr(normalize_expr(inner))
+        } else {
+            Token::Stmt(inner.split_whitespace().collect::<Vec<_>>().join(" "))
+        });
+        rest = &rest[close + 2..];
+    }
+}
+
+fn push_text(tokens: &mut Vec<Token>, text: &str, trim_start: bool) {
+    let text = if trim_start { text.trim_start() } else { text };
+    if !text.is_empty() {
+        tokens.push(Token::Text(text.to_string()));
+    }
+}
+
+/// `message['role']` and `message.role` are the same expression.
+fn normalize_expr(expr: &str) -> String {
+    let compact: String = expr.split_whitespace().collect();
+    match compact.as_str() {
+        "message.role" | "message['role']" | "message[\"role\"]" => "message.role".into(),
+        "message.content" | "message['content']" | "message[\"content\"]" => {
+            "message.content".into()
+        }
+        _ => expr.trim().to_string(),
+    }
+}
+
+fn is_message_loop(stmt: &str) -> bool {
+    stmt == "for message in messages"
+}
+
+/// The value of a quoted string literal expression.
+fn string_literal(expr: &str) -> Option<&str> {
+    ['\'', '"'].iter().find_map(|&quote| {
+        expr.strip_prefix(quote)?
+            .strip_suffix(quote)
+            .filter(|inner| !inner.contains(quote))
+    })
+}
+
+/// Concatenate tokens that render the same for every message.
+///
+/// `add_generation_prompt` conditionals are treated as always true.
+fn literal_text(tokens: &[Token]) -> Result<String, TemplateError> {
+    if tokens.iter().any(is_role_expr) {
+        return Err(TemplateError::Unsupported("message.role outside a prefix".into()));
+    }
+    role_text(tokens, "")
+}
+
+/// Render `tokens` for a message with `role`.
+fn role_text(tokens: &[Token], role: &str) -> Result<String, TemplateError> {
+    let mut text = String::new();
+    for token in tokens {
+        match token {
+            Token::Text(t) => text.push_str(t),
+            Token::Expr(e) if e == "message.role" => text.push_str(role),
+            Token::Expr(e) => match string_literal(e) {
+                Some(literal) => text.push_str(&literal.replace("\\n", "\n")),
+                None => return Err(TemplateError::Unsupported(format!("{{{{ {} }}}}", e))),
+            },
+            Token::Stmt(s) if s == "if add_generation_prompt" || s == "endif" => {}
+            Token::Stmt(s) => return Err(TemplateError::Unsupported(format!("{{% {} %}}", s))),
+        }
+    }
+    Ok(text)
+}
+
+fn is_role_expr(token: &Token) -> bool {
+    matches!(token, Token::Expr(e) if e == "message.role")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT_JINJA: &str = "{% for message in messages %}<|{{ message.role }}|>\
+        {{ message.content }}<|end|>\n{% endfor %}<|assistant|>";
+
+    #[test]
+    fn tokenize_splits_tags_and_applies_whitespace_control() {
+        let source = "{%- for message in messages -%}\n  {{ message['role'] }}:\
+            {{message.content}} \n{%- endfor %}";
+        assert_eq!(
+            tokenize(source).unwrap(),
+            [
+                Token::Stmt("for message in messages".into()),
+                Token::Expr("message.role".into()),
+                Token::Text(":".into()),
+                Token::Expr("message.content".into()),
+                Token::Stmt("endfor".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn jinja_source_matches_default_template() {
+        assert_eq!(ChatTemplate::from_jinja(DEFAULT_JINJA).unwrap(), ChatTemplate::default());
+    }
+
+    #[test]
+    fn jinja_literals_indexing_and_whitespace_control() {
+        let source = "{%- for message in messages -%}\n  {{ '<|im_start|>' }}\
+            {{ message['role'] }}{{ '\\n' }}{{ message['content'] }}{{ '<|im_end|>\\n' }}\n\
+            {%- endfor %}{% if add_generation_prompt %}{{ '<|im_start|>assistant\\n' }}{% endif %}";
+        let template = ChatTemplate::from_jinja(source).unwrap();
+
+        assert_eq!(template.user_prefix, "<|im_start|>user\n");
+        assert_eq!(template.assistant_prefix, "<|im_start|>assistant\n");
+        assert_eq!(template.message_suffix, "<|im_end|>\n");
+    }
+
+    #[test]
+    fn jinja_rejects_unsupported_sources() {
+        let unsupported = [
+            "<s>{% for message in messages %}{{ message.content }}{% endfor %}",
+            "{% for message in messages %}{{ message.role }}{% endfor %}",
+            "{% for message in messages %}{{ message.content }}{{ message.role }}{% endfor %}",
+            "{% for message in messages %}[{{ message.role }}]{{ message.content }}{% endfor %}A:",
+            "{% for message in messages %}{{ bos_token }}{{ message.content }}{% endfor %}",
+            "{% for message in messages %}{{ message.content }}",
+        ];
+        for source in unsupported {
+            assert!(
+                matches!(ChatTemplate::from_jinja(source), Err(TemplateError::Unsupported(_))),
+                "{source}"
+            );
+        }
+        assert!(matches!(
+            ChatTemplate::from_jinja("{% for message in messages"),
+            Err(TemplateError::Unterminated(_))
+        ));
+    }
+}
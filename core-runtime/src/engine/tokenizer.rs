@@ -1,12 +1,14 @@
 //! Tokenization wrapper for model-agnostic token handling.
 //!
 //! Provides encode/decode via the GGUF backend when the `gguf` feature
-//! is enabled, falling back to a no-op stub for other builds.
+//! is enabled, falling back to a no-op stub for other builds. A
+//! [`Detokenizer`] can be plugged in for vocabularies whose spacing the
+//! backend does not handle, such as byte-level BPE.
 
+use std::sync::Arc;
 use thiserror::Error;
 
-#[cfg(feature = "gguf")]
-use std::sync::Arc;
+use super::detokenize::{DecodeOptions, Detokenizer};
 
 #[cfg(feature = "gguf")]
 use crate::engine::gguf::LlamaBackendInner;
@@ -34,6 +36,7 @@ pub struct TokenizerWrapper {
     vocab_size: u32,
     eos_token: u32,
     bos_token: u32,
+    detokenizer: Option<Arc<dyn Detokenizer>>,
     #[cfg(feature = "gguf")]
     backend: Option<Arc<LlamaBackendInner>>,
 }
@@ -45,11 +48,18 @@ impl TokenizerWrapper {
             vocab_size,
             eos_token,
             bos_token,
+            detokenizer: None,
             #[cfg(feature = "gguf")]
             backend: None,
         }
     }
 
+    /// Decode through `detokenizer` instead of the backend.
+    pub fn with_detokenizer(mut self, detokenizer: Arc<dyn Detokenizer>) -> Self {
+        self.detokenizer = Some(detokenizer);
+        self
+    }
+
     /// Create a tokenizer backed by a loaded GGUF backend.
     #[cfg(feature = "gguf")]
     pub fn with_backend(
@@ -62,6 +72,7 @@ impl TokenizerWrapper {
             vocab_size,
             eos_token,
             bos_token,
+            detokenizer: None,
             backend: Some(backend),
         }
     }
@@ -82,11 +93,15 @@ impl TokenizerWrapper {
 
     /// Decode token IDs back to text.
     ///
-    /// When a backend is loaded, uses llama-cpp-2 detokenization.
-    /// Returns error if no backend.
+    /// Uses the plugged-in detokenizer if set, otherwise llama-cpp-2
+    /// detokenization. Returns error if neither is available.
     pub fn decode(&self, tokens: &[u32]) -> Result<String, TokenizerError> {
         self.validate_tokens(tokens)?;
 
+        if let Some(detok) = &self.detokenizer {
+            let bytes = detok.decode_bytes(tokens)?;
+            return Ok(String::from_utf8_lossy(&bytes).into_owned());
+        }
         #[cfg(feature = "gguf")]
         if let Some(be) = &self.backend {
             return decode_via_backend(be, tokens);
@@ -95,6 +110,18 @@ impl TokenizerWrapper {
         Err(TokenizerError::NotLoaded)
     }
 
+    /// Decode token IDs, applying `options` to the leading space.
+    ///
+    /// Use `add_leading_space: true` for continuation segments that are
+    /// appended to earlier output, false for standalone text.
+    pub fn decode_with_options(
+        &self,
+        tokens: &[u32],
+        options: &DecodeOptions,
+    ) -> Result<String, TokenizerError> {
+        self.decode(tokens).map(|text| options.apply(text))
+    }
+
     pub fn eos_token(&self) -> u32 {
         self.eos_token
     }
@@ -113,6 +140,8 @@ impl TokenizerWrapper {
     }
 
     /// Returns true if a real model tokenizer is available.
+    ///
+    /// A plugged-in detokenizer alone does not count: it cannot encode.
     pub fn has_model(&self) -> bool {
         #[cfg(feature = "gguf")]
         {
@@ -232,6 +261,28 @@ mod tests {
         assert!(matches!(result, Err(TokenizerError::NotLoaded)));
     }
 
+    #[test]
+    fn byte_level_bpe_decodes_spacing() {
+        use crate::engine::ByteLevelBpe;
+
+        // GPT-2 pieces for " Hello world,\n how are you?"
+        let pieces = ["ĠHello", "Ġworld", ",", "Ċ", "Ġhow", "Ġare", "Ġyou", "?"];
+        let bpe = ByteLevelBpe::new(pieces.iter().map(|p| p.to_string()).collect());
+        let tw = TokenizerWrapper::new(pieces.len() as u32, 2, 1)
+            .with_detokenizer(Arc::new(bpe));
+        let tokens: Vec<u32> = (0..pieces.len() as u32).collect();
+
+        let standalone = tw.decode_with_options(&tokens, &DecodeOptions::default());
+        assert_eq!(standalone.unwrap(), "Hello world,\n how are you?");
+
+        let continuation = DecodeOptions { add_leading_space: true };
+        assert_eq!(
+            tw.decode_with_options(&tokens[4..], &continuation).unwrap(),
+            " how are you?"
+        );
+        assert_eq!(tw.decode(&tokens[..2]).unwrap(), " Hello world");
+    }
+
     #[test]
     fn decode_boundary_token_invalid() {
         let tw = TokenizerWrapper::new(100, 2, 1);
@@ -0,0 +1,78 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Build information query for FFI
+//!
+//! Lets C hosts branch on compiled features without parsing strings.
+
+use std::ffi::c_char;
+
+use super::error::{set_last_error, CoreErrorCode};
+use super::types::*;
+use crate::ipc::protocol::{MAX_PROTOCOL_VERSION, MIN_PROTOCOL_VERSION};
+
+/// NUL-terminated crate version with static lifetime
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Feature flags enabled at compile time
+fn enabled_features() -> u32 {
+    let mut features = CORE_FEATURE_FFI;
+    if cfg!(feature = "onnx") {
+        features |= CORE_FEATURE_ONNX;
+    }
+    if cfg!(feature = "gguf") {
+        features |= CORE_FEATURE_GGUF;
+    }
+    if cfg!(feature = "cuda") {
+        features |= CORE_FEATURE_CUDA;
+    }
+    if cfg!(feature = "metal") {
+        features |= CORE_FEATURE_METAL;
+    }
+    if cfg!(feature = "python") {
+        features |= CORE_FEATURE_PYTHON;
+    }
+    features
+}
+
+/// Capabilities backed by the compiled inference backends
+fn supported_capabilities() -> u32 {
+    let mut capabilities = 0;
+    if cfg!(feature = "gguf") {
+        capabilities |= CORE_CAPABILITY_TEXT_GENERATION | CORE_CAPABILITY_STREAMING;
+    }
+    if cfg!(feature = "onnx") {
+        capabilities |= CORE_CAPABILITY_CLASSIFICATION | CORE_CAPABILITY_EMBEDDING;
+    }
+    capabilities
+}
+
+fn version_part(index: usize) -> u32 {
+    env!("CARGO_PKG_VERSION")
+        .split('.')
+        .nth(index)
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Query library version, build features, and capabilities
+#[no_mangle]
+pub unsafe extern "C" fn core_build_info(out_info: *mut CoreBuildInfo) -> CoreErrorCode {
+    if out_info.is_null() {
+        set_last_error("null pointer argument");
+        return CoreErrorCode::NullPointer;
+    }
+
+    *out_info = CoreBuildInfo {
+        version: VERSION.as_ptr() as *const c_char,
+        version_major: version_part(0),
+        version_minor: version_part(1),
+        version_patch: version_part(2),
+        features: enabled_features(),
+        capabilities: supported_capabilities(),
+        min_protocol_version: MIN_PROTOCOL_VERSION.as_u32(),
+        max_protocol_version: MAX_PROTOCOL_VERSION.as_u32(),
+    };
+
+    CoreErrorCode::Ok
+}
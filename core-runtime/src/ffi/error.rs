@@ -29,6 +29,7 @@ pub enum CoreErrorCode {
     ShuttingDown = -13,
     Timeout = -14,
     Cancelled = -15,
+    ResourceExhausted = -16,
     Internal = -99,
 }
 
@@ -82,6 +83,25 @@ impl From<crate::ipc::AuthError> for CoreErrorCode {
             AuthError::SessionExpired => CoreErrorCode::SessionExpired,
             AuthError::NotAuthenticated => CoreErrorCode::AuthFailed,
             AuthError::RateLimited => CoreErrorCode::RateLimited,
+            AuthError::SessionLimitReached => CoreErrorCode::ResourceExhausted,
+        }
+    }
+}
+
+impl From<crate::ipc::HandlerError> for CoreErrorCode {
+    fn from(err: crate::ipc::HandlerError) -> Self {
+        use crate::ipc::HandlerError;
+        match err {
+            HandlerError::Auth(e) => e.into(),
+            other => {
+                set_last_error(format!("{}", other));
+                match other {
+                    HandlerError::NotAuthenticated => CoreErrorCode::AuthFailed,
+                    HandlerError::QueueFull(_) => CoreErrorCode::QueueFull,
+                    HandlerError::ShuttingDown => CoreErrorCode::ShuttingDown,
+                    _ => CoreErrorCode::Internal,
+                }
+            }
         }
     }
 }
@@ -94,7 +114,8 @@ impl From<crate::engine::InferenceError> for CoreErrorCode {
             InferenceError::ModelNotLoaded(_) => CoreErrorCode::ModelNotFound,
             InferenceError::InputValidation(_) => CoreErrorCode::InvalidParams,
             InferenceError::Timeout(_) => CoreErrorCode::Timeout,
-            InferenceError::MemoryExceeded { .. } => CoreErrorCode::ContextExceeded,
+            InferenceError::CpuLimitExceeded { .. } => CoreErrorCode::ResourceExhausted,
+            InferenceError::MemoryExceeded { .. } => CoreErrorCode::ResourceExhausted,
             InferenceError::OutputFiltered { .. } => CoreErrorCode::InferenceFailed,
             InferenceError::ModelError(_) => CoreErrorCode::InferenceFailed,
             InferenceError::RateLimited => CoreErrorCode::RateLimited,
@@ -102,6 +123,9 @@ impl From<crate::engine::InferenceError> for CoreErrorCode {
             InferenceError::CapabilityNotSupported(_) => CoreErrorCode::InvalidParams,
             InferenceError::HashMismatch { .. } => CoreErrorCode::ModelLoadFailed,
             InferenceError::InvalidFormat(_) => CoreErrorCode::InvalidParams,
+            InferenceError::InvalidToken { .. } => CoreErrorCode::InvalidParams,
+            InferenceError::MemoryPressure { .. } => CoreErrorCode::ResourceExhausted,
+            InferenceError::DecodeStepLimit { .. } => CoreErrorCode::InferenceFailed,
         }
     }
 }
@@ -114,6 +138,8 @@ impl From<crate::models::LoadError> for CoreErrorCode {
             LoadError::PathNotAllowed(_) => CoreErrorCode::InvalidParams,
             LoadError::NotFound(_) => CoreErrorCode::ModelNotFound,
             LoadError::InvalidFormat(_) => CoreErrorCode::ModelLoadFailed,
+            LoadError::HashMismatch { .. } => CoreErrorCode::ModelLoadFailed,
+            LoadError::LoadInProgress(_) => CoreErrorCode::ModelLoadFailed,
             LoadError::Io(_) => CoreErrorCode::ModelLoadFailed,
         }
     }
@@ -129,6 +155,7 @@ impl From<crate::engine::inference::InferenceError> for CoreErrorCode {
             InferenceError::InvalidParams(_) => CoreErrorCode::InvalidParams,
             InferenceError::ExecutionFailed(_) => CoreErrorCode::InferenceFailed,
             InferenceError::ContextExceeded { .. } => CoreErrorCode::ContextExceeded,
+            InferenceError::ResourceLimit(_) => CoreErrorCode::ResourceExhausted,
         }
     }
 }
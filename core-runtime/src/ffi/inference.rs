@@ -9,8 +9,9 @@ use super::auth::CoreSession;
 use super::error::{set_last_error, CoreErrorCode};
 use super::runtime::CoreRuntime;
 use super::types::{CoreInferenceParams, CoreInferenceResult};
-use crate::engine::InferenceParams;
+use crate::engine::{InferenceParams, OutputEncoding, SamplerKind};
 use crate::models::ModelHandle;
+use crate::scheduler::{Priority, RequestOrigin};
 
 /// Submit inference request (blocking)
 #[no_mangle]
@@ -45,7 +46,7 @@ pub unsafe extern "C" fn core_infer(
     }
 
     // Parse model ID
-    let _model_str = match CStr::from_ptr(model_id).to_str() {
+    let model_str = match CStr::from_ptr(model_id).to_str() {
         Ok(s) => s,
         Err(_) => {
             set_last_error("invalid UTF-8 in model_id");
@@ -53,12 +54,9 @@ pub unsafe extern "C" fn core_infer(
         }
     };
 
-    // SECURITY: Validate token count to prevent memory safety issues
-    // Maximum reasonable token count (1M tokens = ~4MB of u32)
-    const MAX_TOKEN_COUNT: u32 = 1_000_000;
-    if prompt_token_count > MAX_TOKEN_COUNT {
-        set_last_error("prompt_token_count exceeds maximum allowed");
-        return CoreErrorCode::InvalidParams;
+    // SECURITY: Held to the same prompt token cap as IPC requests
+    if let Err(code) = check_prompt_token_count(rt, prompt_token_count) {
+        return code;
     }
 
     // Copy input tokens with bounds checking
@@ -76,14 +74,42 @@ pub unsafe extern "C" fn core_infer(
     } else {
         &*params
     };
-    let rust_params = params_from_c(c_params);
+    let rust_params = match params_from_c(c_params) {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
 
-    // Run inference
-    let result = rt.tokio.block_on(async {
+    // Track request in the shared queue; its ID comes from the same allocator as IPC
+    let enqueue_result = rt.tokio.block_on(async {
         rt.inner
+            .request_queue
+            .enqueue_from(
+                RequestOrigin::Ffi,
+                None,
+                model_str.to_string(),
+                String::new(), // Token prompts are not queued as text
+                rust_params.clone(),
+                Priority::Normal,
+            )
+            .await
+    });
+    let request_id = match enqueue_result {
+        Ok((request_id, _)) => request_id,
+        Err(e) => {
+            set_last_error(e.to_string());
+            return CoreErrorCode::QueueFull;
+        }
+    };
+
+    // Run inference, then give the queue slot back whatever the outcome
+    let result = rt.tokio.block_on(async {
+        let result = rt
+            .inner
             .inference_engine
             .run(ModelHandle::new(0), &tokens, &rust_params)
-            .await
+            .await;
+        rt.inner.request_queue.complete(request_id).await;
+        result
     });
 
     match result {
@@ -134,20 +160,67 @@ pub unsafe extern "C" fn core_free_tokens(tokens: *mut u32, count: u32) {
     }
 }
 
+/// Maximum number of logit bias entries accepted from C callers
+const MAX_LOGIT_BIAS_COUNT: u32 = 65_536;
+
+/// Reject a token prompt longer than the runtime's `max_prompt_tokens`
+pub(super) fn check_prompt_token_count(
+    rt: &CoreRuntime,
+    prompt_token_count: u32,
+) -> Result<(), CoreErrorCode> {
+    let max = rt.inner.config.max_prompt_tokens;
+    if prompt_token_count as usize > max {
+        set_last_error(format!(
+            "prompt_token_count {} exceeds max_prompt_tokens {}",
+            prompt_token_count, max
+        ));
+        return Err(CoreErrorCode::InvalidParams);
+    }
+    Ok(())
+}
+
 /// Convert C params to Rust params
-pub(super) fn params_from_c(c: &CoreInferenceParams) -> InferenceParams {
-    InferenceParams {
+///
+/// # Safety
+/// `c.logit_bias` must be NULL or point to `c.logit_bias_count` entries.
+pub(super) unsafe fn params_from_c(
+    c: &CoreInferenceParams,
+) -> Result<InferenceParams, CoreErrorCode> {
+    if c.logit_bias_count > MAX_LOGIT_BIAS_COUNT {
+        set_last_error("logit_bias_count exceeds maximum allowed");
+        return Err(CoreErrorCode::InvalidParams);
+    }
+    let logit_bias = if c.logit_bias.is_null() || c.logit_bias_count == 0 {
+        Default::default()
+    } else {
+        // SAFETY: count is bounded above and the caller guarantees the pointer
+        std::slice::from_raw_parts(c.logit_bias, c.logit_bias_count as usize)
+            .iter()
+            .map(|entry| (entry.token_id, entry.bias))
+            .collect()
+    };
+    Ok(InferenceParams {
         max_tokens: c.max_tokens as usize,
         temperature: c.temperature,
         top_p: c.top_p,
         top_k: c.top_k as usize,
+        min_p: None,
         stream: c.stream,
         timeout_ms: if c.timeout_ms == 0 {
             None
         } else {
             Some(c.timeout_ms)
         },
-    }
+        max_cpu_ms: None,
+        output_encoding: OutputEncoding::Utf8,
+        no_cache: false,
+        logit_bias,
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        seed: if c.seed == 0 { None } else { Some(c.seed) },
+        stop_sequences: Vec::new(),
+        sampler: SamplerKind::Default,
+    })
 }
 
 /// Write inference result to C struct
@@ -168,6 +241,9 @@ impl Clone for CoreInferenceParams {
             top_k: self.top_k,
             stream: self.stream,
             timeout_ms: self.timeout_ms,
+            logit_bias: self.logit_bias,
+            logit_bias_count: self.logit_bias_count,
+            seed: self.seed,
         }
     }
 }
@@ -7,19 +7,24 @@
 //! All functions use error codes and thread-local error messages.
 
 mod auth;
+mod build_info;
 mod error;
 mod health;
 mod inference;
 mod models;
 mod runtime;
 mod streaming;
+mod text_decode;
+mod text_stream;
 mod types;
 
 pub use auth::*;
+pub use build_info::*;
 pub use error::{core_clear_last_error, core_get_last_error, CoreErrorCode};
 pub use health::*;
 pub use inference::*;
 pub use models::*;
 pub use runtime::*;
 pub use streaming::*;
+pub use text_stream::*;
 pub use types::*;
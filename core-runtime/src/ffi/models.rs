@@ -36,6 +36,15 @@ pub unsafe extern "C" fn core_model_load(
         Err(e) => return e.into(),
     };
 
+    // Refuse a file that does not match its manifest's hash
+    let loader = &rt.inner.model_loader;
+    let checked = loader.read_manifest(&model_path).and_then(|manifest| {
+        manifest.map_or(Ok(()), |manifest| loader.verify_integrity(&model_path, &manifest))
+    });
+    if let Err(e) = checked {
+        return e.into();
+    }
+
     // Load metadata
     let metadata = match rt.inner.model_loader.load_metadata(&model_path) {
         Ok(m) => m,
@@ -43,12 +52,23 @@ pub unsafe extern "C" fn core_model_load(
     };
 
     // Register model
-    let handle = rt.tokio.block_on(async {
+    let handle = match rt.tokio.block_on(async {
         rt.inner
             .model_registry
-            .register(metadata, 0)
+            .register_file(
+                metadata,
+                0,
+                "unknown".to_string(),
+                model_path.as_path().to_path_buf(),
+            )
             .await
-    });
+    }) {
+        Ok(h) => h,
+        Err(e) => {
+            set_last_error(e.to_string());
+            return CoreErrorCode::ModelLoadFailed;
+        }
+    };
 
     *out_handle_id = handle.id();
     CoreErrorCode::Ok
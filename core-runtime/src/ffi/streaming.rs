@@ -9,7 +9,7 @@ use std::sync::Arc;
 
 use super::auth::CoreSession;
 use super::error::{set_last_error, CoreErrorCode};
-use super::inference::params_from_c;
+use super::inference::{check_prompt_token_count, params_from_c};
 use super::runtime::CoreRuntime;
 use super::types::CoreInferenceParams;
 use crate::engine::TokenStream;
@@ -97,12 +97,9 @@ pub unsafe extern "C" fn core_infer_streaming(
         }
     };
 
-    // SECURITY: Validate token count to prevent memory safety issues
-    // Maximum reasonable token count (1M tokens = ~4MB of u32)
-    const MAX_TOKEN_COUNT: u32 = 1_000_000;
-    if prompt_token_count > MAX_TOKEN_COUNT {
-        set_last_error("prompt_token_count exceeds maximum allowed");
-        return CoreErrorCode::InvalidParams;
+    // SECURITY: Held to the same prompt token cap as IPC requests
+    if let Err(code) = check_prompt_token_count(rt, prompt_token_count) {
+        return code;
     }
 
     // SAFETY: We've validated that prompt_token_count is within bounds
@@ -120,7 +117,10 @@ pub unsafe extern "C" fn core_infer_streaming(
     } else {
         &*params
     };
-    let rust_params = params_from_c(c_params);
+    let rust_params = match params_from_c(c_params) {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
 
     let cancelled = Arc::new(AtomicBool::new(false));
     let invoker = CallbackInvoker {
@@ -0,0 +1,266 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Relaying stream frames to a text callback as decoded UTF-8
+
+use std::ffi::{c_void, CString};
+
+use tokio::sync::mpsc;
+
+use super::runtime::CoreRuntime;
+use super::text_stream::CoreTextStreamCallback;
+use crate::engine::StreamTextDecoder;
+use crate::ipc::protocol::IpcMessage;
+
+/// Why relaying text to the callback stopped
+pub(super) enum RelayEnd {
+    /// The stream ended or its final frame arrived
+    Finished,
+    /// The callback returned false
+    CallbackCancelled,
+    /// The stream reported an error, or its tokens could not be decoded
+    Failed(String),
+}
+
+/// Pass the text of every frame from `receiver` to `callback` until the
+/// stream finishes, fails or the callback asks to cancel
+pub(super) fn relay_text(
+    rt: &CoreRuntime,
+    model_id: &str,
+    receiver: &mut mpsc::Receiver<IpcMessage>,
+    callback: CoreTextStreamCallback,
+    user_data: *mut c_void,
+) -> RelayEnd {
+    let mut decoder = TextDecoder::default();
+    while let Some(message) = rt.tokio.block_on(receiver.recv()) {
+        let text = match decoder.push(&rt.inner, model_id, message, &rt.tokio) {
+            Ok(Some(text)) => text,
+            Ok(None) => continue,
+            Err(message) => return RelayEnd::Failed(message),
+        };
+        if !text.is_empty() && !invoke_text(callback, user_data, &text) {
+            return RelayEnd::CallbackCancelled;
+        }
+        if decoder.finished {
+            break;
+        }
+    }
+    RelayEnd::Finished
+}
+
+/// Pass one piece of text to the callback; false if it asked to cancel
+fn invoke_text(callback: CoreTextStreamCallback, user_data: *mut c_void, text: &str) -> bool {
+    // Interior NULs would truncate the piece on the C side
+    let Ok(piece) = CString::new(text.replace('\0', "")) else {
+        return true;
+    };
+    // SAFETY: the caller guarantees callback and user_data are valid for
+    // the duration of core_infer_stream; piece outlives the call
+    unsafe { callback(piece.as_ptr(), user_data) }
+}
+
+/// Turns stream frames into text
+#[derive(Default)]
+struct TextDecoder {
+    text: StreamTextDecoder,
+    finished: bool,
+}
+
+impl TextDecoder {
+    /// Text completed by `message`, `None` for frames carrying no tokens,
+    /// or the stream's error message
+    fn push(
+        &mut self,
+        runtime: &crate::Runtime,
+        model_id: &str,
+        message: IpcMessage,
+        tokio: &tokio::runtime::Runtime,
+    ) -> Result<Option<String>, String> {
+        let (tokens, is_final) = match message {
+            IpcMessage::StreamChunk(chunk) => {
+                if let Some(error) = chunk.error {
+                    self.finished = true;
+                    return Err(error);
+                }
+                (vec![chunk.token], chunk.is_final)
+            }
+            IpcMessage::StreamBatch(batch) => (batch.tokens, batch.is_final),
+            _ => return Ok(None),
+        };
+        self.finished = is_final;
+        let engine = &runtime.inference_engine;
+        let text = tokio.block_on(self.text.push(engine, model_id, &tokens, is_final));
+        text.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::{c_char, CStr};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::engine::{
+        GgufModel, InferenceCapability, InferenceConfig, InferenceError, InferenceInput,
+        InferenceOutput,
+    };
+    use crate::ipc::protocol::{RequestId, StreamBatch, StreamChunk};
+    use crate::models::ModelHandle;
+    use crate::{Runtime, RuntimeConfig};
+
+    const MODEL: &str = "pieces";
+    /// Token `i` decodes to `PIECES[i]`; "é" is split over tokens 3 and 4
+    const PIECES: &[&[u8]] = &[b"Hel", b"lo", b" caf", b"\xC3", b"\xA9", b"!"];
+
+    /// Model whose tokenizer maps tokens to `PIECES`, recording the longest
+    /// token run it was asked to decode
+    #[derive(Default)]
+    struct PieceModel {
+        longest_decode: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl GgufModel for PieceModel {
+        fn model_id(&self) -> &str {
+            MODEL
+        }
+
+        fn capabilities(&self) -> &[InferenceCapability] {
+            &[]
+        }
+
+        fn memory_usage(&self) -> usize {
+            0
+        }
+
+        async fn infer(
+            &self,
+            _input: &InferenceInput,
+            _config: &InferenceConfig,
+        ) -> Result<InferenceOutput, InferenceError> {
+            Err(InferenceError::ModelError("decode only".into()))
+        }
+
+        async fn unload(&mut self) -> Result<(), InferenceError> {
+            Ok(())
+        }
+
+        fn detokenize_bytes(&self, tokens: &[u32]) -> Option<Result<Vec<u8>, InferenceError>> {
+            self.longest_decode.fetch_max(tokens.len(), Ordering::SeqCst);
+            let bytes = tokens.iter().flat_map(|&t| PIECES[t as usize].iter().copied());
+            Some(Ok(bytes.collect()))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn runtime(model: Arc<PieceModel>) -> CoreRuntime {
+        let runtime = CoreRuntime {
+            inner: Arc::new(Runtime::new(RuntimeConfig::default())),
+            tokio: tokio::runtime::Runtime::new().unwrap(),
+        };
+        let engine = &runtime.inner.inference_engine;
+        runtime
+            .tokio
+            .block_on(engine.register_model(MODEL.into(), ModelHandle::new(1), model));
+        runtime
+    }
+
+    /// Pieces received so far, and how many to accept before cancelling
+    struct Collected {
+        pieces: Vec<String>,
+        cancel_after: Option<usize>,
+    }
+
+    unsafe extern "C" fn collect(token_utf8: *const c_char, user_data: *mut c_void) -> bool {
+        let collected = &mut *(user_data as *mut Collected);
+        collected
+            .pieces
+            .push(CStr::from_ptr(token_utf8).to_str().unwrap().to_string());
+        collected.cancel_after != Some(collected.pieces.len())
+    }
+
+    fn relay(
+        rt: &CoreRuntime,
+        frames: Vec<IpcMessage>,
+        cancel_after: Option<usize>,
+    ) -> (RelayEnd, Vec<String>) {
+        let (sender, mut receiver) = mpsc::channel(frames.len().max(1));
+        for frame in frames {
+            sender.try_send(frame).unwrap();
+        }
+        drop(sender);
+        let mut collected = Collected {
+            pieces: Vec::new(),
+            cancel_after,
+        };
+        let user_data = &mut collected as *mut Collected as *mut c_void;
+        let end = relay_text(rt, MODEL, &mut receiver, collect, user_data);
+        (end, collected.pieces)
+    }
+
+    fn chunk(token: u32) -> IpcMessage {
+        IpcMessage::StreamChunk(StreamChunk::token(RequestId(1), token))
+    }
+
+    #[test]
+    fn test_relay_passes_every_piece_to_callback() {
+        let rt = runtime(Arc::default());
+        let frames = vec![
+            chunk(0),
+            IpcMessage::StreamBatch(StreamBatch {
+                request_id: RequestId(1),
+                tokens: vec![1, 2, 3],
+                is_final: false,
+                client_metadata: None,
+            }),
+            IpcMessage::StreamChunk(StreamChunk::final_token(RequestId(1), 4)),
+        ];
+
+        let (end, pieces) = relay(&rt, frames, None);
+
+        assert!(matches!(end, RelayEnd::Finished));
+        // The first byte of "é" is held back until the second arrives
+        assert_eq!(pieces, ["Hel", "lo caf", "é"]);
+    }
+
+    #[test]
+    fn test_relay_stops_when_callback_returns_false() {
+        let rt = runtime(Arc::default());
+        let frames = (0..3).map(chunk).collect();
+
+        let (end, pieces) = relay(&rt, frames, Some(2));
+
+        assert!(matches!(end, RelayEnd::CallbackCancelled));
+        // The callback is never invoked again after asking to cancel
+        assert_eq!(pieces, ["Hel", "lo"]);
+    }
+
+    #[test]
+    fn test_relay_reports_stream_error() {
+        let rt = runtime(Arc::default());
+        let error = StreamChunk::error(RequestId(1), "generation failed".into());
+        let frames = vec![chunk(0), IpcMessage::StreamChunk(error)];
+
+        let (end, pieces) = relay(&rt, frames, None);
+
+        assert!(matches!(end, RelayEnd::Failed(message) if message == "generation failed"));
+        assert_eq!(pieces, ["Hel"]);
+    }
+
+    #[test]
+    fn test_decoder_only_redecodes_unfinished_tokens() {
+        let model = Arc::new(PieceModel::default());
+        let rt = runtime(Arc::clone(&model));
+        let frames: Vec<_> = [0, 1, 2, 3, 4, 5].repeat(50).into_iter().map(chunk).collect();
+
+        let (end, pieces) = relay(&rt, frames, None);
+
+        assert!(matches!(end, RelayEnd::Finished));
+        assert_eq!(pieces.concat(), "Hello café!".repeat(50));
+        assert_eq!(model.longest_decode.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming decoded text to a C callback
+
+use std::ffi::{c_char, c_void, CStr};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use super::auth::CoreSession;
+use super::error::{set_last_error, CoreErrorCode};
+use super::inference::params_from_c;
+use super::runtime::CoreRuntime;
+use super::text_decode::{relay_text, RelayEnd};
+use super::types::CoreInferenceParams;
+use crate::ipc::protocol::{encode_message, InferenceRequest, IpcMessage, RequestId};
+use crate::ipc::{HandlerError, SessionToken, StreamSender};
+use crate::scheduler::{RequestIdAllocator, RequestOrigin};
+
+/// Stream frames buffered between generation and the callback
+const STREAM_BUFFER: usize = 32;
+
+/// Text streaming callback signature
+/// Receives each decoded piece as a null-terminated UTF-8 string that is
+/// freed once the callback returns. Return false to cancel generation.
+pub type CoreTextStreamCallback =
+    unsafe extern "C" fn(token_utf8: *const c_char, user_data: *mut c_void) -> bool;
+
+/// Forwards stream frames to the calling thread
+struct ChannelSender(mpsc::Sender<IpcMessage>);
+
+#[async_trait::async_trait]
+impl StreamSender for ChannelSender {
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError> {
+        self.0
+            .send(message)
+            .await
+            .map_err(|_| HandlerError::StreamSend("caller stopped reading".into()))
+    }
+}
+
+/// Stream the decoded text of a prompt's completion to `callback`
+/// (blocks until complete/cancelled)
+///
+/// The callback runs on the calling thread. Returning false from it cancels
+/// the request the same way an IPC `CancelRequest` does, and this function
+/// returns `Cancelled` once generation has stopped.
+#[no_mangle]
+pub unsafe extern "C" fn core_infer_stream(
+    runtime: *mut CoreRuntime,
+    session: *mut CoreSession,
+    model_id: *const c_char,
+    prompt: *const c_char,
+    params: *const CoreInferenceParams,
+    callback: CoreTextStreamCallback,
+    user_data: *mut c_void,
+) -> CoreErrorCode {
+    if runtime.is_null() || session.is_null() {
+        set_last_error("null runtime or session pointer");
+        return CoreErrorCode::NullPointer;
+    }
+    if model_id.is_null() || prompt.is_null() {
+        set_last_error("null argument pointer");
+        return CoreErrorCode::NullPointer;
+    }
+
+    let rt = &*runtime;
+    let sess = &*session;
+    if let Err(e) = rt.tokio.block_on(rt.inner.ipc_handler.auth.validate(&sess.token)) {
+        return e.into();
+    }
+    let request = match text_request(model_id, prompt, params) {
+        Ok(request) => request,
+        Err(code) => return code,
+    };
+    let request_id = request.request_id;
+    let model = request.model_id.clone();
+
+    let (sender, mut receiver) = mpsc::channel(STREAM_BUFFER);
+    let task = spawn_stream(rt, &sess.token, request, sender);
+    let end = relay_text(rt, &model, &mut receiver, callback, user_data);
+    if !matches!(end, RelayEnd::Finished) {
+        cancel_stream(rt, &sess.token, request_id);
+    }
+    // Generation has stopped once the handler releases the channel
+    while rt.tokio.block_on(receiver.recv()).is_some() {}
+    match end {
+        RelayEnd::Finished => {}
+        RelayEnd::CallbackCancelled => return CoreErrorCode::Cancelled,
+        RelayEnd::Failed(message) => {
+            set_last_error(message);
+            return CoreErrorCode::InferenceFailed;
+        }
+    }
+    match rt.tokio.block_on(task) {
+        Ok(Ok(())) => CoreErrorCode::Ok,
+        Ok(Err(e)) => e.into(),
+        Err(e) => {
+            set_last_error(format!("stream task: {}", e));
+            CoreErrorCode::Internal
+        }
+    }
+}
+
+/// Build the streaming request for the C arguments of `core_infer_stream`
+unsafe fn text_request(
+    model_id: *const c_char,
+    prompt: *const c_char,
+    params: *const CoreInferenceParams,
+) -> Result<InferenceRequest, CoreErrorCode> {
+    let Ok(model_str) = CStr::from_ptr(model_id).to_str() else {
+        set_last_error("invalid UTF-8 in model_id");
+        return Err(CoreErrorCode::InvalidParams);
+    };
+    let Ok(prompt_str) = CStr::from_ptr(prompt).to_str() else {
+        set_last_error("invalid UTF-8 in prompt");
+        return Err(CoreErrorCode::InvalidParams);
+    };
+    let default_params = CoreInferenceParams::default();
+    let c_params = if params.is_null() {
+        &default_params
+    } else {
+        &*params
+    };
+
+    Ok(InferenceRequest {
+        request_id: RequestId(RequestIdAllocator::global().allocate(RequestOrigin::Ffi)),
+        model_id: model_str.to_string(),
+        prompt: prompt_str.to_string(),
+        parameters: params_from_c(c_params)?,
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    })
+}
+
+/// Run `request` through the IPC streaming path, forwarding its frames
+/// to `sender`
+fn spawn_stream(
+    rt: &CoreRuntime,
+    token: &SessionToken,
+    request: InferenceRequest,
+    sender: mpsc::Sender<IpcMessage>,
+) -> tokio::task::JoinHandle<Result<(), HandlerError>> {
+    let runtime = Arc::clone(&rt.inner);
+    let token = token.clone();
+    rt.tokio.spawn(async move {
+        let sender = ChannelSender(sender);
+        runtime
+            .ipc_handler
+            .process_streaming(request, &token, &sender, CancellationToken::new())
+            .await
+    })
+}
+
+/// Cancel `request_id` through the same path as a client-sent CancelRequest
+fn cancel_stream(rt: &CoreRuntime, token: &SessionToken, request_id: RequestId) {
+    let Ok(bytes) = encode_message(&IpcMessage::CancelRequest { request_id }) else {
+        return;
+    };
+    let _ = rt
+        .tokio
+        .block_on(rt.inner.ipc_handler.process(&bytes, Some(token)));
+}
@@ -35,6 +35,16 @@ impl Default for CoreConfig {
     }
 }
 
+/// Additive bias for one token's logit
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CoreLogitBias {
+    /// Token ID to bias
+    pub token_id: u32,
+    /// Added to the raw logit (-INFINITY bans the token)
+    pub bias: f32,
+}
+
 /// Inference parameters (matches InferenceParams)
 #[repr(C)]
 pub struct CoreInferenceParams {
@@ -50,6 +60,12 @@ pub struct CoreInferenceParams {
     pub stream: bool,
     /// Timeout in milliseconds (0 = no timeout)
     pub timeout_ms: u64,
+    /// Per-token logit biases (NULL = none)
+    pub logit_bias: *const CoreLogitBias,
+    /// Number of entries in logit_bias
+    pub logit_bias_count: u32,
+    /// Sampling seed for reproducible output (0 = random per call)
+    pub seed: u64,
 }
 
 impl Default for CoreInferenceParams {
@@ -61,6 +77,9 @@ impl Default for CoreInferenceParams {
             top_k: 40,
             stream: false,
             timeout_ms: 0,
+            logit_bias: std::ptr::null(),
+            logit_bias_count: 0,
+            seed: 0,
         }
     }
 }
@@ -148,3 +167,61 @@ impl Default for CoreModelMetadata {
         }
     }
 }
+
+/// Build feature flag: ONNX backend (`onnx`)
+pub const CORE_FEATURE_ONNX: u32 = 1 << 0;
+/// Build feature flag: GGUF backend (`gguf`)
+pub const CORE_FEATURE_GGUF: u32 = 1 << 1;
+/// Build feature flag: CUDA GPU support (`cuda`)
+pub const CORE_FEATURE_CUDA: u32 = 1 << 2;
+/// Build feature flag: Metal GPU support (`metal`)
+pub const CORE_FEATURE_METAL: u32 = 1 << 3;
+/// Build feature flag: Python bindings (`python`)
+pub const CORE_FEATURE_PYTHON: u32 = 1 << 4;
+/// Build feature flag: C FFI (`ffi`)
+pub const CORE_FEATURE_FFI: u32 = 1 << 5;
+
+/// Capability flag: text generation
+pub const CORE_CAPABILITY_TEXT_GENERATION: u32 = 1 << 0;
+/// Capability flag: token streaming
+pub const CORE_CAPABILITY_STREAMING: u32 = 1 << 1;
+/// Capability flag: text classification
+pub const CORE_CAPABILITY_CLASSIFICATION: u32 = 1 << 2;
+/// Capability flag: embeddings
+pub const CORE_CAPABILITY_EMBEDDING: u32 = 1 << 3;
+
+/// Library build information
+#[repr(C)]
+pub struct CoreBuildInfo {
+    /// Version string (static, do not free)
+    pub version: *const c_char,
+    /// Major version
+    pub version_major: u32,
+    /// Minor version
+    pub version_minor: u32,
+    /// Patch version
+    pub version_patch: u32,
+    /// Enabled build features (CORE_FEATURE_* bitset)
+    pub features: u32,
+    /// Supported capabilities (CORE_CAPABILITY_* bitset)
+    pub capabilities: u32,
+    /// Minimum supported IPC protocol version
+    pub min_protocol_version: u32,
+    /// Maximum supported IPC protocol version
+    pub max_protocol_version: u32,
+}
+
+impl Default for CoreBuildInfo {
+    fn default() -> Self {
+        Self {
+            version: std::ptr::null(),
+            version_major: 0,
+            version_minor: 0,
+            version_patch: 0,
+            features: 0,
+            capabilities: 0,
+            min_protocol_version: 0,
+            max_protocol_version: 0,
+        }
+    }
+}
@@ -3,12 +3,21 @@
 //! Provides liveness, readiness, and full health report capabilities
 //! for orchestrator integration (Kubernetes, systemd).
 
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
 
+use crate::engine::accel::{self, Accelerations};
+use crate::engine::simd_tokenizer::SimdTokenizer;
+use crate::ipc::clock::{Clock, SystemClock};
 use crate::shutdown::ShutdownState;
 
+/// Gauge name for the age of the last successful inference.
+pub const LAST_SUCCESS_AGE_GAUGE: &str = "core_last_successful_inference_age_seconds";
+
 /// Overall health status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HealthState {
@@ -17,6 +26,43 @@ pub enum HealthState {
     Unhealthy,
 }
 
+/// Why the runtime is not ready to serve traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotReadyReason {
+    /// Shutdown has begun; new requests are refused.
+    ShuttingDown,
+    /// A loaded model is required but none is registered.
+    NoModelsRegistered,
+    /// At least one registered model is still loading.
+    ModelsLoading,
+    /// The request queue is at its readiness limit.
+    QueueFull,
+    /// A loaded model failed to complete a forward step.
+    WorkerWedged,
+    /// Requests keep arriving but none has succeeded within the window.
+    NoRecentSuccess,
+}
+
+impl NotReadyReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotReadyReason::ShuttingDown => "shutting_down",
+            NotReadyReason::NoModelsRegistered => "no_models_registered",
+            NotReadyReason::ModelsLoading => "models_loading",
+            NotReadyReason::QueueFull => "queue_full",
+            NotReadyReason::WorkerWedged => "worker_wedged",
+            NotReadyReason::NoRecentSuccess => "no_recent_success",
+        }
+    }
+}
+
+impl std::fmt::Display for NotReadyReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Detailed health report.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthReport {
@@ -27,13 +73,43 @@ pub struct HealthReport {
     pub memory_used_bytes: usize,
     pub queue_depth: usize,
     pub uptime_secs: u64,
+    /// CPU accelerations in use; false entries run on the scalar fallback.
+    #[serde(default)]
+    pub accelerations: Accelerations,
+    /// CPU instruction set extensions detected at startup (e.g. "avx2", "neon").
+    #[serde(default)]
+    pub cpu_features: Vec<String>,
+    /// Whether the tokenizer's whitespace scan runs vectorized (AVX2) or scalar.
+    #[serde(default)]
+    pub simd_tokenizer: bool,
+}
+
+/// Error returned to inference requests while the startup gate is closed.
+pub const WARMING_UP_MESSAGE: &str = "Server is warming up (models loading); retry shortly";
+
+/// How traffic is treated before startup preload and warmup complete.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupGate {
+    /// Serve immediately; requests for models not yet loaded fail as unknown.
+    #[default]
+    Off,
+    /// Accept connections, but answer inference with a warming-up error.
+    WarmingUp,
+    /// Do not bind the socket until startup completes.
+    DelayBind,
 }
 
 /// Health check configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HealthConfig {
     pub require_model_loaded: bool,
     pub max_queue_depth: usize,
+    /// Not ready when inferences have failed since the last success and that
+    /// success is older than this. `None` only reports the age.
+    pub max_success_age: Option<Duration>,
+    /// Hold traffic until [`HealthChecker::mark_startup_complete`] is called.
+    pub startup_gate: StartupGate,
 }
 
 impl Default for HealthConfig {
@@ -41,24 +117,145 @@ impl Default for HealthConfig {
         Self {
             require_model_loaded: false,
             max_queue_depth: 1000,
+            max_success_age: None,
+            startup_gate: StartupGate::Off,
         }
     }
 }
 
+/// When inferences last succeeded and failed.
+#[derive(Debug, Default)]
+struct InferenceOutcomes {
+    last_success: Option<Instant>,
+    last_failure: Option<Instant>,
+}
+
 /// Aggregates health information from runtime components.
 pub struct HealthChecker {
     config: HealthConfig,
     start_time: Instant,
+    clock: Arc<dyn Clock>,
+    /// Reference point for the success age before any inference succeeds.
+    tracking_since: Instant,
+    outcomes: Mutex<InferenceOutcomes>,
+    /// Cleared once preload/warmup finishes; always clear without a gate.
+    starting_up: AtomicBool,
+    startup_complete: Notify,
+    /// Model auto-warmups still running; readiness waits for them.
+    warmups_pending: Arc<AtomicUsize>,
+}
+
+/// Holds readiness at `models_loading` until dropped.
+#[derive(Debug)]
+pub struct WarmupGuard {
+    pending: Arc<AtomicUsize>,
+}
+
+impl Drop for WarmupGuard {
+    fn drop(&mut self) {
+        self.pending.fetch_sub(1, Ordering::AcqRel);
+    }
 }
 
 impl HealthChecker {
     pub fn new(config: HealthConfig) -> Self {
+        let starting_up = config.startup_gate != StartupGate::Off;
         Self {
             config,
             start_time: Instant::now(),
+            clock: Arc::new(SystemClock),
+            tracking_since: Instant::now(),
+            outcomes: Mutex::new(InferenceOutcomes::default()),
+            starting_up: AtomicBool::new(starting_up),
+            startup_complete: Notify::new(),
+            warmups_pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Measure inference outcome ages with `clock` (for deterministic tests).
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.tracking_since = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    /// Startup gate in effect.
+    pub fn startup_gate(&self) -> StartupGate {
+        self.config.startup_gate
+    }
+
+    /// True while the startup gate holds traffic.
+    pub fn is_starting_up(&self) -> bool {
+        self.starting_up.load(Ordering::Acquire)
+    }
+
+    /// Preload and warmup are done; open the startup gate.
+    pub fn mark_startup_complete(&self) {
+        self.starting_up.store(false, Ordering::Release);
+        self.startup_complete.notify_waiters();
+    }
+
+    /// Wait until the startup gate opens (immediately without a gate).
+    pub async fn wait_startup_complete(&self) {
+        loop {
+            let notified = self.startup_complete.notified();
+            if !self.is_starting_up() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// A model load with auto-warmup started; not ready until the guard drops.
+    pub fn begin_warmup(&self) -> WarmupGuard {
+        self.warmups_pending.fetch_add(1, Ordering::AcqRel);
+        WarmupGuard {
+            pending: Arc::clone(&self.warmups_pending),
         }
     }
 
+    /// Number of model auto-warmups still running.
+    pub fn warmups_pending(&self) -> usize {
+        self.warmups_pending.load(Ordering::Acquire)
+    }
+
+    /// An inference completed successfully.
+    pub fn record_inference_success(&self) {
+        self.outcomes.lock().unwrap().last_success = Some(self.clock.now());
+    }
+
+    /// An inference was attempted and failed.
+    pub fn record_inference_failure(&self) {
+        self.outcomes.lock().unwrap().last_failure = Some(self.clock.now());
+    }
+
+    /// Time since the last successful inference, or since startup if none has.
+    pub fn last_success_age(&self) -> Duration {
+        let since = self
+            .outcomes
+            .lock()
+            .unwrap()
+            .last_success
+            .unwrap_or(self.tracking_since);
+        self.clock.now().saturating_duration_since(since)
+    }
+
+    /// True if requests failed since the last success and it is older than
+    /// `max_success_age`. Idle runtimes are never stale.
+    fn success_is_stale(&self) -> bool {
+        let Some(window) = self.config.max_success_age else {
+            return false;
+        };
+        let outcomes = self.outcomes.lock().unwrap();
+        let failing = match (outcomes.last_failure, outcomes.last_success) {
+            (Some(failure), Some(success)) => failure > success,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        drop(outcomes);
+        failing && self.last_success_age() > window
+    }
+
     /// Check liveness: process is responsive.
     pub fn is_alive(&self) -> bool {
         true
@@ -66,16 +263,32 @@ impl HealthChecker {
 
     /// Check readiness: accepting traffic.
     pub fn is_ready(&self, shutdown_state: ShutdownState, models: usize, queue: usize) -> bool {
+        self.not_ready_reason(shutdown_state, models, queue).is_none()
+    }
+
+    /// First reason the runtime is not ready, or `None` if it is.
+    pub fn not_ready_reason(
+        &self,
+        shutdown_state: ShutdownState,
+        models: usize,
+        queue: usize,
+    ) -> Option<NotReadyReason> {
         if shutdown_state != ShutdownState::Running {
-            return false;
+            return Some(NotReadyReason::ShuttingDown);
+        }
+        if self.is_starting_up() || self.warmups_pending() > 0 {
+            return Some(NotReadyReason::ModelsLoading);
         }
         if self.config.require_model_loaded && models == 0 {
-            return false;
+            return Some(NotReadyReason::NoModelsRegistered);
         }
         if queue >= self.config.max_queue_depth {
-            return false;
+            return Some(NotReadyReason::QueueFull);
         }
-        true
+        if self.success_is_stale() {
+            return Some(NotReadyReason::NoRecentSuccess);
+        }
+        None
     }
 
     /// Generate full health report.
@@ -98,6 +311,9 @@ impl HealthChecker {
             memory_used_bytes: memory_bytes,
             queue_depth: queue,
             uptime_secs: self.start_time.elapsed().as_secs(),
+            accelerations: accel::active(),
+            cpu_features: accel::cpu_features().to_vec(),
+            simd_tokenizer: SimdTokenizer::is_vectorized(),
         }
     }
 
@@ -105,10 +321,11 @@ impl HealthChecker {
         if shutdown_state != ShutdownState::Running {
             return HealthState::Unhealthy;
         }
-        if self.config.require_model_loaded && models == 0 {
+        let loading = self.is_starting_up() || self.warmups_pending() > 0;
+        if loading || (self.config.require_model_loaded && models == 0) {
             return HealthState::Degraded;
         }
-        if queue >= self.config.max_queue_depth {
+        if queue >= self.config.max_queue_depth || self.success_is_stale() {
             return HealthState::Degraded;
         }
         HealthState::Healthy
@@ -0,0 +1,143 @@
+//! Registry of requests currently executing on a model.
+//!
+//! The queue only knows about waiting work. When a worker wedges, operators
+//! need to see what is running instead: which model, for how long, and
+//! whether it is still in prefill or already generating.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::auth::SessionToken;
+use super::protocol::{ActiveRequestInfo, RequestId, RequestPhase};
+use crate::engine::DecodeSignal;
+
+/// Fingerprint characters reported per request.
+const SESSION_PREFIX_LEN: usize = 8;
+
+struct ActiveEntry {
+    request_id: RequestId,
+    model_id: String,
+    session_prefix: Option<String>,
+    started: Instant,
+    decode_signal: DecodeSignal,
+}
+
+/// In-flight requests, keyed internally so client IDs may repeat.
+#[derive(Default)]
+pub struct ActiveRequests {
+    next_key: AtomicU64,
+    entries: Mutex<HashMap<u64, ActiveEntry>>,
+}
+
+impl ActiveRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a request as executing until the returned guard drops.
+    pub fn begin(
+        &self,
+        request_id: RequestId,
+        model_id: &str,
+        session: Option<&SessionToken>,
+    ) -> ActiveRequestGuard<'_> {
+        let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+        let decode_signal = DecodeSignal::attached();
+        let entry = ActiveEntry {
+            request_id,
+            model_id: model_id.to_string(),
+            session_prefix: session.map(|s| {
+                let mut fingerprint = s.fingerprint();
+                fingerprint.truncate(SESSION_PREFIX_LEN);
+                fingerprint
+            }),
+            started: Instant::now(),
+            decode_signal: decode_signal.clone(),
+        };
+        self.lock().insert(key, entry);
+        ActiveRequestGuard {
+            registry: self,
+            key,
+            decode_signal,
+        }
+    }
+
+    /// Executing requests, oldest first.
+    pub fn snapshot(&self) -> Vec<ActiveRequestInfo> {
+        let entries = self.lock();
+        let mut active: Vec<_> = entries.values().collect();
+        active.sort_by_key(|entry| entry.started);
+        active
+            .into_iter()
+            .map(|entry| ActiveRequestInfo {
+                request_id: entry.request_id,
+                model_id: entry.model_id.clone(),
+                age_ms: entry.started.elapsed().as_millis() as u64,
+                session_prefix: entry.session_prefix.clone(),
+                phase: if entry.decode_signal.is_decoding() {
+                    RequestPhase::Decode
+                } else {
+                    RequestPhase::Prefill
+                },
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, ActiveEntry>> {
+        self.entries.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+/// Keeps a request listed as active; removes it on drop.
+pub struct ActiveRequestGuard<'a> {
+    registry: &'a ActiveRequests,
+    key: u64,
+    decode_signal: DecodeSignal,
+}
+
+impl ActiveRequestGuard<'_> {
+    /// Signal to hand the model so the listed phase follows it.
+    pub fn decode_signal(&self) -> DecodeSignal {
+        self.decode_signal.clone()
+    }
+}
+
+impl Drop for ActiveRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_lists_request_until_dropped() {
+        let active = ActiveRequests::new();
+        let first = active.begin(RequestId(7), "model-a", None);
+        let second = active.begin(RequestId(7), "model-b", None);
+        second.decode_signal().mark();
+
+        let listed = active.snapshot();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].model_id, "model-a");
+        assert_eq!(listed[0].phase, RequestPhase::Prefill);
+        assert_eq!(listed[1].phase, RequestPhase::Decode);
+
+        drop(first);
+        assert_eq!(active.snapshot()[0].model_id, "model-b");
+        drop(second);
+        assert!(active.is_empty());
+    }
+}
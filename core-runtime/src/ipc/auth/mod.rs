@@ -0,0 +1,965 @@
+//! Handshake token and session ID validation.
+//!
+//! SECURITY: This module enforces that only authenticated callers can
+//! communicate with the runtime. All requests MUST have valid session.
+//!
+//! Security features:
+//! - Constant-time token comparison (prevents timing attacks)
+//! - Constant-time session validation (prevents session enumeration)
+//! - CSPRNG session IDs (prevents session prediction)
+//! - Rate limiting (prevents brute-force attacks)
+//! - Session timeout (limits exposure window)
+//! - Security audit logging (enables forensic analysis)
+
+mod rate_limit;
+
+use super::clock::{Clock, SystemClock};
+use crate::telemetry::{log_security_event, SecurityEvent};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use rate_limit::RateLimiter;
+#[cfg(test)]
+use rate_limit::{ATTEMPT_WINDOW, MAX_FAILED_ATTEMPTS, RATE_LIMIT_DURATION};
+pub use rate_limit::AuthRateLimitConfig;
+
+/// Maximum requests per session per minute.
+const MAX_REQUESTS_PER_MINUTE: u64 = 1000;
+
+/// Request rate limiting window.
+const REQUEST_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default grace past `session_timeout` before a session is treated as expired,
+/// so a session expiring mid-request is not cut off in flight.
+pub const DEFAULT_SESSION_GRACE: Duration = Duration::from_millis(500);
+
+/// Label given to the single token passed to [`SessionAuth::new`].
+pub const DEFAULT_TOKEN_LABEL: &str = "default";
+
+/// Label of the token whose sessions may send admin-only messages such as
+/// `ReloadConfig`.
+pub const ADMIN_TOKEN_LABEL: &str = "admin";
+
+/// Minimum time for session validation to prevent timing attacks.
+/// This masks any timing differences from HashMap lookups.
+const MIN_VALIDATION_TIME_MICROS: u64 = 100;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Invalid handshake token")]
+    InvalidToken,
+
+    #[error("Session not found")]
+    SessionNotFound,
+
+    #[error("Session expired")]
+    SessionExpired,
+
+    #[error("Authentication required")]
+    NotAuthenticated,
+
+    #[error("Too many failed attempts, please try again later")]
+    RateLimited,
+
+    #[error("Session request rate limit exceeded")]
+    SessionRateLimited,
+
+    #[error("Active session limit reached")]
+    SessionLimitReached,
+}
+
+/// What `authenticate` does when `max_active_sessions` is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionLimitPolicy {
+    /// Drop the least recently active session with no open connections.
+    /// Rejects if every session has a connection open.
+    #[default]
+    EvictOldestIdle,
+    /// Refuse the new session.
+    Reject,
+}
+
+/// Cap on simultaneously live sessions.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SessionLimitConfig {
+    /// Maximum live sessions. `None` means unbounded.
+    pub max_active_sessions: Option<usize>,
+    pub policy: SessionLimitPolicy,
+}
+
+/// Validated session token from handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionToken(String);
+
+impl SessionToken {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Non-secret identifier for grouping work by session.
+    ///
+    /// Derived by hashing, so it can be stored or logged without exposing
+    /// the token itself.
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(self.0.as_bytes());
+        hex::encode(&digest[..8])
+    }
+}
+
+struct Session {
+    /// Label of the configured token this session authenticated with.
+    label: String,
+    created_at: Instant,
+    last_activity: Instant,
+    connection_count: AtomicUsize,
+    /// Request count for rate limiting.
+    request_count: AtomicU64,
+    /// Window start for request rate limiting.
+    request_window_start: std::sync::Mutex<Option<Instant>>,
+}
+
+/// Manages session authentication.
+pub struct SessionAuth {
+    sessions: Arc<RwLock<HashMap<SessionToken, Session>>>,
+    /// SHA-256 of each accepted handshake token, keyed by label.
+    token_hashes: HashMap<String, [u8; 32]>,
+    session_timeout: std::sync::Mutex<Duration>,
+    session_grace: Duration,
+    session_limit: SessionLimitConfig,
+    rate_limiter: RateLimiter,
+    clock: Arc<dyn Clock>,
+}
+
+impl SessionAuth {
+    /// Create new auth manager with expected handshake token.
+    ///
+    /// Sessions it creates carry the label [`DEFAULT_TOKEN_LABEL`].
+    pub fn new(expected_token: &str, session_timeout: Duration) -> Self {
+        Self::with_tokens(
+            &[(DEFAULT_TOKEN_LABEL.to_string(), expected_token.to_string())],
+            session_timeout,
+        )
+    }
+
+    /// Create an auth manager accepting any of several labelled tokens.
+    ///
+    /// Lets credentials rotate, or distinct clients use their own token.
+    /// The label a session authenticated with is available through
+    /// [`session_label`](Self::session_label).
+    pub fn with_tokens(tokens: &[(String, String)], session_timeout: Duration) -> Self {
+        let token_hashes = tokens
+            .iter()
+            .map(|(label, token)| (label.clone(), hash_token(token)))
+            .collect();
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            token_hashes,
+            session_timeout: std::sync::Mutex::new(session_timeout),
+            session_grace: DEFAULT_SESSION_GRACE,
+            session_limit: SessionLimitConfig::default(),
+            rate_limiter: RateLimiter::new(Arc::clone(&clock)),
+            clock,
+        }
+    }
+
+    /// Cap the number of live sessions.
+    pub fn with_session_limit(mut self, limit: SessionLimitConfig) -> Self {
+        self.session_limit = limit;
+        self
+    }
+
+    /// Allow sessions to be used for `grace` past `session_timeout`.
+    pub fn with_session_grace(mut self, grace: Duration) -> Self {
+        self.session_grace = grace;
+        self
+    }
+
+    /// Whether `session` is past its timeout plus grace at `now`.
+    fn is_expired(&self, session: &Session, now: Instant) -> bool {
+        now.duration_since(session.created_at) > self.session_timeout() + self.session_grace
+    }
+
+    /// How long a session lasts before the grace period starts.
+    pub fn session_timeout(&self) -> Duration {
+        *self.session_timeout.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Change the timeout; applies to existing sessions too.
+    pub fn set_session_timeout(&self, timeout: Duration) {
+        *self.session_timeout.lock().unwrap_or_else(|e| e.into_inner()) = timeout;
+    }
+
+    /// Block handshakes per `config` after repeated failures.
+    pub fn with_rate_limit(self, config: AuthRateLimitConfig) -> Self {
+        self.rate_limiter.set_config(config);
+        self
+    }
+
+    /// Failed-handshake limits currently applied.
+    pub fn rate_limit(&self) -> AuthRateLimitConfig {
+        self.rate_limiter.config()
+    }
+
+    /// Change the failed-handshake limits; a block already in place runs
+    /// to its original end.
+    pub fn set_rate_limit(&self, config: AuthRateLimitConfig) {
+        self.rate_limiter.set_config(config);
+    }
+
+    /// Use `clock` for session expiry and rate-limit windows.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        let rate_limit = self.rate_limiter.config();
+        self.rate_limiter = RateLimiter::new(Arc::clone(&clock));
+        self.rate_limiter.set_config(rate_limit);
+        self.clock = clock;
+        self
+    }
+
+    /// Validate handshake token and create session.
+    /// Implements rate limiting to prevent brute-force attacks.
+    pub async fn authenticate(&self, token: &str) -> Result<SessionToken, AuthError> {
+        // Check rate limiting first
+        if self.rate_limiter.is_rate_limited() {
+            log_security_event(
+                SecurityEvent::RateLimited,
+                "Authentication blocked due to rate limiting",
+                &[("reason", "too_many_failures")],
+            );
+            return Err(AuthError::RateLimited);
+        }
+
+        // Compare against every configured hash so timing does not reveal
+        // which (or how many) tokens were checked before a match
+        let token_hash = hash_token(token);
+        let mut matched = None;
+        for (label, expected) in &self.token_hashes {
+            let equal = constant_time_compare(token_hash.as_slice(), expected.as_slice());
+            if equal && matched.is_none() {
+                matched = Some(label);
+            }
+        }
+
+        let Some(label) = matched else {
+            // Record failed attempt for rate limiting
+            self.rate_limiter.record_failure();
+            log_security_event(
+                SecurityEvent::AuthFailure,
+                "Invalid handshake token",
+                &[("reason", "invalid_token")],
+            );
+            return Err(AuthError::InvalidToken);
+        };
+
+        // Reset rate limiter on successful authentication
+        self.rate_limiter.reset();
+
+        let session_id = generate_session_id();
+        let session_token = SessionToken(session_id);
+        let now = self.clock.now();
+
+        let mut sessions = self.sessions.write().await;
+        self.enforce_session_limit(&mut sessions, now)?;
+        sessions.insert(
+            session_token.clone(),
+            Session {
+                label: label.clone(),
+                created_at: now,
+                last_activity: now,
+                connection_count: AtomicUsize::new(0),
+                request_count: AtomicU64::new(0),
+                request_window_start: std::sync::Mutex::new(Some(now)),
+            },
+        );
+        drop(sessions);
+
+        log_security_event(
+            SecurityEvent::AuthSuccess,
+            "Authentication successful",
+            &[
+                ("session_prefix", &session_token.as_str()[..8]),
+                ("token_label", label),
+            ],
+        );
+
+        Ok(session_token)
+    }
+
+    /// Make room for one more session under `max_active_sessions`.
+    fn enforce_session_limit(
+        &self,
+        sessions: &mut HashMap<SessionToken, Session>,
+        now: Instant,
+    ) -> Result<(), AuthError> {
+        let Some(max) = self.session_limit.max_active_sessions else {
+            return Ok(());
+        };
+        if sessions.len() >= max {
+            // Expired sessions should not count against the cap
+            sessions.retain(|_, s| !self.is_expired(s, now));
+        }
+        if sessions.len() < max {
+            return Ok(());
+        }
+
+        let victim = match self.session_limit.policy {
+            SessionLimitPolicy::Reject => None,
+            SessionLimitPolicy::EvictOldestIdle => sessions
+                .iter()
+                .filter(|(_, s)| s.connection_count.load(Ordering::SeqCst) == 0)
+                .min_by_key(|(_, s)| s.last_activity)
+                .map(|(token, _)| token.clone()),
+        };
+        let Some(victim) = victim else {
+            log_security_event(
+                SecurityEvent::SessionLimitReached,
+                "Session rejected: active session limit reached",
+                &[("max_active_sessions", &max.to_string())],
+            );
+            return Err(AuthError::SessionLimitReached);
+        };
+
+        sessions.remove(&victim);
+        log_security_event(
+            SecurityEvent::SessionLimitReached,
+            "Evicted oldest idle session: active session limit reached",
+            &[
+                ("session_prefix", &victim.as_str()[..8]),
+                ("max_active_sessions", &max.to_string()),
+            ],
+        );
+        Ok(())
+    }
+
+    /// Validate session token and update activity.
+    /// Also enforces per-session request rate limiting.
+    ///
+    /// SECURITY: Uses constant-time validation to prevent timing attacks
+    /// that could be used to enumerate valid session IDs.
+    pub async fn validate(&self, token: &SessionToken) -> Result<(), AuthError> {
+        let start = Instant::now();
+
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(token).ok_or_else(|| {
+            log_security_event(
+                SecurityEvent::InvalidSession,
+                "Invalid session token used",
+                &[("session_prefix", &token.as_str()[..8])],
+            );
+            AuthError::SessionNotFound
+        })?;
+
+        let now = self.clock.now();
+        if self.is_expired(session, now) {
+            sessions.remove(token);
+            log_security_event(
+                SecurityEvent::SessionExpired,
+                "Session expired",
+                &[("session_prefix", &token.as_str()[..8])],
+            );
+            return Err(AuthError::SessionExpired);
+        }
+
+        // Per-session request rate limiting
+        let should_reset_window = if let Ok(window_start) = &session.request_window_start.lock() {
+            window_start
+                .map(|start| now.duration_since(start) > REQUEST_WINDOW)
+                .unwrap_or(true)
+        } else {
+            false
+        };
+
+        if should_reset_window {
+            session.request_count.store(1, Ordering::SeqCst);
+            if let Ok(mut ws) = session.request_window_start.lock() {
+                *ws = Some(now);
+            }
+        } else {
+            let count = session.request_count.fetch_add(1, Ordering::SeqCst) + 1;
+            if count > MAX_REQUESTS_PER_MINUTE {
+                log_security_event(
+                    SecurityEvent::RateLimited,
+                    "Session request rate limit exceeded",
+                    &[
+                        ("session_prefix", &token.as_str()[..8]),
+                        ("request_count", &count.to_string()),
+                    ],
+                );
+                return Err(AuthError::SessionRateLimited);
+            }
+        }
+
+        session.last_activity = now;
+
+        // Constant-time delay to prevent timing attacks
+        // This ensures all validation operations take the same minimum time
+        let elapsed = start.elapsed().as_micros() as u64;
+        if elapsed < MIN_VALIDATION_TIME_MICROS {
+            let remaining = MIN_VALIDATION_TIME_MICROS - elapsed;
+            std::thread::sleep(Duration::from_micros(remaining));
+        }
+
+        Ok(())
+    }
+
+    /// Label of the token `token`'s session authenticated with, for audit logs.
+    pub async fn session_label(&self, token: &SessionToken) -> Option<String> {
+        let sessions = self.sessions.read().await;
+        sessions.get(token).map(|s| s.label.clone())
+    }
+
+    /// Remove expired sessions.
+    pub async fn cleanup(&self) {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.write().await;
+        sessions.retain(|_, s| !self.is_expired(s, now));
+    }
+
+    /// Increment connection count for session.
+    pub async fn track_connection(&self, token: &SessionToken) -> Result<usize, AuthError> {
+        let sessions = self.sessions.read().await;
+        let session = sessions.get(token).ok_or(AuthError::SessionNotFound)?;
+        let new_count = session.connection_count.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(new_count)
+    }
+
+    /// Decrement connection count for session.
+    pub async fn release_connection(&self, token: &SessionToken) {
+        let sessions = self.sessions.read().await;
+        if let Some(session) = sessions.get(token) {
+            session.connection_count.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Get current connection count for session.
+    pub async fn connection_count(&self, token: &SessionToken) -> Result<usize, AuthError> {
+        let sessions = self.sessions.read().await;
+        let session = sessions.get(token).ok_or(AuthError::SessionNotFound)?;
+        Ok(session.connection_count.load(Ordering::Relaxed))
+    }
+}
+
+fn hash_token(token: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Constant-time comparison to prevent timing attacks.
+fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Generate a cryptographically secure random session ID.
+/// Uses OsRng for entropy from the operating system's CSPRNG.
+fn generate_session_id() -> String {
+    use rand::RngCore;
+
+    // Generate 32 bytes of cryptographically secure random data
+    let mut random_bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(random_bytes.as_mut_slice());
+
+    // Encode as hex for a 64-character session ID
+    hex::encode(random_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::clock::MockClock;
+
+    fn mock_auth(timeout: Duration) -> (SessionAuth, Arc<MockClock>) {
+        let clock = Arc::new(MockClock::new());
+        let auth = SessionAuth::new("test-token", timeout).with_clock(clock.clone());
+        (auth, clock)
+    }
+
+    /// Test that constant_time_compare returns true for equal slices
+    #[test]
+    fn test_constant_time_compare_equal() {
+        let a = [1u8, 2, 3, 4, 5];
+        let b = [1u8, 2, 3, 4, 5];
+        assert!(constant_time_compare(&a, &b));
+    }
+
+    /// Test that constant_time_compare returns false for different slices
+    #[test]
+    fn test_constant_time_compare_different() {
+        let a = [1u8, 2, 3, 4, 5];
+        let b = [1u8, 2, 3, 4, 6];
+        assert!(!constant_time_compare(&a, &b));
+    }
+
+    /// Test that constant_time_compare returns false for different lengths
+    #[test]
+    fn test_constant_time_compare_different_lengths() {
+        let a = [1u8, 2, 3];
+        let b = [1u8, 2, 3, 4];
+        assert!(!constant_time_compare(&a, &b));
+    }
+
+    /// Test that constant_time_compare returns true for empty slices
+    #[test]
+    fn test_constant_time_compare_empty() {
+        let a: [u8; 0] = [];
+        let b: [u8; 0] = [];
+        assert!(constant_time_compare(&a, &b));
+    }
+
+    /// Test that generate_session_id produces 64-character hex strings
+    #[test]
+    fn test_generate_session_id_length() {
+        let id = generate_session_id();
+        assert_eq!(id.len(), 64); // 32 bytes = 64 hex chars
+    }
+
+    /// Test that generate_session_id produces unique IDs
+    #[test]
+    fn test_generate_session_id_unique() {
+        let id1 = generate_session_id();
+        let id2 = generate_session_id();
+        assert_ne!(id1, id2);
+    }
+
+    /// Test that generate_session_id only contains hex characters
+    #[test]
+    fn test_generate_session_id_hex() {
+        let id = generate_session_id();
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    /// Test SessionToken creation and as_str
+    #[test]
+    fn test_session_token() {
+        let token = SessionToken("test-session-id".to_string());
+        assert_eq!(token.as_str(), "test-session-id");
+    }
+
+    /// Test SessionToken traits
+    #[test]
+    fn test_session_token_traits() {
+        let token1 = SessionToken("abc".to_string());
+        let token2 = SessionToken("abc".to_string());
+        let token3 = SessionToken("def".to_string());
+
+        // Clone
+        let cloned = token1.clone();
+        assert_eq!(token1, cloned);
+
+        // PartialEq
+        assert_eq!(token1, token2);
+        assert_ne!(token1, token3);
+
+        // Hash (can be used in HashMap)
+        let mut map = std::collections::HashMap::new();
+        map.insert(token1, 1);
+        assert_eq!(map.get(&token2), Some(&1)); // token2 has same hash
+    }
+
+    /// Test fingerprint is stable and does not reveal the token
+    #[test]
+    fn test_session_token_fingerprint() {
+        let token = SessionToken(generate_session_id());
+        assert_eq!(token.fingerprint(), token.fingerprint());
+        assert_eq!(token.fingerprint().len(), 16);
+        assert!(!token.as_str().contains(&token.fingerprint()));
+    }
+
+    /// Test AuthError display
+    #[test]
+    fn test_auth_error_display() {
+        assert!(AuthError::InvalidToken.to_string().contains("Invalid"));
+        assert!(AuthError::SessionNotFound.to_string().contains("not found"));
+        assert!(AuthError::SessionExpired.to_string().contains("expired"));
+        assert!(AuthError::NotAuthenticated.to_string().contains("required"));
+        assert!(AuthError::RateLimited.to_string().contains("try again"));
+        assert!(AuthError::SessionRateLimited
+            .to_string()
+            .contains("rate limit"));
+    }
+
+    /// Test RateLimiter initial state
+    #[test]
+    fn test_rate_limiter_initial() {
+        let limiter = RateLimiter::new(Arc::new(SystemClock));
+        assert!(!limiter.is_rate_limited());
+    }
+
+    /// Test RateLimiter reset
+    #[test]
+    fn test_rate_limiter_reset() {
+        let limiter = RateLimiter::new(Arc::new(SystemClock));
+        limiter.record_failure();
+        limiter.reset();
+        assert!(!limiter.is_rate_limited());
+    }
+
+    // === Async tests with tokio runtime ===
+
+    /// Test successful authentication
+    #[tokio::test]
+    async fn test_authenticate_success() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600));
+        let result = auth.authenticate("test-token").await;
+        assert!(result.is_ok());
+        let session = result.unwrap();
+        assert_eq!(session.as_str().len(), 64); // 32 bytes hex-encoded
+    }
+
+    /// Test authentication with wrong token
+    #[tokio::test]
+    async fn test_authenticate_wrong_token() {
+        let auth = SessionAuth::new("correct-token", Duration::from_secs(3600));
+        let result = auth.authenticate("wrong-token").await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+
+    /// Test session validation
+    #[tokio::test]
+    async fn test_validate_session() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600));
+        let session = auth.authenticate("test-token").await.unwrap();
+        let result = auth.validate(&session).await;
+        assert!(result.is_ok());
+    }
+
+    /// Test validation of invalid session
+    #[tokio::test]
+    async fn test_validate_invalid_session() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600));
+        let fake_session = SessionToken("nonexistent-session-id".to_string());
+        let result = auth.validate(&fake_session).await;
+        assert!(matches!(result, Err(AuthError::SessionNotFound)));
+    }
+
+    /// Test session expiration
+    #[tokio::test]
+    async fn test_session_expiration() {
+        let (auth, clock) = mock_auth(Duration::from_secs(60));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        clock.advance(Duration::from_secs(59));
+        assert!(auth.validate(&session).await.is_ok());
+
+        clock.advance(Duration::from_secs(2));
+        let result = auth.validate(&session).await;
+        assert!(matches!(result, Err(AuthError::SessionExpired)));
+    }
+
+    #[tokio::test]
+    async fn test_session_within_grace_still_valid() {
+        let (auth, clock) = mock_auth(Duration::from_secs(60));
+        let auth = auth.with_session_grace(Duration::from_millis(200));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        clock.advance(Duration::from_secs(60) + Duration::from_millis(50));
+        assert!(auth.validate(&session).await.is_ok());
+        auth.cleanup().await;
+        assert!(auth.validate(&session).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_session_beyond_grace_expires() {
+        let (auth, clock) = mock_auth(Duration::from_secs(60));
+        let auth = auth.with_session_grace(Duration::from_millis(200));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        clock.advance(Duration::from_secs(60) + Duration::from_millis(250));
+        let result = auth.validate(&session).await;
+        assert!(matches!(result, Err(AuthError::SessionExpired)));
+    }
+
+    /// Test cleanup removes expired sessions
+    #[tokio::test]
+    async fn test_cleanup_expired_sessions() {
+        let (auth, clock) = mock_auth(Duration::from_secs(60));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        clock.advance(Duration::from_secs(61));
+
+        // Cleanup should remove the expired session
+        auth.cleanup().await;
+
+        // Session should now be not found (removed by cleanup)
+        let result = auth.validate(&session).await;
+        assert!(matches!(result, Err(AuthError::SessionNotFound)));
+    }
+
+    /// Test connection tracking
+    #[tokio::test]
+    async fn test_connection_tracking() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        // Track connections
+        let count1 = auth.track_connection(&session).await.unwrap();
+        assert_eq!(count1, 1);
+
+        let count2 = auth.track_connection(&session).await.unwrap();
+        assert_eq!(count2, 2);
+
+        // Release a connection
+        auth.release_connection(&session).await;
+
+        let current = auth.connection_count(&session).await.unwrap();
+        assert_eq!(current, 1);
+    }
+
+    /// Test rate limiting after multiple failures
+    #[tokio::test]
+    async fn test_rate_limiting() {
+        let auth = SessionAuth::new("correct-token", Duration::from_secs(3600));
+
+        // Make multiple failed attempts
+        for _ in 0..5 {
+            let _ = auth.authenticate("wrong-token").await;
+        }
+
+        // Should now be rate limited
+        let result = auth.authenticate("correct-token").await;
+        assert!(matches!(result, Err(AuthError::RateLimited)));
+    }
+
+    /// Test rate limiting lifts once the block duration elapses
+    #[tokio::test]
+    async fn test_rate_limit_expires() {
+        let (auth, clock) = mock_auth(Duration::from_secs(3600));
+
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            let _ = auth.authenticate("wrong-token").await;
+        }
+        clock.advance(RATE_LIMIT_DURATION - Duration::from_secs(1));
+        assert!(matches!(
+            auth.authenticate("test-token").await,
+            Err(AuthError::RateLimited)
+        ));
+
+        clock.advance(Duration::from_secs(2));
+        assert!(auth.authenticate("test-token").await.is_ok());
+    }
+
+    /// Test a changed rate limit applies to the next failures
+    #[tokio::test]
+    async fn test_set_rate_limit_applies() {
+        let (auth, clock) = mock_auth(Duration::from_secs(3600));
+        auth.set_rate_limit(AuthRateLimitConfig {
+            max_failed_attempts: 2,
+            block_duration: Duration::from_secs(5),
+            ..Default::default()
+        });
+
+        for _ in 0..2 {
+            let _ = auth.authenticate("wrong-token").await;
+        }
+        assert!(matches!(
+            auth.authenticate("test-token").await,
+            Err(AuthError::RateLimited)
+        ));
+
+        clock.advance(Duration::from_secs(6));
+        assert!(auth.authenticate("test-token").await.is_ok());
+    }
+
+    /// Test failures outside the attempt window do not accumulate
+    #[tokio::test]
+    async fn test_failed_attempt_window_resets() {
+        let (auth, clock) = mock_auth(Duration::from_secs(3600));
+
+        for _ in 0..MAX_FAILED_ATTEMPTS - 1 {
+            let _ = auth.authenticate("wrong-token").await;
+        }
+        clock.advance(ATTEMPT_WINDOW + Duration::from_secs(1));
+        let _ = auth.authenticate("wrong-token").await;
+
+        assert!(auth.authenticate("test-token").await.is_ok());
+    }
+
+    /// Test per-session request limit resets with the request window
+    #[tokio::test]
+    async fn test_session_request_window() {
+        let (auth, clock) = mock_auth(Duration::from_secs(3600));
+        let session = auth.authenticate("test-token").await.unwrap();
+
+        // authenticate() opens the window with a count of zero
+        for _ in 0..MAX_REQUESTS_PER_MINUTE {
+            auth.validate(&session).await.unwrap();
+        }
+        assert!(matches!(
+            auth.validate(&session).await,
+            Err(AuthError::SessionRateLimited)
+        ));
+
+        clock.advance(REQUEST_WINDOW + Duration::from_secs(1));
+        assert!(auth.validate(&session).await.is_ok());
+    }
+
+    /// Test rate limit resets after successful auth
+    #[tokio::test]
+    async fn test_rate_limit_reset_on_success() {
+        let auth = SessionAuth::new("correct-token", Duration::from_secs(3600));
+
+        // Make some failed attempts (but not enough to trigger rate limiting)
+        for _ in 0..3 {
+            let _ = auth.authenticate("wrong-token").await;
+        }
+
+        // Successful auth should reset the rate limiter
+        let result = auth.authenticate("correct-token").await;
+        assert!(result.is_ok());
+
+        // Should be able to continue authenticating
+        let result = auth.authenticate("correct-token").await;
+        assert!(result.is_ok());
+    }
+
+    /// Test the session cap evicts the least recently active idle session
+    #[tokio::test]
+    async fn test_session_limit_evicts_oldest_idle() {
+        let (auth, clock) = mock_auth(Duration::from_secs(3600));
+        let auth = auth.with_session_limit(SessionLimitConfig {
+            max_active_sessions: Some(2),
+            policy: SessionLimitPolicy::EvictOldestIdle,
+        });
+        let first = auth.authenticate("test-token").await.unwrap();
+        clock.advance(Duration::from_secs(1));
+        let second = auth.authenticate("test-token").await.unwrap();
+        clock.advance(Duration::from_secs(1));
+        auth.validate(&first).await.unwrap();
+
+        let third = auth.authenticate("test-token").await.unwrap();
+
+        assert!(auth.validate(&first).await.is_ok());
+        assert!(matches!(
+            auth.validate(&second).await,
+            Err(AuthError::SessionNotFound)
+        ));
+        assert!(auth.validate(&third).await.is_ok());
+    }
+
+    /// Test the eviction policy skips sessions with open connections
+    #[tokio::test]
+    async fn test_session_limit_keeps_connected_sessions() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600))
+            .with_session_limit(SessionLimitConfig {
+                max_active_sessions: Some(1),
+                policy: SessionLimitPolicy::EvictOldestIdle,
+            });
+        let session = auth.authenticate("test-token").await.unwrap();
+        auth.track_connection(&session).await.unwrap();
+
+        assert!(matches!(
+            auth.authenticate("test-token").await,
+            Err(AuthError::SessionLimitReached)
+        ));
+    }
+
+    /// Test the reject policy refuses sessions past the cap
+    #[tokio::test]
+    async fn test_session_limit_rejects() {
+        let (auth, clock) = mock_auth(Duration::from_secs(60));
+        let auth = auth.with_session_limit(SessionLimitConfig {
+            max_active_sessions: Some(2),
+            policy: SessionLimitPolicy::Reject,
+        });
+        let first = auth.authenticate("test-token").await.unwrap();
+        auth.authenticate("test-token").await.unwrap();
+
+        assert!(matches!(
+            auth.authenticate("test-token").await,
+            Err(AuthError::SessionLimitReached)
+        ));
+        assert!(auth.validate(&first).await.is_ok());
+
+        // Expired sessions free their slots
+        clock.advance(Duration::from_secs(61));
+        assert!(auth.authenticate("test-token").await.is_ok());
+    }
+
+    /// Test multiple sessions
+    #[tokio::test]
+    async fn test_multiple_sessions() {
+        let auth = SessionAuth::new("test-token", Duration::from_secs(3600));
+
+        let session1 = auth.authenticate("test-token").await.unwrap();
+        let session2 = auth.authenticate("test-token").await.unwrap();
+
+        // Sessions should be different
+        assert_ne!(session1, session2);
+
+        // Both should be valid
+        assert!(auth.validate(&session1).await.is_ok());
+        assert!(auth.validate(&session2).await.is_ok());
+    }
+
+    fn labelled_auth() -> SessionAuth {
+        SessionAuth::with_tokens(
+            &[
+                ("old".to_string(), "token-a".to_string()),
+                ("new".to_string(), "token-b".to_string()),
+            ],
+            Duration::from_secs(3600),
+        )
+    }
+
+    /// Test that every configured token authenticates
+    #[tokio::test]
+    async fn test_with_tokens_accepts_each_token() {
+        let auth = labelled_auth();
+        let a = auth.authenticate("token-a").await.unwrap();
+        let b = auth.authenticate("token-b").await.unwrap();
+        assert!(auth.validate(&a).await.is_ok());
+        assert!(auth.validate(&b).await.is_ok());
+    }
+
+    /// Test that sessions remember which token label they matched
+    #[tokio::test]
+    async fn test_session_label() {
+        let auth = labelled_auth();
+        let a = auth.authenticate("token-a").await.unwrap();
+        let b = auth.authenticate("token-b").await.unwrap();
+        assert_eq!(auth.session_label(&a).await.as_deref(), Some("old"));
+        assert_eq!(auth.session_label(&b).await.as_deref(), Some("new"));
+        assert_eq!(auth.session_label(&SessionToken("x".repeat(64))).await, None);
+
+        let single = SessionAuth::new("test-token", Duration::from_secs(3600));
+        let session = single.authenticate("test-token").await.unwrap();
+        assert_eq!(
+            single.session_label(&session).await.as_deref(),
+            Some(DEFAULT_TOKEN_LABEL)
+        );
+    }
+
+    /// Test that an unknown token counts toward the global rate limit
+    #[tokio::test]
+    async fn test_with_tokens_invalid_token_records_failure() {
+        let auth = labelled_auth();
+        assert!(matches!(
+            auth.authenticate("token-c").await,
+            Err(AuthError::InvalidToken)
+        ));
+        assert_eq!(auth.rate_limiter.failed_attempts.load(Ordering::SeqCst), 1);
+
+        for _ in 1..MAX_FAILED_ATTEMPTS {
+            let _ = auth.authenticate("token-c").await;
+        }
+        // Rate limiting is global: valid tokens are blocked too
+        assert!(matches!(
+            auth.authenticate("token-a").await,
+            Err(AuthError::RateLimited)
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+//! Rate limiting of failed handshakes.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::ipc::clock::Clock;
+
+/// Maximum failed authentication attempts before rate limiting kicks in.
+pub(super) const MAX_FAILED_ATTEMPTS: u64 = 5;
+
+/// Duration to block after too many failed attempts.
+pub(super) const RATE_LIMIT_DURATION: Duration = Duration::from_secs(30);
+
+/// Duration to track failed attempts for rate limiting.
+pub(super) const ATTEMPT_WINDOW: Duration = Duration::from_secs(60);
+
+/// How many failed handshakes block authentication, and for how long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AuthRateLimitConfig {
+    /// Failed attempts within `attempt_window` that start a block.
+    pub max_failed_attempts: u64,
+    /// How long every handshake is refused once blocked.
+    pub block_duration: Duration,
+    /// Window failed attempts are counted over.
+    pub attempt_window: Duration,
+}
+
+impl Default for AuthRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: MAX_FAILED_ATTEMPTS,
+            block_duration: RATE_LIMIT_DURATION,
+            attempt_window: ATTEMPT_WINDOW,
+        }
+    }
+}
+
+/// Rate limiter for authentication attempts.
+pub(super) struct RateLimiter {
+    /// Number of failed attempts in the current window.
+    pub(super) failed_attempts: AtomicU64,
+    /// Time of the first failed attempt in the current window.
+    window_start: Mutex<Option<Instant>>,
+    /// Time until rate limiting expires (if active).
+    blocked_until: Mutex<Option<Instant>>,
+    config: Mutex<AuthRateLimitConfig>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RateLimiter {
+    pub(super) fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            failed_attempts: AtomicU64::new(0),
+            window_start: Mutex::new(None),
+            blocked_until: Mutex::new(None),
+            config: Mutex::new(AuthRateLimitConfig::default()),
+        }
+    }
+
+    pub(super) fn config(&self) -> AuthRateLimitConfig {
+        *self.config.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Switch to `config`; a block already in place keeps its end time.
+    pub(super) fn set_config(&self, config: AuthRateLimitConfig) {
+        *self.config.lock().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
+    /// Check if authentication is currently rate limited.
+    pub(super) fn is_rate_limited(&self) -> bool {
+        // Check if we're in a blocked period
+        if let Ok(blocked_until) = self.blocked_until.lock() {
+            if let Some(until) = *blocked_until {
+                if self.clock.now() < until {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Record a failed authentication attempt.
+    pub(super) fn record_failure(&self) {
+        let now = self.clock.now();
+        let config = self.config();
+
+        // Check if we need to reset the window
+        if let Ok(window_start) = self.window_start.lock() {
+            let should_reset = window_start
+                .map(|start| now.duration_since(start) > config.attempt_window)
+                .unwrap_or(true);
+
+            if should_reset {
+                self.failed_attempts.store(1, Ordering::SeqCst);
+                drop(window_start);
+                if let Ok(mut ws) = self.window_start.lock() {
+                    *ws = Some(now);
+                }
+                return;
+            }
+        }
+
+        // Increment failed attempts
+        let attempts = self.failed_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+
+        // Check if we should block
+        if attempts >= config.max_failed_attempts {
+            if let Ok(mut blocked_until) = self.blocked_until.lock() {
+                *blocked_until = Some(now + config.block_duration);
+            }
+        }
+    }
+
+    /// Reset rate limiting after successful authentication.
+    pub(super) fn reset(&self) {
+        self.failed_attempts.store(0, Ordering::SeqCst);
+        if let Ok(mut window_start) = self.window_start.lock() {
+            *window_start = None;
+        }
+        if let Ok(mut blocked_until) = self.blocked_until.lock() {
+            *blocked_until = None;
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Time source for session expiry and rate-limit windows.
+//!
+//! Production code uses [`SystemClock`]. Tests inject a [`MockClock`] and
+//! advance it explicitly instead of sleeping.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Monotonic time source.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Real monotonic clock backed by [`Instant::now`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Manually advanced clock for deterministic timing tests.
+#[derive(Debug)]
+pub struct MockClock {
+    base: Instant,
+    offset: Mutex<Duration>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            offset: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut offset = self.offset.lock().unwrap_or_else(|p| p.into_inner());
+        *offset += by;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        let offset = *self.offset.lock().unwrap_or_else(|p| p.into_inner());
+        self.base + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advances_only_when_told() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now().duration_since(start), Duration::from_secs(90));
+    }
+}
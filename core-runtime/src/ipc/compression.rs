@@ -0,0 +1,234 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional per-session response compression.
+//!
+//! Clients request a codec in the handshake; the server acknowledges the
+//! codec it will use. Once a codec other than `None` is negotiated, every
+//! server frame payload starts with a one-byte codec flag:
+//!
+//! ```text
+//! [len: u32 LE][flag: u8][payload]
+//! ```
+//!
+//! Payloads below the threshold are sent with `FLAG_NONE`. Sessions that
+//! did not negotiate compression keep the legacy unflagged framing.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Frame flag: payload is uncompressed.
+pub const FLAG_NONE: u8 = 0;
+
+/// Frame flag: payload is zstd-compressed.
+pub const FLAG_ZSTD: u8 = 1;
+
+#[derive(Error, Debug)]
+pub enum CompressionError {
+    #[error("Empty frame")]
+    EmptyFrame,
+
+    #[error("Unknown codec flag: {0}")]
+    UnknownCodec(u8),
+
+    #[error("Compression failed: {0}")]
+    Failed(String),
+}
+
+/// Response compression codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+}
+
+impl Compression {
+    pub fn is_none(&self) -> bool {
+        *self == Compression::None
+    }
+}
+
+/// Server-side compression settings. Disabled by default.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressionConfig {
+    pub enabled: bool,
+    /// Payloads smaller than this are sent uncompressed.
+    pub threshold_bytes: usize,
+    /// zstd compression level.
+    pub level: i32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_bytes: 16 * 1024,
+            level: 3,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Pick the codec for a session given the client's request.
+    pub fn negotiate(&self, requested: Option<Compression>) -> Compression {
+        match requested {
+            Some(Compression::Zstd) if self.enabled => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// Per-connection frame payload encoder.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCodec {
+    compression: Compression,
+    threshold_bytes: usize,
+    level: i32,
+}
+
+impl FrameCodec {
+    /// Legacy framing: payloads are written as-is with no flag byte.
+    pub fn plain() -> Self {
+        Self {
+            compression: Compression::None,
+            threshold_bytes: usize::MAX,
+            level: 0,
+        }
+    }
+
+    /// Framing for a session that negotiated `compression`.
+    pub fn negotiated(compression: Compression, config: &CompressionConfig) -> Self {
+        Self {
+            compression,
+            threshold_bytes: config.threshold_bytes,
+            level: config.level,
+        }
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Encode a payload for the wire.
+    pub fn encode(&self, payload: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        if self.compression.is_none() {
+            return Ok(payload.to_vec());
+        }
+
+        if payload.len() >= self.threshold_bytes {
+            let compressed = zstd::bulk::compress(payload, self.level)
+                .map_err(|e| CompressionError::Failed(e.to_string()))?;
+            // Incompressible data is cheaper to send raw
+            if compressed.len() < payload.len() {
+                return Ok(with_flag(FLAG_ZSTD, &compressed));
+            }
+        }
+        Ok(with_flag(FLAG_NONE, payload))
+    }
+}
+
+fn with_flag(flag: u8, data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + 1);
+    out.push(flag);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Decode a flagged frame payload from a compression-enabled session.
+///
+/// # Security
+/// Decompressed output is capped at `max_size` to defeat decompression bombs.
+pub fn decode_frame(frame: &[u8], max_size: usize) -> Result<Vec<u8>, CompressionError> {
+    let (&flag, data) = frame.split_first().ok_or(CompressionError::EmptyFrame)?;
+    match flag {
+        FLAG_NONE => Ok(data.to_vec()),
+        FLAG_ZSTD => zstd::bulk::decompress(data, max_size)
+            .map_err(|e| CompressionError::Failed(e.to_string())),
+        other => Err(CompressionError::UnknownCodec(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::protocol::{decode_message, encode_message, InferenceResponse, IpcMessage};
+    use crate::ipc::RequestId;
+
+    const MAX: usize = 16 * 1024 * 1024;
+
+    fn zstd_codec() -> FrameCodec {
+        let config = CompressionConfig { enabled: true, ..Default::default() };
+        FrameCodec::negotiated(Compression::Zstd, &config)
+    }
+
+    #[test]
+    fn test_negotiation_is_off_by_default() {
+        let config = CompressionConfig::default();
+        assert_eq!(config.negotiate(Some(Compression::Zstd)), Compression::None);
+
+        let enabled = CompressionConfig { enabled: true, ..Default::default() };
+        assert_eq!(enabled.negotiate(Some(Compression::Zstd)), Compression::Zstd);
+        assert_eq!(enabled.negotiate(None), Compression::None);
+    }
+
+    #[test]
+    fn test_plain_codec_has_no_flag() {
+        let payload = b"{\"type\":\"metrics_request\"}";
+        assert_eq!(FrameCodec::plain().encode(payload).unwrap(), payload.to_vec());
+    }
+
+    #[test]
+    fn test_large_response_round_trip() {
+        let output = "embedding 0.12345, ".repeat(10_000);
+        let message = IpcMessage::InferenceResponse(InferenceResponse::success(
+            RequestId(7),
+            output,
+            10_000,
+            true,
+        ));
+        let payload = encode_message(&message).unwrap();
+
+        let frame = zstd_codec().encode(&payload).unwrap();
+        assert_eq!(frame[0], FLAG_ZSTD);
+        assert!(frame.len() < payload.len());
+
+        let decoded = decode_frame(&frame, MAX).unwrap();
+        assert_eq!(decoded, payload);
+        let roundtrip = decode_message(&decoded).unwrap();
+        assert_eq!(encode_message(&roundtrip).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_small_payload_sent_uncompressed() {
+        let frame = zstd_codec().encode(b"small").unwrap();
+        assert_eq!(frame, [&[FLAG_NONE][..], b"small"].concat());
+        assert_eq!(decode_frame(&frame, MAX).unwrap(), b"small");
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_output() {
+        let frame = zstd_codec().encode(&vec![b'a'; 1 << 20]).unwrap();
+        assert!(matches!(decode_frame(&frame, 1024), Err(CompressionError::Failed(_))));
+    }
+
+    #[test]
+    fn test_decompression_bomb_over_message_limit_rejected() {
+        // A few KB on the wire that would inflate past the 16 MB limit
+        let bomb = zstd::bulk::compress(&vec![0u8; MAX + 1], 19).unwrap();
+        assert!(bomb.len() < 64 * 1024);
+        let frame = with_flag(FLAG_ZSTD, &bomb);
+        assert!(matches!(decode_frame(&frame, MAX), Err(CompressionError::Failed(_))));
+
+        // Exactly at the limit still decodes
+        let at_limit = zstd_codec().encode(&vec![0u8; MAX]).unwrap();
+        assert_eq!(decode_frame(&at_limit, MAX).unwrap().len(), MAX);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_flag() {
+        assert!(matches!(decode_frame(&[9, 1, 2], MAX), Err(CompressionError::UnknownCodec(9))));
+        assert!(matches!(decode_frame(&[], MAX), Err(CompressionError::EmptyFrame)));
+    }
+}
@@ -0,0 +1,160 @@
+//! Re-reading tunable limits while the server runs.
+//!
+//! `ReloadConfig` reads the configuration again from its source and applies
+//! the fields listed in [`RELOADABLE_FIELDS`]. Every other difference is
+//! reported as needing a restart and left untouched.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+use super::protocol::{ConfigReloadResponse, EffectiveConfig};
+use crate::RuntimeConfig;
+
+/// Produces the configuration a `ReloadConfig` switches to, e.g. by
+/// re-reading the environment.
+pub type ConfigSource = Arc<dyn Fn() -> RuntimeConfig + Send + Sync>;
+
+/// Fields `ReloadConfig` applies without a restart.
+pub const RELOADABLE_FIELDS: &[&str] = &[
+    "request_queue.max_pending",
+    "session_timeout",
+    "resource_limits",
+    "auth_rate_limit",
+];
+
+/// The running configuration and where to re-read it from.
+pub(crate) struct ConfigReloader {
+    source: ConfigSource,
+    current: Mutex<RuntimeConfig>,
+}
+
+impl ConfigReloader {
+    pub(crate) fn new(current: RuntimeConfig, source: ConfigSource) -> Self {
+        Self {
+            source,
+            current: Mutex::new(current),
+        }
+    }
+
+    /// Re-read the source and fold its reloadable fields into the running
+    /// configuration, which is returned for the caller to apply.
+    pub(crate) fn reload(&self) -> (RuntimeConfig, ConfigReloadResponse) {
+        let loaded = (self.source)();
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut applied = Vec::new();
+        if current.request_queue.max_pending != loaded.request_queue.max_pending {
+            current.request_queue.max_pending = loaded.request_queue.max_pending;
+            applied.push("request_queue.max_pending".to_string());
+        }
+        if current.session_timeout != loaded.session_timeout {
+            current.session_timeout = loaded.session_timeout;
+            applied.push("session_timeout".to_string());
+        }
+        if current.resource_limits != loaded.resource_limits {
+            current.resource_limits = loaded.resource_limits.clone();
+            applied.push("resource_limits".to_string());
+        }
+        if current.auth_rate_limit != loaded.auth_rate_limit {
+            current.auth_rate_limit = loaded.auth_rate_limit;
+            applied.push("auth_rate_limit".to_string());
+        }
+
+        // Reloadable fields now match, so anything still different needs a restart
+        let restart_required = changed_fields(&current.effective(), &loaded.effective());
+        let response = ConfigReloadResponse {
+            applied,
+            restart_required,
+        };
+        (current.clone(), response)
+    }
+}
+
+/// Names of the top-level fields and sections that differ between `a` and `b`.
+fn changed_fields(a: &EffectiveConfig, b: &EffectiveConfig) -> Vec<String> {
+    let (Ok(Value::Object(a)), Ok(Value::Object(b))) =
+        (serde_json::to_value(a), serde_json::to_value(b))
+    else {
+        return Vec::new();
+    };
+    let mut changed = BTreeSet::new();
+    for (key, value) in &a {
+        match (value, b.get(key)) {
+            (Value::Object(sections_a), Some(Value::Object(sections_b))) => {
+                let names = sections_a.keys().chain(sections_b.keys());
+                changed.extend(
+                    names
+                        .filter(|name| sections_a.get(*name) != sections_b.get(*name))
+                        .cloned(),
+                );
+            }
+            (value, other) if Some(value) != other => {
+                changed.insert(key.clone());
+            }
+            _ => {}
+        }
+    }
+    changed.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn reloader(source: fn() -> RuntimeConfig) -> ConfigReloader {
+        ConfigReloader::new(RuntimeConfig::default(), Arc::new(source))
+    }
+
+    #[test]
+    fn test_unchanged_source_reports_nothing() {
+        let (_, response) = reloader(RuntimeConfig::default).reload();
+        assert_eq!(response, ConfigReloadResponse::default());
+    }
+
+    #[test]
+    fn test_reloadable_fields_applied() {
+        let reloader = reloader(|| {
+            let mut config = RuntimeConfig::default();
+            config.request_queue.max_pending = 8;
+            config.session_timeout = Duration::from_secs(60);
+            config.resource_limits.max_concurrent = 3;
+            config.auth_rate_limit.max_failed_attempts = 10;
+            config
+        });
+
+        let (config, response) = reloader.reload();
+
+        assert_eq!(response.applied, RELOADABLE_FIELDS);
+        assert!(response.restart_required.is_empty());
+        assert_eq!(config.request_queue.max_pending, 8);
+        assert_eq!(config.session_timeout, Duration::from_secs(60));
+        assert_eq!(config.resource_limits.max_concurrent, 3);
+        assert_eq!(config.auth_rate_limit.max_failed_attempts, 10);
+
+        // Already applied, so a second reload changes nothing
+        assert_eq!(reloader.reload().1, ConfigReloadResponse::default());
+    }
+
+    #[test]
+    fn test_immutable_fields_reported_not_applied() {
+        let (config, response) = reloader(|| RuntimeConfig {
+            base_path: PathBuf::from("/srv/models"),
+            strict_protocol: true,
+            max_prompt_tokens: 100,
+            ..Default::default()
+        })
+        .reload();
+
+        assert!(response.applied.is_empty());
+        assert_eq!(
+            response.restart_required,
+            ["base_path", "max_prompt_tokens", "strict_protocol"]
+        );
+        assert_eq!(config.base_path, PathBuf::from("."));
+        assert!(!config.strict_protocol);
+    }
+}
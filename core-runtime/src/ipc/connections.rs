@@ -1,19 +1,59 @@
 //! Connection pool management with limits.
 //!
-//! Provides global connection limiting with RAII guards.
+//! Provides global connection limiting with RAII guards. Guards also track
+//! when their connection last did anything, so the server can reap idle
+//! connections that would otherwise hold a slot forever.
 
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
 
 /// Configuration for connection pool.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ConnectionConfig {
     pub max_connections: usize,
+    /// Close connections that send no message for this long. Time spent
+    /// answering (including streaming a generation) does not count as idle.
+    /// `None` keeps connections open until the client hangs up.
+    pub idle_timeout: Option<Duration>,
 }
 
 impl Default for ConnectionConfig {
     fn default() -> Self {
-        Self { max_connections: 64 }
+        Self {
+            max_connections: 64,
+            idle_timeout: None,
+        }
+    }
+}
+
+/// Last time a connection was active, as an offset from when it opened.
+struct Activity {
+    opened: Instant,
+    last_ns: AtomicU64,
+}
+
+impl Activity {
+    fn new() -> Self {
+        Self {
+            opened: Instant::now(),
+            last_ns: AtomicU64::new(0),
+        }
+    }
+
+    fn touch(&self) {
+        let elapsed = self.opened.elapsed().as_nanos() as u64;
+        self.last_ns.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    fn last(&self) -> Instant {
+        self.opened + Duration::from_nanos(self.last_ns.load(Ordering::Relaxed))
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last().elapsed()
     }
 }
 
@@ -45,7 +85,10 @@ impl ConnectionPool {
                 .compare_exchange(current, current + 1, Ordering::SeqCst, Ordering::Relaxed)
                 .is_ok()
             {
-                return Some(ConnectionGuard { pool: self });
+                return Some(ConnectionGuard {
+                    pool: self,
+                    activity: Activity::new(),
+                });
             }
             // CAS failed, retry
         }
@@ -61,6 +104,11 @@ impl ConnectionPool {
         self.config.max_connections
     }
 
+    /// How long a connection may stay idle before it is closed.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.config.idle_timeout
+    }
+
     /// Try to acquire a connection slot with owned Arc guard.
     /// Suitable for spawned tasks that require `'static` lifetime.
     pub fn try_acquire_owned(self: &Arc<Self>) -> Option<OwnedConnectionGuard> {
@@ -76,6 +124,7 @@ impl ConnectionPool {
             {
                 return Some(OwnedConnectionGuard {
                     pool: Arc::clone(self),
+                    activity: Activity::new(),
                 });
             }
         }
@@ -89,6 +138,24 @@ impl ConnectionPool {
 /// RAII guard that releases connection on drop.
 pub struct ConnectionGuard<'a> {
     pool: &'a ConnectionPool,
+    activity: Activity,
+}
+
+impl ConnectionGuard<'_> {
+    /// Record activity on the connection now.
+    pub fn touch(&self) {
+        self.activity.touch();
+    }
+
+    /// When the connection was last active (opening counts).
+    pub fn last_activity(&self) -> Instant {
+        self.activity.last()
+    }
+
+    /// Time since the connection was last active.
+    pub fn idle_for(&self) -> Duration {
+        self.activity.idle_for()
+    }
 }
 
 impl Drop for ConnectionGuard<'_> {
@@ -100,6 +167,29 @@ impl Drop for ConnectionGuard<'_> {
 /// Owned RAII guard for spawned tasks (`'static` lifetime).
 pub struct OwnedConnectionGuard {
     pool: Arc<ConnectionPool>,
+    activity: Activity,
+}
+
+impl OwnedConnectionGuard {
+    /// Record activity on the connection now.
+    pub fn touch(&self) {
+        self.activity.touch();
+    }
+
+    /// When the connection was last active (opening counts).
+    pub fn last_activity(&self) -> Instant {
+        self.activity.last()
+    }
+
+    /// Time since the connection was last active.
+    pub fn idle_for(&self) -> Duration {
+        self.activity.idle_for()
+    }
+
+    /// How long the connection may stay idle before it is closed.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.pool.idle_timeout()
+    }
 }
 
 impl Drop for OwnedConnectionGuard {
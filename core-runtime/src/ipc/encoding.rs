@@ -69,7 +69,9 @@ impl TokenEncoder for V2Encoder {
 /// Get encoder for a given protocol version.
 pub fn get_encoder(version: super::protocol::ProtocolVersion) -> Box<dyn TokenEncoder + Send + Sync> {
     match version {
-        super::protocol::ProtocolVersion::V1 => Box::new(V1Encoder),
+        super::protocol::ProtocolVersion::V1 | super::protocol::ProtocolVersion::Unknown => {
+            Box::new(V1Encoder)
+        }
         super::protocol::ProtocolVersion::V2 => Box::new(V2Encoder),
     }
 }
@@ -0,0 +1,208 @@
+//! Metrics, warmup, ping and configuration handlers.
+
+use super::IpcHandler;
+use crate::engine::InferenceParams;
+use crate::engine::embedding_cache::{
+    EMBEDDING_CACHE_HITS_TOTAL, EMBEDDING_CACHE_HIT_RATE, EMBEDDING_CACHE_MISSES_TOTAL,
+};
+use crate::health::LAST_SUCCESS_AGE_GAUGE;
+use crate::ipc::auth::{SessionToken, ADMIN_TOKEN_LABEL};
+use crate::ipc::protocol::{IpcMessage, PingModelResponse, WarmupResponse};
+use crate::scheduler::{LoadSample, Priority, RequestOrigin};
+use crate::telemetry::{self, model_latency_histogram, MetricsSnapshot, REQUEST_LATENCY_HISTOGRAM};
+
+/// Minimal prompt a `WarmupRequest` generates from.
+const WARMUP_PROMPT: &str = "warmup";
+
+impl IpcHandler {
+    /// Metrics store snapshot plus values sampled at request time.
+    pub(super) async fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.update_load_factor().await;
+        self.metrics_store.set_gauge(
+            LAST_SUCCESS_AGE_GAUGE,
+            self.health.last_success_age().as_secs_f64(),
+        );
+        let mut snapshot = self.metrics_store.snapshot();
+        self.protocol_stats.export_connections(&mut snapshot.counters);
+        let embeddings = self.inference_engine.embedding_cache().stats();
+        snapshot
+            .counters
+            .insert(EMBEDDING_CACHE_HITS_TOTAL.into(), embeddings.hits);
+        snapshot
+            .counters
+            .insert(EMBEDDING_CACHE_MISSES_TOTAL.into(), embeddings.misses);
+        snapshot
+            .gauges
+            .insert(EMBEDDING_CACHE_HIT_RATE.into(), embeddings.hit_rate());
+        snapshot
+    }
+
+    /// Sample current load and publish it as the `core_load_factor` gauge.
+    pub(super) async fn update_load_factor(&self) {
+        let sample = LoadSample {
+            queue_depth: self.queue.len().await,
+            queue_capacity: self.queue.max_pending(),
+            in_flight: self.shutdown.in_flight_count() as usize,
+            queue_wait_ms: self
+                .queue
+                .oldest_wait()
+                .await
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        };
+        let load_factor = sample.load_factor(&self.config.load_factor);
+        self.metrics_store.set_gauge("core_load_factor", load_factor);
+        telemetry::record_load_factor(load_factor);
+    }
+
+    /// Generate `tokens` tokens on `model_id`, queued at low priority.
+    pub(super) async fn handle_warmup(&self, model_id: String, tokens: usize) -> WarmupResponse {
+        let start = std::time::Instant::now();
+        let params = InferenceParams {
+            max_tokens: tokens.max(1),
+            ..Default::default()
+        };
+        let result = match self
+            .queue
+            .enqueue_tracked(
+                RequestOrigin::Ipc,
+                None,
+                model_id.clone(),
+                WARMUP_PROMPT.to_string(),
+                params.clone(),
+                Priority::Low,
+            )
+            .await
+        {
+            Ok(ticket) => {
+                let result = self.inference_engine.run(&model_id, WARMUP_PROMPT, &params).await;
+                self.queue.complete(ticket.id).await;
+                result.map(|_| ()).map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        match result {
+            Ok(()) => WarmupResponse::success(model_id, elapsed_ms),
+            Err(e) => WarmupResponse::error(model_id, e, elapsed_ms),
+        }
+    }
+
+    /// Record a successfully completed request's total latency, overall and
+    /// for its model.
+    pub(super) fn record_completion(&self, model_id: &str, elapsed_ms: f64) {
+        self.metrics_store
+            .record_histogram(REQUEST_LATENCY_HISTOGRAM, elapsed_ms);
+        self.metrics_store
+            .record_histogram(&model_latency_histogram(model_id), elapsed_ms);
+    }
+
+    pub(super) async fn handle_ping(&self, model_id: String) -> PingModelResponse {
+        match self.inference_engine.ping(&model_id).await {
+            Ok(latency_ms) => PingModelResponse {
+                model_id,
+                ok: true,
+                latency_ms,
+                error: None,
+            },
+            Err(e) => PingModelResponse {
+                model_id,
+                ok: false,
+                latency_ms: 0,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Run `model_id`'s servability checks, admitted like a low-priority
+    /// one-token request since they step the model.
+    pub(super) async fn handle_diagnose(
+        &self,
+        model_id: String,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        let refused = |message: String| IpcMessage::Error { code: 503, message };
+        let Some(_guard) = self.shutdown.track() else {
+            return refused("Server is shutting down".into());
+        };
+        if let Err(e) = self.config.memory_floor.check() {
+            return refused(e.to_string());
+        }
+        let params = InferenceParams {
+            max_tokens: 1,
+            ..Default::default()
+        };
+        let ticket = match self
+            .queue
+            .enqueue_tracked(
+                RequestOrigin::Ipc,
+                session.map(SessionToken::fingerprint),
+                model_id.clone(),
+                String::new(),
+                params,
+                Priority::Low,
+            )
+            .await
+        {
+            Ok(ticket) => ticket,
+            Err(e) => return refused(e.to_string()),
+        };
+        let report = self.inference_engine.diagnose(&model_id).await;
+        self.queue.complete(ticket.id).await;
+        IpcMessage::DiagnoseModelResponse(report)
+    }
+
+    /// Redacted effective configuration, if the runtime provided one.
+    pub(super) fn handle_config(&self) -> IpcMessage {
+        let effective_config = self
+            .effective_config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        match effective_config {
+            Some(config) => IpcMessage::ConfigResponse(config),
+            None => IpcMessage::Error {
+                code: 404,
+                message: "Effective configuration not available".into(),
+            },
+        }
+    }
+
+    /// Re-read the configuration and apply its reloadable fields, for
+    /// sessions opened with the admin token only.
+    pub(super) async fn handle_reload_config(&self, session: Option<&SessionToken>) -> IpcMessage {
+        if !self.config.allow_config_reload || !self.is_admin(session).await {
+            return IpcMessage::Error {
+                code: 403,
+                message: "Config reload requires an admin session".into(),
+            };
+        }
+        let Some(reloader) = &self.reloader else {
+            return IpcMessage::Error {
+                code: 503,
+                message: "Config reload not available".into(),
+            };
+        };
+        let (config, response) = reloader.reload();
+        self.queue.set_max_pending(config.request_queue.max_pending);
+        self.auth.set_session_timeout(config.session_timeout);
+        self.auth.set_rate_limit(config.auth_rate_limit);
+        self.resource_limits.set_config(config.resource_limits.clone());
+        let mut effective_config = self.effective_config.write().unwrap_or_else(|e| e.into_inner());
+        if effective_config.is_some() {
+            *effective_config = Some(config.effective());
+        }
+        if !response.applied.is_empty() {
+            tracing::info!(applied = ?response.applied, "configuration reloaded");
+        }
+        IpcMessage::ConfigReloadResponse(response)
+    }
+
+    /// Whether `session` authenticated with the admin token.
+    async fn is_admin(&self, session: Option<&SessionToken>) -> bool {
+        let Some(session) = session else {
+            return false;
+        };
+        self.auth.session_label(session).await.as_deref() == Some(ADMIN_TOKEN_LABEL)
+    }
+}
@@ -0,0 +1,59 @@
+//! Optional collaborators attached to an [`IpcHandler`] after construction.
+
+use std::sync::{Arc, RwLock};
+
+use super::swap::swap_manager;
+use super::IpcHandler;
+use crate::engine::{ModelFactory, OutputFilter};
+use crate::ipc::config_reload::{ConfigReloader, ConfigSource};
+use crate::ipc::protocol::EffectiveConfig;
+use crate::models::{FlightTracker, ModelLoader};
+use crate::telemetry::SpanCollector;
+use crate::RuntimeConfig;
+
+impl IpcHandler {
+    /// Track in-flight requests in `flights`, e.g. one shared with a swap manager.
+    pub fn with_flight_tracker(mut self, flights: Arc<FlightTracker>) -> Self {
+        self.swaps = swap_manager(&self.model_registry, &self.router, &flights);
+        self.flights = flights;
+        self
+    }
+
+    /// Serve `LoadModelRequest` and `SwapModel` by loading weights with
+    /// `factory`.
+    pub fn with_model_factory(mut self, factory: ModelFactory) -> Self {
+        self.model_factory = Some(factory);
+        self
+    }
+
+    /// Serve `LoadModelRequest` from files resolved by `loader`.
+    pub fn with_model_loader(mut self, loader: Arc<ModelLoader>) -> Self {
+        self.model_loader = Some(loader);
+        self
+    }
+
+    /// Filter generated output with `filter` (no filtering by default).
+    pub fn with_output_filter(mut self, filter: OutputFilter) -> Self {
+        self.output_filter = filter;
+        self
+    }
+
+    /// Record request spans into `spans` instead of a private collector.
+    pub fn with_span_collector(mut self, spans: Arc<SpanCollector>) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    /// Report `config` in response to authenticated `ConfigRequest`s.
+    pub fn with_effective_config(mut self, config: EffectiveConfig) -> Self {
+        self.effective_config = RwLock::new(Some(config));
+        self
+    }
+
+    /// Serve `ReloadConfig` by re-reading `source`; `current` is the
+    /// configuration the runtime was started with.
+    pub fn with_config_source(mut self, current: RuntimeConfig, source: ConfigSource) -> Self {
+        self.reloader = Some(ConfigReloader::new(current, source));
+        self
+    }
+}
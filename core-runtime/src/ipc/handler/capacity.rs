@@ -0,0 +1,26 @@
+//! Counting requests against their model's registry capacity.
+
+use std::future::Future;
+
+use super::IpcHandler;
+
+impl IpcHandler {
+    /// Run `serve` counted as one in-flight request on `model_id`, so
+    /// least-loaded routing sees the model's free capacity shrink while it
+    /// runs. Models not in the registry are served uncounted.
+    pub(super) async fn with_model_capacity<T>(
+        &self,
+        model_id: &str,
+        serve: impl Future<Output = T>,
+    ) -> T {
+        let counted = match self.inference_engine.get_handle(model_id).await {
+            Some(handle) if self.model_registry.begin_request(handle).await => Some(handle),
+            _ => None,
+        };
+        let output = serve.await;
+        if let Some(handle) = counted {
+            self.model_registry.end_request(handle).await;
+        }
+        output
+    }
+}
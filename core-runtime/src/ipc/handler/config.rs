@@ -0,0 +1,94 @@
+//! Configuration for the IPC handler.
+
+use thiserror::Error;
+
+use crate::ipc::compression::CompressionConfig;
+use crate::ipc::protocol::DEFAULT_MAX_PROMPT_BYTES;
+use crate::ipc::protocol_stats::DecodeErrorPolicy;
+use crate::ipc::stream_coalesce::StreamCoalesceConfig;
+use crate::memory::{MemoryFloorConfig, ResourceLimitsConfig};
+use crate::models::CircuitBreakerConfig;
+use crate::scheduler::{LoadFactorConfig, OutputCacheConfig, Priority};
+
+/// Default cap on client-requested priority: `High`, keeping `Critical`
+/// for server-internal work.
+pub const DEFAULT_MAX_CLIENT_PRIORITY: Priority = Priority::High;
+
+/// Default cap on streaming requests one session may have open at once.
+pub const DEFAULT_MAX_STREAMS_PER_SESSION: usize = 4;
+
+/// Setting an IPC handler cannot run with.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum HandlerConfigError {
+    /// Every streaming request would be refused.
+    #[error("max_streams_per_session must be at least 1")]
+    ZeroStreamsPerSession,
+}
+
+/// Configuration for IPC handler.
+#[derive(Debug, Clone)]
+pub struct IpcHandlerConfig {
+    pub require_auth: bool,
+    /// Limits used to normalize the `core_load_factor` autoscaling gauge.
+    pub load_factor: LoadFactorConfig,
+    /// Response compression offered to clients at handshake.
+    pub compression: CompressionConfig,
+    /// Token coalescing for streaming responses (per-token by default).
+    pub stream_coalesce: StreamCoalesceConfig,
+    /// Available system memory required to admit a request (off by default).
+    pub memory_floor: MemoryFloorConfig,
+    /// Decode error rate at which a connection is reported (and optionally dropped).
+    pub decode_errors: DecodeErrorPolicy,
+    /// Model used when an inference request leaves `model_id` empty.
+    pub default_model: Option<String>,
+    /// Reject messages carrying fields the protocol does not define.
+    pub strict_protocol: bool,
+    /// Largest text prompt admitted, in bytes.
+    pub max_prompt_bytes: usize,
+    /// Memory and concurrency limits per inference call; changed by
+    /// `ReloadConfig`.
+    pub resource_limits: ResourceLimitsConfig,
+    /// Highest priority a client may request; higher requests are capped.
+    pub max_client_priority: Priority,
+    /// Streaming requests one session may have open at once; further
+    /// streams are rejected until one finishes.
+    pub max_streams_per_session: usize,
+    /// Sharing of results between identical deterministic requests.
+    pub output_cache: OutputCacheConfig,
+    /// Accept `ReloadConfig` from admin sessions (off by default).
+    pub allow_config_reload: bool,
+    /// Per-model circuit breaking on inference failures.
+    pub circuit_breaker: CircuitBreakerConfig,
+}
+
+impl Default for IpcHandlerConfig {
+    fn default() -> Self {
+        Self {
+            require_auth: true,
+            load_factor: LoadFactorConfig::default(),
+            compression: CompressionConfig::default(),
+            stream_coalesce: StreamCoalesceConfig::default(),
+            memory_floor: MemoryFloorConfig::default(),
+            decode_errors: DecodeErrorPolicy::default(),
+            default_model: None,
+            strict_protocol: false,
+            max_prompt_bytes: DEFAULT_MAX_PROMPT_BYTES,
+            resource_limits: ResourceLimitsConfig::unlimited(),
+            max_client_priority: DEFAULT_MAX_CLIENT_PRIORITY,
+            max_streams_per_session: DEFAULT_MAX_STREAMS_PER_SESSION,
+            output_cache: OutputCacheConfig::default(),
+            allow_config_reload: false,
+            circuit_breaker: CircuitBreakerConfig::default(),
+        }
+    }
+}
+
+impl IpcHandlerConfig {
+    /// Reject settings that would leave the handler unusable.
+    pub fn validate(&self) -> Result<(), HandlerConfigError> {
+        if self.max_streams_per_session == 0 {
+            return Err(HandlerConfigError::ZeroStreamsPerSession);
+        }
+        Ok(())
+    }
+}
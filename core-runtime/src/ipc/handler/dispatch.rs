@@ -0,0 +1,238 @@
+//! Routing of decoded IPC messages to their handlers.
+
+use super::{HandlerError, IpcHandler};
+use crate::ipc::auth::SessionToken;
+use crate::ipc::compression::Compression;
+use crate::ipc::protocol::{
+    IpcMessage, ProtocolError, ProtocolVersion, RequestId, MAX_PROTOCOL_VERSION,
+    MIN_PROTOCOL_VERSION,
+};
+use crate::telemetry;
+
+/// Response to a message that is not a request.
+fn unexpected() -> IpcMessage {
+    IpcMessage::Error {
+        code: 400,
+        message: "Unexpected message type".into(),
+    }
+}
+
+impl IpcHandler {
+    pub(super) async fn handle_message(
+        &self,
+        message: IpcMessage,
+        session: Option<&SessionToken>,
+    ) -> Result<(IpcMessage, Option<SessionToken>), HandlerError> {
+        let response = match message {
+            IpcMessage::Handshake {
+                token,
+                protocol_version,
+                compression,
+                strict_version,
+            } => {
+                return self
+                    .handshake(token, protocol_version, compression, strict_version)
+                    .await
+            }
+            IpcMessage::InferenceRequest(request) => {
+                self.require_auth(session).await?;
+                IpcMessage::InferenceResponse(self.handle_inference(request, session).await)
+            }
+            IpcMessage::ChatRequest(request) => {
+                self.require_auth(session).await?;
+                IpcMessage::InferenceResponse(self.handle_chat(request, session).await)
+            }
+            // NO AUTH REQUIRED (orchestrator pattern)
+            m @ (IpcMessage::HealthCheck { .. }
+            | IpcMessage::MetricsRequest
+            | IpcMessage::PrometheusMetricsRequest
+            | IpcMessage::SpansRequest { .. }) => self.handle_observability(m).await,
+            // NO AUTH REQUIRED (orchestrator pattern, same as health/metrics)
+            m @ (IpcMessage::ModelsRequest
+            | IpcMessage::ModelInfoRequest { .. }
+            | IpcMessage::ModelStatsRequest { .. }
+            | IpcMessage::WarmupRequest(_)
+            | IpcMessage::PingModelRequest(_)) => self.handle_model_query(m).await,
+            // AUTH REQUIRED: steps the model, so it is admitted like inference
+            IpcMessage::DiagnoseModelRequest(request) => {
+                self.require_auth(session).await?;
+                self.handle_diagnose(request.model_id, session).await
+            }
+            // AUTH REQUIRED: changes what every caller can use
+            m @ (IpcMessage::LoadModelRequest { .. }
+            | IpcMessage::UnloadModelRequest { .. }
+            | IpcMessage::DrainModel { .. }
+            | IpcMessage::SwapModel { .. }) => {
+                self.require_auth(session).await?;
+                self.handle_model_admin(m).await
+            }
+            // AUTH REQUIRED: session-scoped or exposes other sessions' work
+            m @ (IpcMessage::CancelRequest { .. }
+            | IpcMessage::CancelAllRequest
+            | IpcMessage::ActiveRequestsRequest) => {
+                self.require_auth(session).await?;
+                self.handle_request_control(m, session).await
+            }
+            // ADMIN REQUIRED: changes limits for every caller
+            IpcMessage::ReloadConfig => {
+                self.require_auth(session).await?;
+                self.handle_reload_config(session).await
+            }
+            // AUTH REQUIRED: exposes or changes deployment settings
+            m @ (IpcMessage::ConfigRequest
+            | IpcMessage::SetExperiment { .. }
+            | IpcMessage::GetExperimentResults) => {
+                self.require_auth(session).await?;
+                self.handle_settings(m).await
+            }
+            _ => unexpected(),
+        };
+        Ok((response, None))
+    }
+
+    /// Negotiate the protocol version and open a session for `token`.
+    async fn handshake(
+        &self,
+        token: String,
+        protocol_version: Option<ProtocolVersion>,
+        compression: Option<Compression>,
+        strict_version: bool,
+    ) -> Result<(IpcMessage, Option<SessionToken>), HandlerError> {
+        // A strict client is rejected before a session is created for it
+        let downgrade_reason = ProtocolVersion::downgrade_reason(protocol_version);
+        if let (true, Some(requested)) = (strict_version, protocol_version) {
+            if downgrade_reason.is_some() {
+                return Err(HandlerError::Protocol(
+                    ProtocolError::UnsupportedProtocolVersion {
+                        requested,
+                        min: MIN_PROTOCOL_VERSION,
+                        max: MAX_PROTOCOL_VERSION,
+                    },
+                ));
+            }
+        }
+        let session_token = self.auth.authenticate(&token).await?;
+        let response = IpcMessage::HandshakeAck {
+            session_id: session_token.as_str().to_string(),
+            protocol_version: ProtocolVersion::negotiate(protocol_version),
+            compression: self.config.compression.negotiate(compression),
+            downgrade_reason,
+        };
+        Ok((response, Some(session_token)))
+    }
+
+    async fn handle_observability(&self, message: IpcMessage) -> IpcMessage {
+        match message {
+            IpcMessage::HealthCheck { check_type } => {
+                IpcMessage::HealthResponse(self.health_handler.handle(check_type).await)
+            }
+            IpcMessage::MetricsRequest => IpcMessage::MetricsResponse(self.metrics_snapshot().await),
+            IpcMessage::PrometheusMetricsRequest => {
+                let text = telemetry::encode_prometheus(&self.metrics_snapshot().await);
+                IpcMessage::PrometheusMetricsResponse { text }
+            }
+            IpcMessage::SpansRequest {
+                max_count,
+                request_id,
+            } => {
+                let spans = match request_id {
+                    Some(id) => self.spans.for_request(id.0, max_count),
+                    None => self.spans.drain(max_count),
+                };
+                IpcMessage::SpansResponse { spans }
+            }
+            _ => unexpected(),
+        }
+    }
+
+    async fn handle_model_query(&self, message: IpcMessage) -> IpcMessage {
+        match message {
+            IpcMessage::ModelsRequest => IpcMessage::ModelsResponse(self.handle_models_request().await),
+            IpcMessage::ModelInfoRequest { model_id } => self.handle_model_info(model_id).await,
+            IpcMessage::ModelStatsRequest { model_id } => self.handle_model_stats(model_id).await,
+            IpcMessage::WarmupRequest(request) => IpcMessage::WarmupResponse(
+                self.handle_warmup(request.model_id, request.tokens).await,
+            ),
+            IpcMessage::PingModelRequest(request) => {
+                IpcMessage::PingModelResponse(self.handle_ping(request.model_id).await)
+            }
+            _ => unexpected(),
+        }
+    }
+
+    async fn handle_model_admin(&self, message: IpcMessage) -> IpcMessage {
+        match message {
+            IpcMessage::LoadModelRequest { model_id, path } => self.handle_load(model_id, path).await,
+            IpcMessage::UnloadModelRequest { model_id } => self.handle_unload(model_id).await,
+            IpcMessage::DrainModel {
+                model_id,
+                timeout_ms,
+            } => self.handle_drain(model_id, timeout_ms).await,
+            IpcMessage::SwapModel { model_id, new_path } => self.handle_swap(model_id, new_path).await,
+            _ => unexpected(),
+        }
+    }
+
+    async fn handle_request_control(
+        &self,
+        message: IpcMessage,
+        session: Option<&SessionToken>,
+    ) -> IpcMessage {
+        match message {
+            IpcMessage::CancelRequest { request_id } => IpcMessage::CancelResponse {
+                request_id,
+                cancelled: self.cancel_owned(request_id, session).await,
+            },
+            IpcMessage::CancelAllRequest => IpcMessage::CancelAllResponse {
+                cancelled: self.queue.cancel_all().await,
+            },
+            IpcMessage::ActiveRequestsRequest => IpcMessage::ActiveRequestsResponse {
+                requests: self.active.snapshot(),
+            },
+            _ => unexpected(),
+        }
+    }
+
+    async fn handle_settings(&self, message: IpcMessage) -> IpcMessage {
+        match message {
+            IpcMessage::ConfigRequest => self.handle_config(),
+            IpcMessage::SetExperiment { experiment } => {
+                match self.router.set_experiment(experiment).await {
+                    Ok(active) => IpcMessage::ExperimentResults {
+                        results: active.map(|experiment| experiment.results()),
+                    },
+                    Err(e) => IpcMessage::Error {
+                        code: 400,
+                        message: format!("Invalid experiment: {}", e),
+                    },
+                }
+            }
+            IpcMessage::GetExperimentResults => IpcMessage::ExperimentResults {
+                results: self.router.experiment().await.map(|e| e.results()),
+            },
+            _ => unexpected(),
+        }
+    }
+
+    /// Cancel `session`'s stream or queued request `request_id`. A request
+    /// that already completed, or belongs to another session, is a no-op.
+    async fn cancel_owned(&self, request_id: RequestId, session: Option<&SessionToken>) -> bool {
+        if session.is_some_and(|session| self.streams.cancel(session, request_id)) {
+            return true;
+        }
+        let owner = session.map(SessionToken::fingerprint);
+        self.queue
+            .cancel_for_session(request_id.0, owner.as_deref())
+            .await
+    }
+
+    async fn require_auth(&self, session: Option<&SessionToken>) -> Result<(), HandlerError> {
+        if !self.config.require_auth {
+            return Ok(());
+        }
+
+        let token = session.ok_or(HandlerError::NotAuthenticated)?;
+        self.auth.validate(token).await?;
+        Ok(())
+    }
+}
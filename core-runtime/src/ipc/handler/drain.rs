@@ -0,0 +1,64 @@
+//! Per-model drain tracking.
+
+use std::time::Duration;
+
+use super::IpcHandler;
+use crate::ipc::protocol::{DrainResponse, IpcMessage};
+use crate::models::FlightGuard;
+
+impl IpcHandler {
+    /// Count a request against `model_id` for drains, or refuse it if the
+    /// model is draining.
+    ///
+    /// Tracks before checking, so a drain that has marked the model either
+    /// sees this request in flight or this request sees the mark.
+    pub(super) async fn begin_flight(&self, model_id: &str) -> Result<Option<FlightGuard>, String> {
+        let flight = match self.inference_engine.get_handle(model_id).await {
+            Some(handle) => Some(self.flights.track(handle).await),
+            None => None,
+        };
+        let draining = self
+            .draining
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(model_id);
+        if draining {
+            return Err(format!(
+                "Model '{}' is draining and not accepting new requests",
+                model_id
+            ));
+        }
+        Ok(flight)
+    }
+
+    /// Stop routing requests to `model_id`, then wait up to `timeout_ms`
+    /// for its in-flight requests. The model stays draining afterwards.
+    pub(super) async fn handle_drain(&self, model_id: String, timeout_ms: u64) -> IpcMessage {
+        let Some(handle) = self.inference_engine.get_handle(&model_id).await else {
+            return IpcMessage::Error {
+                code: 404,
+                message: format!("Model not found: {}", model_id),
+            };
+        };
+        self.draining
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(model_id.clone());
+
+        let drained = self
+            .flights
+            .drain(handle, Duration::from_millis(timeout_ms))
+            .await
+            .is_ok();
+        let in_flight_remaining = if drained {
+            0
+        } else {
+            self.flights.in_flight_count(handle).await as usize
+        };
+        IpcMessage::DrainResponse(DrainResponse {
+            model_id,
+            drained,
+            in_flight_remaining,
+        })
+    }
+}
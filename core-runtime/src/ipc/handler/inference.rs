@@ -0,0 +1,212 @@
+//! Non-streaming inference handlers.
+
+use std::time::Instant;
+
+use super::outcome::Leader;
+use super::IpcHandler;
+use crate::ab_testing::ExperimentAssignment;
+use crate::engine::{ErrorCategory, InferenceError, InferenceResult};
+use crate::health::WARMING_UP_MESSAGE;
+use crate::memory::ResourceGuard;
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{ChatRequest, InferenceRequest, InferenceResponse, RequestId};
+use crate::models::{CircuitPass, FlightGuard};
+use crate::scheduler::{QueueError, QueueTicket};
+use crate::shutdown::ShutdownGuard;
+use crate::telemetry::span_export::now_unix_ns;
+use crate::telemetry::{self, RequestTrace};
+
+/// A request accepted to run, counted in flight while held.
+struct Admission {
+    _shutdown: ShutdownGuard,
+    _flight: Option<FlightGuard>,
+    _resources: ResourceGuard,
+    circuit: CircuitPass,
+}
+
+impl IpcHandler {
+    pub(super) async fn handle_inference(
+        &self,
+        mut request: InferenceRequest,
+        session: Option<&SessionToken>,
+    ) -> InferenceResponse {
+        request.resolve_model(self.config.default_model.as_deref());
+        if let Err(e) = request.validate_with_max_prompt_bytes(self.config.max_prompt_bytes) {
+            return self.fail(request.request_id, ErrorCategory::Client, e.to_string());
+        }
+
+        // Clients in a running experiment may be served by its variant model
+        let assignment = self.assign_arm(&mut request).await;
+
+        // Echo client metadata unchanged on every response to a valid request
+        let client_metadata = request.client_metadata.clone();
+        let trace = RequestTrace::start(request.request_id.0, &request.model_id);
+        let started = Instant::now();
+        let model_id = request.model_id.clone();
+        let serve = self.run_inference(request, session, &trace);
+        let response = self.with_model_capacity(&model_id, serve).await;
+        self.spans.record(trace.finish("inference", response.error.is_none()));
+        if let Some(assignment) = assignment {
+            if response.error.is_none() {
+                let tokens = response.tokens_generated as u64;
+                assignment.record_success(started.elapsed(), tokens);
+            } else {
+                assignment.record_failure();
+            }
+        }
+        response.with_client_metadata(client_metadata)
+    }
+
+    /// Point `request` at the model of its arm in the running experiment,
+    /// keyed by its `client_id`. `None` if no experiment covers its model.
+    pub(super) async fn assign_arm(
+        &self,
+        request: &mut InferenceRequest,
+    ) -> Option<ExperimentAssignment> {
+        let client_id = request.client_id.as_deref();
+        let assignment = self.router.assign_experiment(&request.model_id, client_id).await?;
+        request.model_id = assignment.model_id.clone();
+        Some(assignment)
+    }
+
+    /// Render `request` with its model's chat template and serve the
+    /// resulting prompt as an ordinary inference request.
+    pub(super) async fn handle_chat(
+        &self,
+        mut request: ChatRequest,
+        session: Option<&SessionToken>,
+    ) -> InferenceResponse {
+        let messages = std::mem::take(&mut request.messages);
+        let mut request = request.into_inference(String::new());
+        request.resolve_model(self.config.default_model.as_deref());
+        match self.inference_engine.render_chat(&request.model_id, &messages).await {
+            Ok(prompt) => request.prompt = prompt,
+            Err(e) => return self.fail(request.request_id, e.category(), e.to_string()),
+        }
+        self.handle_inference(request, session).await
+    }
+
+    async fn run_inference(
+        &self,
+        request: InferenceRequest,
+        session: Option<&SessionToken>,
+        trace: &RequestTrace,
+    ) -> InferenceResponse {
+        let admission = match self.admit(&request).await {
+            Ok(admission) => admission,
+            Err(rejected) => return rejected,
+        };
+        let leader = match self.join_dedup(&request).await {
+            Ok(leader) => leader,
+            Err(shared) => return shared,
+        };
+        let (ticket, queued_at) = match self.enqueue(&request, session, trace).await {
+            Ok(queued) => queued,
+            Err(response) => return response,
+        };
+        let start = Instant::now();
+        let generated = self.generate(&request, session, trace, &ticket, queued_at).await;
+        // Give the queue slot back whatever the outcome
+        self.queue.complete(ticket.id).await;
+        let result = match generated {
+            Some(result) => result.and_then(|result| self.filter_output(result)),
+            None => {
+                let message = QueueError::Cancelled.to_string();
+                return self.fail(request.request_id, ErrorCategory::Infra, message);
+            }
+        };
+        match result {
+            Ok(result) => self.complete(&request, result, admission.circuit, leader, start).await,
+            Err(e) => self.abort(&request, e, admission.circuit, leader),
+        }
+        // guards dropped here, decrementing in-flight counts
+    }
+
+    /// Check that the runtime can take `request` now, holding its place
+    /// among in-flight requests until the admission is dropped.
+    async fn admit(&self, request: &InferenceRequest) -> Result<Admission, InferenceResponse> {
+        let request_id = request.request_id;
+        // Check shutdown state before accepting new request
+        let Some(shutdown) = self.shutdown.track() else {
+            let message = "Server is shutting down".into();
+            return Err(self.fail(request_id, ErrorCategory::Infra, message));
+        };
+        if self.health.is_starting_up() {
+            return Err(self.fail(request_id, ErrorCategory::Infra, WARMING_UP_MESSAGE.into()));
+        }
+        let flight = self
+            .begin_flight(&request.model_id)
+            .await
+            .map_err(|message| self.fail(request_id, ErrorCategory::Client, message))?;
+        if let Err(e) = self.config.memory_floor.check() {
+            return Err(self.fail(request_id, e.category(), e.to_string()));
+        }
+        let resources = self
+            .acquire_resources(request)
+            .map_err(|e| self.fail(request_id, e.category(), e.to_string()))?;
+        // A model failing every request is taken out of service for a while
+        let circuit = self
+            .router
+            .admit(&request.model_id)
+            .map_err(|e| self.fail(request_id, ErrorCategory::Infra, e.to_string()))?;
+        Ok(Admission {
+            _shutdown: shutdown,
+            _flight: flight,
+            _resources: resources,
+            circuit,
+        })
+    }
+
+    /// Reserve `request` a slot under the resource limits, charged its
+    /// prompt size in bytes.
+    pub(super) fn acquire_resources(
+        &self,
+        request: &InferenceRequest,
+    ) -> Result<ResourceGuard, InferenceError> {
+        self.resource_limits.try_acquire(request.prompt.len())
+    }
+
+    /// Run `request` on its model; `None` if the queue cancelled it first.
+    /// Its queue wait, from `queued_at` until generation starts, is
+    /// reported on the result.
+    async fn generate(
+        &self,
+        request: &InferenceRequest,
+        session: Option<&SessionToken>,
+        trace: &RequestTrace,
+        ticket: &QueueTicket,
+        queued_at: u64,
+    ) -> Option<Result<InferenceResult, InferenceError>> {
+        let active = self.active.begin(request.request_id, &request.model_id, session);
+        let queue_wait_ms = self.record_queue_wait(trace, queued_at, true);
+        let generate_start = now_unix_ns();
+        let result = tokio::select! {
+            result = self.inference_engine.run_with_signal(
+                &request.model_id,
+                &request.prompt,
+                &request.parameters,
+                active.decode_signal(),
+            ) => Some(result),
+            _ = ticket.cancelled() => None,
+        };
+        let ok = matches!(result, Some(Ok(_)));
+        self.spans.record(trace.phase("generate", generate_start, ok));
+        result.map(|result| result.map(|result| InferenceResult { queue_wait_ms, ..result }))
+    }
+
+    /// Count a failure under its category and build the error response.
+    pub(super) fn fail(
+        &self,
+        request_id: RequestId,
+        category: ErrorCategory,
+        error: String,
+    ) -> InferenceResponse {
+        // Bad requests say nothing about whether the runtime can serve
+        if category != ErrorCategory::Client {
+            self.health.record_inference_failure();
+        }
+        self.metrics_store.increment_counter(category.counter_name(), 1);
+        telemetry::record_error_category(category);
+        InferenceResponse::failed(request_id, category, error)
+    }
+}
@@ -0,0 +1,202 @@
+//! Model file resolution and the load handler.
+
+use std::sync::Arc;
+
+use super::IpcHandler;
+use crate::engine::{ChatTemplate, GgufModel, ModelFactory};
+use crate::ipc::protocol::{IpcMessage, LoadModelResponse};
+use crate::models::{
+    LoadError, LoadGuard, LoadedModelState, ModelHandle, ModelLoader, ModelManifest,
+    ModelMetadata, ModelPath,
+};
+
+/// Map a model file error to an IPC error response.
+fn load_error(e: LoadError) -> IpcMessage {
+    let code = match e {
+        LoadError::NotFound(_) => 404,
+        LoadError::PathNotAllowed(_) => 403,
+        LoadError::LoadInProgress(_) => 409,
+        _ => 400,
+    };
+    IpcMessage::Error {
+        code,
+        message: e.to_string(),
+    }
+}
+
+/// A model file validated and claimed for loading under a model ID.
+pub(super) struct ModelFile<'a> {
+    pub(super) path: ModelPath,
+    pub(super) metadata: ModelMetadata,
+    /// Lowercased file extension, e.g. `gguf`.
+    pub(super) format: String,
+    /// Manifest shipped beside the file; its hash is checked before loading.
+    pub(super) manifest: Option<ModelManifest>,
+    _guard: LoadGuard<'a>,
+}
+
+impl ModelFile<'_> {
+    /// Chat template from the manifest, if one shipped with the file.
+    ///
+    /// Manifests are validated when read, so their template always parses.
+    pub(super) fn chat_template(&self) -> Option<ChatTemplate> {
+        self.manifest.as_ref()?.chat_template().ok()
+    }
+}
+
+/// Validate `path`, claim it against concurrent loads of the same file and
+/// read its metadata, named `model_id`.
+pub(super) fn open_model_file<'a>(
+    loader: &'a ModelLoader,
+    model_id: &str,
+    path: &str,
+) -> Result<ModelFile<'a>, IpcMessage> {
+    let model_path = loader.validate_path(path).map_err(load_error)?;
+    let file_name = model_path
+        .as_path()
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let guard = loader.begin_load(&file_name).map_err(load_error)?;
+    let mut metadata = loader.load_metadata(&model_path).map_err(load_error)?;
+    let manifest = loader.read_manifest(&model_path).map_err(load_error)?;
+    metadata.name = model_id.to_string();
+    let format = model_path
+        .as_path()
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| "unknown".to_string());
+    Ok(ModelFile {
+        path: model_path,
+        metadata,
+        format,
+        manifest,
+        _guard: guard,
+    })
+}
+
+/// Check `file` against its manifest's hash, then build its weights with
+/// `factory`, both off the async runtime.
+pub(super) async fn load_weights(
+    loader: &Arc<ModelLoader>,
+    factory: &ModelFactory,
+    file: &ModelFile<'_>,
+    model_id: &str,
+) -> Result<Arc<dyn GgufModel>, IpcMessage> {
+    let load = {
+        let loader = Arc::clone(loader);
+        let factory = Arc::clone(factory);
+        let path = file.path.clone();
+        let manifest = file.manifest.clone();
+        let model_id = model_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            if let Some(manifest) = &manifest {
+                loader.verify_integrity(&path, manifest).map_err(load_error)?;
+            }
+            factory(path.as_path(), &model_id).map_err(|e| IpcMessage::Error {
+                code: 500,
+                message: e.to_string(),
+            })
+        })
+    };
+    match load.await {
+        Ok(loaded) => loaded,
+        Err(e) => Err(IpcMessage::Error {
+            code: 500,
+            message: format!("Model load task failed: {}", e),
+        }),
+    }
+}
+
+impl IpcHandler {
+    /// Load `path` (default `models/<model_id>.gguf`) and serve it as
+    /// `model_id`.
+    ///
+    /// The model is registered only once its weights are built, and is
+    /// attached to the inference engine under the new handle.
+    pub(super) async fn handle_load(&self, model_id: String, path: Option<String>) -> IpcMessage {
+        let (Some(loader), Some(factory)) = (&self.model_loader, &self.model_factory) else {
+            return IpcMessage::Error {
+                code: 503,
+                message: "Model loading not available".into(),
+            };
+        };
+        let path = path.unwrap_or_else(|| format!("models/{}.gguf", model_id));
+        // Held until the model is registered, so a concurrent load of the
+        // same file is refused
+        let file = match open_model_file(loader, &model_id, &path) {
+            Ok(file) => file,
+            Err(response) => return response,
+        };
+        let restored = match self.restored_record(&model_id).await {
+            Ok(restored) => restored,
+            Err(response) => return response,
+        };
+        let model = match load_weights(loader, factory, &file, &model_id).await {
+            Ok(model) => model,
+            Err(response) => return response,
+        };
+        if let Some(handle) = restored {
+            self.model_registry.unregister(handle).await;
+        }
+        self.register_loaded(model_id, file, model).await
+    }
+
+    /// Handle of `model_id`'s record restored from the last snapshot, which
+    /// a load replaces. Refuses a model that is already loaded.
+    async fn restored_record(&self, model_id: &str) -> Result<Option<ModelHandle>, IpcMessage> {
+        let registered = self.find_model(model_id).await;
+        let restored = registered
+            .as_ref()
+            .is_some_and(|model| model.state == LoadedModelState::Unloaded);
+        let served = self.inference_engine.get_handle(model_id).await.is_some();
+        if served || (registered.is_some() && !restored) {
+            return Err(IpcMessage::Error {
+                code: 409,
+                message: format!("Model already loaded: {}", model_id),
+            });
+        }
+        Ok(registered.map(|model| ModelHandle::new(model.handle_id)))
+    }
+
+    /// Register `model`, built from `file`, and attach it to the engine
+    /// along with its manifest's chat template.
+    async fn register_loaded(
+        &self,
+        model_id: String,
+        file: ModelFile<'_>,
+        model: Arc<dyn GgufModel>,
+    ) -> IpcMessage {
+        let size_bytes = file.metadata.size_bytes;
+        let format = file.format.clone();
+        let path = file.path.as_path().to_path_buf();
+        let template = file.chat_template();
+        let registered = self
+            .model_registry
+            .register_file(file.metadata, model.memory_usage(), file.format, path)
+            .await;
+        let handle = match registered {
+            Ok(handle) => handle,
+            Err(e) => {
+                return IpcMessage::Error {
+                    code: 503,
+                    message: e.to_string(),
+                }
+            }
+        };
+        if let Some(template) = template {
+            self.inference_engine.set_chat_template(&model_id, template).await;
+        }
+        self.inference_engine
+            .register_model(model_id.clone(), handle, model)
+            .await;
+        IpcMessage::LoadModelResponse(LoadModelResponse {
+            model_id,
+            handle_id: handle.id(),
+            format,
+            size_bytes,
+        })
+    }
+}
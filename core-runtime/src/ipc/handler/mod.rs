@@ -0,0 +1,227 @@
+//! Request/response handling for IPC connections.
+//!
+//! Message routing lives in `dispatch`; each group of handlers has its own
+//! submodule.
+
+mod admin;
+mod builder;
+mod capacity;
+mod config;
+mod dispatch;
+mod drain;
+mod inference;
+mod load;
+mod models;
+mod outcome;
+mod queueing;
+#[cfg(feature = "gguf")]
+mod stream_relay;
+mod streaming;
+mod swap;
+
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+
+use super::active_requests::ActiveRequests;
+use super::auth::{AuthError, SessionAuth, SessionToken};
+use super::compression::{Compression, FrameCodec};
+use super::config_reload::ConfigReloader;
+use super::health_handler::HealthHandler;
+use super::protocol::{
+    decode_message, decode_message_strict, encode_message, EffectiveConfig, IpcMessage,
+    ProtocolError,
+};
+use super::protocol_stats::ProtocolStats;
+use super::session_streams::SessionStreams;
+use super::stream_cancel::StreamCancellations;
+use crate::engine::{ErrorCategory, InferenceEngine, InferenceResult, ModelFactory, OutputFilter};
+use crate::health::HealthChecker;
+use crate::memory::ResourceLimits;
+use crate::models::{FlightTracker, ModelLoader, ModelRegistry, ModelRouter, SwapManager};
+use crate::scheduler::{RequestDedup, RequestQueue};
+use crate::shutdown::ShutdownCoordinator;
+use crate::telemetry::{MetricsStore, SpanCollector};
+use swap::swap_manager;
+
+pub use config::{
+    HandlerConfigError, IpcHandlerConfig, DEFAULT_MAX_CLIENT_PRIORITY,
+    DEFAULT_MAX_STREAMS_PER_SESSION,
+};
+
+/// Outcome of a deduplicated inference, shared with identical requests.
+type SharedOutcome = Result<InferenceResult, (ErrorCategory, String)>;
+
+#[derive(Error, Debug)]
+pub enum HandlerError {
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    #[error("Authentication error: {0}")]
+    Auth(#[from] AuthError),
+
+    #[error("Not authenticated")]
+    NotAuthenticated,
+
+    #[error("Queue error: {0}")]
+    QueueFull(String),
+
+    #[error("Server is shutting down")]
+    ShuttingDown,
+
+    #[error("Stream send error: {0}")]
+    StreamSend(String),
+}
+
+/// Trait for sending streaming responses over IPC.
+#[async_trait::async_trait]
+pub trait StreamSender: Send + Sync {
+    /// Send a message to the stream. Returns error if stream is closed.
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError>;
+}
+
+/// Handles IPC message processing with authentication.
+pub struct IpcHandler {
+    /// Session authentication manager (public for FFI access)
+    pub auth: Arc<SessionAuth>,
+    queue: Arc<RequestQueue>,
+    config: IpcHandlerConfig,
+    shutdown: Arc<ShutdownCoordinator>,
+    health_handler: HealthHandler,
+    /// Tracks inference outcomes for the last-success gauge and readiness.
+    health: Arc<HealthChecker>,
+    metrics_store: Arc<MetricsStore>,
+    model_registry: Arc<ModelRegistry>,
+    inference_engine: Arc<InferenceEngine>,
+    /// Resolves model files for `LoadModelRequest` and `SwapModel`.
+    model_loader: Option<Arc<ModelLoader>>,
+    /// Loads weights for `LoadModelRequest` and `SwapModel`; both are
+    /// refused without one.
+    model_factory: Option<ModelFactory>,
+    /// Serializes `SwapModel`s and drains the old model through `flights`.
+    swaps: SwapManager,
+    /// Holds the A/B experiment that redirects inference requests.
+    router: Arc<ModelRouter>,
+    /// Redacted runtime configuration served to `ConfigRequest`.
+    effective_config: RwLock<Option<EffectiveConfig>>,
+    /// Serves `ReloadConfig`; reloading is refused without one.
+    reloader: Option<ConfigReloader>,
+    /// Enforces `config.resource_limits`, updated by `ReloadConfig`.
+    resource_limits: ResourceLimits,
+    /// Per-request queue/generate spans served to `SpansRequest`.
+    spans: Arc<SpanCollector>,
+    /// Frame-level traffic counters for server connections.
+    protocol_stats: Arc<ProtocolStats>,
+    /// Requests executing on a model, served to `ActiveRequestsRequest`.
+    active: ActiveRequests,
+    /// Running streams, cancellable by `CancelRequest`.
+    streams: StreamCancellations,
+    /// Open streams per session, capped by `max_streams_per_session`.
+    session_streams: SessionStreams,
+    /// Running and recent deterministic inferences, keyed by request.
+    dedup: RequestDedup<SharedOutcome>,
+    /// In-flight requests per model, awaited by `DrainModel`.
+    flights: Arc<FlightTracker>,
+    /// Models refusing new requests after a `DrainModel`.
+    draining: RwLock<HashSet<String>>,
+    /// Applied to every generation before it reaches the client.
+    output_filter: OutputFilter,
+}
+
+impl IpcHandler {
+    pub fn new(
+        auth: Arc<SessionAuth>,
+        queue: Arc<RequestQueue>,
+        config: IpcHandlerConfig,
+        shutdown: Arc<ShutdownCoordinator>,
+        health: Arc<HealthChecker>,
+        model_registry: Arc<ModelRegistry>,
+        metrics_store: Arc<MetricsStore>,
+        inference_engine: Arc<InferenceEngine>,
+    ) -> Self {
+        let health_handler = HealthHandler::new(
+            Arc::clone(&health),
+            Arc::clone(&shutdown),
+            Arc::clone(&model_registry),
+            Arc::clone(&queue),
+            Arc::clone(&inference_engine),
+        );
+        let protocol_stats = Arc::new(ProtocolStats::new(
+            Arc::clone(&metrics_store),
+            config.decode_errors.clone(),
+        ));
+        let flights = Arc::new(FlightTracker::new());
+        let router = ModelRouter::new()
+            .with_circuit_breaker(config.circuit_breaker)
+            .with_registry(Arc::clone(&model_registry));
+        let router = Arc::new(router);
+        let swaps = swap_manager(&model_registry, &router, &flights);
+        let session_streams = SessionStreams::new(config.max_streams_per_session);
+        let dedup = RequestDedup::new(config.output_cache.clone());
+        let resource_limits = ResourceLimits::new(config.resource_limits.clone());
+        Self {
+            auth,
+            queue,
+            config,
+            shutdown,
+            health_handler,
+            health,
+            metrics_store,
+            model_registry,
+            inference_engine,
+            model_loader: None,
+            model_factory: None,
+            swaps,
+            router,
+            effective_config: RwLock::new(None),
+            reloader: None,
+            resource_limits,
+            spans: Arc::new(SpanCollector::new()),
+            protocol_stats,
+            active: ActiveRequests::new(),
+            streams: StreamCancellations::default(),
+            session_streams,
+            dedup,
+            flights,
+            draining: RwLock::new(HashSet::new()),
+            output_filter: OutputFilter::default(),
+        }
+    }
+
+    /// Requests currently executing on a model.
+    pub fn active_requests(&self) -> &ActiveRequests {
+        &self.active
+    }
+
+    /// Frame-level traffic counters, shared by all server connections.
+    pub fn protocol_stats(&self) -> &Arc<ProtocolStats> {
+        &self.protocol_stats
+    }
+
+    /// Frame codec for a session that requested `compression` at handshake.
+    pub fn frame_codec(&self, requested: Option<Compression>) -> FrameCodec {
+        let negotiated = self.config.compression.negotiate(requested);
+        FrameCodec::negotiated(negotiated, &self.config.compression)
+    }
+
+    /// Decode a request frame, strictly if `strict_protocol` is set.
+    pub fn decode(&self, bytes: &[u8]) -> Result<IpcMessage, ProtocolError> {
+        if self.config.strict_protocol {
+            decode_message_strict(bytes)
+        } else {
+            decode_message(bytes)
+        }
+    }
+
+    /// Process incoming message bytes and return response bytes.
+    pub async fn process(
+        &self,
+        bytes: &[u8],
+        session: Option<&SessionToken>,
+    ) -> Result<(Vec<u8>, Option<SessionToken>), HandlerError> {
+        let message = self.decode(bytes)?;
+        let (response, new_session) = self.handle_message(message, session).await?;
+        let response_bytes = encode_message(&response)?;
+        Ok((response_bytes, new_session))
+    }
+}
@@ -0,0 +1,145 @@
+//! Model listing, stats and unload handlers.
+
+use super::IpcHandler;
+use crate::ipc::protocol::{
+    IpcMessage, ModelInfo, ModelStats, ModelsListResponse, UnloadModelResponse,
+};
+use crate::models::{LoadedModelInfo, ModelHandle};
+use crate::telemetry::model_latency_histogram;
+
+/// Format SystemTime as ISO 8601 string for IPC responses.
+fn format_system_time(time: std::time::SystemTime) -> String {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .map(|d| {
+            let secs = d.as_secs();
+            let datetime = chrono::DateTime::from_timestamp(secs as i64, 0);
+            datetime
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+                .unwrap_or_else(|| format!("{}s", secs))
+        })
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Wire view of a registered model.
+fn model_info(m: LoadedModelInfo) -> ModelInfo {
+    let avg_latency_ms = if m.request_count > 0 {
+        m.total_latency_ms / m.request_count as f64
+    } else {
+        0.0
+    };
+    ModelInfo {
+        handle_id: m.handle_id,
+        name: m.name,
+        format: m.format,
+        size_bytes: m.size_bytes,
+        memory_bytes: m.memory_bytes,
+        state: m.state.as_str().to_string(),
+        request_count: m.request_count,
+        avg_latency_ms,
+        loaded_at: format_system_time(m.loaded_at),
+    }
+}
+
+impl IpcHandler {
+    pub(super) async fn handle_models_request(&self) -> ModelsListResponse {
+        let models = self.model_registry.list_models().await;
+        let total_memory_bytes = models.iter().map(|m| m.memory_bytes).sum();
+
+        ModelsListResponse {
+            models: models.into_iter().map(model_info).collect(),
+            total_memory_bytes,
+        }
+    }
+
+    pub(super) async fn handle_model_info(&self, model_id: String) -> IpcMessage {
+        match self.find_model(&model_id).await {
+            Some(model) => IpcMessage::ModelInfoResponse(model_info(model)),
+            None => IpcMessage::Error {
+                code: 404,
+                message: format!("Model not loaded: {}", model_id),
+            },
+        }
+    }
+
+    pub(super) async fn find_model(&self, model_id: &str) -> Option<LoadedModelInfo> {
+        self.model_registry
+            .list_models()
+            .await
+            .into_iter()
+            .find(|m| m.name == model_id)
+    }
+
+    /// Live stats for `model_id`, served or registered.
+    ///
+    /// Request counts and latency come from the model's latency histogram in
+    /// the metrics store, in-flight requests from the drain tracker, and
+    /// memory from the registry (or the served model when unregistered).
+    pub(super) async fn handle_model_stats(&self, model_id: String) -> IpcMessage {
+        let served = self.inference_engine.model(&model_id).await;
+        let registered = self.find_model(&model_id).await;
+        if served.is_none() && registered.is_none() {
+            return IpcMessage::Error {
+                code: 404,
+                message: format!("Model not loaded: {}", model_id),
+            };
+        }
+
+        let in_flight = match self.inference_engine.get_handle(&model_id).await {
+            Some(handle) => self.flights.in_flight_count(handle).await as usize,
+            None => 0,
+        };
+        let latency = self
+            .metrics_store
+            .histogram(&model_latency_histogram(&model_id));
+        let (total_requests, avg_latency_ms) = match latency {
+            Some(l) if l.count > 0 => (l.count, l.sum / l.count as f64),
+            _ => (0, 0.0),
+        };
+        let memory_bytes = match (registered, served) {
+            (Some(model), _) => model.memory_bytes,
+            (None, Some(model)) => model.memory_usage() as u64,
+            (None, None) => 0,
+        };
+        let circuit_state = self.router.circuit_state(&model_id);
+        IpcMessage::ModelStatsResponse(ModelStats {
+            model_id,
+            in_flight,
+            total_requests,
+            avg_latency_ms,
+            memory_bytes,
+            circuit_state,
+        })
+    }
+
+    /// Remove `model_id` from the registry and the inference engine.
+    pub(super) async fn handle_unload(&self, model_id: String) -> IpcMessage {
+        let unregistered = match self.find_model(&model_id).await {
+            Some(model) => {
+                let handle = ModelHandle::new(model.handle_id);
+                self.model_registry
+                    .unregister(handle)
+                    .await
+                    .map(|freed| (handle, freed))
+            }
+            None => None,
+        };
+        let Some((handle, freed_bytes)) = unregistered else {
+            return IpcMessage::Error {
+                code: 404,
+                message: format!("Model not loaded: {}", model_id),
+            };
+        };
+        self.inference_engine.unregister_model(&model_id).await;
+        self.router.reset_circuit(&model_id);
+        self.dedup.clear();
+        self.draining
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&model_id);
+        IpcMessage::UnloadModelResponse(UnloadModelResponse {
+            model_id,
+            handle_id: handle.id(),
+            freed_bytes: freed_bytes as u64,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+//! Outcomes of inference runs: filtering, recording, and sharing them
+//! with identical requests.
+
+use std::time::Instant;
+
+use super::{IpcHandler, SharedOutcome};
+use crate::engine::{
+    ErrorCategory, FinishReason, GenerationResult, InferenceError, InferenceResult,
+    OutputEncoding,
+};
+use crate::ipc::protocol::{InferenceRequest, InferenceResponse};
+use crate::models::CircuitPass;
+use crate::scheduler::{DedupLeader, DedupLookup, OutputCache};
+use crate::telemetry::{self, LATENCY_HISTOGRAM};
+
+/// The run that fills a dedup entry, if the request leads one.
+pub(super) type Leader = Option<DedupLeader<SharedOutcome>>;
+
+/// Build the response for a successful run of `request`.
+fn success_response(request: &InferenceRequest, result: InferenceResult) -> InferenceResponse {
+    let response = match request.parameters.output_encoding {
+        OutputEncoding::Utf8 => InferenceResponse::success(
+            request.request_id,
+            result.output,
+            result.tokens_generated,
+            result.finished,
+        ),
+        OutputEncoding::Bytes => {
+            let bytes = result.raw_output.unwrap_or_else(|| result.output.into_bytes());
+            InferenceResponse::success_bytes(
+                request.request_id,
+                &bytes,
+                result.tokens_generated,
+                result.finished,
+            )
+        }
+    };
+    response.with_timing(result.queue_wait_ms, result.prefill_ms, result.decode_ms)
+}
+
+impl IpcHandler {
+    /// Run `result` through the output filter. Output cut to
+    /// `max_output_bytes` is reported as unfinished.
+    pub(super) fn filter_output(
+        &self,
+        result: InferenceResult,
+    ) -> Result<InferenceResult, InferenceError> {
+        let mut generation = GenerationResult {
+            text: result.output,
+            tokens_generated: result.tokens_generated as u32,
+            finish_reason: FinishReason::Stop,
+            raw_bytes: result.raw_output,
+        };
+        self.output_filter.apply(&mut generation)?;
+        Ok(InferenceResult {
+            output: generation.text,
+            raw_output: generation.raw_bytes,
+            finished: result.finished && generation.finish_reason != FinishReason::Length,
+            ..result
+        })
+    }
+
+    /// Record a successful run and build its response.
+    pub(super) async fn complete(
+        &self,
+        request: &InferenceRequest,
+        result: InferenceResult,
+        circuit: CircuitPass,
+        leader: Leader,
+        start: Instant,
+    ) -> InferenceResponse {
+        circuit.record(true);
+        if let Some(leader) = leader {
+            leader.finish(Ok(result.clone()), true);
+        }
+        let latency_ms = start.elapsed().as_millis() as u64;
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        self.metrics_store.record_bucketed(LATENCY_HISTOGRAM, elapsed_ms);
+        self.record_completion(&request.model_id, elapsed_ms);
+        self.health.record_inference_success();
+
+        // Record metrics via telemetry facade (Prometheus-compatible)
+        let tokens = result.tokens_generated as u64;
+        telemetry::record_request_success(&request.model_id, latency_ms, tokens);
+
+        // Also record in model registry with correct handle for per-model stats
+        if let Some(handle) = self.inference_engine.get_handle(&request.model_id).await {
+            self.model_registry.record_request(handle, latency_ms as f64).await;
+        }
+        success_response(request, result)
+    }
+
+    /// Record a failed run and build its error response.
+    pub(super) fn abort(
+        &self,
+        request: &InferenceRequest,
+        e: InferenceError,
+        circuit: CircuitPass,
+        leader: Leader,
+    ) -> InferenceResponse {
+        telemetry::record_request_failure(&request.model_id, &e.to_string());
+        // Only the model's own failures count towards opening its breaker
+        if e.category() == ErrorCategory::Model {
+            circuit.record(false);
+        }
+        if let Some(leader) = leader {
+            leader.finish(Err((e.category(), e.to_string())), false);
+        }
+        self.fail(request.request_id, e.category(), e.to_string())
+    }
+
+    /// Join the dedup entry for `request`; `None` when its output is not
+    /// reproducible or the client opted out with `no_cache`.
+    fn dedup_lookup(&self, request: &InferenceRequest) -> Option<DedupLookup<SharedOutcome>> {
+        if !request.parameters.is_reproducible() {
+            return None;
+        }
+        let key = OutputCache::request_key(&request.model_id, &request.prompt, &request.parameters);
+        self.dedup.lookup(key)
+    }
+
+    /// Answer `request` with the outcome of an identical request.
+    fn shared_response(
+        &self,
+        request: &InferenceRequest,
+        outcome: SharedOutcome,
+    ) -> InferenceResponse {
+        match outcome {
+            Ok(result) => success_response(request, result),
+            Err((category, message)) => self.fail(request.request_id, category, message),
+        }
+    }
+
+    /// Join the dedup entry for `request`. Fails with the response to send
+    /// when an identical request already produced (or is producing) one.
+    pub(super) async fn join_dedup(
+        &self,
+        request: &InferenceRequest,
+    ) -> Result<Leader, InferenceResponse> {
+        match self.dedup_lookup(request) {
+            Some(DedupLookup::Cached(outcome)) => Err(self.shared_response(request, outcome)),
+            Some(DedupLookup::Wait(waiter)) => match waiter.result().await {
+                Some(outcome) => Err(self.shared_response(request, outcome)),
+                // The leader was cancelled; run the request ourselves
+                None => Ok(None),
+            },
+            Some(DedupLookup::Lead(leader)) => Ok(Some(leader)),
+            None => Ok(None),
+        }
+    }
+}
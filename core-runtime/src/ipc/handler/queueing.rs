@@ -0,0 +1,74 @@
+//! Tracking inference requests in the request queue.
+
+use super::IpcHandler;
+use crate::engine::ErrorCategory;
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{InferenceRequest, InferenceResponse, QUEUE_TIMEOUT_ERROR_CODE};
+use crate::scheduler::{QueueError, QueueTicket, RequestOrigin};
+use crate::telemetry::span_export::now_unix_ns;
+use crate::telemetry::{RequestTrace, QUEUE_WAIT_HISTOGRAM};
+
+impl IpcHandler {
+    /// Track `request` in the queue, returning its ticket and when
+    /// enqueueing began (Unix ns).
+    pub(super) async fn enqueue(
+        &self,
+        request: &InferenceRequest,
+        session: Option<&SessionToken>,
+        trace: &RequestTrace,
+    ) -> Result<(QueueTicket, u64), InferenceResponse> {
+        let queued_at = now_unix_ns();
+        let error = match self.enqueue_ticket(request, session).await {
+            Ok(ticket) => return Ok((ticket, queued_at)),
+            Err(e) => e,
+        };
+        self.record_queue_wait(trace, queued_at, false);
+        let response = self.fail(request.request_id, ErrorCategory::Infra, error.to_string());
+        match error {
+            QueueError::EnqueueTimeout(_) => Err(InferenceResponse {
+                error_code: Some(QUEUE_TIMEOUT_ERROR_CODE),
+                ..response
+            }),
+            _ => Err(response),
+        }
+    }
+
+    /// Record the time from `queued_at` until now, when the request is
+    /// admitted to run (or turned away by the queue), in milliseconds.
+    pub(super) fn record_queue_wait(
+        &self,
+        trace: &RequestTrace,
+        queued_at: u64,
+        admitted: bool,
+    ) -> f64 {
+        self.spans.record(trace.phase("queue", queued_at, admitted));
+        let queue_wait_ms = now_unix_ns().saturating_sub(queued_at) as f64 / 1_000_000.0;
+        self.metrics_store.record_bucketed(QUEUE_WAIT_HISTOGRAM, queue_wait_ms);
+        queue_wait_ms
+    }
+
+    /// Track `request` in the queue at its client priority. The caller
+    /// must [`complete`](crate::scheduler::RequestQueue::complete) the
+    /// ticket once the request finishes, returning its slot.
+    pub(super) async fn enqueue_ticket(
+        &self,
+        request: &InferenceRequest,
+        session: Option<&SessionToken>,
+    ) -> Result<QueueTicket, QueueError> {
+        // Clients cannot jump above the cap (Critical stays server-internal)
+        let priority = request
+            .priority
+            .unwrap_or_default()
+            .min(self.config.max_client_priority);
+        self.queue
+            .enqueue_tracked(
+                RequestOrigin::Ipc,
+                session.map(SessionToken::fingerprint),
+                request.model_id.clone(),
+                request.prompt.clone(),
+                request.parameters.clone(),
+                priority,
+            )
+            .await
+    }
+}
@@ -0,0 +1,102 @@
+//! Relaying generated tokens to a streaming client.
+
+use std::time::Instant;
+
+use tokio::task::JoinError;
+
+use super::{HandlerError, IpcHandler, StreamSender};
+use crate::engine::{ErrorCategory, InferenceError, OutputLimiter, StreamingOutput};
+use crate::ipc::protocol::{IpcMessage, RequestId, StreamChunk};
+use crate::ipc::stream_coalesce::StreamCoalescer;
+use crate::models::CircuitPass;
+use crate::telemetry;
+
+impl IpcHandler {
+    /// Feed a finished stream's outcome to health, metrics and the model's
+    /// circuit breaker; the tokens it generated if it succeeded.
+    pub(super) fn finish_stream(
+        &self,
+        model_id: &str,
+        outcome: Result<Result<(), InferenceError>, JoinError>,
+        circuit: CircuitPass,
+        relay: &Relay,
+    ) -> Option<u64> {
+        match outcome {
+            Ok(Ok(())) => {
+                circuit.record(true);
+                let elapsed_ms = relay.started.elapsed().as_secs_f64() * 1000.0;
+                self.record_completion(model_id, elapsed_ms);
+                self.health.record_inference_success();
+                return Some(relay.tokens);
+            }
+            Ok(Err(e)) if e.category() == ErrorCategory::Client => {}
+            Ok(Err(e)) => {
+                // Only the model's own failures count towards opening its breaker
+                if e.category() == ErrorCategory::Model {
+                    circuit.record(false);
+                }
+                self.health.record_inference_failure();
+            }
+            Err(_) => self.health.record_inference_failure(),
+        }
+        None
+    }
+
+    /// Send `output` to the client; false once the stream has ended, either
+    /// on the final token or because the output byte limit was reached.
+    pub(super) async fn relay_token(
+        &self,
+        relay: &mut Relay,
+        model_id: &str,
+        output: StreamingOutput,
+        sender: &dyn StreamSender,
+    ) -> Result<bool, HandlerError> {
+        let now = Instant::now();
+        let (histogram, since) = match relay.last_token {
+            None => (telemetry::TTFT_HISTOGRAM, relay.started),
+            Some(previous) => (telemetry::INTER_TOKEN_HISTOGRAM, previous),
+        };
+        let gap_ms = now.duration_since(since).as_secs_f64() * 1000.0;
+        self.metrics_store.record_bucketed(histogram, gap_ms);
+        relay.last_token = Some(now);
+
+        if relay.limiter.is_limited() {
+            let piece = self.inference_engine.detokenize_bytes(model_id, &[output.token]).await;
+            if !relay.limiter.admit(piece.map_or(0, |bytes| bytes.len())) {
+                relay.end(sender).await?;
+                return Ok(false);
+            }
+        }
+        relay.tokens += 1;
+        let metadata = output.is_final.then(|| relay.client_metadata.clone()).flatten();
+        if let Some(frame) = relay.coalescer.push(output.token, output.is_final, metadata) {
+            sender.send(frame).await?;
+        }
+        Ok(!output.is_final)
+    }
+}
+
+/// Per-stream state carried across relayed tokens.
+pub(super) struct Relay {
+    pub(super) request_id: RequestId,
+    pub(super) coalescer: StreamCoalescer,
+    /// Enforces `max_output_bytes` on the streamed tokens.
+    pub(super) limiter: OutputLimiter,
+    pub(super) client_metadata: Option<String>,
+    pub(super) started: Instant,
+    pub(super) last_token: Option<Instant>,
+    /// Tokens relayed to the client so far.
+    pub(super) tokens: u64,
+}
+
+impl Relay {
+    /// Flush buffered tokens and close the stream with an end chunk.
+    pub(super) async fn end(&mut self, sender: &dyn StreamSender) -> Result<(), HandlerError> {
+        if let Some(batch) = self.coalescer.flush() {
+            sender.send(batch).await?;
+        }
+        let chunk = StreamChunk::end(self.request_id)
+            .with_client_metadata(self.client_metadata.clone());
+        sender.send(IpcMessage::StreamChunk(chunk)).await
+    }
+}
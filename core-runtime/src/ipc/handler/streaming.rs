@@ -0,0 +1,225 @@
+//! Streaming inference handler.
+
+#[cfg(feature = "gguf")]
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+
+use super::{HandlerError, IpcHandler, StreamSender};
+use crate::engine::ErrorCategory;
+#[cfg(feature = "gguf")]
+use super::stream_relay::Relay;
+#[cfg(feature = "gguf")]
+use crate::engine::{InferenceConfig, TokenStream};
+use crate::health::WARMING_UP_MESSAGE;
+use crate::ipc::auth::SessionToken;
+use crate::ipc::protocol::{InferenceRequest, IpcMessage, RequestId, StreamChunk};
+use crate::ipc::session_streams::TOO_MANY_STREAMS_MESSAGE;
+#[cfg(feature = "gguf")]
+use crate::ipc::stream_coalesce::{sleep_until_deadline, StreamCoalescer};
+#[cfg(feature = "gguf")]
+use crate::models::CircuitPass;
+use crate::telemetry;
+
+/// Tokens a stream generated when it ran to completion; `None` if it was
+/// refused or generation failed.
+type Streamed = Result<Option<u64>, HandlerError>;
+
+impl IpcHandler {
+    /// Process streaming inference request. Sends token chunks via sender.
+    ///
+    /// Creates a token stream channel, spawns inference on a blocking task,
+    /// and relays tokens to the client until completion or cancellation.
+    pub async fn process_streaming(
+        &self,
+        mut request: InferenceRequest,
+        session: &SessionToken,
+        sender: &dyn StreamSender,
+        cancel: CancellationToken,
+    ) -> Result<(), HandlerError> {
+        self.auth.validate(session).await?;
+        let _guard = self.shutdown.track().ok_or(HandlerError::ShuttingDown)?;
+        let _registration = self.streams.register(session, request.request_id, cancel.clone());
+
+        let request_id = request.request_id;
+        let Some(_slot) = self.session_streams.try_acquire(session) else {
+            let message = TOO_MANY_STREAMS_MESSAGE.into();
+            return self.reject_stream(request_id, ErrorCategory::Client, message, sender).await;
+        };
+
+        request.resolve_model(self.config.default_model.as_deref());
+        if let Err(e) = request.validate_with_max_prompt_bytes(self.config.max_prompt_bytes) {
+            let message = e.to_string();
+            return self.reject_stream(request_id, ErrorCategory::Client, message, sender).await;
+        }
+
+        // Clients in a running experiment may be served by its variant model
+        let assignment = self.assign_arm(&mut request).await;
+        let started = Instant::now();
+        let streamed = self.admit_stream(request, session, sender, cancel).await;
+        if let Some(assignment) = assignment {
+            match &streamed {
+                Ok(Some(tokens)) => assignment.record_success(started.elapsed(), *tokens),
+                _ => assignment.record_failure(),
+            }
+        }
+        streamed.map(|_| ())
+    }
+
+    /// Check the runtime can take the stream now, then run it.
+    #[allow(unused_variables)]
+    async fn admit_stream(
+        &self,
+        request: InferenceRequest,
+        session: &SessionToken,
+        sender: &dyn StreamSender,
+        cancel: CancellationToken,
+    ) -> Streamed {
+        let request_id = request.request_id;
+        let refuse =
+            move |category, message| self.refuse_stream(request_id, category, message, sender);
+        if self.health.is_starting_up() {
+            return refuse(ErrorCategory::Infra, WARMING_UP_MESSAGE.into()).await;
+        }
+        if let Err(e) = self.config.memory_floor.check() {
+            return refuse(e.category(), e.to_string()).await;
+        }
+        let _flight = match self.begin_flight(&request.model_id).await {
+            Ok(flight) => flight,
+            Err(message) => return refuse(ErrorCategory::Client, message).await,
+        };
+        let _resources = match self.acquire_resources(&request) {
+            Ok(resources) => resources,
+            Err(e) => return refuse(e.category(), e.to_string()).await,
+        };
+
+        // Streaming requires gguf feature
+        #[cfg(not(feature = "gguf"))]
+        {
+            let message = "Streaming requires GGUF feature. Rebuild with --features gguf.";
+            let chunk = StreamChunk::error(request_id, message.into());
+            sender.send(IpcMessage::StreamChunk(chunk)).await?;
+            Ok(None)
+        }
+
+        #[cfg(feature = "gguf")]
+        {
+            // A model failing every request is taken out of service for a while
+            let circuit = match self.router.admit(&request.model_id) {
+                Ok(circuit) => circuit,
+                Err(e) => return refuse(ErrorCategory::Infra, e.to_string()).await,
+            };
+            let ticket = match self.enqueue_ticket(&request, Some(session)).await {
+                Ok(ticket) => ticket,
+                Err(e) => return refuse(ErrorCategory::Infra, e.to_string()).await,
+            };
+            let model_id = request.model_id.clone();
+            let serve = self.run_streaming_inference(request, session, sender, cancel, circuit);
+            let streamed = self.with_model_capacity(&model_id, serve).await;
+            // Give the queue slot back however the stream ended
+            self.queue.complete(ticket.id).await;
+            streamed
+        }
+    }
+
+    /// Refuse a stream the way `reject_stream` does; it generated nothing.
+    async fn refuse_stream(
+        &self,
+        request_id: RequestId,
+        category: ErrorCategory,
+        message: String,
+        sender: &dyn StreamSender,
+    ) -> Streamed {
+        self.reject_stream(request_id, category, message, sender).await?;
+        Ok(None)
+    }
+
+    /// Count a stream refused under `category` and tell the client why.
+    async fn reject_stream(
+        &self,
+        request_id: RequestId,
+        category: ErrorCategory,
+        message: String,
+        sender: &dyn StreamSender,
+    ) -> Result<(), HandlerError> {
+        self.metrics_store.increment_counter(category.counter_name(), 1);
+        telemetry::record_error_category(category);
+        let chunk = StreamChunk::error(request_id, message);
+        sender.send(IpcMessage::StreamChunk(chunk)).await
+    }
+
+    /// Internal streaming implementation (gguf feature only).
+    #[cfg(feature = "gguf")]
+    async fn run_streaming_inference(
+        &self,
+        request: InferenceRequest,
+        session: &SessionToken,
+        sender: &dyn StreamSender,
+        cancel: CancellationToken,
+        circuit: CircuitPass,
+    ) -> Streamed {
+        let request_id = request.request_id;
+        let model_id = request.model_id.clone();
+        let prompt = request.prompt.clone();
+        let active = self.active.begin(request_id, &model_id, Some(session));
+        let config = InferenceConfig {
+            decode_signal: active.decode_signal(),
+            cancel: cancel.clone(),
+            ..self.inference_engine.config_for(&request.parameters)
+        };
+        let engine = Arc::clone(&self.inference_engine);
+        let mut relay = Relay {
+            request_id,
+            coalescer: StreamCoalescer::new(request_id, self.config.stream_coalesce.clone()),
+            limiter: self.output_filter.limiter(),
+            client_metadata: request.client_metadata.clone(),
+            started: Instant::now(),
+            last_token: None,
+            tokens: 0,
+        };
+
+        // Create channel for token streaming
+        let (token_sender, mut stream) = TokenStream::new(32);
+
+        // Spawn blocking inference task
+        let inf_handle = tokio::task::spawn_blocking(move || {
+            engine.run_stream_sync(&model_id, &prompt, &config, token_sender)
+        });
+
+        // Relay tokens to IPC, handling cancellation
+        loop {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    // Cancellation is not an error: end the stream cleanly
+                    let _ = relay.end(sender).await;
+                    break;
+                }
+                _ = sleep_until_deadline(relay.coalescer.deadline()) => {
+                    if let Some(batch) = relay.coalescer.flush() {
+                        sender.send(batch).await?;
+                    }
+                }
+                token_opt = stream.next() => {
+                    let Some(output) = token_opt else {
+                        // Channel closed
+                        if let Some(batch) = relay.coalescer.flush() {
+                            sender.send(batch).await?;
+                        }
+                        break;
+                    };
+                    if !self.relay_token(&mut relay, &request.model_id, output, sender).await? {
+                        // Stops generation if the stream ended early
+                        cancel.cancel();
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Wait for inference task (tokens already sent; outcome feeds health)
+        let outcome = inf_handle.await;
+        Ok(self.finish_stream(&request.model_id, outcome, circuit, &relay))
+    }
+}
@@ -0,0 +1,98 @@
+//! Hot-swap handler.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::load::{load_weights, open_model_file};
+use super::IpcHandler;
+use crate::ipc::protocol::{IpcMessage, SwapModelResponse};
+use crate::models::{
+    FlightTracker, ModelRegistry, ModelReplacement, ModelRouter, SwapError, SwapManager, SwapResult,
+};
+
+/// How long a `SwapModel` waits for the old model's in-flight requests.
+const SWAP_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Swap manager draining through `flights`. Requests route through the
+/// inference engine, so `router` only mirrors routes added to it.
+pub(super) fn swap_manager(
+    registry: &Arc<ModelRegistry>,
+    router: &Arc<ModelRouter>,
+    flights: &Arc<FlightTracker>,
+) -> SwapManager {
+    SwapManager::new(Arc::clone(registry), Arc::clone(router), Arc::clone(flights))
+}
+
+impl IpcHandler {
+    /// Load `new_path` and hot-swap it in for `model_id`.
+    ///
+    /// Requests keep landing on the old model until the engine is repointed;
+    /// from then on they land on the new one while the old one drains. A
+    /// manifest shipped with the new file replaces the chat template.
+    pub(super) async fn handle_swap(&self, model_id: String, new_path: String) -> IpcMessage {
+        let (Some(loader), Some(factory)) = (&self.model_loader, &self.model_factory) else {
+            return IpcMessage::Error {
+                code: 503,
+                message: "Model swapping not available".into(),
+            };
+        };
+        // Checked before loading so an unknown model costs no weights
+        if self.inference_engine.get_handle(&model_id).await.is_none() {
+            return IpcMessage::Error {
+                code: 404,
+                message: format!("Model not loaded: {}", model_id),
+            };
+        }
+        let file = match open_model_file(loader, &model_id, &new_path) {
+            Ok(file) => file,
+            Err(response) => return response,
+        };
+        let model = match load_weights(loader, factory, &file, &model_id).await {
+            Ok(model) => model,
+            Err(response) => return response,
+        };
+        let template = file.chat_template();
+        let replacement = ModelReplacement {
+            path: file.path.as_path().to_path_buf(),
+            metadata: file.metadata,
+            format: file.format,
+            model,
+        };
+        let swapped = self
+            .swaps
+            .swap_engine_model(&self.inference_engine, &model_id, replacement, SWAP_DRAIN_TIMEOUT)
+            .await;
+        if let (Ok(_), Some(template)) = (&swapped, template) {
+            self.inference_engine.set_chat_template(&model_id, template).await;
+        }
+        self.swap_response(model_id, swapped)
+    }
+
+    fn swap_response(&self, model_id: String, swapped: Result<SwapResult, SwapError>) -> IpcMessage {
+        let result = match swapped {
+            Ok(result) => result,
+            Err(e) => {
+                let code = match e {
+                    SwapError::RouteNotFound(_) => 404,
+                    SwapError::SwapInProgress => 409,
+                    SwapError::DrainTimeout => 504,
+                    SwapError::PreloadFailed(_) => 503,
+                };
+                return IpcMessage::Error {
+                    code,
+                    message: e.to_string(),
+                };
+            }
+        };
+        self.dedup.clear();
+        if !result.drained {
+            tracing::warn!(model_id = %model_id, "swapped model still has requests on its old handle");
+        }
+        IpcMessage::SwapModelResponse(SwapModelResponse {
+            model_id,
+            old_handle_id: result.old_handle.id(),
+            new_handle_id: result.new_handle.id(),
+            drain_ms: result.drain_duration.as_millis() as u64,
+        })
+    }
+}
@@ -6,8 +6,9 @@
 use std::sync::Arc;
 
 use super::protocol::{HealthCheckResponse, HealthCheckType};
-use crate::health::HealthChecker;
-use crate::models::ModelRegistry;
+use crate::engine::InferenceEngine;
+use crate::health::{HealthChecker, NotReadyReason};
+use crate::models::{LoadedModelState, ModelRegistry};
 use crate::scheduler::RequestQueue;
 use crate::shutdown::ShutdownCoordinator;
 
@@ -17,6 +18,7 @@ pub struct HealthHandler {
     shutdown: Arc<ShutdownCoordinator>,
     model_registry: Arc<ModelRegistry>,
     queue: Arc<RequestQueue>,
+    inference_engine: Arc<InferenceEngine>,
 }
 
 impl HealthHandler {
@@ -25,8 +27,9 @@ impl HealthHandler {
         shutdown: Arc<ShutdownCoordinator>,
         model_registry: Arc<ModelRegistry>,
         queue: Arc<RequestQueue>,
+        inference_engine: Arc<InferenceEngine>,
     ) -> Self {
-        Self { health, shutdown, model_registry, queue }
+        Self { health, shutdown, model_registry, queue, inference_engine }
     }
 
     /// Handle a health check request. Returns appropriate response.
@@ -38,7 +41,16 @@ impl HealthHandler {
         match check_type {
             HealthCheckType::Liveness => self.liveness_response(),
             HealthCheckType::Readiness => {
-                self.readiness_response(shutdown_state, models, queue_len)
+                let mut reason = self.health.not_ready_reason(shutdown_state, models, queue_len);
+                if reason.is_none() {
+                    reason = self.model_not_ready_reason().await;
+                }
+                HealthCheckResponse {
+                    check_type: HealthCheckType::Readiness,
+                    ok: reason.is_none(),
+                    report: None,
+                    reason,
+                }
             }
             HealthCheckType::Full => {
                 self.full_response(shutdown_state, models, queue_len).await
@@ -51,20 +63,24 @@ impl HealthHandler {
             check_type: HealthCheckType::Liveness,
             ok: self.health.is_alive(),
             report: None,
+            reason: None,
         }
     }
 
-    fn readiness_response(
-        &self,
-        shutdown_state: crate::shutdown::ShutdownState,
-        models: usize,
-        queue_len: usize,
-    ) -> HealthCheckResponse {
-        HealthCheckResponse {
-            check_type: HealthCheckType::Readiness,
-            ok: self.health.is_ready(shutdown_state, models, queue_len),
-            report: None,
+    /// Ready only if no model is still loading and every loaded model
+    /// completes a forward step.
+    async fn model_not_ready_reason(&self) -> Option<NotReadyReason> {
+        let models = self.model_registry.list_models().await;
+        if models.iter().any(|m| m.state == LoadedModelState::Loading) {
+            return Some(NotReadyReason::ModelsLoading);
+        }
+        for model_id in self.inference_engine.model_ids().await {
+            if let Err(e) = self.inference_engine.ping(&model_id).await {
+                tracing::warn!(model_id = %model_id, error = %e, "readiness ping failed");
+                return Some(NotReadyReason::WorkerWedged);
+            }
         }
+        None
     }
 
     async fn full_response(
@@ -79,6 +95,7 @@ impl HealthHandler {
             check_type: HealthCheckType::Full,
             ok: report.ready,
             report: Some(report),
+            reason: self.health.not_ready_reason(shutdown_state, models, queue_len),
         }
     }
 }
@@ -3,25 +3,50 @@
 //! Handles named pipe/Unix socket communication with authenticated callers.
 //! This is the ONLY external interface - no HTTP/REST/WebSocket allowed.
 
+mod active_requests;
 mod auth;
+pub mod clock;
+pub mod compression;
+mod config_reload;
 mod connections;
 pub mod encoding;
 mod handler;
 mod health_handler;
 pub mod protocol;
+mod protocol_stats;
 pub mod server;
+mod session_streams;
+mod strict;
+mod stream_cancel;
 mod stream_bridge;
+pub mod stream_coalesce;
 
-pub use auth::{AuthError, SessionAuth, SessionToken};
+pub use active_requests::{ActiveRequestGuard, ActiveRequests};
+pub use auth::{
+    AuthError, AuthRateLimitConfig, SessionAuth, SessionLimitConfig, SessionLimitPolicy,
+    SessionToken, ADMIN_TOKEN_LABEL, DEFAULT_SESSION_GRACE, DEFAULT_TOKEN_LABEL,
+};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use compression::{Compression, CompressionConfig, CompressionError, FrameCodec};
+pub use config_reload::{ConfigSource, RELOADABLE_FIELDS};
 pub use connections::{ConnectionConfig, ConnectionGuard, ConnectionPool, OwnedConnectionGuard};
 pub use encoding::{get_encoder, TokenEncoder, V1Encoder, V2Encoder};
-pub use handler::{HandlerError, IpcHandler, IpcHandlerConfig, StreamSender};
+pub use handler::{
+    HandlerConfigError, HandlerError, IpcHandler, IpcHandlerConfig, StreamSender,
+    DEFAULT_MAX_CLIENT_PRIORITY, DEFAULT_MAX_STREAMS_PER_SESSION,
+};
+pub use protocol_stats::{
+    ConnectionCounters, ConnectionStats, DecodeErrorPolicy, ProtocolStats, ABUSIVE_CONNECTIONS_TOTAL,
+    BYTES_IN_TOTAL, BYTES_OUT_TOTAL, DECODE_ERRORS_TOTAL, MESSAGES_DECODED_TOTAL,
+    OVERSIZED_FRAMES_TOTAL,
+};
 pub use stream_bridge::IpcStreamBridge;
+pub use stream_coalesce::{StreamCoalesceConfig, StreamCoalescer};
 pub use protocol::{
-    decode_message, decode_message_binary, encode_message, encode_message_binary,
-    HealthCheckResponse, HealthCheckType, InferenceRequest, InferenceResponse, IpcMessage,
-    ModelInfo, ModelsListResponse, ProtocolError, ProtocolVersion, RequestId, StreamChunk,
-    WarmupRequest, WarmupResponse,
+    decode_message, decode_message_binary, decode_message_strict, encode_message, encode_message_binary,
+    ActiveRequestInfo, ChatRequest, ConfigReloadResponse, DiagnoseModelRequest, DrainResponse, EffectiveConfig, HealthCheckResponse, HealthCheckType, InferenceRequest, InferenceResponse, IpcMessage,
+    LoadModelResponse, ModelInfo, ModelStats, ModelsListResponse, PingModelRequest, PingModelResponse, ProtocolError, ProtocolVersion,
+    RequestId, RequestPhase, StreamBatch, StreamChunk, SwapModelResponse, UnloadModelResponse, WarmupRequest, WarmupResponse,
 };
 // Re-export MetricsSnapshot for IPC consumers
 pub use crate::telemetry::MetricsSnapshot;
@@ -5,11 +5,16 @@
 //! - Protocol versioning enables backward-compatible security updates
 //! - Response size limits prevent resource exhaustion
 
+use base64ct::{Base64, Encoding};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::engine::InferenceParams;
-use crate::health::HealthReport;
+use super::compression::Compression;
+use crate::ab_testing::{AbExperiment, ExperimentResults};
+use crate::engine::{ChatMessage, ErrorCategory, InferenceParams, ModelDiagnostic};
+use crate::health::{HealthReport, NotReadyReason};
+use crate::models::CircuitState;
+use crate::scheduler::Priority;
 use crate::telemetry::{ExportableSpan, MetricsSnapshot};
 
 /// Model information for diagnostics.
@@ -44,12 +49,67 @@ pub struct ModelsListResponse {
     pub total_memory_bytes: u64,
 }
 
+/// Execution phase of an in-flight request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestPhase {
+    /// Processing the prompt; no token generated yet.
+    Prefill,
+    /// Generating tokens.
+    Decode,
+}
+
+/// A request currently executing on a model (not waiting in the queue).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveRequestInfo {
+    pub request_id: RequestId,
+    pub model_id: String,
+    /// Milliseconds since execution started.
+    pub age_ms: u64,
+    /// Leading characters of the submitting session's fingerprint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_prefix: Option<String>,
+    pub phase: RequestPhase,
+}
+
+/// Placeholder sent in place of secret configuration values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Effective runtime configuration as reported over IPC.
+///
+/// # Security
+/// Secrets are replaced with [`REDACTED`]; an unset secret stays empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveConfig {
+    pub base_path: String,
+    pub auth_token: String,
+    pub session_timeout_secs: u64,
+    pub max_context_length: usize,
+    pub top_p_floor: f32,
+    pub max_registered_models: usize,
+    pub shutdown_timeout_secs: u64,
+    /// Remaining settings and nested subsystem configs, as serialized.
+    pub sections: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+/// Outcome of a `ReloadConfig`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigReloadResponse {
+    /// Reloadable fields whose new values are now in effect.
+    pub applied: Vec<String>,
+    /// Fields that changed in the source but only take effect on restart.
+    pub restart_required: Vec<String>,
+}
+
 /// Current protocol version for new connections.
 pub const CURRENT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1;
 
 /// Minimum supported protocol version.
 pub const MIN_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1;
 
+/// Maximum supported protocol version.
+pub const MAX_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V2;
+
 /// Protocol version for negotiating encoding strategies.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProtocolVersion {
@@ -57,6 +117,9 @@ pub enum ProtocolVersion {
     V1,
     /// V2: Packed varint encoding (experimental).
     V2,
+    /// A version this server does not know (e.g. from a newer client).
+    #[serde(other)]
+    Unknown,
 }
 
 impl Default for ProtocolVersion {
@@ -66,6 +129,15 @@ impl Default for ProtocolVersion {
 }
 
 impl ProtocolVersion {
+    /// Numeric version (V1 = 1, V2 = 2).
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            ProtocolVersion::V1 => 1,
+            ProtocolVersion::V2 => 2,
+            ProtocolVersion::Unknown => 0,
+        }
+    }
+
     /// Check if this version is supported.
     pub fn is_supported(&self) -> bool {
         matches!(self, ProtocolVersion::V1 | ProtocolVersion::V2)
@@ -80,6 +152,18 @@ impl ProtocolVersion {
             CURRENT_PROTOCOL_VERSION
         }
     }
+
+    /// Why [`negotiate`](Self::negotiate) would not grant the client's
+    /// request, or `None` if it would.
+    pub fn downgrade_reason(client_requested: Option<ProtocolVersion>) -> Option<String> {
+        match client_requested {
+            Some(requested) if !requested.is_supported() => Some(format!(
+                "requested protocol version {:?} is not supported (server supports {:?} to {:?}); using {:?}",
+                requested, MIN_PROTOCOL_VERSION, MAX_PROTOCOL_VERSION, CURRENT_PROTOCOL_VERSION
+            )),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -95,6 +179,49 @@ pub enum ProtocolError {
 
     #[error("Message too large: {size} bytes (max {max})")]
     MessageTooLarge { size: usize, max: usize },
+
+    #[error("Field too large: {field} is {size} bytes (max {max})")]
+    FieldTooLarge {
+        field: String,
+        size: usize,
+        max: usize,
+    },
+
+    #[error("Unknown fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+
+    #[error("Unsupported protocol version {requested:?}: server supports {min:?} to {max:?}")]
+    UnsupportedProtocolVersion {
+        requested: ProtocolVersion,
+        min: ProtocolVersion,
+        max: ProtocolVersion,
+    },
+
+    #[error("Prompt too long: {bytes} bytes (max {max})")]
+    PromptTooLong { bytes: usize, max: usize },
+}
+
+/// Maximum size of opaque client metadata echoed back in responses.
+pub const MAX_CLIENT_METADATA_BYTES: usize = 4096;
+
+/// Maximum size of a request's stable client ID.
+pub const MAX_CLIENT_ID_BYTES: usize = 256;
+
+/// Default cap on token-array prompts (FFI), regardless of model context
+/// length.
+pub const DEFAULT_MAX_PROMPT_TOKENS: usize = 32_768;
+
+/// Default cap on text prompts, in bytes (about 32K tokens at the average
+/// ~4 bytes per token), far below `MAX_MESSAGE_SIZE`.
+pub const DEFAULT_MAX_PROMPT_BYTES: usize = 128 * 1024;
+
+/// `error_code` for a request that timed out waiting on a full queue.
+/// Nothing was started, so the client may retry it as-is.
+pub const QUEUE_TIMEOUT_ERROR_CODE: u16 = 429;
+
+/// Token count of `prompt` before tokenization (avg ~4 bytes per token).
+pub fn estimate_prompt_tokens(prompt: &str) -> usize {
+    prompt.len().div_ceil(4)
 }
 
 /// Unique request identifier.
@@ -105,34 +232,149 @@ pub struct RequestId(pub u64);
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceRequest {
     pub request_id: RequestId,
+    /// Empty (or omitted) selects the server's default model, if configured.
+    #[serde(default)]
     pub model_id: String,
     /// Text prompt for inference (tokenization handled by model).
     pub prompt: String,
     pub parameters: InferenceParams,
+    /// Opaque client data (e.g. trace ID) echoed unchanged in the response.
+    /// Never interpreted by the runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_metadata: Option<String>,
+    /// Requested queue priority (omitted = `normal`). The server caps it at
+    /// `IpcHandlerConfig::max_client_priority`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Stable ID of the calling client (e.g. an install or user ID), kept
+    /// across sessions. A running A/B experiment serves every request with
+    /// the same ID from the same arm; requests without one are assigned
+    /// independently.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
 }
 
 impl InferenceRequest {
+    /// Fill an empty `model_id` with `default_model`, if one is configured.
+    pub fn resolve_model(&mut self, default_model: Option<&str>) {
+        if self.model_id.is_empty() {
+            if let Some(default_model) = default_model {
+                self.model_id = default_model.to_string();
+            }
+        }
+    }
+
     pub fn validate(&self) -> Result<(), ProtocolError> {
+        self.validate_with_max_prompt_bytes(DEFAULT_MAX_PROMPT_BYTES)
+    }
+
+    /// Like [`validate`](Self::validate), with an explicit prompt size cap
+    /// in bytes.
+    pub fn validate_with_max_prompt_bytes(
+        &self,
+        max_prompt_bytes: usize,
+    ) -> Result<(), ProtocolError> {
         if self.model_id.is_empty() {
-            return Err(ProtocolError::MissingField("model_id".into()));
+            return Err(ProtocolError::MissingField(
+                "model_id (no default model configured)".into(),
+            ));
         }
         if self.prompt.is_empty() {
             return Err(ProtocolError::MissingField("prompt".into()));
         }
+        if self.prompt.len() > max_prompt_bytes {
+            return Err(ProtocolError::PromptTooLong {
+                bytes: self.prompt.len(),
+                max: max_prompt_bytes,
+            });
+        }
+        if let Some(metadata) = &self.client_metadata {
+            if metadata.len() > MAX_CLIENT_METADATA_BYTES {
+                return Err(ProtocolError::FieldTooLarge {
+                    field: "client_metadata".into(),
+                    size: metadata.len(),
+                    max: MAX_CLIENT_METADATA_BYTES,
+                });
+            }
+        }
+        if let Some(client_id) = &self.client_id {
+            if client_id.len() > MAX_CLIENT_ID_BYTES {
+                return Err(ProtocolError::FieldTooLarge {
+                    field: "client_id".into(),
+                    size: client_id.len(),
+                    max: MAX_CLIENT_ID_BYTES,
+                });
+            }
+        }
         Ok(())
     }
 }
 
+/// Chat inference request: role-tagged messages rendered with the model's
+/// chat template, then served like an `InferenceRequest` with that prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatRequest {
+    pub request_id: RequestId,
+    /// Empty (or omitted) selects the server's default model, if configured.
+    #[serde(default)]
+    pub model_id: String,
+    pub messages: Vec<ChatMessage>,
+    pub parameters: InferenceParams,
+    /// Opaque client data echoed unchanged in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_metadata: Option<String>,
+    /// Requested queue priority, capped as for `InferenceRequest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Stable client ID, as for `InferenceRequest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+}
+
+impl ChatRequest {
+    /// The text request serving this chat once rendered to `prompt`.
+    pub fn into_inference(self, prompt: String) -> InferenceRequest {
+        InferenceRequest {
+            request_id: self.request_id,
+            model_id: self.model_id,
+            prompt,
+            parameters: self.parameters,
+            client_metadata: self.client_metadata,
+            priority: self.priority,
+            client_id: self.client_id,
+        }
+    }
+}
+
 /// Inference response to caller.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceResponse {
     pub request_id: RequestId,
-    /// Generated text output from model.
+    /// Generated text output from model. Empty when `output_bytes` is set.
     pub output: String,
+    /// Exact output bytes, base64-encoded (`output_encoding: bytes` only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_bytes: Option<String>,
     /// Number of tokens generated.
     pub tokens_generated: usize,
     pub finished: bool,
     pub error: Option<String>,
+    /// Error class as a status code: 400 client, 429 queue wait timed out,
+    /// 502 model, 503 infra.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<u16>,
+    /// Client metadata echoed from the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_metadata: Option<String>,
+    /// Time spent in the request queue, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_wait_ms: Option<f64>,
+    /// Time spent processing the prompt before decoding, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefill_ms: Option<f64>,
+    /// Time spent generating tokens, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decode_ms: Option<f64>,
 }
 
 impl InferenceResponse {
@@ -140,9 +382,15 @@ impl InferenceResponse {
         Self {
             request_id,
             output,
+            output_bytes: None,
             tokens_generated,
             finished,
             error: None,
+            error_code: None,
+            client_metadata: None,
+            queue_wait_ms: None,
+            prefill_ms: None,
+            decode_ms: None,
         }
     }
 
@@ -150,11 +398,60 @@ impl InferenceResponse {
         Self {
             request_id,
             output: String::new(),
+            output_bytes: None,
             tokens_generated: 0,
             finished: true,
             error: Some(error),
+            error_code: None,
+            client_metadata: None,
+            queue_wait_ms: None,
+            prefill_ms: None,
+            decode_ms: None,
+        }
+    }
+
+    /// Success response carrying exact output bytes instead of text.
+    pub fn success_bytes(
+        request_id: RequestId,
+        output: &[u8],
+        tokens_generated: usize,
+        finished: bool,
+    ) -> Self {
+        Self {
+            output_bytes: Some(Base64::encode_string(output)),
+            ..Self::success(request_id, String::new(), tokens_generated, finished)
+        }
+    }
+
+    /// Decode `output_bytes`, if present.
+    pub fn decode_output_bytes(&self) -> Option<Result<Vec<u8>, ProtocolError>> {
+        self.output_bytes.as_ref().map(|encoded| {
+            Base64::decode_vec(encoded)
+                .map_err(|e| ProtocolError::InvalidFormat(format!("output_bytes: {}", e)))
+        })
+    }
+
+    /// Error response tagged with the status code for its category.
+    pub fn failed(request_id: RequestId, category: ErrorCategory, error: String) -> Self {
+        Self {
+            error_code: Some(category.status_code()),
+            ..Self::error(request_id, error)
         }
     }
+
+    /// Attach client metadata echoed from the request.
+    pub fn with_client_metadata(mut self, client_metadata: Option<String>) -> Self {
+        self.client_metadata = client_metadata;
+        self
+    }
+
+    /// Attach the request's queue, prefill and decode durations.
+    pub fn with_timing(mut self, queue_wait_ms: f64, prefill_ms: f64, decode_ms: f64) -> Self {
+        self.queue_wait_ms = Some(queue_wait_ms);
+        self.prefill_ms = Some(prefill_ms);
+        self.decode_ms = Some(decode_ms);
+        self
+    }
 }
 
 /// Single token chunk for streaming responses.
@@ -167,6 +464,9 @@ pub struct StreamChunk {
     pub text: Option<String>,
     pub is_final: bool,
     pub error: Option<String>,
+    /// Client metadata echoed from the request (final chunk only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_metadata: Option<String>,
 }
 
 impl StreamChunk {
@@ -178,6 +478,7 @@ impl StreamChunk {
             text: None,
             is_final: false,
             error: None,
+            client_metadata: None,
         }
     }
 
@@ -189,6 +490,7 @@ impl StreamChunk {
             text: Some(text),
             is_final: false,
             error: None,
+            client_metadata: None,
         }
     }
 
@@ -200,6 +502,7 @@ impl StreamChunk {
             text: None,
             is_final: true,
             error: None,
+            client_metadata: None,
         }
     }
 
@@ -211,6 +514,19 @@ impl StreamChunk {
             text: Some(text),
             is_final: true,
             error: None,
+            client_metadata: None,
+        }
+    }
+
+    /// Create a final chunk carrying no token (stream ended early by cancel).
+    pub fn end(request_id: RequestId) -> Self {
+        Self {
+            request_id,
+            token: 0,
+            text: None,
+            is_final: true,
+            error: None,
+            client_metadata: None,
         }
     }
 
@@ -222,8 +538,30 @@ impl StreamChunk {
             text: None,
             is_final: true,
             error: Some(error),
+            client_metadata: None,
         }
     }
+
+    /// Attach client metadata echoed from the request.
+    pub fn with_client_metadata(mut self, client_metadata: Option<String>) -> Self {
+        self.client_metadata = client_metadata;
+        self
+    }
+}
+
+/// Several streamed tokens coalesced into one frame.
+///
+/// Sent instead of per-token `StreamChunk`s when stream coalescing is enabled.
+/// Error and cancellation still use `StreamChunk::error`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamBatch {
+    pub request_id: RequestId,
+    /// Tokens in generation order.
+    pub tokens: Vec<u32>,
+    pub is_final: bool,
+    /// Client metadata echoed from the request (final batch only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_metadata: Option<String>,
 }
 
 /// Warmup request to prime a model.
@@ -268,6 +606,84 @@ impl WarmupResponse {
     }
 }
 
+/// Single forward-step probe that a model is servable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingModelRequest {
+    pub model_id: String,
+}
+
+/// Result of a model ping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingModelResponse {
+    pub model_id: String,
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Outcome of draining a model's in-flight requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainResponse {
+    pub model_id: String,
+    /// Every in-flight request finished before the timeout.
+    pub drained: bool,
+    /// Requests still running when the drain gave up (0 if drained).
+    pub in_flight_remaining: usize,
+}
+
+/// Outcome of a `SwapModel` hot-swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapModelResponse {
+    pub model_id: String,
+    /// Handle that served `model_id` before the swap, now unloaded.
+    pub old_handle_id: u64,
+    /// Handle serving `model_id` from now on.
+    pub new_handle_id: u64,
+    /// Time spent waiting for the old handle's in-flight requests.
+    pub drain_ms: u64,
+}
+
+/// Live per-model stats served to `ModelStatsRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelStats {
+    pub model_id: String,
+    /// Requests executing on the model right now.
+    pub in_flight: usize,
+    /// Requests the model has completed successfully.
+    pub total_requests: u64,
+    /// Mean latency of those requests in milliseconds (0 if none).
+    pub avg_latency_ms: f64,
+    /// Memory held by the model in bytes.
+    pub memory_bytes: u64,
+    /// The model's circuit breaker; `open` while requests fail fast.
+    #[serde(default)]
+    pub circuit_state: CircuitState,
+}
+
+/// A model registered by `LoadModelRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadModelResponse {
+    pub model_id: String,
+    pub handle_id: u64,
+    pub format: String,
+    pub size_bytes: u64,
+}
+
+/// A model removed by `UnloadModelRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnloadModelResponse {
+    pub model_id: String,
+    pub handle_id: u64,
+    /// Memory the registry accounted to the model.
+    pub freed_bytes: u64,
+}
+
+/// Per-check servability diagnosis of one model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnoseModelRequest {
+    pub model_id: String,
+}
+
 /// Health check request types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HealthCheckType {
@@ -282,6 +698,9 @@ pub struct HealthCheckResponse {
     pub check_type: HealthCheckType,
     pub ok: bool,
     pub report: Option<HealthReport>,
+    /// Why readiness failed. Absent when `ok` is true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<NotReadyReason>,
 }
 
 /// All possible IPC message types.
@@ -294,6 +713,12 @@ pub enum IpcMessage {
         /// Optional protocol version request. Defaults to V1 if not specified.
         #[serde(default)]
         protocol_version: Option<ProtocolVersion>,
+        /// Optional response compression request. Off unless the server enables it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        compression: Option<Compression>,
+        /// Reject the handshake instead of downgrading an unsupported version.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        strict_version: bool,
     },
 
     #[serde(rename = "handshake_ack")]
@@ -302,6 +727,12 @@ pub enum IpcMessage {
         /// Negotiated protocol version for this session.
         #[serde(default)]
         protocol_version: ProtocolVersion,
+        /// Compression applied to all subsequent server frames.
+        #[serde(default, skip_serializing_if = "Compression::is_none")]
+        compression: Compression,
+        /// Why the negotiated version differs from the one requested.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        downgrade_reason: Option<String>,
     },
 
     #[serde(rename = "inference_request")]
@@ -310,9 +741,16 @@ pub enum IpcMessage {
     #[serde(rename = "inference_response")]
     InferenceResponse(InferenceResponse),
 
+    /// Answered with an `InferenceResponse`.
+    #[serde(rename = "chat_request")]
+    ChatRequest(ChatRequest),
+
     #[serde(rename = "stream_chunk")]
     StreamChunk(StreamChunk),
 
+    #[serde(rename = "stream_batch")]
+    StreamBatch(StreamBatch),
+
     #[serde(rename = "health_check")]
     HealthCheck { check_type: HealthCheckType },
 
@@ -332,7 +770,12 @@ pub enum IpcMessage {
     PrometheusMetricsResponse { text: String },
 
     #[serde(rename = "spans_request")]
-    SpansRequest { max_count: usize },
+    SpansRequest {
+        max_count: usize,
+        /// Return only this request's spans, leaving the buffer intact.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        request_id: Option<RequestId>,
+    },
 
     #[serde(rename = "spans_response")]
     SpansResponse { spans: Vec<ExportableSpan> },
@@ -346,18 +789,118 @@ pub enum IpcMessage {
         cancelled: bool,
     },
 
+    /// Cancel every pending request (shutdown or emergency).
+    #[serde(rename = "cancel_all_request")]
+    CancelAllRequest,
+
+    #[serde(rename = "cancel_all_response")]
+    CancelAllResponse { cancelled: usize },
+
+    /// List requests currently executing, oldest first.
+    #[serde(rename = "active_requests_request")]
+    ActiveRequestsRequest,
+
+    #[serde(rename = "active_requests_response")]
+    ActiveRequestsResponse { requests: Vec<ActiveRequestInfo> },
+
+    /// Stop routing new requests to a model and wait for in-flight ones.
+    #[serde(rename = "drain_model")]
+    DrainModel { model_id: String, timeout_ms: u64 },
+
+    #[serde(rename = "drain_response")]
+    DrainResponse(DrainResponse),
+
+    /// Replace a loaded model with the file at `new_path` without dropping
+    /// requests.
+    #[serde(rename = "swap_model")]
+    SwapModel { model_id: String, new_path: String },
+
+    #[serde(rename = "swap_model_response")]
+    SwapModelResponse(SwapModelResponse),
+
+    /// Register a model file under `models/`; `path` defaults to
+    /// `models/<model_id>.gguf`.
+    #[serde(rename = "load_model_request")]
+    LoadModelRequest {
+        model_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+
+    #[serde(rename = "load_model_response")]
+    LoadModelResponse(LoadModelResponse),
+
+    #[serde(rename = "unload_model_request")]
+    UnloadModelRequest { model_id: String },
+
+    #[serde(rename = "unload_model_response")]
+    UnloadModelResponse(UnloadModelResponse),
+
+    #[serde(rename = "model_info_request")]
+    ModelInfoRequest { model_id: String },
+
+    #[serde(rename = "model_info_response")]
+    ModelInfoResponse(ModelInfo),
+
+    #[serde(rename = "model_stats_request")]
+    ModelStatsRequest { model_id: String },
+
+    #[serde(rename = "model_stats_response")]
+    ModelStatsResponse(ModelStats),
+
     #[serde(rename = "warmup_request")]
     WarmupRequest(WarmupRequest),
 
     #[serde(rename = "warmup_response")]
     WarmupResponse(WarmupResponse),
 
+    #[serde(rename = "ping_model_request")]
+    PingModelRequest(PingModelRequest),
+
+    #[serde(rename = "ping_model_response")]
+    PingModelResponse(PingModelResponse),
+
+    #[serde(rename = "diagnose_model_request")]
+    DiagnoseModelRequest(DiagnoseModelRequest),
+
+    #[serde(rename = "diagnose_model_response")]
+    DiagnoseModelResponse(ModelDiagnostic),
+
     #[serde(rename = "models_request")]
     ModelsRequest,
 
     #[serde(rename = "models_response")]
     ModelsResponse(ModelsListResponse),
 
+    #[serde(rename = "config_request")]
+    ConfigRequest,
+
+    #[serde(rename = "config_response")]
+    ConfigResponse(EffectiveConfig),
+
+    /// Re-read the configuration and apply the fields that can change
+    /// without a restart.
+    #[serde(rename = "reload_config")]
+    ReloadConfig,
+
+    #[serde(rename = "config_reload_response")]
+    ConfigReloadResponse(ConfigReloadResponse),
+
+    /// Start, replace or (with `null`) stop the A/B experiment. Replacing
+    /// an experiment discards its results.
+    #[serde(rename = "set_experiment")]
+    SetExperiment {
+        #[serde(default)]
+        experiment: Option<AbExperiment>,
+    },
+
+    #[serde(rename = "get_experiment_results")]
+    GetExperimentResults,
+
+    /// Per-arm results of the running experiment; `null` when none runs.
+    #[serde(rename = "experiment_results")]
+    ExperimentResults { results: Option<ExperimentResults> },
+
     #[serde(rename = "error")]
     Error { code: u32, message: String },
 }
@@ -418,6 +961,27 @@ pub fn decode_message(bytes: &[u8]) -> Result<IpcMessage, ProtocolError> {
     Ok(serde_json::from_slice(bytes)?)
 }
 
+/// Decode message from bytes, rejecting fields the protocol does not define.
+///
+/// For hardened deployments: an unrecognized field may signal protocol
+/// confusion or a client speaking a different version, so it is an error
+/// rather than silently ignored as in [`decode_message`].
+pub fn decode_message_strict(bytes: &[u8]) -> Result<IpcMessage, ProtocolError> {
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::MessageTooLarge {
+            size: bytes.len(),
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+    let value: serde_json::Value = serde_json::from_slice(bytes)?;
+    let message: IpcMessage = serde_json::from_value(value.clone())?;
+    let unknown = super::strict::unknown_fields(&value, &message);
+    if !unknown.is_empty() {
+        return Err(ProtocolError::UnknownFields(unknown));
+    }
+    Ok(message)
+}
+
 /// Encode message to bytes for IPC transport.
 ///
 /// Uses JSON encoding; bincode is incompatible with the internally-tagged
@@ -504,6 +1068,40 @@ mod tests {
         assert!(matches!(result, Err(ProtocolError::MessageTooLarge { .. })));
     }
 
+    #[test]
+    fn test_extra_field_accepted_lenient_rejected_strict() {
+        let json = br#"{"type":"inference_request","request_id":1,"model_id":"m","prompt":"hi",
+            "parameters":{"max_tokens":8,"temperature":0.7,"top_p":0.9,"top_k":40,"legacy_sampler":"greedy"},
+            "urgency":"high"}"#;
+
+        assert!(decode_message(json).is_ok());
+        match decode_message_strict(json) {
+            Err(ProtocolError::UnknownFields(fields)) => {
+                assert_eq!(fields, vec!["parameters.legacy_sampler", "urgency"]);
+            }
+            other => panic!("Expected UnknownFields, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_accepts_known_fields_at_defaults() {
+        // Known fields that serialize away at their defaults are not unknown
+        let json = br#"{"type":"inference_request","request_id":1,"model_id":"m","prompt":"hi",
+            "parameters":{"max_tokens":8,"temperature":0.7,"top_p":0.9,"top_k":40,
+            "output_encoding":"utf8","max_cpu_ms":null,"sampler":{"type":"default"},
+            "stop_sequences":[],"no_cache":false},"client_metadata":null}"#;
+        assert!(decode_message_strict(json).is_ok());
+
+        let handshake = encode_message(&IpcMessage::Handshake {
+            token: "t".into(),
+            protocol_version: Some(ProtocolVersion::V2),
+            compression: None,
+            strict_version: false,
+        })
+        .unwrap();
+        assert!(decode_message_strict(&handshake).is_ok());
+    }
+
     #[test]
     fn test_encode_message_binary_roundtrip() {
         let msg = IpcMessage::HealthCheck {
@@ -533,6 +1131,9 @@ mod tests {
             model_id: "test-model".to_string(),
             prompt: "Hello, world!".to_string(),
             parameters: InferenceParams::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
         assert!(valid.validate().is_ok());
 
@@ -541,6 +1142,9 @@ mod tests {
             model_id: "".to_string(),
             prompt: "Hello, world!".to_string(),
             parameters: InferenceParams::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
         assert!(invalid_model.validate().is_err());
 
@@ -549,10 +1153,67 @@ mod tests {
             model_id: "test".to_string(),
             prompt: "".to_string(),
             parameters: InferenceParams::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
         assert!(invalid_prompt.validate().is_err());
     }
 
+    #[test]
+    fn test_client_metadata_size_limit() {
+        let mut request = InferenceRequest {
+            request_id: RequestId(1),
+            model_id: "test".to_string(),
+            prompt: "Hello".to_string(),
+            parameters: InferenceParams::default(),
+            client_metadata: Some("x".repeat(MAX_CLIENT_METADATA_BYTES)),
+            priority: None,
+            client_id: None,
+        };
+        assert!(request.validate().is_ok());
+
+        request.client_metadata = Some("x".repeat(MAX_CLIENT_METADATA_BYTES + 1));
+        assert!(matches!(
+            request.validate(),
+            Err(ProtocolError::FieldTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_prompt_byte_cap() {
+        let mut request = InferenceRequest {
+            request_id: RequestId(1),
+            model_id: "test".to_string(),
+            prompt: "x".repeat(16),
+            parameters: InferenceParams::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
+        };
+        assert!(request.validate_with_max_prompt_bytes(16).is_ok());
+
+        // Counted in bytes, not characters
+        request.prompt.push('é');
+        assert!(matches!(
+            request.validate_with_max_prompt_bytes(16),
+            Err(ProtocolError::PromptTooLong { bytes: 18, max: 16 })
+        ));
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_client_metadata_omitted_when_none() {
+        let response = InferenceResponse::success(RequestId(1), "ok".to_string(), 1, true);
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("client_metadata"));
+
+        // Requests from older clients without the field still decode
+        let json = r#"{"request_id":1,"model_id":"m","prompt":"p","parameters":{"max_tokens":1,"temperature":0.5,"top_p":0.9,"top_k":40}}"#;
+        let request: InferenceRequest = serde_json::from_str(json).unwrap();
+        assert!(request.client_metadata.is_none());
+    }
+
     #[test]
     fn test_inference_response_success() {
         let response = InferenceResponse::success(RequestId(1), "Generated text".to_string(), 5, true);
@@ -571,6 +1232,18 @@ mod tests {
         assert!(response.output.is_empty());
     }
 
+    #[test]
+    fn test_inference_response_timing_is_optional() {
+        let old_client = r#"{"request_id":1,"output":"hi","tokens_generated":1,"finished":true,"error":null}"#;
+        let response: InferenceResponse = serde_json::from_str(old_client).unwrap();
+        assert!(response.queue_wait_ms.is_none() && response.decode_ms.is_none());
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("prefill_ms"));
+        let timed = response.with_timing(1.0, 2.0, 3.0);
+        assert_eq!(timed.prefill_ms, Some(2.0));
+    }
+
     #[test]
     fn test_stream_chunk_token() {
         let chunk = StreamChunk::token(RequestId(1), 42);
@@ -607,6 +1280,8 @@ mod tests {
         let msg = IpcMessage::Handshake {
             token: "test-token".to_string(),
             protocol_version: Some(ProtocolVersion::V2),
+            compression: None,
+            strict_version: false,
         };
         let encoded = encode_message(&msg).unwrap();
         let decoded: IpcMessage = serde_json::from_slice(&encoded).unwrap();
@@ -624,6 +1299,8 @@ mod tests {
         let msg = IpcMessage::HandshakeAck {
             session_id: "session-123".to_string(),
             protocol_version: ProtocolVersion::V1,
+            compression: Compression::None,
+            downgrade_reason: None,
         };
         let encoded = encode_message(&msg).unwrap();
         let decoded = decode_message(&encoded).unwrap();
@@ -632,6 +1309,7 @@ mod tests {
             IpcMessage::HandshakeAck {
                 session_id,
                 protocol_version: ProtocolVersion::V1,
+                ..
             } if session_id == "session-123"
         ));
     }
@@ -0,0 +1,319 @@
+//! Protocol-level traffic counters, per connection and in aggregate.
+//!
+//! Request metrics only see messages that decode. These counters cover the
+//! raw frames underneath: what was decoded, what failed to decode, bytes in
+//! each direction, and frames rejected for size. A connection whose decode
+//! error rate crosses a threshold is reported as a likely fuzzing client and
+//! can optionally be dropped.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
+
+use crate::telemetry::{labelled, log_security_event, MetricsStore, SecurityEvent};
+
+/// Aggregate counter names in the metrics store.
+pub const MESSAGES_DECODED_TOTAL: &str = "core_ipc_messages_decoded_total";
+pub const DECODE_ERRORS_TOTAL: &str = "core_ipc_decode_errors_total";
+pub const BYTES_IN_TOTAL: &str = "core_ipc_bytes_in_total";
+pub const BYTES_OUT_TOTAL: &str = "core_ipc_bytes_out_total";
+pub const OVERSIZED_FRAMES_TOTAL: &str = "core_ipc_oversized_frames_total";
+pub const ABUSIVE_CONNECTIONS_TOTAL: &str = "core_ipc_abusive_connections_total";
+
+/// Per-connection counter names, labelled with the connection ID.
+pub const CONN_MESSAGES_DECODED_TOTAL: &str = "core_ipc_conn_messages_decoded_total";
+pub const CONN_DECODE_ERRORS_TOTAL: &str = "core_ipc_conn_decode_errors_total";
+pub const CONN_BYTES_IN_TOTAL: &str = "core_ipc_conn_bytes_in_total";
+pub const CONN_BYTES_OUT_TOTAL: &str = "core_ipc_conn_bytes_out_total";
+pub const CONN_OVERSIZED_FRAMES_TOTAL: &str = "core_ipc_conn_oversized_frames_total";
+
+/// When a connection's decode errors mark it as abusive.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodeErrorPolicy {
+    /// Fraction of frames failing to decode above which a connection is flagged.
+    pub max_error_rate: f64,
+    /// Frames seen before the rate is evaluated, so one early typo is not fatal.
+    pub min_frames: u64,
+    /// Close flagged connections instead of only reporting them.
+    pub drop_connection: bool,
+}
+
+impl Default for DecodeErrorPolicy {
+    fn default() -> Self {
+        Self {
+            max_error_rate: 0.5,
+            min_frames: 20,
+            drop_connection: false,
+        }
+    }
+}
+
+/// Point-in-time counters for one connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionCounters {
+    pub messages_decoded: u64,
+    pub decode_errors: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub oversized_frames: u64,
+}
+
+impl ConnectionCounters {
+    /// Decode errors as a fraction of all frames received.
+    pub fn decode_error_rate(&self) -> f64 {
+        let frames = self.messages_decoded + self.decode_errors;
+        if frames == 0 {
+            0.0
+        } else {
+            self.decode_errors as f64 / frames as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct AtomicCounters {
+    messages_decoded: AtomicU64,
+    decode_errors: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    oversized_frames: AtomicU64,
+    flagged: AtomicBool,
+}
+
+impl AtomicCounters {
+    fn snapshot(&self) -> ConnectionCounters {
+        ConnectionCounters {
+            messages_decoded: self.messages_decoded.load(Ordering::Relaxed),
+            decode_errors: self.decode_errors.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            oversized_frames: self.oversized_frames.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Registry of live connections' counters, feeding the aggregate metrics.
+pub struct ProtocolStats {
+    metrics: Arc<MetricsStore>,
+    policy: DecodeErrorPolicy,
+    next_id: AtomicU64,
+    live: RwLock<HashMap<u64, Arc<AtomicCounters>>>,
+}
+
+impl ProtocolStats {
+    pub fn new(metrics: Arc<MetricsStore>, policy: DecodeErrorPolicy) -> Self {
+        Self {
+            metrics,
+            policy,
+            next_id: AtomicU64::new(1),
+            live: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Start counting for a new connection; counters are dropped with it.
+    pub fn open(self: &Arc<Self>) -> ConnectionStats {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let counters = Arc::new(AtomicCounters::default());
+        self.live.write().unwrap().insert(id, Arc::clone(&counters));
+        ConnectionStats {
+            id,
+            counters,
+            registry: Arc::clone(self),
+        }
+    }
+
+    /// Counters for every live connection, keyed by connection ID.
+    pub fn connections(&self) -> HashMap<u64, ConnectionCounters> {
+        self.live
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, counters)| (*id, counters.snapshot()))
+            .collect()
+    }
+
+    /// Add live per-connection counters to an exported counter map, one
+    /// series per connection labelled `connection="<id>"`.
+    pub fn export_connections(&self, counters: &mut HashMap<String, u64>) {
+        for (id, c) in self.connections() {
+            let id = id.to_string();
+            let series = |name: &str| labelled(name, "connection", &id);
+            counters.insert(series(CONN_MESSAGES_DECODED_TOTAL), c.messages_decoded);
+            counters.insert(series(CONN_DECODE_ERRORS_TOTAL), c.decode_errors);
+            counters.insert(series(CONN_BYTES_IN_TOTAL), c.bytes_in);
+            counters.insert(series(CONN_BYTES_OUT_TOTAL), c.bytes_out);
+            counters.insert(series(CONN_OVERSIZED_FRAMES_TOTAL), c.oversized_frames);
+        }
+    }
+}
+
+/// Counters for one connection. Unregisters itself on drop.
+pub struct ConnectionStats {
+    id: u64,
+    counters: Arc<AtomicCounters>,
+    registry: Arc<ProtocolStats>,
+}
+
+impl ConnectionStats {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn snapshot(&self) -> ConnectionCounters {
+        self.counters.snapshot()
+    }
+
+    /// A frame of `bytes` decoded into a message.
+    pub fn record_decoded(&self, bytes: usize) {
+        self.counters
+            .messages_decoded
+            .fetch_add(1, Ordering::Relaxed);
+        self.add_bytes_in(bytes);
+        self.registry
+            .metrics
+            .increment_counter(MESSAGES_DECODED_TOTAL, 1);
+    }
+
+    /// A frame of `bytes` failed to decode.
+    ///
+    /// Returns true if the connection should be dropped under the policy.
+    pub fn record_decode_error(&self, bytes: usize) -> bool {
+        self.counters.decode_errors.fetch_add(1, Ordering::Relaxed);
+        self.add_bytes_in(bytes);
+        self.registry
+            .metrics
+            .increment_counter(DECODE_ERRORS_TOTAL, 1);
+
+        let policy = &self.registry.policy;
+        let snapshot = self.snapshot();
+        let frames = snapshot.messages_decoded + snapshot.decode_errors;
+        if frames < policy.min_frames || snapshot.decode_error_rate() <= policy.max_error_rate {
+            return false;
+        }
+        // Report once per connection, not once per bad frame
+        if !self.counters.flagged.swap(true, Ordering::Relaxed) {
+            self.registry
+                .metrics
+                .increment_counter(ABUSIVE_CONNECTIONS_TOTAL, 1);
+            let id = self.id.to_string();
+            let errors = snapshot.decode_errors.to_string();
+            let frames = frames.to_string();
+            log_security_event(
+                SecurityEvent::ProtocolAbuse,
+                "Connection exceeded decode error rate",
+                &[
+                    ("connection", id.as_str()),
+                    ("decode_errors", errors.as_str()),
+                    ("frames", frames.as_str()),
+                    (
+                        "dropped",
+                        if policy.drop_connection {
+                            "true"
+                        } else {
+                            "false"
+                        },
+                    ),
+                ],
+            );
+        }
+        policy.drop_connection
+    }
+
+    /// A frame announcing more than the maximum size was rejected.
+    pub fn record_oversized(&self) {
+        self.counters
+            .oversized_frames
+            .fetch_add(1, Ordering::Relaxed);
+        self.registry
+            .metrics
+            .increment_counter(OVERSIZED_FRAMES_TOTAL, 1);
+    }
+
+    /// A frame of `bytes` was written to the client.
+    pub fn record_bytes_out(&self, bytes: usize) {
+        self.counters
+            .bytes_out
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.registry
+            .metrics
+            .increment_counter(BYTES_OUT_TOTAL, bytes as u64);
+    }
+
+    fn add_bytes_in(&self, bytes: usize) {
+        self.counters
+            .bytes_in
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.registry
+            .metrics
+            .increment_counter(BYTES_IN_TOTAL, bytes as u64);
+    }
+}
+
+impl Drop for ConnectionStats {
+    fn drop(&mut self) {
+        self.registry.live.write().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(policy: DecodeErrorPolicy) -> (Arc<MetricsStore>, Arc<ProtocolStats>) {
+        let metrics = Arc::new(MetricsStore::new());
+        let stats = Arc::new(ProtocolStats::new(Arc::clone(&metrics), policy));
+        (metrics, stats)
+    }
+
+    #[test]
+    fn test_counters_are_per_connection_and_aggregate() {
+        let (metrics, stats) = stats(DecodeErrorPolicy::default());
+        let a = stats.open();
+        let b = stats.open();
+        a.record_decoded(10);
+        b.record_decode_error(3);
+        b.record_bytes_out(7);
+
+        assert_eq!(a.snapshot().messages_decoded, 1);
+        assert_eq!(b.snapshot().decode_errors, 1);
+        let counters = metrics.snapshot().counters;
+        assert_eq!(counters[BYTES_IN_TOTAL], 13);
+        assert_eq!(counters[BYTES_OUT_TOTAL], 7);
+
+        drop(a);
+        let live = stats.connections();
+        assert_eq!(live.len(), 1);
+        assert!(live.contains_key(&b.id()));
+    }
+
+    #[test]
+    fn test_error_rate_needs_min_frames_before_dropping() {
+        let (metrics, stats) = stats(DecodeErrorPolicy {
+            max_error_rate: 0.5,
+            min_frames: 4,
+            drop_connection: true,
+        });
+        let conn = stats.open();
+        assert!(!conn.record_decode_error(1));
+        assert!(!conn.record_decode_error(1));
+        conn.record_decoded(1);
+        assert!(conn.record_decode_error(1));
+        assert!(conn.record_decode_error(1));
+        assert_eq!(metrics.snapshot().counters[ABUSIVE_CONNECTIONS_TOTAL], 1);
+    }
+
+    #[test]
+    fn test_export_labels_connections_under_one_name() {
+        let (_metrics, stats) = stats(DecodeErrorPolicy::default());
+        let conn = stats.open();
+        conn.record_decoded(4);
+
+        let mut counters = HashMap::new();
+        stats.export_connections(&mut counters);
+        let key = format!("{}{{connection=\"{}\"}}", CONN_MESSAGES_DECODED_TOTAL, conn.id());
+        assert_eq!(counters[&key], 1);
+        assert!(counters.keys().all(|k| k.starts_with("core_ipc_conn_") && k.contains('{')));
+    }
+}
@@ -5,9 +5,9 @@
 //! - Windows: `tokio::net::windows::named_pipe` (named pipes)
 //!
 //! All connections use length-prefixed framing (4-byte LE + payload)
-//! matching the CLI client protocol in `cli::ipc_client`.
+//! matching the CLI client protocol in `cli::ipc_client`. Sessions that
+//! negotiate compression add a codec flag byte (see `ipc::compression`).
 
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -15,10 +15,13 @@ use tokio::sync::{watch, Mutex};
 use tokio_util::sync::CancellationToken;
 use thiserror::Error;
 
+use super::compression::{CompressionError, FrameCodec};
 use super::connections::{ConnectionPool, OwnedConnectionGuard};
 use super::handler::IpcHandler;
-use super::protocol::{decode_message, encode_message, IpcMessage};
+use super::protocol::IpcMessage;
+use super::protocol_stats::ConnectionStats;
 use super::stream_bridge::IpcStreamBridge;
+use crate::telemetry::{log_security_event, SecurityEvent};
 
 /// Maximum allowed message frame size (16 MB).
 const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
@@ -30,6 +33,9 @@ pub enum ServerError {
 
     #[error("Frame too large: {size} bytes (max {max})")]
     FrameTooLarge { size: usize, max: usize },
+
+    #[error("Compression error: {0}")]
+    Compression(#[from] CompressionError),
 }
 
 /// Read a length-prefixed frame from an async reader.
@@ -64,29 +70,60 @@ async fn write_frame<W: AsyncWriteExt + Unpin>(
     Ok(())
 }
 
-/// Write a frame using a locked writer.
+/// Encode a payload with the session codec and write it using a locked writer.
 async fn write_frame_locked<W: AsyncWriteExt + Unpin>(
     writer: &Arc<Mutex<W>>,
+    codec: &FrameCodec,
+    stats: &ConnectionStats,
     data: &[u8],
 ) -> Result<(), ServerError> {
+    let payload = codec.encode(data)?;
     let mut w = writer.lock().await;
-    write_frame(&mut *w, data).await
+    write_frame(&mut *w, &payload).await?;
+    stats.record_bytes_out(payload.len() + 4);
+    Ok(())
 }
 
 /// Handle one IPC connection: read requests, dispatch, write responses.
 /// Supports both synchronous request/response and streaming inference.
-async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'static>(
+///
+/// Frame traffic is counted in the handler's `ProtocolStats`; a connection
+/// breaching the decode error policy is closed if the policy says so. With
+/// an idle timeout on the pool, a connection that sends no decodable message
+/// for that long after its last response is closed.
+pub async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'static>(
     stream: S,
     handler: Arc<IpcHandler>,
-    _guard: OwnedConnectionGuard,
+    guard: OwnedConnectionGuard,
 ) {
     let (mut read_half, write_half) = tokio::io::split(stream);
     let write_half = Arc::new(Mutex::new(write_half));
     let mut session = None;
-    let mut active_streams: HashMap<u64, CancellationToken> = HashMap::new();
+    // Legacy framing until a handshake negotiates compression
+    let mut codec = FrameCodec::plain();
+    let stats = Arc::new(handler.protocol_stats().open());
 
     loop {
-        let request_bytes = match read_frame(&mut read_half).await {
+        let read = read_frame(&mut read_half);
+        let read_result = match guard.idle_timeout() {
+            Some(limit) => {
+                match tokio::time::timeout(limit.saturating_sub(guard.idle_for()), read).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        let id = stats.id().to_string();
+                        let idle_ms = guard.idle_for().as_millis().to_string();
+                        log_security_event(
+                            SecurityEvent::IdleConnectionClosed,
+                            "Closing idle connection",
+                            &[("connection", id.as_str()), ("idle_ms", idle_ms.as_str())],
+                        );
+                        break;
+                    }
+                }
+            }
+            None => read.await,
+        };
+        let request_bytes = match read_result {
             Ok(bytes) => bytes,
             Err(ServerError::Io(ref e))
                 if e.kind() == std::io::ErrorKind::UnexpectedEof =>
@@ -94,17 +131,30 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
                 break;
             }
             Err(e) => {
+                if matches!(e, ServerError::FrameTooLarge { .. }) {
+                    stats.record_oversized();
+                }
                 eprintln!("Connection read error: {}", e);
                 break;
             }
         };
+        let frame_bytes = request_bytes.len() + 4;
 
         // Parse message to detect streaming vs non-streaming
-        let message = match decode_message(&request_bytes) {
-            Ok(m) => m,
+        let message = match handler.decode(&request_bytes) {
+            Ok(m) => {
+                stats.record_decoded(frame_bytes);
+                guard.touch();
+                m
+            }
             Err(e) => {
+                let drop_connection = stats.record_decode_error(frame_bytes);
                 let err = format!(r#"{{"type":"error","code":400,"message":"{}"}}"#, e);
-                let _ = write_frame_locked(&write_half, err.as_bytes()).await;
+                let _ = write_frame_locked(&write_half, &codec, &stats, err.as_bytes()).await;
+                if drop_connection {
+                    eprintln!("Dropping connection {}: decode error rate exceeded", stats.id());
+                    break;
+                }
                 continue;
             }
         };
@@ -113,58 +163,71 @@ async fn handle_connection<S: AsyncReadExt + AsyncWriteExt + Unpin + Send + 'sta
             // Streaming inference request
             IpcMessage::InferenceRequest(ref req) if req.parameters.stream => {
                 if let Some(ref sess) = session {
+                    // Cancelled through the handler by a CancelRequest
                     let cancel = CancellationToken::new();
-                    active_streams.insert(req.request_id.0, cancel.clone());
                     let bridge = IpcStreamBridge::new(
                         Arc::clone(&write_half),
                         req.request_id,
                         cancel.clone(),
-                    );
+                    )
+                    .with_codec(codec)
+                    .with_stats(Arc::clone(&stats));
                     let _ = handler
                         .process_streaming(req.clone(), sess, &bridge, cancel)
                         .await;
-                    active_streams.remove(&req.request_id.0);
+                    // A long generation is activity, not idleness
+                    guard.touch();
                 } else {
                     let err = r#"{"type":"error","code":401,"message":"Not authenticated"}"#;
-                    let _ = write_frame_locked(&write_half, err.as_bytes()).await;
-                }
-            }
-
-            // Cancel request - trigger cancellation for active streams
-            IpcMessage::CancelRequest { request_id } => {
-                let cancelled = if let Some(cancel) = active_streams.get(&request_id.0) {
-                    cancel.cancel();
-                    true
-                } else {
-                    false
-                };
-                let response = IpcMessage::CancelResponse { request_id, cancelled };
-                if let Ok(bytes) = encode_message(&response) {
-                    let _ = write_frame_locked(&write_half, &bytes).await;
+                    let _ = write_frame_locked(&write_half, &codec, &stats, err.as_bytes()).await;
                 }
             }
 
             // Non-streaming: use standard request/response processing
             _ => {
+                let requested_compression = match &message {
+                    IpcMessage::Handshake { compression, .. } => Some(*compression),
+                    _ => None,
+                };
                 match handler.process(&request_bytes, session.as_ref()).await {
                     Ok((response_bytes, new_session)) => {
-                        if new_session.is_some() {
-                            session = new_session;
+                        let authenticated = new_session.is_some();
+                        if authenticated {
+                            // Counted as connected so session eviction leaves it alone
+                            if let Some(ref sess) = new_session {
+                                let _ = handler.auth.track_connection(sess).await;
+                            }
+                            if let Some(old) = std::mem::replace(&mut session, new_session) {
+                                handler.auth.release_connection(&old).await;
+                            }
                         }
-                        if let Err(e) = write_frame_locked(&write_half, &response_bytes).await {
+                        let written =
+                            write_frame_locked(&write_half, &codec, &stats, &response_bytes).await;
+                        if let Err(e) = written {
                             eprintln!("Connection write error: {}", e);
                             break;
                         }
+                        guard.touch();
+                        // The ack itself uses the old framing; later frames use the new codec
+                        if authenticated {
+                            if let Some(requested) = requested_compression {
+                                codec = handler.frame_codec(requested);
+                            }
+                        }
                     }
                     Err(e) => {
                         let err = format!(r#"{{"type":"error","code":500,"message":"{}"}}"#, e);
-                        let _ = write_frame_locked(&write_half, err.as_bytes()).await;
+                        let _ =
+                            write_frame_locked(&write_half, &codec, &stats, err.as_bytes()).await;
                         break;
                     }
                 }
             }
         }
     }
+    if let Some(sess) = session {
+        handler.auth.release_connection(&sess).await;
+    }
 }
 
 /// Accept one connection, acquire a guard, and spawn a handler task.
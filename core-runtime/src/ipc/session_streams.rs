@@ -0,0 +1,63 @@
+//! Per-session limit on concurrent streaming requests.
+//!
+//! Keeps one authenticated session from monopolizing the worker with many
+//! open streams. Non-streaming requests are not counted.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::auth::SessionToken;
+
+/// Error sent to a client that opens too many streams at once.
+pub(crate) const TOO_MANY_STREAMS_MESSAGE: &str = "too many concurrent streams for this session";
+
+/// Active stream counts, keyed by session.
+pub(crate) struct SessionStreams {
+    limit: usize,
+    counts: Mutex<HashMap<SessionToken, usize>>,
+}
+
+impl SessionStreams {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            counts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim a stream slot for `session`; `None` if it already has `limit` open.
+    pub(crate) fn try_acquire(&self, session: &SessionToken) -> Option<SessionStreamSlot<'_>> {
+        let mut counts = self.lock();
+        let count = counts.get(session).copied().unwrap_or(0);
+        if count >= self.limit {
+            return None;
+        }
+        counts.insert(session.clone(), count + 1);
+        Some(SessionStreamSlot {
+            streams: self,
+            session: session.clone(),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<SessionToken, usize>> {
+        self.counts.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+/// An open stream counted against its session; released on drop.
+pub(crate) struct SessionStreamSlot<'a> {
+    streams: &'a SessionStreams,
+    session: SessionToken,
+}
+
+impl Drop for SessionStreamSlot<'_> {
+    fn drop(&mut self) {
+        let mut counts = self.streams.lock();
+        if let Some(count) = counts.get_mut(&self.session) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&self.session);
+            }
+        }
+    }
+}
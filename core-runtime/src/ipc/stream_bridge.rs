@@ -9,8 +9,10 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 
+use super::compression::FrameCodec;
 use super::handler::{HandlerError, StreamSender};
 use super::protocol::{encode_message, IpcMessage, RequestId};
+use super::protocol_stats::ConnectionStats;
 
 /// Adapts an IPC connection's write half to the StreamSender trait.
 ///
@@ -20,6 +22,8 @@ pub struct IpcStreamBridge<W> {
     writer: Arc<Mutex<W>>,
     request_id: RequestId,
     cancel: CancellationToken,
+    codec: FrameCodec,
+    stats: Option<Arc<ConnectionStats>>,
 }
 
 impl<W> IpcStreamBridge<W> {
@@ -33,9 +37,23 @@ impl<W> IpcStreamBridge<W> {
             writer,
             request_id,
             cancel,
+            codec: FrameCodec::plain(),
+            stats: None,
         }
     }
 
+    /// Encode frames with the session's negotiated codec.
+    pub fn with_codec(mut self, codec: FrameCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Count written bytes against a connection's protocol stats.
+    pub fn with_stats(mut self, stats: Arc<ConnectionStats>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     /// Get the request ID this bridge is sending for.
     pub fn request_id(&self) -> RequestId {
         self.request_id
@@ -55,6 +73,10 @@ impl<W> IpcStreamBridge<W> {
 impl<W: AsyncWriteExt + Unpin + Send + 'static> IpcStreamBridge<W> {
     /// Write a length-prefixed frame to the underlying writer.
     async fn write_frame(&self, data: &[u8]) -> Result<(), HandlerError> {
+        let data = self
+            .codec
+            .encode(data)
+            .map_err(|e| HandlerError::StreamSend(e.to_string()))?;
         let mut writer = self.writer.lock().await;
         let len = data.len() as u32;
         writer
@@ -62,13 +84,16 @@ impl<W: AsyncWriteExt + Unpin + Send + 'static> IpcStreamBridge<W> {
             .await
             .map_err(|e| HandlerError::StreamSend(e.to_string()))?;
         writer
-            .write_all(data)
+            .write_all(&data)
             .await
             .map_err(|e| HandlerError::StreamSend(e.to_string()))?;
         writer
             .flush()
             .await
             .map_err(|e| HandlerError::StreamSend(e.to_string()))?;
+        if let Some(stats) = &self.stats {
+            stats.record_bytes_out(data.len() + 4);
+        }
         Ok(())
     }
 }
@@ -76,7 +101,13 @@ impl<W: AsyncWriteExt + Unpin + Send + 'static> IpcStreamBridge<W> {
 #[async_trait::async_trait]
 impl<W: AsyncWriteExt + Unpin + Send + 'static> StreamSender for IpcStreamBridge<W> {
     async fn send(&self, message: IpcMessage) -> Result<(), HandlerError> {
-        if self.cancel.is_cancelled() {
+        // After cancellation only the frame ending the stream goes out
+        let is_final = match &message {
+            IpcMessage::StreamChunk(chunk) => chunk.is_final,
+            IpcMessage::StreamBatch(batch) => batch.is_final,
+            _ => false,
+        };
+        if self.cancel.is_cancelled() && !is_final {
             return Err(HandlerError::StreamSend("cancelled".into()));
         }
         let bytes = encode_message(&message)?;
@@ -117,4 +148,16 @@ mod tests {
         let result = bridge.send(IpcMessage::StreamChunk(chunk)).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_stream_bridge_sends_final_chunk_after_cancel() {
+        let writer = Arc::new(Mutex::new(Cursor::new(Vec::new())));
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let bridge = IpcStreamBridge::new(Arc::clone(&writer), RequestId(123), cancel);
+        let chunk = super::super::protocol::StreamChunk::end(RequestId(123));
+        assert!(bridge.send(IpcMessage::StreamChunk(chunk)).await.is_ok());
+        assert!(!writer.lock().await.get_ref().is_empty());
+    }
 }
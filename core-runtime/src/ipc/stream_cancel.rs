@@ -0,0 +1,127 @@
+//! Cancellation tokens for streaming requests in progress.
+//!
+//! A `CancelRequest` may arrive on any connection of the session that started
+//! the stream, so tokens live on the handler rather than the connection.
+//! Request IDs are chosen by clients, so each token is keyed by its session
+//! too and only that session can cancel it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use tokio_util::sync::CancellationToken;
+
+use super::auth::SessionToken;
+use super::protocol::RequestId;
+
+type StreamKey = (SessionToken, u64);
+
+/// Tokens of running streams, keyed by session and request ID.
+#[derive(Default)]
+pub(crate) struct StreamCancellations {
+    next_serial: AtomicU64,
+    tokens: Mutex<HashMap<StreamKey, (u64, CancellationToken)>>,
+}
+
+impl StreamCancellations {
+    /// Make `token` cancellable by `session` until the registration drops.
+    pub(crate) fn register(
+        &self,
+        session: &SessionToken,
+        request_id: RequestId,
+        token: CancellationToken,
+    ) -> StreamRegistration<'_> {
+        let serial = self.next_serial.fetch_add(1, Ordering::Relaxed);
+        let key = (session.clone(), request_id.0);
+        self.lock().insert(key.clone(), (serial, token));
+        StreamRegistration {
+            streams: self,
+            key,
+            serial,
+        }
+    }
+
+    /// Cancel `session`'s running stream for `request_id`. False if that
+    /// session has none running, even if another session does.
+    pub(crate) fn cancel(&self, session: &SessionToken, request_id: RequestId) -> bool {
+        match self.lock().get(&(session.clone(), request_id.0)) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<StreamKey, (u64, CancellationToken)>> {
+        self.tokens.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+/// Keeps a stream cancellable; unregisters it on drop.
+pub(crate) struct StreamRegistration<'a> {
+    streams: &'a StreamCancellations,
+    key: StreamKey,
+    serial: u64,
+}
+
+impl Drop for StreamRegistration<'_> {
+    fn drop(&mut self) {
+        let mut tokens = self.streams.lock();
+        // A newer stream may have reused the request ID
+        if tokens
+            .get(&self.key)
+            .is_some_and(|(serial, _)| *serial == self.serial)
+        {
+            tokens.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::auth::SessionAuth;
+
+    /// Two distinct sessions opened with the same token.
+    async fn sessions() -> (SessionToken, SessionToken) {
+        let auth = SessionAuth::new("token", std::time::Duration::from_secs(60));
+        let first = auth.authenticate("token").await.unwrap();
+        let second = auth.authenticate("token").await.unwrap();
+        (first, second)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_reaches_running_stream_only() {
+        let streams = StreamCancellations::default();
+        let (owner, _) = sessions().await;
+        let token = CancellationToken::new();
+        let registration = streams.register(&owner, RequestId(9), token.clone());
+
+        assert!(!streams.cancel(&owner, RequestId(10)));
+        assert!(streams.cancel(&owner, RequestId(9)));
+        assert!(token.is_cancelled());
+
+        // After completion, cancel is a no-op
+        drop(registration);
+        assert!(!streams.cancel(&owner, RequestId(9)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ignores_other_sessions_streams() {
+        let streams = StreamCancellations::default();
+        let (owner, other) = sessions().await;
+        let token = CancellationToken::new();
+        let _registration = streams.register(&owner, RequestId(9), token.clone());
+
+        assert!(!streams.cancel(&other, RequestId(9)));
+        assert!(!token.is_cancelled());
+
+        // The same client-chosen ID in another session is a separate stream
+        let other_token = CancellationToken::new();
+        let _other = streams.register(&other, RequestId(9), other_token.clone());
+        assert!(streams.cancel(&other, RequestId(9)));
+        assert!(other_token.is_cancelled());
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coalesces streamed tokens into fewer IPC frames.
+//!
+//! With the default config every token is sent as its own `StreamChunk`.
+//! When `max_tokens > 1`, tokens are buffered and flushed as a single
+//! `StreamBatch` once `max_tokens` accumulate, `max_interval_ms` elapses
+//! since the first buffered token, or the final token arrives.
+
+use std::time::Duration;
+use serde::Serialize;
+use tokio::time::Instant;
+
+use super::protocol::{IpcMessage, RequestId, StreamBatch, StreamChunk};
+
+/// Stream coalescing limits. Default is per-token for interactivity.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamCoalesceConfig {
+    /// Tokens per frame. 0 or 1 disables coalescing.
+    pub max_tokens: usize,
+    /// Longest a buffered token may wait before being flushed.
+    pub max_interval_ms: u64,
+}
+
+impl Default for StreamCoalesceConfig {
+    fn default() -> Self {
+        Self {
+            max_tokens: 1,
+            max_interval_ms: 0,
+        }
+    }
+}
+
+impl StreamCoalesceConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_tokens > 1
+    }
+}
+
+/// Per-request token buffer.
+#[derive(Debug)]
+pub struct StreamCoalescer {
+    request_id: RequestId,
+    config: StreamCoalesceConfig,
+    pending: Vec<u32>,
+    first_buffered: Option<Instant>,
+}
+
+impl StreamCoalescer {
+    pub fn new(request_id: RequestId, config: StreamCoalesceConfig) -> Self {
+        Self {
+            request_id,
+            pending: Vec::with_capacity(config.max_tokens.max(1)),
+            config,
+            first_buffered: None,
+        }
+    }
+
+    /// Buffer a token. Returns a frame to send if one is due.
+    pub fn push(
+        &mut self,
+        token: u32,
+        is_final: bool,
+        client_metadata: Option<String>,
+    ) -> Option<IpcMessage> {
+        if !self.config.is_enabled() {
+            let chunk = if is_final {
+                StreamChunk::final_token(self.request_id, token)
+                    .with_client_metadata(client_metadata)
+            } else {
+                StreamChunk::token(self.request_id, token)
+            };
+            return Some(IpcMessage::StreamChunk(chunk));
+        }
+
+        self.pending.push(token);
+        self.first_buffered.get_or_insert_with(Instant::now);
+
+        if is_final {
+            return self.take_batch(true, client_metadata);
+        }
+        if self.pending.len() >= self.config.max_tokens || self.interval_elapsed() {
+            return self.flush();
+        }
+        None
+    }
+
+    /// Flush buffered tokens as a non-final batch.
+    pub fn flush(&mut self) -> Option<IpcMessage> {
+        self.take_batch(false, None)
+    }
+
+    /// When the buffered tokens must be flushed, if any are pending.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.first_buffered
+            .map(|first| first + Duration::from_millis(self.config.max_interval_ms))
+    }
+
+    fn interval_elapsed(&self) -> bool {
+        self.deadline()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    fn take_batch(
+        &mut self,
+        is_final: bool,
+        client_metadata: Option<String>,
+    ) -> Option<IpcMessage> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.first_buffered = None;
+        Some(IpcMessage::StreamBatch(StreamBatch {
+            request_id: self.request_id,
+            tokens: std::mem::take(&mut self.pending),
+            is_final,
+            client_metadata,
+        }))
+    }
+}
+
+/// Sleep until `deadline`, or forever if nothing is buffered.
+pub async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coalesced(max_tokens: usize, max_interval_ms: u64) -> StreamCoalescer {
+        StreamCoalescer::new(
+            RequestId(1),
+            StreamCoalesceConfig {
+                max_tokens,
+                max_interval_ms,
+            },
+        )
+    }
+
+    #[test]
+    fn test_default_is_per_token() {
+        let mut coalescer = StreamCoalescer::new(RequestId(1), StreamCoalesceConfig::default());
+        assert!(matches!(
+            coalescer.push(7, false, None),
+            Some(IpcMessage::StreamChunk(StreamChunk {
+                token: 7,
+                is_final: false,
+                ..
+            }))
+        ));
+        assert!(coalescer.deadline().is_none());
+    }
+
+    #[test]
+    fn test_final_flushes_partial_batch_with_metadata() {
+        let mut coalescer = coalesced(8, 1000);
+        assert!(coalescer.push(1, false, None).is_none());
+        match coalescer.push(2, true, Some("meta".into())) {
+            Some(IpcMessage::StreamBatch(batch)) => {
+                assert_eq!(batch.tokens, vec![1, 2]);
+                assert!(batch.is_final);
+                assert_eq!(batch.client_metadata.as_deref(), Some("meta"));
+            }
+            other => panic!("expected final batch, got {:?}", other),
+        }
+        assert!(coalescer.flush().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_interval_forces_flush() {
+        let mut coalescer = coalesced(100, 10);
+        assert!(coalescer.push(1, false, None).is_none());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        match coalescer.push(2, false, None) {
+            Some(IpcMessage::StreamBatch(batch)) => assert_eq!(batch.tokens, vec![1, 2]),
+            other => panic!("expected batch, got {:?}", other),
+        }
+        assert!(coalescer.deadline().is_none());
+    }
+}
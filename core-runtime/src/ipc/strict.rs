@@ -0,0 +1,81 @@
+//! Detection of unknown fields in decoded IPC messages.
+//!
+//! serde ignores fields it does not recognize, and `deny_unknown_fields` is
+//! fixed at compile time, so strict mode finds them after decoding instead.
+//! The input is walked once alongside the re-encoded message: an input key
+//! missing from it is unknown, unless its value is one a known field
+//! serializes away at (its default).
+
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use super::compression::Compression;
+use super::protocol::IpcMessage;
+use crate::engine::{OutputEncoding, SamplerKind};
+
+/// Dotted paths of fields in `input` that decoding `message` ignored.
+pub(crate) fn unknown_fields(input: &Value, message: &IpcMessage) -> Vec<String> {
+    let Ok(decoded) = serde_json::to_value(message) else {
+        return Vec::new();
+    };
+    let mut unknown = Vec::new();
+    collect_unknown(input, &decoded, &mut String::new(), &mut unknown);
+    unknown
+}
+
+fn collect_unknown(input: &Value, decoded: &Value, path: &mut String, out: &mut Vec<String>) {
+    let len = path.len();
+    match (input, decoded) {
+        (Value::Object(input), Value::Object(decoded)) => {
+            for (key, value) in input {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                match decoded.get(key) {
+                    Some(d) => collect_unknown(value, d, path, out),
+                    None if !is_skipped_default(value) => out.push(path.clone()),
+                    None => {}
+                }
+                path.truncate(len);
+            }
+        }
+        (Value::Array(input), Value::Array(decoded)) => {
+            for (i, (value, d)) in input.iter().zip(decoded).enumerate() {
+                path.push_str(&format!("[{}]", i));
+                collect_unknown(value, d, path, out);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `value` is one protocol fields are skipped at when serialized:
+/// null, false, an empty array or object, or a defaulted enum.
+fn is_skipped_default(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(b) => !b,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty() || enum_defaults().contains(value),
+        Value::String(_) => enum_defaults().contains(value),
+        Value::Number(_) => false,
+    }
+}
+
+/// Serialized defaults of the enums protocol fields skip at.
+fn enum_defaults() -> &'static [Value] {
+    static DEFAULTS: OnceLock<Vec<Value>> = OnceLock::new();
+    DEFAULTS.get_or_init(|| {
+        [
+            serde_json::to_value(OutputEncoding::default()),
+            serde_json::to_value(SamplerKind::default()),
+            serde_json::to_value(Compression::default()),
+        ]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect()
+    })
+}
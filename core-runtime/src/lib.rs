@@ -61,18 +61,32 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use engine::InferenceEngine;
+use engine::{
+    gguf_model_factory, EmbeddingCacheConfig, FilterConfig, GgufConfig, InferenceEngine,
+    OutputFilter,
+};
 use health::{HealthChecker, HealthConfig};
-use ipc::{ConnectionConfig, ConnectionPool, IpcHandler, IpcHandlerConfig, SessionAuth};
+use ipc::{
+    AuthRateLimitConfig, CompressionConfig, ConnectionConfig, ConnectionPool, DecodeErrorPolicy,
+    EffectiveConfig, HandlerConfigError, IpcHandler, IpcHandlerConfig, SessionAuth,
+    SessionLimitConfig, StreamCoalesceConfig, ADMIN_TOKEN_LABEL, DEFAULT_MAX_CLIENT_PRIORITY,
+    DEFAULT_MAX_STREAMS_PER_SESSION, DEFAULT_TOKEN_LABEL,
+};
+use ipc::protocol::{DEFAULT_MAX_PROMPT_BYTES, DEFAULT_MAX_PROMPT_TOKENS};
 use memory::{
-    ContextCache, ContextCacheConfig, GpuMemory, GpuMemoryConfig, MemoryPool, MemoryPoolConfig,
+    ContextCache, ContextCacheConfig, GpuMemory, GpuMemoryConfig, MemoryFloorConfig, MemoryPool,
+    MemoryPoolConfig, PromptCache, ResourceLimitsConfig,
+};
+use models::{
+    CircuitBreakerConfig, ModelLifecycle, ModelLoader, ModelRegistry, PersistenceError,
+    RegistryPersistence, SmartLoader, SmartLoaderConfig,
 };
-use models::{ModelLoader, ModelRegistry};
 use scheduler::{
-    BatchConfig, BatchProcessor, OutputCache, OutputCacheConfig, RequestQueue, RequestQueueConfig,
+    BatchConfig, BatchProcessor, OutputCache, OutputCacheConfig, Priority, RequestQueue,
+    RequestQueueConfig,
 };
 use shutdown::ShutdownCoordinator;
-use telemetry::MetricsStore;
+use telemetry::{HistogramBuckets, LogConfig, MetricsStore, SpanCollector};
 use tokio::sync::Mutex;
 
 /// Runtime configuration.
@@ -81,7 +95,20 @@ pub struct RuntimeConfig {
     pub base_path: PathBuf,
     pub auth_token: String,
     pub session_timeout: Duration,
+    /// How long past `session_timeout` a session stays usable, so requests
+    /// straddling expiry are not cut off.
+    pub session_grace: Duration,
+    /// Cap on simultaneously live sessions (unbounded by default).
+    pub session_limit: SessionLimitConfig,
     pub max_context_length: usize,
+    /// Lower bound applied to non-zero top_p values (0.0 selects greedy).
+    pub top_p_floor: f32,
+    /// Last-resort cap on decode steps per request; hitting it is a bug.
+    pub absolute_max_decode_steps: u32,
+    /// Recent tokens `repetition_penalty` and `no_repeat_ngram_size` cover.
+    pub history_window: usize,
+    /// Maximum number of models the registry will hold at once.
+    pub max_registered_models: usize,
     pub memory_pool: MemoryPoolConfig,
     pub gpu_memory: GpuMemoryConfig,
     pub context_cache: ContextCacheConfig,
@@ -89,7 +116,61 @@ pub struct RuntimeConfig {
     pub batch: BatchConfig,
     pub shutdown_timeout: Duration,
     pub output_cache: OutputCacheConfig,
+    /// Cache of embedding vectors for duplicate texts.
+    pub embedding_cache: EmbeddingCacheConfig,
     pub connections: ConnectionConfig,
+    /// IPC response compression offered at handshake (disabled by default).
+    pub ipc_compression: CompressionConfig,
+    /// Streaming token coalescing (per-token by default).
+    pub stream_coalesce: StreamCoalesceConfig,
+    /// Reject requests while available system memory is below a floor.
+    pub memory_floor: MemoryFloorConfig,
+    /// Report (and optionally drop) connections sending undecodable frames.
+    pub decode_errors: DecodeErrorPolicy,
+    /// Model served when a request leaves `model_id` empty (single-model deployments).
+    pub default_model: Option<String>,
+    /// Reject IPC messages carrying unknown fields (lenient by default).
+    pub strict_protocol: bool,
+    /// Largest text prompt admitted over IPC, in bytes, whatever the
+    /// model's context length.
+    pub max_prompt_bytes: usize,
+    /// Largest token-array prompt admitted over FFI, whatever the model's
+    /// context length.
+    pub max_prompt_tokens: usize,
+    /// Highest queue priority an IPC client may request; higher requests
+    /// are capped silently.
+    pub max_client_priority: Priority,
+    /// Streaming requests one IPC session may have open at once.
+    pub max_streams_per_session: usize,
+    /// Bucket boundaries for the request timing histograms.
+    pub histogram_buckets: HistogramBuckets,
+    /// Turn off the output, prompt and embedding caches together, whatever
+    /// their own configs say (for workloads that must never see reused results).
+    pub disable_caches: bool,
+    /// Readiness gating (queue depth, whether a model must be loaded).
+    pub health: HealthConfig,
+    /// Rebuild the model registry from `registry_state_path()` on startup.
+    /// Restored models marked `auto_load` are preloaded by `spawn_startup`.
+    pub restore_registry: bool,
+    /// Let admin sessions send `ReloadConfig`.
+    pub allow_config_reload: bool,
+    /// Handshake token whose sessions may send admin-only messages such as
+    /// `ReloadConfig`; none are admin when unset.
+    pub admin_token: Option<String>,
+    /// Memory and concurrency limits per inference call (unlimited by
+    /// default). Reloadable.
+    pub resource_limits: ResourceLimitsConfig,
+    /// Blocking of handshakes after repeated failures. Reloadable.
+    pub auth_rate_limit: AuthRateLimitConfig,
+    /// Fast-fail requests to a model after repeated inference failures.
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Blocklist, patterns and size limits applied to IPC inference output.
+    pub output_filter: FilterConfig,
+    /// Resident cap and auto-warmup for models preloaded through the smart
+    /// loader.
+    pub smart_loader: SmartLoaderConfig,
+    /// Log format, level and destination the server installs at startup.
+    pub logging: LogConfig,
 }
 
 impl Default for RuntimeConfig {
@@ -98,7 +179,13 @@ impl Default for RuntimeConfig {
             base_path: PathBuf::from("."),
             auth_token: String::new(),
             session_timeout: Duration::from_secs(3600),
+            session_grace: ipc::DEFAULT_SESSION_GRACE,
+            session_limit: SessionLimitConfig::default(),
             max_context_length: 4096,
+            top_p_floor: engine::config::DEFAULT_TOP_P_FLOOR,
+            absolute_max_decode_steps: engine::DEFAULT_ABSOLUTE_MAX_DECODE_STEPS,
+            history_window: engine::DEFAULT_HISTORY_WINDOW,
+            max_registered_models: models::DEFAULT_MAX_REGISTERED_MODELS,
             memory_pool: MemoryPoolConfig::default(),
             gpu_memory: GpuMemoryConfig::default(),
             context_cache: ContextCacheConfig::default(),
@@ -106,26 +193,195 @@ impl Default for RuntimeConfig {
             batch: BatchConfig::default(),
             shutdown_timeout: Duration::from_secs(30),
             output_cache: OutputCacheConfig::default(),
+            embedding_cache: EmbeddingCacheConfig::default(),
             connections: ConnectionConfig::default(),
+            ipc_compression: CompressionConfig::default(),
+            stream_coalesce: StreamCoalesceConfig::default(),
+            memory_floor: MemoryFloorConfig::default(),
+            decode_errors: DecodeErrorPolicy::default(),
+            default_model: None,
+            strict_protocol: false,
+            max_prompt_bytes: DEFAULT_MAX_PROMPT_BYTES,
+            max_prompt_tokens: DEFAULT_MAX_PROMPT_TOKENS,
+            max_client_priority: DEFAULT_MAX_CLIENT_PRIORITY,
+            max_streams_per_session: DEFAULT_MAX_STREAMS_PER_SESSION,
+            histogram_buckets: HistogramBuckets::default(),
+            disable_caches: false,
+            health: HealthConfig::default(),
+            restore_registry: false,
+            allow_config_reload: false,
+            admin_token: None,
+            resource_limits: ResourceLimitsConfig::unlimited(),
+            auth_rate_limit: AuthRateLimitConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            output_filter: FilterConfig::default(),
+            smart_loader: SmartLoaderConfig::default(),
+            logging: LogConfig::default(),
         }
     }
 }
 
+/// `value` as reported in `EffectiveConfig::sections`.
+fn section<T: serde::Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+impl RuntimeConfig {
+    /// Configuration as reported to IPC clients, with secrets masked.
+    pub fn effective(&self) -> EffectiveConfig {
+        let redact = |token: &str| {
+            if token.is_empty() {
+                String::new()
+            } else {
+                ipc::protocol::REDACTED.to_string()
+            }
+        };
+        let auth_token = redact(&self.auth_token);
+        let sections = [
+            ("session_grace", section(&self.session_grace)),
+            ("session_limit", section(&self.session_limit)),
+            ("absolute_max_decode_steps", section(&self.absolute_max_decode_steps)),
+            ("history_window", section(&self.history_window)),
+            ("memory_pool", section(&self.memory_pool)),
+            ("gpu_memory", section(&self.gpu_memory)),
+            ("context_cache", section(&self.context_cache)),
+            ("request_queue", section(&self.request_queue)),
+            ("batch", section(&self.batch)),
+            ("output_cache", section(&self.output_cache)),
+            ("embedding_cache", section(&self.embedding_cache)),
+            ("connections", section(&self.connections)),
+            ("ipc_compression", section(&self.ipc_compression)),
+            ("stream_coalesce", section(&self.stream_coalesce)),
+            ("memory_floor", section(&self.memory_floor)),
+            ("decode_errors", section(&self.decode_errors)),
+            ("default_model", section(&self.default_model)),
+            ("strict_protocol", section(&self.strict_protocol)),
+            ("max_prompt_bytes", section(&self.max_prompt_bytes)),
+            ("max_prompt_tokens", section(&self.max_prompt_tokens)),
+            ("max_client_priority", section(&self.max_client_priority)),
+            ("max_streams_per_session", section(&self.max_streams_per_session)),
+            ("histogram_buckets", section(&self.histogram_buckets)),
+            ("disable_caches", section(&self.disable_caches)),
+            ("health", section(&self.health)),
+            ("restore_registry", section(&self.restore_registry)),
+            ("allow_config_reload", section(&self.allow_config_reload)),
+            ("admin_token", section(&redact(self.admin_token.as_deref().unwrap_or_default()))),
+            ("resource_limits", section(&self.resource_limits)),
+            ("auth_rate_limit", section(&self.auth_rate_limit)),
+            ("circuit_breaker", section(&self.circuit_breaker)),
+            ("output_filter", section(&self.output_filter)),
+            ("smart_loader", section(&self.smart_loader)),
+            ("logging", section(&self.logging)),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+
+        EffectiveConfig {
+            base_path: self.base_path.display().to_string(),
+            auth_token,
+            session_timeout_secs: self.session_timeout.as_secs(),
+            max_context_length: self.max_context_length,
+            top_p_floor: self.top_p_floor,
+            max_registered_models: self.max_registered_models,
+            shutdown_timeout_secs: self.shutdown_timeout.as_secs(),
+            sections,
+        }
+    }
+
+    /// Reject settings the server cannot run with, such as a
+    /// `max_streams_per_session` of 0 that would refuse every stream.
+    pub fn validate(&self) -> Result<(), HandlerConfigError> {
+        handler_config(self).validate()
+    }
+
+    /// Where `Runtime::persist_registry` writes the registry snapshot.
+    pub fn registry_state_path(&self) -> PathBuf {
+        self.base_path.join("cache").join("registry.json")
+    }
+
+    /// Output cache config with `disable_caches` applied.
+    pub fn effective_output_cache(&self) -> OutputCacheConfig {
+        OutputCacheConfig {
+            max_entries: self.cache_capacity(self.output_cache.max_entries),
+            ..self.output_cache.clone()
+        }
+    }
+
+    /// Embedding cache config with `disable_caches` applied.
+    pub fn effective_embedding_cache(&self) -> EmbeddingCacheConfig {
+        EmbeddingCacheConfig {
+            max_entries: self.cache_capacity(self.embedding_cache.max_entries),
+            ..self.embedding_cache.clone()
+        }
+    }
+
+    /// Prompt cache holding up to `max_entries`, or none with `disable_caches`.
+    pub fn prompt_cache(&self, max_entries: usize) -> PromptCache {
+        PromptCache::new(self.cache_capacity(max_entries))
+    }
+
+    fn cache_capacity(&self, max_entries: usize) -> usize {
+        if self.disable_caches {
+            0
+        } else {
+            max_entries
+        }
+    }
+}
+
+/// Session manager accepting `auth_token`, and `admin_token` under
+/// [`ADMIN_TOKEN_LABEL`] when set.
+fn session_auth(config: &RuntimeConfig) -> SessionAuth {
+    let mut tokens = vec![(DEFAULT_TOKEN_LABEL.to_string(), config.auth_token.clone())];
+    if let Some(admin_token) = &config.admin_token {
+        tokens.push((ADMIN_TOKEN_LABEL.to_string(), admin_token.clone()));
+    }
+    SessionAuth::with_tokens(&tokens, config.session_timeout)
+        .with_session_grace(config.session_grace)
+        .with_session_limit(config.session_limit)
+        .with_rate_limit(config.auth_rate_limit)
+}
+
+/// IPC handler settings taken from `config`.
+fn handler_config(config: &RuntimeConfig) -> IpcHandlerConfig {
+    IpcHandlerConfig {
+        compression: config.ipc_compression.clone(),
+        stream_coalesce: config.stream_coalesce.clone(),
+        memory_floor: config.memory_floor.clone(),
+        decode_errors: config.decode_errors.clone(),
+        default_model: config.default_model.clone(),
+        strict_protocol: config.strict_protocol,
+        max_prompt_bytes: config.max_prompt_bytes,
+        resource_limits: config.resource_limits.clone(),
+        max_client_priority: config.max_client_priority,
+        max_streams_per_session: config.max_streams_per_session,
+        output_cache: config.effective_output_cache(),
+        allow_config_reload: config.allow_config_reload,
+        circuit_breaker: config.circuit_breaker,
+        ..Default::default()
+    }
+}
+
 /// The CORE Runtime instance.
 pub struct Runtime {
     pub config: RuntimeConfig,
     pub memory_pool: MemoryPool,
     pub gpu_memory: GpuMemory,
     pub context_cache: ContextCache,
-    pub model_loader: ModelLoader,
+    pub model_loader: Arc<ModelLoader>,
     pub model_registry: Arc<ModelRegistry>,
+    pub smart_loader: Arc<SmartLoader>,
     pub inference_engine: Arc<InferenceEngine>,
+    /// Reloads served models' tokenizers and chat templates in place.
+    pub model_lifecycle: Arc<ModelLifecycle>,
     pub request_queue: Arc<RequestQueue>,
     pub batch_processor: BatchProcessor,
     pub ipc_handler: IpcHandler,
     pub shutdown: Arc<ShutdownCoordinator>,
     pub health: Arc<HealthChecker>,
     pub metrics_store: Arc<MetricsStore>,
+    pub span_collector: Arc<SpanCollector>,
     pub output_cache: Arc<Mutex<OutputCache>>,
     pub connections: Arc<ConnectionPool>,
 }
@@ -133,32 +389,68 @@ pub struct Runtime {
 impl Runtime {
     /// Create a new runtime instance with the given configuration.
     pub fn new(config: RuntimeConfig) -> Self {
+        engine::accel::init_accelerations();
         let memory_pool = MemoryPool::new(config.memory_pool.clone());
         let gpu_memory = GpuMemory::new(config.gpu_memory.clone());
         let context_cache = ContextCache::new(config.context_cache.clone());
-        let model_loader = ModelLoader::new(config.base_path.clone());
-        let model_registry = Arc::new(ModelRegistry::new());
-        let inference_engine = InferenceEngine::new(config.max_context_length);
+        let model_loader = Arc::new(ModelLoader::new(config.base_path.clone()));
+        let model_registry = Arc::new(if config.restore_registry {
+            Self::restore_registry(&config)
+        } else {
+            ModelRegistry::with_max_models(config.max_registered_models)
+        });
+        let inference_engine = InferenceEngine::new(config.max_context_length)
+            .with_top_p_floor(config.top_p_floor)
+            .with_absolute_max_decode_steps(config.absolute_max_decode_steps)
+            .with_history_window(config.history_window)
+            .with_embedding_cache(config.effective_embedding_cache());
         let request_queue = Arc::new(RequestQueue::new(config.request_queue.clone()));
         let batch_processor = BatchProcessor::new(config.batch.clone());
         let shutdown = Arc::new(ShutdownCoordinator::new());
-        let health = Arc::new(HealthChecker::new(HealthConfig::default()));
-        let metrics_store = Arc::new(MetricsStore::new());
-        let output_cache = Arc::new(Mutex::new(OutputCache::new(config.output_cache.clone())));
+        let health = Arc::new(HealthChecker::new(config.health.clone()));
+        let histogram_buckets = match config.histogram_buckets.validate() {
+            Ok(()) => config.histogram_buckets.clone(),
+            Err(e) => {
+                tracing::warn!(error = %e, "invalid histogram buckets, using defaults");
+                HistogramBuckets::default()
+            }
+        };
+        let metrics_store = Arc::new(MetricsStore::with_histogram_buckets(&histogram_buckets));
+        let span_collector = Arc::new(SpanCollector::new());
+        let output_cache = Arc::new(Mutex::new(OutputCache::new(config.effective_output_cache())));
+        let output_filter = OutputFilter::new(config.output_filter.clone()).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "invalid output filter, filtering disabled");
+            OutputFilter::default()
+        });
         let connections = Arc::new(ConnectionPool::new(config.connections.clone()));
 
-        let session_auth = Arc::new(SessionAuth::new(&config.auth_token, config.session_timeout));
+        let session_auth = Arc::new(session_auth(&config));
         let inference_engine = Arc::new(inference_engine);
+        let model_lifecycle = Arc::new(ModelLifecycle::new(Arc::clone(&inference_engine)));
+        let model_factory = gguf_model_factory(GgufConfig::default());
+        let smart_loader = Arc::new(SmartLoader::serving(
+            config.smart_loader.clone(),
+            Arc::clone(&model_loader),
+            Arc::clone(&model_factory),
+            Arc::clone(&model_registry),
+            Arc::clone(&inference_engine),
+            Arc::clone(&health),
+        ));
         let ipc_handler = IpcHandler::new(
             session_auth,
             request_queue.clone(),
-            IpcHandlerConfig::default(),
+            handler_config(&config),
             shutdown.clone(),
             health.clone(),
             model_registry.clone(),
             metrics_store.clone(),
             Arc::clone(&inference_engine),
-        );
+        )
+        .with_effective_config(config.effective())
+        .with_span_collector(span_collector.clone())
+        .with_model_loader(Arc::clone(&model_loader))
+        .with_model_factory(model_factory)
+        .with_output_filter(output_filter);
 
         Self {
             config,
@@ -167,6 +459,8 @@ impl Runtime {
             context_cache,
             model_loader,
             model_registry,
+            smart_loader,
+            model_lifecycle,
             inference_engine,
             request_queue,
             batch_processor,
@@ -174,9 +468,62 @@ impl Runtime {
             shutdown,
             health,
             metrics_store,
+            span_collector,
             output_cache,
             connections,
         }
     }
+
+    /// Preload restored `auto_load` models through the smart loader and warm
+    /// every model the engine serves in the background, then open the
+    /// startup gate. Under `StartupGate::DelayBind` the server binds only
+    /// once this finishes.
+    ///
+    /// A model failing its warmup step stays registered; readiness reports it.
+    pub fn spawn_startup(&self) -> tokio::task::JoinHandle<()> {
+        let engine = Arc::clone(&self.inference_engine);
+        let health = Arc::clone(&self.health);
+        let smart_loader = Arc::clone(&self.smart_loader);
+        let registry = Arc::clone(&self.model_registry);
+        tokio::spawn(async move {
+            smart_loader.preload_restored(&registry).await;
+            for model_id in engine.model_ids().await {
+                if let Err(e) = engine.ping(&model_id).await {
+                    tracing::warn!(model_id = %model_id, error = %e, "startup warmup failed");
+                }
+            }
+            health.mark_startup_complete();
+        })
+    }
+
+    /// Let `ReloadConfig` re-read the configuration from `source`.
+    pub fn with_config_source(mut self, source: ipc::ConfigSource) -> Self {
+        self.ipc_handler = self.ipc_handler.with_config_source(self.config.clone(), source);
+        self
+    }
+
+    /// Write registered models to `registry_state_path()`, replacing the
+    /// previous snapshot atomically.
+    pub async fn persist_registry(&self) -> Result<(), PersistenceError> {
+        let mut state = self.model_registry.snapshot().await;
+        state.default_model = self.config.default_model.clone();
+        RegistryPersistence::new(self.config.registry_state_path()).save(&state)
+    }
+
+    /// Registry rebuilt from the saved snapshot, or empty if there is none
+    /// or it cannot be read.
+    fn restore_registry(config: &RuntimeConfig) -> ModelRegistry {
+        let persistence = RegistryPersistence::new(config.registry_state_path());
+        match persistence.load() {
+            Ok(state) => ModelRegistry::from_state(config.max_registered_models, &state),
+            Err(PersistenceError::NotFound) => {
+                ModelRegistry::with_max_models(config.max_registered_models)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "could not restore model registry, starting empty");
+                ModelRegistry::with_max_models(config.max_registered_models)
+            }
+        }
+    }
 }
 
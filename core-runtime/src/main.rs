@@ -15,13 +15,25 @@
 
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::Arc;
 use std::time::Duration;
 
-use gg_core::cli::{get_socket_path, run_health, run_liveness, run_readiness, run_status, CliIpcClient};
+use gg_core::cli::{
+    get_socket_path, print_config, run_active_requests, run_cancel_all, run_config_show_remote,
+    run_health, run_info, run_liveness, run_load, run_metrics, run_models_diagnose, run_models_drain,
+    run_readiness, run_scale_hint, run_stats, run_status, run_trace, run_unload, run_validate_only,
+    CliIpcClient, MetricsFormat, DEFAULT_DRAIN_TIMEOUT_MS,
+};
 use gg_core::engine::InferenceParams;
+use gg_core::health::StartupGate;
+use gg_core::ipc::protocol::{InferenceResponse, DEFAULT_MAX_PROMPT_BYTES, DEFAULT_MAX_PROMPT_TOKENS};
 use gg_core::ipc::server;
+use gg_core::ipc::{AuthRateLimitConfig, ConnectionConfig};
+use gg_core::memory::ResourceLimitsConfig;
+use gg_core::scheduler::RequestQueueConfig;
 use gg_core::security::fips_tests;
-use gg_core::shutdown::ShutdownResult;
+use gg_core::shutdown::{ShutdownResult, ShutdownSignals};
+use gg_core::telemetry::{init_logging, LogConfig, LogFormat, LOG_FORMAT_ENV};
 use gg_core::{Runtime, RuntimeConfig};
 
 #[tokio::main]
@@ -31,6 +43,16 @@ async fn main() -> ExitCode {
 
     match command {
         "serve" | "" => {
+            let config = load_config();
+            if let Err(e) = config.validate() {
+                eprintln!("Invalid configuration: {}", e);
+                return ExitCode::from(2u8);
+            }
+            if let Err(e) = init_logging(&config.logging) {
+                eprintln!("Logging setup failed: {}", e);
+                return ExitCode::from(2u8);
+            }
+
             // FIPS 140-3 power-on self-tests (fail-fast)
             if let Err(e) = fips_tests::run_power_on_self_tests() {
                 eprintln!("FIPS self-test FAILED: {}", e);
@@ -39,8 +61,7 @@ async fn main() -> ExitCode {
             }
             eprintln!("FIPS 140-3 self-tests: PASSED");
 
-            let config = load_config();
-            let runtime = Runtime::new(config);
+            let runtime = Runtime::new(config).with_config_source(Arc::new(load_config));
             match run_ipc_server(runtime).await {
                 Ok(()) => ExitCode::SUCCESS,
                 Err(e) => {
@@ -79,14 +100,53 @@ async fn main() -> ExitCode {
         }
         "status" => {
             let socket_path = get_socket_path();
-            let json_output = args.get(2).map(|s| s.as_str()) == Some("--json");
-            let code = run_status(&socket_path, json_output).await;
+            let flag = args.get(2).map(|s| s.as_str());
+            let code = if flag == Some("--scale-hint") {
+                run_scale_hint(&socket_path).await
+            } else if flag == Some("--active") {
+                let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+                let json = args.get(3).map(|s| s.as_str()) == Some("--json");
+                run_active_requests(&socket_path, &token, json).await
+            } else {
+                run_status(&socket_path, flag == Some("--json")).await
+            };
             ExitCode::from(code as u8)
         }
+        "metrics" => {
+            let format = match args.get(2) {
+                None => Some(MetricsFormat::default()),
+                Some(flag) => MetricsFormat::from_flag(flag),
+            };
+            match format {
+                Some(format) => {
+                    let code = run_metrics(&get_socket_path(), format).await;
+                    ExitCode::from(code as u8)
+                }
+                None => {
+                    eprintln!("Usage: GG-CORE metrics [--prometheus|--json]");
+                    ExitCode::FAILURE
+                }
+            }
+        }
         "infer" => {
             let code = run_inference(&args).await;
             ExitCode::from(code as u8)
         }
+        "trace" => match args.get(2).map(|id| id.parse::<u64>()) {
+            Some(Ok(request_id)) => {
+                let code = run_trace(&get_socket_path(), request_id).await;
+                ExitCode::from(code as u8)
+            }
+            _ => {
+                eprintln!("Usage: GG-CORE trace <REQUEST_ID>");
+                ExitCode::FAILURE
+            }
+        },
+        "cancel-all" => {
+            let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+            let code = run_cancel_all(&get_socket_path(), &token).await;
+            ExitCode::from(code as u8)
+        }
         "verify" => {
             // TODO: Implement verify command
             eprintln!(
@@ -101,6 +161,64 @@ async fn main() -> ExitCode {
                     eprintln!("Models list not yet implemented.");
                     ExitCode::from(2u8)
                 }
+                "load" | "unload" | "info" => match args.get(3) {
+                    Some(name) => {
+                        let socket = get_socket_path();
+                        let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+                        let code = match subcommand {
+                            "load" => run_load(&socket, &token, name).await,
+                            "unload" => run_unload(&socket, &token, name).await,
+                            _ => run_info(&socket, name).await,
+                        };
+                        ExitCode::from(code as u8)
+                    }
+                    None => {
+                        eprintln!("Usage: GG-CORE models {} <NAME>", subcommand);
+                        ExitCode::FAILURE
+                    }
+                },
+                "stats" => match args.get(3) {
+                    Some(name) => {
+                        let json = args.get(4).map(|s| s.as_str()) == Some("--json");
+                        let code = run_stats(&get_socket_path(), name, json).await;
+                        ExitCode::from(code as u8)
+                    }
+                    None => {
+                        eprintln!("Usage: GG-CORE models stats <NAME> [--json]");
+                        ExitCode::FAILURE
+                    }
+                },
+                "diagnose" => match args.get(3) {
+                    Some(name) => {
+                        let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+                        let code = run_models_diagnose(&get_socket_path(), &token, name).await;
+                        ExitCode::from(code as u8)
+                    }
+                    None => {
+                        eprintln!("Usage: GG-CORE models diagnose <NAME>");
+                        ExitCode::FAILURE
+                    }
+                },
+                "drain" => {
+                    let timeout_ms = match args.get(4).map(|s| s.as_str()) {
+                        None => Some(DEFAULT_DRAIN_TIMEOUT_MS),
+                        Some("--timeout-ms") => args.get(5).and_then(|v| v.parse().ok()),
+                        Some(_) => None,
+                    };
+                    match (args.get(3), timeout_ms) {
+                        (Some(name), Some(timeout_ms)) => {
+                            let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+                            let code =
+                                run_models_drain(&get_socket_path(), &token, name, timeout_ms)
+                                    .await;
+                            ExitCode::from(code as u8)
+                        }
+                        _ => {
+                            eprintln!("Usage: GG-CORE models drain <NAME> [--timeout-ms MS]");
+                            ExitCode::FAILURE
+                        }
+                    }
+                }
                 _ => {
                     eprintln!("Unknown models subcommand: {}", subcommand);
                     print_command_help("models");
@@ -111,7 +229,16 @@ async fn main() -> ExitCode {
         "config" => {
             let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("show");
             match subcommand {
-                "show" | "validate" | "defaults" => {
+                "show" => {
+                    let code = if args.get(3).map(|s| s.as_str()) == Some("--remote") {
+                        let token = std::env::var("CORE_AUTH_TOKEN").unwrap_or_default();
+                        run_config_show_remote(&get_socket_path(), &token).await
+                    } else {
+                        print_config(&load_config().effective())
+                    };
+                    ExitCode::from(code as u8)
+                }
+                "validate" | "defaults" => {
                     eprintln!("Config {} not yet implemented.", subcommand);
                     ExitCode::from(2u8)
                 }
@@ -145,6 +272,9 @@ COMMANDS:
     live         Liveness probe for Kubernetes (exit 0 if alive)
     ready        Readiness probe for Kubernetes (exit 0 if ready)
     status       Show system status and statistics
+    metrics      Dump server metrics (Prometheus text or JSON)
+    trace        Show spans recorded for one request ID
+    cancel-all   Cancel every pending request (requires CORE_AUTH_TOKEN)
     verify       Verify deployment health and configuration
     models       Manage loaded models (list, load, unload)
     config       Manage configuration (validate, show)
@@ -166,6 +296,8 @@ EXAMPLES:
     GG-CORE live                     # Liveness probe
     GG-CORE ready                    # Readiness probe
     GG-CORE status                   # Show system status
+    GG-CORE metrics --prometheus     # Prometheus exposition text
+    GG-CORE trace 1234               # Spans for request 1234
     GG-CORE models list              # List loaded models
     GG-CORE config validate          # Validate configuration
     GG-CORE --socket /custom/path    # Use custom socket path
@@ -173,7 +305,16 @@ EXAMPLES:
 ENVIRONMENT:
     VERITAS_SOCKET_PATH  IPC socket path (default: /var/run/veritas/GG-CORE.sock on Unix)
     CORE_AUTH_TOKEN      Authentication token for server mode
+    CORE_DEFAULT_MODEL   Model used when a request omits model_id
+    CORE_STRICT_PROTOCOL Reject IPC messages with unknown fields (1/true)
+    CORE_DISABLE_CACHES  Turn off output, prompt and embedding caches (1/true)
+    CORE_MAX_PROMPT_TOKENS Largest prompt admitted, in estimated tokens
+    CORE_IDLE_TIMEOUT_SECS Close IPC connections silent for this long
+    CORE_MAX_PENDING     Request queue depth (reloadable)
+    CORE_SESSION_TIMEOUT_SECS Session lifetime in seconds (reloadable)
+    CORE_ALLOW_CONFIG_RELOAD Accept ReloadConfig from authenticated clients (1/true)
     RUST_LOG             Log level (debug, info, warn, error)
+    GG_CORE_LOG_FORMAT   Log format: text (default) or json (NDJSON)
     VERITAS_ENV          Environment (development, staging, production)
 
 EXIT CODES:
@@ -292,7 +433,9 @@ OPTIONS:
 DESCRIPTION:
     Kubernetes readiness probe. Returns success (0) if the runtime is
     ready to accept inference requests (models loaded, warmed up).
-    Use for kubelet readinessProbe.
+    Use for kubelet readinessProbe. On failure the reason is printed to
+    stderr: shutting_down, no_models_registered, models_loading,
+    queue_full, worker_wedged or no_recent_success.
 
 EXIT CODES:
     0  Ready to serve traffic
@@ -319,6 +462,9 @@ OPTIONS:
     --socket PATH  Override IPC socket path
     --json         Output in JSON format
     --watch        Continuously update status
+    --scale-hint   Print only the load factor (for autoscalers)
+    --active       List executing requests: ID, model, phase, age, session
+                   (requires CORE_AUTH_TOKEN; add --json for JSON)
 
 DESCRIPTION:
     Displays current system status including:
@@ -332,6 +478,8 @@ EXAMPLES:
     GG-CORE status
     GG-CORE status --json
     GG-CORE status --watch
+    GG-CORE status --scale-hint
+    GG-CORE status --active
 "
             );
         }
@@ -340,28 +488,116 @@ EXAMPLES:
                 "GG-CORE infer - Run inference
 
 USAGE:
-    GG-CORE infer --model <MODEL> --prompt <PROMPT> [OPTIONS]
+    GG-CORE infer [--model <MODEL>] --prompt <PROMPT> [OPTIONS]
 
 OPTIONS:
-    --model <MODEL>      Model ID to use for inference
+    --model <MODEL>      Model ID to use for inference (default: CORE_DEFAULT_MODEL)
     --prompt <PROMPT>    Input prompt for generation
     --max-tokens <N>     Maximum tokens to generate (default: 256)
     --stream             Enable token-by-token streaming output
+    --validate-only      Run the prompt security scan without generating
+    --timing             Print queue, prefill and decode times after the output
     --socket PATH        Override IPC socket path
 
 DESCRIPTION:
     Sends an inference request to the running GG-CORE server
     and prints the generated output. Use --stream for real-time
-    token streaming.
+    token streaming. With --validate-only, the prompt is scanned
+    locally for injection and PII and the server is not contacted.
 
 EXIT CODES:
-    0  Inference completed successfully
+    0  Inference completed successfully (or prompt passed validation)
     1  Inference failed or connection error
+    2  Prompt would be blocked (--validate-only)
 
 EXAMPLES:
     GG-CORE infer --model phi-3 --prompt \"Hello, world!\"
     GG-CORE infer --model phi-3 --prompt \"Count to 5\" --stream
     GG-CORE infer --model qwen --prompt \"Hi\" --max-tokens 100
+    GG-CORE infer --model phi-3 --prompt \"Hi\" --timing
+"
+            );
+        }
+        "metrics" => {
+            eprintln!(
+                "GG-CORE metrics - Dump server metrics
+
+USAGE:
+    GG-CORE metrics [--prometheus|--json]
+
+OPTIONS:
+    --prometheus   Print the Prometheus text exposition format verbatim
+    --json         Print the metrics snapshot as JSON (default)
+    --socket PATH  Override IPC socket path
+
+DESCRIPTION:
+    Fetches the running server's metrics over IPC and writes them to
+    stdout. The Prometheus output is exactly what a scraper would see,
+    so it can be redirected into a node_exporter textfile collector.
+    No auth token is needed.
+
+EXIT CODES:
+    0  Metrics printed
+    1  Request failed
+    3  Connection error
+
+EXAMPLES:
+    GG-CORE metrics --prometheus > /var/lib/node_exporter/gg_core.prom
+    GG-CORE metrics --json | jq .counters
+"
+            );
+        }
+        "trace" => {
+            eprintln!(
+                "GG-CORE trace - Show a request's spans
+
+USAGE:
+    GG-CORE trace <REQUEST_ID> [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+
+DESCRIPTION:
+    Fetches the spans the server still has buffered for one request ID
+    (as logged by the client) and prints the inference root span with its
+    queue and generate phases, offsets relative to the request start.
+    Spans are kept in a bounded buffer, so old requests may be gone.
+
+EXIT CODES:
+    0  Spans found
+    1  No spans buffered for the request, or request failed
+    3  Connection error
+
+EXAMPLES:
+    GG-CORE trace 1234
+"
+            );
+        }
+        "cancel-all" => {
+            eprintln!(
+                "GG-CORE cancel-all - Cancel every pending request
+
+USAGE:
+    GG-CORE cancel-all [OPTIONS]
+
+OPTIONS:
+    --socket PATH  Override IPC socket path
+
+ENVIRONMENT:
+    CORE_AUTH_TOKEN  Auth token for the handshake (required)
+
+DESCRIPTION:
+    Drains the server's request queue for shutdown or emergencies. Every
+    pending caller receives a \"request cancelled\" error. Requests already
+    finished are unaffected.
+
+EXIT CODES:
+    0  Queue drained (prints how many requests were cancelled)
+    1  Rejected (e.g. bad token)
+    3  Connection error
+
+EXAMPLES:
+    CORE_AUTH_TOKEN=secret GG-CORE cancel-all
 "
             );
         }
@@ -403,9 +639,13 @@ USAGE:
 
 SUBCOMMANDS:
     list           List loaded models
-    load <NAME>    Load a model
-    unload <NAME>  Unload a model
+    load <NAME>    Load models/<NAME>.gguf (requires CORE_AUTH_TOKEN)
+    unload <NAME>  Unload a model (requires CORE_AUTH_TOKEN)
     info <NAME>    Show model information
+    stats <NAME>   Show live in-flight count, requests served, latency, memory
+    diagnose <NAME>  Check why a model is not servable
+    drain <NAME>   Stop routing to a model and wait for in-flight requests
+                   (requires CORE_AUTH_TOKEN; --timeout-ms, default 30000)
 
 OPTIONS:
     --socket PATH  Override IPC socket path
@@ -415,6 +655,9 @@ EXAMPLES:
     GG-CORE models list
     GG-CORE models load llama-2-7b-chat
     GG-CORE models info llama-2-7b-chat
+    GG-CORE models stats llama-2-7b-chat --json
+    GG-CORE models diagnose llama-2-7b-chat
+    GG-CORE models drain llama-2-7b-chat --timeout-ms 10000
     GG-CORE models unload llama-2-7b-chat
 "
             );
@@ -434,9 +677,11 @@ SUBCOMMANDS:
 OPTIONS:
     --socket PATH  Override IPC socket path
     --file PATH    Configuration file path
+    --remote       Show the running server's configuration (uses CORE_AUTH_TOKEN)
 
 EXAMPLES:
     GG-CORE config show
+    GG-CORE config show --remote
     GG-CORE config validate --file values.yaml
     GG-CORE config defaults
 "
@@ -457,18 +702,113 @@ fn load_config() -> RuntimeConfig {
     RuntimeConfig {
         base_path: PathBuf::from("."),
         auth_token: std::env::var("CORE_AUTH_TOKEN").unwrap_or_default(),
-        session_timeout: Duration::from_secs(3600),
+        session_timeout: Duration::from_secs(
+            std::env::var("CORE_SESSION_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&secs| secs > 0)
+                .unwrap_or(3600),
+        ),
         max_context_length: 4096,
+        default_model: std::env::var("CORE_DEFAULT_MODEL").ok().filter(|m| !m.is_empty()),
+        strict_protocol: std::env::var("CORE_STRICT_PROTOCOL")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false),
+        disable_caches: std::env::var("CORE_DISABLE_CACHES")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false),
+        max_prompt_bytes: std::env::var("CORE_MAX_PROMPT_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_PROMPT_BYTES),
+        max_prompt_tokens: std::env::var("CORE_MAX_PROMPT_TOKENS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_PROMPT_TOKENS),
+        request_queue: RequestQueueConfig {
+            max_pending: std::env::var("CORE_MAX_PENDING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(RequestQueueConfig::default().max_pending),
+            ..Default::default()
+        },
+        allow_config_reload: std::env::var("CORE_ALLOW_CONFIG_RELOAD")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false),
+        admin_token: std::env::var("CORE_ADMIN_TOKEN").ok().filter(|t| !t.is_empty()),
+        resource_limits: load_resource_limits(),
+        logging: load_log_config(),
+        auth_rate_limit: AuthRateLimitConfig {
+            max_failed_attempts: std::env::var("CORE_AUTH_MAX_FAILED_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&attempts| attempts > 0)
+                .unwrap_or(AuthRateLimitConfig::default().max_failed_attempts),
+            block_duration: std::env::var("CORE_AUTH_BLOCK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(AuthRateLimitConfig::default().block_duration),
+            ..Default::default()
+        },
+        connections: ConnectionConfig {
+            idle_timeout: std::env::var("CORE_IDLE_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            ..Default::default()
+        },
         ..Default::default()
     }
 }
 
+/// Log settings from `GG_CORE_LOG_FORMAT` (text unless set) and `RUST_LOG`.
+/// An unknown format falls back to text with a warning.
+fn load_log_config() -> LogConfig {
+    let format = match std::env::var(LOG_FORMAT_ENV).ok().filter(|v| !v.is_empty()) {
+        Some(format) => format.parse().unwrap_or_else(|e| {
+            eprintln!("{}; logging as text", e);
+            LogFormat::Text
+        }),
+        None => LogFormat::Text,
+    };
+    let defaults = LogConfig::default();
+    LogConfig {
+        format,
+        level: std::env::var("RUST_LOG")
+            .ok()
+            .filter(|level| !level.is_empty())
+            .unwrap_or(defaults.level),
+        ..defaults
+    }
+}
+
+/// Per-call limits from `CORE_MAX_CONCURRENT`, `CORE_MAX_MEMORY_PER_CALL`
+/// and `CORE_MAX_TOTAL_MEMORY` (bytes); unset ones are unlimited.
+fn load_resource_limits() -> ResourceLimitsConfig {
+    let unlimited = ResourceLimitsConfig::unlimited();
+    let env = |name: &str, default: usize| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    ResourceLimitsConfig {
+        max_memory_per_call: env("CORE_MAX_MEMORY_PER_CALL", unlimited.max_memory_per_call),
+        max_total_memory: env("CORE_MAX_TOTAL_MEMORY", unlimited.max_total_memory),
+        max_concurrent: env("CORE_MAX_CONCURRENT", unlimited.max_concurrent),
+    }
+}
+
 /// Run the inference CLI command.
 async fn run_inference(args: &[String]) -> i32 {
     let mut model_id = String::new();
     let mut prompt = String::new();
     let mut max_tokens = 256usize;
     let mut stream = false;
+    let mut validate_only = false;
+    let mut timing = false;
 
     // Parse arguments
     let mut i = 2;
@@ -505,6 +845,14 @@ async fn run_inference(args: &[String]) -> i32 {
                 stream = true;
                 i += 1;
             }
+            "--validate-only" => {
+                validate_only = true;
+                i += 1;
+            }
+            "--timing" => {
+                timing = true;
+                i += 1;
+            }
             _ => {
                 eprintln!("Unknown argument: {}", args[i]);
                 return 1;
@@ -512,11 +860,22 @@ async fn run_inference(args: &[String]) -> i32 {
         }
     }
 
+    // --model may be omitted when a default model is configured
+    if model_id.is_empty() {
+        if let Some(default_model) = load_config().default_model {
+            model_id = default_model;
+        }
+    }
     if model_id.is_empty() || prompt.is_empty() {
-        eprintln!("Usage: GG-CORE infer --model <MODEL> --prompt <PROMPT> [--max-tokens N] [--stream]");
+        eprintln!("Usage: GG-CORE infer --model <MODEL> --prompt <PROMPT> [--max-tokens N] [--stream] [--validate-only] [--timing]");
+        eprintln!("       (--model is optional when CORE_DEFAULT_MODEL is set)");
         return 1;
     }
 
+    if validate_only {
+        return run_validate_only(&model_id, &prompt);
+    }
+
     let socket_path = get_socket_path();
     let client = CliIpcClient::new(socket_path);
     let params = InferenceParams {
@@ -524,16 +883,24 @@ async fn run_inference(args: &[String]) -> i32 {
         ..Default::default()
     };
 
-    let result = if stream {
-        client.send_streaming_inference(&model_id, &prompt, &params).await
-    } else {
-        client.send_inference(&model_id, &prompt, &params).await
-    };
+    if stream {
+        if timing {
+            eprintln!("Note: --timing is not available with --stream");
+        }
+        return match client.send_streaming_inference(&model_id, &prompt, &params).await {
+            Ok(_) => 0,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        };
+    }
 
-    match result {
-        Ok(output) => {
-            if !stream {
-                println!("{}", output);
+    match client.send_inference_response(&model_id, &prompt, &params).await {
+        Ok(response) => {
+            println!("{}", response.output);
+            if timing {
+                print_timing(&response);
             }
             0
         }
@@ -544,6 +911,19 @@ async fn run_inference(args: &[String]) -> i32 {
     }
 }
 
+/// Print the server-reported timing breakdown to stderr.
+fn print_timing(response: &InferenceResponse) {
+    match (response.queue_wait_ms, response.prefill_ms, response.decode_ms) {
+        (Some(queue), Some(prefill), Some(decode)) => {
+            eprintln!("queue_wait: {:>10.2} ms", queue);
+            eprintln!("prefill:    {:>10.2} ms", prefill);
+            eprintln!("decode:     {:>10.2} ms", decode);
+            eprintln!("total:      {:>10.2} ms", queue + prefill + decode);
+        }
+        _ => eprintln!("Timing not reported by server"),
+    }
+}
+
 async fn run_ipc_server(runtime: Runtime) -> Result<(), Box<dyn std::error::Error>> {
     let socket_path = get_socket_path();
     let handler = std::sync::Arc::new(runtime.ipc_handler);
@@ -553,6 +933,22 @@ async fn run_ipc_server(runtime: Runtime) -> Result<(), Box<dyn std::error::Erro
 
     let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
 
+    // Install handlers before serving so an early SIGTERM still drains
+    let mut signals = ShutdownSignals::new()?;
+
+    // Clients can't connect until preload/warmup opens the gate
+    let _startup = runtime.spawn_startup();
+    if runtime.health.startup_gate() == StartupGate::DelayBind {
+        eprintln!("Waiting for startup preload before binding {}", socket_path);
+        tokio::select! {
+            _ = runtime.health.wait_startup_complete() => {}
+            signal = signals.recv() => {
+                eprintln!("Shutdown signal received ({:?}) during startup", signal);
+                return Ok(());
+            }
+        }
+    }
+
     let server_handle = tokio::spawn(server::run_server(
         socket_path,
         handler,
@@ -560,9 +956,9 @@ async fn run_ipc_server(runtime: Runtime) -> Result<(), Box<dyn std::error::Erro
         shutdown_rx,
     ));
 
-    // Wait for Ctrl+C, then initiate graceful shutdown
-    tokio::signal::ctrl_c().await?;
-    eprintln!("Shutdown signal received, draining...");
+    // Wait for SIGINT or SIGTERM (Kubernetes), then initiate graceful shutdown
+    let signal = signals.recv().await;
+    eprintln!("Shutdown signal received ({:?}), draining...", signal);
 
     // Signal the server loop to stop accepting
     let _ = shutdown_tx.send(true);
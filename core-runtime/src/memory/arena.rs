@@ -4,6 +4,7 @@
 
 use std::cell::UnsafeCell;
 use std::collections::VecDeque;
+use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
@@ -145,8 +146,49 @@ impl ArenaPool {
         // Otherwise drop the arena
     }
 
+    /// Acquire an arena that is released back to the pool when the
+    /// returned guard drops, including while unwinding from a panic.
+    pub fn lease(&self) -> PooledArena<'_> {
+        PooledArena {
+            pool: self,
+            arena: Some(self.acquire()),
+        }
+    }
+
     /// Number of arenas currently available in pool.
     pub fn available(&self) -> usize {
         self.arenas.lock().unwrap().len()
     }
 }
+
+/// Arena on loan from an [`ArenaPool`] (see [`ArenaPool::lease`]).
+pub struct PooledArena<'a> {
+    pool: &'a ArenaPool,
+    arena: Option<Arena>,
+}
+
+impl Deref for PooledArena<'_> {
+    type Target = Arena;
+
+    fn deref(&self) -> &Arena {
+        self.arena.as_ref().expect("arena is held until drop")
+    }
+}
+
+impl Drop for PooledArena<'_> {
+    fn drop(&mut self) {
+        if let Some(arena) = self.arena.take() {
+            self.pool.release(arena);
+        }
+    }
+}
+
+impl std::fmt::Debug for ArenaPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArenaPool")
+            .field("arena_size", &self.arena_size)
+            .field("max_arenas", &self.max_arenas)
+            .field("available", &self.available())
+            .finish()
+    }
+}
@@ -4,9 +4,10 @@
 
 use std::time::{Duration, Instant};
 use dashmap::DashMap;
+use serde::Serialize;
 
 /// Configuration for context cache.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ContextCacheConfig {
     pub max_entries: usize,
     pub ttl: Duration,
@@ -1,6 +1,7 @@
 //! GPU memory tracking and management.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,7 +11,7 @@ pub enum GpuMemoryError {
 }
 
 /// Configuration for GPU memory management.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GpuMemoryConfig {
     pub max_bytes: usize,
 }
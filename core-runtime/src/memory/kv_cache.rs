@@ -11,7 +11,7 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::Instant;
 
 /// Acquire a mutex lock, recovering from poison if a thread panicked.
@@ -42,6 +42,7 @@ fn write_or_recover<T>(rwlock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
 }
 
 use super::kv_quant::Q8KvStore;
+use crate::engine::GgufModel;
 use super::paged::{PageId, PageTable, PAGE_TOKENS};
 
 /// Configuration for the KV Cache Manager.
@@ -55,6 +56,10 @@ pub struct KvCacheConfig {
     pub max_seq_len: usize,
     /// Number of attention heads.
     pub num_heads: usize,
+    /// Number of KV heads. Fewer than `num_heads` for grouped-query
+    /// attention, where each KV head serves `num_heads / num_kv_heads`
+    /// consecutive query heads.
+    pub num_kv_heads: usize,
     /// Head dimension.
     pub head_dim: usize,
     /// Enable Q8 quantization for KV storage.
@@ -63,6 +68,8 @@ pub struct KvCacheConfig {
     pub enable_paged: bool,
     /// Cache eviction policy.
     pub eviction_policy: EvictionPolicy,
+    /// Fraction of `max_pages` a single sequence may hold (0.0, 1.0].
+    pub max_sequence_page_fraction: f32,
 }
 
 impl Default for KvCacheConfig {
@@ -72,14 +79,70 @@ impl Default for KvCacheConfig {
             max_pages: 1024,
             max_seq_len: 4096,
             num_heads: 32,
+            num_kv_heads: 32,
             head_dim: 128,
             enable_quantization: true,
             enable_paged: true,
             eviction_policy: EvictionPolicy::Lru,
+            max_sequence_page_fraction: 1.0,
         }
     }
 }
 
+impl KvCacheConfig {
+    /// Whether KV heads are shared between query heads.
+    pub fn is_grouped(&self) -> bool {
+        self.num_kv_heads > 0 && self.num_kv_heads < self.num_heads
+    }
+
+    /// Width of one token's keys (and of its values) in storage.
+    ///
+    /// `hidden_dim` without grouping, `num_kv_heads * head_dim` with it.
+    pub fn kv_dim(&self) -> usize {
+        if self.is_grouped() {
+            self.num_kv_heads * self.head_dim
+        } else {
+            self.hidden_dim
+        }
+    }
+
+    /// Query heads served by each KV head.
+    pub fn group_size(&self) -> usize {
+        if self.is_grouped() {
+            self.num_heads / self.num_kv_heads
+        } else {
+            1
+        }
+    }
+
+    /// This config with the head layout `model` records in its metadata;
+    /// unchanged if it records none.
+    pub fn with_model_heads(mut self, model: &dyn GgufModel) -> Self {
+        if let Some(heads) = model.attention_heads() {
+            self.num_heads = heads.heads;
+            self.num_kv_heads = heads.kv_heads;
+        }
+        self
+    }
+
+    /// Check a grouped layout divides query heads evenly between KV heads.
+    pub fn validate(&self) -> Result<(), KvCacheError> {
+        if self.num_kv_heads == 0 || !self.num_heads.is_multiple_of(self.num_kv_heads) {
+            return Err(KvCacheError::InvalidHeadLayout {
+                num_heads: self.num_heads,
+                num_kv_heads: self.num_kv_heads,
+            });
+        }
+        Ok(())
+    }
+
+    /// Maximum pages a single sequence may hold. Always at least one.
+    pub fn max_pages_per_sequence(&self) -> usize {
+        let fraction = self.max_sequence_page_fraction.clamp(0.0, 1.0) as f64;
+        ((self.max_pages as f64 * fraction).floor() as usize).max(1)
+    }
+}
+
 /// Cache eviction policy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EvictionPolicy {
@@ -115,6 +178,56 @@ impl KvCacheStats {
     }
 }
 
+/// Live counters behind the [`KvCacheStats`] snapshot.
+#[derive(Debug, Default)]
+struct StatsCounters {
+    total_pages_allocated: AtomicU64,
+    total_pages_freed: AtomicU64,
+    current_pages_in_use: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    evictions: AtomicU64,
+    quantization_errors: AtomicU64,
+    memory_bytes_used: AtomicU64,
+    peak_memory_bytes: AtomicU64,
+}
+
+impl StatsCounters {
+    fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the page table now holding `in_use` pages of `page_bytes` each.
+    fn record_pages_in_use(&self, in_use: u64, page_bytes: u64) {
+        let previous = self.current_pages_in_use.swap(in_use, Ordering::Relaxed);
+        if in_use > previous {
+            self.total_pages_allocated
+                .fetch_add(in_use - previous, Ordering::Relaxed);
+        } else {
+            self.total_pages_freed
+                .fetch_add(previous - in_use, Ordering::Relaxed);
+        }
+        let bytes = in_use * page_bytes;
+        self.memory_bytes_used.store(bytes, Ordering::Relaxed);
+        self.peak_memory_bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> KvCacheStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        KvCacheStats {
+            total_pages_allocated: load(&self.total_pages_allocated),
+            total_pages_freed: load(&self.total_pages_freed),
+            current_pages_in_use: load(&self.current_pages_in_use),
+            cache_hits: load(&self.cache_hits),
+            cache_misses: load(&self.cache_misses),
+            evictions: load(&self.evictions),
+            quantization_errors: load(&self.quantization_errors),
+            memory_bytes_used: load(&self.memory_bytes_used),
+            peak_memory_bytes: load(&self.peak_memory_bytes),
+        }
+    }
+}
+
 /// Unique identifier for a cache sequence.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SequenceId(pub u64);
@@ -140,24 +253,31 @@ pub struct KvCacheManager {
     config: KvCacheConfig,
     page_table: RwLock<PageTable>,
     sequences: RwLock<HashMap<SequenceId, SequenceEntry>>,
+    /// Eviction candidates, oldest first: insertion order, or recency of
+    /// use under `EvictionPolicy::Lru`.
     access_order: Mutex<VecDeque<SequenceId>>,
-    stats: Arc<KvCacheStats>,
+    stats: StatsCounters,
     next_seq_id: AtomicU64,
 }
 
 impl KvCacheManager {
     /// Create a new KV Cache Manager.
-    pub fn new(config: KvCacheConfig) -> Self {
-        let page_table = RwLock::new(PageTable::new(config.hidden_dim, config.max_pages));
-
-        Self {
+    ///
+    /// Storage is sized by [`KvCacheConfig::kv_dim`]. Fails with
+    /// `InvalidHeadLayout` when [`KvCacheConfig::validate`] rejects the
+    /// head layout.
+    pub fn new(config: KvCacheConfig) -> Result<Self, KvCacheError> {
+        config.validate()?;
+        let page_table = RwLock::new(PageTable::new(config.kv_dim(), config.max_pages));
+
+        Ok(Self {
             config,
             page_table,
             sequences: RwLock::new(HashMap::new()),
             access_order: Mutex::new(VecDeque::new()),
-            stats: Arc::new(KvCacheStats::default()),
+            stats: StatsCounters::default(),
             next_seq_id: AtomicU64::new(1),
-        }
+        })
     }
 
     /// Allocate a new sequence in the cache.
@@ -166,10 +286,7 @@ impl KvCacheManager {
 
         // Create per-sequence quantized store
         let quant_store = if self.config.enable_quantization {
-            Some(Q8KvStore::new(
-                self.config.hidden_dim,
-                self.config.max_seq_len,
-            ))
+            Some(Q8KvStore::new(self.config.kv_dim(), self.config.max_seq_len))
         } else {
             None
         };
@@ -190,6 +307,9 @@ impl KvCacheManager {
     }
 
     /// Append KV pairs to a sequence.
+    ///
+    /// `keys` and `values` are [`KvCacheConfig::kv_dim`] wide: one entry per
+    /// KV head, not per query head.
     pub fn append_kv(
         &self,
         seq_id: SequenceId,
@@ -206,25 +326,34 @@ impl KvCacheManager {
 
         let seq_pos = entry.seq_len;
         let slot = seq_pos % PAGE_TOKENS;
+        let needs_page = slot == 0 || entry.page_ids.is_empty();
 
-        // Allocate new page if needed
-        if slot == 0 || entry.page_ids.is_empty() {
-            let mut page_table = write_or_recover(&self.page_table);
+        // Per-sequence cap: fail this sequence rather than evict others
+        if needs_page && entry.page_ids.len() >= self.config.max_pages_per_sequence() {
+            return Err(KvCacheError::MemoryExhausted);
+        }
+        self.touch(seq_id);
 
-            // Try to allocate, evict if necessary
-            let page_id = match page_table.allocate(seq_pos) {
+        // Allocate new page if needed, evicting another sequence if necessary
+        if needs_page {
+            let allocated = self.allocate_page(seq_pos);
+            let page_id = match allocated {
                 Some(id) => id,
                 None => {
-                    drop(page_table);
-                    self.evict_lru()?;
-                    write_or_recover(&self.page_table)
-                        .allocate(seq_pos)
+                    self.evict(&mut sequences, seq_id)?;
+                    self.allocate_page(seq_pos)
                         .ok_or(KvCacheError::MemoryExhausted)?
                 }
             };
-
+            // Eviction never removes `seq_id`
+            let entry = sequences
+                .get_mut(&seq_id)
+                .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
             entry.page_ids.push(page_id);
         }
+        let entry = sequences
+            .get_mut(&seq_id)
+            .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
 
         // Write to page with mutable access
         {
@@ -238,6 +367,7 @@ impl KvCacheManager {
         if let Some(ref mut qs) = entry.quant_store {
             if !qs.append(keys, values) {
                 // Quantization store full, reset and retry
+                StatsCounters::increment(&self.stats.quantization_errors);
                 qs.reset();
                 qs.append(keys, values);
             }
@@ -269,17 +399,20 @@ impl KvCacheManager {
 
         entry.last_access = Instant::now();
         entry.access_count += 1;
+        self.touch(seq_id);
 
         // Try per-sequence quantized store first
         if let Some(ref qs) = entry.quant_store {
             if pos < qs.seq_len() {
                 qs.read_keys(pos, keys_out);
                 qs.read_values(pos, values_out);
+                StatsCounters::increment(&self.stats.cache_hits);
                 return Ok(());
             }
         }
 
         // Fall back to page table
+        StatsCounters::increment(&self.stats.cache_misses);
         let page_table = read_or_recover(&self.page_table);
         if let Some(page) = page_table.get(pos) {
             let slot = pos % PAGE_TOKENS;
@@ -292,12 +425,19 @@ impl KvCacheManager {
     }
 
     /// Compute attention scores for a query against cached keys.
+    ///
+    /// `query` spans all query heads. Under grouped-query attention each
+    /// cached KV head is broadcast to its group of query heads, so scores
+    /// match a non-grouped cache holding every key once per query head.
     pub fn attention_scores(
         &self,
         seq_id: SequenceId,
         query: &[f32],
         scores_out: &mut [f32],
     ) -> Result<(), KvCacheError> {
+        let folded = self.fold_query_groups(query);
+        let query = folded.as_deref().unwrap_or(query);
+
         let sequences = read_or_recover(&self.sequences);
         let entry = sequences
             .get(&seq_id)
@@ -332,6 +472,15 @@ impl KvCacheManager {
     /// Free a sequence and its pages.
     pub fn free_sequence(&self, seq_id: SequenceId) -> Result<(), KvCacheError> {
         let mut sequences = write_or_recover(&self.sequences);
+        self.remove_sequence(&mut sequences, seq_id)
+    }
+
+    /// Remove `seq_id` with the sequence map already locked.
+    fn remove_sequence(
+        &self,
+        sequences: &mut HashMap<SequenceId, SequenceEntry>,
+        seq_id: SequenceId,
+    ) -> Result<(), KvCacheError> {
         let entry = sequences
             .remove(&seq_id)
             .ok_or(KvCacheError::SequenceNotFound(seq_id.0))?;
@@ -339,6 +488,8 @@ impl KvCacheManager {
         // Free pages
         let mut page_table = write_or_recover(&self.page_table);
         page_table.free(&entry.page_ids);
+        self.record_page_usage(&page_table);
+        drop(page_table);
 
         // Remove from access order
         if let Ok(mut order) = self.access_order.lock() {
@@ -349,19 +500,11 @@ impl KvCacheManager {
     }
 
     /// Get current statistics.
+    ///
+    /// Hits are reads served from the quantized store; misses fall back
+    /// to the page table.
     pub fn stats(&self) -> KvCacheStats {
-        let stats = self.stats.clone();
-        KvCacheStats {
-            total_pages_allocated: stats.total_pages_allocated,
-            total_pages_freed: stats.total_pages_freed,
-            current_pages_in_use: stats.current_pages_in_use,
-            cache_hits: stats.cache_hits,
-            cache_misses: stats.cache_misses,
-            evictions: stats.evictions,
-            quantization_errors: stats.quantization_errors,
-            memory_bytes_used: stats.memory_bytes_used,
-            peak_memory_bytes: stats.peak_memory_bytes,
-        }
+        self.stats.snapshot()
     }
 
     /// Get sequence length.
@@ -386,24 +529,94 @@ impl KvCacheManager {
     /// Get memory usage in bytes.
     pub fn memory_usage(&self) -> usize {
         let page_table = read_or_recover(&self.page_table);
-        let page_count = page_table.page_count();
-        page_count * PAGE_TOKENS * self.config.hidden_dim * 2 * std::mem::size_of::<f32>()
+        page_table.page_count() * self.page_bytes()
     }
 
-    /// Evict least recently used sequence.
-    fn evict_lru(&self) -> Result<(), KvCacheError> {
-        let victim_id = {
-            let mut order = lock_or_recover(&self.access_order);
-            order.pop_front()
-        };
+    /// Bytes of KV storage in one page.
+    fn page_bytes(&self) -> usize {
+        PAGE_TOKENS * self.config.kv_dim() * 2 * std::mem::size_of::<f32>()
+    }
+
+    /// Map a page for `seq_pos`, updating page and memory counters.
+    fn allocate_page(&self, seq_pos: usize) -> Option<PageId> {
+        let mut page_table = write_or_recover(&self.page_table);
+        let page_id = page_table.allocate(seq_pos);
+        self.record_page_usage(&page_table);
+        page_id
+    }
+
+    fn record_page_usage(&self, page_table: &PageTable) {
+        let in_use = page_table.page_count().saturating_sub(page_table.free_count());
+        self.stats
+            .record_pages_in_use(in_use as u64, self.page_bytes() as u64);
+    }
+
+    /// Record a use of `seq_id` for LRU ordering.
+    fn touch(&self, seq_id: SequenceId) {
+        if self.config.eviction_policy != EvictionPolicy::Lru {
+            return;
+        }
+        let mut order = lock_or_recover(&self.access_order);
+        if let Some(pos) = order.iter().position(|&id| id == seq_id) {
+            order.remove(pos);
+            order.push_back(seq_id);
+        }
+    }
 
-        if let Some(id) = victim_id {
-            self.free_sequence(id)?;
+    /// Choose the sequence to evict under the configured policy, never `keep`.
+    fn select_victim(
+        &self,
+        sequences: &HashMap<SequenceId, SequenceEntry>,
+        keep: SequenceId,
+    ) -> Option<SequenceId> {
+        let order = lock_or_recover(&self.access_order);
+        let mut candidates = order.iter().copied().filter(|&id| id != keep);
+        match self.config.eviction_policy {
+            EvictionPolicy::Lru | EvictionPolicy::Fifo => candidates.next(),
+            // Ties go to the oldest sequence
+            EvictionPolicy::Lfu => candidates
+                .min_by_key(|id| sequences.get(id).map_or(0, |e| e.access_count)),
         }
+    }
 
+    /// Evict one sequence other than `keep` to free its pages.
+    fn evict(
+        &self,
+        sequences: &mut HashMap<SequenceId, SequenceEntry>,
+        keep: SequenceId,
+    ) -> Result<(), KvCacheError> {
+        let victim = self
+            .select_victim(sequences, keep)
+            .ok_or(KvCacheError::MemoryExhausted)?;
+        self.remove_sequence(sequences, victim)?;
+        StatsCounters::increment(&self.stats.evictions);
         Ok(())
     }
 
+    /// Sum each group's query heads into the KV head they share.
+    ///
+    /// Dot products distribute over addition, so scoring the summed query
+    /// against a KV head equals scoring every query head in its group.
+    /// `None` when there is nothing to fold.
+    fn fold_query_groups(&self, query: &[f32]) -> Option<Vec<f32>> {
+        let group = self.config.group_size();
+        let head_dim = self.config.head_dim;
+        if group == 1 || query.len() == self.config.kv_dim() {
+            return None;
+        }
+        let mut folded = vec![0.0f32; self.config.kv_dim()];
+        for (head, q_head) in query.chunks(head_dim).enumerate() {
+            let kv_head = head / group;
+            let Some(out) = folded.get_mut(kv_head * head_dim..(kv_head + 1) * head_dim) else {
+                break;
+            };
+            for (o, &q) in out.iter_mut().zip(q_head) {
+                *o += q;
+            }
+        }
+        Some(folded)
+    }
+
     /// Compute dot product of two vectors.
     fn dot_product(a: &[f32], b: &[f32]) -> f32 {
         a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
@@ -436,6 +649,9 @@ pub enum KvCacheError {
 
     #[error("Quantization error: {0}")]
     QuantizationError(String),
+
+    #[error("{num_heads} query heads cannot be grouped over {num_kv_heads} KV heads")]
+    InvalidHeadLayout { num_heads: usize, num_kv_heads: usize },
 }
 
 #[cfg(test)]
@@ -451,7 +667,7 @@ mod tests {
             ..Default::default()
         };
 
-        let manager = KvCacheManager::new(config);
+        let manager = KvCacheManager::new(config).unwrap();
         let seq_id = manager.allocate_sequence();
 
         let keys = vec![1.0f32; 128];
@@ -478,7 +694,7 @@ mod tests {
             ..Default::default()
         };
 
-        let manager = KvCacheManager::new(config);
+        let manager = KvCacheManager::new(config).unwrap();
 
         // Allocate multiple sequences
         let seq1 = manager.allocate_sequence();
@@ -496,6 +712,77 @@ mod tests {
         assert!(manager.has_sequence(seq2));
     }
 
+    /// Allocate three sequences with skewed access, then evict one.
+    ///
+    /// `a` is oldest, `b` least recently used, `c` least frequently used.
+    fn evict_skewed(policy: EvictionPolicy) -> (KvCacheManager, [SequenceId; 3]) {
+        let manager = KvCacheManager::new(KvCacheConfig {
+            hidden_dim: 8,
+            max_pages: 4,
+            max_seq_len: 32,
+            eviction_policy: policy,
+            ..Default::default()
+        })
+            .unwrap();
+        let ids = [(); 3].map(|_| manager.allocate_sequence());
+        let (keys, values) = (vec![1.0f32; 8], vec![2.0f32; 8]);
+        for &id in &ids {
+            manager.append_kv(id, &keys, &values).unwrap();
+        }
+
+        let (mut k_out, mut v_out) = (vec![0.0f32; 8], vec![0.0f32; 8]);
+        let [a, b, c] = ids;
+        for id in [b, b, a, a, a, c] {
+            manager.read_kv(id, 0, &mut k_out, &mut v_out).unwrap();
+        }
+
+        let mut sequences = write_or_recover(&manager.sequences);
+        manager.evict(&mut sequences, SequenceId(0)).unwrap();
+        drop(sequences);
+        (manager, ids)
+    }
+
+    fn survivors(manager: &KvCacheManager, ids: [SequenceId; 3]) -> Vec<bool> {
+        ids.iter().map(|&id| manager.has_sequence(id)).collect()
+    }
+
+    #[test]
+    fn test_fifo_evicts_oldest_regardless_of_access() {
+        let (manager, ids) = evict_skewed(EvictionPolicy::Fifo);
+        assert_eq!(survivors(&manager, ids), [false, true, true]);
+        assert_eq!(manager.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let (manager, ids) = evict_skewed(EvictionPolicy::Lru);
+        assert_eq!(survivors(&manager, ids), [true, false, true]);
+    }
+
+    #[test]
+    fn test_lfu_evicts_least_frequently_used() {
+        let (manager, ids) = evict_skewed(EvictionPolicy::Lfu);
+        assert_eq!(survivors(&manager, ids), [true, true, false]);
+    }
+
+    #[test]
+    fn test_eviction_spares_requesting_sequence() {
+        let manager = KvCacheManager::new(KvCacheConfig {
+            hidden_dim: 8,
+            max_pages: 4,
+            eviction_policy: EvictionPolicy::Fifo,
+            ..Default::default()
+        })
+            .unwrap();
+        let only = manager.allocate_sequence();
+        let mut sequences = write_or_recover(&manager.sequences);
+        assert!(matches!(
+            manager.evict(&mut sequences, only),
+            Err(KvCacheError::MemoryExhausted)
+        ));
+        assert!(sequences.contains_key(&only));
+    }
+
     #[test]
     fn test_attention_scores() {
         let config = KvCacheConfig {
@@ -506,7 +793,7 @@ mod tests {
             ..Default::default()
         };
 
-        let manager = KvCacheManager::new(config);
+        let manager = KvCacheManager::new(config).unwrap();
         let seq_id = manager.allocate_sequence();
 
         // Add some KV pairs
@@ -526,4 +813,76 @@ mod tests {
         // Scores should be computed
         assert!(scores.iter().any(|&s| s != 0.0));
     }
+
+    #[test]
+    fn test_per_sequence_page_cap() {
+        let config = KvCacheConfig {
+            hidden_dim: 64,
+            max_pages: 8,
+            max_seq_len: 256,
+            max_sequence_page_fraction: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(config.max_pages_per_sequence(), 2);
+
+        let manager = KvCacheManager::new(config).unwrap();
+        let greedy = manager.allocate_sequence();
+        let other = manager.allocate_sequence();
+
+        let keys = vec![1.0f32; 64];
+        let values = vec![2.0f32; 64];
+
+        // Two pages worth of tokens fit within the cap
+        for _ in 0..(2 * PAGE_TOKENS) {
+            manager.append_kv(greedy, &keys, &values).unwrap();
+        }
+        assert!(matches!(
+            manager.append_kv(greedy, &keys, &values),
+            Err(KvCacheError::MemoryExhausted)
+        ));
+        assert_eq!(manager.seq_len(greedy).unwrap(), 2 * PAGE_TOKENS);
+
+        // Hitting the cap did not evict anyone; others can still allocate
+        assert!(manager.has_sequence(greedy));
+        manager.append_kv(other, &keys, &values).unwrap();
+        assert_eq!(manager.seq_len(other).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_grouped_head_layout() {
+        let gqa = KvCacheConfig {
+            num_kv_heads: 8,
+            ..Default::default()
+        };
+        assert!(gqa.is_grouped());
+        assert_eq!((gqa.kv_dim(), gqa.group_size()), (1024, 4));
+        assert!(gqa.validate().is_ok());
+
+        let dense = KvCacheConfig::default();
+        assert!(!dense.is_grouped());
+        assert_eq!((dense.kv_dim(), dense.group_size()), (4096, 1));
+
+        for num_kv_heads in [0, 5] {
+            let bad = KvCacheConfig {
+                num_kv_heads,
+                ..Default::default()
+            };
+            assert!(matches!(
+                bad.validate(),
+                Err(KvCacheError::InvalidHeadLayout { num_heads: 32, .. })
+            ));
+            assert!(KvCacheManager::new(bad).is_err());
+        }
+    }
+
+    #[test]
+    fn test_page_fraction_never_below_one_page() {
+        let config = KvCacheConfig {
+            max_pages: 4,
+            max_sequence_page_fraction: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(config.max_pages_per_sequence(), 1);
+        assert_eq!(KvCacheConfig::default().max_pages_per_sequence(), 1024);
+    }
 }
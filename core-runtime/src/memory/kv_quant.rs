@@ -2,7 +2,7 @@
 //!
 //! Stores keys and values in Q8 format for 4x memory bandwidth reduction.
 
-use crate::engine::simd_matmul;
+use crate::engine::accel;
 
 /// Quantized KV storage with per-position scales.
 #[derive(Debug)]
@@ -48,12 +48,12 @@ impl Q8KvStore {
         true
     }
 
-    /// Compute attention scores using SIMD dot product.
+    /// Compute attention scores, using the SIMD dot product when active.
     pub fn attention_scores(&self, query: &[f32], output: &mut [f32]) {
         for pos in 0..self.seq_len {
             let offset = pos * self.hidden_dim;
             let scale = self.key_scales[pos];
-            output[pos] = simd_matmul::dot_q8(
+            output[pos] = accel::dot_q8(
                 &self.keys[offset..offset + self.hidden_dim],
                 query,
                 scale,
@@ -3,12 +3,14 @@
 //! Tracks and enforces memory and concurrency limits per inference call.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
 
 use crate::engine::InferenceError;
 
 /// Configuration for resource limits.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ResourceLimitsConfig {
     /// Maximum memory per inference call (bytes).
     pub max_memory_per_call: usize,
@@ -28,9 +30,20 @@ impl Default for ResourceLimitsConfig {
     }
 }
 
+impl ResourceLimitsConfig {
+    /// Limits that never reject a call.
+    pub fn unlimited() -> Self {
+        Self {
+            max_memory_per_call: usize::MAX,
+            max_total_memory: usize::MAX,
+            max_concurrent: usize::MAX,
+        }
+    }
+}
+
 /// Shared state for resource tracking.
 struct LimitsInner {
-    config: ResourceLimitsConfig,
+    config: RwLock<ResourceLimitsConfig>,
     current_memory: AtomicUsize,
     current_concurrent: AtomicUsize,
 }
@@ -46,43 +59,55 @@ impl ResourceLimits {
     pub fn new(config: ResourceLimitsConfig) -> Self {
         Self {
             inner: Arc::new(LimitsInner {
-                config,
+                config: RwLock::new(config),
                 current_memory: AtomicUsize::new(0),
                 current_concurrent: AtomicUsize::new(0),
             }),
         }
     }
 
+    /// Limits currently enforced.
+    pub fn config(&self) -> ResourceLimitsConfig {
+        self.inner.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Enforce `config` from the next acquisition on. Calls already holding
+    /// resources keep them, even past a lowered limit.
+    pub fn set_config(&self, config: ResourceLimitsConfig) {
+        *self.inner.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
     /// Try to acquire resources for an inference call.
     pub fn try_acquire(&self, memory_bytes: usize) -> Result<ResourceGuard, InferenceError> {
         let inner = &self.inner;
+        let config = self.config();
 
         // Check memory limit
-        if memory_bytes > inner.config.max_memory_per_call {
+        if memory_bytes > config.max_memory_per_call {
             return Err(InferenceError::MemoryExceeded {
                 used: memory_bytes,
-                limit: inner.config.max_memory_per_call,
+                limit: config.max_memory_per_call,
             });
         }
 
         // Try to reserve memory
         let prev_memory = inner.current_memory.fetch_add(memory_bytes, Ordering::SeqCst);
-        if prev_memory + memory_bytes > inner.config.max_total_memory {
+        if prev_memory + memory_bytes > config.max_total_memory {
             inner.current_memory.fetch_sub(memory_bytes, Ordering::SeqCst);
             return Err(InferenceError::MemoryExceeded {
                 used: prev_memory + memory_bytes,
-                limit: inner.config.max_total_memory,
+                limit: config.max_total_memory,
             });
         }
 
         // Try to reserve concurrency slot
         let prev_concurrent = inner.current_concurrent.fetch_add(1, Ordering::SeqCst);
-        if prev_concurrent >= inner.config.max_concurrent {
+        if prev_concurrent >= config.max_concurrent {
             inner.current_concurrent.fetch_sub(1, Ordering::SeqCst);
             inner.current_memory.fetch_sub(memory_bytes, Ordering::SeqCst);
             return Err(InferenceError::QueueFull {
                 current: prev_concurrent + 1,
-                max: inner.config.max_concurrent,
+                max: config.max_concurrent,
             });
         }
 
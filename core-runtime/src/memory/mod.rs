@@ -1,7 +1,8 @@
 //! Memory management module for CORE Runtime.
 //!
 //! Provides pooled memory allocation, GPU memory tracking, context caching,
-//! arena allocation, paged KV-cache, and resource limit enforcement.
+//! arena allocation, paged KV-cache, resource limit enforcement, and the
+//! system memory floor checked at admission.
 
 mod arena;
 mod cache;
@@ -11,16 +12,18 @@ pub mod kv_quant;
 mod limits;
 pub mod paged;
 mod pool;
+mod pressure;
 pub mod prompt_cache;
 
-pub use arena::{Arena, ArenaPool, ArenaSlice};
+pub use arena::{Arena, ArenaPool, ArenaSlice, PooledArena};
 pub use cache::{ContextCache, ContextCacheConfig, KvCache, KvCacheEntry};
 pub use gpu::{GpuMemory, GpuMemoryConfig, GpuMemoryError};
 pub use kv_cache::{
     EvictionPolicy, KvCacheConfig, KvCacheError, KvCacheManager, KvCacheStats, SequenceId,
 };
 pub use kv_quant::{compute_scale, dequantize, quantize_to, Q8KvStore};
-pub use limits::{ResourceLimits, ResourceLimitsConfig};
+pub use limits::{ResourceGuard, ResourceLimits, ResourceLimitsConfig};
 pub use paged::{Page, PageId, PageTable, PAGE_TOKENS};
 pub use pool::{MemoryPool, MemoryPoolConfig, PooledBuffer};
+pub use pressure::{available_memory, parse_mem_available, MemoryFloorConfig};
 pub use prompt_cache::{CachedKv, PromptCache};
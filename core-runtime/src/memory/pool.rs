@@ -6,9 +6,10 @@
 use std::collections::VecDeque;
 use std::sync::Arc;
 use parking_lot::Mutex;
+use serde::Serialize;
 
 /// Configuration for memory pool.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MemoryPoolConfig {
     pub buffer_size: usize,
     pub max_buffers: usize,
@@ -0,0 +1,105 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! System memory floor enforced at request admission.
+//!
+//! `max_total_memory` only bounds what this process reserves; the host can
+//! still approach OOM through page cache or other processes. When a floor is
+//! configured, new requests are rejected while `MemAvailable` is below it so
+//! the runtime degrades before the kernel OOM-killer acts.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::engine::InferenceError;
+use crate::telemetry::{log_security_event, SecurityEvent};
+
+/// Minimum available system memory required to admit a request.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryFloorConfig {
+    /// Floor in bytes. `None` disables the check.
+    pub min_available_bytes: Option<u64>,
+    /// Source of `MemAvailable` (read on Linux only).
+    pub meminfo_path: PathBuf,
+}
+
+impl Default for MemoryFloorConfig {
+    fn default() -> Self {
+        Self {
+            min_available_bytes: None,
+            meminfo_path: PathBuf::from("/proc/meminfo"),
+        }
+    }
+}
+
+impl MemoryFloorConfig {
+    /// Reject if available system memory is below the floor.
+    ///
+    /// Admits when no floor is set or available memory cannot be read.
+    pub fn check(&self) -> Result<(), InferenceError> {
+        let Some(floor) = self.min_available_bytes else {
+            return Ok(());
+        };
+        let Some(available) = available_memory(&self.meminfo_path) else {
+            return Ok(());
+        };
+        if available >= floor {
+            return Ok(());
+        }
+
+        log_security_event(
+            SecurityEvent::MemoryPressure,
+            "Request rejected: available system memory below floor",
+            &[
+                ("available_bytes", &available.to_string()),
+                ("floor_bytes", &floor.to_string()),
+            ],
+        );
+        Err(InferenceError::MemoryPressure { available, floor })
+    }
+}
+
+/// Available system memory in bytes, if the platform exposes it.
+#[cfg(target_os = "linux")]
+pub fn available_memory(meminfo_path: &Path) -> Option<u64> {
+    let meminfo = std::fs::read_to_string(meminfo_path).ok()?;
+    parse_mem_available(&meminfo)
+}
+
+/// Available system memory in bytes, if the platform exposes it.
+#[cfg(not(target_os = "linux"))]
+pub fn available_memory(_meminfo_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Extract `MemAvailable` (reported in kB) from `/proc/meminfo` contents.
+pub fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       16314368 kB\nMemFree:          512000 kB\nMemAvailable:    2048000 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(2048000 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_no_floor_always_admits() {
+        let config = MemoryFloorConfig {
+            meminfo_path: PathBuf::from("/nonexistent/meminfo"),
+            ..Default::default()
+        };
+        assert!(config.check().is_ok());
+    }
+}
@@ -1,14 +1,20 @@
 //! LRU prompt cache for repeated prefix reuse.
 //!
-//! Caches serialized KV data keyed by token sequence hash.
+//! Caches serialized KV data keyed by a rolling hash of the token sequence,
+//! so every prefix of a prompt can be looked up in a single pass.
 
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
+/// Multiplier for the polynomial rolling hash (the 64-bit FNV prime).
+const ROLLING_BASE: u64 = 0x0000_0100_0000_01B3;
+
 /// Cached KV entry with LRU tracking.
 #[derive(Debug, Clone)]
 pub struct CachedKv {
-    _token_hash: [u8; 32],
+    /// Full token sequence, compared on lookup so a hash collision can never
+    /// hand out another prompt's KV.
+    tokens: Vec<u32>,
     kv_data: Vec<u8>,
     seq_len: usize,
     last_used: u64,
@@ -26,13 +32,13 @@ impl CachedKv {
 /// LRU prompt cache with hash-based lookup.
 #[derive(Debug)]
 pub struct PromptCache {
-    entries: HashMap<[u8; 32], CachedKv>,
+    entries: HashMap<u64, CachedKv>,
     max_entries: usize,
     access_counter: u64,
 }
 
 impl PromptCache {
-    /// Create a new prompt cache with given capacity.
+    /// Create a new prompt cache with given capacity; 0 disables caching.
     pub fn new(max_entries: usize) -> Self {
         Self {
             entries: HashMap::with_capacity(max_entries),
@@ -50,30 +56,49 @@ impl PromptCache {
         hasher.finalize().into()
     }
 
+    /// Rolling hash of a token sequence, used as the cache key.
+    ///
+    /// Extending a sequence by one token updates the hash in O(1), which
+    /// lets prefix lookup hash every prefix in one pass over the prompt.
+    pub fn rolling_hash(tokens: &[u32]) -> u64 {
+        tokens.iter().fold(0, |hash, &t| Self::roll(hash, t))
+    }
+
+    fn roll(hash: u64, token: u32) -> u64 {
+        // Offset by one so a leading token 0 still changes the hash
+        hash.wrapping_mul(ROLLING_BASE).wrapping_add(u64::from(token) + 1)
+    }
+
     /// Look up cached KV for exact token match.
     pub fn get(&mut self, tokens: &[u32]) -> Option<&CachedKv> {
-        let hash = Self::hash_tokens(tokens);
+        let hash = Self::rolling_hash(tokens);
         self.access_counter += 1;
         let counter = self.access_counter;
-        if let Some(entry) = self.entries.get_mut(&hash) {
-            entry.last_used = counter;
-            return Some(entry);
+        match self.entries.get_mut(&hash) {
+            Some(entry) if entry.tokens == tokens => {
+                entry.last_used = counter;
+                Some(entry)
+            }
+            _ => None,
         }
-        None
     }
 
     /// Store computed KV for token sequence.
     pub fn insert(&mut self, tokens: &[u32], kv_data: Vec<u8>, seq_len: usize) {
-        if self.entries.len() >= self.max_entries {
+        if self.max_entries == 0 {
+            return;
+        }
+        let hash = Self::rolling_hash(tokens);
+        // Replacing an existing key needs no room
+        if !self.entries.contains_key(&hash) && self.entries.len() >= self.max_entries {
             self.evict_lru();
         }
 
-        let hash = Self::hash_tokens(tokens);
         self.access_counter += 1;
         self.entries.insert(
             hash,
             CachedKv {
-                _token_hash: hash,
+                tokens: tokens.to_vec(),
                 kv_data,
                 seq_len,
                 last_used: self.access_counter,
@@ -81,20 +106,33 @@ impl PromptCache {
         );
     }
 
-    /// Find longest cached prefix of tokens. Returns (prefix_len, cloned entry).
-    pub fn find_prefix(&mut self, tokens: &[u32]) -> Option<(usize, CachedKv)> {
-        for len in (1..=tokens.len()).rev() {
-            let hash = Self::hash_tokens(&tokens[..len]);
-            if self.entries.contains_key(&hash) {
-                self.access_counter += 1;
-                let counter = self.access_counter;
-                if let Some(entry) = self.entries.get_mut(&hash) {
-                    entry.last_used = counter;
-                    return Some((len, entry.clone()));
+    /// Longest cached prefix of `tokens`, as (prefix_len, cloned entry).
+    ///
+    /// Does not count as a use for LRU purposes; see `find_prefix`.
+    pub fn longest_prefix_match(&self, tokens: &[u32]) -> Option<(usize, CachedKv)> {
+        let mut best = None;
+        let mut hash = 0;
+        for (i, &token) in tokens.iter().enumerate() {
+            hash = Self::roll(hash, token);
+            if let Some(entry) = self.entries.get(&hash) {
+                if entry.tokens[..] == tokens[..=i] {
+                    best = Some((i + 1, entry));
                 }
             }
         }
-        None
+        best.map(|(len, entry)| (len, entry.clone()))
+    }
+
+    /// Find longest cached prefix of tokens and mark it recently used.
+    /// Returns (prefix_len, cloned entry).
+    pub fn find_prefix(&mut self, tokens: &[u32]) -> Option<(usize, CachedKv)> {
+        let (len, entry) = self.longest_prefix_match(tokens)?;
+        self.access_counter += 1;
+        let counter = self.access_counter;
+        if let Some(cached) = self.entries.get_mut(&Self::rolling_hash(&tokens[..len])) {
+            cached.last_used = counter;
+        }
+        Some((len, entry))
     }
 
     /// Evict least recently used entry.
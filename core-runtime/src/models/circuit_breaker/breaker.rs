@@ -0,0 +1,159 @@
+//! One model's breaker state machine.
+
+use std::time::Instant;
+
+use super::{CircuitBreakerConfig, CircuitState};
+
+/// How a request got past the breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Admission {
+    Normal,
+    Probe,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Breaker {
+    pub(super) state: CircuitState,
+    consecutive_failures: u32,
+    /// Start of the current failure streak.
+    streak_started: Option<Instant>,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl Breaker {
+    /// Admit a request, half-opening once the cooldown has passed.
+    pub(super) fn admit(
+        &mut self,
+        config: &CircuitBreakerConfig,
+        now: Instant,
+    ) -> Option<Admission> {
+        match self.state {
+            CircuitState::Closed => Some(Admission::Normal),
+            CircuitState::Open => {
+                let cooled = self
+                    .opened_at
+                    .is_none_or(|opened| now.duration_since(opened) >= config.cooldown);
+                cooled.then(|| {
+                    self.state = CircuitState::HalfOpen;
+                    self.probe_in_flight = true;
+                    Admission::Probe
+                })
+            }
+            CircuitState::HalfOpen if self.probe_in_flight => None,
+            CircuitState::HalfOpen => {
+                self.probe_in_flight = true;
+                Some(Admission::Probe)
+            }
+        }
+    }
+
+    pub(super) fn succeeded(&mut self, admission: Admission) {
+        match (self.state, admission) {
+            (CircuitState::HalfOpen, Admission::Probe) => *self = Self::default(),
+            (CircuitState::Closed, _) => {
+                self.consecutive_failures = 0;
+                self.streak_started = None;
+            }
+            // Finished after the breaker opened; says nothing about now
+            _ => {}
+        }
+    }
+
+    pub(super) fn failed(
+        &mut self,
+        admission: Admission,
+        config: &CircuitBreakerConfig,
+        now: Instant,
+    ) {
+        match (self.state, admission) {
+            (CircuitState::HalfOpen, Admission::Probe) => self.open(now),
+            (CircuitState::Closed, _) if config.failure_threshold > 0 => {
+                let streak_expired = self
+                    .streak_started
+                    .is_none_or(|started| now.duration_since(started) > config.failure_window);
+                if streak_expired {
+                    self.streak_started = Some(now);
+                    self.consecutive_failures = 0;
+                }
+                self.consecutive_failures += 1;
+                if self.consecutive_failures >= config.failure_threshold {
+                    self.open(now);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A probe that ended without reaching the model frees the slot.
+    pub(super) fn abandoned(&mut self, admission: Admission) {
+        if admission == Admission::Probe && self.state == CircuitState::HalfOpen {
+            self.probe_in_flight = false;
+        }
+    }
+
+    /// Closed with no failures: the same as having no breaker at all.
+    pub(super) fn is_idle(&self) -> bool {
+        self.state == CircuitState::Closed && self.consecutive_failures == 0
+    }
+
+    fn open(&mut self, now: Instant) {
+        *self = Self {
+            state: CircuitState::Open,
+            opened_at: Some(now),
+            ..Self::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 3,
+            failure_window: Duration::from_secs(10),
+            cooldown: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn failures_outside_window_start_a_new_streak() {
+        let mut breaker = Breaker::default();
+        let start = Instant::now();
+        breaker.failed(Admission::Normal, &config(), start);
+        breaker.failed(Admission::Normal, &config(), start);
+        breaker.failed(Admission::Normal, &config(), start + Duration::from_secs(11));
+
+        assert_eq!(breaker.state, CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures, 1);
+    }
+
+    #[test]
+    fn open_breaker_half_opens_after_cooldown() {
+        let mut breaker = Breaker::default();
+        let start = Instant::now();
+        breaker.open(start);
+
+        assert_eq!(breaker.admit(&config(), start + Duration::from_secs(4)), None);
+        assert_eq!(
+            breaker.admit(&config(), start + Duration::from_secs(5)),
+            Some(Admission::Probe)
+        );
+        assert_eq!(breaker.state, CircuitState::HalfOpen);
+        // Only one probe at a time
+        assert_eq!(breaker.admit(&config(), start + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn zero_threshold_never_opens() {
+        let mut breaker = Breaker::default();
+        let config = CircuitBreakerConfig { failure_threshold: 0, ..config() };
+        for _ in 0..10 {
+            breaker.failed(Admission::Normal, &config, Instant::now());
+        }
+        assert_eq!(breaker.state, CircuitState::Closed);
+    }
+}
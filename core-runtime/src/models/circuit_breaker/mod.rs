@@ -0,0 +1,207 @@
+//! Per-model circuit breakers for inference failures.
+//!
+//! A model that fails `failure_threshold` requests in a row within
+//! `failure_window` is taken out of service: requests to it fail fast for
+//! `cooldown`. The breaker then half-opens and lets a single probe request
+//! through, whose outcome closes the breaker or reopens it for another
+//! cooldown.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::security::audit::{log_detached, AuditCategory, AuditEvent, AuditSeverity};
+use crate::telemetry;
+
+mod breaker;
+
+use breaker::{Admission, Breaker};
+
+/// When a model's breaker opens and how long it stays open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the breaker. 0 disables breaking.
+    pub failure_threshold: u32,
+    /// Failures further apart than this start a new streak.
+    pub failure_window: Duration,
+    /// Time spent open before a probe request is let through.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            failure_window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// State of one model's breaker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests reach the model.
+    #[default]
+    Closed,
+    /// Requests fail fast until the cooldown ends.
+    Open,
+    /// One probe request is testing the model.
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Breakers for every model, keyed by model_id.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    breakers: Arc<Mutex<HashMap<String, Breaker>>>,
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: Arc::default(),
+        }
+    }
+
+    /// Admit a request for `model_id`; `None` while its breaker is open.
+    pub(crate) fn admit(&self, model_id: &str) -> Option<CircuitPass> {
+        let admission = self.update(model_id, |breaker, config| {
+            breaker.admit(config, Instant::now())
+        })?;
+        Some(CircuitPass {
+            breakers: self.clone(),
+            model_id: model_id.to_string(),
+            admission,
+            recorded: false,
+        })
+    }
+
+    pub(crate) fn state(&self, model_id: &str) -> CircuitState {
+        lock(&self.breakers)
+            .get(model_id)
+            .map_or(CircuitState::Closed, |breaker| breaker.state)
+    }
+
+    /// Forget `model_id`'s failures, e.g. when the model is replaced.
+    pub(crate) fn reset(&self, model_id: &str) {
+        lock(&self.breakers).remove(model_id);
+    }
+
+    /// Apply `change` to `model_id`'s breaker, reporting any state change.
+    ///
+    /// Only models with a failure history keep an entry, so requests for
+    /// arbitrary model IDs do not grow the map.
+    fn update<T>(
+        &self,
+        model_id: &str,
+        change: impl FnOnce(&mut Breaker, &CircuitBreakerConfig) -> T,
+    ) -> T {
+        let (before, after, result) = {
+            let mut breakers = lock(&self.breakers);
+            let breaker = breakers.entry(model_id.to_string()).or_default();
+            let before = breaker.state;
+            let result = change(breaker, &self.config);
+            let after = breaker.state;
+            if breaker.is_idle() {
+                breakers.remove(model_id);
+            }
+            (before, after, result)
+        };
+        if before != after {
+            report_transition(model_id, before, after);
+        }
+        result
+    }
+}
+
+/// Permission for one request to reach a model.
+///
+/// Call [`record`](Self::record) with the request's outcome. A pass dropped
+/// unrecorded (the request never reached the model) leaves the breaker as
+/// it was, apart from releasing a half-open probe slot.
+#[derive(Debug)]
+pub struct CircuitPass {
+    breakers: CircuitBreakers,
+    model_id: String,
+    admission: Admission,
+    recorded: bool,
+}
+
+impl CircuitPass {
+    /// Whether this request is the half-open probe.
+    pub fn is_probe(&self) -> bool {
+        self.admission == Admission::Probe
+    }
+
+    /// Count the request's outcome against the model.
+    pub fn record(mut self, success: bool) {
+        self.recorded = true;
+        let admission = self.admission;
+        self.breakers.update(&self.model_id, |breaker, config| {
+            if success {
+                breaker.succeeded(admission);
+            } else {
+                breaker.failed(admission, config, Instant::now());
+            }
+        });
+    }
+}
+
+impl Drop for CircuitPass {
+    fn drop(&mut self) {
+        if !self.recorded {
+            let admission = self.admission;
+            self.breakers
+                .update(&self.model_id, |breaker, _| breaker.abandoned(admission));
+        }
+    }
+}
+
+/// Log, count and audit a breaker state change.
+fn report_transition(model_id: &str, from: CircuitState, to: CircuitState) {
+    let (from, to_str) = (from.as_str(), to.as_str());
+    if to == CircuitState::Open {
+        tracing::warn!(model = model_id, from, "circuit breaker opened");
+    } else {
+        tracing::info!(model = model_id, from, to = to_str, "circuit breaker state changed");
+    }
+    telemetry::record_circuit_transition(model_id, to_str);
+
+    let severity = match to {
+        CircuitState::Open => AuditSeverity::Warning,
+        _ => AuditSeverity::Info,
+    };
+    if let Ok(event) = AuditEvent::builder()
+        .severity(severity)
+        .category(AuditCategory::ModelOperation)
+        .event_type("circuit_breaker_transition")
+        .message(format!("Circuit breaker {} -> {}", from, to_str))
+        .source("model_router")
+        .resource(model_id)
+        .metadata("from", from)
+        .metadata("to", to_str)
+        .success(to == CircuitState::Closed)
+        .build()
+    {
+        log_detached(event);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
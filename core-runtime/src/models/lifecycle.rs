@@ -0,0 +1,110 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tokenizer and chat template lifecycle for served models.
+//!
+//! Lets a broken tokenizer config or chat template be replaced without
+//! reloading the model weights. Both are swapped in the inference engine
+//! (`InferenceEngine::set_detokenizer` / `set_chat_template`), whose locks
+//! make a swap wait for requests mid-rendering or mid-detokenization on the
+//! model to finish; later requests see only the new one.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use super::registry::ModelHandle;
+use crate::engine::{ChatTemplate, Detokenizer, InferenceEngine, TokenizerError};
+
+#[derive(Error, Debug)]
+pub enum LifecycleError {
+    #[error("Model not served: {0:?}")]
+    ModelNotFound(ModelHandle),
+
+    #[error("No tokenizer attached to model: {0:?}")]
+    AssetsNotAttached(ModelHandle),
+
+    #[error("Tokenizer load failed: {0}")]
+    TokenizerLoad(#[from] TokenizerError),
+}
+
+/// Source a tokenizer can be (re)loaded from.
+pub trait TokenizerSource: Send + Sync {
+    fn load(&self) -> Result<Arc<dyn Detokenizer>, TokenizerError>;
+}
+
+impl<F> TokenizerSource for F
+where
+    F: Fn() -> Result<Arc<dyn Detokenizer>, TokenizerError> + Send + Sync,
+{
+    fn load(&self) -> Result<Arc<dyn Detokenizer>, TokenizerError> {
+        self()
+    }
+}
+
+/// Reloads the tokenizer and chat template of each model the engine serves.
+pub struct ModelLifecycle {
+    engine: Arc<InferenceEngine>,
+    sources: RwLock<HashMap<ModelHandle, Arc<dyn TokenizerSource>>>,
+}
+
+impl ModelLifecycle {
+    pub fn new(engine: Arc<InferenceEngine>) -> Self {
+        Self {
+            engine,
+            sources: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Attach a tokenizer source to a served model and decode with it.
+    pub async fn attach(
+        &self,
+        handle: ModelHandle,
+        source: Arc<dyn TokenizerSource>,
+    ) -> Result<(), LifecycleError> {
+        let model_id = self.model_id(handle).await?;
+        let detokenizer = source.load()?;
+        self.engine.set_detokenizer(&model_id, detokenizer).await;
+        self.sources.write().await.insert(handle, source);
+        Ok(())
+    }
+
+    /// Forget the tokenizer source attached to a model.
+    pub async fn detach(&self, handle: ModelHandle) {
+        self.sources.write().await.remove(&handle);
+    }
+
+    /// Reload the tokenizer from its source without touching the weights.
+    pub async fn reload_tokenizer(&self, handle: ModelHandle) -> Result<(), LifecycleError> {
+        let source = {
+            let sources = self.sources.read().await;
+            let source = sources.get(&handle).ok_or(LifecycleError::AssetsNotAttached(handle))?;
+            Arc::clone(source)
+        };
+        let model_id = self.model_id(handle).await?;
+        // Load before swapping so a bad source never blocks traffic
+        let detokenizer = source.load()?;
+        self.engine.set_detokenizer(&model_id, detokenizer).await;
+        Ok(())
+    }
+
+    /// Render the model's chat requests with `template` from now on,
+    /// without touching the weights.
+    pub async fn reload_template(
+        &self,
+        handle: ModelHandle,
+        template: ChatTemplate,
+    ) -> Result<(), LifecycleError> {
+        let model_id = self.model_id(handle).await?;
+        self.engine.set_chat_template(&model_id, template).await;
+        Ok(())
+    }
+
+    async fn model_id(&self, handle: ModelHandle) -> Result<String, LifecycleError> {
+        self.engine
+            .model_id_for(handle)
+            .await
+            .ok_or(LifecycleError::ModelNotFound(handle))
+    }
+}
@@ -0,0 +1,122 @@
+//! Checking model files against the SHA-256 in their manifest.
+//!
+//! A model may ship a manifest beside it as `<stem>.manifest.json`. When it
+//! carries a `sha256`, the file must match before its weights are loaded.
+//! Every check is audited, whatever its outcome.
+
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::{LoadError, MappedModel, ModelLoader, ModelPath};
+use crate::models::manifest::ModelManifest;
+use crate::security::audit::{log_detached, AuditCategory, AuditEvent, AuditSeverity};
+use crate::telemetry::{log_security_event, SecurityEvent};
+
+/// Suffix replacing a model file's extension to name its manifest.
+const MANIFEST_SUFFIX: &str = "manifest.json";
+
+impl ModelLoader {
+    /// Read and validate the manifest beside `model_path`; `None` if the
+    /// model ships without one. Pass it to `verify_integrity` before loading.
+    pub fn read_manifest(
+        &self,
+        model_path: &ModelPath,
+    ) -> Result<Option<ModelManifest>, LoadError> {
+        let path = manifest_path(model_path.as_path());
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let manifest = ModelManifest::from_file(&path)
+            .and_then(|manifest| manifest.validate().map(|()| manifest))
+            .map_err(|e| LoadError::InvalidFormat(e.to_string()))?;
+        Ok(Some(manifest))
+    }
+
+    /// Check `model_path` against `manifest.sha256`; manifests without a
+    /// hash pass unchecked. Reads the whole file, so call it off the async
+    /// runtime, while holding the file's load guard.
+    pub fn verify_integrity(
+        &self,
+        model_path: &ModelPath,
+        manifest: &ModelManifest,
+    ) -> Result<(), LoadError> {
+        let Some(expected) = manifest.sha256.as_deref() else {
+            return Ok(());
+        };
+        let actual = sha256_file(model_path.as_path())?;
+        let verified = actual.eq_ignore_ascii_case(expected);
+        audit_integrity_check(&manifest.model_id, &actual, verified);
+        if !verified {
+            log_security_event(
+                SecurityEvent::ModelHashMismatch,
+                "Model integrity check failed; load refused",
+                &[("model", &manifest.model_id), ("expected", expected), ("actual", &actual)],
+            );
+            return Err(LoadError::HashMismatch {
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Memory-map a model once it matches `manifest.sha256`.
+    pub fn load_verified(
+        &self,
+        model_path: &ModelPath,
+        manifest: &ModelManifest,
+    ) -> Result<MappedModel, LoadError> {
+        let name = model_path
+            .as_path()
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let _guard = self.begin_load(name)?;
+        self.verify_integrity(model_path, manifest)?;
+        MappedModel::open(model_path)
+    }
+}
+
+/// `models/llama.gguf` -> `models/llama.manifest.json`.
+fn manifest_path(model: &Path) -> PathBuf {
+    model.with_extension(MANIFEST_SUFFIX)
+}
+
+/// Record a model integrity check in the audit log.
+fn audit_integrity_check(model_id: &str, actual: &str, success: bool) {
+    let (severity, message) = if success {
+        (AuditSeverity::Info, "Model integrity verified")
+    } else {
+        (AuditSeverity::Critical, "Model integrity check failed")
+    };
+    if let Ok(event) = AuditEvent::builder()
+        .severity(severity)
+        .category(AuditCategory::ModelOperation)
+        .event_type("model_integrity_check")
+        .message(message)
+        .source("model_loader")
+        .resource(model_id)
+        .metadata("sha256", actual)
+        .success(success)
+        .build()
+    {
+        log_detached(event);
+    }
+}
+
+/// Hex-encoded SHA-256 of a file, read in chunks.
+pub(super) fn sha256_file(path: &Path) -> Result<String, LoadError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
@@ -0,0 +1,42 @@
+//! Read-only memory mappings of model files.
+
+use memmap2::Mmap;
+use std::fs::File;
+
+use super::{LoadError, ModelPath};
+
+/// Memory-mapped model for zero-copy loading.
+/// Uses memmap2 for cross-platform support.
+pub struct MappedModel {
+    mmap: Mmap,
+}
+
+// SAFETY: Mmap is Send+Sync when underlying file is read-only and not modified.
+// We only use read-only mappings and models are immutable during inference.
+unsafe impl Send for MappedModel {}
+unsafe impl Sync for MappedModel {}
+
+impl MappedModel {
+    /// Memory-map a model file for zero-copy access.
+    pub fn open(path: &ModelPath) -> Result<Self, LoadError> {
+        let file = File::open(path.as_path())?;
+        // SAFETY: File is opened read-only, model files are not modified during runtime
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self { mmap })
+    }
+
+    /// Get model data as a byte slice (zero-copy).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Length of mapped data in bytes.
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Check if mapped region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.mmap.is_empty()
+    }
+}
@@ -0,0 +1,210 @@
+//! Model loading and validation.
+//!
+//! New model files are written to `temp/` and only enter `models/` through
+//! `stage_and_promote`, which verifies the checksum first and then renames
+//! atomically, so a partially written file is never loadable.
+
+mod integrity;
+mod mapped;
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+use crate::telemetry::{log_security_event, SecurityEvent};
+use integrity::sha256_file;
+
+pub use mapped::MappedModel;
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("Model path not allowed: {0}")]
+    PathNotAllowed(PathBuf),
+
+    #[error("Model file not found: {0}")]
+    NotFound(PathBuf),
+
+    #[error("Invalid model format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Hash mismatch: expected {expected}, got {actual}")]
+    HashMismatch { expected: String, actual: String },
+
+    #[error("Load in progress for model: {0}")]
+    LoadInProgress(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Validated model path within allowed directories.
+#[derive(Debug, Clone)]
+pub struct ModelPath {
+    path: PathBuf,
+}
+
+impl ModelPath {
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Allowed directories for model loading.
+const ALLOWED_DIRS: &[&str] = &["models", "tokenizers"];
+
+/// Directory model files are promoted into.
+const MODELS_DIR: &str = "models";
+
+/// Writable staging directory for incoming model files.
+const STAGING_DIR: &str = "temp";
+
+/// Loads and validates models from allowed directories.
+pub struct ModelLoader {
+    base_path: PathBuf,
+    /// File names under `models/` currently being loaded or promoted.
+    in_flight: Mutex<HashSet<String>>,
+}
+
+/// Marks a model file busy until dropped.
+pub struct LoadGuard<'a> {
+    in_flight: &'a Mutex<HashSet<String>>,
+    name: String,
+}
+
+impl Drop for LoadGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&self.name);
+        }
+    }
+}
+
+impl ModelLoader {
+    pub fn new(base_path: PathBuf) -> Self {
+        Self { base_path, in_flight: Mutex::new(HashSet::new()) }
+    }
+
+    /// Mark `name` (a file name under `models/`) as loading.
+    ///
+    /// Fails if a load or promotion of the same file is already running.
+    pub fn begin_load(&self, name: &str) -> Result<LoadGuard<'_>, LoadError> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if !in_flight.insert(name.to_string()) {
+            return Err(LoadError::LoadInProgress(name.to_string()));
+        }
+        Ok(LoadGuard { in_flight: &self.in_flight, name: name.to_string() })
+    }
+
+    /// Verify a staged file in `temp/` and atomically move it into `models/`.
+    ///
+    /// On checksum mismatch the staged file is left in place for inspection
+    /// and `models/` is not touched.
+    pub fn stage_and_promote(
+        &self,
+        src_temp: &str,
+        dest_name: &str,
+        expected_sha256: &str,
+    ) -> Result<ModelPath, LoadError> {
+        let dest_name = plain_file_name(dest_name)?;
+        let staging_dir = self.base_path.join(STAGING_DIR);
+        let staged = staging_dir.join(src_temp).canonicalize().map_err(|_| {
+            LoadError::NotFound(staging_dir.join(src_temp))
+        })?;
+        let staging_canonical = staging_dir.canonicalize()?;
+        if !staged.starts_with(&staging_canonical) || !staged.is_file() {
+            return Err(LoadError::PathNotAllowed(staged));
+        }
+
+        let _guard = self.begin_load(dest_name)?;
+
+        let actual = sha256_file(&staged)?;
+        if !actual.eq_ignore_ascii_case(expected_sha256) {
+            log_security_event(
+                SecurityEvent::ModelHashMismatch,
+                "Staged model checksum mismatch; promotion refused",
+                &[("model", dest_name), ("expected", expected_sha256), ("actual", &actual)],
+            );
+            return Err(LoadError::HashMismatch {
+                expected: expected_sha256.to_string(),
+                actual,
+            });
+        }
+
+        let models_dir = self.base_path.join(MODELS_DIR);
+        std::fs::create_dir_all(&models_dir)?;
+        // Same filesystem under base_path, so rename is atomic
+        std::fs::rename(&staged, models_dir.join(dest_name))?;
+        tracing::info!(model = dest_name, sha256 = %actual, "promoted staged model");
+
+        self.validate_path(&format!("{}/{}", MODELS_DIR, dest_name))
+    }
+
+    /// Validate and create a ModelPath if within allowed directories.
+    pub fn validate_path(&self, relative_path: &str) -> Result<ModelPath, LoadError> {
+        let full_path = self.base_path.join(relative_path);
+        let canonical = full_path.canonicalize().map_err(|_| {
+            LoadError::NotFound(full_path.clone())
+        })?;
+
+        let is_allowed = ALLOWED_DIRS.iter().any(|dir| {
+            let allowed = self.base_path.join(dir);
+            // Canonicalize the allowed path to match format of canonical path
+            allowed.canonicalize()
+                .map(|allowed_canonical| canonical.starts_with(&allowed_canonical))
+                .unwrap_or(false)
+        });
+
+        if !is_allowed {
+            return Err(LoadError::PathNotAllowed(canonical));
+        }
+
+        Ok(ModelPath { path: canonical })
+    }
+
+    /// Load model metadata from validated path.
+    pub fn load_metadata(&self, model_path: &ModelPath) -> Result<ModelMetadata, LoadError> {
+        let path = model_path.as_path();
+
+        if !path.exists() {
+            return Err(LoadError::NotFound(path.to_path_buf()));
+        }
+
+        let size = std::fs::metadata(path)?.len();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        Ok(ModelMetadata { name, size_bytes: size })
+    }
+
+    /// Load model using memory-mapping (zero-copy).
+    /// Returns a MappedModel that provides direct access to file contents.
+    pub fn load_mapped(&self, model_path: &ModelPath) -> Result<MappedModel, LoadError> {
+        let name = model_path
+            .as_path()
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let _guard = self.begin_load(name)?;
+        MappedModel::open(model_path)
+    }
+}
+
+/// Accept only a bare file name, never a path.
+fn plain_file_name(name: &str) -> Result<&str, LoadError> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(name),
+        _ => Err(LoadError::PathNotAllowed(PathBuf::from(name))),
+    }
+}
+
+/// Basic model metadata.
+#[derive(Debug, Clone)]
+pub struct ModelMetadata {
+    pub name: String,
+    pub size_bytes: u64,
+}
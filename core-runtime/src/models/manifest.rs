@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 use crate::engine::error::InferenceError;
+use crate::engine::ChatTemplate;
 
 /// Model metadata from manifest.json file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,14 +19,18 @@ pub struct ModelManifest {
     pub version: String,
     /// Model capabilities.
     pub capabilities: Vec<ModelCapability>,
-    /// SHA-256 hash of the model file.
-    pub sha256: String,
+    /// SHA-256 hash of the model file; checked on load when present.
+    #[serde(default)]
+    pub sha256: Option<String>,
     /// Size in bytes on disk.
     pub size_bytes: u64,
     /// Model architecture/format.
     pub architecture: ModelArchitecture,
     /// License identifier (SPDX).
     pub license: String,
+    /// Minijinja-style chat template; the default role tags when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<String>,
 }
 
 /// What a model can do.
@@ -71,7 +76,7 @@ impl ModelManifest {
         if self.model_id.is_empty() {
             return Err(InferenceError::ModelError("model_id cannot be empty".into()));
         }
-        if self.sha256.len() != 64 {
+        if self.sha256.as_ref().is_some_and(|h| h.len() != 64) {
             return Err(InferenceError::ModelError(
                 "sha256 must be 64 hex characters".into(),
             ));
@@ -81,9 +86,20 @@ impl ModelManifest {
                 "capabilities cannot be empty".into(),
             ));
         }
+        self.chat_template()?;
         Ok(())
     }
 
+    /// The template chat requests to this model are rendered with.
+    pub fn chat_template(&self) -> Result<ChatTemplate, InferenceError> {
+        match &self.chat_template {
+            Some(source) => ChatTemplate::from_jinja(source).map_err(|e| {
+                InferenceError::ModelError(format!("invalid chat_template: {}", e))
+            }),
+            None => Ok(ChatTemplate::default()),
+        }
+    }
+
     /// Check if this model supports a specific capability.
     pub fn has_capability(&self, cap: ModelCapability) -> bool {
         self.capabilities.contains(&cap)
@@ -7,7 +7,9 @@ pub mod pool;
 pub mod smart_loader;
 pub mod tier_synergy;
 
+mod circuit_breaker;
 mod drain;
+mod lifecycle;
 mod loader;
 mod preload;
 pub mod registry;
@@ -18,21 +20,30 @@ mod swap;
 pub mod history;
 pub mod persistence;
 pub mod search;
+pub mod store;
 pub mod version;
 
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitPass, CircuitState};
 pub use drain::{DrainError, FlightGuard, FlightTracker};
 pub use history::{VersionHistory, VersionHistoryEntry, VersionSource};
-pub use loader::{LoadError, MappedModel, ModelLoader, ModelMetadata, ModelPath};
+pub use lifecycle::{LifecycleError, ModelLifecycle, TokenizerSource};
+pub use loader::{LoadError, LoadGuard, MappedModel, ModelLoader, ModelMetadata, ModelPath};
 pub use manifest::{ModelArchitecture, ModelCapability, ModelManifest};
 pub use persistence::{PersistenceError, PersistedModel, RegistryPersistence, RegistryState};
 pub use pool::{ModelPool, PoolConfig, PoolError, PoolMetrics, PoolStatus, SwitchResult};
 pub use pool::ModelTier as PoolModelTier;
-pub use preload::{ModelPreloader, PreloadError, PreloadedModel};
-pub use registry::{LoadedModelInfo, LoadedModelState, ModelHandle, ModelRegistry};
+pub use preload::{
+    ModelPreloader, PreloadConfig, PreloadError, PreloadProgress, PreloadedModel,
+};
+pub use registry::{
+    LoadedModelInfo, LoadedModelState, ModelHandle, ModelRegistry, RegistryError,
+    DEFAULT_MAX_IN_FLIGHT_PER_MODEL, DEFAULT_MAX_REGISTERED_MODELS,
+};
 pub use router::{ModelRouter, RouterError};
 pub use search::{ModelQuery, ModelQueryBuilder, ModelSearchResult};
-pub use smart_loader::{LoadHint, SmartLoader, SmartLoaderConfig, SmartLoaderError, SmartLoaderMetrics, SmartLoaderStatus};
+pub use store::{FileRegistryStore, RegistryStore};
+pub use smart_loader::{LoadHint, ResidentModel, SmartLoader, SmartLoaderConfig, SmartLoaderError, SmartLoaderMetrics, SmartLoaderStatus, WarmupCallback, WarmupFuture};
 pub use smart_loader::ModelTier as SmartModelTier;
-pub use swap::{SwapError, SwapManager, SwapResult};
+pub use swap::{ModelReplacement, SwapError, SwapManager, SwapResult};
 pub use tier_synergy::{SynergyMode, SynergyResult, SynergyStatus, TierSynergy};
 pub use version::{ModelVersion, VersionRange};
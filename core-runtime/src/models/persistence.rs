@@ -3,17 +3,17 @@
 
 //! JSON-based model registry persistence.
 //!
-//! Saves and loads registry state for restart recovery.
+//! Saves and loads registry state for restart recovery. Storage is
+//! delegated to a pluggable `RegistryStore` (filesystem by default).
 
 use std::collections::HashMap;
-use std::fs::{self, File};
-use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
 use super::history::VersionHistory;
 use super::manifest::{ModelArchitecture, ModelCapability};
+use super::store::{FileRegistryStore, RegistryStore};
 use super::version::ModelVersion;
 
 /// Error type for persistence operations.
@@ -59,6 +59,18 @@ pub struct PersistedModel {
     pub auto_load: bool,
     /// Version history.
     pub history: VersionHistory,
+    /// Registry handle ID when saved (0 if never registered).
+    #[serde(default)]
+    pub handle_id: u64,
+    /// Model file size in bytes.
+    #[serde(default)]
+    pub size_bytes: u64,
+    /// Memory accounted to the model while registered.
+    #[serde(default)]
+    pub memory_bytes: usize,
+    /// Model format label (e.g. "gguf").
+    #[serde(default)]
+    pub format: String,
 }
 
 /// Complete registry state for persistence.
@@ -85,58 +97,30 @@ impl Default for RegistryState {
     }
 }
 
-/// Handles saving and loading registry state to JSON.
+/// Handles saving and loading registry state through a `RegistryStore`.
 pub struct RegistryPersistence {
-    state_path: PathBuf,
+    store: Box<dyn RegistryStore>,
 }
 
 impl RegistryPersistence {
-    /// Create a new persistence handler.
+    /// Create a new persistence handler backed by a JSON file.
     pub fn new(state_path: PathBuf) -> Self {
-        Self { state_path }
+        Self::with_store(Box::new(FileRegistryStore::new(state_path)))
     }
 
-    /// Save registry state to disk.
-    pub fn save(&self, state: &RegistryState) -> Result<(), PersistenceError> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.state_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
-        }
-
-        // Write to temp file first, then rename for atomic update
-        let temp_path = self.state_path.with_extension("tmp");
-        let file =
-            File::create(&temp_path).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
-        let writer = BufWriter::new(file);
-
-        serde_json::to_writer_pretty(writer, state)
-            .map_err(|e| PersistenceError::WriteError(e.to_string()))?;
-
-        fs::rename(&temp_path, &self.state_path)
-            .map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+    /// Create a persistence handler backed by a custom store.
+    pub fn with_store(store: Box<dyn RegistryStore>) -> Self {
+        Self { store }
+    }
 
-        Ok(())
+    /// Save registry state to the store.
+    pub fn save(&self, state: &RegistryState) -> Result<(), PersistenceError> {
+        self.store.save(state)
     }
 
-    /// Load registry state from disk.
-    ///
-    /// # Security
-    /// This method avoids TOCTOU (Time-Of-Check-Time-Of-Use) vulnerabilities
-    /// by attempting to open the file directly and handling the error,
-    /// rather than checking existence first.
+    /// Load registry state from the store.
     pub fn load(&self) -> Result<RegistryState, PersistenceError> {
-        // SECURITY: Do NOT check exists() before opening - that's a TOCTOU race.
-        // Instead, try to open directly and handle the error appropriately.
-        let file = File::open(&self.state_path).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                PersistenceError::NotFound
-            } else {
-                PersistenceError::ReadError(e.to_string())
-            }
-        })?;
-        let reader = BufReader::new(file);
-
-        serde_json::from_reader(reader).map_err(|e| PersistenceError::ParseError(e.to_string()))
+        self.store.load()
     }
 
     /// Load state or return default if not found.
@@ -144,34 +128,21 @@ impl RegistryPersistence {
         self.load().unwrap_or_default()
     }
 
-    /// Check if state file exists.
+    /// Check if persisted state exists.
     pub fn exists(&self) -> bool {
-        self.state_path.exists()
+        self.store.exists()
     }
 
-    /// Delete state file.
-    ///
-    /// # Security
-    /// This method avoids TOCTOU (Time-Of-Check-Time-Of-Use) vulnerabilities
-    /// by attempting to delete the file directly and handling the error,
-    /// rather than checking existence first.
+    /// Delete persisted state.
     pub fn delete(&self) -> Result<(), PersistenceError> {
-        // SECURITY: Do NOT check exists() before deleting - that's a TOCTOU race.
-        // Instead, try to delete directly and handle NotFound gracefully.
-        match fs::remove_file(&self.state_path) {
-            Ok(()) => Ok(()),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                // File doesn't exist - that's fine, it's already "deleted"
-                Ok(())
-            }
-            Err(e) => Err(PersistenceError::WriteError(e.to_string())),
-        }
+        self.store.delete()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn timestamp() -> u64 {
@@ -205,6 +176,10 @@ mod tests {
                 architecture: ModelArchitecture::Gguf,
                 auto_load: true,
                 history: VersionHistory::new(),
+                handle_id: 0,
+                size_bytes: 0,
+                memory_bytes: 0,
+                format: String::new(),
             },
         );
 
@@ -258,6 +233,10 @@ mod tests {
             architecture: ModelArchitecture::Gguf,
             auto_load: false,
             history: VersionHistory::new(),
+            handle_id: 0,
+            size_bytes: 0,
+            memory_bytes: 0,
+            format: String::new(),
         };
 
         let json = serde_json::to_string(&model).unwrap();
@@ -367,6 +346,10 @@ mod tests {
                     architecture: ModelArchitecture::Gguf,
                     auto_load: i % 2 == 0,
                     history: VersionHistory::new(),
+                    handle_id: 0,
+                    size_bytes: 0,
+                    memory_bytes: 0,
+                    format: String::new(),
                 },
             );
         }
@@ -417,6 +400,10 @@ mod tests {
                 architecture: arch,
                 auto_load: true,
                 history: VersionHistory::new(),
+                handle_id: 0,
+                size_bytes: 0,
+                memory_bytes: 0,
+                format: String::new(),
             };
 
             let json = serde_json::to_string(&model).unwrap();
@@ -441,6 +428,10 @@ mod tests {
             architecture: ModelArchitecture::Gguf,
             auto_load: true,
             history: VersionHistory::new(),
+            handle_id: 0,
+            size_bytes: 0,
+            memory_bytes: 0,
+            format: String::new(),
         };
 
         let json = serde_json::to_string(&model).unwrap();
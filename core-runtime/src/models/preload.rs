@@ -1,9 +1,13 @@
 //! Model preloading and validation for hot-swap operations.
 //!
 //! Preloads and validates models before they are swapped into active service.
+//! Startup preload of many models runs through a bounded pipeline so loads
+//! do not all hit disk and memory at once.
 
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
+use tokio::sync::Semaphore;
 
 use super::manifest::ModelManifest;
 use super::registry::{ModelHandle, ModelRegistry};
@@ -29,46 +33,124 @@ pub struct PreloadedModel {
     pub manifest: ModelManifest,
 }
 
+/// Preload pipeline limits, independent of runtime load concurrency.
+#[derive(Debug, Clone)]
+pub struct PreloadConfig {
+    /// Models loaded at the same time by `preload_from_manifest`.
+    pub max_concurrent_loads: usize,
+}
+
+impl Default for PreloadConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_loads: 2,
+        }
+    }
+}
+
+/// Aggregate progress of the current `preload_from_manifest` run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PreloadProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub in_flight: usize,
+}
+
+impl PreloadProgress {
+    pub fn is_done(&self) -> bool {
+        self.completed + self.failed == self.total
+    }
+}
+
 /// Preloads and validates models before swap.
 pub struct ModelPreloader {
     registry: Arc<ModelRegistry>,
+    config: PreloadConfig,
+    progress: Arc<Mutex<PreloadProgress>>,
 }
 
 impl ModelPreloader {
     pub fn new(registry: Arc<ModelRegistry>) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            config: PreloadConfig::default(),
+            progress: Arc::new(Mutex::new(PreloadProgress::default())),
+        }
+    }
+
+    pub fn with_config(mut self, config: PreloadConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Preload model: validate manifest, register in registry.
     pub async fn preload(&self, manifest: ModelManifest) -> Result<PreloadedModel, PreloadError> {
-        self.validate_manifest(&manifest)?;
+        validate_manifest(&manifest)?;
+        register(&self.registry, manifest).await
+    }
 
-        let metadata = super::loader::ModelMetadata {
-            name: manifest.name.clone(),
-            size_bytes: manifest.size_bytes,
+    /// Load and register every manifest, at most `max_concurrent_loads` at a time.
+    ///
+    /// `load` performs the actual model load. Results are returned in
+    /// manifest order; one failure does not stop the others.
+    pub async fn preload_from_manifest<F, Fut>(
+        &self,
+        manifests: Vec<ModelManifest>,
+        load: F,
+    ) -> Vec<Result<PreloadedModel, PreloadError>>
+    where
+        F: Fn(ModelManifest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), PreloadError>> + Send + 'static,
+    {
+        *lock(&self.progress) = PreloadProgress {
+            total: manifests.len(),
+            ..Default::default()
         };
 
-        let handle = self.registry.register(metadata, manifest.size_bytes as usize).await;
+        let permits = Arc::new(Semaphore::new(self.config.max_concurrent_loads.max(1)));
+        let load = Arc::new(load);
+        let tasks: Vec<_> = manifests
+            .into_iter()
+            .map(|manifest| {
+                let permits = Arc::clone(&permits);
+                let load = Arc::clone(&load);
+                let registry = Arc::clone(&self.registry);
+                let progress = Arc::clone(&self.progress);
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await.map_err(|_| {
+                        PreloadError::LoadFailed("preload pipeline closed".into())
+                    })?;
+                    lock(&progress).in_flight += 1;
 
-        Ok(PreloadedModel { handle, manifest })
-    }
+                    let result = async {
+                        validate_manifest(&manifest)?;
+                        load(manifest.clone()).await?;
+                        register(&registry, manifest).await
+                    }
+                    .await;
+                    record_outcome(&progress, result.is_ok());
+                    result
+                })
+            })
+            .collect();
 
-    /// Validate manifest fields.
-    fn validate_manifest(&self, manifest: &ModelManifest) -> Result<(), PreloadError> {
-        if manifest.model_id.is_empty() {
-            return Err(PreloadError::ManifestInvalid("model_id cannot be empty".into()));
-        }
-        if manifest.sha256.len() != 64 {
-            return Err(PreloadError::ManifestInvalid(
-                "sha256 must be 64 hex characters".into(),
-            ));
+        let mut results = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            results.push(match task.await {
+                Ok(result) => result,
+                Err(e) => {
+                    record_outcome(&self.progress, false);
+                    Err(PreloadError::LoadFailed(format!("preload task failed: {e}")))
+                }
+            });
         }
-        if manifest.capabilities.is_empty() {
-            return Err(PreloadError::ManifestInvalid(
-                "capabilities cannot be empty".into(),
-            ));
-        }
-        Ok(())
+        results
+    }
+
+    /// Snapshot of the current or most recent manifest preload.
+    pub fn progress(&self) -> PreloadProgress {
+        *lock(&self.progress)
     }
 
     /// Abort preload: unregister from registry, free resources.
@@ -76,3 +158,58 @@ impl ModelPreloader {
         self.registry.unregister(preloaded.handle).await;
     }
 }
+
+async fn register(
+    registry: &ModelRegistry,
+    manifest: ModelManifest,
+) -> Result<PreloadedModel, PreloadError> {
+    let metadata = super::loader::ModelMetadata {
+        name: manifest.name.clone(),
+        size_bytes: manifest.size_bytes,
+    };
+
+    let handle = registry
+        .register(metadata, manifest.size_bytes as usize)
+        .await
+        .map_err(|e| PreloadError::LoadFailed(e.to_string()))?;
+
+    Ok(PreloadedModel { handle, manifest })
+}
+
+fn lock(progress: &Mutex<PreloadProgress>) -> std::sync::MutexGuard<'_, PreloadProgress> {
+    progress.lock().unwrap_or_else(|p| p.into_inner())
+}
+
+fn record_outcome(progress: &Mutex<PreloadProgress>, ok: bool) {
+    let mut progress = lock(progress);
+    progress.in_flight = progress.in_flight.saturating_sub(1);
+    if ok {
+        progress.completed += 1;
+    } else {
+        progress.failed += 1;
+    }
+    tracing::info!(
+        completed = progress.completed,
+        failed = progress.failed,
+        total = progress.total,
+        "model preload progress"
+    );
+}
+
+/// Validate manifest fields.
+fn validate_manifest(manifest: &ModelManifest) -> Result<(), PreloadError> {
+    if manifest.model_id.is_empty() {
+        return Err(PreloadError::ManifestInvalid("model_id cannot be empty".into()));
+    }
+    if manifest.sha256.as_ref().is_some_and(|h| h.len() != 64) {
+        return Err(PreloadError::ManifestInvalid(
+            "sha256 must be 64 hex characters".into(),
+        ));
+    }
+    if manifest.capabilities.is_empty() {
+        return Err(PreloadError::ManifestInvalid(
+            "capabilities cannot be empty".into(),
+        ));
+    }
+    Ok(())
+}
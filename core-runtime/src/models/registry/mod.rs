@@ -0,0 +1,436 @@
+//! Model registry for tracking loaded models.
+//!
+//! Snapshotting to and restoring from `RegistryState` lives in `persist`.
+
+mod persist;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use super::loader::ModelMetadata;
+use super::persistence::PersistedModel;
+use super::search::{ModelQuery, ModelSearchResult};
+use crate::security::audit::{log_detached, AuditCategory, AuditEvent, AuditSeverity};
+use crate::telemetry::{log_security_event, SecurityEvent};
+
+/// Default maximum number of models held in the registry at once.
+pub const DEFAULT_MAX_REGISTERED_MODELS: usize = 64;
+
+/// Default concurrent requests a model accepts before it has no free capacity.
+pub const DEFAULT_MAX_IN_FLIGHT_PER_MODEL: u64 = 4;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    #[error("Model registry full: {max} models already registered")]
+    CapacityExceeded { max: usize },
+}
+
+/// Unique handle to a loaded model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModelHandle(u64);
+
+impl ModelHandle {
+    /// Create a new handle with the given ID (primarily for testing).
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Model state enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadedModelState {
+    Loading,
+    Ready,
+    Unloading,
+    Error,
+    /// Restored from a snapshot; no weights are attached yet.
+    Unloaded,
+}
+
+impl LoadedModelState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoadedModelState::Loading => "loading",
+            LoadedModelState::Ready => "ready",
+            LoadedModelState::Unloading => "unloading",
+            LoadedModelState::Error => "error",
+            LoadedModelState::Unloaded => "unloaded",
+        }
+    }
+}
+
+/// Information about a loaded model for diagnostics.
+#[derive(Debug, Clone)]
+pub struct LoadedModelInfo {
+    pub handle_id: u64,
+    pub name: String,
+    pub format: String,
+    pub size_bytes: u64,
+    pub memory_bytes: u64,
+    pub state: LoadedModelState,
+    pub request_count: u64,
+    pub total_latency_ms: f64,
+    pub loaded_at: SystemTime,
+}
+
+struct LoadedModel {
+    metadata: ModelMetadata,
+    memory_bytes: usize,
+    format: String,
+    state: LoadedModelState,
+    request_count: AtomicU64,
+    total_latency_ms: std::sync::atomic::AtomicU64,
+    loaded_at: SystemTime,
+    in_flight: AtomicU64,
+    max_in_flight: u64,
+    /// File the model was loaded from, if known.
+    path: Option<PathBuf>,
+    /// Manifest entry written back out by `snapshot` (None if not persisted).
+    persisted: Option<PersistedModel>,
+}
+
+impl LoadedModel {
+    fn new(
+        metadata: ModelMetadata,
+        memory_bytes: usize,
+        format: String,
+        persisted: Option<PersistedModel>,
+    ) -> Self {
+        Self {
+            metadata,
+            memory_bytes,
+            format,
+            state: LoadedModelState::Ready,
+            request_count: AtomicU64::new(0),
+            total_latency_ms: AtomicU64::new(0),
+            loaded_at: SystemTime::now(),
+            in_flight: AtomicU64::new(0),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT_PER_MODEL,
+            path: persisted.as_ref().map(|entry| entry.path.clone()),
+            persisted,
+        }
+    }
+}
+
+/// Thread-safe registry of loaded models.
+pub struct ModelRegistry {
+    models: Arc<RwLock<HashMap<ModelHandle, LoadedModel>>>,
+    next_id: AtomicU64,
+    max_models: usize,
+}
+
+impl ModelRegistry {
+    pub fn new() -> Self {
+        Self::with_max_models(DEFAULT_MAX_REGISTERED_MODELS)
+    }
+
+    /// Create a registry that rejects registrations beyond `max_models`.
+    pub fn with_max_models(max_models: usize) -> Self {
+        Self {
+            models: Arc::new(RwLock::new(HashMap::new())),
+            next_id: AtomicU64::new(1),
+            max_models,
+        }
+    }
+
+    /// Maximum number of models this registry will hold.
+    pub fn max_models(&self) -> usize {
+        self.max_models
+    }
+
+    /// Register a new model and return its handle.
+    pub async fn register(
+        &self,
+        metadata: ModelMetadata,
+        memory_bytes: usize,
+    ) -> Result<ModelHandle, RegistryError> {
+        self.register_with_format(metadata, memory_bytes, "unknown".to_string()).await
+    }
+
+    /// Register a new model with format info and return its handle.
+    ///
+    /// Fails with `CapacityExceeded` once `max_models` are registered.
+    pub async fn register_with_format(
+        &self,
+        metadata: ModelMetadata,
+        memory_bytes: usize,
+        format: String,
+    ) -> Result<ModelHandle, RegistryError> {
+        self.insert(LoadedModel::new(metadata, memory_bytes, format, None)).await
+    }
+
+    /// Register a model loaded from `path` and return its handle.
+    ///
+    /// The path is kept so the model survives `snapshot` and restore.
+    pub async fn register_file(
+        &self,
+        metadata: ModelMetadata,
+        memory_bytes: usize,
+        format: String,
+        path: PathBuf,
+    ) -> Result<ModelHandle, RegistryError> {
+        let mut model = LoadedModel::new(metadata, memory_bytes, format, None);
+        model.path = Some(path);
+        self.insert(model).await
+    }
+
+    async fn insert(&self, model: LoadedModel) -> Result<ModelHandle, RegistryError> {
+        // Hold the write lock across the check and insert so concurrent
+        // registrations cannot overshoot the cap
+        let mut models = self.models.write().await;
+        if models.len() >= self.max_models {
+            let max = self.max_models.to_string();
+            log_security_event(
+                SecurityEvent::ResourceLimitExceeded,
+                "Model registration rejected: registry full",
+                &[("model", &model.metadata.name), ("max_models", &max)],
+            );
+            audit_capacity_rejection(&model.metadata.name, &max);
+            return Err(RegistryError::CapacityExceeded { max: self.max_models });
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let handle = ModelHandle(id);
+        models.insert(handle, model);
+
+        Ok(handle)
+    }
+
+    /// Check if a model handle is valid.
+    pub async fn contains(&self, handle: ModelHandle) -> bool {
+        self.models.read().await.contains_key(&handle)
+    }
+
+    /// Get metadata for a loaded model.
+    pub async fn get_metadata(&self, handle: ModelHandle) -> Option<ModelMetadata> {
+        self.models.read().await.get(&handle).map(|m| m.metadata.clone())
+    }
+
+    /// Remove a model from the registry.
+    pub async fn unregister(&self, handle: ModelHandle) -> Option<usize> {
+        self.models.write().await.remove(&handle).map(|m| m.memory_bytes)
+    }
+
+    /// Total memory used by all registered models.
+    pub async fn total_memory(&self) -> usize {
+        self.models.read().await.values().map(|m| m.memory_bytes).sum()
+    }
+
+    /// Number of loaded models.
+    pub async fn count(&self) -> usize {
+        self.models.read().await.len()
+    }
+
+    /// List all loaded models with their current status.
+    pub async fn list_models(&self) -> Vec<LoadedModelInfo> {
+        let models = self.models.read().await;
+        models
+            .iter()
+            .map(|(handle, model)| LoadedModelInfo {
+                handle_id: handle.id(),
+                name: model.metadata.name.clone(),
+                format: model.format.clone(),
+                size_bytes: model.metadata.size_bytes,
+                memory_bytes: model.memory_bytes as u64,
+                state: model.state,
+                request_count: model.request_count.load(Ordering::Relaxed),
+                total_latency_ms: f64::from_bits(model.total_latency_ms.load(Ordering::Relaxed)),
+                loaded_at: model.loaded_at,
+            })
+            .collect()
+    }
+
+    /// Models matching `query`, in the stable order of
+    /// [`ModelQuery::paginate`].
+    ///
+    /// Models registered without a manifest entry are searched as the entry
+    /// `snapshot` would write for them: unversioned, with no declared
+    /// capabilities and the architecture of their format.
+    pub async fn search(&self, query: &ModelQuery) -> Vec<ModelSearchResult> {
+        let models = self.models.read().await;
+        let results = models
+            .iter()
+            .filter_map(|(handle, model)| {
+                let entry = persist::entry_for(*handle, model);
+                let name = &model.metadata.name;
+                query
+                    .matches(name, &entry.version, &entry.capabilities, &entry.architecture)
+                    .then(|| ModelSearchResult {
+                        model_id: name.clone(),
+                        handle_id: handle.id(),
+                        score: query.score(name),
+                        version: entry.version,
+                        capabilities: entry.capabilities,
+                        architecture: entry.architecture,
+                        size_bytes: model.metadata.size_bytes,
+                        is_loaded: model.state == LoadedModelState::Ready,
+                    })
+            })
+            .collect();
+        query.paginate(results)
+    }
+
+    /// Record a completed request for a model.
+    pub async fn record_request(&self, handle: ModelHandle, latency_ms: f64) {
+        if let Some(model) = self.models.read().await.get(&handle) {
+            model.request_count.fetch_add(1, Ordering::Relaxed);
+            // Atomic f64 addition via CAS loop
+            loop {
+                let old_bits = model.total_latency_ms.load(Ordering::Relaxed);
+                let old_value = f64::from_bits(old_bits);
+                let new_value = old_value + latency_ms;
+                let new_bits = new_value.to_bits();
+                if model
+                    .total_latency_ms
+                    .compare_exchange(old_bits, new_bits, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Mark a request as started on `handle`. Returns false if not registered.
+    pub async fn begin_request(&self, handle: ModelHandle) -> bool {
+        match self.models.read().await.get(&handle) {
+            Some(model) => {
+                model.in_flight.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark a request started with `begin_request` as finished.
+    pub async fn end_request(&self, handle: ModelHandle) {
+        if let Some(model) = self.models.read().await.get(&handle) {
+            let _ = model
+                .in_flight
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        }
+    }
+
+    /// Set how many concurrent requests `handle` accepts.
+    pub async fn set_max_in_flight(&self, handle: ModelHandle, max_in_flight: u64) {
+        if let Some(model) = self.models.write().await.get_mut(&handle) {
+            model.max_in_flight = max_in_flight;
+        }
+    }
+
+    /// Request slots left on `handle` (None if not registered).
+    pub async fn free_capacity(&self, handle: ModelHandle) -> Option<u64> {
+        self.models.read().await.get(&handle).map(|model| {
+            model.max_in_flight.saturating_sub(model.in_flight.load(Ordering::Relaxed))
+        })
+    }
+
+    /// Update model state.
+    pub async fn set_state(&self, handle: ModelHandle, state: LoadedModelState) {
+        if let Some(model) = self.models.write().await.get_mut(&handle) {
+            model.state = state;
+        }
+    }
+}
+
+/// Record a registration refused by the registry cap in the audit log.
+fn audit_capacity_rejection(model_name: &str, max_models: &str) {
+    if let Ok(event) = AuditEvent::builder()
+        .severity(AuditSeverity::Warning)
+        .category(AuditCategory::Security)
+        .event_type("model_registry_full")
+        .message("Model registration rejected: registry full")
+        .source("model_registry")
+        .resource(model_name)
+        .metadata("max_models", max_models)
+        .success(false)
+        .build()
+    {
+        log_detached(event);
+    }
+}
+
+impl Default for ModelRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::audit::{audit_logger, init_audit_logger, AuditConfig};
+
+    fn metadata(name: &str) -> ModelMetadata {
+        ModelMetadata { name: name.to_string(), size_bytes: 1024 }
+    }
+
+    #[tokio::test]
+    async fn test_register_up_to_cap() {
+        init_audit_logger(AuditConfig { log_to_stdout: false, ..Default::default() });
+        let registry = ModelRegistry::with_max_models(3);
+        for i in 0..3 {
+            registry.register(metadata(&format!("model-{}", i)), 100).await.unwrap();
+        }
+        assert_eq!(registry.count().await, 3);
+
+        let result = registry.register(metadata("overflow"), 100).await;
+        assert_eq!(result, Err(RegistryError::CapacityExceeded { max: 3 }));
+        assert_eq!(registry.count().await, 3);
+
+        // The rejection is audited as a security event
+        let logger = audit_logger().unwrap();
+        for _ in 0..100 {
+            let events = logger.get_events_by_category(AuditCategory::Security).await;
+            if events.iter().any(|e| {
+                e.event_type == "model_registry_full"
+                    && e.resource.as_deref() == Some("overflow")
+                    && e.metadata.get("max_models").map(String::as_str) == Some("3")
+                    && !e.success
+            }) {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("registry capacity rejection was not audited");
+    }
+
+    #[tokio::test]
+    async fn test_free_capacity_tracks_in_flight_requests() {
+        let registry = ModelRegistry::new();
+        let handle = registry.register(metadata("a"), 100).await.unwrap();
+        registry.set_max_in_flight(handle, 2).await;
+
+        assert!(registry.begin_request(handle).await);
+        assert_eq!(registry.free_capacity(handle).await, Some(1));
+        assert!(registry.begin_request(handle).await);
+        assert!(registry.begin_request(handle).await);
+        assert_eq!(registry.free_capacity(handle).await, Some(0));
+
+        for _ in 0..4 {
+            registry.end_request(handle).await;
+        }
+        assert_eq!(registry.free_capacity(handle).await, Some(2));
+        assert!(!registry.begin_request(ModelHandle::new(999)).await);
+        assert_eq!(registry.free_capacity(ModelHandle::new(999)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_unregister_frees_slot() {
+        let registry = ModelRegistry::with_max_models(1);
+        let handle = registry.register(metadata("a"), 100).await.unwrap();
+        assert!(registry.register(metadata("b"), 100).await.is_err());
+
+        registry.unregister(handle).await;
+        assert!(registry.register(metadata("b"), 100).await.is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+//! Saving the registry to a `RegistryState` and rebuilding it from one.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::sync::RwLock;
+
+use super::{LoadedModel, LoadedModelState, ModelHandle, ModelRegistry, RegistryError};
+use crate::models::loader::ModelMetadata;
+use crate::models::manifest::ModelArchitecture;
+use crate::models::persistence::{PersistedModel, RegistryState};
+use crate::models::{ModelVersion, VersionHistory};
+
+/// Architecture matching a registry format label, GGUF when unrecognized.
+fn architecture_for(format: &str) -> ModelArchitecture {
+    match format {
+        "onnx" => ModelArchitecture::Onnx,
+        "safetensors" => ModelArchitecture::SafeTensors,
+        _ => ModelArchitecture::Gguf,
+    }
+}
+
+/// Manifest entry for `model` as registered under `handle`.
+///
+/// Models registered without a manifest entry get one built from their
+/// metadata: unversioned, with no declared capabilities.
+pub(super) fn entry_for(handle: ModelHandle, model: &LoadedModel) -> PersistedModel {
+    let mut entry = model.persisted.clone().unwrap_or_else(|| PersistedModel {
+        model_id: model.metadata.name.clone(),
+        path: model.path.clone().unwrap_or_default(),
+        version: ModelVersion::new(0, 0, 0),
+        capabilities: Vec::new(),
+        architecture: architecture_for(&model.format),
+        auto_load: false,
+        history: VersionHistory::new(),
+        handle_id: 0,
+        size_bytes: model.metadata.size_bytes,
+        memory_bytes: 0,
+        format: model.format.clone(),
+    });
+    entry.handle_id = handle.id();
+    entry.memory_bytes = model.memory_bytes;
+    entry
+}
+
+impl ModelRegistry {
+    /// Register a model described by a manifest entry and return its handle.
+    ///
+    /// The entry is kept so `snapshot` writes its version, capabilities and
+    /// architecture back out.
+    pub async fn register_persisted(
+        &self,
+        entry: PersistedModel,
+        memory_bytes: usize,
+    ) -> Result<ModelHandle, RegistryError> {
+        let metadata = ModelMetadata {
+            name: entry.model_id.clone(),
+            size_bytes: entry.size_bytes,
+        };
+        let format = entry.format.clone();
+        self.insert(LoadedModel::new(metadata, memory_bytes, format, Some(entry))).await
+    }
+
+    /// Rebuild a registry from a persisted snapshot, keeping saved handles.
+    ///
+    /// Restored models are `Unloaded` until their weights are attached again.
+    /// Entries whose model file no longer exists are skipped with a warning,
+    /// as are entries beyond `max_models`. Entries without a saved handle (or
+    /// whose handle is already taken) get a fresh one.
+    pub fn from_state(max_models: usize, state: &RegistryState) -> Self {
+        let mut entries: Vec<&PersistedModel> = state.models.values().collect();
+        // Saved handles first, in order, so fresh IDs never collide with them
+        entries.sort_by_key(|e| (e.handle_id == 0, e.handle_id, e.model_id.clone()));
+
+        let mut models = HashMap::new();
+        let mut next_id = 1;
+        for entry in entries {
+            let known_path = !entry.path.as_os_str().is_empty();
+            if known_path && !entry.path.exists() {
+                tracing::warn!(
+                    model_id = %entry.model_id,
+                    path = %entry.path.display(),
+                    "skipping persisted model: file no longer exists"
+                );
+                continue;
+            }
+            if models.len() >= max_models {
+                tracing::warn!(
+                    model_id = %entry.model_id,
+                    max_models,
+                    "skipping persisted model: registry full"
+                );
+                continue;
+            }
+
+            let saved = ModelHandle(entry.handle_id);
+            let handle = if entry.handle_id != 0 && !models.contains_key(&saved) {
+                saved
+            } else {
+                ModelHandle(next_id)
+            };
+            next_id = next_id.max(handle.id() + 1);
+            models.insert(handle, restored(entry));
+        }
+
+        Self {
+            models: Arc::new(RwLock::new(models)),
+            next_id: AtomicU64::new(next_id),
+            max_models,
+        }
+    }
+
+    /// Registry state for every registered model.
+    ///
+    /// Entries are keyed by model ID; if a name is registered under several
+    /// handles, the newest registration wins.
+    pub async fn snapshot(&self) -> RegistryState {
+        let models = self.models.read().await;
+        let mut state = RegistryState {
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            ..RegistryState::default()
+        };
+        let mut handles: Vec<&ModelHandle> = models.keys().collect();
+        handles.sort_by_key(|handle| handle.id());
+        for handle in handles {
+            let entry = entry_for(*handle, &models[handle]);
+            state.models.insert(entry.model_id.clone(), entry);
+        }
+        state
+    }
+}
+
+/// Registry record for a snapshot `entry`, awaiting its weights.
+fn restored(entry: &PersistedModel) -> LoadedModel {
+    let metadata = ModelMetadata {
+        name: entry.model_id.clone(),
+        size_bytes: entry.size_bytes,
+    };
+    let mut model = LoadedModel::new(
+        metadata,
+        entry.memory_bytes,
+        entry.format.clone(),
+        Some(entry.clone()),
+    );
+    model.state = LoadedModelState::Unloaded;
+    model
+}
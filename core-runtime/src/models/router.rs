@@ -1,18 +1,33 @@
 //! Atomic routing table for model_id → ModelHandle resolution.
 //!
 //! Provides thread-safe routing operations for zero-downtime model swaps.
+//! Routes added from a manifest also record the model's capabilities, so
+//! callers can ask for any model that can do a task instead of a model ID.
+//! A running A/B experiment redirects a share of sessions asking for its
+//! control model to the variant model. A per-model circuit breaker stops
+//! requests reaching a model that keeps failing.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
-use super::registry::ModelHandle;
+use super::circuit_breaker::{CircuitBreakerConfig, CircuitBreakers, CircuitPass, CircuitState};
+use super::manifest::{ModelCapability, ModelManifest};
+use crate::ab_testing::traffic::TrafficError;
+use crate::ab_testing::{AbExperiment, ActiveExperiment, ExperimentAssignment};
+use super::registry::{ModelHandle, ModelRegistry};
 
 #[derive(Error, Debug)]
 pub enum RouterError {
     #[error("Route already exists for model: {0}")]
     RouteExists(String),
+
+    #[error("No routed model supports capability: {0:?}")]
+    NoCapableModel(ModelCapability),
+
+    #[error("Circuit open for model: {0}")]
+    CircuitOpen(String),
 }
 
 /// Atomic routing table: model_id → ModelHandle.
@@ -21,15 +36,35 @@ pub enum RouterError {
 /// Supports atomic swap for zero-downtime model replacement.
 pub struct ModelRouter {
     routes: Arc<RwLock<HashMap<String, ModelHandle>>>,
+    capabilities: Arc<RwLock<HashMap<String, Vec<ModelCapability>>>>,
+    registry: Option<Arc<ModelRegistry>>,
+    experiment: Arc<RwLock<Option<Arc<ActiveExperiment>>>>,
+    circuits: CircuitBreakers,
 }
 
 impl ModelRouter {
     pub fn new() -> Self {
         Self {
             routes: Arc::new(RwLock::new(HashMap::new())),
+            capabilities: Arc::new(RwLock::new(HashMap::new())),
+            registry: None,
+            experiment: Arc::new(RwLock::new(None)),
+            circuits: CircuitBreakers::new(CircuitBreakerConfig::default()),
         }
     }
 
+    /// Open per-model circuit breakers according to `config`.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuits = CircuitBreakers::new(config);
+        self
+    }
+
+    /// Rank capability matches by the free capacity `registry` reports.
+    pub fn with_registry(mut self, registry: Arc<ModelRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
     /// Resolve model_id to handle (returns None if not routed).
     pub async fn resolve(&self, model_id: &str) -> Option<ModelHandle> {
         self.routes.read().await.get(model_id).copied()
@@ -47,15 +82,124 @@ impl ModelRouter {
         Ok(())
     }
 
+    /// Add a route for `manifest.model_id` and record its capabilities.
+    pub async fn add_manifest_route(
+        &self,
+        manifest: &ModelManifest,
+        handle: ModelHandle,
+    ) -> Result<(), RouterError> {
+        self.add_route(&manifest.model_id, handle).await?;
+        self.set_capabilities(&manifest.model_id, manifest.capabilities.clone())
+            .await;
+        Ok(())
+    }
+
+    /// Replace the capabilities recorded for `model_id`.
+    pub async fn set_capabilities(&self, model_id: &str, capabilities: Vec<ModelCapability>) {
+        self.capabilities
+            .write()
+            .await
+            .insert(model_id.to_string(), capabilities);
+    }
+
+    /// Route to any model declaring `cap`.
+    ///
+    /// With a registry attached, the match with the most free capacity wins
+    /// and routes to unregistered handles are skipped. Ties go to the
+    /// lowest model ID.
+    pub async fn route_by_capability(
+        &self,
+        cap: ModelCapability,
+    ) -> Result<ModelHandle, RouterError> {
+        let mut candidates: Vec<(String, ModelHandle)> = {
+            let routes = self.routes.read().await;
+            let capabilities = self.capabilities.read().await;
+            routes
+                .iter()
+                .filter(|(id, _)| capabilities.get(*id).is_some_and(|caps| caps.contains(&cap)))
+                .map(|(id, handle)| (id.clone(), *handle))
+                .collect()
+        };
+        candidates.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        let mut best: Option<(u64, ModelHandle)> = None;
+        for (_, handle) in candidates {
+            let free = match &self.registry {
+                Some(registry) => match registry.free_capacity(handle).await {
+                    Some(free) => free,
+                    None => continue,
+                },
+                None => 0,
+            };
+            if best.is_none_or(|(most, _)| free > most) {
+                best = Some((free, handle));
+            }
+        }
+        best.map(|(_, handle)| handle)
+            .ok_or(RouterError::NoCapableModel(cap))
+    }
+
+    /// Start `experiment`, replacing a running one and its results.
+    /// `None` stops the running experiment.
+    pub async fn set_experiment(
+        &self,
+        experiment: Option<AbExperiment>,
+    ) -> Result<Option<Arc<ActiveExperiment>>, TrafficError> {
+        let active = experiment.map(ActiveExperiment::new).transpose()?.map(Arc::new);
+        *self.experiment.write().await = active.clone();
+        Ok(active)
+    }
+
+    /// The running experiment, if any.
+    pub async fn experiment(&self) -> Option<Arc<ActiveExperiment>> {
+        self.experiment.read().await.clone()
+    }
+
+    /// Assign a request for `model_id` to an arm of the running experiment,
+    /// the same arm for every request from `client_id`.
+    ///
+    /// `None` when no experiment runs on `model_id`; the request is served
+    /// as asked.
+    pub async fn assign_experiment(
+        &self,
+        model_id: &str,
+        client_id: Option<&str>,
+    ) -> Option<ExperimentAssignment> {
+        self.experiment().await?.assign(model_id, client_id)
+    }
+
+    /// Let one request through to `model_id`, unless its circuit breaker
+    /// is open. Record the request's outcome on the returned pass.
+    pub fn admit(&self, model_id: &str) -> Result<CircuitPass, RouterError> {
+        self.circuits
+            .admit(model_id)
+            .ok_or_else(|| RouterError::CircuitOpen(model_id.to_string()))
+    }
+
+    /// Current circuit breaker state for `model_id`.
+    pub fn circuit_state(&self, model_id: &str) -> CircuitState {
+        self.circuits.state(model_id)
+    }
+
+    /// Start `model_id` over with a closed breaker, e.g. after it was
+    /// replaced or unloaded.
+    pub fn reset_circuit(&self, model_id: &str) {
+        self.circuits.reset(model_id);
+    }
+
     /// Atomically swap route to new handle.
     /// Returns the old handle if route existed, None if new route created.
+    /// The new model starts with a closed circuit breaker.
     pub async fn swap_route(&self, model_id: &str, new_handle: ModelHandle) -> Option<ModelHandle> {
         let mut routes = self.routes.write().await;
+        self.circuits.reset(model_id);
         routes.insert(model_id.to_string(), new_handle)
     }
 
     /// Remove route (returns old handle if existed).
     pub async fn remove_route(&self, model_id: &str) -> Option<ModelHandle> {
+        self.capabilities.write().await.remove(model_id);
+        self.circuits.reset(model_id);
         self.routes.write().await.remove(model_id)
     }
 
@@ -85,3 +229,113 @@ impl Default for ModelRouter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::loader::ModelMetadata;
+    use crate::models::ModelArchitecture;
+
+    fn manifest(model_id: &str, capabilities: Vec<ModelCapability>) -> ModelManifest {
+        ModelManifest {
+            model_id: model_id.to_string(),
+            name: model_id.to_string(),
+            version: "1.0.0".to_string(),
+            capabilities,
+            sha256: Some("a".repeat(64)),
+            size_bytes: 1024,
+            architecture: ModelArchitecture::Onnx,
+            license: "MIT".to_string(),
+            chat_template: None,
+        }
+    }
+
+    async fn register(
+        router: &ModelRouter,
+        registry: &ModelRegistry,
+        model_id: &str,
+        capabilities: Vec<ModelCapability>,
+    ) -> ModelHandle {
+        let metadata = ModelMetadata { name: model_id.to_string(), size_bytes: 1024 };
+        let handle = registry.register(metadata, 1024).await.unwrap();
+        router
+            .add_manifest_route(&manifest(model_id, capabilities), handle)
+            .await
+            .unwrap();
+        handle
+    }
+
+    #[tokio::test]
+    async fn test_experiment_assigns_control_model_requests() {
+        let router = ModelRouter::new();
+        assert!(router.assign_experiment("chat", Some("s1")).await.is_none());
+
+        let experiment = AbExperiment {
+            control_model: "chat".into(),
+            variant_model: "chat-v2".into(),
+            variant_percent: 100,
+        };
+        router.set_experiment(Some(experiment)).await.unwrap();
+        let assignment = router.assign_experiment("chat", Some("s1")).await.unwrap();
+        assert_eq!(assignment.model_id, "chat-v2");
+        assert!(router.assign_experiment("embed", Some("s1")).await.is_none());
+
+        router.set_experiment(None).await.unwrap();
+        assert!(router.experiment().await.is_none());
+        assert!(router.assign_experiment("chat", Some("s1")).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_route_by_capability_without_candidates() {
+        let registry = Arc::new(ModelRegistry::new());
+        let router = ModelRouter::new().with_registry(registry.clone());
+        register(&router, &registry, "gen", vec![ModelCapability::TextGeneration]).await;
+        router.add_route("untracked", ModelHandle::new(99)).await.unwrap();
+
+        let err = router.route_by_capability(ModelCapability::Embedding).await.unwrap_err();
+        assert!(matches!(err, RouterError::NoCapableModel(ModelCapability::Embedding)));
+    }
+
+    #[tokio::test]
+    async fn test_route_by_capability_single_candidate() {
+        let registry = Arc::new(ModelRegistry::new());
+        let router = ModelRouter::new().with_registry(registry.clone());
+        register(&router, &registry, "gen", vec![ModelCapability::TextGeneration]).await;
+        let embed = register(
+            &router,
+            &registry,
+            "embed",
+            vec![ModelCapability::TextClassification, ModelCapability::Embedding],
+        )
+        .await;
+
+        assert_eq!(router.route_by_capability(ModelCapability::Embedding).await.unwrap(), embed);
+
+        router.remove_route("embed").await;
+        assert!(router.route_by_capability(ModelCapability::Embedding).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_by_capability_prefers_most_free_capacity() {
+        let registry = Arc::new(ModelRegistry::new());
+        let router = ModelRouter::new().with_registry(registry.clone());
+        let caps = vec![ModelCapability::Embedding];
+        let a = register(&router, &registry, "embed-a", caps.clone()).await;
+        let b = register(&router, &registry, "embed-b", caps.clone()).await;
+
+        // Equal capacity: lowest model ID wins
+        assert_eq!(router.route_by_capability(ModelCapability::Embedding).await.unwrap(), a);
+
+        registry.begin_request(a).await;
+        assert_eq!(router.route_by_capability(ModelCapability::Embedding).await.unwrap(), b);
+
+        // A larger model with more headroom beats a less busy small one
+        registry.set_max_in_flight(a, 10).await;
+        registry.begin_request(b).await;
+        assert_eq!(router.route_by_capability(ModelCapability::Embedding).await.unwrap(), a);
+
+        // Routes whose handle left the registry are skipped
+        registry.unregister(a).await;
+        assert_eq!(router.route_by_capability(ModelCapability::Embedding).await.unwrap(), b);
+    }
+}
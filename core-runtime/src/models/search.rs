@@ -4,6 +4,8 @@
 //! Model search and query API.
 //!
 //! Provides a builder-pattern query interface for searching the model registry.
+//! Results are ordered by score (best first), then model_id, then handle, so
+//! the same query over the same registry always returns the same page.
 
 use serde::{Deserialize, Serialize};
 
@@ -25,7 +27,7 @@ pub struct ModelQuery {
     pub name_pattern: Option<String>,
     /// Maximum results to return.
     pub limit: Option<usize>,
-    /// Skip first N results.
+    /// Skip first N results (applied before `limit`, after ordering).
     pub offset: Option<usize>,
 }
 
@@ -70,6 +72,38 @@ impl ModelQuery {
         }
         true
     }
+
+    /// Relevance of a matching `name`: 3 for an exact name match, 2 for a
+    /// prefix, 1 for any other substring, 0 when there is no name pattern.
+    pub fn score(&self, name: &str) -> u32 {
+        let Some(pattern) = &self.name_pattern else {
+            return 0;
+        };
+        let (name, pattern) = (name.to_lowercase(), pattern.to_lowercase());
+        if name == pattern {
+            3
+        } else if name.starts_with(&pattern) {
+            2
+        } else {
+            u32::from(name.contains(&pattern))
+        }
+    }
+
+    /// Order `results` by (score desc, model_id asc, handle asc), then
+    /// apply `offset` and `limit`.
+    pub fn paginate(&self, mut results: Vec<ModelSearchResult>) -> Vec<ModelSearchResult> {
+        results.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.model_id.cmp(&b.model_id))
+                .then_with(|| a.handle_id.cmp(&b.handle_id))
+        });
+        results
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
 }
 
 /// Builder for constructing model queries.
@@ -127,10 +161,14 @@ impl ModelQueryBuilder {
 }
 
 /// Search result containing matched model info.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModelSearchResult {
     /// Model identifier.
     pub model_id: String,
+    /// Registry handle ID; breaks ties between equally named entries.
+    pub handle_id: u64,
+    /// Relevance from [`ModelQuery::score`]; higher sorts first.
+    pub score: u32,
     /// Model version.
     pub version: ModelVersion,
     /// Model capabilities.
@@ -347,6 +385,8 @@ mod tests {
     fn test_model_search_result_serialization() {
         let result = ModelSearchResult {
             model_id: "test-model".to_string(),
+            handle_id: 1,
+            score: 0,
             version: ModelVersion::new(1, 0, 0),
             capabilities: vec![ModelCapability::TextGeneration],
             architecture: ModelArchitecture::Gguf,
@@ -392,4 +432,51 @@ mod tests {
 
         assert!(!query.matches("model", &version, &caps, &arch));
     }
+
+    fn result(model_id: &str, handle_id: u64, score: u32) -> ModelSearchResult {
+        ModelSearchResult {
+            model_id: model_id.to_string(),
+            handle_id,
+            score,
+            version: ModelVersion::new(1, 0, 0),
+            capabilities: vec![ModelCapability::TextGeneration],
+            architecture: ModelArchitecture::Gguf,
+            size_bytes: 0,
+            is_loaded: true,
+        }
+    }
+
+    #[test]
+    fn test_score_prefers_exact_then_prefix_matches() {
+        let query = ModelQuery::builder().name_contains("Phi").build();
+
+        assert_eq!(query.score("phi"), 3);
+        assert_eq!(query.score("phi-3-mini"), 2);
+        assert_eq!(query.score("tiny-phi"), 1);
+        assert_eq!(query.score("llama"), 0);
+        assert_eq!(ModelQuery::new().score("phi"), 0);
+    }
+
+    #[test]
+    fn test_paginate_orders_ties_by_name_then_handle() {
+        let query = ModelQuery::builder().offset(1).limit(3).build();
+        let results = vec![
+            result("beta", 4, 1),
+            result("alpha", 9, 1),
+            result("zeta", 7, 2),
+            result("alpha", 2, 1),
+            result("gamma", 1, 0),
+        ];
+
+        let page: Vec<(String, u64)> = query
+            .paginate(results)
+            .into_iter()
+            .map(|r| (r.model_id, r.handle_id))
+            .collect();
+
+        assert_eq!(
+            page,
+            [("alpha".into(), 2), ("alpha".into(), 9), ("beta".into(), 4)]
+        );
+    }
 }
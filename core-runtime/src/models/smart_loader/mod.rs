@@ -0,0 +1,249 @@
+//! Smart model loader with semantic hints and adaptive caching.
+//!
+//! Zero idle overhead with intelligent prefetching based on usage patterns.
+//! Uses OS-level page cache for automatic memory management.
+//!
+//! The runtime builds one from `RuntimeConfig::smart_loader`, backed by its
+//! model loader and inference engine (`serving`), and preloads restored
+//! models through it at startup.
+
+mod prefetch;
+mod residency;
+mod serving;
+mod types;
+mod warmup;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{RwLock, Semaphore};
+
+use super::registry::ModelHandle;
+use crate::health::HealthChecker;
+use warmup::AutoWarmup;
+
+pub use serving::{engine_load_callback, engine_unload_callback, engine_warmup_callback};
+pub use types::{
+    LoadCallback, LoadFuture, LoadHint, LoadState, ModelTier, ResidentModel, SmartLoaderConfig,
+    SmartLoaderError, SmartLoaderMetrics, SmartLoaderStatus, UnloadCallback, UnloadFuture,
+    WarmupCallback, WarmupFuture,
+};
+use types::ModelEntry;
+
+/// Smart model loader with semantic hints.
+pub struct SmartLoader {
+    /// Configuration for auto-unload timing and prediction
+    config: SmartLoaderConfig,
+    models: Arc<RwLock<HashMap<String, ModelEntry>>>,
+    active_tier: Arc<RwLock<Option<ModelTier>>>,
+    metrics: Arc<RwLock<SmartLoaderMetrics>>,
+    load_semaphore: Semaphore,
+    load_callback: Option<Arc<LoadCallback>>,
+    unload_callback: Option<Arc<UnloadCallback>>,
+    warmup: AutoWarmup,
+    predicted_next: Arc<RwLock<Option<String>>>,
+}
+
+impl SmartLoader {
+    pub fn new(config: SmartLoaderConfig) -> Self {
+        let max_loads = config.max_concurrent_loads;
+        let warmup = AutoWarmup {
+            tokens: config.warmup_tokens,
+            callback: None,
+            health: None,
+        };
+        Self {
+            config,
+            models: Arc::new(RwLock::new(HashMap::new())),
+            active_tier: Arc::new(RwLock::new(None)),
+            metrics: Arc::new(RwLock::new(SmartLoaderMetrics::default())),
+            load_semaphore: Semaphore::new(max_loads),
+            load_callback: None,
+            unload_callback: None,
+            warmup,
+            predicted_next: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Set the callback for actual model loading.
+    pub fn set_load_callback(&mut self, callback: LoadCallback) {
+        self.load_callback = Some(Arc::new(callback));
+    }
+
+    /// Set the callback releasing a model's weights when it is unloaded.
+    pub fn set_unload_callback(&mut self, callback: UnloadCallback) {
+        self.unload_callback = Some(Arc::new(callback));
+    }
+
+    /// Set the callback run after each load when `warmup_tokens` > 0.
+    pub fn set_warmup_callback(&mut self, callback: WarmupCallback) {
+        self.warmup.callback = Some(Arc::new(callback));
+    }
+
+    /// Report loads with pending auto-warmup to `health`, keeping readiness
+    /// at `models_loading` until they finish.
+    pub fn set_health_checker(&mut self, health: Arc<HealthChecker>) {
+        self.warmup.health = Some(health);
+    }
+
+    /// Register a model (zero overhead).
+    pub async fn register(
+        &self,
+        model_id: String,
+        path: PathBuf,
+        tier: ModelTier,
+    ) -> Result<(), SmartLoaderError> {
+        let size_bytes = std::fs::metadata(&path)
+            .map_err(|e| SmartLoaderError::LoadFailed(e.to_string()))?
+            .len();
+
+        self.models.write().await.insert(
+            model_id,
+            ModelEntry {
+                path,
+                tier,
+                size_bytes,
+                state: LoadState::Registered,
+                handle: None,
+                last_used: None,
+                use_count: 0,
+                load_time_ms: None,
+                in_flight: Arc::new(AtomicUsize::new(0)),
+                evicted: false,
+                pinned: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Pin or unpin a model. Pinned models are skipped by auto-unload even when
+    /// least recently used; explicit `unload` still applies.
+    pub async fn set_pinned(&self, model_id: &str, pinned: bool) -> Result<(), SmartLoaderError> {
+        let mut models = self.models.write().await;
+        let entry = models
+            .get_mut(model_id)
+            .ok_or_else(|| SmartLoaderError::NotRegistered(model_id.to_string()))?;
+        entry.pinned = pinned;
+        Ok(())
+    }
+
+    /// Get a model, loading if necessary.
+    pub async fn get(&self, model_id: &str) -> Result<ModelHandle, SmartLoaderError> {
+        let start = Instant::now();
+
+        // Check prediction accuracy
+        {
+            let predicted = self.predicted_next.read().await.clone();
+            if predicted.as_ref() == Some(&model_id.to_string()) {
+                self.metrics.write().await.predictions_correct += 1;
+            }
+        }
+
+        // Check if already loaded
+        {
+            let mut models = self.models.write().await;
+            if let Some(entry) = models.get_mut(model_id) {
+                if entry.state == LoadState::Ready {
+                    if let Some(handle) = entry.handle {
+                        entry.last_used = Some(Instant::now());
+                        entry.use_count += 1;
+                        *self.active_tier.write().await = Some(entry.tier);
+
+                        let load_ms = start.elapsed().as_millis() as f64;
+                        let mut metrics = self.metrics.write().await;
+                        metrics.cache_hits += 1;
+                        let n = metrics.cache_hits as f64;
+                        metrics.avg_cache_hit_ms =
+                            (metrics.avg_cache_hit_ms * (n - 1.0) + load_ms) / n;
+
+                        return Ok(handle);
+                    }
+                }
+            }
+        }
+
+        // Need to load
+        self.load_sync(model_id).await?;
+
+        let models = self.models.read().await;
+        let entry = models
+            .get(model_id)
+            .ok_or_else(|| SmartLoaderError::NotRegistered(model_id.to_string()))?;
+
+        let handle = entry
+            .handle
+            .ok_or_else(|| SmartLoaderError::LoadFailed("Load did not produce handle".into()))?;
+
+        let load_ms = start.elapsed().as_millis() as f64;
+        let mut metrics = self.metrics.write().await;
+        metrics.total_loads += 1;
+        let n = metrics.total_loads as f64;
+        metrics.avg_load_ms = (metrics.avg_load_ms * (n - 1.0) + load_ms) / n;
+
+        Ok(handle)
+    }
+
+    /// Get a model and pin it against auto-unload while the guard is held.
+    pub async fn acquire(&self, model_id: &str) -> Result<ResidentModel, SmartLoaderError> {
+        loop {
+            let handle = self.get(model_id).await?;
+            let models = self.models.read().await;
+            if let Some(entry) = models.get(model_id) {
+                // Re-check under the lock: the model may have been evicted
+                if entry.state == LoadState::Ready && entry.handle == Some(handle) {
+                    entry.in_flight.fetch_add(1, Ordering::SeqCst);
+                    return Ok(ResidentModel {
+                        handle,
+                        in_flight: Arc::clone(&entry.in_flight),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Unload a model.
+    pub async fn unload(&self, model_id: &str) {
+        let handle = match self.models.write().await.get_mut(model_id) {
+            Some(entry) => {
+                entry.state = LoadState::Registered;
+                entry.handle.take()
+            }
+            None => None,
+        };
+        self.release(model_id, handle).await;
+    }
+
+    /// Get metrics.
+    pub async fn metrics(&self) -> SmartLoaderMetrics {
+        self.metrics.read().await.clone()
+    }
+
+    /// Get current status.
+    pub async fn status(&self) -> SmartLoaderStatus {
+        let models = self.models.read().await;
+        let active_tier = self.active_tier.read().await.clone();
+
+        let loaded: Vec<_> = models
+            .iter()
+            .filter(|(_, m)| m.state == LoadState::Ready)
+            .map(|(id, m)| (id.clone(), m.tier))
+            .collect();
+
+        let total_loaded_bytes: u64 = models
+            .values()
+            .filter(|m| m.state == LoadState::Ready)
+            .map(|m| m.size_bytes)
+            .sum();
+
+        SmartLoaderStatus {
+            registered_count: models.len(),
+            loaded_count: loaded.len(),
+            loaded_models: loaded,
+            active_tier,
+            total_loaded_bytes,
+            predicted_next: self.predicted_next.read().await.clone(),
+        }
+    }
+}
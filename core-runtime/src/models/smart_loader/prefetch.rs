@@ -0,0 +1,140 @@
+//! Semantic hints and speculative background preloads.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+
+use super::residency::{load_weights, resident_count};
+use super::types::{LoadCallback, LoadHint, LoadState, ModelEntry, ModelTier};
+use super::warmup::AutoWarmup;
+use super::SmartLoader;
+
+type Models = Arc<RwLock<HashMap<String, ModelEntry>>>;
+
+impl SmartLoader {
+    /// Provide a semantic hint about upcoming usage.
+    pub async fn hint(&self, hint: LoadHint) {
+        let prediction = match hint {
+            LoadHint::QuickQuery => self.find_model_by_tier(ModelTier::Light).await,
+            LoadHint::ComplexTask => self.find_model_by_tier(ModelTier::Quality).await,
+            LoadHint::BatchIncoming { count } if count > 10 => {
+                self.find_model_by_tier(ModelTier::Quality).await
+            }
+            LoadHint::BatchIncoming { .. } => {
+                self.find_model_by_tier(ModelTier::Balanced).await
+            }
+            LoadHint::UserIdle => {
+                // Preload the most likely next model
+                self.predict_next().await
+            }
+            LoadHint::PreferModel { tier } => self.find_model_by_tier(tier).await,
+        };
+
+        if let Some(model_id) = prediction {
+            *self.predicted_next.write().await = Some(model_id.clone());
+            self.metrics.write().await.predictions_made += 1;
+
+            // Start background preload
+            self.preload_background(&model_id);
+        }
+    }
+
+    /// Find a registered model by tier.
+    async fn find_model_by_tier(&self, tier: ModelTier) -> Option<String> {
+        self.models
+            .read()
+            .await
+            .iter()
+            .find(|(_, m)| m.tier == tier)
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Predict most likely next model based on usage patterns.
+    async fn predict_next(&self) -> Option<String> {
+        let models = self.models.read().await;
+
+        // Simple heuristic: predict based on time of day and usage patterns
+        // In production, this could use ML-based prediction
+
+        // For now, predict the most frequently used model that isn't active
+        let active = *self.active_tier.read().await;
+
+        models
+            .iter()
+            .filter(|(_, m)| Some(m.tier) != active)
+            .max_by_key(|(_, m)| m.use_count)
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Preload a model in the background.
+    fn preload_background(&self, model_id: &str) {
+        // Check if we can acquire a permit (non-blocking)
+        if self.load_semaphore.available_permits() == 0 {
+            return; // Another load in progress
+        }
+        // Note: We don't actually need the permit for background load
+        // since we're just touching pages. The semaphore check above
+        // prevents too many concurrent background loads.
+        tokio::spawn(preload(
+            Arc::clone(&self.models),
+            model_id.to_string(),
+            self.load_callback.clone(),
+            self.warmup.clone(),
+            self.config.max_resident_models,
+        ));
+    }
+}
+
+async fn preload(
+    models: Models,
+    model_id: String,
+    callback: Option<Arc<LoadCallback>>,
+    warmup: AutoWarmup,
+    max_resident: Option<usize>,
+) {
+    let Some(path) = claim(&models, &model_id, max_resident).await else {
+        return;
+    };
+    let _warming = warmup.guard();
+
+    let start = Instant::now();
+    let result = load_weights(callback.as_deref(), &model_id, &path).await;
+    let load_ms = start.elapsed().as_millis() as u64;
+    if let Ok(handle) = result {
+        warmup.run(&model_id, handle).await;
+    }
+
+    let mut models = models.write().await;
+    if let Some(entry) = models.get_mut(&model_id) {
+        match result {
+            Ok(handle) => {
+                entry.state = LoadState::Ready;
+                entry.handle = Some(handle);
+                entry.load_time_ms = Some(load_ms);
+            }
+            Err(_) => {
+                entry.state = LoadState::Failed;
+            }
+        }
+    }
+}
+
+/// Mark `model_id` as loading and return its path, unless it is already
+/// loaded or loading or the resident cap is reached.
+async fn claim(models: &Models, model_id: &str, max_resident: Option<usize>) -> Option<PathBuf> {
+    let mut models = models.write().await;
+    // Speculative loads never evict; skip when at the resident cap
+    if let Some(max) = max_resident {
+        if resident_count(&models) >= max {
+            return None;
+        }
+    }
+    let entry = match models.get_mut(model_id) {
+        Some(e) if e.state == LoadState::Registered => e,
+        _ => return None, // Already loaded or loading
+    };
+    entry.state = LoadState::Loading;
+    Some(entry.path.clone())
+}
@@ -0,0 +1,203 @@
+//! Loading models on demand and auto-unloading to respect the resident cap.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::types::{LoadCallback, LoadState, ModelEntry, SmartLoaderError};
+use super::SmartLoader;
+use crate::models::registry::ModelHandle;
+
+impl SmartLoader {
+    /// Synchronous load (blocks until complete).
+    pub(super) async fn load_sync(&self, model_id: &str) -> Result<(), SmartLoaderError> {
+        let _permit = self.load_semaphore.acquire().await.unwrap();
+
+        match self.entry_state(model_id).await? {
+            LoadState::Ready => return Ok(()), // Loaded while waiting for permit
+            LoadState::Unloading => return Err(SmartLoaderError::Unloading(model_id.to_string())),
+            _ => {}
+        }
+
+        self.make_room(model_id).await?;
+
+        let path = {
+            let mut models = self.models.write().await;
+            let entry = models
+                .get_mut(model_id)
+                .ok_or_else(|| SmartLoaderError::NotRegistered(model_id.to_string()))?;
+
+            if entry.state == LoadState::Ready {
+                return Ok(());
+            }
+
+            entry.state = LoadState::Loading;
+            entry.path.clone()
+        };
+        let _warming = self.warmup.guard();
+
+        let start = Instant::now();
+        let result = load_weights(self.load_callback.as_deref(), model_id, &path).await;
+        let load_ms = start.elapsed().as_millis() as u64;
+        // Still `Loading`, so the model is not handed out before it is warm
+        if let Ok(handle) = result {
+            self.warmup.run(model_id, handle).await;
+        }
+        self.finish_load(model_id, result, load_ms).await
+    }
+
+    async fn entry_state(&self, model_id: &str) -> Result<LoadState, SmartLoaderError> {
+        let models = self.models.read().await;
+        models
+            .get(model_id)
+            .map(|entry| entry.state)
+            .ok_or_else(|| SmartLoaderError::NotRegistered(model_id.to_string()))
+    }
+
+    /// Record the outcome of loading `model_id`.
+    async fn finish_load(
+        &self,
+        model_id: &str,
+        result: Result<ModelHandle, String>,
+        load_ms: u64,
+    ) -> Result<(), SmartLoaderError> {
+        let mut models = self.models.write().await;
+        let entry = models
+            .get_mut(model_id)
+            .ok_or_else(|| SmartLoaderError::NotRegistered(model_id.to_string()))?;
+
+        match result {
+            Ok(handle) => {
+                entry.state = LoadState::Ready;
+                entry.handle = Some(handle);
+                entry.load_time_ms = Some(load_ms);
+                entry.last_used = Some(Instant::now());
+                entry.use_count += 1;
+                *self.active_tier.write().await = Some(entry.tier);
+                if std::mem::take(&mut entry.evicted) {
+                    self.metrics.write().await.reloads += 1;
+                    tracing::info!(model_id, "Reloaded auto-unloaded model");
+                }
+                Ok(())
+            }
+            Err(e) => {
+                entry.state = LoadState::Failed;
+                Err(SmartLoaderError::LoadFailed(e))
+            }
+        }
+    }
+
+    /// Auto-unload least recently used models until there is room for one more.
+    async fn make_room(&self, loading: &str) -> Result<(), SmartLoaderError> {
+        let Some(max) = self.config.max_resident_models else {
+            return Ok(());
+        };
+        // Evicting would leave whatever the load callback attached in place
+        if self.load_callback.is_some() && self.unload_callback.is_none() {
+            return Err(SmartLoaderError::NoUnloadCallback(loading.to_string()));
+        }
+
+        loop {
+            let Some((victim, in_flight)) = self.pick_victim(loading, max).await? else {
+                return Ok(());
+            };
+
+            let drained = tokio::time::timeout(self.config.drain_timeout, async {
+                while in_flight.load(Ordering::SeqCst) > 0 {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .is_ok();
+
+            let mut models = self.models.write().await;
+            let Some(entry) = models.get_mut(&victim) else {
+                continue;
+            };
+            if !drained {
+                entry.state = LoadState::Ready;
+                return Err(SmartLoaderError::DrainTimeout(victim));
+            }
+            entry.state = LoadState::Registered;
+            let handle = entry.handle.take();
+            entry.evicted = true;
+            drop(models);
+            self.release(&victim, handle).await;
+
+            self.metrics.write().await.auto_unloads += 1;
+            tracing::info!(
+                model_id = %victim,
+                loading,
+                "Auto-unloaded model to respect resident limit"
+            );
+        }
+    }
+
+    /// Hand the weights `model_id` held under `handle` to the unload callback.
+    pub(super) async fn release(&self, model_id: &str, handle: Option<ModelHandle>) {
+        if let (Some(callback), Some(handle)) = (&self.unload_callback, handle) {
+            callback(model_id, handle).await;
+        }
+    }
+
+    /// Least recently used unpinned model to evict so `loading` fits under
+    /// `max`, marked `Unloading`; `None` once there is room.
+    async fn pick_victim(
+        &self,
+        loading: &str,
+        max: usize,
+    ) -> Result<Option<(String, Arc<AtomicUsize>)>, SmartLoaderError> {
+        let mut models = self.models.write().await;
+        if resident_count(&models) < max.max(1) {
+            return Ok(None);
+        }
+        let candidates = || {
+            models
+                .iter()
+                .filter(|(id, m)| m.state == LoadState::Ready && id.as_str() != loading)
+        };
+        let victim = candidates()
+            .filter(|(_, m)| !m.pinned)
+            .min_by_key(|(_, m)| m.last_used)
+            .map(|(id, _)| id.clone());
+        let Some(victim) = victim else {
+            if candidates().next().is_some() {
+                return Err(SmartLoaderError::AllSlotsPinned(loading.to_string()));
+            }
+            return Ok(None);
+        };
+        // Stop handing out the victim while it drains
+        let entry = models.get_mut(&victim).unwrap();
+        entry.state = LoadState::Unloading;
+        Ok(Some((victim, Arc::clone(&entry.in_flight))))
+    }
+}
+
+/// Load `model_id` from `path` through `callback`, or by mapping the file
+/// and touching its first page when there is none.
+pub(super) async fn load_weights(
+    callback: Option<&LoadCallback>,
+    model_id: &str,
+    path: &Path,
+) -> Result<ModelHandle, String> {
+    if let Some(callback) = callback {
+        return callback(model_id, path).await;
+    }
+    std::fs::File::open(path)
+        .and_then(|f| unsafe { memmap2::Mmap::map(&f) })
+        .map(|mmap| {
+            let _ = mmap.get(0); // Touch first page
+            ModelHandle::new(1) // Placeholder
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Models currently holding memory (ready or draining).
+pub(super) fn resident_count(models: &HashMap<String, ModelEntry>) -> usize {
+    models
+        .values()
+        .filter(|m| matches!(m.state, LoadState::Ready | LoadState::Unloading))
+        .count()
+}
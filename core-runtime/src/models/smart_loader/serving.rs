@@ -0,0 +1,200 @@
+//! Backing the smart loader with the runtime's model loader and inference
+//! engine, so the models it loads are served over IPC.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use super::types::{LoadCallback, UnloadCallback, WarmupCallback};
+use crate::engine::{GgufModel, InferenceEngine, InferenceParams, ModelFactory};
+use super::{ModelTier, SmartLoader, SmartLoaderConfig};
+use crate::health::HealthChecker;
+use crate::models::{
+    LoadedModelState, ModelHandle, ModelLoader, ModelManifest, ModelPath, ModelRegistry,
+    PersistedModel,
+};
+
+/// Prompt generated from when warming up a freshly loaded model.
+const WARMUP_PROMPT: &str = "Hello";
+
+impl SmartLoader {
+    /// Loader whose loads are built with `factory`, served from `engine`
+    /// and warmed up through it, holding `health` readiness meanwhile.
+    /// Unloaded models leave both `engine` and `registry`.
+    pub fn serving(
+        config: SmartLoaderConfig,
+        loader: Arc<ModelLoader>,
+        factory: ModelFactory,
+        registry: Arc<ModelRegistry>,
+        engine: Arc<InferenceEngine>,
+        health: Arc<HealthChecker>,
+    ) -> Self {
+        let mut smart_loader = SmartLoader::new(config);
+        smart_loader.set_warmup_callback(engine_warmup_callback(Arc::clone(&engine)));
+        let unload = engine_unload_callback(Arc::clone(&registry), Arc::clone(&engine));
+        smart_loader.set_unload_callback(unload);
+        smart_loader.set_load_callback(engine_load_callback(loader, factory, registry, engine));
+        smart_loader.set_health_checker(health);
+        smart_loader
+    }
+
+    /// Load every model restored from the registry snapshot whose entry has
+    /// `auto_load` set. A model that fails to load stays restored.
+    pub async fn preload_restored(&self, registry: &ModelRegistry) {
+        let restored: Vec<String> = registry
+            .list_models()
+            .await
+            .into_iter()
+            .filter(|info| info.state == LoadedModelState::Unloaded)
+            .map(|info| info.name)
+            .collect();
+        let mut entries = registry.snapshot().await.models;
+        for model_id in restored {
+            let Some(entry) = entries.remove(&model_id).filter(|entry| entry.auto_load) else {
+                continue;
+            };
+            let registered = self.register(model_id.clone(), entry.path, ModelTier::Balanced);
+            if let Err(e) = registered.await {
+                tracing::warn!(model_id = %model_id, error = %e, "cannot preload restored model");
+                continue;
+            }
+            if let Err(e) = self.get(&model_id).await {
+                tracing::warn!(model_id = %model_id, error = %e, "restored model preload failed");
+            }
+        }
+    }
+}
+
+/// Load callback checking the file against its manifest, building its
+/// weights with `factory`, registering it and attaching it to `engine`.
+///
+/// A record of the same model restored from the registry snapshot is
+/// replaced, as `LoadModelRequest` does.
+pub fn engine_load_callback(
+    loader: Arc<ModelLoader>,
+    factory: ModelFactory,
+    registry: Arc<ModelRegistry>,
+    engine: Arc<InferenceEngine>,
+) -> LoadCallback {
+    Box::new(move |model_id, path| {
+        let loader = Arc::clone(&loader);
+        let factory = Arc::clone(&factory);
+        let registry = Arc::clone(&registry);
+        let engine = Arc::clone(&engine);
+        let model_id = model_id.to_string();
+        let path = path.to_string_lossy().into_owned();
+        Box::pin(async move {
+            let model_path = loader.validate_path(&path).map_err(|e| e.to_string())?;
+            let manifest = loader.read_manifest(&model_path).map_err(|e| e.to_string())?;
+            let template = manifest.as_ref().and_then(|m| m.chat_template().ok());
+            let model = build_weights(&loader, factory, &model_id, &model_path, manifest).await?;
+            let handle = register(&loader, &registry, &model_id, &model_path, &model).await?;
+            if let Some(template) = template {
+                engine.set_chat_template(&model_id, template).await;
+            }
+            engine.register_model(model_id, handle, model).await;
+            Ok(handle)
+        })
+    })
+}
+
+/// Unload callback detaching the model from `engine` and dropping its
+/// registry record, so a later load registers it afresh.
+pub fn engine_unload_callback(
+    registry: Arc<ModelRegistry>,
+    engine: Arc<InferenceEngine>,
+) -> UnloadCallback {
+    Box::new(move |model_id, handle| {
+        let registry = Arc::clone(&registry);
+        let engine = Arc::clone(&engine);
+        let model_id = model_id.to_string();
+        Box::pin(async move {
+            engine.unregister_model(&model_id).await;
+            registry.unregister(handle).await;
+        })
+    })
+}
+
+/// Warmup callback generating `tokens` tokens on the engine model behind
+/// the loaded handle.
+pub fn engine_warmup_callback(engine: Arc<InferenceEngine>) -> WarmupCallback {
+    Box::new(move |handle, tokens| {
+        let engine = Arc::clone(&engine);
+        Box::pin(async move {
+            let params = InferenceParams {
+                max_tokens: tokens,
+                ..Default::default()
+            };
+            engine
+                .run_by_handle(handle, WARMUP_PROMPT, &params)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+    })
+}
+
+/// Verify `model_path` against `manifest` and build its weights, both off
+/// the async runtime.
+async fn build_weights(
+    loader: &Arc<ModelLoader>,
+    factory: ModelFactory,
+    model_id: &str,
+    model_path: &ModelPath,
+    manifest: Option<ModelManifest>,
+) -> Result<Arc<dyn GgufModel>, String> {
+    let loader = Arc::clone(loader);
+    let model_path = model_path.clone();
+    let model_id = model_id.to_string();
+    tokio::task::spawn_blocking(move || {
+        if let Some(manifest) = &manifest {
+            loader.verify_integrity(&model_path, manifest).map_err(|e| e.to_string())?;
+        }
+        factory(model_path.as_path(), &model_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Model load task failed: {}", e))?
+}
+
+/// Register `model`. A record of it restored from the snapshot is
+/// replaced, keeping its manifest entry.
+async fn register(
+    loader: &ModelLoader,
+    registry: &ModelRegistry,
+    model_id: &str,
+    model_path: &ModelPath,
+    model: &Arc<dyn GgufModel>,
+) -> Result<ModelHandle, String> {
+    if let Some((handle, entry)) = restored_entry(registry, model_id).await {
+        registry.unregister(handle).await;
+        let registered = registry.register_persisted(entry, model.memory_usage()).await;
+        return registered.map_err(|e| e.to_string());
+    }
+    let mut metadata = loader.load_metadata(model_path).map_err(|e| e.to_string())?;
+    metadata.name = model_id.to_string();
+    let file = model_path.as_path();
+    registry
+        .register_file(metadata, model.memory_usage(), format_of(file), file.to_path_buf())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Handle and manifest entry of `model_id` restored from the snapshot and
+/// not loaded since.
+async fn restored_entry(
+    registry: &ModelRegistry,
+    model_id: &str,
+) -> Option<(ModelHandle, PersistedModel)> {
+    let info = registry.list_models().await.into_iter().find(|info| {
+        info.name == model_id && info.state == LoadedModelState::Unloaded
+    })?;
+    let entry = registry.snapshot().await.models.remove(model_id)?;
+    Some((ModelHandle::new(info.handle_id), entry))
+}
+
+/// Lowercased file extension, e.g. `gguf`.
+fn format_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| "unknown".to_string())
+}
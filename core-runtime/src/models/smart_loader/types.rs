@@ -0,0 +1,194 @@
+//! Smart loader configuration, state and callback types.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::models::registry::ModelHandle;
+
+#[derive(Error, Debug)]
+pub enum SmartLoaderError {
+    #[error("Model not registered: {0}")]
+    NotRegistered(String),
+
+    #[error("Load failed: {0}")]
+    LoadFailed(String),
+
+    #[error("Already loading")]
+    AlreadyLoading,
+
+    #[error("Model is being unloaded: {0}")]
+    Unloading(String),
+
+    #[error("Timed out draining model {0} for auto-unload")]
+    DrainTimeout(String),
+
+    #[error("Cannot load {0}: all resident slots pinned")]
+    AllSlotsPinned(String),
+
+    #[error("Cannot load {0}: resident cap set but no unload callback to release evictions")]
+    NoUnloadCallback(String),
+}
+
+/// Semantic hints for adaptive loading decisions.
+#[derive(Debug, Clone, Copy)]
+pub enum LoadHint {
+    /// Quick single query - prefer lightweight model
+    QuickQuery,
+    /// Complex task - prefer quality model
+    ComplexTask,
+    /// Batch incoming - preload appropriate model
+    BatchIncoming { count: usize },
+    /// User going idle - good time to preload
+    UserIdle,
+    /// Explicit model preference
+    PreferModel { tier: ModelTier },
+}
+
+/// Model tier classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModelTier {
+    /// Lightweight for quick responses (~500MB)
+    Light,
+    /// Balanced for general use (~1.5GB)
+    Balanced,
+    /// Quality for complex tasks (~2.5GB)
+    Quality,
+}
+
+/// Model load state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadState {
+    /// Registered but not loaded
+    Registered,
+    /// Currently loading
+    Loading,
+    /// Loaded and ready
+    Ready,
+    /// Load failed
+    Failed,
+    /// Draining in-flight requests before auto-unload
+    Unloading,
+}
+
+/// Registered model metadata.
+pub(super) struct ModelEntry {
+    pub(super) path: PathBuf,
+    pub(super) tier: ModelTier,
+    pub(super) size_bytes: u64,
+    pub(super) state: LoadState,
+    pub(super) handle: Option<ModelHandle>,
+    pub(super) last_used: Option<Instant>,
+    pub(super) use_count: u64,
+    pub(super) load_time_ms: Option<u64>,
+    /// Requests holding a `ResidentModel` for this entry.
+    pub(super) in_flight: Arc<AtomicUsize>,
+    /// Auto-unloaded to make room; the next load counts as a reload.
+    pub(super) evicted: bool,
+    /// Never auto-unloaded, however long it has been idle.
+    pub(super) pinned: bool,
+}
+
+/// Smart loader configuration.
+#[derive(Debug, Clone, Serialize)]
+pub struct SmartLoaderConfig {
+    /// Auto-unload after this duration of inactivity
+    pub auto_unload_after: Duration,
+    /// Max concurrent loads
+    pub max_concurrent_loads: usize,
+    /// Enable predictive loading
+    pub enable_prediction: bool,
+    /// Max models held in memory at once; the least recently used model is
+    /// auto-unloaded to make room. `None` keeps every loaded model resident.
+    /// A loader with a load callback also needs an unload callback for this.
+    pub max_resident_models: Option<usize>,
+    /// How long to wait for in-flight requests before auto-unloading a model.
+    pub drain_timeout: Duration,
+    /// Tokens to generate through the warmup callback after each load,
+    /// before the model is marked ready. 0 disables auto-warmup.
+    pub warmup_tokens: usize,
+}
+
+impl Default for SmartLoaderConfig {
+    fn default() -> Self {
+        Self {
+            auto_unload_after: Duration::from_secs(60),
+            max_concurrent_loads: 1,
+            enable_prediction: true,
+            max_resident_models: None,
+            drain_timeout: Duration::from_secs(30),
+            warmup_tokens: 0,
+        }
+    }
+}
+
+/// Smart loader metrics.
+#[derive(Debug, Default, Clone)]
+pub struct SmartLoaderMetrics {
+    pub total_loads: u64,
+    pub cache_hits: u64,
+    pub predictions_made: u64,
+    pub predictions_correct: u64,
+    pub avg_load_ms: f64,
+    pub avg_cache_hit_ms: f64,
+    /// Models unloaded to stay within `max_resident_models`.
+    pub auto_unloads: u64,
+    /// Loads of models that were previously auto-unloaded.
+    pub reloads: u64,
+}
+
+/// A loaded model pinned against auto-unload until dropped.
+#[derive(Debug)]
+pub struct ResidentModel {
+    pub(super) handle: ModelHandle,
+    pub(super) in_flight: Arc<AtomicUsize>,
+}
+
+impl ResidentModel {
+    pub fn handle(&self) -> ModelHandle {
+        self.handle
+    }
+}
+
+impl Drop for ResidentModel {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Future returned by a [`LoadCallback`].
+pub type LoadFuture = Pin<Box<dyn Future<Output = Result<ModelHandle, String>> + Send>>;
+
+/// Callback loading `model_id`'s weights from `path` and serving them
+/// (see [`engine_load_callback`](super::engine_load_callback)).
+pub type LoadCallback = Box<dyn Fn(&str, &Path) -> LoadFuture + Send + Sync>;
+
+/// Future returned by a [`WarmupCallback`].
+pub type WarmupFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// Callback generating `tokens` tokens on a freshly loaded model, so its
+/// first slow forward pass happens before it is marked ready.
+pub type WarmupCallback = Box<dyn Fn(ModelHandle, usize) -> WarmupFuture + Send + Sync>;
+
+/// Future returned by an [`UnloadCallback`].
+pub type UnloadFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Callback releasing what the load callback attached for `model_id` under
+/// `handle` (see [`engine_unload_callback`](super::engine_unload_callback)).
+pub type UnloadCallback = Box<dyn Fn(&str, ModelHandle) -> UnloadFuture + Send + Sync>;
+
+/// Current loader status.
+#[derive(Debug)]
+pub struct SmartLoaderStatus {
+    pub registered_count: usize,
+    pub loaded_count: usize,
+    pub loaded_models: Vec<(String, ModelTier)>,
+    pub active_tier: Option<ModelTier>,
+    pub total_loaded_bytes: u64,
+    pub predicted_next: Option<String>,
+}
@@ -0,0 +1,45 @@
+//! Warmup generation run after a load, before the model is marked ready.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::types::WarmupCallback;
+use crate::health::{HealthChecker, WarmupGuard};
+use crate::models::registry::ModelHandle;
+
+/// Auto-warmup settings shared with background preloads.
+#[derive(Clone)]
+pub(super) struct AutoWarmup {
+    pub(super) tokens: usize,
+    pub(super) callback: Option<Arc<WarmupCallback>>,
+    pub(super) health: Option<Arc<HealthChecker>>,
+}
+
+impl AutoWarmup {
+    fn enabled(&self) -> bool {
+        self.tokens > 0 && self.callback.is_some()
+    }
+
+    /// Hold readiness while a model loads and warms up.
+    pub(super) fn guard(&self) -> Option<WarmupGuard> {
+        self.health.as_ref().filter(|_| self.enabled()).map(|h| h.begin_warmup())
+    }
+
+    /// Run the warmup generation. Failures are logged and the model is
+    /// still served, just cold.
+    pub(super) async fn run(&self, model_id: &str, handle: ModelHandle) {
+        let Some(callback) = self.callback.as_ref().filter(|_| self.tokens > 0) else {
+            return;
+        };
+        let start = Instant::now();
+        match callback(handle, self.tokens).await {
+            Ok(()) => tracing::info!(
+                model_id,
+                tokens = self.tokens,
+                elapsed_ms = start.elapsed().as_millis() as u64,
+                "Model warmed up"
+            ),
+            Err(e) => tracing::warn!(model_id, error = %e, "Model auto-warmup failed"),
+        }
+    }
+}
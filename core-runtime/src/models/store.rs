@@ -0,0 +1,228 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pluggable storage backends for registry state.
+//!
+//! `RegistryPersistence` delegates to a `RegistryStore`, so deployments can
+//! persist to a mounted ConfigMap path or a custom location. The default
+//! `FileRegistryStore` writes a temp file and renames it into place.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::persistence::{PersistenceError, RegistryState};
+
+/// Storage backend for registry state.
+pub trait RegistryStore: Send + Sync {
+    /// Persist the full registry state, replacing any previous state.
+    fn save(&self, state: &RegistryState) -> Result<(), PersistenceError>;
+
+    /// Load the registry state. Returns `NotFound` if nothing was saved.
+    fn load(&self) -> Result<RegistryState, PersistenceError>;
+
+    /// Remove persisted state. Succeeds if nothing was saved.
+    fn delete(&self) -> Result<(), PersistenceError>;
+
+    /// Check whether state has been persisted.
+    fn exists(&self) -> bool {
+        !matches!(self.load(), Err(PersistenceError::NotFound))
+    }
+}
+
+/// Filesystem store using write-temp-then-rename for atomic updates.
+///
+/// Readers observe either the previous complete state or the new one,
+/// never a partially written file.
+pub struct FileRegistryStore {
+    state_path: PathBuf,
+}
+
+impl FileRegistryStore {
+    /// Create a store backed by the given state file path.
+    pub fn new(state_path: PathBuf) -> Self {
+        Self { state_path }
+    }
+
+    /// Path of the state file.
+    pub fn path(&self) -> &Path {
+        &self.state_path
+    }
+
+    fn temp_path(&self) -> PathBuf {
+        let mut name = self.state_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".tmp");
+        self.state_path.with_file_name(name)
+    }
+
+    fn write_temp(&self, temp_path: &Path, state: &RegistryState) -> Result<(), PersistenceError> {
+        let file =
+            File::create(temp_path).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, state)
+            .map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+        writer.flush().map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+        // Flush to disk before rename so a crash cannot expose a truncated file
+        writer
+            .get_ref()
+            .sync_all()
+            .map_err(|e| PersistenceError::WriteError(e.to_string()))
+    }
+}
+
+impl RegistryStore for FileRegistryStore {
+    fn save(&self, state: &RegistryState) -> Result<(), PersistenceError> {
+        if let Some(parent) = self.state_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+        }
+
+        let temp_path = self.temp_path();
+        let result = self.write_temp(&temp_path, state).and_then(|()| {
+            fs::rename(&temp_path, &self.state_path)
+                .map_err(|e| PersistenceError::WriteError(e.to_string()))
+        });
+
+        if result.is_err() {
+            // Never leave a partial temp file behind
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    /// # Security
+    /// Opens the file directly rather than checking existence first,
+    /// avoiding a TOCTOU race.
+    fn load(&self) -> Result<RegistryState, PersistenceError> {
+        let file = File::open(&self.state_path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                PersistenceError::NotFound
+            } else {
+                PersistenceError::ReadError(e.to_string())
+            }
+        })?;
+        let reader = BufReader::new(file);
+
+        serde_json::from_reader(reader).map_err(|e| PersistenceError::ParseError(e.to_string()))
+    }
+
+    /// # Security
+    /// Deletes directly and treats `NotFound` as success, avoiding a TOCTOU race.
+    fn delete(&self) -> Result<(), PersistenceError> {
+        match fs::remove_file(&self.state_path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(PersistenceError::WriteError(e.to_string())),
+        }
+    }
+
+    fn exists(&self) -> bool {
+        self.state_path.exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::persistence::RegistryPersistence;
+    use std::sync::Mutex;
+
+    /// In-memory store holding the serialized JSON.
+    #[derive(Default)]
+    struct MemoryStore {
+        data: Mutex<Option<String>>,
+    }
+
+    impl RegistryStore for MemoryStore {
+        fn save(&self, state: &RegistryState) -> Result<(), PersistenceError> {
+            let json = serde_json::to_string(state)
+                .map_err(|e| PersistenceError::WriteError(e.to_string()))?;
+            *self.data.lock().unwrap() = Some(json);
+            Ok(())
+        }
+
+        fn load(&self) -> Result<RegistryState, PersistenceError> {
+            let data = self.data.lock().unwrap();
+            let json = data.as_ref().ok_or(PersistenceError::NotFound)?;
+            serde_json::from_str(json).map_err(|e| PersistenceError::ParseError(e.to_string()))
+        }
+
+        fn delete(&self) -> Result<(), PersistenceError> {
+            *self.data.lock().unwrap() = None;
+            Ok(())
+        }
+    }
+
+    fn temp_test_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("veritas_test_{}", name))
+    }
+
+    #[test]
+    fn test_memory_store_round_trip() {
+        let persistence = RegistryPersistence::with_store(Box::new(MemoryStore::default()));
+        assert!(!persistence.exists());
+
+        let state = RegistryState {
+            saved_at: 42,
+            default_model: Some("injected".to_string()),
+            ..Default::default()
+        };
+        persistence.save(&state).unwrap();
+
+        assert!(persistence.exists());
+        let loaded = persistence.load().unwrap();
+        assert_eq!(loaded.saved_at, 42);
+        assert_eq!(loaded.default_model, Some("injected".to_string()));
+
+        persistence.delete().unwrap();
+        assert!(matches!(persistence.load(), Err(PersistenceError::NotFound)));
+    }
+
+    #[test]
+    fn test_file_store_leaves_no_temp_file() {
+        let temp_dir = temp_test_dir("store_no_temp");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let store = FileRegistryStore::new(temp_dir.join("state.json"));
+
+        store.save(&RegistryState::default()).unwrap();
+
+        assert!(store.path().exists());
+        assert!(!store.temp_path().exists());
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_file_store_failed_rename_leaves_no_partial_file() {
+        let temp_dir = temp_test_dir("store_failed_rename");
+        let _ = fs::remove_dir_all(&temp_dir);
+        // A non-empty directory at the target path makes the rename fail
+        let state_path = temp_dir.join("state.json");
+        fs::create_dir_all(state_path.join("occupied")).unwrap();
+        let store = FileRegistryStore::new(state_path.clone());
+
+        let result = store.save(&RegistryState::default());
+
+        assert!(matches!(result, Err(PersistenceError::WriteError(_))));
+        assert!(!store.temp_path().exists());
+        assert!(state_path.is_dir());
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_file_store_overwrite_is_complete() {
+        let temp_dir = temp_test_dir("store_overwrite");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let store = FileRegistryStore::new(temp_dir.join("state.json"));
+
+        for i in 0..10 {
+            let state = RegistryState {
+                saved_at: i,
+                ..Default::default()
+            };
+            store.save(&state).unwrap();
+            // Every save must leave a fully parseable file
+            assert_eq!(store.load().unwrap().saved_at, i);
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
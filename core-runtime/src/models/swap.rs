@@ -2,16 +2,19 @@
 //!
 //! Orchestrates preload, drain, and route swap for seamless transitions.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
 use super::drain::{DrainError, FlightTracker};
+use super::loader::ModelMetadata;
 use super::manifest::ModelManifest;
 use super::preload::{ModelPreloader, PreloadError};
 use super::registry::{ModelHandle, ModelRegistry};
 use super::router::ModelRouter;
+use crate::engine::{GgufModel, InferenceEngine};
 
 #[derive(Error, Debug)]
 pub enum SwapError {
@@ -34,6 +37,17 @@ pub struct SwapResult {
     pub old_handle: ModelHandle,
     pub new_handle: ModelHandle,
     pub drain_duration: Duration,
+    /// Whether every request on the old handle finished within the timeout.
+    pub drained: bool,
+}
+
+/// A model already loaded from its file, waiting to replace a live one.
+pub struct ModelReplacement {
+    pub metadata: ModelMetadata,
+    pub format: String,
+    /// File the replacement weights were loaded from.
+    pub path: PathBuf,
+    pub model: Arc<dyn GgufModel>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -127,6 +141,9 @@ impl SwapManager {
             *self.state.write().await = SwapState::Swapping;
         }
         self.router.swap_route(model_id, new_handle).await;
+        self.router
+            .set_capabilities(model_id, preloaded.manifest.capabilities.clone())
+            .await;
 
         // Cleanup old model
         self.registry.unregister(old_handle).await;
@@ -134,7 +151,81 @@ impl SwapManager {
 
         self.reset_state().await;
 
-        Ok(SwapResult { old_handle, new_handle, drain_duration })
+        Ok(SwapResult { old_handle, new_handle, drain_duration, drained: true })
+    }
+
+    /// Swap the model `engine` serves as `model_id` for `replacement`:
+    /// 1. Register the replacement under a fresh handle
+    /// 2. Atomically repoint the engine (and any route) to the new handle
+    /// 3. Drain requests already in flight on the old handle
+    /// 4. Unregister the old handle
+    ///
+    /// Requests arriving after step 2 land on the new handle, so the drain
+    /// only waits for work that started before the swap. Stragglers still
+    /// running when `drain_timeout` expires keep their reference to the old
+    /// model and finish on it; `SwapResult::drained` is false then.
+    pub async fn swap_engine_model(
+        &self,
+        engine: &InferenceEngine,
+        model_id: &str,
+        replacement: ModelReplacement,
+        drain_timeout: Duration,
+    ) -> Result<SwapResult, SwapError> {
+        self.begin_swap().await?;
+        let result = self
+            .repoint_engine(engine, model_id, replacement, drain_timeout)
+            .await;
+        self.reset_state().await;
+        result
+    }
+
+    /// Claim the swap lock, failing if another swap holds it.
+    async fn begin_swap(&self) -> Result<(), SwapError> {
+        let mut state = self.state.write().await;
+        if *state != SwapState::Idle {
+            return Err(SwapError::SwapInProgress);
+        }
+        *state = SwapState::Preparing;
+        Ok(())
+    }
+
+    async fn repoint_engine(
+        &self,
+        engine: &InferenceEngine,
+        model_id: &str,
+        replacement: ModelReplacement,
+        drain_timeout: Duration,
+    ) -> Result<SwapResult, SwapError> {
+        let old_handle = engine
+            .get_handle(model_id)
+            .await
+            .ok_or_else(|| SwapError::RouteNotFound(model_id.to_string()))?;
+        let memory_bytes = replacement.model.memory_usage();
+        let new_handle = self
+            .registry
+            .register_file(
+                replacement.metadata,
+                memory_bytes,
+                replacement.format,
+                replacement.path,
+            )
+            .await
+            .map_err(|e| SwapError::PreloadFailed(PreloadError::LoadFailed(e.to_string())))?;
+
+        *self.state.write().await = SwapState::Swapping;
+        engine.swap_model(model_id, new_handle, replacement.model).await;
+        if self.router.has_route(model_id).await {
+            self.router.swap_route(model_id, new_handle).await;
+        }
+
+        *self.state.write().await = SwapState::Draining;
+        let drain_start = std::time::Instant::now();
+        let drained = self.flight_tracker.drain(old_handle, drain_timeout).await.is_ok();
+        let drain_duration = drain_start.elapsed();
+        self.registry.unregister(old_handle).await;
+        self.flight_tracker.remove(old_handle).await;
+
+        Ok(SwapResult { old_handle, new_handle, drain_duration, drained })
     }
 
     async fn reset_state(&self) {
@@ -6,6 +6,8 @@
 use pyo3::prelude::*;
 
 use crate::engine::InferenceParams as RustParams;
+use crate::engine::OutputEncoding;
+use crate::engine::SamplerKind;
 use crate::engine::InferenceResult as RustResult;
 
 /// Inference parameters for controlling generation
@@ -99,8 +101,18 @@ impl From<&InferenceParams> for RustParams {
             temperature: py.temperature,
             top_p: py.top_p,
             top_k: py.top_k as usize,
+            min_p: None,
             stream: py.stream,
             timeout_ms: py.timeout_ms,
+            max_cpu_ms: None,
+            output_encoding: OutputEncoding::Utf8,
+            no_cache: false,
+            logit_bias: Default::default(),
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: SamplerKind::Default,
         }
     }
 }
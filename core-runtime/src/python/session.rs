@@ -7,15 +7,35 @@ use std::sync::Arc;
 
 use pyo3::prelude::*;
 use tokio::runtime::Runtime as TokioRuntime;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 use super::exceptions::AuthenticationError;
 use super::inference::{InferenceParams, InferenceResult};
-use super::streaming::StreamingIterator;
+use super::streaming::{TextStreamIterator, TokenIterator};
 use crate::engine::InferenceParams as RustParams;
-use crate::ipc::SessionToken;
+use crate::ipc::protocol::{InferenceRequest, IpcMessage, RequestId};
+use crate::ipc::{HandlerError, SessionToken, StreamSender};
 use crate::models::ModelHandle;
+use crate::scheduler::{RequestIdAllocator, RequestOrigin};
 use crate::Runtime as CoreRuntime;
 
+/// Chunks buffered between the inference task and a `TextStreamIterator`.
+const STREAM_BUFFER: usize = 32;
+
+/// Forwards stream frames to the iterator; fails once it has been dropped.
+struct ChannelSender(mpsc::Sender<IpcMessage>);
+
+#[async_trait::async_trait]
+impl StreamSender for ChannelSender {
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError> {
+        self.0
+            .send(message)
+            .await
+            .map_err(|_| HandlerError::StreamSend("iterator dropped".into()))
+    }
+}
+
 /// Synchronous session for inference operations
 ///
 /// Use as a context manager:
@@ -104,7 +124,7 @@ impl Session {
         model_id: u64,
         tokens: Vec<u32>,
         params: Option<&InferenceParams>,
-    ) -> PyResult<StreamingIterator> {
+    ) -> PyResult<TokenIterator> {
         self.check_valid()?;
 
         let rust_params = params
@@ -119,7 +139,57 @@ impl Session {
                 .await
         })?;
 
-        Ok(StreamingIterator::new(result.output_tokens))
+        Ok(TokenIterator::new(result.output_tokens))
+    }
+
+    /// Stream the decoded text of a prompt's completion
+    ///
+    /// Returns an iterator yielding text pieces as tokens are generated.
+    /// Stopping iteration early (dropping the iterator) cancels the request.
+    ///
+    /// Example:
+    /// ```python
+    /// text = "".join(session.infer_stream("model", "Hello"))
+    /// ```
+    #[pyo3(signature = (model_id, prompt, params=None))]
+    fn infer_stream(
+        &self,
+        model_id: &str,
+        prompt: &str,
+        params: Option<&InferenceParams>,
+    ) -> PyResult<TextStreamIterator> {
+        self.check_valid()?;
+
+        let request_id = RequestId(RequestIdAllocator::global().allocate(RequestOrigin::Ffi));
+        let request = InferenceRequest {
+            request_id,
+            model_id: model_id.to_string(),
+            prompt: prompt.to_string(),
+            parameters: params.map(RustParams::from).unwrap_or_default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
+        };
+
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        let runtime = Arc::clone(&self.runtime);
+        let token = self.token.clone();
+        self.tokio.spawn(async move {
+            let sender = ChannelSender(sender);
+            let _ = runtime
+                .ipc_handler
+                .process_streaming(request, &token, &sender, CancellationToken::new())
+                .await;
+        });
+
+        Ok(TextStreamIterator::new(
+            Arc::clone(&self.runtime),
+            Arc::clone(&self.tokio),
+            self.token.clone(),
+            request_id,
+            model_id.to_string(),
+            receiver,
+        ))
     }
 
     /// Context manager enter
@@ -3,7 +3,17 @@
 
 //! Python streaming types for token-by-token output
 
+use std::sync::Arc;
+
 use pyo3::prelude::*;
+use tokio::runtime::Runtime as TokioRuntime;
+use tokio::sync::mpsc;
+
+use super::exceptions::InferenceError;
+use crate::engine::StreamTextDecoder;
+use crate::ipc::protocol::{encode_message, IpcMessage, RequestId};
+use crate::ipc::SessionToken;
+use crate::Runtime as CoreRuntime;
 
 /// A single streaming result chunk
 ///
@@ -61,19 +71,19 @@ impl StreamingResult {
 ///         print(f"Token {chunk.index}: {chunk.token}")
 /// ```
 #[pyclass]
-pub struct StreamingIterator {
+pub struct TokenIterator {
     tokens: Vec<u32>,
     index: usize,
 }
 
-impl StreamingIterator {
+impl TokenIterator {
     pub fn new(tokens: Vec<u32>) -> Self {
         Self { tokens, index: 0 }
     }
 }
 
 #[pymethods]
-impl StreamingIterator {
+impl TokenIterator {
     fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
     }
@@ -103,6 +113,118 @@ impl StreamingIterator {
     }
 }
 
+/// Former name of `TokenIterator`, kept for existing callers.
+pub type StreamingIterator = TokenIterator;
+
+/// Iterator over decoded text of a live streaming request
+///
+/// Yields each new piece of text as soon as the tokens producing it arrive.
+/// The GIL is released while waiting for the next chunk. Dropping the
+/// iterator before it is exhausted cancels the request.
+///
+/// Example:
+/// ```python
+/// for text in session.infer_stream("model", "Hello"):
+///     print(text, end="", flush=True)
+/// ```
+#[pyclass]
+pub struct TextStreamIterator {
+    runtime: Arc<CoreRuntime>,
+    tokio: Arc<TokioRuntime>,
+    token: SessionToken,
+    request_id: RequestId,
+    model_id: String,
+    receiver: mpsc::Receiver<IpcMessage>,
+    decoder: StreamTextDecoder,
+    finished: bool,
+}
+
+impl TextStreamIterator {
+    pub(super) fn new(
+        runtime: Arc<CoreRuntime>,
+        tokio: Arc<TokioRuntime>,
+        token: SessionToken,
+        request_id: RequestId,
+        model_id: String,
+        receiver: mpsc::Receiver<IpcMessage>,
+    ) -> Self {
+        Self {
+            runtime,
+            tokio,
+            token,
+            request_id,
+            model_id,
+            receiver,
+            decoder: StreamTextDecoder::default(),
+            finished: false,
+        }
+    }
+
+    /// Wait for the next chunk and return the text it completes, if any.
+    fn next_text(&mut self) -> Result<Option<String>, String> {
+        let message = self.tokio.block_on(self.receiver.recv());
+        let (tokens, is_final) = match message {
+            None => {
+                self.finished = true;
+                return Ok(None);
+            }
+            Some(IpcMessage::StreamChunk(chunk)) => {
+                if let Some(error) = chunk.error {
+                    self.finished = true;
+                    return Err(error);
+                }
+                (vec![chunk.token], chunk.is_final)
+            }
+            Some(IpcMessage::StreamBatch(batch)) => (batch.tokens, batch.is_final),
+            Some(_) => return Ok(Some(String::new())),
+        };
+        self.finished = is_final;
+        let engine = &self.runtime.inference_engine;
+        let text = self
+            .tokio
+            .block_on(self.decoder.push(engine, &self.model_id, &tokens, is_final));
+        text.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+#[pymethods]
+impl TextStreamIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> PyResult<Option<String>> {
+        let this = &mut *slf;
+        while !this.finished {
+            match py.allow_threads(|| this.next_text()) {
+                Ok(Some(text)) if text.is_empty() => continue,
+                Ok(text) => return Ok(text),
+                Err(error) => return Err(InferenceError::new_err(error)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for TextStreamIterator {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        // Same path as a client-sent CancelRequest
+        let Ok(bytes) = encode_message(&IpcMessage::CancelRequest {
+            request_id: self.request_id,
+        }) else {
+            return;
+        };
+        let runtime = Arc::clone(&self.runtime);
+        let token = self.token.clone();
+        self.tokio.spawn(async move {
+            let _ = runtime.ipc_handler.process(&bytes, Some(&token)).await;
+        });
+    }
+}
+
 /// Async iterator for streaming inference (future implementation)
 ///
 /// For true async streaming, this would yield tokens as they are generated.
@@ -144,3 +266,14 @@ impl AsyncStreamingIterator {
         })
     }
 }
+
+/// Add the streaming classes to `module`, with `StreamingIterator` bound to
+/// the same class as `TokenIterator`.
+pub(super) fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<StreamingResult>()?;
+    module.add_class::<TokenIterator>()?;
+    module.add("StreamingIterator", module.getattr("TokenIterator")?)?;
+    module.add_class::<TextStreamIterator>()?;
+    module.add_class::<AsyncStreamingIterator>()?;
+    Ok(())
+}
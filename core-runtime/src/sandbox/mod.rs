@@ -21,6 +21,10 @@ pub struct SandboxConfig {
     pub max_cpu_time_ms: u64,
     /// Whether to enable the sandbox (false = dry run).
     pub enabled: bool,
+    /// Log syscalls outside the whitelist instead of killing the process
+    /// (Unix seccomp only). For discovering the syscalls a backend needs
+    /// before turning on enforcement; cgroup limits still apply.
+    pub audit_only: bool,
 }
 
 impl Default for SandboxConfig {
@@ -29,6 +33,7 @@ impl Default for SandboxConfig {
             max_memory_bytes: 2 * 1024 * 1024 * 1024, // 2GB
             max_cpu_time_ms: 30_000,                   // 30s
             enabled: true,
+            audit_only: false,
         }
     }
 }
@@ -15,12 +15,19 @@
 //! When enabled, seccomp-bpf restricts the syscalls available to the process
 //! to a minimal whitelist required for inference operations. This provides
 //! defense-in-depth against code execution vulnerabilities.
+//!
+//! With `SandboxConfig::audit_only`, syscalls outside the whitelist are
+//! allowed but logged by the kernel (`SECCOMP_RET_LOG`, visible in the audit
+//! log or dmesg), so the whitelist a backend needs can be found before
+//! enforcement is turned on.
 
 use super::{Sandbox, SandboxConfig, SandboxResult, SandboxUsage};
 use crate::telemetry::{log_security_event, SecurityEvent};
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 /// Cgroup base path for v2
 const CGROUP_V2_BASE: &str = "/sys/fs/cgroup";
@@ -40,6 +47,10 @@ const SECCOMP_RET_ALLOW: u32 = 0x7FFF0000;
 #[cfg(target_os = "linux")]
 const SECCOMP_RET_KILL_PROCESS: u32 = 0x80000000;
 
+/// Seccomp return action: allow syscall after logging it (audit mode)
+#[cfg(target_os = "linux")]
+const SECCOMP_RET_LOG: u32 = 0x7FFC0000;
+
 /// BPF instruction classes
 #[cfg(target_os = "linux")]
 mod bpf {
@@ -126,11 +137,143 @@ struct SockFprog {
     filter: *const SockFilter,
 }
 
+/// Syscall whitelist for inference operations (x86_64 numbers).
+/// These are the minimal syscalls needed for the runtime.
+#[cfg(target_os = "linux")]
+const ALLOWED_SYSCALLS_X86_64: &[i32] = &[
+    // File operations
+    0,   // read
+    1,   // write
+    2,   // open
+    3,   // close
+    8,   // lseek
+    9,   // mmap
+    10,  // mprotect
+    11,  // munmap
+    12,  // brk
+    16,  // ioctl
+    22,  // pipe
+    23,  // select
+    24,  // sched_yield
+    28,  // madvise
+    257, // openat
+    262, // newfstatat
+    // Process management
+    39,  // getpid
+    60,  // exit
+    186, // gettid
+    218, // set_tid_address
+    231, // exit_group
+    // Signal handling
+    13, // rt_sigaction
+    14, // rt_sigprocmask
+    15, // rt_sigreturn
+    // Time
+    35,  // nanosleep
+    228, // clock_gettime
+    229, // clock_getres
+    // Thread operations
+    56, // clone
+    58, // fork
+    59, // execve
+    61, // wait4
+    // IPC (for tokio)
+    41, // socket
+    42, // connect
+    43, // accept
+    44, // sendto
+    45, // recvfrom
+    46, // sendmsg
+    47, // recvmsg
+    53, // socketpair
+    54, // setsockopt
+    55, // getsockopt
+    // Futex for synchronization
+    202, // futex
+    // Eventfd for tokio
+    281, // eventfd2
+    // Epoll for tokio
+    232, // epoll_wait
+    233, // epoll_ctl
+    254, // epoll_create1
+    // Random
+    318, // getrandom
+    // GPU driver support
+    157, // prctl
+    158, // arch_prctl
+];
+
+// Jump offsets are u8, so the whitelist must fit in one jump
+#[cfg(target_os = "linux")]
+const _: () = assert!(ALLOWED_SYSCALLS_X86_64.len() < u8::MAX as usize);
+
+/// Build the BPF program: allow whitelisted syscalls, apply `default_action`
+/// to everything else (including syscalls from a foreign architecture).
+#[cfg(target_os = "linux")]
+fn build_seccomp_filter(default_action: u32) -> Vec<SockFilter> {
+    let n = ALLOWED_SYSCALLS_X86_64.len();
+    let mut filter = Vec::with_capacity(n + 5);
+
+    // Load architecture
+    filter.push(SockFilter {
+        code: bpf::LD | bpf_size::W | bpf_mode::ABS,
+        jt: 0,
+        jf: 0,
+        k: 4, // offsetof(seccomp_data, arch)
+    });
+
+    // Check architecture (x86_64); otherwise skip the load and every
+    // syscall check to land on the default action
+    filter.push(SockFilter {
+        code: bpf::JMP | bpf_jmp::JEQ | bpf_src::K,
+        jt: 0,
+        jf: (n + 1) as u8,
+        k: AUDIT_ARCH_X86_64,
+    });
+
+    // Load syscall number
+    filter.push(SockFilter {
+        code: bpf::LD | bpf_size::W | bpf_mode::ABS,
+        jt: 0,
+        jf: 0,
+        k: 0, // offsetof(seccomp_data, nr)
+    });
+
+    // Check against allowed syscalls; a match skips the remaining checks
+    // and the default action to land on allow
+    for (i, &syscall_nr) in ALLOWED_SYSCALLS_X86_64.iter().enumerate() {
+        filter.push(SockFilter {
+            code: bpf::JMP | bpf_jmp::JEQ | bpf_src::K,
+            jt: (n - i) as u8,
+            jf: 0,
+            k: syscall_nr as u32,
+        });
+    }
+
+    // Default: kill process (or log, in audit mode)
+    filter.push(SockFilter {
+        code: bpf::RET | bpf_src::K,
+        jt: 0,
+        jf: 0,
+        k: default_action,
+    });
+
+    // Allow syscall
+    filter.push(SockFilter {
+        code: bpf::RET | bpf_src::K,
+        jt: 0,
+        jf: 0,
+        k: SECCOMP_RET_ALLOW,
+    });
+
+    filter
+}
+
 /// Unix sandbox implementation using cgroups v2.
 pub struct UnixSandbox {
     config: SandboxConfig,
-    active: bool,
-    cgroup_path: Option<String>,
+    active: AtomicBool,
+    cgroup_path: OnceLock<String>,
 }
 
 impl UnixSandbox {
@@ -138,8 +281,8 @@ impl UnixSandbox {
     pub fn new(config: SandboxConfig) -> Self {
         Self {
             config,
-            active: false,
-            cgroup_path: None,
+            active: AtomicBool::new(false),
+            cgroup_path: OnceLock::new(),
         }
     }
 
@@ -203,127 +346,21 @@ impl UnixSandbox {
         Ok(cgroup_path)
     }
 
+    /// Seccomp action for syscalls outside the whitelist.
+    #[cfg(target_os = "linux")]
+    fn seccomp_default_action(&self) -> u32 {
+        if self.config.audit_only {
+            SECCOMP_RET_LOG
+        } else {
+            SECCOMP_RET_KILL_PROCESS
+        }
+    }
+
     /// Apply seccomp-bpf filter to restrict syscalls
     /// This provides defense-in-depth against code execution vulnerabilities
     #[cfg(target_os = "linux")]
     fn apply_seccomp_filter(&self) -> Result<(), String> {
-        // Syscall whitelist for inference operations (x86_64 numbers)
-        // These are the minimal syscalls needed for the runtime
-        const ALLOWED_SYSCALLS_X86_64: &[i32] = &[
-            // File operations
-            0,   // read
-            1,   // write
-            2,   // open
-            3,   // close
-            8,   // lseek
-            9,   // mmap
-            10,  // mprotect
-            11,  // munmap
-            12,  // brk
-            16,  // ioctl
-            22,  // pipe
-            23,  // select
-            24,  // sched_yield
-            28,  // madvise
-            257, // openat
-            262, // newfstatat
-            // Process management
-            39,  // getpid
-            60,  // exit
-            186, // gettid
-            218, // set_tid_address
-            231, // exit_group
-            // Signal handling
-            13, // rt_sigaction
-            14, // rt_sigprocmask
-            15, // rt_sigreturn
-            // Time
-            35,  // nanosleep
-            228, // clock_gettime
-            229, // clock_getres
-            // Thread operations
-            56, // clone
-            58, // fork
-            59, // execve
-            61, // wait4
-            // IPC (for tokio)
-            41, // socket
-            42, // connect
-            43, // accept
-            44, // sendto
-            45, // recvfrom
-            46, // sendmsg
-            47, // recvmsg
-            53, // socketpair
-            54, // setsockopt
-            55, // getsockopt
-            // Futex for synchronization
-            202, // futex
-            // Eventfd for tokio
-            281, // eventfd2
-            // Epoll for tokio
-            232, // epoll_wait
-            233, // epoll_ctl
-            254, // epoll_create1
-            // Random
-            318, // getrandom
-            // GPU driver support
-            157, // prctl
-            158, // arch_prctl
-        ];
-
-        // Build BPF filter program
-        let mut filter = Vec::new();
-
-        // Load architecture
-        filter.push(SockFilter {
-            code: bpf::LD | bpf_size::W | bpf_mode::ABS,
-            jt: 0,
-            jf: 0,
-            k: 4, // offsetof(seccomp_data, arch)
-        });
-
-        // Check architecture (x86_64)
-        filter.push(SockFilter {
-            code: bpf::JMP | bpf_jmp::JEQ | bpf_src::K,
-            jt: 0,
-            jf: 4, // Skip to kill if wrong arch
-            k: AUDIT_ARCH_X86_64,
-        });
-
-        // Load syscall number
-        filter.push(SockFilter {
-            code: bpf::LD | bpf_size::W | bpf_mode::ABS,
-            jt: 0,
-            jf: 0,
-            k: 0, // offsetof(seccomp_data, nr)
-        });
-
-        // Check against allowed syscalls
-        for &syscall_nr in ALLOWED_SYSCALLS_X86_64 {
-            filter.push(SockFilter {
-                code: bpf::JMP | bpf_jmp::JEQ | bpf_src::K,
-                jt: 1, // Jump to allow
-                jf: 0, // Continue to next check
-                k: syscall_nr as u32,
-            });
-        }
-
-        // Default: kill process
-        filter.push(SockFilter {
-            code: bpf::RET | bpf_src::K,
-            jt: 0,
-            jf: 0,
-            k: SECCOMP_RET_KILL_PROCESS,
-        });
-
-        // Allow syscall
-        filter.push(SockFilter {
-            code: bpf::RET | bpf_src::K,
-            jt: 0,
-            jf: 0,
-            k: SECCOMP_RET_ALLOW,
-        });
+        let filter = build_seccomp_filter(self.seccomp_default_action());
 
         let prog = SockFprog {
             len: filter.len() as u16,
@@ -353,6 +390,15 @@ impl UnixSandbox {
     fn apply_seccomp_filter(&self) -> Result<(), String> {
         Ok(())
     }
+
+    /// Seccomp mode label for logs.
+    fn seccomp_mode(&self) -> &'static str {
+        if self.config.audit_only {
+            "audit"
+        } else {
+            "enforce"
+        }
+    }
 }
 
 impl Sandbox for UnixSandbox {
@@ -381,9 +427,18 @@ impl Sandbox for UnixSandbox {
                         ("max_memory_mb", &format!("{}", max_memory_mb)),
                         ("max_cpu_ms", &format!("{}", max_cpu_ms)),
                         ("cgroup_path", cgroup_path),
-                        ("seccomp", "enabled"),
+                        ("seccomp", self.seccomp_mode()),
                     ],
                 );
+                if self.config.audit_only {
+                    log_security_event(
+                        SecurityEvent::SandboxViolation,
+                        "Seccomp in audit mode: disallowed syscalls are logged by the kernel, not blocked",
+                        &[("cgroup_path", cgroup_path)],
+                    );
+                }
+                let _ = self.cgroup_path.set(cgroup_path.clone());
+                self.active.store(true, Ordering::SeqCst);
                 SandboxResult {
                     success: true,
                     error: None,
@@ -410,16 +465,16 @@ impl Sandbox for UnixSandbox {
     }
 
     fn is_active(&self) -> bool {
-        self.active
+        self.active.load(Ordering::SeqCst)
     }
 
     fn get_usage(&self) -> Option<SandboxUsage> {
-        if !self.active {
+        if !self.is_active() {
             return None;
         }
 
         // Read from cgroup files if available
-        if let Some(ref cgroup_path) = self.cgroup_path {
+        if let Some(cgroup_path) = self.cgroup_path.get() {
             let memory_path = format!("{}/memory.current", cgroup_path);
             let cpu_path = format!("{}/cpu.stat", cgroup_path);
 
@@ -487,10 +542,88 @@ mod tests {
         // this should return an error (not silently succeed)
         if !result.success {
             assert!(
-                result.error.unwrap().contains("Failed")
+                result.error.as_ref().unwrap().contains("Failed")
                     || result.error.unwrap().contains("not available")
             );
         }
         // If it succeeds, that's also valid (we have permissions)
     }
+
+    #[test]
+    fn test_audit_only_defaults_to_false() {
+        assert!(!SandboxConfig::default().audit_only);
+    }
+
+    /// Run `filter` on a syscall the way the kernel would, returning the action.
+    #[cfg(target_os = "linux")]
+    fn run_filter(filter: &[SockFilter], arch: u32, nr: u32) -> u32 {
+        let mut acc = 0;
+        let mut pc = 0;
+        loop {
+            let insn = &filter[pc];
+            pc += 1;
+            match insn.code {
+                c if c == bpf::LD | bpf_size::W | bpf_mode::ABS => {
+                    acc = if insn.k == 4 { arch } else { nr };
+                }
+                c if c == bpf::JMP | bpf_jmp::JEQ | bpf_src::K => {
+                    pc += usize::from(if acc == insn.k { insn.jt } else { insn.jf });
+                }
+                c if c == bpf::RET | bpf_src::K => return insn.k,
+                other => panic!("unexpected BPF opcode {:#x}", other),
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_seccomp_filter_allows_whitelist_and_applies_default() {
+        for default_action in [SECCOMP_RET_KILL_PROCESS, SECCOMP_RET_LOG] {
+            let filter = build_seccomp_filter(default_action);
+            for &nr in ALLOWED_SYSCALLS_X86_64 {
+                assert_eq!(
+                    run_filter(&filter, AUDIT_ARCH_X86_64, nr as u32),
+                    SECCOMP_RET_ALLOW,
+                    "syscall {} should be allowed",
+                    nr
+                );
+            }
+            // kill(2) and ptrace(2) are not whitelisted
+            for nr in [62, 101] {
+                assert_eq!(run_filter(&filter, AUDIT_ARCH_X86_64, nr), default_action);
+            }
+            assert_eq!(run_filter(&filter, AUDIT_ARCH_AARCH64, 0), default_action);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_audit_only_logs_instead_of_killing() {
+        let enforce = UnixSandbox::new(SandboxConfig::default());
+        let audit = UnixSandbox::new(SandboxConfig {
+            audit_only: true,
+            ..Default::default()
+        });
+        assert_eq!(enforce.seccomp_default_action(), SECCOMP_RET_KILL_PROCESS);
+        assert_eq!(audit.seccomp_default_action(), SECCOMP_RET_LOG);
+    }
+
+    #[test]
+    fn test_audit_only_apply_fails_only_where_enforcement_would() {
+        let sandbox = UnixSandbox::new(SandboxConfig {
+            audit_only: true,
+            ..Default::default()
+        });
+        let result = sandbox.apply();
+
+        // Audit mode changes only the seccomp action, so any failure is the
+        // same privilege or cgroup failure enforcement would hit
+        if result.success {
+            assert!(sandbox.is_active());
+            assert!(sandbox.get_usage().is_some());
+        } else {
+            assert!(!sandbox.is_active());
+            assert!(result.error.unwrap().contains("Sandbox enforcement failed"));
+        }
+    }
 }
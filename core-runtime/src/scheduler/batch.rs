@@ -1,9 +1,12 @@
 //! Request batching logic.
 
+use serde::Serialize;
+
 use super::queue::QueuedRequest;
+use crate::ipc::protocol::estimate_prompt_tokens;
 
 /// Configuration for batch processing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BatchConfig {
     pub max_batch_size: usize,
     pub max_total_tokens: usize,
@@ -64,16 +67,14 @@ impl BatchProcessor {
             return false;
         }
 
-        // Estimate token count from prompt bytes (avg ~4 chars per token)
-        let estimated_tokens = (request.prompt.len() + 3) / 4;
+        let estimated_tokens = estimate_prompt_tokens(&request.prompt);
         let new_total = batch.total_tokens + estimated_tokens;
         new_total <= self.config.max_total_tokens
     }
 
     /// Add a request to the batch.
     pub fn add(&self, batch: &mut RequestBatch, request: QueuedRequest) {
-        // Estimate token count from prompt bytes (avg ~4 chars per token)
-        let estimated_tokens = (request.prompt.len() + 3) / 4;
+        let estimated_tokens = estimate_prompt_tokens(&request.prompt);
         batch.total_tokens += estimated_tokens;
         batch.requests.push(request);
     }
@@ -3,11 +3,14 @@
 //! Caches outputs for identical prompts within a TTL window
 //! to avoid redundant inference computation.
 
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
+use tokio::sync::watch;
 
-use crate::engine::InferenceParams;
+use crate::engine::{InferenceParams, OutputEncoding, SamplerKind};
 
 /// Cached output for a completed request.
 #[derive(Debug, Clone)]
@@ -17,9 +20,10 @@ pub struct CachedOutput {
 }
 
 /// Configuration for output cache.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OutputCacheConfig {
     pub ttl: Duration,
+    /// Maximum cached outputs; 0 disables the cache.
     pub max_entries: usize,
 }
 
@@ -54,10 +58,18 @@ impl OutputCache {
         for &t in tokens {
             hasher.update(t.to_le_bytes());
         }
-        hasher.update(params.max_tokens.to_le_bytes());
-        hasher.update(params.temperature.to_le_bytes());
-        hasher.update(params.top_p.to_le_bytes());
-        hasher.update(params.top_k.to_le_bytes());
+        hash_params(&mut hasher, params);
+        hasher.finalize().into()
+    }
+
+    /// Compute cache key for a text request to `model_id`.
+    pub fn request_key(model_id: &str, prompt: &str, params: &InferenceParams) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update((model_id.len() as u64).to_le_bytes());
+        hasher.update(model_id.as_bytes());
+        hasher.update((prompt.len() as u64).to_le_bytes());
+        hasher.update(prompt.as_bytes());
+        hash_params(&mut hasher, params);
         hasher.finalize().into()
     }
 
@@ -73,6 +85,9 @@ impl OutputCache {
 
     /// Store output for future dedup.
     pub fn insert(&mut self, key: [u8; 32], output_tokens: Vec<u32>) {
+        if self.max_entries == 0 {
+            return;
+        }
         // Evict oldest if at capacity
         if self.entries.len() >= self.max_entries {
             self.evict_oldest();
@@ -112,6 +127,197 @@ impl OutputCache {
     }
 }
 
+/// Hash every param that can change the generated output.
+fn hash_params(hasher: &mut Sha256, params: &InferenceParams) {
+    hasher.update(params.max_tokens.to_le_bytes());
+    hasher.update(params.temperature.to_le_bytes());
+    hasher.update(params.top_p.to_le_bytes());
+    hasher.update(params.top_k.to_le_bytes());
+    hash_option(hasher, params.min_p.map(f32::to_le_bytes));
+    hasher.update(params.repetition_penalty.to_le_bytes());
+    hasher.update(params.no_repeat_ngram_size.to_le_bytes());
+    let mut bias: Vec<_> = params.logit_bias.iter().collect();
+    bias.sort_unstable_by_key(|&(&id, _)| id);
+    hasher.update((bias.len() as u64).to_le_bytes());
+    for (id, b) in bias {
+        hasher.update(id.to_le_bytes());
+        hasher.update(b.to_le_bytes());
+    }
+    hash_option(hasher, params.seed.map(u64::to_le_bytes));
+    hasher.update((params.stop_sequences.len() as u64).to_le_bytes());
+    for stop in &params.stop_sequences {
+        hasher.update((stop.len() as u64).to_le_bytes());
+        hasher.update(stop.as_bytes());
+    }
+    match params.sampler {
+        SamplerKind::Default => hasher.update([0]),
+        SamplerKind::Mirostat { tau, eta } => {
+            hasher.update([1]);
+            hasher.update(tau.to_le_bytes());
+            hasher.update(eta.to_le_bytes());
+        }
+    }
+    hasher.update(match params.output_encoding {
+        OutputEncoding::Utf8 => [0],
+        OutputEncoding::Bytes => [1],
+    });
+    // Limits can cut the output short
+    hash_option(hasher, params.timeout_ms.map(u64::to_le_bytes));
+    hash_option(hasher, params.max_cpu_ms.map(u64::to_le_bytes));
+}
+
+/// Hash a presence tag before `value`, so `None` never collides with the
+/// bytes of a following field.
+fn hash_option<const N: usize>(hasher: &mut Sha256, value: Option<[u8; N]>) {
+    match value {
+        None => hasher.update([0]),
+        Some(bytes) => {
+            hasher.update([1]);
+            hasher.update(bytes);
+        }
+    }
+}
+
+/// Shares one execution among identical requests.
+///
+/// The first caller for a key leads and runs the request; callers arriving
+/// while it runs wait for its result. Results the leader marks cacheable are
+/// replayed for `ttl` afterwards. `max_entries == 0` disables both.
+pub struct RequestDedup<T> {
+    ttl: Duration,
+    max_entries: usize,
+    state: Arc<Mutex<DedupState<T>>>,
+}
+
+struct DedupState<T> {
+    completed: HashMap<[u8; 32], (T, Instant)>,
+    in_flight: HashMap<[u8; 32], watch::Receiver<Option<T>>>,
+}
+
+/// What a caller should do with a request.
+pub enum DedupLookup<T> {
+    /// A recent identical request finished with this result.
+    Cached(T),
+    /// An identical request is running; wait for its result.
+    Wait(DedupWaiter<T>),
+    /// Run the request and hand the result to the leader.
+    Lead(DedupLeader<T>),
+}
+
+impl<T: Clone> RequestDedup<T> {
+    pub fn new(config: OutputCacheConfig) -> Self {
+        Self {
+            ttl: config.ttl,
+            max_entries: config.max_entries,
+            state: Arc::new(Mutex::new(DedupState {
+                completed: HashMap::new(),
+                in_flight: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Look up `key`; `None` when deduplication is disabled.
+    pub fn lookup(&self, key: [u8; 32]) -> Option<DedupLookup<T>> {
+        if self.max_entries == 0 {
+            return None;
+        }
+        let mut state = lock(&self.state);
+        if let Some((value, at)) = state.completed.get(&key) {
+            if at.elapsed() <= self.ttl {
+                return Some(DedupLookup::Cached(value.clone()));
+            }
+            state.completed.remove(&key);
+        }
+        if let Some(rx) = state.in_flight.get(&key) {
+            return Some(DedupLookup::Wait(DedupWaiter(rx.clone())));
+        }
+        let (tx, rx) = watch::channel(None);
+        state.in_flight.insert(key, rx);
+        Some(DedupLookup::Lead(DedupLeader {
+            key,
+            tx,
+            ttl: self.ttl,
+            max_entries: self.max_entries,
+            state: Arc::clone(&self.state),
+            finished: false,
+        }))
+    }
+
+    /// Forget completed results, e.g. after a model is replaced.
+    pub fn clear(&self) {
+        lock(&self.state).completed.clear();
+    }
+
+    /// Requests currently running as leaders.
+    pub fn in_flight(&self) -> usize {
+        lock(&self.state).in_flight.len()
+    }
+}
+
+fn lock<T>(state: &Mutex<DedupState<T>>) -> MutexGuard<'_, DedupState<T>> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Waits for the leader of an identical request.
+pub struct DedupWaiter<T>(watch::Receiver<Option<T>>);
+
+impl<T: Clone> DedupWaiter<T> {
+    /// The leader's result, or `None` if it gave up without one (e.g. it
+    /// was cancelled); the caller should then run the request itself.
+    pub async fn result(mut self) -> Option<T> {
+        let value = self.0.wait_for(Option::is_some).await.ok()?;
+        value.clone()
+    }
+}
+
+/// Runs a request on behalf of every identical caller.
+///
+/// Dropping it without [`finish`](Self::finish) releases the waiters with
+/// no result.
+pub struct DedupLeader<T> {
+    key: [u8; 32],
+    tx: watch::Sender<Option<T>>,
+    ttl: Duration,
+    max_entries: usize,
+    state: Arc<Mutex<DedupState<T>>>,
+    finished: bool,
+}
+
+impl<T: Clone> DedupLeader<T> {
+    /// Publish `value` to the waiters, and to later callers if `cacheable`.
+    pub fn finish(mut self, value: T, cacheable: bool) {
+        {
+            let mut state = lock(&self.state);
+            state.in_flight.remove(&self.key);
+            if cacheable {
+                let ttl = self.ttl;
+                state.completed.retain(|_, (_, at)| at.elapsed() <= ttl);
+                if state.completed.len() >= self.max_entries {
+                    let oldest = state
+                        .completed
+                        .iter()
+                        .min_by_key(|(_, (_, at))| *at)
+                        .map(|(k, _)| *k);
+                    if let Some(oldest) = oldest {
+                        state.completed.remove(&oldest);
+                    }
+                }
+                state.completed.insert(self.key, (value.clone(), Instant::now()));
+            }
+        }
+        self.finished = true;
+        self.tx.send_replace(Some(value));
+    }
+}
+
+impl<T> Drop for DedupLeader<T> {
+    fn drop(&mut self) {
+        if !self.finished {
+            lock(&self.state).in_flight.remove(&self.key);
+        }
+    }
+}
+
 /// Result of deduplication check.
 #[derive(Debug)]
 pub enum DedupResult {
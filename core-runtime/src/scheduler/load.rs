@@ -0,0 +1,115 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Load factor computation for autoscaling hints.
+//!
+//! Collapses queue depth, in-flight concurrency, and queue wait time into a
+//! single number. 1.0 means every signal is at its configured limit; values
+//! above 1.0 mean the runtime is overloaded.
+
+/// Weights and limits used to normalize load signals.
+#[derive(Debug, Clone)]
+pub struct LoadFactorConfig {
+    /// Concurrency at which the in-flight signal reads 1.0.
+    pub max_in_flight: usize,
+    /// Queue wait (ms) at which the wait signal reads 1.0.
+    pub target_queue_wait_ms: u64,
+    pub queue_weight: f64,
+    pub concurrency_weight: f64,
+    pub wait_weight: f64,
+}
+
+impl Default for LoadFactorConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 64,
+            target_queue_wait_ms: 1000,
+            queue_weight: 0.4,
+            concurrency_weight: 0.4,
+            wait_weight: 0.2,
+        }
+    }
+}
+
+/// Point-in-time load signals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadSample {
+    pub queue_depth: usize,
+    pub queue_capacity: usize,
+    pub in_flight: usize,
+    /// Wait time of the oldest queued request.
+    pub queue_wait_ms: u64,
+}
+
+impl LoadSample {
+    /// Weighted load factor; unbounded above so overload stays visible.
+    pub fn load_factor(&self, config: &LoadFactorConfig) -> f64 {
+        let queue = ratio(self.queue_depth as f64, self.queue_capacity as f64);
+        let concurrency = ratio(self.in_flight as f64, config.max_in_flight as f64);
+        let wait = ratio(self.queue_wait_ms as f64, config.target_queue_wait_ms as f64);
+
+        let total_weight = config.queue_weight + config.concurrency_weight + config.wait_weight;
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        (queue * config.queue_weight
+            + concurrency * config.concurrency_weight
+            + wait * config.wait_weight)
+            / total_weight
+    }
+}
+
+fn ratio(value: f64, limit: f64) -> f64 {
+    if limit <= 0.0 {
+        0.0
+    } else {
+        value / limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(queue_depth: usize, in_flight: usize) -> LoadSample {
+        LoadSample { queue_depth, queue_capacity: 100, in_flight, queue_wait_ms: 0 }
+    }
+
+    #[test]
+    fn test_idle_is_zero() {
+        let config = LoadFactorConfig::default();
+        assert_eq!(LoadSample::default().load_factor(&config), 0.0);
+    }
+
+    #[test]
+    fn test_rises_with_queue_depth() {
+        let config = LoadFactorConfig::default();
+        let low = sample(10, 0).load_factor(&config);
+        let high = sample(80, 0).load_factor(&config);
+        assert!(low > 0.0);
+        assert!(high > low);
+    }
+
+    #[test]
+    fn test_rises_with_in_flight_fraction() {
+        let config = LoadFactorConfig { max_in_flight: 10, ..Default::default() };
+        let low = sample(0, 2).load_factor(&config);
+        let high = sample(0, 9).load_factor(&config);
+        assert!(high > low);
+    }
+
+    #[test]
+    fn test_saturation_reaches_one_and_overload_exceeds() {
+        let config = LoadFactorConfig { max_in_flight: 10, ..Default::default() };
+        let saturated = LoadSample {
+            queue_depth: 100,
+            queue_capacity: 100,
+            in_flight: 10,
+            queue_wait_ms: 1000,
+        };
+        assert!((saturated.load_factor(&config) - 1.0).abs() < 1e-9);
+
+        let overloaded = LoadSample { queue_wait_ms: 5000, ..saturated };
+        assert!(overloaded.load_factor(&config) > 1.0);
+    }
+}
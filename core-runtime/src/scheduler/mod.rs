@@ -6,19 +6,29 @@
 mod batch;
 pub mod continuous;
 mod dedup;
+mod load;
 mod pool;
 mod priority;
 mod queue;
+mod request_id;
 pub mod thread_pool;
 
 pub use batch::{BatchConfig, BatchProcessor, RequestBatch};
 pub use continuous::{
     BatchSlot, ContinuousBatcher, PendingRequest, RequestId, RequestPhase, StepResult,
 };
-pub use dedup::{CachedOutput, DedupResult, OutputCache, OutputCacheConfig};
+pub use dedup::{
+    CachedOutput, DedupLeader, DedupLookup, DedupResult, DedupWaiter, OutputCache,
+    OutputCacheConfig, RequestDedup,
+};
+pub use load::{LoadFactorConfig, LoadSample};
 pub use pool::ThreadPoolConfig;
 pub use priority::{Priority, PriorityQueue};
-pub use queue::{QueuedRequest, RequestQueue, RequestQueueConfig};
+pub use queue::{
+    ModelAffinity, PriorityCaps, QueueError, QueueFairness, QueueTicket, QueuedRequest,
+    RequestQueue, RequestQueueConfig,
+};
+pub use request_id::{RequestIdAllocator, RequestOrigin};
 pub use thread_pool::{
     TaskPriority, ThreadPool, ThreadPoolConfig as TunableThreadPoolConfig, ThreadPoolStats,
 };
@@ -3,8 +3,11 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
-/// Priority level for inference requests.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+/// Priority level for inference requests, ordered `Low` to `Critical`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Priority {
     Low = 0,
     Normal = 1,
@@ -84,6 +87,47 @@ impl<T> PriorityQueue<T> {
         self.heap.pop().map(|p| p.item)
     }
 
+    /// Pop the highest priority item satisfying `pred`, leaving the rest queued.
+    pub fn pop_matching(&mut self, mut pred: impl FnMut(&T) -> bool) -> Option<T> {
+        let mut skipped = Vec::new();
+        let found = loop {
+            match self.heap.pop() {
+                Some(entry) if pred(&entry.item) => break Some(entry.item),
+                Some(entry) => skipped.push(entry),
+                None => break None,
+            }
+        };
+        // Skipped entries keep their sequence numbers, so FIFO order holds
+        self.heap.extend(skipped);
+        found
+    }
+
+    /// Pop from the highest priority level holding an item that satisfies
+    /// `pred`, choosing the item with the lowest `rank` (FIFO among ties).
+    pub fn pop_ranked<K: Ord>(
+        &mut self,
+        mut pred: impl FnMut(&T) -> bool,
+        mut rank: impl FnMut(&T) -> K,
+    ) -> Option<T> {
+        let mut entries = std::mem::take(&mut self.heap).into_vec();
+        let top = entries
+            .iter()
+            .filter(|e| pred(&e.item))
+            .map(|e| e.priority as u8)
+            .max();
+        let best = top.and_then(|top| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.priority as u8 == top && pred(&e.item))
+                .min_by_key(|(_, e)| (rank(&e.item), e.sequence))
+                .map(|(index, _)| index)
+        });
+        let found = best.map(|index| entries.swap_remove(index).item);
+        self.heap = entries.into();
+        found
+    }
+
     pub fn peek(&self) -> Option<&T> {
         self.heap.peek().map(|p| &p.item)
     }
@@ -96,6 +140,19 @@ impl<T> PriorityQueue<T> {
         self.heap.is_empty()
     }
 
+    /// Number of queued items at the given priority.
+    pub fn count_at(&self, priority: Priority) -> usize {
+        self.heap.iter().filter(|p| p.priority == priority).count()
+    }
+
+    /// Remove and return every item (not in priority order).
+    pub fn drain(&mut self) -> Vec<T> {
+        std::mem::take(&mut self.heap)
+            .into_iter()
+            .map(|p| p.item)
+            .collect()
+    }
+
     /// Iterate over items in the queue (not in priority order).
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.heap.iter().map(|p| &p.item)
@@ -0,0 +1,119 @@
+//! Queue depth, fairness and model affinity settings.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::scheduler::priority::Priority;
+
+/// Configuration for request queue.
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestQueueConfig {
+    pub max_pending: usize,
+    /// Per-priority depth caps applied within `max_pending`.
+    pub priority_caps: PriorityCaps,
+    /// Order of dequeue within a priority level.
+    pub fairness: QueueFairness,
+    /// How long an enqueue waits for a free slot when `max_pending` is
+    /// reached. Waiters are admitted in arrival order. `None` fails at once.
+    pub enqueue_timeout: Option<Duration>,
+}
+
+impl Default for RequestQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_pending: 256,
+            priority_caps: PriorityCaps::default(),
+            fairness: QueueFairness::default(),
+            enqueue_timeout: None,
+        }
+    }
+}
+
+/// Dequeue order among requests of equal priority.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueFairness {
+    /// Strict arrival order; one busy session can drain ahead of others.
+    #[default]
+    Fifo,
+    /// Alternate across sessions with pending requests, FIFO within each.
+    SessionRoundRobin,
+}
+
+/// Turn bookkeeping for `QueueFairness::SessionRoundRobin`.
+#[derive(Debug, Default)]
+pub(super) struct SessionRotation {
+    turn: u64,
+    /// Turn at which each session was last served; unseen sessions go first.
+    pub(super) last_served: HashMap<Option<String>, u64>,
+}
+
+impl SessionRotation {
+    pub(super) fn rank(&self, session: &Option<String>) -> u64 {
+        self.last_served.get(session).copied().unwrap_or(0)
+    }
+
+    pub(super) fn mark_served(&mut self, session: Option<String>) {
+        self.turn += 1;
+        self.last_served.insert(session, self.turn);
+    }
+}
+
+/// Maximum pending requests per priority level.
+///
+/// Capping `Low` (and `Normal`) below `max_pending` reserves the remaining
+/// slots for higher priorities, so a low-priority flood cannot block
+/// `High`/`Critical` admission. `None` means only `max_pending` applies.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PriorityCaps {
+    pub low: Option<usize>,
+    pub normal: Option<usize>,
+    pub high: Option<usize>,
+    pub critical: Option<usize>,
+}
+
+impl PriorityCaps {
+    /// Cap for the given priority, if any.
+    pub fn cap(&self, priority: Priority) -> Option<usize> {
+        match priority {
+            Priority::Low => self.low,
+            Priority::Normal => self.normal,
+            Priority::High => self.high,
+            Priority::Critical => self.critical,
+        }
+    }
+}
+
+/// Which models a worker will dequeue requests for.
+///
+/// Binding a worker to a subset of models keeps each model's working set
+/// hot on one NUMA node or GPU when several workers share a queue.
+#[derive(Debug, Clone, Default)]
+pub enum ModelAffinity {
+    /// Serve requests for any model.
+    #[default]
+    Any,
+    /// Serve only requests whose `model_id` is in the set.
+    Only(HashSet<String>),
+}
+
+impl ModelAffinity {
+    /// Affinity restricted to the given model IDs.
+    pub fn only<I, S>(models: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Only(models.into_iter().map(Into::into).collect())
+    }
+
+    /// Check whether a request for `model_id` may be served.
+    pub fn allows(&self, model_id: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Only(models) => models.contains(model_id),
+        }
+    }
+}
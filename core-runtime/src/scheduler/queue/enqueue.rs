@@ -0,0 +1,107 @@
+//! Admitting requests into the queue.
+
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use super::{QueueError, QueueTicket, QueuedRequest, RequestQueue};
+use crate::engine::InferenceParams;
+use crate::scheduler::priority::Priority;
+use crate::scheduler::request_id::{RequestIdAllocator, RequestOrigin};
+
+impl RequestQueue {
+    /// Enqueue a new request. Returns request ID and queue position.
+    pub async fn enqueue(
+        &self,
+        model_id: String,
+        prompt: String,
+        params: InferenceParams,
+        priority: Priority,
+    ) -> Result<(u64, usize), QueueError> {
+        self.enqueue_for_session(None, model_id, prompt, params, priority).await
+    }
+
+    /// Enqueue a request tagged with the session it came from.
+    pub async fn enqueue_for_session(
+        &self,
+        session: Option<String>,
+        model_id: String,
+        prompt: String,
+        params: InferenceParams,
+        priority: Priority,
+    ) -> Result<(u64, usize), QueueError> {
+        self.enqueue_from(RequestOrigin::default(), session, model_id, prompt, params, priority)
+            .await
+    }
+
+    /// Enqueue a request tagged with its entry point and session.
+    ///
+    /// IDs come from [`RequestIdAllocator::global`], so they are unique
+    /// across every queue and entry point in the process.
+    pub async fn enqueue_from(
+        &self,
+        origin: RequestOrigin,
+        session: Option<String>,
+        model_id: String,
+        prompt: String,
+        params: InferenceParams,
+        priority: Priority,
+    ) -> Result<(u64, usize), QueueError> {
+        self.enqueue_tracked(origin, session, model_id, prompt, params, priority)
+            .await
+            .map(|ticket| (ticket.id, ticket.position))
+    }
+
+    /// Enqueue a request and return a ticket that observes its cancellation.
+    ///
+    /// When the queue is full this waits up to `enqueue_timeout` for a slot.
+    /// Priority caps are not waited on.
+    pub async fn enqueue_tracked(
+        &self,
+        origin: RequestOrigin,
+        session: Option<String>,
+        model_id: String,
+        prompt: String,
+        params: InferenceParams,
+        priority: Priority,
+    ) -> Result<QueueTicket, QueueError> {
+        let slot = match self.config.enqueue_timeout {
+            None => self.slots.try_acquire().map_err(|_| QueueError::QueueFull)?,
+            Some(timeout) => tokio::time::timeout(timeout, self.slots.acquire())
+                .await
+                .map_err(|_| QueueError::EnqueueTimeout(timeout))?
+                .map_err(|_| QueueError::QueueFull)?,
+        };
+        let mut queue = self.queue.lock().await;
+
+        if let Some(cap) = self.config.priority_caps.cap(priority) {
+            if queue.count_at(priority) >= cap {
+                return Err(QueueError::PriorityCapReached(priority));
+            }
+        }
+
+        let id = RequestIdAllocator::global().allocate(origin);
+        let enqueued_at = Instant::now();
+        let deadline = params.timeout_ms.map(|ms| enqueued_at + Duration::from_millis(ms));
+        let request = QueuedRequest {
+            id,
+            origin,
+            model_id,
+            prompt,
+            params,
+            session,
+            enqueued_at,
+            deadline,
+            cancelled: CancellationToken::new(),
+        };
+        let ticket = QueueTicket {
+            id,
+            position: queue.len(),
+            cancelled: request.cancelled.clone(),
+        };
+        queue.push(request, priority);
+        // The slot is returned by whichever path removes the request
+        slot.forget();
+
+        Ok(ticket)
+    }
+}
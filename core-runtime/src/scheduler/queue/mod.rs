@@ -0,0 +1,199 @@
+//! Request queue management.
+
+mod config;
+mod enqueue;
+mod request;
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+
+use super::priority::PriorityQueue;
+use config::SessionRotation;
+
+pub use config::{ModelAffinity, PriorityCaps, QueueFairness, RequestQueueConfig};
+pub use request::{QueueError, QueueTicket, QueuedRequest};
+
+/// Thread-safe request queue with priority support.
+pub struct RequestQueue {
+    queue: Arc<Mutex<PriorityQueue<QueuedRequest>>>,
+    /// Free slots under `max_pending`; returned whenever a request leaves.
+    slots: Semaphore,
+    /// Current `max_pending`; may change after construction.
+    max_pending: AtomicUsize,
+    /// Slots still to be withheld after `max_pending` shrank below the
+    /// number of queued requests.
+    slot_debt: AtomicUsize,
+    config: RequestQueueConfig,
+    rotation: std::sync::Mutex<SessionRotation>,
+}
+
+impl RequestQueue {
+    /// Queue holding up to `config.max_pending` requests, clamped to
+    /// [`Semaphore::MAX_PERMITS`].
+    pub fn new(config: RequestQueueConfig) -> Self {
+        let max_pending = clamp_max_pending(config.max_pending);
+        Self {
+            queue: Arc::new(Mutex::new(PriorityQueue::new())),
+            slots: Semaphore::new(max_pending),
+            max_pending: AtomicUsize::new(max_pending),
+            slot_debt: AtomicUsize::new(0),
+            config,
+            rotation: std::sync::Mutex::new(SessionRotation::default()),
+        }
+    }
+
+    /// Cancel a pending request by ID. Returns true if found and cancelled.
+    pub async fn cancel(&self, request_id: u64) -> bool {
+        let queue = self.queue.lock().await;
+        for request in queue.iter() {
+            if request.id == request_id {
+                request.cancel();
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Like [`cancel`](Self::cancel), but only for a request enqueued by
+    /// `session`; another session's request with the same ID is left alone.
+    pub async fn cancel_for_session(&self, request_id: u64, session: Option<&str>) -> bool {
+        let queue = self.queue.lock().await;
+        match queue
+            .iter()
+            .find(|r| r.id == request_id && r.session.as_deref() == session)
+        {
+            Some(request) => {
+                request.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a request that finished without being dequeued, returning its
+    /// slot. Returns false if it already left the queue.
+    pub async fn complete(&self, request_id: u64) -> bool {
+        let removed = self
+            .queue
+            .lock()
+            .await
+            .pop_matching(|r| r.id == request_id)
+            .is_some();
+        if removed {
+            self.release_slots(1);
+        }
+        removed
+    }
+
+    /// Drain every pending request, cancelling each one.
+    ///
+    /// Callers holding a [`QueueTicket`] receive [`QueueError::Cancelled`].
+    /// Entries that already finished (cancelled or expired) are removed
+    /// too, returning their slots, but not counted. Returns the number of
+    /// requests cancelled.
+    pub async fn cancel_all(&self) -> usize {
+        let drained = self.queue.lock().await.drain();
+        self.release_slots(drained.len());
+        let mut cancelled = 0;
+        for request in drained.iter().filter(|r| !r.is_cancelled() && !r.is_expired()) {
+            request.cancel();
+            cancelled += 1;
+        }
+        cancelled
+    }
+
+    /// Dequeue the highest priority request, skipping cancelled/expired.
+    pub async fn dequeue(&self) -> Option<QueuedRequest> {
+        self.dequeue_for(&ModelAffinity::Any).await
+    }
+
+    /// Dequeue the highest priority request allowed by `affinity`, skipping
+    /// cancelled/expired. Requests for other models stay queued in order.
+    pub async fn dequeue_for(&self, affinity: &ModelAffinity) -> Option<QueuedRequest> {
+        let mut queue = self.queue.lock().await;
+        loop {
+            let allowed = |r: &QueuedRequest| affinity.allows(&r.model_id);
+            let request = match self.config.fairness {
+                QueueFairness::Fifo => queue.pop_matching(allowed)?,
+                QueueFairness::SessionRoundRobin => {
+                    let mut rotation = self.rotation.lock().unwrap_or_else(|e| e.into_inner());
+                    let request = queue.pop_ranked(allowed, |r| rotation.rank(&r.session))?;
+                    rotation.mark_served(request.session.clone());
+                    // Forget sessions with nothing pending so the map stays bounded
+                    if rotation.last_served.len() > queue.len() {
+                        let pending: HashSet<&Option<String>> =
+                            queue.iter().map(|r| &r.session).collect();
+                        rotation.last_served.retain(|session, _| pending.contains(session));
+                    }
+                    request
+                }
+            };
+            self.release_slots(1);
+            if request.is_cancelled() || request.is_expired() {
+                continue; // Skip cancelled/expired requests
+            }
+            return Some(request);
+        }
+    }
+
+    /// Current queue length.
+    pub async fn len(&self) -> usize {
+        self.queue.lock().await.len()
+    }
+
+    /// Check if queue is empty.
+    pub async fn is_empty(&self) -> bool {
+        self.queue.lock().await.is_empty()
+    }
+
+    /// Maximum number of pending requests.
+    pub fn max_pending(&self) -> usize {
+        self.max_pending.load(Ordering::SeqCst)
+    }
+
+    /// Change `max_pending`. Growing admits waiting enqueues at once;
+    /// shrinking below the current depth never evicts, new requests are
+    /// refused until enough have left. Values above
+    /// [`Semaphore::MAX_PERMITS`] are clamped to it.
+    pub fn set_max_pending(&self, max_pending: usize) {
+        let max_pending = clamp_max_pending(max_pending);
+        let previous = self.max_pending.swap(max_pending, Ordering::SeqCst);
+        if max_pending >= previous {
+            self.release_slots(max_pending - previous);
+        } else {
+            let shrink = previous - max_pending;
+            let withheld = self.slots.forget_permits(shrink);
+            self.slot_debt.fetch_add(shrink - withheld, Ordering::SeqCst);
+        }
+    }
+
+    /// Return `n` slots, first settling any debt from a shrink.
+    fn release_slots(&self, n: usize) {
+        let debt = self
+            .slot_debt
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |debt| Some(debt.saturating_sub(n)))
+            .unwrap_or_else(|debt| debt);
+        self.slots.add_permits(n - debt.min(n));
+    }
+
+    /// How long the oldest pending request has been waiting.
+    pub async fn oldest_wait(&self) -> Option<Duration> {
+        let queue = self.queue.lock().await;
+        queue.iter().map(|r| r.enqueued_at.elapsed()).max()
+    }
+}
+
+/// `max_pending` capped at the most slots a semaphore can hold.
+fn clamp_max_pending(max_pending: usize) -> usize {
+    if max_pending > Semaphore::MAX_PERMITS {
+        tracing::warn!(
+            max_pending,
+            limit = Semaphore::MAX_PERMITS,
+            "max_pending above the queue limit, clamping"
+        );
+    }
+    max_pending.min(Semaphore::MAX_PERMITS)
+}
@@ -0,0 +1,132 @@
+//! Queued requests, the tickets handed to their callers, and queue errors.
+
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::engine::InferenceParams;
+use crate::scheduler::priority::Priority;
+use crate::scheduler::request_id::RequestOrigin;
+
+/// A queued inference request with timeout and cancellation support.
+#[derive(Debug)]
+pub struct QueuedRequest {
+    pub id: u64,
+    /// Entry point the request arrived through.
+    pub origin: RequestOrigin,
+    pub model_id: String,
+    /// Text prompt for inference.
+    pub prompt: String,
+    pub params: InferenceParams,
+    /// Non-secret key grouping requests from one session for fair dequeue.
+    pub session: Option<String>,
+    pub enqueued_at: Instant,
+    pub deadline: Option<Instant>,
+    pub(super) cancelled: CancellationToken,
+}
+
+impl Clone for QueuedRequest {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            origin: self.origin,
+            model_id: self.model_id.clone(),
+            prompt: self.prompt.clone(),
+            params: self.params.clone(),
+            session: self.session.clone(),
+            enqueued_at: self.enqueued_at,
+            deadline: self.deadline,
+            cancelled: self.cancelled.clone(),
+        }
+    }
+}
+
+impl QueuedRequest {
+    /// Create a new queued request. Used for testing and batch processing.
+    pub fn new(
+        id: u64,
+        model_id: String,
+        prompt: String,
+        params: InferenceParams,
+    ) -> Self {
+        let enqueued_at = Instant::now();
+        let deadline = params.timeout_ms.map(|ms| enqueued_at + Duration::from_millis(ms));
+        Self {
+            id,
+            origin: RequestOrigin::default(),
+            model_id,
+            prompt,
+            params,
+            session: None,
+            enqueued_at,
+            deadline,
+            cancelled: CancellationToken::new(),
+        }
+    }
+
+    /// Check if request has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.is_cancelled()
+    }
+
+    /// Check if request has exceeded its deadline.
+    pub fn is_expired(&self) -> bool {
+        self.deadline.map_or(false, |d| Instant::now() > d)
+    }
+
+    /// Mark the request as cancelled.
+    pub fn cancel(&self) {
+        self.cancelled.cancel();
+    }
+}
+
+/// Handle returned to the caller of [`RequestQueue::enqueue_tracked`].
+///
+/// Shares the request's cancellation token, so the caller learns when the
+/// request is cancelled while pending (individually or by `cancel_all`).
+#[derive(Debug)]
+pub struct QueueTicket {
+    pub id: u64,
+    pub position: usize,
+    pub(super) cancelled: CancellationToken,
+}
+
+impl QueueTicket {
+    /// Check if the request has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.is_cancelled()
+    }
+
+    /// Resolve with [`QueueError::Cancelled`] once the request is cancelled.
+    pub async fn cancelled(&self) -> QueueError {
+        self.cancelled.cancelled().await;
+        QueueError::Cancelled
+    }
+}
+
+#[derive(Debug)]
+pub enum QueueError {
+    QueueFull,
+    /// The per-priority cap is reached even though total capacity remains.
+    PriorityCapReached(Priority),
+    /// The request was cancelled before it completed.
+    Cancelled,
+    /// The queue stayed full for the whole enqueue wait; safe to retry.
+    EnqueueTimeout(Duration),
+}
+
+impl std::fmt::Display for QueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QueueFull => write!(f, "request queue is full"),
+            Self::PriorityCapReached(p) => {
+                write!(f, "request queue is full for {:?} priority", p)
+            }
+            Self::Cancelled => write!(f, "request cancelled"),
+            Self::EnqueueTimeout(waited) => {
+                write!(f, "request queue stayed full for {:?}; retry later", waited)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueueError {}
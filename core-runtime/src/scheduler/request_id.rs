@@ -0,0 +1,93 @@
+//! Process-wide request ID allocation.
+//!
+//! IPC, FFI, and CLI requests share the queue and metrics, so their IDs come
+//! from one counter. Each allocation is tagged with the entry point it came
+//! through and counted under `core_requests_by_origin_total`.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Entry point a request arrived through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestOrigin {
+    #[default]
+    Ipc,
+    Ffi,
+    Cli,
+}
+
+impl RequestOrigin {
+    /// Metric label value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ipc => "ipc",
+            Self::Ffi => "ffi",
+            Self::Cli => "cli",
+        }
+    }
+}
+
+impl fmt::Display for RequestOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Monotonic request ID source. IDs start at 1 and are never reused.
+#[derive(Debug)]
+pub struct RequestIdAllocator {
+    next: AtomicU64,
+}
+
+static GLOBAL: RequestIdAllocator = RequestIdAllocator::new();
+
+impl RequestIdAllocator {
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicU64::new(1),
+        }
+    }
+
+    /// The allocator shared by every entry point in this process.
+    pub fn global() -> &'static RequestIdAllocator {
+        &GLOBAL
+    }
+
+    /// Allocate the next ID for a request from `origin`.
+    pub fn allocate(&self, origin: RequestOrigin) -> u64 {
+        crate::telemetry::record_request_origin(origin.as_str());
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl Default for RequestIdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_sequential_across_origins() {
+        let allocator = RequestIdAllocator::new();
+        assert_eq!(allocator.allocate(RequestOrigin::Ipc), 1);
+        assert_eq!(allocator.allocate(RequestOrigin::Ffi), 2);
+        assert_eq!(allocator.allocate(RequestOrigin::Cli), 3);
+    }
+
+    #[test]
+    fn test_origin_labels() {
+        assert_eq!(RequestOrigin::default(), RequestOrigin::Ipc);
+        assert_eq!(RequestOrigin::Ffi.to_string(), "ffi");
+        assert_eq!(
+            serde_json::to_string(&RequestOrigin::Cli).unwrap(),
+            "\"cli\""
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Durable JSON-lines audit file with size-based rotation.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Append-only JSON-lines audit file with size-based rotation.
+///
+/// Lines are only ever appended and generations only ever renamed, so a
+/// crash leaves every file holding whole events (at worst a torn last line).
+pub(super) struct AuditFile {
+    path: PathBuf,
+    max_bytes: usize,
+    generations: usize,
+    /// Handle on `path`; `None` after a rotation that could not reopen it
+    file: Option<File>,
+    len: u64,
+}
+
+impl AuditFile {
+    pub(super) fn open(
+        path: PathBuf,
+        max_bytes: usize,
+        generations: usize,
+    ) -> std::io::Result<Self> {
+        let mut audit_file = Self {
+            path,
+            max_bytes,
+            generations,
+            file: None,
+            len: 0,
+        };
+        audit_file.reopen()?;
+        Ok(audit_file)
+    }
+
+    /// Append `line`, rotating first if it would push the file past the cap.
+    ///
+    /// A failed rotation is reported, but the line still goes to the live file.
+    pub(super) fn append(&mut self, line: &str) -> std::io::Result<()> {
+        let size = line.len() as u64 + 1;
+        let rotated = if self.len > 0 && self.len + size > self.max_bytes as u64 {
+            self.rotate()
+        } else {
+            Ok(())
+        };
+        if self.file.is_none() {
+            self.reopen()?;
+        }
+        let mut bytes = Vec::with_capacity(line.len() + 1);
+        bytes.extend_from_slice(line.as_bytes());
+        bytes.push(b'\n');
+        if let Some(file) = &mut self.file {
+            file.write_all(&bytes)?;
+        }
+        self.len += size;
+        rotated
+    }
+
+    /// Shift `<file>.N-1` to `<file>.N` (dropping the oldest), move the live
+    /// file to `<file>.1` and start a new one.
+    ///
+    /// The live file is reopened however far the shift got, so a handle on
+    /// a file already renamed to `<file>.1` is never written to again.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        let shifted = self.shift_generations();
+        self.reopen()?;
+        shifted
+    }
+
+    fn shift_generations(&self) -> std::io::Result<()> {
+        if self.generations == 0 {
+            return std::fs::remove_file(&self.path);
+        }
+        remove_if_exists(&generation_path(&self.path, self.generations))?;
+        for n in (1..self.generations).rev() {
+            let from = generation_path(&self.path, n);
+            if from.exists() {
+                std::fs::rename(&from, generation_path(&self.path, n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, generation_path(&self.path, 1))
+    }
+
+    /// Open (or create) the live file and pick up its current length.
+    fn reopen(&mut self) -> std::io::Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.len = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+/// Append `line` to `file` on the blocking pool, keeping file I/O off the
+/// async workers. Outside a tokio runtime the write happens in place.
+pub(super) async fn append_line(file: &Arc<Mutex<AuditFile>>, line: String) -> std::io::Result<()> {
+    let file = Arc::clone(file);
+    let write = move || file.lock().unwrap_or_else(|e| e.into_inner()).append(&line);
+    if tokio::runtime::Handle::try_current().is_err() {
+        return write();
+    }
+    tokio::task::spawn_blocking(write)
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Path of rotated generation `n` of `path` (`audit.log` -> `audit.log.n`).
+fn generation_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{AuditCategory, AuditConfig, AuditEvent, AuditLogger, AuditSeverity};
+    use std::path::Path;
+
+    fn file_config(dir: &Path, max_file_bytes: usize, generations: usize) -> AuditConfig {
+        AuditConfig {
+            log_to_stdout: false,
+            file_path: Some(dir.join("audit.log")),
+            max_file_bytes,
+            max_file_generations: generations,
+            ..Default::default()
+        }
+    }
+
+    fn numbered_event(i: usize) -> AuditEvent {
+        AuditEvent::builder()
+            .severity(AuditSeverity::Info)
+            .category(AuditCategory::System)
+            .event_type("test")
+            .message(format!("Event {}", i))
+            .source("test")
+            .build()
+            .unwrap()
+    }
+
+    fn read_events(path: &Path) -> Vec<AuditEvent> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_file_appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = AuditLogger::new(file_config(dir.path(), 1024 * 1024, 3));
+
+        for i in 0..3 {
+            logger.log(numbered_event(i)).await;
+        }
+
+        let events = read_events(&dir.path().join("audit.log"));
+        let messages: Vec<_> = events.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["Event 0", "Event 1", "Event 2"]);
+        assert!(!dir.path().join("audit.log.1").exists());
+    }
+
+    #[tokio::test]
+    async fn test_file_rotates_past_size_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let line_len = numbered_event(0).to_json().unwrap().len() + 1;
+        // Room for two events per file
+        let logger = AuditLogger::new(file_config(dir.path(), line_len * 2 + line_len / 2, 3));
+
+        for i in 0..5 {
+            logger.log(numbered_event(i)).await;
+        }
+
+        let message = |path: &str| -> Vec<String> {
+            read_events(&dir.path().join(path))
+                .into_iter()
+                .map(|e| e.message)
+                .collect()
+        };
+        assert_eq!(message("audit.log"), ["Event 4"]);
+        assert_eq!(message("audit.log.1"), ["Event 2", "Event 3"]);
+        assert_eq!(message("audit.log.2"), ["Event 0", "Event 1"]);
+        for path in ["audit.log", "audit.log.1", "audit.log.2"] {
+            let len = std::fs::metadata(dir.path().join(path)).unwrap().len();
+            assert!(len as usize <= line_len * 2 + line_len / 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_prunes_generations_beyond_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        // Every event rotates the one before it out
+        let logger = AuditLogger::new(file_config(dir.path(), 1, 2));
+
+        for i in 0..6 {
+            logger.log(numbered_event(i)).await;
+        }
+
+        assert_eq!(read_events(&dir.path().join("audit.log"))[0].message, "Event 5");
+        assert_eq!(read_events(&dir.path().join("audit.log.1"))[0].message, "Event 4");
+        assert_eq!(read_events(&dir.path().join("audit.log.2"))[0].message, "Event 3");
+        assert!(!dir.path().join("audit.log.3").exists());
+    }
+
+    #[tokio::test]
+    async fn test_file_leaves_memory_buffer_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AuditConfig {
+            max_events: 3,
+            ..file_config(dir.path(), 1, 1)
+        };
+        let logger = AuditLogger::new(config);
+
+        for i in 0..5 {
+            logger.log(numbered_event(i)).await;
+        }
+
+        let messages: Vec<_> = logger
+            .get_events()
+            .await
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, ["Event 2", "Event 3", "Event 4"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_reopen_continues_appending() {
+        let dir = tempfile::tempdir().unwrap();
+        AuditLogger::new(file_config(dir.path(), 1024 * 1024, 3))
+            .log(numbered_event(0))
+            .await;
+
+        AuditLogger::new(file_config(dir.path(), 1024 * 1024, 3))
+            .log(numbered_event(1))
+            .await;
+
+        assert_eq!(read_events(&dir.path().join("audit.log")).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_file_keeps_appending_after_failed_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        // A non-empty directory where the oldest generation goes cannot be removed
+        std::fs::create_dir_all(dir.path().join("audit.log.1").join("blocker")).unwrap();
+        let logger = AuditLogger::new(file_config(dir.path(), 1, 1));
+
+        for i in 0..3 {
+            logger.log(numbered_event(i)).await;
+        }
+
+        let messages: Vec<_> = read_events(&dir.path().join("audit.log"))
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, ["Event 0", "Event 1", "Event 2"]);
+    }
+}
@@ -3,15 +3,21 @@
 //! Provides comprehensive security audit logging and compliance features:
 //! - Structured audit events with severity levels
 //! - Configurable retention policies
+//! - Durable JSON-lines audit files with size-based rotation
 //! - Integration with SIEM systems
 //! - Compliance reporting (SOC2, HIPAA, GDPR)
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
+mod file;
+
+use file::AuditFile;
+
 /// Audit event severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum AuditSeverity {
@@ -55,6 +61,8 @@ pub enum AuditCategory {
     ModelOperation,
     /// System events (startup, shutdown)
     System,
+    /// Security events (resource limits hit, policy violations)
+    Security,
 }
 
 impl std::fmt::Display for AuditCategory {
@@ -68,6 +76,7 @@ impl std::fmt::Display for AuditCategory {
             AuditCategory::Network => write!(f, "NETWORK"),
             AuditCategory::ModelOperation => write!(f, "MODEL_OPERATION"),
             AuditCategory::System => write!(f, "SYSTEM"),
+            AuditCategory::Security => write!(f, "SECURITY"),
         }
     }
 }
@@ -232,6 +241,12 @@ pub struct AuditConfig {
     pub log_to_stdout: bool,
     /// Whether to include sensitive data (for debugging only)
     pub include_sensitive: bool,
+    /// File each event is also appended to as a JSON line (none by default)
+    pub file_path: Option<PathBuf>,
+    /// Size at which the file rotates to `<file>.1`, `<file>.2`, ...
+    pub max_file_bytes: usize,
+    /// Rotated generations kept beside the live file; older ones are deleted
+    pub max_file_generations: usize,
 }
 
 impl Default for AuditConfig {
@@ -241,6 +256,9 @@ impl Default for AuditConfig {
             max_events: 10000,
             log_to_stdout: true,
             include_sensitive: false,
+            file_path: None,
+            max_file_bytes: 10 * 1024 * 1024,
+            max_file_generations: 5,
         }
     }
 }
@@ -249,14 +267,23 @@ impl Default for AuditConfig {
 pub struct AuditLogger {
     config: AuditConfig,
     events: Arc<RwLock<Vec<AuditEvent>>>,
+    file: Option<Arc<Mutex<AuditFile>>>,
 }
 
 impl AuditLogger {
     /// Create a new audit logger
+    ///
+    /// If `file_path` cannot be opened, events are kept in memory only.
     pub fn new(config: AuditConfig) -> Self {
+        let file = config.file_path.clone().and_then(|path| {
+            AuditFile::open(path, config.max_file_bytes, config.max_file_generations)
+                .map_err(|e| tracing::warn!(error = %e, "audit log file unavailable"))
+                .ok()
+        });
         Self {
             config,
             events: Arc::new(RwLock::new(Vec::new())),
+            file: file.map(|file| Arc::new(Mutex::new(file))),
         }
     }
 
@@ -272,6 +299,17 @@ impl AuditLogger {
             println!("{}", event.to_log_string());
         }
 
+        // Append to the audit file if configured
+        if let Some(file) = &self.file {
+            let written = match event.to_json() {
+                Ok(line) => file::append_line(file, line).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = written {
+                tracing::warn!(error = %e, "failed to append audit event to file");
+            }
+        }
+
         // Store event
         let mut events = self.events.write().await;
         events.push(event);
@@ -382,6 +420,22 @@ pub fn audit_logger() -> Option<Arc<AuditLogger>> {
     AUDIT_LOGGER.get().cloned()
 }
 
+/// Record `event` with the global audit logger, if one is installed.
+///
+/// Inside a tokio runtime the write runs as a task; elsewhere it completes
+/// before returning, so callers outside a runtime are audited too.
+pub fn log_detached(event: AuditEvent) {
+    let Some(logger) = audit_logger() else {
+        return;
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => {
+            runtime.spawn(async move { logger.log(event).await });
+        }
+        Err(_) => futures::executor::block_on(logger.log(event)),
+    }
+}
+
 /// Convenience macro for audit logging
 #[macro_export]
 macro_rules! audit_log {
@@ -14,6 +14,13 @@
 //! generates a unique, installation-specific salt rather than a hardcoded value.
 //! This prevents attackers from deriving keys even if they know the machine ID.
 //!
+//! # PBKDF2 Iterations
+//!
+//! Password-derived keys use [`DEFAULT_PBKDF2_ITERATIONS`] unless configured
+//! otherwise. Encrypted files record the iteration count in their header, so
+//! files written with an older, lower count still decrypt; use
+//! [`ModelEncryption::reencrypt_file`] to upgrade them.
+//!
 //! # Key Zeroing
 //!
 //! All key material is securely zeroed when dropped using the `zeroize` crate.
@@ -42,6 +49,17 @@ pub const TAG_SIZE: usize = 16;
 pub const BLOCK_SIZE: usize = 16;
 /// Minimum salt size for security (16 bytes = 128 bits)
 pub const MIN_SALT_SIZE: usize = 16;
+/// PBKDF2 iterations for new password-derived keys (OWASP 2023, SHA-256)
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+/// Lowest PBKDF2 iteration count accepted for new keys
+pub const MIN_PBKDF2_ITERATIONS: u32 = 100_000;
+/// Highest PBKDF2 iteration count accepted, for new keys and file headers
+/// alike, so a crafted header cannot stall decryption
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10 * DEFAULT_PBKDF2_ITERATIONS;
+/// Iteration count of files written before the header recorded it
+pub const LEGACY_PBKDF2_ITERATIONS: u32 = 100_000;
+/// File format version written by `encrypt_file` (v3 adds PBKDF2 iterations)
+const FILE_VERSION: u8 = 3;
 /// Default salt file name
 const SALT_FILE_NAME: &str = ".gg-core-salt";
 /// Maximum nonce history to track for reuse detection
@@ -223,6 +241,10 @@ pub enum EncryptionError {
     AuthenticationFailed,
     /// Nonce reuse detected (critical security failure)
     NonceReuseDetected,
+    /// PBKDF2 iteration count below `MIN_PBKDF2_ITERATIONS`
+    IterationsTooLow(u32),
+    /// PBKDF2 iteration count above `MAX_PBKDF2_ITERATIONS`
+    IterationsTooHigh(u32),
 }
 
 impl std::fmt::Display for EncryptionError {
@@ -237,6 +259,16 @@ impl std::fmt::Display for EncryptionError {
             EncryptionError::NonceReuseDetected => {
                 write!(f, "CRITICAL: Nonce reuse detected - possible RNG failure")
             }
+            EncryptionError::IterationsTooLow(n) => write!(
+                f,
+                "PBKDF2 iteration count {} is below the minimum of {}",
+                n, MIN_PBKDF2_ITERATIONS
+            ),
+            EncryptionError::IterationsTooHigh(n) => write!(
+                f,
+                "PBKDF2 iteration count {} is above the maximum of {}",
+                n, MAX_PBKDF2_ITERATIONS
+            ),
         }
     }
 }
@@ -256,6 +288,25 @@ pub struct ModelEncryption {
     key: Zeroizing<[u8; KEY_SIZE]>,
     /// Whether hardware acceleration is available
     hw_accelerated: bool,
+    /// Password and salt, kept to re-derive keys for files written with a
+    /// different iteration count (`None` for raw keys)
+    #[zeroize(skip)]
+    password: Option<PasswordSource>,
+}
+
+/// Inputs to PBKDF2 for a password-derived key.
+struct PasswordSource {
+    password: Zeroizing<Vec<u8>>,
+    salt: Vec<u8>,
+    iterations: u32,
+}
+
+/// Parsed header of an encrypted model file.
+struct FileHeader {
+    is_legacy_ecb: bool,
+    /// PBKDF2 iterations the file's key was derived with (0 = raw key)
+    iterations: u32,
+    nonce: [u8; NONCE_SIZE],
 }
 
 impl ModelEncryption {
@@ -272,44 +323,80 @@ impl ModelEncryption {
         Self {
             key: Zeroizing::new(key),
             hw_accelerated,
+            password: None,
         }
     }
 
-    /// PBKDF2 iteration count (100,000 iterations per OWASP recommendations)
-    /// This provides resistance against brute-force attacks on passwords
-    const PBKDF2_ITERATIONS: u32 = 100_000;
-
     /// Create encryption handler from a password (derived key)
     ///
-    /// Uses PBKDF2-HMAC-SHA256 with 100,000 iterations for secure key derivation.
-    /// This provides resistance against brute-force and dictionary attacks.
+    /// Uses PBKDF2-HMAC-SHA256 with `DEFAULT_PBKDF2_ITERATIONS` (600,000) for
+    /// secure key derivation. This provides resistance against brute-force and
+    /// dictionary attacks.
     ///
     /// # Arguments
     /// * `password` - User-provided password
     /// * `salt` - Cryptographic salt (should be unique per password)
     ///
     /// # Security
-    /// - Uses PBKDF2 with 100,000 iterations (OWASP recommended minimum)
+    /// - Uses PBKDF2 with 600,000 iterations (OWASP 2023 recommendation)
     /// - Salt should be at least 16 bytes and unique per password
     /// - Password should be high entropy (use a password manager)
     /// - Derived key is securely zeroed on drop
     pub fn from_password(password: &str, salt: &[u8]) -> Self {
+        Self::derive(password.as_bytes(), salt, DEFAULT_PBKDF2_ITERATIONS)
+    }
+
+    /// Create encryption handler from a password with a custom PBKDF2 iteration count.
+    ///
+    /// Rejects counts outside `MIN_PBKDF2_ITERATIONS..=MAX_PBKDF2_ITERATIONS`.
+    pub fn from_password_with_iterations(
+        password: &str,
+        salt: &[u8],
+        iterations: u32,
+    ) -> Result<Self, EncryptionError> {
+        if iterations < MIN_PBKDF2_ITERATIONS {
+            return Err(EncryptionError::IterationsTooLow(iterations));
+        }
+        if iterations > MAX_PBKDF2_ITERATIONS {
+            return Err(EncryptionError::IterationsTooHigh(iterations));
+        }
+        Ok(Self::derive(password.as_bytes(), salt, iterations))
+    }
+
+    /// PBKDF2 iteration count of a password-derived key (`None` for raw keys)
+    pub fn iterations(&self) -> Option<u32> {
+        self.password.as_ref().map(|p| p.iterations)
+    }
+
+    fn derive(password: &[u8], salt: &[u8], iterations: u32) -> Self {
         let mut key = [0u8; KEY_SIZE];
 
         // Use PBKDF2-HMAC-SHA256 for secure key derivation
-        pbkdf2_hmac::<Sha256>(
-            password.as_bytes(),
-            salt,
-            Self::PBKDF2_ITERATIONS,
-            &mut key[..],
-        );
+        pbkdf2_hmac::<Sha256>(password, salt, iterations, &mut key[..]);
 
-        let result = Self::new(key);
+        let mut result = Self::new(key);
         // Zero the local key copy (the Zeroizing in Self will handle the other)
         key.zeroize();
+        result.password = Some(PasswordSource {
+            password: Zeroizing::new(password.to_vec()),
+            salt: salt.to_vec(),
+            iterations,
+        });
         result
     }
 
+    /// Key handler for a file whose key was derived with `iterations`.
+    ///
+    /// Returns `None` when this handler's own key applies: raw keys, raw-key
+    /// files, or a matching iteration count.
+    fn for_iterations(&self, iterations: u32) -> Option<Self> {
+        let source = self.password.as_ref()?;
+        if iterations == 0 || iterations == source.iterations {
+            return None;
+        }
+        Some(Self::derive(&source.password, &source.salt, iterations))
+    }
+
     /// Generate a key from machine-specific identifiers
     /// This ties encryption to the specific machine.
     ///
@@ -440,6 +527,9 @@ impl ModelEncryption {
     }
 
     /// Encrypt a file
+    ///
+    /// The header records this handler's PBKDF2 iteration count so the file
+    /// can be decrypted after the default changes.
     pub fn encrypt_file(
         &self,
         input_path: &Path,
@@ -449,13 +539,61 @@ impl ModelEncryption {
         let mut input_file =
             std::fs::File::open(input_path).map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
-        let mut plaintext = Vec::new();
+        let mut plaintext = Zeroizing::new(Vec::new());
         input_file
             .read_to_end(&mut plaintext)
             .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
+        self.write_encrypted(&plaintext, output_path)
+    }
+
+    /// Decrypt a file
+    ///
+    /// Files written with a different PBKDF2 iteration count are decrypted with
+    /// a key re-derived at the count recorded in their header.
+    pub fn decrypt_file(
+        &self,
+        input_path: &Path,
+        output_path: &Path,
+    ) -> Result<(), EncryptionError> {
+        let plaintext = self.read_encrypted(input_path)?;
+
+        // Write output file
+        let mut output_file = std::fs::File::create(output_path)
+            .map_err(|e| EncryptionError::IoError(e.to_string()))?;
+        output_file
+            .write_all(&plaintext)
+            .map_err(|e| EncryptionError::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Re-encrypt a file under this handler's iteration count.
+    ///
+    /// Upgrade path for files written with fewer PBKDF2 iterations. The
+    /// plaintext never touches disk; `output_path` may equal `input_path`.
+    pub fn reencrypt_file(
+        &self,
+        input_path: &Path,
+        output_path: &Path,
+    ) -> Result<(), EncryptionError> {
+        let plaintext = self.read_encrypted(input_path)?;
+        self.write_encrypted(&plaintext, output_path)
+    }
+
+    /// PBKDF2 iteration count recorded in an encrypted file's header.
+    ///
+    /// Returns `LEGACY_PBKDF2_ITERATIONS` for files predating the field and 0
+    /// for files encrypted with a raw key.
+    pub fn file_iterations(path: &Path) -> Result<u32, EncryptionError> {
+        let mut file =
+            std::fs::File::open(path).map_err(|e| EncryptionError::IoError(e.to_string()))?;
+        Ok(Self::read_header(&mut file)?.iterations)
+    }
+
+    fn write_encrypted(&self, plaintext: &[u8], output_path: &Path) -> Result<(), EncryptionError> {
         // Encrypt
-        let (nonce, ciphertext) = self.encrypt(&plaintext)?;
+        let (nonce, ciphertext) = self.encrypt(plaintext)?;
 
         // Write output file with header
         let mut output_file = std::fs::File::create(output_path)
@@ -466,9 +604,15 @@ impl ModelEncryption {
             .write_all(b"GGGCM")
             .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
-        // Write version (version 2 = GCM)
+        // Write version (version 3 = GCM with PBKDF2 iteration count)
+        output_file
+            .write_all(&[FILE_VERSION, 0])
+            .map_err(|e| EncryptionError::IoError(e.to_string()))?;
+
+        // Write PBKDF2 iteration count (0 = raw key)
+        let iterations = self.iterations().unwrap_or(0);
         output_file
-            .write_all(&[2, 0])
+            .write_all(&iterations.to_le_bytes())
             .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
         // Write nonce
@@ -490,16 +634,7 @@ impl ModelEncryption {
         Ok(())
     }
 
-    /// Decrypt a file
-    pub fn decrypt_file(
-        &self,
-        input_path: &Path,
-        output_path: &Path,
-    ) -> Result<(), EncryptionError> {
-        // Read input file
-        let mut input_file =
-            std::fs::File::open(input_path).map_err(|e| EncryptionError::IoError(e.to_string()))?;
-
+    fn read_header(input_file: &mut std::fs::File) -> Result<FileHeader, EncryptionError> {
         // Read and verify magic number
         let mut magic = [0u8; 5];
         input_file
@@ -523,13 +658,45 @@ impl ModelEncryption {
             .read_exact(&mut version)
             .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
+        // Read PBKDF2 iteration count (v3+); older files used the legacy count
+        let iterations = if is_gcm && version[0] >= 3 {
+            let mut iter_bytes = [0u8; 4];
+            input_file
+                .read_exact(&mut iter_bytes)
+                .map_err(|e| EncryptionError::IoError(e.to_string()))?;
+            u32::from_le_bytes(iter_bytes)
+        } else {
+            LEGACY_PBKDF2_ITERATIONS
+        };
+        // The count comes from the file, so bound the work it can demand
+        if iterations > MAX_PBKDF2_ITERATIONS {
+            return Err(EncryptionError::IterationsTooHigh(iterations));
+        }
+
         // Read nonce
         let mut nonce = [0u8; NONCE_SIZE];
         input_file
             .read_exact(&mut nonce)
             .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
-        let plaintext = if is_gcm || is_legacy_gcm {
+        Ok(FileHeader {
+            is_legacy_ecb,
+            iterations,
+            nonce,
+        })
+    }
+
+    fn read_encrypted(&self, input_path: &Path) -> Result<Zeroizing<Vec<u8>>, EncryptionError> {
+        // Read input file
+        let mut input_file =
+            std::fs::File::open(input_path).map_err(|e| EncryptionError::IoError(e.to_string()))?;
+
+        let header = Self::read_header(&mut input_file)?;
+        let rederived = self.for_iterations(header.iterations);
+        let cipher = rederived.as_ref().unwrap_or(self);
+        let nonce = header.nonce;
+
+        let plaintext = if !header.is_legacy_ecb {
             // GCM format: nonce + ciphertext (with embedded tag)
 
             // Read ciphertext length
@@ -546,7 +713,7 @@ impl ModelEncryption {
                 .map_err(|e| EncryptionError::IoError(e.to_string()))?;
 
             // Decrypt
-            self.decrypt(&nonce[..], &ciphertext)?
+            cipher.decrypt(&nonce[..], &ciphertext)?
         } else {
             // Legacy ECB format (deprecated, for migration only)
             // Read tag
@@ -570,17 +737,10 @@ impl ModelEncryption {
 
             // Decrypt using legacy ECB method
             #[allow(deprecated)]
-            self.decrypt_legacy(&nonce[..], &ciphertext, &tag[..])?
+            cipher.decrypt_legacy(&nonce[..], &ciphertext, &tag[..])?
         };
 
-        // Write output file
-        let mut output_file = std::fs::File::create(output_path)
-            .map_err(|e| EncryptionError::IoError(e.to_string()))?;
-        output_file
-            .write_all(&plaintext)
-            .map_err(|e| EncryptionError::IoError(e.to_string()))?;
-
-        Ok(())
+        Ok(Zeroizing::new(plaintext))
     }
 
     /// Check if hardware acceleration is available
@@ -934,9 +1094,11 @@ mod tests {
 
         // Check magic number
         assert_eq!(&encrypted[0..5], b"GGGCM");
-        // Check version (2.0)
-        assert_eq!(encrypted[5], 2);
+        // Check version (3.0)
+        assert_eq!(encrypted[5], 3);
         assert_eq!(encrypted[6], 0);
+        // Raw keys record zero PBKDF2 iterations
+        assert_eq!(&encrypted[7..11], &[0, 0, 0, 0]);
     }
 
     #[test]
@@ -1158,9 +1320,123 @@ mod tests {
     #[test]
     fn test_pbkdf2_iterations_owasp_compliant() {
         // OWASP recommends at least 600,000 iterations for PBKDF2-SHA256 as of 2023
-        // However, 100,000 is still acceptable for many use cases
-        // We use 100,000 as a balance between security and performance
-        assert!(ModelEncryption::PBKDF2_ITERATIONS >= 100_000);
+        assert!(DEFAULT_PBKDF2_ITERATIONS >= 600_000);
+        assert!(MIN_PBKDF2_ITERATIONS >= LEGACY_PBKDF2_ITERATIONS);
+    }
+
+    #[test]
+    fn test_iterations_below_minimum_rejected() {
+        let result = ModelEncryption::from_password_with_iterations("pw", b"salt", 1_000);
+        assert!(matches!(result, Err(EncryptionError::IterationsTooLow(1_000))));
+    }
+
+    fn write_plaintext(bytes: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(bytes).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_decrypts_file_written_with_different_iterations() {
+        let salt = b"0123456789abcdef";
+        let old = ModelEncryption::from_password_with_iterations("pw", salt, 100_000).unwrap();
+        let new = ModelEncryption::from_password_with_iterations("pw", salt, 120_000).unwrap();
+        let input = write_plaintext(b"model weights");
+        let encrypted = NamedTempFile::new().unwrap();
+        let decrypted = NamedTempFile::new().unwrap();
+
+        old.encrypt_file(input.path(), encrypted.path()).unwrap();
+        assert_eq!(ModelEncryption::file_iterations(encrypted.path()).unwrap(), 100_000);
+
+        // Header selects 100,000 even though the handler is configured for 120,000
+        new.decrypt_file(encrypted.path(), decrypted.path()).unwrap();
+        assert_eq!(std::fs::read(decrypted.path()).unwrap(), b"model weights");
+    }
+
+    #[test]
+    fn test_header_iterations_drive_key_selection() {
+        let salt = b"0123456789abcdef";
+        let enc = ModelEncryption::from_password_with_iterations("pw", salt, 100_000).unwrap();
+        let input = write_plaintext(b"model weights");
+        let encrypted = NamedTempFile::new().unwrap();
+        let decrypted = NamedTempFile::new().unwrap();
+        enc.encrypt_file(input.path(), encrypted.path()).unwrap();
+
+        // Rewrite the header's iteration count: the re-derived key no longer matches
+        let mut bytes = std::fs::read(encrypted.path()).unwrap();
+        bytes[7..11].copy_from_slice(&110_000u32.to_le_bytes());
+        std::fs::write(encrypted.path(), &bytes).unwrap();
+
+        let result = enc.decrypt_file(encrypted.path(), decrypted.path());
+        assert!(matches!(result, Err(EncryptionError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_iterations_above_maximum_rejected() {
+        let too_many = MAX_PBKDF2_ITERATIONS + 1;
+        let result = ModelEncryption::from_password_with_iterations("pw", b"salt", too_many);
+        assert!(matches!(result, Err(EncryptionError::IterationsTooHigh(n)) if n == too_many));
+    }
+
+    #[test]
+    fn test_header_iterations_above_maximum_rejected_before_derivation() {
+        let salt = b"0123456789abcdef";
+        let enc = ModelEncryption::from_password_with_iterations("pw", salt, 100_000).unwrap();
+        let input = write_plaintext(b"model weights");
+        let encrypted = NamedTempFile::new().unwrap();
+        let decrypted = NamedTempFile::new().unwrap();
+        enc.encrypt_file(input.path(), encrypted.path()).unwrap();
+
+        let mut bytes = std::fs::read(encrypted.path()).unwrap();
+        bytes[7..11].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(encrypted.path(), &bytes).unwrap();
+
+        let result = enc.decrypt_file(encrypted.path(), decrypted.path());
+        assert!(matches!(result, Err(EncryptionError::IterationsTooHigh(u32::MAX))));
+        assert!(matches!(
+            ModelEncryption::file_iterations(encrypted.path()),
+            Err(EncryptionError::IterationsTooHigh(u32::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_decrypts_v2_file_with_legacy_iterations() {
+        let salt = b"0123456789abcdef";
+        let old = ModelEncryption::from_password_with_iterations("pw", salt, 100_000).unwrap();
+        let new = ModelEncryption::from_password_with_iterations("pw", salt, 120_000).unwrap();
+
+        // v2 layout: magic, version, nonce, length, ciphertext (no iteration field)
+        let (nonce, ciphertext) = old.encrypt(b"model weights").unwrap();
+        let mut bytes = b"GGGCM\x02\x00".to_vec();
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&ciphertext);
+        let encrypted = write_plaintext(&bytes);
+        let decrypted = NamedTempFile::new().unwrap();
+
+        assert_eq!(
+            ModelEncryption::file_iterations(encrypted.path()).unwrap(),
+            LEGACY_PBKDF2_ITERATIONS
+        );
+        new.decrypt_file(encrypted.path(), decrypted.path()).unwrap();
+        assert_eq!(std::fs::read(decrypted.path()).unwrap(), b"model weights");
+    }
+
+    #[test]
+    fn test_reencrypt_upgrades_iterations() {
+        let salt = b"0123456789abcdef";
+        let old = ModelEncryption::from_password_with_iterations("pw", salt, 100_000).unwrap();
+        let new = ModelEncryption::from_password_with_iterations("pw", salt, 120_000).unwrap();
+        let input = write_plaintext(b"model weights");
+        let encrypted = NamedTempFile::new().unwrap();
+        let decrypted = NamedTempFile::new().unwrap();
+        old.encrypt_file(input.path(), encrypted.path()).unwrap();
+
+        new.reencrypt_file(encrypted.path(), encrypted.path()).unwrap();
+        assert_eq!(ModelEncryption::file_iterations(encrypted.path()).unwrap(), 120_000);
+
+        new.decrypt_file(encrypted.path(), decrypted.path()).unwrap();
+        assert_eq!(std::fs::read(decrypted.path()).unwrap(), b"model weights");
     }
 
     #[test]
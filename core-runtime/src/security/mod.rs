@@ -20,7 +20,7 @@ pub use audit::{AuditCategory, AuditEvent, AuditLogger, AuditSeverity};
 pub use encryption::ModelEncryption;
 pub use fips_tests::{run_power_on_self_tests, SelfTestError, SelfTestResults};
 pub use key_rotation::{KeyRotationError, KeyRotationManager};
-pub use output_sanitizer::OutputSanitizer;
+pub use output_sanitizer::{OutputSanitizer, SanitizerConfig};
 pub use pii_detector::{PIIDetector, PIIMatch};
 pub use prompt_injection::{InjectionMatch, PromptInjectionFilter};
 
@@ -35,6 +35,9 @@ pub struct SecurityConfig {
     pub enable_pii_detection: bool,
     /// Redact PII in outputs
     pub redact_pii: bool,
+    /// PII types and minimum confidence that count as PII, for both
+    /// redaction and scan reporting
+    pub sanitizer: SanitizerConfig,
     /// Enable model encryption
     pub enable_model_encryption: bool,
     /// Encryption key (if None, generates from machine ID)
@@ -48,6 +51,7 @@ impl Default for SecurityConfig {
             block_prompt_injection: true,
             enable_pii_detection: true,
             redact_pii: true,
+            sanitizer: SanitizerConfig::default(),
             enable_model_encryption: false,
             encryption_key: None,
         }
@@ -92,3 +96,74 @@ pub enum SecurityIssueType {
     /// Suspicious pattern
     SuspiciousPattern,
 }
+
+/// Run the injection filter and PII detector over a prompt.
+///
+/// `passed` reflects whether the prompt would be blocked by the injection
+/// filter under `config`; detected PII is reported but does not block.
+/// Only PII allowed by `config.sanitizer` (enabled type, enough
+/// confidence) is reported.
+pub fn scan_prompt(prompt: &str, config: &SecurityConfig) -> SecurityScanResult {
+    let mut issues = Vec::new();
+    let mut passed = true;
+    let mut risk_score = 0;
+
+    if config.enable_prompt_injection_detection {
+        let filter = PromptInjectionFilter::new(config.block_prompt_injection);
+        let (is_safe, score, matches) = filter.scan(prompt);
+        passed = is_safe;
+        risk_score = score;
+        issues.extend(matches.into_iter().map(|m| SecurityIssue {
+            issue_type: SecurityIssueType::PromptInjection,
+            severity: m.severity,
+            description: format!("injection pattern \"{}\"", m.pattern),
+            location: Some(m.start),
+        }));
+    }
+
+    if config.enable_pii_detection {
+        let pii = PIIDetector::new().detect(prompt);
+        let allowed = pii.into_iter().filter(|m| config.sanitizer.allows(m));
+        issues.extend(allowed.map(|m| SecurityIssue {
+            issue_type: SecurityIssueType::PII,
+            severity: m.pii_type.severity(),
+            description: format!("{} detected", m.pii_type.name()),
+            location: Some(m.start),
+        }));
+    }
+
+    SecurityScanResult {
+        passed,
+        issues,
+        risk_score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pii_detector::PIIType;
+
+    #[test]
+    fn test_scan_reports_only_enabled_pii_types() {
+        let prompt = "Server 192.168.1.1, SSN: 123-45-6789";
+        let pii = |config: &SecurityConfig| -> Vec<String> {
+            scan_prompt(prompt, config)
+                .issues
+                .into_iter()
+                .filter(|i| i.issue_type == SecurityIssueType::PII)
+                .map(|i| i.description)
+                .collect()
+        };
+
+        // IP addresses are not enabled by default
+        assert_eq!(pii(&SecurityConfig::default()), ["Social Security Number detected"]);
+
+        let mut config = SecurityConfig::default();
+        config.sanitizer.redact_types.push(PIIType::IPAddress);
+        assert_eq!(
+            pii(&config),
+            ["IP Address detected", "Social Security Number detected"]
+        );
+    }
+}
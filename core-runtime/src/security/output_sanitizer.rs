@@ -3,7 +3,7 @@
 //! Sanitizes model outputs for security and safety.
 //! Combines PII detection, content filtering, and format validation.
 
-use crate::security::{PIIDetector, pii_detector::PIIType};
+use crate::security::{PIIDetector, PIIMatch, pii_detector::PIIType};
 use std::sync::Arc;
 
 /// Output sanitizer configuration
@@ -15,9 +15,10 @@ pub struct SanitizerConfig {
     pub filter_content: bool,
     /// Maximum output length
     pub max_length: usize,
-    /// Minimum confidence for PII detection
+    /// Minimum confidence for PII detection; matches below it pass
+    /// through unredacted
     pub pii_confidence_threshold: f32,
-    /// PII types to redact
+    /// PII types to redact; matches of other types pass through
     pub redact_types: Vec<PIIType>,
 }
 
@@ -42,6 +43,14 @@ impl Default for SanitizerConfig {
     }
 }
 
+impl SanitizerConfig {
+    /// Whether `m` is of a type in `redact_types` and meets
+    /// `pii_confidence_threshold`
+    pub fn allows(&self, m: &PIIMatch) -> bool {
+        self.redact_types.contains(&m.pii_type) && m.confidence >= self.pii_confidence_threshold
+    }
+}
+
 /// Sanitization result
 #[derive(Debug, Clone)]
 pub struct SanitizationResult {
@@ -101,19 +110,15 @@ impl OutputSanitizer {
         if self.config.redact_pii {
             let pii_matches = self.pii_detector.detect(&result);
             
-            for m in pii_matches {
-                // Check if this PII type should be redacted
-                if !self.config.redact_types.contains(&m.pii_type) {
-                    continue;
-                }
-                
-                // Check confidence threshold
-                if m.confidence < self.config.pii_confidence_threshold {
+            // Matches are sorted and disjoint; redacting from the end keeps
+            // earlier offsets valid
+            for m in pii_matches.iter().rev() {
+                if !self.config.allows(m) {
                     continue;
                 }
                 
                 // Redact
-                result = self.redact_pii(&result, &m);
+                result = self.redact_pii(&result, m);
                 pii_redacted += 1;
                 modified = true;
             }
@@ -150,6 +155,9 @@ impl OutputSanitizer {
             let pii_matches = self.pii_detector.detect(&state.buffer);
             
             for m in pii_matches {
+                if !self.config.allows(&m) {
+                    continue;
+                }
                 if m.start >= state.processed_until {
                     // New PII found
                     if m.end <= state.buffer.len() {
@@ -226,7 +234,7 @@ impl OutputSanitizer {
     }
     
     /// Redact a single PII instance
-    fn redact_pii(&self, text: &str, m: &crate::security::pii_detector::PIIMatch) -> String {
+    fn redact_pii(&self, text: &str, m: &PIIMatch) -> String {
         let mut result = text.to_string();
         let replacement = format!("[REDACTED:{}]", m.pii_type.name());
         result.replace_range(m.start..m.end, &replacement);
@@ -326,28 +334,28 @@ impl Default for OutputSanitizer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_pii_redaction() {
         let sanitizer = OutputSanitizer::default_sanitizer();
         let output = "Contact support@example.com for assistance";
         let result = sanitizer.sanitize(output);
-        
+
         assert!(result.modified);
         assert!(result.pii_redacted > 0);
         assert!(result.output.contains("[REDACTED:Email Address]"));
     }
-    
+
     #[test]
     fn test_no_modification_needed() {
         let sanitizer = OutputSanitizer::default_sanitizer();
         let output = "The weather is nice today.";
         let result = sanitizer.sanitize(output);
-        
+
         assert!(!result.modified);
         assert_eq!(result.pii_redacted, 0);
     }
-    
+
     #[test]
     fn test_length_truncation() {
         let config = SanitizerConfig {
@@ -355,63 +363,63 @@ mod tests {
             ..Default::default()
         };
         let sanitizer = OutputSanitizer::new(config);
-        
+
         let output = "This is a very long output that should be truncated to fit within the limit.";
         let result = sanitizer.sanitize(output);
-        
+
         assert!(result.modified);
         assert!(result.output.len() <= 50);
         assert!(!result.warnings.is_empty());
     }
-    
+
     #[test]
     fn test_multiple_pii_types() {
         let sanitizer = OutputSanitizer::default_sanitizer();
         let output = "Email: test@example.com, Phone: 555-123-4567, SSN: 123-45-6789";
         let result = sanitizer.sanitize(output);
-        
+
         assert!(result.modified);
         assert!(result.pii_redacted >= 3);
     }
-    
+
     #[test]
     fn test_format_validation() {
         let sanitizer = OutputSanitizer::default_sanitizer();
-        
+
         // Valid output
         assert!(sanitizer.validate_format("This is valid output").is_ok());
-        
+
         // Null characters
         assert!(sanitizer.validate_format("Invalid\0output").is_err());
     }
-    
+
     #[test]
     fn test_excessive_repetition_detection() {
         let sanitizer = OutputSanitizer::default_sanitizer();
-        
+
         // Create text with clear repetition (same 3-word phrase 6+ times)
         let repetitive = "hello world test hello world test hello world test hello world test hello world test hello world test hello world test";
         assert!(sanitizer.has_excessive_repetition(repetitive));
-        
+
         let normal = "The quick brown fox jumps over the lazy dog and runs through the forest.";
         assert!(!sanitizer.has_excessive_repetition(normal));
     }
-    
+
     #[test]
     fn test_streaming_sanitization() {
         let sanitizer = OutputSanitizer::default_sanitizer();
         let mut state = StreamingSanitizerState::default();
-        
+
         // Process chunks
         let chunk1 = sanitizer.sanitize_chunk("Contact ", &mut state);
         let chunk2 = sanitizer.sanitize_chunk("test@example.com", &mut state);
         let chunk3 = sanitizer.sanitize_chunk(" for help", &mut state);
-        
+
         // At least some chunk should be modified
         let full_output = format!("{}{}{}", chunk1, chunk2, chunk3);
         assert!(full_output.contains("[REDACTED") || state.buffer.contains("@"));
     }
-    
+
     #[test]
     fn test_confidence_threshold() {
         let config = SanitizerConfig {
@@ -419,15 +427,15 @@ mod tests {
             ..Default::default()
         };
         let sanitizer = OutputSanitizer::new(config);
-        
+
         // Most PII won't meet 99% confidence
         let output = "Email: test@example.com";
         let _result = sanitizer.sanitize(output);
-        
+
         // May not be redacted due to high threshold
         // (depends on confidence calculation)
     }
-    
+
     #[test]
     fn test_selective_pii_types() {
         let config = SanitizerConfig {
@@ -435,36 +443,74 @@ mod tests {
             ..Default::default()
         };
         let sanitizer = OutputSanitizer::new(config);
-        
+
         let output = "Email: test@example.com, Phone: 555-123-4567";
         let result = sanitizer.sanitize(output);
-        
+
         assert!(result.output.contains("[REDACTED:Email Address]"));
         assert!(result.output.contains("555-123-4567")); // Phone not redacted
     }
-    
+
+    #[test]
+    fn test_disabled_type_passes_through() {
+        let output = "Server 192.168.1.1, SSN: 123-45-6789";
+        let mut config = SanitizerConfig::default();
+        config.redact_types.push(PIIType::IPAddress);
+        assert_eq!(
+            OutputSanitizer::new(config.clone()).sanitize(output).output,
+            "Server [REDACTED:IP Address], SSN: [REDACTED:Social Security Number]"
+        );
+
+        config.redact_types.retain(|&t| t != PIIType::IPAddress);
+        let sanitizer = OutputSanitizer::new(config);
+        let result = sanitizer.sanitize(output);
+        assert_eq!(result.output, "Server 192.168.1.1, SSN: [REDACTED:Social Security Number]");
+        assert_eq!(result.pii_redacted, 1);
+
+        // Streaming honours the same filter
+        let mut state = StreamingSanitizerState::default();
+        let chunk = "host 192.168.1.1 up";
+        assert_eq!(sanitizer.sanitize_chunk(chunk, &mut state), chunk);
+    }
+
+    #[test]
+    fn test_confidence_threshold_drops_low_confidence_phones() {
+        let output = "Call 555-123-4567 or 5551234";
+        let at = |pii_confidence_threshold: f32| {
+            let config = SanitizerConfig { pii_confidence_threshold, ..Default::default() };
+            OutputSanitizer::new(config)
+                .sanitize(output)
+                .output
+        };
+
+        // The bare 7-digit number scores 0.6, the formatted one 0.85
+        assert_eq!(at(0.5), "Call [REDACTED:Phone Number] or [REDACTED:Phone Number]");
+        assert_eq!(at(0.7), "Call [REDACTED:Phone Number] or 5551234");
+        assert_eq!(at(0.9), output);
+    }
+
     #[test]
     fn test_performance() {
         let sanitizer = OutputSanitizer::default_sanitizer();
         let output = "Contact support@example.com for help. Call 555-123-4567. SSN: 123-45-6789.".repeat(100);
-        
+
         let start = std::time::Instant::now();
         for _ in 0..100 {
             let _ = sanitizer.sanitize(&output);
         }
         let duration = start.elapsed();
-        
+
         // Should complete 100 sanitizations in under 10 seconds
         assert!(duration.as_millis() < 10000, "Sanitization too slow: {:?}", duration);
     }
-    
+
     #[test]
     fn test_streaming_pii_split_attack() {
         // SECURITY TEST: Verify PII split across chunks is detected
         // This tests the fix for ADV-PII-02 from the adversarial audit
         let sanitizer = OutputSanitizer::default_sanitizer();
         let mut state = StreamingSanitizerState::default();
-        
+
         // Split email across chunks to simulate attack
         let chunks = [
             "My email is j",
@@ -474,76 +520,76 @@ mod tests {
             "55-1",
             "23-4567",
         ];
-        
+
         let mut outputs = Vec::new();
         for chunk in &chunks {
             outputs.push(sanitizer.sanitize_chunk(chunk, &mut state));
         }
-        
+
         // The full buffer should contain the complete PII
         let full_buffer = &state.buffer;
-        
+
         // Verify the buffer has accumulated the full content
-        assert!(full_buffer.contains("john.smith@example.com") || 
-                full_buffer.contains("[REDACTED"), 
+        assert!(full_buffer.contains("john.smith@example.com") ||
+                full_buffer.contains("[REDACTED"),
             "Buffer should contain either the full email or redacted version");
-        
+
         // Verify phone number is also tracked
-        assert!(full_buffer.contains("555-123-4567") || 
+        assert!(full_buffer.contains("555-123-4567") ||
                 full_buffer.contains("[REDACTED"),
             "Buffer should contain either the full phone or redacted version");
     }
-    
+
     #[test]
     fn test_safe_trim_point() {
         let sanitizer = OutputSanitizer::default_sanitizer();
-        
+
         // Test with a buffer that is long enough and has clear word boundaries
         // Buffer must be > MAX_PII_LENGTH (100) for trimming to occur
         let buffer = "This is a test sentence with a word boundary. And more text follows here to make it longer. We need at least 100 characters for the trim logic to work properly. Adding more padding text now.";
         assert!(buffer.len() > 100, "Buffer must be longer than MAX_PII_LENGTH");
-        
+
         // Test that the function returns a valid trim point
         let trim_point = sanitizer.find_safe_trim_point(buffer, 80);
-        
+
         // The function should return a value that:
         // 1. Doesn't exceed max_trim
         // 2. Preserves enough buffer for PII detection
         assert!(trim_point <= 80, "Trim point should not exceed max_trim");
-        
+
         // After trimming, the remaining buffer should be at least MAX_PII_LENGTH
         // to allow for PII detection at boundaries
         let remaining = buffer.len() - trim_point;
         assert!(remaining >= 100, "Remaining buffer should be at least MAX_PII_LENGTH");
     }
-    
+
     #[test]
     fn test_streaming_buffer_does_not_lose_pii() {
         // SECURITY TEST: Verify buffer trimming doesn't lose PII at boundaries
         let sanitizer = OutputSanitizer::default_sanitizer();
         let mut state = StreamingSanitizerState::default();
-        
+
         // Create a large buffer that will trigger trimming
         let padding = "x".repeat(900);
-        
+
         // Add padding first
         sanitizer.sanitize_chunk(&padding, &mut state);
-        
+
         // Now add PII that spans the trim boundary
         let pii_start = "Contact j";
         let pii_middle = "ohn.doe@test";
         let pii_end = ".com for help";
-        
+
         sanitizer.sanitize_chunk(pii_start, &mut state);
         sanitizer.sanitize_chunk(pii_middle, &mut state);
         sanitizer.sanitize_chunk(pii_end, &mut state);
-        
+
         // The email should be detected in the buffer
         // Either as redacted or still present for detection
         let has_email = state.buffer.contains("john.doe@test.com") ||
                         state.buffer.contains("[REDACTED");
-        
-        assert!(has_email || state.buffer.len() >= 50, 
+
+        assert!(has_email || state.buffer.len() >= 50,
             "PII should be preserved in buffer for detection");
     }
 }
\ No newline at end of file
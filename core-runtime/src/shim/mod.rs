@@ -10,7 +10,8 @@
 //!
 //! # Open Core Architecture
 //!
-//! The base GG-CORE runtime provides a no-op `PassthroughInterceptor`.
+//! The base GG-CORE runtime provides a no-op `PassthroughInterceptor` and a
+//! `TokenBucketInterceptor` with per-model rate limits.
 //! Commercial extensions (GG-CORE Nexus) can provide implementations
 //! with multi-tenant features, rate limiting, and priority queuing.
 //!
@@ -20,6 +21,7 @@
 //! │  ┌───────────────────────────────────────────┐  │
 //! │  │         RequestInterceptor trait          │  │
 //! │  │  (PassthroughInterceptor default impl)    │  │
+//! │  │  (TokenBucketInterceptor, per-model)      │  │
 //! │  └───────────────────────────────────────────┘  │
 //! └─────────────────────────────────────────────────┘
 //!                        ▲
@@ -35,12 +37,16 @@
 //! └─────────────────────────────────────────────────┘
 //! ```
 
+mod token_bucket;
+
 use std::fmt;
 use std::sync::Arc;
 
 use crate::ipc::protocol::InferenceRequest;
 use crate::scheduler::Priority;
 
+pub use token_bucket::{TokenBucketConfig, TokenBucketInterceptor};
+
 /// Error returned when request interception fails.
 #[derive(Debug, Clone)]
 pub enum InterceptError {
@@ -161,6 +167,9 @@ mod tests {
             model_id: "test".to_string(),
             prompt: "Hello".to_string(),
             parameters: Default::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
         };
 
         let result = interceptor.intercept(&request, None);
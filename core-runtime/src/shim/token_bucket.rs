@@ -0,0 +1,221 @@
+// Copyright 2024-2026 GG-CORE Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Token-bucket rate limiting interceptor.
+//!
+//! Models differ widely in cost, so each model ID can get its own bucket
+//! (a 70B model limited harder than a small classifier). Requests for models
+//! without a configured bucket share the global bucket.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{InterceptError, InterceptResult, RequestInterceptor};
+use crate::ipc::clock::{Clock, SystemClock};
+use crate::ipc::protocol::InferenceRequest;
+
+/// Size and refill rate of one bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucketConfig {
+    /// Maximum burst of requests.
+    pub capacity: u32,
+    /// Tokens added per second.
+    pub refill_per_sec: f64,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            refill_per_sec: 50.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    config: TokenBucketConfig,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(config: TokenBucketConfig, now: Instant) -> Self {
+        Self {
+            config,
+            tokens: config.capacity as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Take one token, or return how long until one is available.
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.config.refill_per_sec).min(self.config.capacity as f64);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.config.refill_per_sec <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.config.refill_per_sec,
+        ))
+    }
+}
+
+/// Rate limiter with per-model buckets and a global fallback.
+///
+/// Buckets are keyed by `InferenceRequest.model_id` exactly as sent, so an
+/// alias is limited by configuring a bucket under the alias name.
+pub struct TokenBucketInterceptor {
+    global: Mutex<Bucket>,
+    models: HashMap<String, Mutex<Bucket>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl TokenBucketInterceptor {
+    /// Limit every model through one global bucket.
+    pub fn new(global: TokenBucketConfig) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        Self {
+            global: Mutex::new(Bucket::new(global, clock.now())),
+            models: HashMap::new(),
+            clock,
+        }
+    }
+
+    /// Give `model_id` its own bucket instead of the global one.
+    pub fn with_model_limit(
+        mut self,
+        model_id: impl Into<String>,
+        config: TokenBucketConfig,
+    ) -> Self {
+        let bucket = Bucket::new(config, self.clock.now());
+        self.models.insert(model_id.into(), Mutex::new(bucket));
+        self
+    }
+
+    /// Use `clock` for refills. Buckets start full at the clock's current time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        reset(&self.global, now);
+        for bucket in self.models.values() {
+            reset(bucket, now);
+        }
+        self.clock = clock;
+        self
+    }
+
+    /// Bucket config applied to `model_id`.
+    pub fn limit_for(&self, model_id: &str) -> TokenBucketConfig {
+        lock(self.bucket_for(model_id)).config
+    }
+
+    fn bucket_for(&self, model_id: &str) -> &Mutex<Bucket> {
+        self.models.get(model_id).unwrap_or(&self.global)
+    }
+}
+
+fn lock(bucket: &Mutex<Bucket>) -> std::sync::MutexGuard<'_, Bucket> {
+    bucket.lock().unwrap_or_else(|p| p.into_inner())
+}
+
+fn reset(bucket: &Mutex<Bucket>, now: Instant) {
+    let mut bucket = lock(bucket);
+    *bucket = Bucket::new(bucket.config, now);
+}
+
+impl RequestInterceptor for TokenBucketInterceptor {
+    fn intercept(
+        &self,
+        request: &InferenceRequest,
+        _session_token: Option<&str>,
+    ) -> Result<InterceptResult, InterceptError> {
+        let now = self.clock.now();
+        match lock(self.bucket_for(&request.model_id)).try_take(now) {
+            Ok(()) => Ok(InterceptResult::default()),
+            Err(wait) => Err(InterceptError::RateLimited {
+                retry_after_ms: wait.as_millis().min(u64::MAX as u128) as u64,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::clock::MockClock;
+    use crate::ipc::protocol::RequestId;
+
+    fn request(model_id: &str) -> InferenceRequest {
+        InferenceRequest {
+            request_id: RequestId(1),
+            model_id: model_id.to_string(),
+            prompt: "Hello".to_string(),
+            parameters: Default::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
+        }
+    }
+
+    #[test]
+    fn test_refill_after_wait() {
+        let clock = Arc::new(MockClock::new());
+        let interceptor = TokenBucketInterceptor::new(TokenBucketConfig {
+            capacity: 1,
+            refill_per_sec: 2.0,
+        })
+        .with_clock(clock.clone());
+
+        assert!(interceptor.intercept(&request("m"), None).is_ok());
+        match interceptor.intercept(&request("m"), None) {
+            Err(InterceptError::RateLimited { retry_after_ms }) => assert_eq!(retry_after_ms, 500),
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+        clock.advance(Duration::from_millis(500));
+        assert!(interceptor.intercept(&request("m"), None).is_ok());
+    }
+
+    #[test]
+    fn test_per_model_buckets_limit_independently() {
+        let clock = Arc::new(MockClock::new());
+        let tight = TokenBucketConfig {
+            capacity: 2,
+            refill_per_sec: 1.0,
+        };
+        let loose = TokenBucketConfig {
+            capacity: 50,
+            refill_per_sec: 100.0,
+        };
+        let interceptor = TokenBucketInterceptor::new(TokenBucketConfig::default())
+            .with_model_limit("llama-70b", tight)
+            .with_model_limit("classifier", loose)
+            .with_clock(clock.clone());
+
+        // Same request rate for both: 20 requests at 10ms intervals
+        let mut limited = (0, 0);
+        for _ in 0..20 {
+            if interceptor.intercept(&request("llama-70b"), None).is_err() {
+                limited.0 += 1;
+            }
+            if interceptor.intercept(&request("classifier"), None).is_err() {
+                limited.1 += 1;
+            }
+            clock.advance(Duration::from_millis(10));
+        }
+        assert_eq!(limited, (18, 0));
+
+        // Unconfigured models fall back to the global bucket
+        assert_eq!(interceptor.limit_for("other"), TokenBucketConfig::default());
+        assert!(interceptor.intercept(&request("other"), None).is_ok());
+    }
+}
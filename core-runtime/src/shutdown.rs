@@ -123,6 +123,78 @@ impl Default for ShutdownCoordinator {
     }
 }
 
+/// Termination signal that triggered shutdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// SIGINT / Ctrl+C.
+    Interrupt,
+    /// SIGTERM (Kubernetes pod termination) or Windows close/shutdown events.
+    Terminate,
+}
+
+/// Listens for process termination signals.
+///
+/// Handlers are installed by `new()`, so a signal delivered after it
+/// returns is never lost, even before `recv()` is awaited.
+pub struct ShutdownSignals {
+    #[cfg(unix)]
+    interrupt: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(windows)]
+    ctrl_c: tokio::signal::windows::CtrlC,
+    #[cfg(windows)]
+    ctrl_break: tokio::signal::windows::CtrlBreak,
+    #[cfg(windows)]
+    ctrl_close: tokio::signal::windows::CtrlClose,
+    #[cfg(windows)]
+    ctrl_shutdown: tokio::signal::windows::CtrlShutdown,
+}
+
+impl ShutdownSignals {
+    /// Install handlers for SIGINT and SIGTERM (or the Windows console events).
+    #[cfg(unix)]
+    pub fn new() -> std::io::Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Self {
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+        })
+    }
+
+    /// Install handlers for SIGINT and SIGTERM (or the Windows console events).
+    #[cfg(windows)]
+    pub fn new() -> std::io::Result<Self> {
+        use tokio::signal::windows;
+        Ok(Self {
+            ctrl_c: windows::ctrl_c()?,
+            ctrl_break: windows::ctrl_break()?,
+            ctrl_close: windows::ctrl_close()?,
+            ctrl_shutdown: windows::ctrl_shutdown()?,
+        })
+    }
+
+    /// Wait for the next termination signal.
+    #[cfg(unix)]
+    pub async fn recv(&mut self) -> ShutdownSignal {
+        tokio::select! {
+            _ = self.interrupt.recv() => ShutdownSignal::Interrupt,
+            _ = self.terminate.recv() => ShutdownSignal::Terminate,
+        }
+    }
+
+    /// Wait for the next termination signal.
+    #[cfg(windows)]
+    pub async fn recv(&mut self) -> ShutdownSignal {
+        tokio::select! {
+            _ = self.ctrl_c.recv() => ShutdownSignal::Interrupt,
+            _ = self.ctrl_break.recv() => ShutdownSignal::Interrupt,
+            _ = self.ctrl_close.recv() => ShutdownSignal::Terminate,
+            _ = self.ctrl_shutdown.recv() => ShutdownSignal::Terminate,
+        }
+    }
+}
+
 /// RAII guard for in-flight request tracking.
 pub struct ShutdownGuard {
     counter: Arc<AtomicU32>,
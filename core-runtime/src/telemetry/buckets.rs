@@ -9,6 +9,7 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// Default latency buckets in milliseconds (Prometheus standard).
 pub const DEFAULT_LATENCY_BUCKETS: [f64; 11] = [
@@ -20,6 +21,103 @@ pub const DEFAULT_THROUGHPUT_BUCKETS: [f64; 10] = [
     10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
 ];
 
+/// Bucketed histogram names in the metrics store.
+pub const LATENCY_HISTOGRAM: &str = "core_latency_ms";
+pub const TTFT_HISTOGRAM: &str = "core_ttft_ms";
+pub const QUEUE_WAIT_HISTOGRAM: &str = "core_queue_wait_ms";
+pub const INTER_TOKEN_HISTOGRAM: &str = "core_inter_token_ms";
+
+/// Why a set of bucket boundaries was rejected.
+#[derive(Debug, Error, PartialEq)]
+pub enum BucketError {
+    #[error("{0}: no bucket boundaries")]
+    Empty(&'static str),
+
+    #[error("{histogram}: boundary {value} is not a positive finite number")]
+    NotPositive { histogram: &'static str, value: f64 },
+
+    #[error("{histogram}: boundaries must be strictly increasing ({previous} then {value})")]
+    NotSorted {
+        histogram: &'static str,
+        previous: f64,
+        value: f64,
+    },
+}
+
+/// Bucket boundaries (milliseconds) for each request timing histogram.
+///
+/// Defaults suit mixed traffic: sub-millisecond embeddings through
+/// multi-second generations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramBuckets {
+    /// End-to-end request latency.
+    pub latency_ms: Vec<f64>,
+    /// Time to first streamed token.
+    pub ttft_ms: Vec<f64>,
+    /// Time spent in the request queue.
+    pub queue_wait_ms: Vec<f64>,
+    /// Gap between consecutive streamed tokens.
+    pub inter_token_ms: Vec<f64>,
+}
+
+impl Default for HistogramBuckets {
+    fn default() -> Self {
+        Self {
+            latency_ms: vec![
+                0.5, 1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+                10000.0, 30000.0,
+            ],
+            ttft_ms: vec![
+                5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+            ],
+            queue_wait_ms: DEFAULT_LATENCY_BUCKETS.to_vec(),
+            inter_token_ms: vec![1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0],
+        }
+    }
+}
+
+impl HistogramBuckets {
+    /// Histogram names paired with their boundaries.
+    pub fn histograms(&self) -> [(&'static str, &[f64]); 4] {
+        [
+            (LATENCY_HISTOGRAM, &self.latency_ms),
+            (TTFT_HISTOGRAM, &self.ttft_ms),
+            (QUEUE_WAIT_HISTOGRAM, &self.queue_wait_ms),
+            (INTER_TOKEN_HISTOGRAM, &self.inter_token_ms),
+        ]
+    }
+
+    /// Check every histogram has positive, strictly increasing boundaries.
+    pub fn validate(&self) -> Result<(), BucketError> {
+        for (histogram, boundaries) in self.histograms() {
+            validate_boundaries(histogram, boundaries)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check `boundaries` are non-empty, positive, finite and strictly increasing.
+pub fn validate_boundaries(histogram: &'static str, boundaries: &[f64]) -> Result<(), BucketError> {
+    if boundaries.is_empty() {
+        return Err(BucketError::Empty(histogram));
+    }
+    for (i, &value) in boundaries.iter().enumerate() {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(BucketError::NotPositive { histogram, value });
+        }
+        if let Some(&previous) = i.checked_sub(1).and_then(|p| boundaries.get(p)) {
+            if value <= previous {
+                return Err(BucketError::NotSorted {
+                    histogram,
+                    previous,
+                    value,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Snapshot of a bucketed histogram for serialization.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BucketedHistogramSnapshot {
@@ -124,6 +222,27 @@ mod tests {
         assert_eq!(snap.bucket_counts, vec![1, 1, 1, 1]);
     }
 
+    #[test]
+    fn test_bucket_validation() {
+        assert!(HistogramBuckets::default().validate().is_ok());
+
+        let unsorted = HistogramBuckets {
+            ttft_ms: vec![10.0, 5.0],
+            ..Default::default()
+        };
+        assert_eq!(
+            unsorted.validate(),
+            Err(BucketError::NotSorted {
+                histogram: TTFT_HISTOGRAM,
+                previous: 10.0,
+                value: 5.0,
+            })
+        );
+        assert!(validate_boundaries("h", &[0.0, 1.0]).is_err());
+        assert!(validate_boundaries("h", &[1.0, f64::NAN]).is_err());
+        assert_eq!(validate_boundaries("h", &[]), Err(BucketError::Empty("h")));
+    }
+
     #[test]
     fn test_default_latency_buckets() {
         let h = BucketedHistogram::latency();
@@ -1,25 +1,51 @@
 //! Logging configuration and initialization for CORE Runtime.
 //!
-//! Supports JSON and pretty-printed formats with configurable output paths.
+//! Supports JSON, single-line text and pretty-printed formats with
+//! configurable output paths. The server reads `GG_CORE_LOG_FORMAT` into
+//! `RuntimeConfig::logging` along with the rest of its configuration.
 
 use std::path::PathBuf;
+use std::str::FromStr;
+use serde::Serialize;
 use thiserror::Error;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
+/// Environment variable selecting the log format (`json` or `text`).
+pub const LOG_FORMAT_ENV: &str = "GG_CORE_LOG_FORMAT";
+
 /// Log output format.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LogFormat {
-    /// JSON structured logging (default for production).
+    /// JSON structured logging (default for production): newline-delimited
+    /// records with timestamp, level, target, fields and spans, for log
+    /// pipelines such as Loki or ELK.
     #[default]
     Json,
-    /// Human-readable pretty printing (for development).
+    /// One human-readable line per event.
+    Text,
+    /// Multi-line pretty printing (for development).
     Pretty,
 }
 
+impl FromStr for LogFormat {
+    type Err = LogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "pretty" => Ok(Self::Pretty),
+            _ => Err(LogError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
 /// Logging configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LogConfig {
-    /// Output format (JSON or Pretty).
+    /// Output format (JSON, Text or Pretty).
     pub format: LogFormat,
     /// Log level filter (e.g., "info", "debug", "core_runtime=trace").
     pub level: String,
@@ -42,6 +68,8 @@ impl Default for LogConfig {
 pub enum LogError {
     #[error("Invalid log filter: {0}")]
     InvalidFilter(String),
+    #[error("Invalid log format {0:?} (expected json or text)")]
+    InvalidFormat(String),
     #[error("Failed to open log file: {0}")]
     FileOpen(String),
     #[error("Subscriber already initialized")]
@@ -54,38 +82,34 @@ pub enum LogError {
 pub fn init_logging(config: &LogConfig) -> Result<(), LogError> {
     let filter = EnvFilter::try_new(&config.level)
         .map_err(|e| LogError::InvalidFilter(e.to_string()))?;
+    let writer = match &config.output_path {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .map_err(|e| LogError::FileOpen(e.to_string()))?;
+            BoxMakeWriter::new(std::sync::Mutex::new(file))
+        }
+        None => BoxMakeWriter::new(std::io::stderr),
+    };
+    let registry = tracing_subscriber::registry().with(filter);
 
     match config.format {
-        LogFormat::Json => init_json_subscriber(filter, &config.output_path),
-        LogFormat::Pretty => init_pretty_subscriber(filter),
+        LogFormat::Json => registry.with(fmt::layer().json().with_writer(writer)).try_init(),
+        LogFormat::Text => registry.with(fmt::layer().with_writer(writer)).try_init(),
+        LogFormat::Pretty => registry.with(fmt::layer().pretty().with_writer(writer)).try_init(),
     }
+    .map_err(|_| LogError::AlreadyInitialized)
 }
 
-fn init_json_subscriber(filter: EnvFilter, path: &Option<PathBuf>) -> Result<(), LogError> {
-    let registry = tracing_subscriber::registry().with(filter);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if let Some(path) = path {
-        let file = std::fs::File::create(path)
-            .map_err(|e| LogError::FileOpen(e.to_string()))?;
-        registry
-            .with(fmt::layer().json().with_writer(std::sync::Mutex::new(file)))
-            .try_init()
-            .map_err(|_| LogError::AlreadyInitialized)?;
-    } else {
-        registry
-            .with(fmt::layer().json())
-            .try_init()
-            .map_err(|_| LogError::AlreadyInitialized)?;
+    #[test]
+    fn test_log_format_parses_case_insensitively() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!(" TEXT ".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert_eq!("pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!(matches!("yaml".parse::<LogFormat>(), Err(LogError::InvalidFormat(_))));
+        assert_eq!(LogConfig::default().format, LogFormat::Json);
     }
-
-    Ok(())
-}
-
-fn init_pretty_subscriber(filter: EnvFilter) -> Result<(), LogError> {
-    tracing_subscriber::registry()
-        .with(filter)
-        .with(fmt::layer().pretty())
-        .try_init()
-        .map_err(|_| LogError::AlreadyInitialized)?;
-    Ok(())
 }
@@ -3,6 +3,7 @@
 //! Defines counters, gauges, and histograms for inference monitoring.
 //! Uses the `metrics` facade - no network exporters included.
 
+use crate::engine::ErrorCategory;
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
 
 /// Initialize metric descriptions.
@@ -13,6 +14,9 @@ pub fn init_metrics() {
     describe_counter!("core_requests_total", "Total inference requests");
     describe_counter!("core_requests_success", "Successful inference requests");
     describe_counter!("core_requests_failed", "Failed inference requests");
+    describe_counter!("core_errors_client_total", "Requests rejected as invalid");
+    describe_counter!("core_errors_model_total", "Requests failed by the model");
+    describe_counter!("core_errors_infra_total", "Requests failed by limits, overload, or timeout");
 
     // Latency histograms
     describe_histogram!("core_inference_latency_ms", "Inference latency in milliseconds");
@@ -29,11 +33,18 @@ pub fn init_metrics() {
     describe_gauge!("core_memory_pool_used_bytes", "Memory pool bytes in use");
     describe_gauge!("core_queue_depth", "Number of pending requests");
     describe_gauge!("core_active_sessions", "Number of active sessions");
+    describe_gauge!("core_load_factor", "Combined load signal for autoscaling (1.0 = at limit)");
 
     // Arena metrics (Tier 3)
     describe_gauge!("core_arena_used_bytes", "Arena allocator bytes in use");
     describe_counter!("core_arena_resets_total", "Arena reset count");
 
+    // Model circuit breakers
+    describe_counter!(
+        "core_circuit_transitions_total",
+        "Circuit breaker state changes, by model and new state"
+    );
+
     // Speculative decoding (Tier 3)
     describe_counter!("core_speculative_drafts_total", "Total draft generation cycles");
     describe_counter!("core_speculative_accepted_tokens", "Draft tokens accepted");
@@ -59,6 +70,26 @@ pub fn record_request_failure(model: &str, error_type: &str) {
     .increment(1);
 }
 
+/// Count a request under the entry point it arrived through.
+pub fn record_request_origin(origin: &'static str) {
+    counter!("core_requests_by_origin_total", "origin" => origin).increment(1);
+}
+
+/// Record a failed request under its error category.
+pub fn record_error_category(category: ErrorCategory) {
+    counter!(category.counter_name()).increment(1);
+}
+
+/// Record a model's circuit breaker entering `state`.
+pub fn record_circuit_transition(model: &str, state: &'static str) {
+    counter!(
+        "core_circuit_transitions_total",
+        "model" => model.to_string(),
+        "state" => state
+    )
+    .increment(1);
+}
+
 /// Record memory pool usage.
 pub fn record_memory_pool(used_bytes: usize) {
     gauge!("core_memory_pool_used_bytes").set(used_bytes as f64);
@@ -69,6 +100,11 @@ pub fn record_queue_depth(depth: usize) {
     gauge!("core_queue_depth").set(depth as f64);
 }
 
+/// Record the autoscaling load factor.
+pub fn record_load_factor(load_factor: f64) {
+    gauge!("core_load_factor").set(load_factor);
+}
+
 /// Record speculative decoding cycle stats.
 pub fn record_speculative_cycle(accepted: usize, rejected: usize) {
     counter!("core_speculative_drafts_total").increment(1);
@@ -12,14 +12,24 @@ pub mod span_export;
 mod spans;
 mod store;
 
-pub use buckets::{BucketedHistogram, BucketedHistogramSnapshot};
-pub use logging::{init_logging, LogConfig, LogError, LogFormat};
+pub use buckets::{
+    BucketError, BucketedHistogram, BucketedHistogramSnapshot, HistogramBuckets,
+    INTER_TOKEN_HISTOGRAM, LATENCY_HISTOGRAM, QUEUE_WAIT_HISTOGRAM, TTFT_HISTOGRAM,
+};
+pub use logging::{init_logging, LogConfig, LogError, LogFormat, LOG_FORMAT_ENV};
 pub use metrics::{
-    init_metrics, record_memory_pool, record_queue_depth, record_request_failure,
-    record_request_success, record_speculative_cycle,
+    init_metrics, record_circuit_transition, record_error_category, record_load_factor,
+    record_memory_pool, record_queue_depth, record_request_failure, record_request_origin,
+    record_request_success,
+    record_speculative_cycle,
 };
 pub use prometheus::{encode_bucketed_histogram, encode_prometheus};
 pub use security_log::{log_security_event, SecurityEvent, SecuritySeverity};
-pub use span_export::{ExportableSpan, SpanAttributeValue, SpanCollector, SpanStatus};
+pub use span_export::{
+    ExportableSpan, RequestTrace, SpanAttributeValue, SpanCollector, SpanStatus, REQUEST_ID_ATTRIBUTE,
+};
 pub use spans::{RequestSpan, SpanExt};
-pub use store::{HistogramSummary, MetricsSnapshot, MetricsStore};
+pub use store::{
+    labelled, model_latency_histogram, HistogramSummary, MetricsSnapshot, MetricsStore,
+    MODEL_LATENCY_HISTOGRAM, REQUEST_LATENCY_HISTOGRAM,
+};
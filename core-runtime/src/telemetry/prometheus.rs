@@ -6,6 +6,7 @@
 //! Exports metrics in Prometheus exposition format for scraping.
 //! Format spec: https://prometheus.io/docs/instrumenting/exposition_formats/
 
+use std::collections::HashMap;
 use std::fmt::Write;
 
 use super::buckets::BucketedHistogramSnapshot;
@@ -23,35 +24,74 @@ pub const METRIC_DEFINITIONS: &[MetricHelp] = &[
     MetricHelp { name: "core_requests_total", help: "Total inference requests", metric_type: "counter" },
     MetricHelp { name: "core_requests_success", help: "Successful inference requests", metric_type: "counter" },
     MetricHelp { name: "core_requests_failed", help: "Failed inference requests", metric_type: "counter" },
+    MetricHelp { name: "core_errors_client_total", help: "Requests rejected as invalid", metric_type: "counter" },
+    MetricHelp { name: "core_errors_model_total", help: "Requests failed by the model", metric_type: "counter" },
+    MetricHelp { name: "core_errors_infra_total", help: "Requests failed by limits, overload, or timeout", metric_type: "counter" },
     MetricHelp { name: "core_tokens_generated", help: "Total tokens generated", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_messages_decoded_total", help: "IPC frames decoded into messages", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_decode_errors_total", help: "IPC frames that failed to decode", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_bytes_in_total", help: "IPC bytes received, including frame headers", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_bytes_out_total", help: "IPC bytes sent, including frame headers", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_oversized_frames_total", help: "IPC frames rejected for exceeding the size limit", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_conn_messages_decoded_total", help: "IPC frames decoded on a live connection", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_conn_decode_errors_total", help: "IPC frames that failed to decode on a live connection", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_conn_bytes_in_total", help: "IPC bytes received on a live connection", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_conn_bytes_out_total", help: "IPC bytes sent on a live connection", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_conn_oversized_frames_total", help: "IPC frames rejected for size on a live connection", metric_type: "counter" },
+    MetricHelp { name: "core_ipc_abusive_connections_total", help: "Connections that exceeded the decode error rate", metric_type: "counter" },
+    MetricHelp { name: "core_embedding_cache_hits_total", help: "Embeddings served from the embedding cache", metric_type: "counter" },
+    MetricHelp { name: "core_embedding_cache_misses_total", help: "Embedding cache lookups that ran the model", metric_type: "counter" },
+    MetricHelp { name: "core_embedding_cache_hit_rate", help: "Fraction of embedding lookups served from cache", metric_type: "gauge" },
+    MetricHelp { name: "core_last_successful_inference_age_seconds", help: "Seconds since an inference last succeeded", metric_type: "gauge" },
     MetricHelp { name: "core_queue_depth", help: "Current request queue depth", metric_type: "gauge" },
+    MetricHelp { name: "core_load_factor", help: "Combined load signal for autoscaling", metric_type: "gauge" },
     MetricHelp { name: "core_memory_used_bytes", help: "Memory currently in use", metric_type: "gauge" },
     MetricHelp { name: "core_models_loaded", help: "Number of loaded models", metric_type: "gauge" },
+    MetricHelp { name: "core_request_latency_ms", help: "Completed request latency in milliseconds", metric_type: "summary" },
+    MetricHelp { name: "core_model_latency_ms", help: "Completed request latency per model in milliseconds", metric_type: "summary" },
     MetricHelp { name: "core_latency_ms", help: "Request latency in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_ttft_ms", help: "Time to first streamed token in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_queue_wait_ms", help: "Time spent in the request queue in milliseconds", metric_type: "histogram" },
+    MetricHelp { name: "core_inter_token_ms", help: "Gap between streamed tokens in milliseconds", metric_type: "histogram" },
     MetricHelp { name: "core_throughput_tps", help: "Token throughput per second", metric_type: "histogram" },
 ];
 
 /// Encode metrics snapshot to Prometheus text format.
+///
+/// Series keyed `name{label="value"}` are grouped under one `name` family.
 pub fn encode_prometheus(snapshot: &MetricsSnapshot) -> String {
     let mut output = String::with_capacity(4096);
+    let mut families = Families::default();
 
     // Counters
-    for (name, value) in &snapshot.counters {
-        write_metric_header(&mut output, name);
-        writeln!(output, "{name} {value}").unwrap();
+    for (key, value) in sorted(&snapshot.counters) {
+        let (name, labels) = families.start(&mut output, key);
+        writeln!(output, "{name}{} {value}", series_labels(labels, None)).unwrap();
     }
 
     // Gauges
-    for (name, value) in &snapshot.gauges {
-        write_metric_header(&mut output, name);
-        writeln!(output, "{name} {value}").unwrap();
+    for (key, value) in sorted(&snapshot.gauges) {
+        let (name, labels) = families.start(&mut output, key);
+        writeln!(output, "{name}{} {value}", series_labels(labels, None)).unwrap();
     }
 
     // Summary histograms (basic stats)
-    for (name, summary) in &snapshot.histograms {
-        write_metric_header(&mut output, name);
-        writeln!(output, "{name}_count {}", summary.count).unwrap();
-        writeln!(output, "{name}_sum {}", summary.sum).unwrap();
+    for (key, summary) in sorted(&snapshot.histograms) {
+        let (name, labels) = families.start(&mut output, key);
+        let quantiles = [("0.5", summary.p50_ms), ("0.9", summary.p90_ms), ("0.99", summary.p99_ms)];
+        for (quantile, value) in quantiles {
+            let quantile = format!("quantile=\"{quantile}\"");
+            writeln!(output, "{name}{} {value}", series_labels(labels, Some(&quantile))).unwrap();
+        }
+        let labels = series_labels(labels, None);
+        writeln!(output, "{name}_count{labels} {}", summary.count).unwrap();
+        writeln!(output, "{name}_sum{labels} {}", summary.sum).unwrap();
+    }
+
+    // Bucketed histograms
+    for (key, snap) in sorted(&snapshot.bucketed_histograms) {
+        let (name, labels) = families.start(&mut output, key);
+        write_buckets(&mut output, name, labels, snap);
     }
 
     output
@@ -60,25 +100,68 @@ pub fn encode_prometheus(snapshot: &MetricsSnapshot) -> String {
 /// Encode bucketed histogram to Prometheus format.
 pub fn encode_bucketed_histogram(name: &str, snap: &BucketedHistogramSnapshot) -> String {
     let mut output = String::with_capacity(512);
+    let (name, labels) = Families::default().start(&mut output, name);
+    write_buckets(&mut output, name, labels, snap);
+    output
+}
 
-    write_metric_header(&mut output, name);
-
+fn write_buckets(output: &mut String, name: &str, labels: &str, snap: &BucketedHistogramSnapshot) {
     // Cumulative bucket counts (Prometheus requirement)
     let mut cumulative = 0u64;
     for (i, &boundary) in snap.boundaries.iter().enumerate() {
         cumulative += snap.bucket_counts[i];
-        writeln!(output, "{name}_bucket{{le=\"{boundary}\"}} {cumulative}").unwrap();
+        let le = format!("le=\"{boundary}\"");
+        writeln!(output, "{name}_bucket{} {cumulative}", series_labels(labels, Some(&le))).unwrap();
     }
 
     // +Inf bucket
     cumulative += snap.bucket_counts.last().copied().unwrap_or(0);
-    writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {cumulative}").unwrap();
+    let le = series_labels(labels, Some("le=\"+Inf\""));
+    writeln!(output, "{name}_bucket{le} {cumulative}").unwrap();
 
     // Count and sum
-    writeln!(output, "{name}_count {}", snap.count).unwrap();
-    writeln!(output, "{name}_sum {}", snap.sum).unwrap();
+    let labels = series_labels(labels, None);
+    writeln!(output, "{name}_count{labels} {}", snap.count).unwrap();
+    writeln!(output, "{name}_sum{labels} {}", snap.sum).unwrap();
+}
 
-    output
+/// Entries of `map` by key, so the series of one family are adjacent.
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&str, &V)> {
+    let mut entries: Vec<(&str, &V)> = map.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    entries
+}
+
+/// Writes each family's HELP and TYPE lines once, before its first series.
+#[derive(Default)]
+struct Families<'a> {
+    last: Option<&'a str>,
+}
+
+impl<'a> Families<'a> {
+    /// Split `key` into family name and label pairs, writing the family
+    /// header when `key` starts a new family.
+    fn start(&mut self, output: &mut String, key: &'a str) -> (&'a str, &'a str) {
+        let (name, labels) = match key.split_once('{') {
+            Some((name, rest)) => (name, rest.strip_suffix('}').unwrap_or(rest)),
+            None => (key, ""),
+        };
+        if self.last != Some(name) {
+            write_metric_header(output, name);
+            self.last = Some(name);
+        }
+        (name, labels)
+    }
+}
+
+/// `{labels,extra}` for a series, or nothing when it has no labels.
+fn series_labels(labels: &str, extra: Option<&str>) -> String {
+    match (labels.is_empty(), extra) {
+        (true, None) => String::new(),
+        (true, Some(extra)) => format!("{{{extra}}}"),
+        (false, None) => format!("{{{labels}}}"),
+        (false, Some(extra)) => format!("{{{labels},{extra}}}"),
+    }
 }
 
 fn write_metric_header(output: &mut String, name: &str) {
@@ -91,7 +174,7 @@ fn write_metric_header(output: &mut String, name: &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::telemetry::{labelled, HistogramSummary};
 
     #[test]
     fn test_encode_counter() {
@@ -100,6 +183,9 @@ mod tests {
             gauges: HashMap::new(),
             histograms: HashMap::new(),
             bucketed_histograms: HashMap::new(),
+            p50_ms: 0.0,
+            p90_ms: 0.0,
+            p99_ms: 0.0,
         };
         snapshot.counters.insert("core_requests_total".to_string(), 42);
 
@@ -123,4 +209,36 @@ mod tests {
         assert!(output.contains("test_latency_bucket{le=\"5\"} 5"));
         assert!(output.contains("test_latency_bucket{le=\"+Inf\"} 7"));
     }
+
+    #[test]
+    fn test_encode_labelled_series_share_one_family() {
+        let mut snapshot = MetricsSnapshot {
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            histograms: HashMap::new(),
+            bucketed_histograms: HashMap::new(),
+            p50_ms: 0.0,
+            p90_ms: 0.0,
+            p99_ms: 0.0,
+        };
+        let summary = HistogramSummary {
+            count: 2,
+            sum: 30.0,
+            min: 10.0,
+            max: 20.0,
+            p50_ms: 10.0,
+            p90_ms: 20.0,
+            p99_ms: 20.0,
+        };
+        for model in ["phi", "llama"] {
+            let key = labelled("core_model_latency_ms", "model", model);
+            snapshot.histograms.insert(key, summary.clone());
+        }
+
+        let output = encode_prometheus(&snapshot);
+        assert_eq!(output.matches("# TYPE core_model_latency_ms summary").count(), 1);
+        assert!(output.contains("core_model_latency_ms{model=\"phi\",quantile=\"0.5\"} 10"));
+        assert!(output.contains("core_model_latency_ms_count{model=\"llama\"} 2"));
+        assert!(output.contains("core_model_latency_ms_sum{model=\"phi\"} 30"));
+    }
 }
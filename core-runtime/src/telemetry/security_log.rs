@@ -20,6 +20,8 @@ pub enum SecurityEvent {
     SessionExpired,
     /// Session validated.
     SessionValidated,
+    /// Active session cap reached; a session was evicted or refused.
+    SessionLimitReached,
     /// Invalid session token used.
     InvalidSession,
     /// Path traversal attempt detected.
@@ -28,12 +30,22 @@ pub enum SecurityEvent {
     InputValidationFailure,
     /// Output filter triggered.
     OutputFiltered,
+    /// Chat template delimiter found (and escaped) in message content.
+    TemplateInjection,
     /// Resource limit exceeded.
     ResourceLimitExceeded,
+    /// Request rejected because available system memory is below the floor.
+    MemoryPressure,
     /// Model hash verification failure.
     ModelHashMismatch,
     /// Sandbox violation attempt.
     SandboxViolation,
+    /// A decode loop hit the absolute step cap (indicates a bug).
+    DecodeStepLimit,
+    /// A connection sent mostly undecodable frames (likely fuzzing).
+    ProtocolAbuse,
+    /// A connection sent nothing within the idle timeout and was closed.
+    IdleConnectionClosed,
 }
 
 impl SecurityEvent {
@@ -46,13 +58,19 @@ impl SecurityEvent {
             Self::SessionCreated => SecuritySeverity::Info,
             Self::SessionExpired => SecuritySeverity::Info,
             Self::SessionValidated => SecuritySeverity::Debug,
+            Self::SessionLimitReached => SecuritySeverity::Warning,
             Self::InvalidSession => SecuritySeverity::Warning,
             Self::PathTraversalAttempt => SecuritySeverity::Critical,
             Self::InputValidationFailure => SecuritySeverity::Warning,
             Self::OutputFiltered => SecuritySeverity::Info,
+            Self::TemplateInjection => SecuritySeverity::Warning,
             Self::ResourceLimitExceeded => SecuritySeverity::Warning,
+            Self::MemoryPressure => SecuritySeverity::Warning,
             Self::ModelHashMismatch => SecuritySeverity::Critical,
             Self::SandboxViolation => SecuritySeverity::Critical,
+            Self::DecodeStepLimit => SecuritySeverity::Critical,
+            Self::ProtocolAbuse => SecuritySeverity::Warning,
+            Self::IdleConnectionClosed => SecuritySeverity::Info,
         }
     }
 
@@ -65,13 +83,19 @@ impl SecurityEvent {
             Self::SessionCreated => "session_created",
             Self::SessionExpired => "session_expired",
             Self::SessionValidated => "session_validated",
+            Self::SessionLimitReached => "session_limit_reached",
             Self::InvalidSession => "invalid_session",
             Self::PathTraversalAttempt => "path_traversal_attempt",
             Self::InputValidationFailure => "input_validation_failure",
             Self::OutputFiltered => "output_filtered",
+            Self::TemplateInjection => "template_injection",
             Self::ResourceLimitExceeded => "resource_limit_exceeded",
+            Self::MemoryPressure => "memory_pressure",
             Self::ModelHashMismatch => "model_hash_mismatch",
             Self::SandboxViolation => "sandbox_violation",
+            Self::DecodeStepLimit => "decode_step_limit",
+            Self::ProtocolAbuse => "protocol_abuse",
+            Self::IdleConnectionClosed => "idle_connection_closed",
         }
     }
 }
@@ -15,6 +15,9 @@ use serde::{Deserialize, Serialize};
 /// Maximum spans retained in the collector buffer.
 const MAX_SPAN_BUFFER: usize = 1000;
 
+/// Attribute carrying the IPC request ID on request spans.
+pub const REQUEST_ID_ATTRIBUTE: &str = "request_id";
+
 /// Attribute value types for span attributes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -38,6 +41,16 @@ pub struct ExportableSpan {
     pub attributes: HashMap<String, SpanAttributeValue>,
 }
 
+impl ExportableSpan {
+    /// Request ID this span was recorded for, if any.
+    pub fn request_id(&self) -> Option<u64> {
+        match self.attributes.get(REQUEST_ID_ATTRIBUTE) {
+            Some(SpanAttributeValue::Int(id)) => Some(*id as u64),
+            _ => None,
+        }
+    }
+}
+
 /// Span completion status.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -83,6 +96,19 @@ impl SpanCollector {
         spans.drain(..count).collect()
     }
 
+    /// Up to `max_count` buffered spans for one request, oldest first.
+    ///
+    /// Unlike `drain`, matching spans stay buffered for other exporters.
+    pub fn for_request(&self, request_id: u64, max_count: usize) -> Vec<ExportableSpan> {
+        let spans = self.spans.read().unwrap();
+        spans
+            .iter()
+            .filter(|span| span.request_id() == Some(request_id))
+            .take(max_count)
+            .cloned()
+            .collect()
+    }
+
     /// Get current span count.
     pub fn len(&self) -> usize {
         self.spans.read().unwrap().len()
@@ -100,6 +126,62 @@ impl Default for SpanCollector {
     }
 }
 
+/// Spans for one request's phases, sharing a trace under a root span.
+pub struct RequestTrace {
+    trace_id: String,
+    root_span_id: String,
+    request_id: u64,
+    model_id: String,
+    start_time_unix_ns: u64,
+}
+
+impl RequestTrace {
+    /// Start tracing a request now.
+    pub fn start(request_id: u64, model_id: &str) -> Self {
+        Self {
+            trace_id: generate_trace_id(),
+            root_span_id: generate_span_id(),
+            request_id,
+            model_id: model_id.to_string(),
+            start_time_unix_ns: now_unix_ns(),
+        }
+    }
+
+    /// Child span for a phase that started at `start_time_unix_ns` and ends now.
+    pub fn phase(&self, name: &str, start_time_unix_ns: u64, ok: bool) -> ExportableSpan {
+        let mut span = self.span(generate_span_id(), name, start_time_unix_ns, ok);
+        span.parent_span_id = Some(self.root_span_id.clone());
+        span
+    }
+
+    /// Root span covering the whole request, ending now.
+    pub fn finish(self, name: &str, ok: bool) -> ExportableSpan {
+        self.span(self.root_span_id.clone(), name, self.start_time_unix_ns, ok)
+    }
+
+    fn span(&self, span_id: String, name: &str, start_time_unix_ns: u64, ok: bool) -> ExportableSpan {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            REQUEST_ID_ATTRIBUTE.to_string(),
+            SpanAttributeValue::Int(self.request_id as i64),
+        );
+        attributes.insert(
+            "model_id".to_string(),
+            SpanAttributeValue::String(self.model_id.clone()),
+        );
+        ExportableSpan {
+            trace_id: self.trace_id.clone(),
+            span_id,
+            parent_span_id: None,
+            name: name.to_string(),
+            start_time_unix_ns,
+            end_time_unix_ns: now_unix_ns(),
+            status: if ok { SpanStatus::Ok } else { SpanStatus::Error },
+            attributes,
+        }
+    }
+}
+
 /// Helper to get current time in nanoseconds since Unix epoch.
 pub fn now_unix_ns() -> u64 {
     SystemTime::now()
@@ -156,4 +238,22 @@ mod tests {
         assert_eq!(drained.len(), 2);
         assert_eq!(drained[0].trace_id, "trace_2");
     }
+
+    #[test]
+    fn test_for_request_filters_without_draining() {
+        let collector = SpanCollector::new();
+        for request_id in [7, 8] {
+            let trace = RequestTrace::start(request_id, "m");
+            collector.record(trace.phase("queue", now_unix_ns(), true));
+            collector.record(trace.finish("inference", true));
+        }
+
+        let spans = collector.for_request(7, 10);
+        assert_eq!(spans.len(), 2);
+        assert!(spans.iter().all(|s| s.request_id() == Some(7)));
+        assert_eq!(spans[0].parent_span_id.as_deref(), Some(spans[1].span_id.as_str()));
+        assert_eq!(collector.len(), 4);
+        assert_eq!(collector.for_request(7, 1).len(), 1);
+        assert!(collector.for_request(9, 10).is_empty());
+    }
 }
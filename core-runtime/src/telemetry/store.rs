@@ -10,7 +10,37 @@ use std::sync::RwLock;
 
 use serde::{Deserialize, Serialize};
 
-use super::buckets::{BucketedHistogram, BucketedHistogramSnapshot};
+use super::buckets::{BucketedHistogram, BucketedHistogramSnapshot, HistogramBuckets};
+
+/// Summary histogram fed with each completed request's total latency.
+pub const REQUEST_LATENCY_HISTOGRAM: &str = "core_request_latency_ms";
+
+/// Summary histogram of completed request latencies, one series per model.
+pub const MODEL_LATENCY_HISTOGRAM: &str = "core_model_latency_ms";
+
+/// Key of one model's series of [`MODEL_LATENCY_HISTOGRAM`].
+pub fn model_latency_histogram(model_id: &str) -> String {
+    labelled(MODEL_LATENCY_HISTOGRAM, "model", model_id)
+}
+
+/// Key of the series of metric `name` with `label` set to `value`, in the
+/// Prometheus `name{label="value"}` form the exporter groups by `name`.
+pub fn labelled(name: &str, label: &str, value: &str) -> String {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("{name}{{{label}=\"{value}\"}}")
+}
+
+/// Lower edge of the percentile buckets; smaller values share bucket 0.
+const PERCENTILE_MIN: f64 = 0.01;
+/// Log-spaced percentile buckets per factor of ten (~12% wide each).
+const PERCENTILE_BUCKETS_PER_DECADE: usize = 20;
+/// Decades covered above `PERCENTILE_MIN` (0.01 ms to ~17 minutes).
+const PERCENTILE_DECADES: usize = 8;
+/// Underflow bucket, log-spaced buckets, overflow bucket.
+const PERCENTILE_BUCKETS: usize = PERCENTILE_BUCKETS_PER_DECADE * PERCENTILE_DECADES + 2;
 
 /// Snapshot of all metrics at a point in time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,15 +50,68 @@ pub struct MetricsSnapshot {
     pub histograms: HashMap<String, HistogramSummary>,
     #[serde(default)]
     pub bucketed_histograms: HashMap<String, BucketedHistogramSnapshot>,
+    /// Request latency percentiles from [`REQUEST_LATENCY_HISTOGRAM`];
+    /// zero until a request completes.
+    #[serde(default)]
+    pub p50_ms: f64,
+    #[serde(default)]
+    pub p90_ms: f64,
+    #[serde(default)]
+    pub p99_ms: f64,
 }
 
 /// Summary statistics for a histogram.
+///
+/// Percentiles are estimated from fixed log-spaced buckets, so they are
+/// accurate to about one bucket (~12%) and clamped to `min..=max`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramSummary {
     pub count: u64,
     pub sum: f64,
     pub min: f64,
     pub max: f64,
+    #[serde(default)]
+    pub p50_ms: f64,
+    #[serde(default)]
+    pub p90_ms: f64,
+    #[serde(default)]
+    pub p99_ms: f64,
+}
+
+/// Percentile bucket holding `value`.
+fn percentile_bucket(value: f64) -> usize {
+    if value.is_nan() || value <= PERCENTILE_MIN {
+        return 0;
+    }
+    let steps = ((value / PERCENTILE_MIN).log10() * PERCENTILE_BUCKETS_PER_DECADE as f64).ceil();
+    (steps as usize).clamp(1, PERCENTILE_BUCKETS - 1)
+}
+
+/// Upper edge of a log-spaced percentile bucket.
+fn percentile_bucket_upper(bucket: usize) -> f64 {
+    PERCENTILE_MIN * 10f64.powf(bucket as f64 / PERCENTILE_BUCKETS_PER_DECADE as f64)
+}
+
+/// Estimate the `quantile` (0.0..=1.0) as the upper edge of the bucket
+/// holding that rank, clamped to the observed range.
+fn percentile(counts: &[u64], total: u64, min: f64, max: f64, quantile: f64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let rank = ((quantile * total as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (bucket, &count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            let estimate = match bucket {
+                0 => min,
+                b if b == PERCENTILE_BUCKETS - 1 => max,
+                b => percentile_bucket_upper(b),
+            };
+            return estimate.clamp(min, max);
+        }
+    }
+    max
 }
 
 /// Internal histogram data with atomic fields.
@@ -37,6 +120,7 @@ struct HistogramData {
     sum: AtomicU64,   // f64 bits stored as u64
     min: AtomicU64,   // f64 bits stored as u64
     max: AtomicU64,   // f64 bits stored as u64
+    buckets: Box<[AtomicU64]>,
 }
 
 impl HistogramData {
@@ -46,6 +130,7 @@ impl HistogramData {
             sum: AtomicU64::new(f64::to_bits(0.0)),
             min: AtomicU64::new(f64::to_bits(f64::MAX)),
             max: AtomicU64::new(f64::to_bits(f64::MIN)),
+            buckets: (0..PERCENTILE_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
         }
     }
 
@@ -54,8 +139,10 @@ impl HistogramData {
         self.atomic_add_f64(&self.sum, value);
         self.atomic_min_f64(&self.min, value);
         self.atomic_max_f64(&self.max, value);
+        self.buckets[percentile_bucket(value)].fetch_add(1, Ordering::Relaxed);
     }
 
+
     fn atomic_add_f64(&self, atomic: &AtomicU64, value: f64) {
         loop {
             let current = atomic.load(Ordering::Relaxed);
@@ -97,12 +184,21 @@ impl HistogramData {
         let sum = f64::from_bits(self.sum.load(Ordering::Relaxed));
         let min = f64::from_bits(self.min.load(Ordering::Relaxed));
         let max = f64::from_bits(self.max.load(Ordering::Relaxed));
+        let (min, max) = if count == 0 { (0.0, 0.0) } else { (min, max) };
+
+        // Buckets are read one by one while writers may still be adding, so
+        // rank against their own total rather than `count`
+        let counts: Vec<u64> = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        let total = counts.iter().sum();
 
         HistogramSummary {
             count,
             sum,
-            min: if count == 0 { 0.0 } else { min },
-            max: if count == 0 { 0.0 } else { max },
+            min,
+            max,
+            p50_ms: percentile(&counts, total, min, max, 0.50),
+            p90_ms: percentile(&counts, total, min, max, 0.90),
+            p99_ms: percentile(&counts, total, min, max, 0.99),
         }
     }
 }
@@ -174,6 +270,22 @@ impl MetricsStore {
         histogram.record(value);
     }
 
+    /// Summary of one histogram, if anything was recorded under `name`.
+    pub fn histogram(&self, name: &str) -> Option<HistogramSummary> {
+        self.histograms.read().unwrap().get(name).map(HistogramData::to_summary)
+    }
+
+    /// Create a store with the request timing histograms registered.
+    ///
+    /// Boundaries are not checked here; see [`HistogramBuckets::validate`].
+    pub fn with_histogram_buckets(buckets: &HistogramBuckets) -> Self {
+        let store = Self::new();
+        for (name, boundaries) in buckets.histograms() {
+            store.register_bucketed(name, boundaries);
+        }
+        store
+    }
+
     /// Register a bucketed histogram with custom boundaries.
     pub fn register_bucketed(&self, name: &str, boundaries: &[f64]) {
         let mut bucketed = self.bucketed_histograms.write().unwrap();
@@ -197,6 +309,11 @@ impl MetricsStore {
         let histograms = self.histograms.read().unwrap();
         let bucketed = self.bucketed_histograms.read().unwrap();
 
+        let latency = histograms.get(REQUEST_LATENCY_HISTOGRAM).map(HistogramData::to_summary);
+        let (p50_ms, p90_ms, p99_ms) = latency
+            .map(|l| (l.p50_ms, l.p90_ms, l.p99_ms))
+            .unwrap_or_default();
+
         MetricsSnapshot {
             counters: counters
                 .iter()
@@ -214,6 +331,9 @@ impl MetricsStore {
                 .iter()
                 .map(|(k, v)| (k.clone(), v.snapshot()))
                 .collect(),
+            p50_ms,
+            p90_ms,
+            p99_ms,
         }
     }
 }
@@ -223,3 +343,49 @@ impl Default for MetricsStore {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_within_one_bucket() {
+        let store = MetricsStore::new();
+        // 1..=1000 ms, once each
+        for ms in 1..=1000 {
+            store.record_histogram(REQUEST_LATENCY_HISTOGRAM, ms as f64);
+        }
+        let snapshot = store.snapshot();
+        let summary = &snapshot.histograms[REQUEST_LATENCY_HISTOGRAM];
+
+        for (estimate, truth) in [
+            (summary.p50_ms, 500.0),
+            (summary.p90_ms, 900.0),
+            (summary.p99_ms, 990.0),
+        ] {
+            let off = percentile_bucket(estimate).abs_diff(percentile_bucket(truth));
+            assert!(off <= 1, "estimate {} vs {} is {} buckets off", estimate, truth, off);
+        }
+        assert_eq!(snapshot.p50_ms, summary.p50_ms);
+        assert_eq!(snapshot.p99_ms, summary.p99_ms);
+    }
+
+    #[test]
+    fn test_percentiles_skewed_and_out_of_range() {
+        let data = HistogramData::new();
+        assert_eq!(data.to_summary().p99_ms, 0.0);
+
+        // 98 fast requests, two that hit the overflow bucket
+        for _ in 0..98 {
+            data.record(20.0);
+        }
+        data.record(5e9);
+        data.record(6e9);
+        let summary = data.to_summary();
+        assert!(percentile_bucket(summary.p50_ms).abs_diff(percentile_bucket(20.0)) <= 1);
+        assert!(percentile_bucket(summary.p90_ms).abs_diff(percentile_bucket(20.0)) <= 1);
+        assert_eq!(summary.p99_ms, 6e9);
+        assert_eq!(percentile_bucket(0.0), 0);
+        assert_eq!(percentile_bucket(f64::MAX), PERCENTILE_BUCKETS - 1);
+    }
+}
@@ -0,0 +1,169 @@
+//! Scalar fallback when CPU accelerations fail to initialize.
+
+use std::sync::Arc;
+
+use gg_core::engine::accel::{self, AccelInitError};
+use gg_core::engine::{
+    Accelerations, FinishReason, FlashAttnConfig, GenerationResult, GgufModel, InferenceCapability,
+    InferenceConfig, InferenceError, InferenceInput, InferenceOutput,
+};
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
+};
+use gg_core::memory::Q8KvStore;
+use gg_core::models::ModelHandle;
+use gg_core::shutdown::ShutdownState;
+
+const HIDDEN: usize = 8;
+
+fn keys() -> Vec<Vec<f32>> {
+    (0..5)
+        .map(|pos| {
+            (0..HIDDEN)
+                .map(|i| ((pos * HIDDEN + i) as f32 * 0.3).sin())
+                .collect()
+        })
+        .collect()
+}
+
+fn query() -> Vec<f32> {
+    (0..HIDDEN).map(|i| (i as f32 * 0.5).cos()).collect()
+}
+
+/// Scores a fixed query against a quantized KV store through the routed kernels.
+struct AttentionModel;
+
+#[async_trait::async_trait]
+impl GgufModel for AttentionModel {
+    fn model_id(&self) -> &str {
+        "attention-model"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        let mut store = Q8KvStore::new(HIDDEN, 8);
+        for k in keys() {
+            store.append(&k, &k);
+        }
+        let mut scores = vec![0.0; 5];
+        store.attention_scores(&query(), &mut scores);
+        let text = scores
+            .iter()
+            .map(|s| format!("{:.4}", s))
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text,
+            tokens_generated: 1,
+            finish_reason: FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn expected_scores() -> String {
+    keys()
+        .iter()
+        .map(|k| {
+            let scale = gg_core::memory::compute_scale(k);
+            let mut q = vec![0u8; HIDDEN];
+            gg_core::memory::quantize_to(&mut q, k, scale);
+            format!("{:.4}", accel::scalar_dot_q8(&q, &query(), scale))
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[tokio::test]
+async fn failed_acceleration_init_serves_scalar_inference() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "attention-model".into(),
+            ModelHandle::new(1),
+            Arc::new(AttentionModel),
+        )
+        .await;
+
+    // Simulate both accelerations failing on this CPU after startup probing
+    let accel = accel::probe(
+        || Err(AccelInitError::Panicked("simd")),
+        || {
+            Err(AccelInitError::SelfTestMismatch {
+                feature: "flash_attention",
+                expected: 1.0,
+                actual: f32::NAN,
+            })
+        },
+    );
+    accel::activate(&accel);
+    assert_eq!(accel::active(), Accelerations::default());
+
+    let report = runtime.health.report(ShutdownState::Running, 1, 0, 0);
+    assert!(!report.accelerations.simd);
+    assert!(!report.accelerations.flash_attention);
+
+    let handler = &runtime.ipc_handler;
+    let handshake = IpcMessage::Handshake {
+        token: "test-token".into(),
+        protocol_version: None,
+        compression: None,
+        strict_version: false,
+    };
+    let (_, session) = handler
+        .process(&encode_message(&handshake).unwrap(), None)
+        .await
+        .unwrap();
+    let request = InferenceRequest {
+        request_id: RequestId(1),
+        model_id: "attention-model".into(),
+        prompt: "Hello".into(),
+        parameters: Default::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+    let message = encode_message(&IpcMessage::InferenceRequest(request)).unwrap();
+    let (bytes, _) = handler.process(&message, session.as_ref()).await.unwrap();
+    let response = match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    };
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.output, expected_scores());
+
+    // Attention falls back to the untiled reference
+    let config = FlashAttnConfig {
+        block_size: 2,
+        head_dim: HIDDEN,
+    };
+    let flat: Vec<f32> = keys().concat();
+    let mut routed = vec![0.0; HIDDEN];
+    accel::attention(&config, &query(), &flat, &flat, 5, &mut routed);
+    let mut reference = vec![0.0; HIDDEN];
+    accel::naive_attention(&query(), &flat, &flat, 5, HIDDEN, &mut reference);
+    assert_eq!(routed, reference);
+}
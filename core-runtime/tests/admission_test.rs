@@ -0,0 +1,108 @@
+//! Admission control: requests turned away before any model work, and the
+//! queue slots admitted requests hold.
+
+mod common;
+
+use common::{error_counters, handshake, infer_once, FailingModel};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
+};
+use gg_core::scheduler::RequestQueueConfig;
+
+#[tokio::test]
+async fn memory_floor_rejects_at_admission() {
+    let meminfo = std::env::temp_dir().join(format!("gg-core-meminfo-e2e-{}", std::process::id()));
+    std::fs::write(&meminfo, "MemTotal: 16384000 kB\nMemAvailable: 1024 kB\n").unwrap();
+
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        memory_floor: gg_core::memory::MemoryFloorConfig {
+            min_available_bytes: Some(64 * 1024 * 1024),
+            meminfo_path: meminfo.clone(),
+        },
+        ..Default::default()
+    });
+    let response = infer_once(&runtime, "unloaded-model").await;
+    let _ = std::fs::remove_file(&meminfo);
+
+    assert_eq!(response.error_code, Some(503));
+    assert!(response.error.unwrap().contains("Memory pressure"));
+    assert_eq!(runtime.request_queue.len().await, 0);
+}
+
+#[tokio::test]
+async fn oversized_prompt_rejected_before_model_work() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        max_prompt_bytes: 16,
+        ..Default::default()
+    });
+    // Any model run would surface as a 502
+    runtime
+        .inference_engine
+        .register_model(
+            "failing-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(FailingModel(|| {
+                gg_core::engine::InferenceError::ModelError("model ran".into())
+            })),
+        )
+        .await;
+    let handler = &runtime.ipc_handler;
+    let session = handshake(&runtime).await;
+
+    let request = InferenceRequest {
+        request_id: RequestId(1),
+        model_id: "failing-model".into(),
+        prompt: "token ".repeat(1_000_000),
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+    let message = encode_message(&IpcMessage::InferenceRequest(request)).unwrap();
+    let (bytes, _) = handler.process(&message, Some(&session)).await.unwrap();
+    let response = match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    };
+
+    assert_eq!(response.error_code, Some(400));
+    assert!(response.error.unwrap().contains("Prompt too long"));
+    assert_eq!(error_counters(&runtime), [1, 0, 0]);
+    assert_eq!(runtime.request_queue.len().await, 0);
+}
+
+#[tokio::test]
+async fn queue_wait_timeout_reported_as_retryable_code() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        request_queue: RequestQueueConfig {
+            max_pending: 0,
+            enqueue_timeout: Some(std::time::Duration::from_millis(20)),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let response = infer_once(&runtime, "any-model").await;
+    assert_eq!(response.error_code, Some(gg_core::ipc::protocol::QUEUE_TIMEOUT_ERROR_CODE));
+    assert!(response.error.unwrap().contains("retry"));
+}
+
+#[tokio::test]
+async fn finished_requests_return_their_queue_slot() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        request_queue: RequestQueueConfig { max_pending: 1, ..Default::default() },
+        ..Default::default()
+    });
+
+    // Each request is queued, fails on the missing model, then leaves
+    for _ in 0..3 {
+        let response = infer_once(&runtime, "unloaded-model").await;
+        assert_eq!(response.error_code, Some(400));
+    }
+    assert_eq!(runtime.request_queue.len().await, 0);
+}
@@ -86,6 +86,9 @@ fn chaos_ipc_massive_prompt() {
         model_id: "test".to_string(),
         prompt: large_prompt,
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     if let Ok(bytes) = encode_message(&msg) {
@@ -98,7 +101,7 @@ fn chaos_ipc_invalid_inference_params() {
     let bad = vec![
         InferenceParams { max_tokens: 0, ..Default::default() },
         InferenceParams { temperature: -1.0, ..Default::default() },
-        InferenceParams { top_p: 0.0, ..Default::default() },
+        InferenceParams { top_p: -0.1, ..Default::default() },
         InferenceParams { top_p: 1.5, ..Default::default() },
     ];
     for (i, p) in bad.iter().enumerate() {
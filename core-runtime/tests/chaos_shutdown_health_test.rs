@@ -81,7 +81,7 @@ async fn chaos_shutdown_concurrent_track_during_drain() {
 
 #[test]
 fn chaos_health_degraded_no_models() {
-    let cfg = HealthConfig { require_model_loaded: true, max_queue_depth: 100 };
+    let cfg = HealthConfig { require_model_loaded: true, max_queue_depth: 100, ..Default::default() };
     let report = HealthChecker::new(cfg).report(ShutdownState::Running, 0, 0, 0);
     assert_eq!(report.state, HealthState::Degraded);
     assert!(!report.ready);
@@ -89,7 +89,7 @@ fn chaos_health_degraded_no_models() {
 
 #[test]
 fn chaos_health_degraded_queue_full() {
-    let cfg = HealthConfig { require_model_loaded: false, max_queue_depth: 100 };
+    let cfg = HealthConfig { require_model_loaded: false, max_queue_depth: 100, ..Default::default() };
     let report = HealthChecker::new(cfg).report(ShutdownState::Running, 1, 1024, 100);
     assert_eq!(report.state, HealthState::Degraded);
     assert!(!report.ready);
@@ -171,7 +171,7 @@ async fn chaos_combined_resource_limits_and_queue() {
     let limits = ResourceLimits::new(ResourceLimitsConfig {
         max_memory_per_call: 1024, max_total_memory: 2048, max_concurrent: 2,
     });
-    let queue = RequestQueue::new(RequestQueueConfig { max_pending: 5 });
+    let queue = RequestQueue::new(RequestQueueConfig { max_pending: 5, ..Default::default() });
     let mut guards = vec![];
     let mut enqueued = 0;
     for i in 0..10 {
@@ -189,7 +189,10 @@ async fn chaos_combined_resource_limits_and_queue() {
 #[tokio::test]
 async fn chaos_combined_shutdown_and_queue() {
     let shutdown = Arc::new(ShutdownCoordinator::new());
-    let queue = Arc::new(RequestQueue::new(RequestQueueConfig { max_pending: 100 }));
+    let queue = Arc::new(RequestQueue::new(RequestQueueConfig {
+        max_pending: 100,
+        ..Default::default()
+    }));
     for i in 0..10u32 {
         queue.enqueue("model".into(), format!("prompt {}", i), InferenceParams::default(), Priority::Normal)
             .await.unwrap();
@@ -208,7 +211,7 @@ async fn chaos_combined_shutdown_and_queue() {
 
 #[test]
 fn chaos_connection_pool_concurrent_stress() {
-    let pool = Arc::new(ConnectionPool::new(ConnectionConfig { max_connections: 10 }));
+    let pool = Arc::new(ConnectionPool::new(ConnectionConfig { max_connections: 10, ..Default::default() }));
     let mut handles = vec![];
     for _ in 0..8 {
         let p = Arc::clone(&pool);
@@ -227,7 +230,7 @@ fn chaos_connection_pool_concurrent_stress() {
 
 #[test]
 fn chaos_connection_pool_exhaustion() {
-    let pool = ConnectionPool::new(ConnectionConfig { max_connections: 3 });
+    let pool = ConnectionPool::new(ConnectionConfig { max_connections: 3, ..Default::default() });
     let _g1 = pool.try_acquire().unwrap();
     let _g2 = pool.try_acquire().unwrap();
     let _g3 = pool.try_acquire().unwrap();
@@ -236,6 +239,6 @@ fn chaos_connection_pool_exhaustion() {
 
 #[test]
 fn chaos_connection_pool_zero_max() {
-    let pool = ConnectionPool::new(ConnectionConfig { max_connections: 0 });
+    let pool = ConnectionPool::new(ConnectionConfig { max_connections: 0, ..Default::default() });
     assert!(pool.try_acquire().is_none());
 }
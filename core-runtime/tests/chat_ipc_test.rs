@@ -0,0 +1,78 @@
+//! `ChatRequest` messages are rendered with the model's chat template and
+//! served like inference requests.
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{send_authenticated, MockModel};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{IpcMessage, RequestId};
+
+fn chat_request(messages: Vec<gg_core::engine::ChatMessage>) -> IpcMessage {
+    IpcMessage::ChatRequest(gg_core::ipc::ChatRequest {
+        request_id: RequestId(1),
+        model_id: String::new(),
+        messages,
+        parameters: InferenceParams::default(),
+        client_metadata: Some("trace-7".into()),
+        priority: None,
+        client_id: None,
+    })
+}
+
+async fn runtime_with_chat_model() -> gg_core::Runtime {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        default_model: Some("prompt-echo".into()),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "prompt-echo".into(),
+            gg_core::models::ModelHandle::new(1),
+            Arc::new(MockModel::new("prompt-echo").echoing_prompt()),
+        )
+        .await;
+    runtime
+}
+
+#[tokio::test]
+async fn chat_request_renders_model_template_before_inference() {
+    use gg_core::engine::{ChatMessage, ChatRole, ChatTemplate};
+
+    let runtime = runtime_with_chat_model().await;
+    let template = ChatTemplate::from_jinja(
+        "{% for message in messages %}[{{ message.role }}]{{ message.content }}\n\
+         {% endfor %}[assistant]",
+    )
+    .unwrap();
+    runtime.inference_engine.set_chat_template("prompt-echo", template).await;
+
+    let messages = vec![
+        ChatMessage { role: ChatRole::System, content: "Be brief.".into() },
+        ChatMessage { role: ChatRole::User, content: "Hi".into() },
+    ];
+    let response = match send_authenticated(&runtime, chat_request(messages)).await {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    };
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.output, "[system]Be brief.\n[user]Hi\n[assistant]");
+    assert_eq!(response.client_metadata.as_deref(), Some("trace-7"));
+}
+
+#[tokio::test]
+async fn empty_chat_request_is_a_client_error() {
+    let runtime = runtime_with_chat_model().await;
+
+    let response = match send_authenticated(&runtime, chat_request(Vec::new())).await {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    };
+
+    assert!(response.error.unwrap().contains("messages cannot be empty"));
+    assert_eq!(response.error_code, Some(400));
+}
@@ -0,0 +1,91 @@
+//! Chat template rendering from model manifests.
+
+use gg_core::engine::{ChatMessage, ChatRole, InferenceEngine};
+use gg_core::models::ModelManifest;
+
+/// ChatML-style manifest, as it would be shipped next to a model file.
+const CHATML_MANIFEST: &str = r#"{
+    "model_id": "chatml-model",
+    "name": "ChatML",
+    "version": "1.0.0",
+    "capabilities": ["text_generation"],
+    "size_bytes": 1024,
+    "architecture": "gguf",
+    "license": "MIT",
+    "chat_template": "{% for message in messages %}<|im_start|>{{ message.role }}\n{{ message.content }}<|im_end|>\n{% endfor %}{% if add_generation_prompt %}<|im_start|>assistant\n{% endif %}"
+}"#;
+
+fn message(role: ChatRole, content: &str) -> ChatMessage {
+    ChatMessage { role, content: content.into() }
+}
+
+fn two_turns() -> Vec<ChatMessage> {
+    vec![
+        message(ChatRole::System, "You are terse."),
+        message(ChatRole::User, "Hi"),
+        message(ChatRole::Assistant, "Hello."),
+        message(ChatRole::User, "Bye"),
+    ]
+}
+
+#[tokio::test]
+async fn two_turn_conversation_renders_manifest_template() {
+    let manifest = ModelManifest::from_json(CHATML_MANIFEST).unwrap();
+    manifest.validate().unwrap();
+    let engine = InferenceEngine::new(4096);
+    engine
+        .set_chat_template(&manifest.model_id, manifest.chat_template().unwrap())
+        .await;
+
+    let prompt = engine.render_chat("chatml-model", &two_turns()).await.unwrap();
+
+    assert_eq!(
+        prompt,
+        "<|im_start|>system\nYou are terse.<|im_end|>\n\
+         <|im_start|>user\nHi<|im_end|>\n\
+         <|im_start|>assistant\nHello.<|im_end|>\n\
+         <|im_start|>user\nBye<|im_end|>\n\
+         <|im_start|>assistant\n"
+    );
+}
+
+#[tokio::test]
+async fn model_without_template_uses_default_role_tags() {
+    let engine = InferenceEngine::new(4096);
+
+    let prompt = engine.render_chat("plain-model", &two_turns()[1..]).await.unwrap();
+
+    assert_eq!(
+        prompt,
+        "<|user|>Hi<|end|>\n<|assistant|>Hello.<|end|>\n<|user|>Bye<|end|>\n<|assistant|>"
+    );
+}
+
+#[tokio::test]
+async fn template_is_dropped_with_its_model() {
+    let manifest = ModelManifest::from_json(CHATML_MANIFEST).unwrap();
+    let engine = InferenceEngine::new(4096);
+    engine
+        .set_chat_template(&manifest.model_id, manifest.chat_template().unwrap())
+        .await;
+
+    engine.unregister_model("chatml-model").await;
+
+    let prompt = engine.render_chat("chatml-model", &two_turns()[1..2]).await.unwrap();
+    assert_eq!(prompt, "<|user|>Hi<|end|>\n<|assistant|>");
+}
+
+#[tokio::test]
+async fn empty_conversation_is_rejected() {
+    let engine = InferenceEngine::new(4096);
+    assert!(engine.render_chat("any", &[]).await.is_err());
+}
+
+#[test]
+fn manifest_with_unsupported_template_fails_validation() {
+    let mut manifest = ModelManifest::from_json(CHATML_MANIFEST).unwrap();
+    manifest.chat_template = Some("{{ messages | tojson }}".into());
+
+    let error = manifest.validate().unwrap_err();
+    assert!(error.to_string().contains("invalid chat_template"), "{}", error);
+}
@@ -0,0 +1,213 @@
+//! Per-model circuit breaking on repeated inference failures.
+
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{handshake, infer, send, MockModel, TEST_TOKEN};
+use gg_core::ipc::protocol::IpcMessage;
+use gg_core::models::{CircuitBreakerConfig, CircuitState, ModelHandle, ModelRouter, RouterError};
+use gg_core::security::audit::{audit_logger, init_audit_logger, AuditConfig};
+use gg_core::security::AuditCategory;
+use gg_core::{Runtime, RuntimeConfig};
+
+const COOLDOWN: Duration = Duration::from_millis(50);
+
+fn router() -> ModelRouter {
+    ModelRouter::new().with_circuit_breaker(CircuitBreakerConfig {
+        failure_threshold: 3,
+        failure_window: Duration::from_secs(60),
+        cooldown: COOLDOWN,
+    })
+}
+
+fn fail_times(router: &ModelRouter, model_id: &str, times: usize) {
+    for _ in 0..times {
+        router.admit(model_id).unwrap().record(false);
+    }
+}
+
+#[test]
+fn consecutive_failures_open_the_breaker() {
+    let router = router();
+
+    fail_times(&router, "flaky", 2);
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Closed);
+    fail_times(&router, "flaky", 1);
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Open);
+    assert!(matches!(router.admit("flaky"), Err(RouterError::CircuitOpen(id)) if id == "flaky"));
+    // Other models are unaffected
+    assert!(router.admit("healthy").is_ok());
+}
+
+#[test]
+fn success_resets_the_failure_streak() {
+    let router = router();
+
+    fail_times(&router, "flaky", 2);
+    router.admit("flaky").unwrap().record(true);
+    fail_times(&router, "flaky", 2);
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn successful_probe_closes_the_breaker() {
+    let router = router();
+    fail_times(&router, "flaky", 3);
+    tokio::time::sleep(COOLDOWN).await;
+
+    let probe = router.admit("flaky").unwrap();
+    assert!(probe.is_probe());
+    assert_eq!(router.circuit_state("flaky"), CircuitState::HalfOpen);
+    // Only the probe gets through while half-open
+    assert!(matches!(router.admit("flaky"), Err(RouterError::CircuitOpen(_))));
+
+    probe.record(true);
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Closed);
+    assert!(!router.admit("flaky").unwrap().is_probe());
+}
+
+#[tokio::test]
+async fn failed_probe_reopens_the_breaker() {
+    let router = router();
+    fail_times(&router, "flaky", 3);
+    tokio::time::sleep(COOLDOWN).await;
+
+    router.admit("flaky").unwrap().record(false);
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Open);
+    assert!(matches!(router.admit("flaky"), Err(RouterError::CircuitOpen(_))));
+    // A fresh cooldown starts from the failed probe
+    tokio::time::sleep(COOLDOWN).await;
+    assert!(router.admit("flaky").unwrap().is_probe());
+}
+
+#[tokio::test]
+async fn abandoned_probe_lets_another_through() {
+    let router = router();
+    fail_times(&router, "flaky", 3);
+    tokio::time::sleep(COOLDOWN).await;
+
+    drop(router.admit("flaky").unwrap());
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::HalfOpen);
+    assert!(router.admit("flaky").unwrap().is_probe());
+}
+
+#[tokio::test]
+async fn swapped_model_starts_closed() {
+    let router = router();
+    fail_times(&router, "flaky", 3);
+
+    router.swap_route("flaky", ModelHandle::new(2)).await;
+
+    assert_eq!(router.circuit_state("flaky"), CircuitState::Closed);
+}
+
+const FLAKY_MODEL: &str = "flaky-model";
+
+/// Runtime whose breaker opens after two failures of a model that fails
+/// while `broken` is set.
+async fn runtime_with_flaky_model(broken: &Arc<AtomicBool>) -> Runtime {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        circuit_breaker: CircuitBreakerConfig {
+            failure_threshold: 2,
+            failure_window: Duration::from_secs(60),
+            cooldown: COOLDOWN,
+        },
+        ..Default::default()
+    });
+    let model = MockModel::new(FLAKY_MODEL)
+        .answering("ok", 1)
+        .broken_by(Arc::clone(broken));
+    runtime
+        .inference_engine
+        .register_model(FLAKY_MODEL.into(), ModelHandle::new(1), Arc::new(model))
+        .await;
+    runtime
+}
+
+async fn circuit_state(runtime: &Runtime) -> CircuitState {
+    let request = IpcMessage::ModelStatsRequest {
+        model_id: FLAKY_MODEL.into(),
+    };
+    match send(runtime, request, None).await {
+        IpcMessage::ModelStatsResponse(stats) => stats.circuit_state,
+        other => panic!("Expected ModelStatsResponse, got {:?}", other),
+    }
+}
+
+/// Wait for the audit write of a transition of the flaky model.
+async fn audited_transition(to: &str) {
+    let logger = audit_logger().unwrap();
+    for _ in 0..100 {
+        let events = logger.get_events_by_category(AuditCategory::ModelOperation).await;
+        if events.iter().any(|e| {
+            e.event_type == "circuit_breaker_transition"
+                && e.resource.as_deref() == Some(FLAKY_MODEL)
+                && e.metadata.get("to").map(String::as_str) == Some(to)
+        }) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("no audited transition to {to}");
+}
+
+#[tokio::test]
+async fn failing_model_fast_fails_until_probe_succeeds() {
+    init_audit_logger(AuditConfig { log_to_stdout: false, ..Default::default() });
+    let broken = Arc::new(AtomicBool::new(true));
+    let runtime = runtime_with_flaky_model(&broken).await;
+    let session = handshake(&runtime).await;
+
+    for id in 0..2 {
+        assert_eq!(infer(&runtime, &session, FLAKY_MODEL, id).await.error_code, Some(502));
+    }
+    let rejected = infer(&runtime, &session, FLAKY_MODEL, 2).await;
+    assert_eq!(rejected.error_code, Some(503));
+    assert!(rejected.error.unwrap().contains("Circuit open for model: flaky-model"));
+    assert_eq!(circuit_state(&runtime).await, CircuitState::Open);
+    audited_transition("open").await;
+
+    broken.store(false, Ordering::SeqCst);
+    tokio::time::sleep(COOLDOWN).await;
+    let probe = infer(&runtime, &session, FLAKY_MODEL, 3).await;
+
+    assert!(probe.error.is_none(), "{:?}", probe.error);
+    assert_eq!(circuit_state(&runtime).await, CircuitState::Closed);
+    audited_transition("closed").await;
+}
+
+#[cfg(feature = "gguf")]
+#[tokio::test]
+async fn open_breaker_refuses_streams() {
+    let broken = Arc::new(AtomicBool::new(true));
+    let runtime = runtime_with_flaky_model(&broken).await;
+    let session = handshake(&runtime).await;
+    for id in 0..2 {
+        infer(&runtime, &session, FLAKY_MODEL, id).await;
+    }
+    assert_eq!(circuit_state(&runtime).await, CircuitState::Open);
+
+    let sender = common::RecordingSender::default();
+    runtime
+        .ipc_handler
+        .process_streaming(
+            common::stream_request(FLAKY_MODEL, 2),
+            &session,
+            &sender,
+            tokio_util::sync::CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+
+    let error = sender.final_error().expect("stream should be refused");
+    assert!(error.contains("Circuit open for model: flaky-model"), "got: {}", error);
+}
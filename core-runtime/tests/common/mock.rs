@@ -0,0 +1,130 @@
+//! Configurable mock model for the IPC integration tests.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use gg_core::engine::{
+    FinishReason, GenerationResult, GgufModel, InferenceCapability, InferenceConfig,
+    InferenceError, InferenceInput, InferenceOutput,
+};
+use tokio::sync::Semaphore;
+
+/// Answers every request with `text` (or its prompt when `echo` is set)
+/// after `delay` and once `gate` has a permit, or fails with a model error
+/// while `broken` is set.
+pub struct MockModel {
+    id: String,
+    text: String,
+    tokens: u32,
+    finish_reason: FinishReason,
+    delay: Duration,
+    memory_bytes: usize,
+    broken: Arc<AtomicBool>,
+    gate: Option<Arc<Semaphore>>,
+    echo: bool,
+}
+
+impl MockModel {
+    /// Model `id` answering "done" at once.
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.into(),
+            text: "done".into(),
+            tokens: 1,
+            finish_reason: FinishReason::MaxTokens,
+            delay: Duration::ZERO,
+            memory_bytes: 0,
+            broken: Arc::default(),
+            gate: None,
+            echo: false,
+        }
+    }
+
+    /// Answer with `text`, counted as `tokens` tokens, stopping naturally.
+    pub fn answering(mut self, text: &str, tokens: u32) -> Self {
+        self.text = text.into();
+        self.tokens = tokens;
+        self.finish_reason = FinishReason::Stop;
+        self
+    }
+
+    /// Answer text prompts with the prompt itself, as rendered for it.
+    pub fn echoing_prompt(mut self) -> Self {
+        self.echo = true;
+        self
+    }
+
+    /// Take `delay` over every request.
+    pub fn taking(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Report `bytes` of memory in use.
+    pub fn using_memory(mut self, bytes: usize) -> Self {
+        self.memory_bytes = bytes;
+        self
+    }
+
+    /// Hold every request until `gate` has a permit to hand out.
+    pub fn gated_by(mut self, gate: Arc<Semaphore>) -> Self {
+        self.gate = Some(gate);
+        self
+    }
+
+    /// Fail every request while `broken` is set.
+    pub fn broken_by(mut self, broken: Arc<AtomicBool>) -> Self {
+        self.broken = broken;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl GgufModel for MockModel {
+    fn model_id(&self) -> &str {
+        &self.id
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.memory_bytes
+    }
+
+    async fn infer(
+        &self,
+        input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        if let Some(gate) = &self.gate {
+            let _permit = gate.acquire().await;
+        }
+        if !self.delay.is_zero() {
+            tokio::time::sleep(self.delay).await;
+        }
+        if self.broken.load(Ordering::SeqCst) {
+            return Err(InferenceError::ModelError("corrupt weights".into()));
+        }
+        let text = match input {
+            InferenceInput::Text(prompt) if self.echo => prompt.clone(),
+            _ => self.text.clone(),
+        };
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text,
+            tokens_generated: self.tokens,
+            finish_reason: self.finish_reason.clone(),
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
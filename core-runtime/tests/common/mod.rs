@@ -0,0 +1,211 @@
+//! Fixtures shared by the IPC integration tests: mock and stub models, and
+//! helpers to handshake and send requests through `IpcHandler`.
+
+#![allow(dead_code)]
+
+mod mock;
+mod stubs;
+
+use std::sync::Mutex;
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, InferenceResponse, IpcMessage, RequestId,
+};
+use gg_core::ipc::{
+    HandlerError, HealthCheckType, PingModelRequest, PingModelResponse, SessionToken,
+    StreamSender,
+};
+use gg_core::Runtime;
+use tokio::sync::Notify;
+
+pub use mock::MockModel;
+pub use stubs::{
+    runtime_with_failing_model, EchoModel, FailingModel, HeldModel, InvalidUtf8Model, INVALID_UTF8,
+};
+
+/// Auth token the fixtures handshake with.
+pub const TEST_TOKEN: &str = "test-token";
+
+/// Send `message` on `session` and decode the reply.
+pub async fn send(
+    runtime: &Runtime,
+    message: IpcMessage,
+    session: Option<&SessionToken>,
+) -> IpcMessage {
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&message).unwrap(), session)
+        .await
+        .unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+/// Open a session with `TEST_TOKEN`.
+pub async fn handshake(runtime: &Runtime) -> SessionToken {
+    let handshake = IpcMessage::Handshake {
+        token: TEST_TOKEN.into(),
+        protocol_version: None,
+        compression: None,
+        strict_version: false,
+    };
+    let (_, session) = runtime
+        .ipc_handler
+        .process(&encode_message(&handshake).unwrap(), None)
+        .await
+        .unwrap();
+    session.expect("handshake should open a session")
+}
+
+/// Send `message` over a fresh session opened with `TEST_TOKEN`.
+pub async fn send_authenticated(runtime: &Runtime, message: IpcMessage) -> IpcMessage {
+    send_with_token(runtime, TEST_TOKEN, message).await
+}
+
+/// Send `message` over a fresh session opened with `token`.
+pub async fn send_with_token(runtime: &Runtime, token: &str, message: IpcMessage) -> IpcMessage {
+    let handshake = IpcMessage::Handshake {
+        token: token.into(),
+        protocol_version: None,
+        compression: None,
+        strict_version: false,
+    };
+    let (_, session) = runtime
+        .ipc_handler
+        .process(&encode_message(&handshake).unwrap(), None)
+        .await
+        .expect("Handshake should succeed");
+    send(runtime, message, session.as_ref()).await
+}
+
+/// Inference request for `model_id` with the default parameters.
+pub fn inference_request(model_id: &str, request_id: u64) -> InferenceRequest {
+    InferenceRequest {
+        request_id: RequestId(request_id),
+        model_id: model_id.into(),
+        prompt: "Hello".into(),
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    }
+}
+
+/// Run `request` on `session` and return its response.
+pub async fn infer_request(
+    runtime: &Runtime,
+    session: &SessionToken,
+    request: InferenceRequest,
+) -> InferenceResponse {
+    match send(runtime, IpcMessage::InferenceRequest(request), Some(session)).await {
+        IpcMessage::InferenceResponse(response) => response,
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    }
+}
+
+/// Run a default request for `model_id` on `session`.
+pub async fn infer(
+    runtime: &Runtime,
+    session: &SessionToken,
+    model_id: &str,
+    request_id: u64,
+) -> InferenceResponse {
+    infer_request(runtime, session, inference_request(model_id, request_id)).await
+}
+
+/// Run one request for `model_id` over a fresh authenticated session.
+pub async fn infer_once(runtime: &Runtime, model_id: &str) -> InferenceResponse {
+    infer_with_params(runtime, model_id, InferenceParams::default()).await
+}
+
+/// Like `infer_once`, with explicit request parameters.
+pub async fn infer_with_params(
+    runtime: &Runtime,
+    model_id: &str,
+    parameters: InferenceParams,
+) -> InferenceResponse {
+    let request = InferenceRequest {
+        parameters,
+        ..inference_request(model_id, 1)
+    };
+    send_inference(runtime, request).await
+}
+
+/// Send `request` over a fresh authenticated session.
+pub async fn send_inference(runtime: &Runtime, request: InferenceRequest) -> InferenceResponse {
+    let session = handshake(runtime).await;
+    infer_request(runtime, &session, request).await
+}
+
+/// Client, model and infrastructure error counts, in that order.
+pub fn error_counters(runtime: &Runtime) -> [u64; 3] {
+    let counters = runtime.metrics_store.snapshot().counters;
+    ["core_errors_client_total", "core_errors_model_total", "core_errors_infra_total"]
+        .map(|name| counters.get(name).copied().unwrap_or(0))
+}
+
+/// Ping `model_id` without a session.
+pub async fn ping(runtime: &Runtime, model_id: &str) -> PingModelResponse {
+    let request = IpcMessage::PingModelRequest(PingModelRequest {
+        model_id: model_id.to_string(),
+    });
+    match send(runtime, request, None).await {
+        IpcMessage::PingModelResponse(response) => response,
+        other => panic!("Expected PingModelResponse, got {:?}", other),
+    }
+}
+
+/// Whether the readiness probe reports the runtime ready.
+pub async fn is_ready(runtime: &Runtime) -> bool {
+    let request = IpcMessage::HealthCheck {
+        check_type: HealthCheckType::Readiness,
+    };
+    match send(runtime, request, None).await {
+        IpcMessage::HealthResponse(response) => response.ok,
+        other => panic!("Expected HealthResponse, got {:?}", other),
+    }
+}
+
+/// Streaming request for `model_id` with the default parameters.
+pub fn stream_request(model_id: &str, request_id: u64) -> InferenceRequest {
+    InferenceRequest {
+        parameters: InferenceParams {
+            stream: true,
+            ..Default::default()
+        },
+        ..inference_request(model_id, request_id)
+    }
+}
+
+/// Stream sender that records every frame.
+#[derive(Default)]
+pub struct RecordingSender(pub Mutex<Vec<IpcMessage>>);
+
+#[async_trait::async_trait]
+impl StreamSender for RecordingSender {
+    async fn send(&self, message: IpcMessage) -> Result<(), HandlerError> {
+        self.0.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+impl RecordingSender {
+    /// Error carried by the final frame, which must have been sent.
+    pub fn final_error(&self) -> Option<String> {
+        match self.0.lock().unwrap().last() {
+            Some(IpcMessage::StreamChunk(chunk)) if chunk.is_final => chunk.error.clone(),
+            other => panic!("Expected a final StreamChunk, got {:?}", other),
+        }
+    }
+}
+
+/// Stream sender that holds each frame until released.
+pub struct GatedSender(pub Notify);
+
+#[async_trait::async_trait]
+impl StreamSender for GatedSender {
+    async fn send(&self, _message: IpcMessage) -> Result<(), HandlerError> {
+        self.0.notified().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Single-purpose model stubs shared by several integration tests.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use gg_core::engine::{
+    FinishReason, GenerationResult, GgufModel, InferenceCapability, InferenceConfig,
+    InferenceError, InferenceInput, InferenceOutput,
+};
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+use tokio::sync::Semaphore;
+
+use super::TEST_TOKEN;
+
+/// Fails every inference with a fixed error.
+pub struct FailingModel(pub fn() -> InferenceError);
+
+#[async_trait::async_trait]
+impl GgufModel for FailingModel {
+    fn model_id(&self) -> &str {
+        "failing-model"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        Err((self.0)())
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Runtime serving `FailingModel` as "failing-model".
+pub async fn runtime_with_failing_model(error: fn() -> InferenceError) -> Runtime {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "failing-model".into(),
+            ModelHandle::new(1),
+            Arc::new(FailingModel(error)),
+        )
+        .await;
+    runtime
+}
+
+/// Answers every inference with a single token; asserts it was asked for one.
+pub struct EchoModel;
+
+#[async_trait::async_trait]
+impl GgufModel for EchoModel {
+    fn model_id(&self) -> &str {
+        "echo-model"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        assert_eq!(config.max_tokens, Some(1));
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text: "a".into(),
+            tokens_generated: 1,
+            finish_reason: FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Counts runs and holds each one until the test hands out a permit.
+pub struct HeldModel {
+    pub runs: AtomicUsize,
+    pub permits: Semaphore,
+}
+
+#[async_trait::async_trait]
+impl GgufModel for HeldModel {
+    fn model_id(&self) -> &str {
+        "held-model"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        self.permits.acquire().await.unwrap().forget();
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text: "shared answer".into(),
+            tokens_generated: 2,
+            finish_reason: FinishReason::Stop,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Output of `InvalidUtf8Model`: a split multi-byte sequence.
+pub const INVALID_UTF8: &[u8] = &[0x66, 0xff, 0xfe, 0x6f];
+
+/// Emits output that is not valid UTF-8, counted as two tokens.
+pub struct InvalidUtf8Model;
+
+#[async_trait::async_trait]
+impl GgufModel for InvalidUtf8Model {
+    fn model_id(&self) -> &str {
+        "invalid-utf8-model"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        Ok(InferenceOutput::Generation(GenerationResult::from_bytes(
+            INVALID_UTF8.to_vec(),
+            2,
+            FinishReason::Stop,
+        )))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,109 @@
+//! `ReloadConfig` applies reloadable settings from the config source to a
+//! running runtime, for admin sessions only.
+
+mod common;
+
+use common::{infer_once, send_authenticated, send_with_token};
+use gg_core::ipc::protocol::{encode_message, IpcMessage};
+use gg_core::memory::ResourceLimitsConfig;
+use gg_core::scheduler::RequestQueueConfig;
+
+#[tokio::test]
+async fn reload_config_raises_queue_depth_without_restart() {
+    let config = gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        admin_token: Some("admin-token".into()),
+        request_queue: RequestQueueConfig { max_pending: 0, ..Default::default() },
+        allow_config_reload: true,
+        ..Default::default()
+    };
+    let reloaded = gg_core::RuntimeConfig {
+        request_queue: RequestQueueConfig { max_pending: 4, ..Default::default() },
+        base_path: "/srv/elsewhere".into(),
+        ..config.clone()
+    };
+    let runtime = gg_core::Runtime::new(config)
+        .with_config_source(std::sync::Arc::new(move || reloaded.clone()));
+
+    // Full queue: rejected before reaching the model
+    assert_eq!(infer_once(&runtime, "unloaded-model").await.error_code, Some(503));
+
+    // Ordinary sessions cannot reload
+    match send_authenticated(&runtime, IpcMessage::ReloadConfig).await {
+        IpcMessage::Error { code, .. } => assert_eq!(code, 403),
+        other => panic!("Expected Error, got {:?}", other),
+    }
+    assert_eq!(runtime.request_queue.max_pending(), 0);
+
+    match send_with_token(&runtime, "admin-token", IpcMessage::ReloadConfig).await {
+        IpcMessage::ConfigReloadResponse(response) => {
+            assert_eq!(response.applied, ["request_queue.max_pending"]);
+            assert_eq!(response.restart_required, ["base_path"]);
+        }
+        other => panic!("Expected ConfigReloadResponse, got {:?}", other),
+    }
+    assert_eq!(runtime.request_queue.max_pending(), 4);
+
+    // Now admitted; fails only because the model is not loaded
+    let response = infer_once(&runtime, "unloaded-model").await;
+    assert_eq!(response.error_code, Some(400));
+    assert!(response.error.unwrap().contains("unloaded-model"));
+
+    // Unauthenticated callers cannot reload
+    assert!(matches!(
+        runtime
+            .ipc_handler
+            .process(&encode_message(&IpcMessage::ReloadConfig).unwrap(), None)
+            .await,
+        Err(gg_core::ipc::HandlerError::NotAuthenticated)
+    ));
+}
+
+#[tokio::test]
+async fn reload_config_applies_resource_limits() {
+    let config = gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        admin_token: Some("admin-token".into()),
+        allow_config_reload: true,
+        resource_limits: ResourceLimitsConfig {
+            max_memory_per_call: 4,
+            ..ResourceLimitsConfig::unlimited()
+        },
+        ..Default::default()
+    };
+    let reloaded = gg_core::RuntimeConfig {
+        resource_limits: ResourceLimitsConfig::unlimited(),
+        ..config.clone()
+    };
+    let runtime = gg_core::Runtime::new(config)
+        .with_config_source(std::sync::Arc::new(move || reloaded.clone()));
+
+    // The 5-byte prompt is over the per-call limit
+    let response = infer_once(&runtime, "unloaded-model").await;
+    assert_eq!(response.error_code, Some(503));
+
+    match send_with_token(&runtime, "admin-token", IpcMessage::ReloadConfig).await {
+        IpcMessage::ConfigReloadResponse(response) => {
+            assert_eq!(response.applied, ["resource_limits"]);
+        }
+        other => panic!("Expected ConfigReloadResponse, got {:?}", other),
+    }
+
+    let response = infer_once(&runtime, "unloaded-model").await;
+    assert_eq!(response.error_code, Some(400));
+}
+
+#[tokio::test]
+async fn reload_config_refused_unless_enabled() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        admin_token: Some("admin-token".into()),
+        ..Default::default()
+    })
+    .with_config_source(std::sync::Arc::new(gg_core::RuntimeConfig::default));
+
+    match send_with_token(&runtime, "admin-token", IpcMessage::ReloadConfig).await {
+        IpcMessage::Error { code, .. } => assert_eq!(code, 403),
+        other => panic!("Expected Error, got {:?}", other),
+    }
+}
@@ -7,7 +7,7 @@ use gg_core::ipc::{ConnectionConfig, ConnectionPool, SessionAuth};
 
 #[test]
 fn test_acquire_within_limit() {
-    let config = ConnectionConfig { max_connections: 2 };
+    let config = ConnectionConfig { max_connections: 2, ..Default::default() };
     let pool = ConnectionPool::new(config);
 
     let guard1 = pool.try_acquire();
@@ -21,7 +21,7 @@ fn test_acquire_within_limit() {
 
 #[test]
 fn test_acquire_at_limit() {
-    let config = ConnectionConfig { max_connections: 1 };
+    let config = ConnectionConfig { max_connections: 1, ..Default::default() };
     let pool = ConnectionPool::new(config);
 
     let _guard = pool.try_acquire();
@@ -35,7 +35,7 @@ fn test_acquire_at_limit() {
 
 #[test]
 fn test_guard_releases_on_drop() {
-    let config = ConnectionConfig { max_connections: 1 };
+    let config = ConnectionConfig { max_connections: 1, ..Default::default() };
     let pool = ConnectionPool::new(config);
 
     {
@@ -55,7 +55,7 @@ fn test_guard_releases_on_drop() {
 fn test_concurrent_acquire() {
     use std::thread;
 
-    let config = ConnectionConfig { max_connections: 100 };
+    let config = ConnectionConfig { max_connections: 100, ..Default::default() };
     let pool = Arc::new(ConnectionPool::new(config));
 
     let handles: Vec<_> = (0..10)
@@ -102,4 +102,23 @@ async fn test_session_connection_tracking() {
 fn test_connection_config_defaults() {
     let config = ConnectionConfig::default();
     assert_eq!(config.max_connections, 64);
+    assert_eq!(config.idle_timeout, None);
+}
+
+#[test]
+fn test_guard_tracks_last_activity() {
+    let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
+        idle_timeout: Some(Duration::from_secs(5)),
+        ..Default::default()
+    }));
+    let guard = pool.try_acquire_owned().unwrap();
+    assert_eq!(guard.idle_timeout(), Some(Duration::from_secs(5)));
+
+    let opened = guard.last_activity();
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(guard.idle_for() >= Duration::from_millis(20));
+
+    guard.touch();
+    assert!(guard.last_activity() > opened);
+    assert!(guard.idle_for() < Duration::from_millis(20));
 }
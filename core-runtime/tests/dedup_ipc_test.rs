@@ -0,0 +1,73 @@
+//! Identical deterministic requests share one model run and its result.
+
+mod common;
+
+use common::{send_inference, HeldModel};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{InferenceRequest, RequestId};
+
+#[tokio::test]
+async fn identical_concurrent_requests_share_one_model_run() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let model = std::sync::Arc::new(HeldModel {
+        runs: Default::default(),
+        permits: tokio::sync::Semaphore::new(0),
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "held-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            model.clone(),
+        )
+        .await;
+    let runs = || model.runs.load(std::sync::atomic::Ordering::SeqCst);
+    let request = |id, parameters| InferenceRequest {
+        request_id: RequestId(id),
+        model_id: "held-model".into(),
+        prompt: "Summarize the report".into(),
+        parameters,
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+    let greedy = InferenceParams {
+        temperature: 0.0,
+        ..Default::default()
+    };
+
+    let release = async {
+        while runs() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        // Let the second request reach the running one before releasing it
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        model.permits.add_permits(1);
+    };
+    let (first, second, ()) = tokio::join!(
+        send_inference(&runtime, request(1, greedy.clone())),
+        send_inference(&runtime, request(2, greedy.clone())),
+        release,
+    );
+
+    assert_eq!(runs(), 1);
+    for (response, id) in [(&first, 1), (&second, 2)] {
+        assert!(response.error.is_none(), "{:?}", response.error);
+        assert_eq!(response.request_id, RequestId(id));
+        assert_eq!(response.output, "shared answer");
+    }
+
+    // A finished result is replayed within the TTL
+    let replayed = send_inference(&runtime, request(3, greedy.clone())).await;
+    assert_eq!(replayed.output, "shared answer");
+    assert_eq!(runs(), 1);
+
+    // A different seed, or opting out, runs the model again
+    model.permits.add_permits(2);
+    send_inference(&runtime, request(4, InferenceParams { seed: Some(7), ..greedy.clone() })).await;
+    send_inference(&runtime, request(5, InferenceParams { no_cache: true, ..greedy })).await;
+    assert_eq!(runs(), 3);
+}
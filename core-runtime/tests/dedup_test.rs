@@ -2,8 +2,8 @@
 
 use std::time::Duration;
 
-use gg_core::engine::InferenceParams;
-use gg_core::scheduler::{OutputCache, OutputCacheConfig};
+use gg_core::engine::{InferenceParams, OutputEncoding, SamplerKind};
+use gg_core::scheduler::{DedupLookup, OutputCache, OutputCacheConfig, RequestDedup};
 
 #[test]
 fn test_cache_key_deterministic() {
@@ -153,3 +153,112 @@ fn test_cache_empty() {
     assert!(cache.is_empty());
     assert_eq!(cache.len(), 0);
 }
+
+#[test]
+fn test_request_key_includes_model_and_output_params() {
+    let params = InferenceParams::default();
+    let key = OutputCache::request_key("chat", "hello", &params);
+
+    assert_eq!(key, OutputCache::request_key("chat", "hello", &params));
+    assert_ne!(key, OutputCache::request_key("chat-v2", "hello", &params));
+    assert_ne!(key, OutputCache::request_key("chat", "hello!", &params));
+    let seeded = InferenceParams { seed: Some(1), ..Default::default() };
+    assert_ne!(key, OutputCache::request_key("chat", "hello", &seeded));
+    let warmer = InferenceParams { temperature: 0.9, ..Default::default() };
+    assert_ne!(key, OutputCache::request_key("chat", "hello", &warmer));
+    // Only output-changing params count
+    let streamed = InferenceParams { stream: true, no_cache: true, ..Default::default() };
+    assert_eq!(key, OutputCache::request_key("chat", "hello", &streamed));
+}
+
+#[test]
+fn test_request_key_tags_optional_and_enum_params() {
+    let key = |params: InferenceParams| OutputCache::request_key("chat", "hello", &params);
+    let base = key(InferenceParams::default());
+
+    let bytes = InferenceParams { output_encoding: OutputEncoding::Bytes, ..Default::default() };
+    assert_ne!(base, key(bytes));
+    let mirostat = SamplerKind::Mirostat { tau: 5.0, eta: 0.1 };
+    assert_ne!(base, key(InferenceParams { sampler: mirostat, ..Default::default() }));
+    // A present zero is not the same as an absent value
+    assert_ne!(base, key(InferenceParams { seed: Some(0), ..Default::default() }));
+    assert_ne!(
+        key(InferenceParams { seed: Some(0), ..Default::default() }),
+        key(InferenceParams { timeout_ms: Some(0), ..Default::default() })
+    );
+}
+
+#[test]
+fn test_unseeded_mirostat_is_not_reproducible() {
+    let mirostat = SamplerKind::Mirostat { tau: 5.0, eta: 0.1 };
+    let greedy = InferenceParams { temperature: 0.0, ..Default::default() };
+    assert!(greedy.is_reproducible());
+    assert!(!InferenceParams { sampler: mirostat, ..greedy.clone() }.is_reproducible());
+    let seeded = InferenceParams { sampler: mirostat, seed: Some(7), ..greedy.clone() };
+    assert!(seeded.is_reproducible());
+    assert!(!InferenceParams { no_cache: true, ..seeded }.is_reproducible());
+}
+
+fn dedup(max_entries: usize) -> RequestDedup<String> {
+    RequestDedup::new(OutputCacheConfig {
+        ttl: Duration::from_secs(60),
+        max_entries,
+    })
+}
+
+#[tokio::test]
+async fn test_request_dedup_waiters_share_leader_result() {
+    let dedup = dedup(10);
+    let key = [1; 32];
+    let Some(DedupLookup::Lead(leader)) = dedup.lookup(key) else {
+        panic!("first caller should lead");
+    };
+    let Some(DedupLookup::Wait(waiter)) = dedup.lookup(key) else {
+        panic!("second caller should wait");
+    };
+    assert_eq!(dedup.in_flight(), 1);
+
+    let waiting = tokio::spawn(waiter.result());
+    leader.finish("done".to_string(), true);
+
+    assert_eq!(waiting.await.unwrap().as_deref(), Some("done"));
+    assert_eq!(dedup.in_flight(), 0);
+    assert!(matches!(dedup.lookup(key), Some(DedupLookup::Cached(v)) if v == "done"));
+}
+
+#[tokio::test]
+async fn test_request_dedup_uncacheable_result_not_replayed() {
+    let dedup = dedup(10);
+    let Some(DedupLookup::Lead(leader)) = dedup.lookup([2; 32]) else {
+        panic!("first caller should lead");
+    };
+    leader.finish("failed".to_string(), false);
+
+    assert!(matches!(dedup.lookup([2; 32]), Some(DedupLookup::Lead(_))));
+}
+
+#[tokio::test]
+async fn test_request_dedup_dropped_leader_releases_waiters() {
+    let dedup = dedup(10);
+    let leader = dedup.lookup([3; 32]);
+    let Some(DedupLookup::Wait(waiter)) = dedup.lookup([3; 32]) else {
+        panic!("second caller should wait");
+    };
+    drop(leader);
+
+    assert_eq!(waiter.result().await, None);
+    assert!(matches!(dedup.lookup([3; 32]), Some(DedupLookup::Lead(_))));
+}
+
+#[test]
+fn test_request_dedup_disabled_and_cleared() {
+    assert!(dedup(0).lookup([4; 32]).is_none());
+
+    let dedup = dedup(10);
+    let Some(DedupLookup::Lead(leader)) = dedup.lookup([4; 32]) else {
+        panic!("first caller should lead");
+    };
+    leader.finish("old model".to_string(), true);
+    dedup.clear();
+    assert!(matches!(dedup.lookup([4; 32]), Some(DedupLookup::Lead(_))));
+}
@@ -0,0 +1,42 @@
+//! Requests with an empty model ID are served by `RuntimeConfig::default_model`.
+
+mod common;
+
+use common::{error_counters, infer_once, runtime_with_failing_model, InvalidUtf8Model};
+
+#[tokio::test]
+async fn empty_model_id_resolves_to_default_model() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        default_model: Some("invalid-utf8-model".into()),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "invalid-utf8-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(InvalidUtf8Model),
+        )
+        .await;
+
+    let response = infer_once(&runtime, "").await;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.tokens_generated, 2);
+}
+
+#[tokio::test]
+async fn empty_model_id_without_default_fails_cleanly() {
+    let runtime = runtime_with_failing_model(|| {
+        gg_core::engine::InferenceError::ModelError("unreachable".into())
+    })
+    .await;
+
+    let response = infer_once(&runtime, "").await;
+
+    let error = response.error.expect("request must fail");
+    assert!(error.contains("no default model configured"), "{}", error);
+    assert_eq!(response.error_code, Some(400));
+    assert_eq!(error_counters(&runtime), [1, 0, 0]);
+}
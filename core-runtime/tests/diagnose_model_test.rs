@@ -0,0 +1,51 @@
+//! `DiagnoseModelRequest` reports why a loaded model cannot serve.
+
+mod common;
+
+use common::{handshake, EchoModel};
+use gg_core::ipc::protocol::{decode_message, encode_message, IpcMessage};
+
+#[tokio::test]
+async fn diagnose_model_reports_missing_tokenizer() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "echo-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(EchoModel),
+        )
+        .await;
+
+    let request = encode_message(&IpcMessage::DiagnoseModelRequest(
+        gg_core::ipc::DiagnoseModelRequest {
+            model_id: "echo-model".to_string(),
+        },
+    ))
+    .unwrap();
+    // Diagnosing steps the model, so it needs a session
+    assert!(runtime.ipc_handler.process(&request, None).await.is_err());
+
+    let session = handshake(&runtime).await;
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&request, Some(&session))
+        .await
+        .unwrap();
+    let report = match decode_message(&bytes).unwrap() {
+        IpcMessage::DiagnoseModelResponse(report) => report,
+        other => panic!("Expected DiagnoseModelResponse, got {:?}", other),
+    };
+    assert!(runtime.request_queue.is_empty().await);
+
+    assert!(!report.is_servable());
+    let tokenizer = report.check("tokenizer").unwrap();
+    assert_eq!(tokenizer.status, gg_core::engine::CheckStatus::Fail);
+    assert!(tokenizer.detail.contains("no tokenizer"));
+    assert!(tokenizer.remediation.is_some());
+    let forward = report.check("forward_step").unwrap();
+    assert_eq!(forward.status, gg_core::engine::CheckStatus::Pass);
+}
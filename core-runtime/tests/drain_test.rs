@@ -0,0 +1,95 @@
+//! Draining a model over IPC before unload.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{handshake, infer, send, MockModel, TEST_TOKEN};
+use gg_core::ipc::protocol::{encode_message, IpcMessage};
+use gg_core::ipc::{DrainResponse, SessionToken};
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+
+const MODEL: &str = "slow-model";
+const INFERENCE_TIME: Duration = Duration::from_millis(300);
+
+async fn runtime_with_slow_model() -> Arc<Runtime> {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        ..Default::default()
+    });
+    let model = MockModel::new(MODEL).taking(INFERENCE_TIME);
+    runtime
+        .inference_engine
+        .register_model(MODEL.into(), ModelHandle::new(1), Arc::new(model))
+        .await;
+    Arc::new(runtime)
+}
+
+async fn drain(runtime: &Runtime, session: &SessionToken, timeout_ms: u64) -> DrainResponse {
+    let message = IpcMessage::DrainModel {
+        model_id: MODEL.into(),
+        timeout_ms,
+    };
+    match send(runtime, message, Some(session)).await {
+        IpcMessage::DrainResponse(response) => response,
+        other => panic!("Expected DrainResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn drain_shorter_than_in_flight_request_reports_not_drained() {
+    let runtime = runtime_with_slow_model().await;
+    let session = handshake(&runtime).await;
+
+    let in_flight = tokio::spawn({
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        async move { infer(&runtime, &session, MODEL, 1).await }
+    });
+    while runtime.ipc_handler.active_requests().is_empty() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let response = drain(&runtime, &session, 20).await;
+    assert_eq!(response.model_id, MODEL);
+    assert!(!response.drained);
+    assert_eq!(response.in_flight_remaining, 1);
+
+    // New requests are refused while the model drains
+    let refused = infer(&runtime, &session, MODEL, 2).await;
+    let error = refused.error.expect("draining model must refuse requests");
+    assert!(error.contains("draining"), "{}", error);
+
+    // The in-flight request still completes, after which the drain succeeds
+    let finished = in_flight.await.unwrap();
+    assert!(finished.error.is_none(), "{:?}", finished.error);
+    let response = drain(&runtime, &session, 1000).await;
+    assert!(response.drained);
+    assert_eq!(response.in_flight_remaining, 0);
+}
+
+#[tokio::test]
+async fn drain_requires_auth_and_known_model() {
+    let runtime = runtime_with_slow_model().await;
+    let message = IpcMessage::DrainModel {
+        model_id: MODEL.into(),
+        timeout_ms: 10,
+    };
+    let result = runtime
+        .ipc_handler
+        .process(&encode_message(&message).unwrap(), None)
+        .await;
+    assert!(result.is_err());
+
+    let session = handshake(&runtime).await;
+    let unknown = IpcMessage::DrainModel {
+        model_id: "missing".into(),
+        timeout_ms: 10,
+    };
+    assert!(matches!(
+        send(&runtime, unknown, Some(&session)).await,
+        IpcMessage::Error { code: 404, .. }
+    ));
+}
@@ -0,0 +1,150 @@
+//! Embeddings for repeated inputs are served from the engine's cache,
+//! unless caching is disabled for the runtime or the request.
+
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{decode_message, encode_message, IpcMessage};
+
+/// Embeds text as its byte values, counting how often it actually runs.
+struct CountingEmbedder(std::sync::atomic::AtomicUsize);
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for CountingEmbedder {
+    fn model_id(&self) -> &str {
+        "counting-embedder"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[gg_core::engine::InferenceCapability::Embedding]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        input: &gg_core::engine::InferenceInput,
+        _config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let gg_core::engine::InferenceInput::Text(text) = input else {
+            unreachable!("embed sends single texts");
+        };
+        let vector: Vec<f32> = text.bytes().map(f32::from).collect();
+        Ok(gg_core::engine::InferenceOutput::Embedding(gg_core::engine::EmbeddingResult {
+            dimensions: vector.len(),
+            vector,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn duplicate_embedding_served_from_cache() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    let embedder = std::sync::Arc::new(CountingEmbedder(Default::default()));
+    runtime
+        .inference_engine
+        .register_model(
+            "counting-embedder".into(),
+            gg_core::models::ModelHandle::new(1),
+            embedder.clone(),
+        )
+        .await;
+
+    let engine = &runtime.inference_engine;
+    let first = engine.embed("counting-embedder", "boilerplate").await.unwrap();
+    let second = engine.embed("counting-embedder", "boilerplate").await.unwrap();
+
+    assert_eq!(embedder.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+    assert_eq!(first.vector, second.vector);
+    let stats = engine.embedding_cache().stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.hit_rate(), 0.5);
+
+    let metrics = match runtime
+        .ipc_handler
+        .process(&encode_message(&IpcMessage::MetricsRequest).unwrap(), None)
+        .await
+        .map(|(bytes, _)| decode_message(&bytes).unwrap())
+        .unwrap()
+    {
+        IpcMessage::MetricsResponse(snapshot) => snapshot,
+        other => panic!("Expected MetricsResponse, got {:?}", other),
+    };
+    assert_eq!(metrics.counters["core_embedding_cache_hits_total"], 1);
+    assert_eq!(metrics.gauges["core_embedding_cache_hit_rate"], 0.5);
+
+    // Unregistering the model drops its cached vectors
+    engine.unregister_model("counting-embedder").await;
+    assert_eq!(engine.embedding_cache().stats().entries, 0);
+}
+
+#[tokio::test]
+async fn disabled_caches_run_model_for_every_request() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        disable_caches: true,
+        ..Default::default()
+    });
+    let embedder = std::sync::Arc::new(CountingEmbedder(Default::default()));
+    runtime
+        .inference_engine
+        .register_model(
+            "counting-embedder".into(),
+            gg_core::models::ModelHandle::new(1),
+            embedder.clone(),
+        )
+        .await;
+    let engine = &runtime.inference_engine;
+    let runs = || embedder.0.load(std::sync::atomic::Ordering::SeqCst);
+
+    engine.embed("counting-embedder", "eval item").await.unwrap();
+    engine.embed("counting-embedder", "eval item").await.unwrap();
+    assert_eq!(runs(), 2);
+    let stats = engine.embedding_cache().stats();
+    assert_eq!((stats.hits, stats.entries), (0, 0));
+    let mut prompts = runtime.config.prompt_cache(16);
+    prompts.insert(&[1, 2, 3], vec![0; 8], 3);
+    assert!(prompts.is_empty());
+
+    // With caches on, a per-request no_cache still bypasses them
+    let cached = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    cached
+        .inference_engine
+        .register_model(
+            "counting-embedder".into(),
+            gg_core::models::ModelHandle::new(1),
+            embedder.clone(),
+        )
+        .await;
+    let cached_engine = &cached.inference_engine;
+    cached_engine.embed("counting-embedder", "eval item").await.unwrap();
+    assert_eq!(runs(), 3);
+    let no_cache = InferenceParams {
+        no_cache: true,
+        logit_bias: Default::default(),
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        cached_engine
+            .embed_with_params("counting-embedder", "eval item", &no_cache)
+            .await
+            .unwrap();
+    }
+    assert_eq!(runs(), 5);
+    assert_eq!(cached_engine.embedding_cache().stats().hits, 0);
+
+    // Without the override the vector stored by the first request is reused
+    cached_engine.embed("counting-embedder", "eval item").await.unwrap();
+    assert_eq!(runs(), 5);
+    assert_eq!(cached_engine.embedding_cache().stats().hits, 1);
+}
@@ -0,0 +1,49 @@
+//! Client, model and infrastructure errors are counted and coded apart.
+
+mod common;
+
+use common::{error_counters, infer_once, runtime_with_failing_model};
+use gg_core::scheduler::RequestQueueConfig;
+
+#[tokio::test]
+async fn client_error_counted_and_coded() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let response = infer_once(&runtime, "unloaded-model").await;
+
+    assert_eq!(response.error_code, Some(400));
+    assert_eq!(error_counters(&runtime), [1, 0, 0]);
+}
+
+#[tokio::test]
+async fn model_error_counted_and_coded() {
+    let runtime = runtime_with_failing_model(|| {
+        gg_core::engine::InferenceError::ModelError("decode failed".into())
+    })
+    .await;
+    let response = infer_once(&runtime, "failing-model").await;
+
+    assert_eq!(response.error_code, Some(502));
+    assert_eq!(error_counters(&runtime), [0, 1, 0]);
+}
+
+#[tokio::test]
+async fn infra_errors_counted_and_coded() {
+    let runtime =
+        runtime_with_failing_model(|| gg_core::engine::InferenceError::Timeout(30_000)).await;
+    let response = infer_once(&runtime, "failing-model").await;
+    assert_eq!(response.error_code, Some(503));
+    assert_eq!(error_counters(&runtime), [0, 0, 1]);
+
+    // Queue rejection is infrastructure, not a bad request
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        request_queue: RequestQueueConfig { max_pending: 0, ..Default::default() },
+        ..Default::default()
+    });
+    let response = infer_once(&runtime, "unloaded-model").await;
+    assert_eq!(response.error_code, Some(503));
+    assert_eq!(error_counters(&runtime), [0, 0, 1]);
+}
@@ -0,0 +1,136 @@
+//! A/B experiments set over IPC route requests between a control and a
+//! variant model and report per-arm counts.
+
+mod common;
+
+use common::{handshake, send_authenticated, send_inference, RecordingSender};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{InferenceRequest, IpcMessage, RequestId};
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn experiment_routes_control_requests_and_reports_per_arm_counts() {
+    use common::infer_once;
+    use gg_core::ab_testing::{AbExperiment, ExperimentResults};
+    use gg_core::ipc::protocol::{decode_message, encode_message};
+
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let handler = &runtime.ipc_handler;
+    let session = handshake(&runtime).await;
+    let send = |message: IpcMessage| {
+        let session = session.clone();
+        async move {
+            let (bytes, _) = handler
+                .process(&encode_message(&message).unwrap(), Some(&session))
+                .await
+                .unwrap();
+            decode_message(&bytes).unwrap()
+        }
+    };
+    let results = |message: IpcMessage| -> Option<ExperimentResults> {
+        match message {
+            IpcMessage::ExperimentResults { results } => results,
+            other => panic!("Expected ExperimentResults, got {:?}", other),
+        }
+    };
+
+    let experiment = AbExperiment {
+        control_model: "chat".into(),
+        variant_model: "chat-v2".into(),
+        variant_percent: 100,
+    };
+    let started = results(send(IpcMessage::SetExperiment { experiment: Some(experiment) }).await);
+    assert!(started.unwrap().arms.iter().all(|arm| arm.stats.requests == 0));
+
+    // Neither model is loaded, so the error names the model that served it
+    let routed = infer_once(&runtime, "chat").await;
+    assert!(routed.error.unwrap().contains("chat-v2"));
+    let untouched = infer_once(&runtime, "embed").await;
+    assert!(!untouched.error.unwrap().contains("chat-v2"));
+
+    let report = results(send(IpcMessage::GetExperimentResults).await).unwrap();
+    let counts: Vec<_> = report
+        .arms
+        .iter()
+        .map(|arm| (arm.model_id.as_str(), arm.stats.requests, arm.stats.failures))
+        .collect();
+    assert_eq!(counts, [("chat", 0, 0), ("chat-v2", 1, 1)]);
+
+    // Stopping the experiment serves the control model again
+    assert!(results(send(IpcMessage::SetExperiment { experiment: None }).await).is_none());
+    assert!(results(send(IpcMessage::GetExperimentResults).await).is_none());
+    assert!(!infer_once(&runtime, "chat").await.error.unwrap().contains("chat-v2"));
+
+    let invalid = AbExperiment {
+        control_model: "chat".into(),
+        variant_model: "chat-v2".into(),
+        variant_percent: 120,
+    };
+    match send(IpcMessage::SetExperiment { experiment: Some(invalid) }).await {
+        IpcMessage::Error { code, .. } => assert_eq!(code, 400),
+        other => panic!("Expected Error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn experiment_keeps_client_on_one_arm_across_sessions_and_streams() {
+    use gg_core::ab_testing::AbExperiment;
+
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let experiment = AbExperiment {
+        control_model: "chat".into(),
+        variant_model: "chat-v2".into(),
+        variant_percent: 50,
+    };
+    let experiment = Some(experiment);
+    send_authenticated(&runtime, IpcMessage::SetExperiment { experiment }).await;
+    let request = |client: &str| InferenceRequest {
+        request_id: RequestId(1),
+        model_id: "chat".into(),
+        prompt: "Hello".into(),
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: Some(client.into()),
+    };
+    let variant_requests = || async {
+        match send_authenticated(&runtime, IpcMessage::GetExperimentResults).await {
+            IpcMessage::ExperimentResults { results } => {
+                let arms = results.expect("experiment running").arms;
+                let variant = arms.iter().find(|arm| arm.model_id == "chat-v2");
+                variant.map_or(0, |arm| arm.stats.requests)
+            }
+            other => panic!("Expected ExperimentResults, got {:?}", other),
+        }
+    };
+
+    let mut arms = std::collections::HashSet::new();
+    for n in 0..16 {
+        let client = format!("client-{n}");
+        // Each request opens a new session; the model it names is the arm's
+        let variant = |response: gg_core::ipc::InferenceResponse| {
+            response.error.unwrap().contains("chat-v2")
+        };
+        let first = variant(send_inference(&runtime, request(&client)).await);
+        assert_eq!(variant(send_inference(&runtime, request(&client)).await), first);
+
+        let before = variant_requests().await;
+        let session = handshake(&runtime).await;
+        let sender = RecordingSender::default();
+        let cancel = tokio_util::sync::CancellationToken::new();
+        runtime
+            .ipc_handler
+            .process_streaming(request(&client), &session, &sender, cancel)
+            .await
+            .unwrap();
+        assert_eq!(variant_requests().await > before, first, "stream from {client}");
+        arms.insert(first);
+    }
+    assert_eq!(arms.len(), 2);
+}
@@ -6,14 +6,15 @@
 
 #![cfg(feature = "ffi")]
 
-use std::ffi::{c_char, CStr, CString};
+use std::ffi::{c_char, c_void, CStr, CString};
 use std::ptr;
 
 use gg_core::ffi::{
-    core_clear_last_error, core_config_default, core_get_last_error,
-    core_runtime_create, core_runtime_destroy,
-    CoreConfig, CoreErrorCode, CoreHealthReport, CoreHealthState,
-    CoreInferenceParams, CoreInferenceResult, CoreModelMetadata,
+    core_authenticate, core_build_info, core_clear_last_error, core_config_default,
+    core_get_last_error, core_infer, core_infer_stream, core_runtime_create, core_runtime_destroy,
+    core_session_release,
+    CoreBuildInfo, CoreConfig, CoreErrorCode, CoreHealthReport, CoreHealthState,
+    CoreInferenceParams, CoreInferenceResult, CoreModelMetadata, CORE_FEATURE_FFI,
 };
 
 // ============================================================================
@@ -38,9 +39,23 @@ fn test_error_codes_have_correct_values() {
     assert_eq!(CoreErrorCode::ShuttingDown as i32, -13);
     assert_eq!(CoreErrorCode::Timeout as i32, -14);
     assert_eq!(CoreErrorCode::Cancelled as i32, -15);
+    assert_eq!(CoreErrorCode::ResourceExhausted as i32, -16);
     assert_eq!(CoreErrorCode::Internal as i32, -99);
 }
 
+#[test]
+fn test_error_categories_map_to_distinct_codes() {
+    use gg_core::engine::inference::InferenceError;
+
+    let client = CoreErrorCode::from(InferenceError::InvalidParams("bad".into()));
+    let model = CoreErrorCode::from(InferenceError::ExecutionFailed("decode".into()));
+    let infra = CoreErrorCode::from(InferenceError::ResourceLimit("memory".into()));
+
+    assert_eq!(client, CoreErrorCode::InvalidParams);
+    assert_eq!(model, CoreErrorCode::InferenceFailed);
+    assert_eq!(infra, CoreErrorCode::ResourceExhausted);
+}
+
 #[test]
 fn test_error_codes_are_copy_and_eq() {
     let code1 = CoreErrorCode::Ok;
@@ -142,6 +157,7 @@ fn test_inference_params_default_values() {
     assert_eq!(params.top_k, 40);
     assert!(!params.stream);
     assert_eq!(params.timeout_ms, 0);
+    assert_eq!(params.seed, 0);
 }
 
 #[test]
@@ -154,11 +170,15 @@ fn test_inference_params_repr_c() {
         top_k: 50,
         stream: true,
         timeout_ms: 30000,
+        logit_bias: std::ptr::null(),
+        logit_bias_count: 0,
+        seed: 42,
     };
 
     assert_eq!(params.max_tokens, 512);
     assert!((params.temperature - 0.5).abs() < f32::EPSILON);
     assert_eq!(params.timeout_ms, 30000);
+    assert_eq!(params.seed, 42);
 }
 
 // ============================================================================
@@ -290,6 +310,185 @@ fn test_runtime_create_and_destroy_lifecycle() {
     unsafe { core_runtime_destroy(out_runtime) };
 }
 
+// ============================================================================
+// Inference Tests
+// ============================================================================
+
+#[test]
+fn test_infer_returns_queue_slot_after_each_call() {
+    let auth_token = CString::new("test_token_12345").unwrap();
+    let mut config = CoreConfig::default();
+    config.auth_token = auth_token.as_ptr();
+    config.max_queue_depth = 2;
+    let mut runtime: *mut gg_core::ffi::CoreRuntime = ptr::null_mut();
+    assert_eq!(unsafe { core_runtime_create(&config, &mut runtime) }, CoreErrorCode::Ok);
+    let mut session = ptr::null_mut();
+    assert_eq!(
+        unsafe { core_authenticate(runtime, auth_token.as_ptr(), &mut session) },
+        CoreErrorCode::Ok
+    );
+
+    let model = CString::new("no-such-model").unwrap();
+    let tokens = [1u32, 2, 3];
+    for _ in 0..config.max_queue_depth * 3 {
+        let mut result = CoreInferenceResult::default();
+        let code = unsafe {
+            core_infer(
+                runtime,
+                session,
+                model.as_ptr(),
+                tokens.as_ptr(),
+                tokens.len() as u32,
+                ptr::null(),
+                &mut result,
+            )
+        };
+        // Every call fails on the missing model, never on a leaked slot
+        assert_ne!(code, CoreErrorCode::QueueFull);
+    }
+
+    unsafe {
+        core_session_release(session);
+        core_runtime_destroy(runtime);
+    }
+}
+
+#[test]
+fn test_infer_rejects_prompt_over_max_prompt_tokens() {
+    let auth_token = CString::new("test_token_12345").unwrap();
+    let mut config = CoreConfig::default();
+    config.auth_token = auth_token.as_ptr();
+    let mut runtime: *mut gg_core::ffi::CoreRuntime = ptr::null_mut();
+    assert_eq!(unsafe { core_runtime_create(&config, &mut runtime) }, CoreErrorCode::Ok);
+    let mut session = ptr::null_mut();
+    assert_eq!(
+        unsafe { core_authenticate(runtime, auth_token.as_ptr(), &mut session) },
+        CoreErrorCode::Ok
+    );
+
+    let model = CString::new("no-such-model").unwrap();
+    let tokens = vec![1u32; gg_core::ipc::protocol::DEFAULT_MAX_PROMPT_TOKENS + 1];
+    let mut result = CoreInferenceResult::default();
+    let code = unsafe {
+        core_infer(
+            runtime,
+            session,
+            model.as_ptr(),
+            tokens.as_ptr(),
+            tokens.len() as u32,
+            ptr::null(),
+            &mut result,
+        )
+    };
+    assert_eq!(code, CoreErrorCode::InvalidParams);
+    let message = unsafe { CStr::from_ptr(core_get_last_error()) }.to_str().unwrap();
+    assert!(message.contains("max_prompt_tokens"));
+
+    unsafe {
+        core_session_release(session);
+        core_runtime_destroy(runtime);
+    }
+}
+
+// ============================================================================
+// Streaming Tests
+// ============================================================================
+
+unsafe extern "C" fn count_pieces(_token_utf8: *const c_char, user_data: *mut c_void) -> bool {
+    *(user_data as *mut usize) += 1;
+    true
+}
+
+#[test]
+fn test_infer_stream_rejects_null_pointers() {
+    core_clear_last_error();
+    let model = CString::new("model").unwrap();
+    let mut count = 0usize;
+
+    let result = unsafe {
+        core_infer_stream(
+            ptr::null_mut(),
+            ptr::null_mut(),
+            model.as_ptr(),
+            model.as_ptr(),
+            ptr::null(),
+            count_pieces,
+            &mut count as *mut usize as *mut c_void,
+        )
+    };
+
+    assert_eq!(result, CoreErrorCode::NullPointer);
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_infer_stream_unknown_model_fails_without_callback() {
+    core_clear_last_error();
+    let auth_token = CString::new("test_token_12345").unwrap();
+    let mut config = CoreConfig::default();
+    config.auth_token = auth_token.as_ptr();
+    let mut runtime: *mut gg_core::ffi::CoreRuntime = ptr::null_mut();
+    assert_eq!(unsafe { core_runtime_create(&config, &mut runtime) }, CoreErrorCode::Ok);
+    let mut session = ptr::null_mut();
+    assert_eq!(
+        unsafe { core_authenticate(runtime, auth_token.as_ptr(), &mut session) },
+        CoreErrorCode::Ok
+    );
+
+    let model = CString::new("no-such-model").unwrap();
+    let prompt = CString::new("Hello").unwrap();
+    let mut count = 0usize;
+    let result = unsafe {
+        core_infer_stream(
+            runtime,
+            session,
+            model.as_ptr(),
+            prompt.as_ptr(),
+            ptr::null(),
+            count_pieces,
+            &mut count as *mut usize as *mut c_void,
+        )
+    };
+
+    assert_ne!(result, CoreErrorCode::Ok);
+    assert!(!core_get_last_error().is_null());
+    assert_eq!(count, 0);
+
+    unsafe {
+        core_session_release(session);
+        core_runtime_destroy(runtime);
+    }
+}
+
+// ============================================================================
+// Build Info Tests
+// ============================================================================
+
+#[test]
+fn test_build_info_populated() {
+    let mut info = CoreBuildInfo::default();
+    let result = unsafe { core_build_info(&mut info) };
+
+    assert_eq!(result, CoreErrorCode::Ok);
+    assert!(!info.version.is_null());
+    let version = unsafe { CStr::from_ptr(info.version) }.to_str().unwrap();
+    assert_eq!(version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        format!("{}.{}.{}", info.version_major, info.version_minor, info.version_patch),
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_ne!(info.features & CORE_FEATURE_FFI, 0);
+    assert!(info.min_protocol_version >= 1);
+    assert!(info.max_protocol_version >= info.min_protocol_version);
+}
+
+#[test]
+fn test_build_info_rejects_null() {
+    core_clear_last_error();
+    let result = unsafe { core_build_info(ptr::null_mut()) };
+    assert_eq!(result, CoreErrorCode::NullPointer);
+}
+
 // ============================================================================
 // Type Size and Alignment Tests (for FFI ABI stability)
 // ============================================================================
@@ -1,6 +1,24 @@
 //! TDD-Light tests for output content filtering.
 
-use gg_core::engine::filter::{FilterConfig, OutputFilter};
+use gg_core::engine::filter::{FilterConfig, OutputFilter, OutputLimiter};
+use gg_core::engine::{FinishReason, GenerationResult};
+
+fn generation(text: &str) -> GenerationResult {
+    GenerationResult {
+        text: text.to_string(),
+        tokens_generated: 8,
+        finish_reason: FinishReason::MaxTokens,
+        raw_bytes: None,
+    }
+}
+
+fn byte_limited(max_output_bytes: usize) -> OutputFilter {
+    OutputFilter::new(FilterConfig {
+        max_output_bytes: Some(max_output_bytes),
+        ..Default::default()
+    })
+    .unwrap()
+}
 
 #[test]
 fn filter_default_passes_all_text() {
@@ -125,3 +143,74 @@ fn filter_multiple_blocklist_entries() {
     let result = filter.filter("bad1 and bad2 and bad3").unwrap();
     assert_eq!(result, "[X] and [X] and [X]");
 }
+
+#[test]
+fn filter_max_output_chars_keeps_multibyte_characters_whole() {
+    let config = FilterConfig {
+        max_output_chars: 4,
+        ..Default::default()
+    };
+    let filter = OutputFilter::new(config).unwrap();
+
+    // 日 spans bytes 3..6, so a 4-byte cut lands inside it
+    assert_eq!(filter.filter("hé日").unwrap(), "hé");
+}
+
+#[test]
+fn filter_apply_truncates_multibyte_output_on_char_boundary() {
+    // 日, 本 and 語 are 3 bytes each; 7 bytes ends inside 語
+    let filter = byte_limited(7);
+    let mut result = generation("日本語です");
+
+    filter.apply(&mut result).unwrap();
+    assert_eq!(result.text, "日本");
+    assert_eq!(result.finish_reason, FinishReason::Length);
+
+    let mut raw = generation("日本語です");
+    raw.raw_bytes = Some("日本語です".as_bytes().to_vec());
+    filter.apply(&mut raw).unwrap();
+    assert_eq!(raw.raw_bytes.as_deref(), Some("日本".as_bytes()));
+    assert_eq!(raw.finish_reason, FinishReason::Length);
+}
+
+#[test]
+fn filter_apply_within_limit_keeps_finish_reason() {
+    let filter = byte_limited(9);
+    let mut result = generation("日本語");
+
+    filter.apply(&mut result).unwrap();
+    assert_eq!(result.text, "日本語");
+    assert_eq!(result.finish_reason, FinishReason::MaxTokens);
+
+    assert_eq!(byte_limited(5).filter("héllo wörld").unwrap(), "héll");
+}
+
+#[test]
+fn filter_limiter_stops_streamed_output_on_char_boundary() {
+    let mut limiter = byte_limited(8).limiter();
+
+    assert_eq!(limiter.push("€1"), "€1");
+    assert!(!limiter.is_exceeded());
+    // 4 bytes remain; "é" fits, the next "€" would be split
+    assert_eq!(limiter.push("é€x"), "é");
+    assert!(limiter.is_exceeded());
+    assert_eq!(limiter.finish_reason(), Some(FinishReason::Length));
+    assert_eq!(limiter.push("more"), "");
+
+    let mut unlimited = OutputLimiter::new(None);
+    assert_eq!(unlimited.push(&"語".repeat(1000)).len(), 3000);
+    assert_eq!(unlimited.finish_reason(), None);
+}
+
+#[test]
+fn filter_limiter_admits_whole_pieces_only() {
+    let mut limiter = byte_limited(5).limiter();
+    assert!(limiter.is_limited());
+
+    assert!(limiter.admit(3));
+    assert!(!limiter.admit(3), "a piece crossing the limit is dropped whole");
+    assert!(!limiter.admit(1), "nothing is admitted once the limit is hit");
+    assert_eq!(limiter.finish_reason(), Some(FinishReason::Length));
+
+    assert!(!OutputLimiter::new(None).is_limited());
+}
@@ -4,7 +4,10 @@
 //! GPU Support Tests
 
 use std::sync::Arc;
-use gg_core::engine::{GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager, GpuMemoryPool};
+use gg_core::engine::{
+    DeviceEnumerator, DeviceSelection, GpuBackend, GpuConfig, GpuDevice, GpuError, GpuManager,
+    GpuMemoryPool,
+};
 
 #[test]
 fn test_gpu_backend_display() {
@@ -63,7 +66,7 @@ fn test_gpu_device_memory_utilization() {
 fn test_gpu_config_default() {
     let config = GpuConfig::default();
     assert_eq!(config.backend, GpuBackend::Cpu);
-    assert_eq!(config.device_index, 0);
+    assert_eq!(config.device_selection, DeviceSelection::Index(0));
     assert_eq!(config.memory_fraction, 0.9);
     assert!(config.flash_attention);
     assert_eq!(config.gpu_layers, 0);
@@ -89,8 +92,7 @@ fn test_gpu_manager_cpu_only() {
     let config = GpuConfig::cpu();
     let manager = GpuManager::new(config).unwrap();
 
-    assert!(manager.active_device().is_some());
-    assert_eq!(manager.active_device().unwrap().backend, GpuBackend::Cpu);
+    assert_eq!(manager.active_device().backend, GpuBackend::Cpu);
     assert!(!manager.is_gpu_available());
 }
 
@@ -186,7 +188,6 @@ fn test_gpu_manager_select_device_fallback() {
     // Request CUDA when only CPU is available
     let config = GpuConfig {
         backend: GpuBackend::Cuda,
-        device_index: 0,
         ..Default::default()
     };
 
@@ -197,7 +198,7 @@ fn test_gpu_manager_select_device_fallback() {
     // Either succeeds with CPU fallback or fails gracefully
     match result {
         Ok(manager) => {
-            assert_eq!(manager.active_device().unwrap().backend, GpuBackend::Cpu);
+            assert_eq!(manager.active_device().backend, GpuBackend::Cpu);
         }
         Err(GpuError::NoDevicesAvailable) => {
             // Also acceptable - no GPU devices found
@@ -205,3 +206,80 @@ fn test_gpu_manager_select_device_fallback() {
         Err(e) => panic!("Unexpected error: {}", e),
     }
 }
+
+/// Reports a fixed set of CUDA devices with the given free memory.
+struct MockDevices(Vec<u64>);
+
+impl DeviceEnumerator for MockDevices {
+    fn devices(&self) -> Vec<GpuDevice> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, &available_memory)| GpuDevice {
+                backend: GpuBackend::Cuda,
+                index,
+                name: format!("Mock GPU {}", index),
+                total_memory: 16 << 30,
+                available_memory,
+                compute_capability: Some((8, 0)),
+            })
+            .collect()
+    }
+}
+
+fn mock_manager(selection: DeviceSelection, free: Vec<u64>) -> Result<GpuManager, GpuError> {
+    let config = GpuConfig {
+        backend: GpuBackend::Cuda,
+        device_selection: selection,
+        ..Default::default()
+    };
+    GpuManager::with_enumerator(config, Box::new(MockDevices(free)))
+}
+
+#[test]
+fn test_device_selection_most_free_memory() {
+    let manager = mock_manager(DeviceSelection::MostFreeMemory, vec![4 << 30, 12 << 30, 8 << 30])
+        .unwrap();
+
+    let device = manager.active_device();
+    assert_eq!(device.backend, GpuBackend::Cuda);
+    assert_eq!(device.index, 1);
+    assert_eq!(device.available_memory, 12 << 30);
+}
+
+#[test]
+fn test_device_selection_by_index() {
+    let manager = mock_manager(DeviceSelection::Index(2), vec![1, 2, 3]).unwrap();
+    assert_eq!(manager.active_device().index, 2);
+    // Not round-robin: loads stay on the chosen device
+    assert_eq!(manager.next_device().index, 2);
+    assert_eq!(manager.next_device().index, 2);
+}
+
+#[test]
+fn test_device_selection_index_out_of_range() {
+    let result = mock_manager(DeviceSelection::Index(3), vec![1, 2, 3]);
+    assert!(matches!(result, Err(GpuError::DeviceNotFound(3))));
+}
+
+#[test]
+fn test_device_selection_round_robin_spreads_loads() {
+    let manager = mock_manager(DeviceSelection::RoundRobin, vec![1, 2, 3]).unwrap();
+
+    let placed: Vec<usize> = (0..5).map(|_| manager.next_device().index).collect();
+    assert_eq!(placed, vec![0, 1, 2, 0, 1]);
+    // Placing a load does not rebind the device other callers see
+    assert_eq!(manager.active_device().index, 0);
+}
+
+#[test]
+fn test_round_robin_allocations_follow_placement() {
+    let manager = mock_manager(DeviceSelection::RoundRobin, vec![1 << 30; 3]).unwrap();
+
+    let placed: Vec<usize> = (0..4)
+        .map(|_| manager.allocate_memory(1024).unwrap().device.index)
+        .collect();
+    assert_eq!(placed, vec![0, 1, 2, 0]);
+    assert_eq!(manager.next_device().index, 1);
+    assert_eq!(manager.active_device().index, 0);
+}
@@ -0,0 +1,153 @@
+//! Tests for grammar-constrained decoding on a mock vocabulary.
+
+use std::sync::Arc;
+
+use gg_core::engine::sampling::{apply_logit_bias, sample_top_k_top_p};
+use gg_core::engine::{GrammarConstraint, GrammarState};
+
+/// Mock vocabulary mixing structural, string, number, literal and junk tokens.
+/// Single characters cover every literal so no valid prefix is a dead end.
+const VOCAB: &[&str] = &[
+    "{", "}", "[", "]", "\"", ":", ",", " ", "a", "b\"", "\\", "n", "u", "0", "1", "-", ".", "e",
+    "+", "true", "fal", "se", "null", "\"k\":", "}}", "]]", ",\"", "d", "\n", "é", "x]", "{\"",
+    "l", "r", "s",
+];
+const EOS: u32 = VOCAB.len() as u32;
+
+fn vocab() -> Arc<[String]> {
+    VOCAB.iter().map(|s| s.to_string()).collect()
+}
+
+/// Deterministic xorshift draws in [0, 1).
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Sample up to `max_tokens` with random logits, a hot temperature and
+/// `bias` pushing toward structurally risky tokens. Returns the text and
+/// whether EOS was sampled.
+fn generate(rng: &mut Rng, bias: &[(u32, f32)], max_tokens: usize) -> (String, bool) {
+    let mut grammar = GrammarState::new(&GrammarConstraint::Json, vocab(), EOS).unwrap();
+    let mut text = String::new();
+    for _ in 0..max_tokens {
+        let mut logits: Vec<f32> = (0..=EOS).map(|_| (rng.next() - 0.5) * 20.0).collect();
+        apply_logit_bias(&mut logits, bias);
+        assert!(
+            grammar.mask_logits(&mut logits) > 0,
+            "dead end after {:?}",
+            text
+        );
+
+        let token = sample_top_k_top_p(&logits, 0, 1.0, 5.0, 0, rng.next()).unwrap();
+        grammar.accept(token).unwrap();
+        if token == EOS {
+            return (text, true);
+        }
+        text.push_str(VOCAB[token as usize]);
+    }
+    (text, false)
+}
+
+#[test]
+fn constrained_generation_never_emits_invalid_json() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let adversarial: Vec<Vec<(u32, f32)>> = vec![
+        vec![],
+        // Push closers, separators and escapes that break naive output
+        vec![
+            (1, 8.0),
+            (3, 8.0),
+            (6, 8.0),
+            (10, 6.0),
+            (24, 8.0),
+            (25, 8.0),
+        ],
+        // Push EOS as early as possible
+        vec![(EOS, 12.0)],
+        // Ban EOS outright: output may be truncated but never invalid
+        vec![(EOS, f32::NEG_INFINITY)],
+    ];
+
+    let mut completed = 0;
+    for bias in &adversarial {
+        for _ in 0..100 {
+            let (text, finished) = generate(&mut rng, bias, 48);
+            if finished {
+                completed += 1;
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(&text) {
+                    panic!("invalid JSON {:?}: {}", text, e);
+                }
+            }
+        }
+    }
+    assert!(completed > 100, "only {} documents completed", completed);
+}
+
+#[test]
+fn eos_is_masked_until_json_is_complete() {
+    let mut grammar = GrammarState::new(&GrammarConstraint::Json, vocab(), EOS).unwrap();
+    assert!(!grammar.allows(EOS));
+
+    for token in [31, 8, 4, 5, 2, 13] {
+        // {"a":[0
+        grammar.accept(token).unwrap();
+        assert!(!grammar.allows(EOS));
+    }
+    assert!(!grammar.allows(1), "a brace cannot close an array");
+    grammar.accept(3).unwrap();
+    grammar.accept(1).unwrap();
+    assert!(grammar.allows(EOS));
+    assert!(!grammar.allows(6), "nothing may follow the top-level value");
+}
+
+/// One token per printable ASCII character, plus a two-byte character.
+fn char_vocab() -> Arc<[String]> {
+    (b' '..=b'~').map(|b| (b as char).to_string()).chain(["é".to_string()]).collect()
+}
+
+/// Feed `text` one character token at a time; false once a token is refused.
+fn feed_chars(grammar: &mut GrammarState, vocab: &[String], text: &str) -> bool {
+    text.chars().all(|c| {
+        let token = vocab.iter().position(|t| *t == c.to_string());
+        token.is_some_and(|token| grammar.accept(token as u32).is_ok())
+    })
+}
+
+#[test]
+fn json_grammar_accepts_valid_documents() {
+    let vocab = char_vocab();
+    let eos = vocab.len() as u32;
+    for doc in [
+        r#"{"a": [1, -2.5e+3, true, null], "b": {"c": "x\u00e9"}}"#,
+        "[]",
+        " {} ",
+        "0",
+        "\"é\"",
+        "[[[]], {}]",
+    ] {
+        let mut grammar = GrammarState::new(&GrammarConstraint::Json, vocab.clone(), eos).unwrap();
+        assert!(feed_chars(&mut grammar, &vocab, doc), "{}", doc);
+        assert!(grammar.allows(eos), "{}", doc);
+        serde_json::from_str::<serde_json::Value>(doc).unwrap();
+    }
+}
+
+#[test]
+fn json_grammar_rejects_invalid_prefixes() {
+    let vocab = char_vocab();
+    let eos = vocab.len() as u32;
+    for bad in ["}", "[1,]", "{\"a\" 1}", "{1:2}", "01", "tru e", "\"\\x\"", "\"\\ud800\"", "[1}"] {
+        let mut grammar = GrammarState::new(&GrammarConstraint::Json, vocab.clone(), eos).unwrap();
+        assert!(!feed_chars(&mut grammar, &vocab, bad) || !grammar.allows(eos), "{}", bad);
+    }
+    let mut grammar = GrammarState::new(&GrammarConstraint::Json, vocab.clone(), eos).unwrap();
+    assert!(feed_chars(&mut grammar, &vocab, "{\"a\": [1"));
+    assert!(!grammar.is_complete());
+}
@@ -1,8 +1,11 @@
 //! Health check tests for CORE Runtime.
 
-use gg_core::health::{HealthChecker, HealthConfig, HealthState};
+use std::sync::Arc;
+use std::time::Duration;
+
+use gg_core::health::{HealthChecker, HealthConfig, HealthState, NotReadyReason, StartupGate};
 use gg_core::ipc::{
-    decode_message, encode_message, HealthCheckResponse, HealthCheckType, IpcMessage,
+    decode_message, encode_message, HealthCheckResponse, HealthCheckType, IpcMessage, MockClock,
 };
 use gg_core::shutdown::ShutdownState;
 
@@ -39,6 +42,7 @@ fn test_ready_respects_model_requirement() {
     let config = HealthConfig {
         require_model_loaded: true,
         max_queue_depth: 1000,
+        ..Default::default()
     };
     let checker = HealthChecker::new(config);
 
@@ -54,6 +58,7 @@ fn test_not_ready_when_queue_full() {
     let config = HealthConfig {
         require_model_loaded: false,
         max_queue_depth: 10,
+        ..Default::default()
     };
     let checker = HealthChecker::new(config);
 
@@ -64,6 +69,29 @@ fn test_not_ready_when_queue_full() {
     assert!(!checker.is_ready(ShutdownState::Running, 0, 10));
 }
 
+#[test]
+fn test_not_ready_reason_identifies_cause() {
+    let checker = HealthChecker::new(HealthConfig {
+        require_model_loaded: true,
+        max_queue_depth: 10,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        checker.not_ready_reason(ShutdownState::Draining, 1, 0),
+        Some(NotReadyReason::ShuttingDown)
+    );
+    assert_eq!(
+        checker.not_ready_reason(ShutdownState::Running, 0, 0),
+        Some(NotReadyReason::NoModelsRegistered)
+    );
+    assert_eq!(
+        checker.not_ready_reason(ShutdownState::Running, 1, 10),
+        Some(NotReadyReason::QueueFull)
+    );
+    assert_eq!(checker.not_ready_reason(ShutdownState::Running, 1, 0), None);
+}
+
 #[test]
 fn test_report_includes_all_fields() {
     let checker = HealthChecker::default();
@@ -78,6 +106,81 @@ fn test_report_includes_all_fields() {
     // uptime_secs will be 0 or very small in tests
 }
 
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_report_cpu_features_match_detection() {
+    let report = HealthChecker::default().report(ShutdownState::Running, 0, 0, 0);
+    let reported = |feature: &str| report.cpu_features.iter().any(|f| f == feature);
+
+    assert_eq!(reported("sse4.2"), is_x86_feature_detected!("sse4.2"));
+    assert_eq!(reported("avx"), is_x86_feature_detected!("avx"));
+    assert_eq!(reported("avx2"), is_x86_feature_detected!("avx2"));
+    assert_eq!(reported("fma"), is_x86_feature_detected!("fma"));
+    assert_eq!(reported("avx512f"), is_x86_feature_detected!("avx512f"));
+    assert_eq!(reported("aes"), is_x86_feature_detected!("aes"));
+    assert!(!reported("neon"));
+    assert_eq!(report.simd_tokenizer, is_x86_feature_detected!("avx2"));
+}
+
+#[test]
+fn test_failures_only_grow_success_age_and_degrade_readiness() {
+    let clock = Arc::new(MockClock::new());
+    let checker = HealthChecker::new(HealthConfig {
+        max_success_age: Some(Duration::from_secs(60)),
+        ..Default::default()
+    })
+    .with_clock(clock.clone());
+
+    checker.record_inference_success();
+    clock.advance(Duration::from_secs(30));
+    checker.record_inference_failure();
+    assert_eq!(checker.last_success_age(), Duration::from_secs(30));
+    assert_eq!(checker.not_ready_reason(ShutdownState::Running, 1, 0), None);
+
+    clock.advance(Duration::from_secs(45));
+    checker.record_inference_failure();
+    assert_eq!(checker.last_success_age(), Duration::from_secs(75));
+    assert_eq!(
+        checker.not_ready_reason(ShutdownState::Running, 1, 0),
+        Some(NotReadyReason::NoRecentSuccess)
+    );
+    assert_eq!(
+        checker.report(ShutdownState::Running, 1, 0, 0).state,
+        HealthState::Degraded
+    );
+
+    checker.record_inference_success();
+    assert_eq!(checker.last_success_age(), Duration::ZERO);
+    assert!(checker.is_ready(ShutdownState::Running, 1, 0));
+}
+
+#[test]
+fn test_success_age_reported_without_affecting_readiness_by_default() {
+    let clock = Arc::new(MockClock::new());
+    let checker = HealthChecker::default().with_clock(clock.clone());
+
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(600));
+        checker.record_inference_failure();
+    }
+    assert_eq!(checker.last_success_age(), Duration::from_secs(1800));
+    assert!(checker.is_ready(ShutdownState::Running, 1, 0));
+}
+
+#[test]
+fn test_idle_runtime_is_not_stale() {
+    let clock = Arc::new(MockClock::new());
+    let checker = HealthChecker::new(HealthConfig {
+        max_success_age: Some(Duration::from_secs(60)),
+        ..Default::default()
+    })
+    .with_clock(clock.clone());
+
+    checker.record_inference_success();
+    clock.advance(Duration::from_secs(3600));
+    assert!(checker.is_ready(ShutdownState::Running, 1, 0));
+}
+
 // ============================================================================
 // Protocol Roundtrip Tests
 // ============================================================================
@@ -139,6 +242,7 @@ fn test_health_response_roundtrip() {
         check_type: HealthCheckType::Liveness,
         ok: true,
         report: None,
+        reason: None,
     };
     let message = IpcMessage::HealthResponse(response);
 
@@ -154,3 +258,61 @@ fn test_health_response_roundtrip() {
         _ => panic!("Expected HealthResponse message"),
     }
 }
+
+#[tokio::test]
+async fn test_readiness_reports_no_models_registered() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        health: HealthConfig {
+            require_model_loaded: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let request = IpcMessage::HealthCheck {
+        check_type: HealthCheckType::Readiness,
+    };
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&request).unwrap(), None)
+        .await
+        .unwrap();
+
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::HealthResponse(response) => {
+            assert!(!response.ok);
+            assert_eq!(response.reason, Some(NotReadyReason::NoModelsRegistered));
+        }
+        other => panic!("Expected HealthResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_startup_gate_holds_readiness_until_complete() {
+    let checker = Arc::new(HealthChecker::new(HealthConfig {
+        startup_gate: StartupGate::DelayBind,
+        ..Default::default()
+    }));
+    assert!(checker.is_starting_up());
+    assert_eq!(
+        checker.not_ready_reason(ShutdownState::Running, 1, 0),
+        Some(NotReadyReason::ModelsLoading)
+    );
+    assert_eq!(checker.report(ShutdownState::Running, 1, 0, 0).state, HealthState::Degraded);
+
+    let waiter = tokio::spawn({
+        let checker = Arc::clone(&checker);
+        async move { checker.wait_startup_complete().await }
+    });
+    tokio::task::yield_now().await;
+    assert!(!waiter.is_finished());
+
+    checker.mark_startup_complete();
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("gate should open")
+        .unwrap();
+    assert!(checker.is_ready(ShutdownState::Running, 1, 0));
+
+    // Without a gate the runtime is never held
+    assert!(!HealthChecker::default().is_starting_up());
+}
@@ -0,0 +1,82 @@
+//! `RuntimeConfig::history_window` reaches the models' decode config.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{handshake, infer, TEST_TOKEN};
+use gg_core::engine::{
+    FinishReason, GenerationResult, GgufModel, InferenceCapability, InferenceConfig,
+    InferenceError, InferenceInput, InferenceOutput, DEFAULT_HISTORY_WINDOW,
+};
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+
+/// Records the history window of every request it decodes.
+struct WindowRecorder(Arc<Mutex<Vec<usize>>>);
+
+#[async_trait::async_trait]
+impl GgufModel for WindowRecorder {
+    fn model_id(&self) -> &str {
+        "recorder"
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        self.0.lock().unwrap().push(config.history_window);
+        Ok(InferenceOutput::Generation(GenerationResult {
+            text: "ok".into(),
+            tokens_generated: 1,
+            finish_reason: FinishReason::Stop,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+async fn window_seen(config: RuntimeConfig) -> usize {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        ..config
+    });
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let model = Arc::new(WindowRecorder(Arc::clone(&seen)));
+    runtime.inference_engine.register_model("recorder".into(), ModelHandle::new(1), model).await;
+    let session = handshake(&runtime).await;
+
+    let response = infer(&runtime, &session, "recorder", 1).await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    seen[0]
+}
+
+#[tokio::test]
+async fn history_window_defaults_to_engine_default() {
+    assert_eq!(window_seen(RuntimeConfig::default()).await, DEFAULT_HISTORY_WINDOW);
+}
+
+#[tokio::test]
+async fn configured_history_window_reaches_the_model() {
+    let config = RuntimeConfig { history_window: 256, ..Default::default() };
+    assert_eq!(config.effective().sections["history_window"].as_u64(), Some(256));
+    assert_eq!(window_seen(config).await, 256);
+}
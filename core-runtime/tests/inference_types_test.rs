@@ -49,6 +49,22 @@ fn config_for_classification_is_deterministic() {
     assert!(config.max_tokens.is_none());
 }
 
+#[test]
+fn config_zero_top_p_is_greedy_unless_min_p_set() {
+    let mut greedy = InferenceConfig { top_p: 0.0, ..Default::default() };
+    greedy.normalize_sampling(0.1);
+    assert_eq!((greedy.top_k, greedy.temperature, greedy.top_p), (1, 0.0, 1.0));
+
+    let defaults = InferenceConfig::default();
+    let mut min_p = InferenceConfig { top_p: 0.0, min_p: Some(0.1), ..Default::default() };
+    min_p.normalize_sampling(0.1);
+    assert_eq!(min_p.min_p, Some(0.1));
+    assert_eq!(min_p.top_p, 1.0);
+    assert_eq!(min_p.top_k, defaults.top_k);
+    assert_eq!(min_p.temperature, defaults.temperature);
+    assert!(min_p.validate().is_ok());
+}
+
 // ============================================================================
 // InferenceInput Tests
 // ============================================================================
@@ -127,6 +143,7 @@ fn output_generation_result_created() {
         text: "Generated text here".to_string(),
         tokens_generated: 10,
         finish_reason: FinishReason::Stop,
+        raw_bytes: None,
     };
     let output = InferenceOutput::Generation(result);
     assert!(output.is_generation());
@@ -2,7 +2,7 @@
 //!
 //! Tests the complete flow: IPC → Scheduler → Engine → Response.
 
-use gg_core::engine::{FilterConfig, InferenceParams};
+use gg_core::engine::{FilterConfig, InferenceParams, OutputEncoding};
 use gg_core::ipc::protocol::{
     decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
 };
@@ -19,9 +19,22 @@ fn ipc_request_roundtrip() {
             temperature: 0.7,
             top_p: 1.0,
             top_k: 50,
+            min_p: None,
             stream: false,
             timeout_ms: None,
+            max_cpu_ms: None,
+            output_encoding: OutputEncoding::Utf8,
+            no_cache: false,
+            logit_bias: Default::default(),
+            repetition_penalty: 1.0,
+            no_repeat_ngram_size: 0,
+            seed: None,
+            stop_sequences: Vec::new(),
+            sampler: Default::default(),
         },
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
 
     let message = IpcMessage::InferenceRequest(request.clone());
@@ -83,7 +96,7 @@ fn error_propagation_model_not_found() {
 
 #[test]
 fn concurrent_request_queue_capacity() {
-    let config = RequestQueueConfig { max_pending: 100 };
+    let config = RequestQueueConfig { max_pending: 100, ..Default::default() };
 
     // Queue should accept requests up to max_pending
     assert_eq!(config.max_pending, 100);
@@ -113,8 +126,18 @@ fn inference_params_serialization() {
         temperature: 0.8,
         top_p: 0.95,
         top_k: 40,
+        min_p: None,
         stream: false,
         timeout_ms: None,
+        max_cpu_ms: None,
+        output_encoding: OutputEncoding::Utf8,
+        no_cache: false,
+        logit_bias: Default::default(),
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        seed: None,
+        stop_sequences: Vec::new(),
+        sampler: Default::default(),
     };
 
     // Params should be serializable
@@ -4,7 +4,7 @@
 
 use gg_core::engine::{
     FinishReason, GenerationResult, GgufConfig, InferenceOutput,
-    InferenceParams, ChatMessage, ChatRole,
+    InferenceParams, ChatMessage, ChatRole, OutputEncoding,
 };
 use gg_core::models::ModelLoader;
 
@@ -39,6 +39,7 @@ fn generation_result_structure_valid() {
         text: "Generated text output".to_string(),
         tokens_generated: 5,
         finish_reason: FinishReason::Stop,
+        raw_bytes: None,
     };
 
     assert!(!result.text.is_empty());
@@ -54,9 +55,10 @@ fn stop_reason_variants_correct() {
         FinishReason::MaxTokens,
         FinishReason::Timeout,
         FinishReason::ContentFiltered,
+        FinishReason::Length,
     ];
 
-    assert_eq!(reasons.len(), 4, "Should have 4 finish reasons");
+    assert_eq!(reasons.len(), 5, "Should have 5 finish reasons");
 }
 
 #[test]
@@ -66,8 +68,18 @@ fn inference_params_valid_values() {
         temperature: 2.5, // High temperature is allowed
         top_p: 1.0,
         top_k: 50,
+        min_p: None,
         stream: false,
         timeout_ms: None,
+        max_cpu_ms: None,
+        output_encoding: OutputEncoding::Utf8,
+        no_cache: false,
+        logit_bias: Default::default(),
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        seed: None,
+        stop_sequences: Vec::new(),
+        sampler: Default::default(),
     };
 
     // Temperature should be usable even if high
@@ -82,8 +94,18 @@ fn inference_params_typical_defaults() {
         temperature: 0.7,
         top_p: 0.9,
         top_k: 40,
+        min_p: None,
         stream: false,
         timeout_ms: None,
+        max_cpu_ms: None,
+        output_encoding: OutputEncoding::Utf8,
+        no_cache: false,
+        logit_bias: Default::default(),
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        seed: None,
+        stop_sequences: Vec::new(),
+        sampler: Default::default(),
     };
 
     assert!(params.max_tokens > 0);
@@ -120,6 +142,7 @@ fn inference_output_generation_identification() {
         text: "Output".to_string(),
         tokens_generated: 1,
         finish_reason: FinishReason::Stop,
+        raw_bytes: None,
     };
     let output = InferenceOutput::Generation(generation);
 
@@ -136,8 +159,18 @@ fn max_tokens_param_respected() {
         temperature: 0.0,
         top_p: 1.0,
         top_k: 1,
+        min_p: None,
         stream: false,
         timeout_ms: None,
+        max_cpu_ms: None,
+        output_encoding: OutputEncoding::Utf8,
+        no_cache: false,
+        logit_bias: Default::default(),
+        repetition_penalty: 1.0,
+        no_repeat_ngram_size: 0,
+        seed: None,
+        stop_sequences: Vec::new(),
+        sampler: Default::default(),
     };
 
     assert_eq!(params.max_tokens, 10);
@@ -0,0 +1,132 @@
+//! IPC message handling seen from the client side, through `IpcHandler`.
+
+mod common;
+
+use common::handshake;
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
+};
+
+#[tokio::test]
+async fn client_metadata_echoed_verbatim() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let handler = &runtime.ipc_handler;
+
+    let session = handshake(&runtime).await;
+
+    // Unicode and JSON-looking content must come back byte-for-byte
+    let metadata = r#"trace-7f3a {"span":"ü"}"#.to_string();
+    let request = InferenceRequest {
+        request_id: RequestId(7),
+        model_id: "unloaded-model".to_string(),
+        prompt: "Hello".to_string(),
+        parameters: InferenceParams::default(),
+        client_metadata: Some(metadata.clone()),
+        priority: None,
+        client_id: None,
+    };
+    let message = encode_message(&IpcMessage::InferenceRequest(request)).unwrap();
+    let (bytes, _) = handler.process(&message, Some(&session)).await.unwrap();
+
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::InferenceResponse(response) => {
+            assert_eq!(response.request_id, RequestId(7));
+            assert_eq!(response.client_metadata, Some(metadata));
+        }
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    }
+}
+
+/// Perform a handshake and return the compression the server acknowledged.
+async fn negotiated_compression(
+    runtime: &gg_core::Runtime,
+    requested: Option<gg_core::ipc::Compression>,
+) -> gg_core::ipc::Compression {
+    let handshake = IpcMessage::Handshake {
+        token: "test-token".into(),
+        protocol_version: None,
+        compression: requested,
+        strict_version: false,
+    };
+    let bytes = encode_message(&handshake).unwrap();
+    let (response, _) = runtime.ipc_handler.process(&bytes, None).await.unwrap();
+    match decode_message(&response).unwrap() {
+        IpcMessage::HandshakeAck { compression, .. } => compression,
+        other => panic!("Expected HandshakeAck, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn compression_negotiated_only_when_enabled() {
+    use gg_core::ipc::{Compression, CompressionConfig};
+
+    let disabled = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let negotiated = negotiated_compression(&disabled, Some(Compression::Zstd)).await;
+    assert_eq!(negotiated, Compression::None);
+
+    let enabled = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ipc_compression: CompressionConfig { enabled: true, ..Default::default() },
+        ..Default::default()
+    });
+    let negotiated = negotiated_compression(&enabled, Some(Compression::Zstd)).await;
+    assert_eq!(negotiated, Compression::Zstd);
+    assert_eq!(negotiated_compression(&enabled, None).await, Compression::None);
+}
+
+#[tokio::test]
+async fn config_request_reports_effective_config_with_token_masked() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        max_context_length: 8192,
+        ..Default::default()
+    });
+    let handler = &runtime.ipc_handler;
+    let request = encode_message(&IpcMessage::ConfigRequest).unwrap();
+
+    // Unauthenticated callers are rejected
+    assert!(handler.process(&request, None).await.is_err());
+
+    let session = handshake(&runtime).await;
+
+    let (bytes, _) = handler.process(&request, Some(&session)).await.unwrap();
+    let raw = String::from_utf8(bytes.clone()).unwrap();
+    assert!(!raw.contains("test-token"));
+
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::ConfigResponse(config) => {
+            assert_eq!(config.max_context_length, 8192);
+            assert_eq!(config.auth_token, gg_core::ipc::protocol::REDACTED);
+            let request_queue = &config.sections["request_queue"];
+            assert!(request_queue["max_pending"].is_u64());
+            assert_eq!(config.sections["strict_protocol"], serde_json::Value::Bool(false));
+        }
+        other => panic!("Expected ConfigResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn strict_protocol_rejects_unknown_fields() {
+    let json = br#"{"type":"metrics_request","downgrade":"v0"}"#;
+
+    let lenient = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    assert!(lenient.ipc_handler.process(json, None).await.is_ok());
+
+    let strict = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        strict_protocol: true,
+        ..Default::default()
+    });
+    match strict.ipc_handler.process(json, None).await {
+        Err(gg_core::ipc::HandlerError::Protocol(gg_core::ipc::ProtocolError::UnknownFields(
+            fields,
+        ))) => assert_eq!(fields, vec!["downgrade"]),
+        other => panic!("Expected UnknownFields, got {:?}", other.map(|_| ())),
+    }
+}
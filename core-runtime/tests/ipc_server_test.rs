@@ -103,6 +103,7 @@ async fn test_framing_multiple_messages() {
 fn test_owned_guard_acquire_and_release() {
     let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
         max_connections: 2,
+        ..Default::default()
     }));
 
     let g1 = pool.try_acquire_owned();
@@ -124,6 +125,7 @@ fn test_owned_guard_acquire_and_release() {
 fn test_owned_guard_rejects_at_limit() {
     let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
         max_connections: 1,
+        ..Default::default()
     }));
 
     let _g = pool.try_acquire_owned();
@@ -157,6 +159,7 @@ mod windows_server_tests {
         let handler = test_handler();
         let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
             max_connections: 4,
+            ..Default::default()
         }));
 
         let (tx, rx) = tokio::sync::watch::channel(false);
@@ -199,6 +202,7 @@ mod windows_server_tests {
         let handler = test_handler();
         let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
             max_connections: 4,
+            ..Default::default()
         }));
 
         let (tx, rx) = tokio::sync::watch::channel(false);
@@ -233,6 +237,7 @@ mod windows_server_tests {
         let handler = test_handler();
         let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
             max_connections: 8,
+            ..Default::default()
         }));
 
         let (tx, rx) = tokio::sync::watch::channel(false);
@@ -269,6 +274,7 @@ mod windows_server_tests {
         let handler = test_handler();
         let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
             max_connections: 4,
+            ..Default::default()
         }));
 
         let (tx, rx) = tokio::sync::watch::channel(false);
@@ -299,6 +305,7 @@ mod windows_server_tests {
         let handler = test_handler();
         let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
             max_connections: 4,
+            ..Default::default()
         }));
 
         let (tx, rx) = tokio::sync::watch::channel(false);
@@ -331,6 +338,168 @@ mod windows_server_tests {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Protocol metrics: malformed frames over an in-memory connection
+// ---------------------------------------------------------------------------
+
+const LIVENESS: &[u8] = br#"{"type":"health_check","check_type":"Liveness"}"#;
+
+/// Serve one duplex connection with the given decode error policy.
+fn serve_duplex(
+    policy: gg_core::ipc::DecodeErrorPolicy,
+) -> (
+    tokio::io::DuplexStream,
+    Arc<gg_core::telemetry::MetricsStore>,
+    Arc<gg_core::ipc::IpcHandler>,
+    tokio::task::JoinHandle<()>,
+) {
+    let rt = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        decode_errors: policy,
+        ..Default::default()
+    });
+    let metrics = Arc::clone(&rt.metrics_store);
+    let handler = Arc::new(rt.ipc_handler);
+    let pool = Arc::new(ConnectionPool::new(ConnectionConfig { max_connections: 1, ..Default::default() }));
+    let guard = pool.try_acquire_owned().unwrap();
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let task = tokio::spawn(gg_core::ipc::server::handle_connection(
+        server,
+        Arc::clone(&handler),
+        guard,
+    ));
+    (client, metrics, handler, task)
+}
+
+#[tokio::test]
+async fn test_malformed_frames_counted_and_connection_dropped() {
+    let (mut client, metrics, handler, task) = serve_duplex(gg_core::ipc::DecodeErrorPolicy {
+        max_error_rate: 0.5,
+        min_frames: 4,
+        drop_connection: true,
+    });
+
+    write_frame(&mut client, LIVENESS).await;
+    assert!(String::from_utf8_lossy(&read_frame(&mut client).await).contains("health_response"));
+
+    // Frame 4 (3 of 4 bad) crosses the rate; the server answers, then hangs up
+    for _ in 0..3 {
+        write_frame(&mut client, b"\x00 not json").await;
+        let resp = read_frame(&mut client).await;
+        assert!(String::from_utf8_lossy(&resp).contains(r#""code":400"#));
+    }
+    tokio::time::timeout(Duration::from_secs(2), task)
+        .await
+        .expect("connection should be dropped")
+        .unwrap();
+
+    let counters = metrics.snapshot().counters;
+    assert_eq!(counters[gg_core::ipc::MESSAGES_DECODED_TOTAL], 1);
+    assert_eq!(counters[gg_core::ipc::DECODE_ERRORS_TOTAL], 3);
+    assert_eq!(counters[gg_core::ipc::ABUSIVE_CONNECTIONS_TOTAL], 1);
+    let frames_in = (LIVENESS.len() + 4) + 3 * (b"\x00 not json".len() + 4);
+    assert_eq!(counters[gg_core::ipc::BYTES_IN_TOTAL], frames_in as u64);
+    assert!(counters[gg_core::ipc::BYTES_OUT_TOTAL] > 0);
+    // Per-connection counters go away with the connection
+    assert!(handler.protocol_stats().connections().is_empty());
+}
+
+#[tokio::test]
+async fn test_malformed_frames_reported_but_kept_without_drop_policy() {
+    let (mut client, metrics, handler, _task) = serve_duplex(gg_core::ipc::DecodeErrorPolicy {
+        max_error_rate: 0.5,
+        min_frames: 2,
+        drop_connection: false,
+    });
+
+    for _ in 0..3 {
+        write_frame(&mut client, b"garbage").await;
+        read_frame(&mut client).await;
+    }
+    write_frame(&mut client, LIVENESS).await;
+    assert!(String::from_utf8_lossy(&read_frame(&mut client).await).contains("health_response"));
+
+    let live = handler.protocol_stats().connections();
+    let conn = live.values().next().expect("connection still live");
+    assert_eq!(conn.decode_errors, 3);
+    assert_eq!(conn.messages_decoded, 1);
+    assert_eq!(metrics.snapshot().counters[gg_core::ipc::ABUSIVE_CONNECTIONS_TOTAL], 1);
+}
+
+#[tokio::test]
+async fn test_oversized_frame_counted() {
+    let (mut client, metrics, _handler, task) =
+        serve_duplex(gg_core::ipc::DecodeErrorPolicy::default());
+
+    client.write_all(&u32::MAX.to_le_bytes()).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(2), task)
+        .await
+        .expect("oversized frame closes the connection")
+        .unwrap();
+
+    assert_eq!(metrics.snapshot().counters[gg_core::ipc::OVERSIZED_FRAMES_TOTAL], 1);
+}
+
+// ---------------------------------------------------------------------------
+// Idle timeout: silent connections are reaped, chatty ones kept
+// ---------------------------------------------------------------------------
+
+/// Serve one duplex connection from a pool with the given idle timeout.
+fn serve_duplex_idle(
+    idle_timeout: Duration,
+) -> (
+    tokio::io::DuplexStream,
+    Arc<ConnectionPool>,
+    tokio::task::JoinHandle<()>,
+) {
+    let pool = Arc::new(ConnectionPool::new(ConnectionConfig {
+        idle_timeout: Some(idle_timeout),
+        ..Default::default()
+    }));
+    let guard = pool.try_acquire_owned().unwrap();
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let task = tokio::spawn(gg_core::ipc::server::handle_connection(
+        server,
+        test_handler(),
+        guard,
+    ));
+    (client, pool, task)
+}
+
+#[tokio::test]
+async fn test_idle_connection_reaped() {
+    let (_client, pool, task) = serve_duplex_idle(Duration::from_millis(100));
+    assert_eq!(pool.active_count(), 1);
+
+    tokio::time::timeout(Duration::from_secs(2), task)
+        .await
+        .expect("idle connection should be closed")
+        .unwrap();
+    assert_eq!(pool.active_count(), 0);
+}
+
+#[tokio::test]
+async fn test_active_connection_survives_idle_timeout() {
+    let (mut client, pool, task) = serve_duplex_idle(Duration::from_millis(150));
+
+    // Well past the timeout in total, but never silent for that long
+    for _ in 0..8 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        write_frame(&mut client, LIVENESS).await;
+        let resp = read_frame(&mut client).await;
+        assert!(String::from_utf8_lossy(&resp).contains("health_response"));
+    }
+    assert!(!task.is_finished());
+    assert_eq!(pool.active_count(), 1);
+
+    // Going quiet afterwards still gets it reaped
+    tokio::time::timeout(Duration::from_secs(2), task)
+        .await
+        .expect("connection should be closed once idle")
+        .unwrap();
+    assert_eq!(pool.active_count(), 0);
+}
+
 // ---------------------------------------------------------------------------
 // ServerError variant tests
 // ---------------------------------------------------------------------------
@@ -7,6 +7,7 @@
 //! - Cache eviction policies (LRU, FIFO, LFU)
 //! - Multi-sequence management
 //! - Memory tracking
+//! - Grouped-query attention layout
 
 use gg_core::memory::{EvictionPolicy, KvCacheConfig, KvCacheManager, SequenceId};
 
@@ -17,16 +18,18 @@ fn test_config() -> KvCacheConfig {
         max_pages: 64,
         max_seq_len: 1024,
         num_heads: 8,
+        num_kv_heads: 8,
         head_dim: 16,
         enable_quantization: true,
         enable_paged: true,
         eviction_policy: EvictionPolicy::Lru,
+        max_sequence_page_fraction: 1.0,
     }
 }
 
 #[test]
 fn test_allocate_sequence() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
 
     let seq1 = manager.allocate_sequence();
     let seq2 = manager.allocate_sequence();
@@ -39,7 +42,7 @@ fn test_allocate_sequence() {
 
 #[test]
 fn test_append_and_read_single() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let keys: Vec<f32> = (0..128).map(|i| i as f32).collect();
@@ -65,7 +68,7 @@ fn test_append_and_read_single() {
 
 #[test]
 fn test_append_multiple_positions() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     // Append 32 KV pairs with values that quantize well
@@ -110,7 +113,7 @@ fn test_cross_page_boundary() {
         max_seq_len: 512,
         ..test_config()
     };
-    let manager = KvCacheManager::new(config);
+    let manager = KvCacheManager::new(config).unwrap();
     let seq_id = manager.allocate_sequence();
 
     // core-runtime uses 16 tokens per page
@@ -146,7 +149,7 @@ fn test_cross_page_boundary() {
 
 #[test]
 fn test_free_sequence() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let keys = vec![1.0f32; 128];
@@ -162,7 +165,7 @@ fn test_free_sequence() {
 
 #[test]
 fn test_free_nonexistent_sequence() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let fake_id = SequenceId(9999);
 
     let result = manager.free_sequence(fake_id);
@@ -171,7 +174,7 @@ fn test_free_nonexistent_sequence() {
 
 #[test]
 fn test_read_out_of_bounds() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let keys = vec![1.0f32; 128];
@@ -188,7 +191,7 @@ fn test_read_out_of_bounds() {
 
 #[test]
 fn test_attention_scores_basic() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     // Add 8 KV pairs with distinct non-zero patterns
@@ -218,7 +221,7 @@ fn test_attention_scores_basic() {
 
 #[test]
 fn test_memory_usage_tracking() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let initial_memory = manager.memory_usage();
@@ -246,7 +249,7 @@ fn test_memory_usage_tracking() {
 
 #[test]
 fn test_multi_sequence_independence() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
 
     let seq1 = manager.allocate_sequence();
     let seq2 = manager.allocate_sequence();
@@ -294,7 +297,7 @@ fn test_multi_sequence_independence() {
 
 #[test]
 fn test_reset_clears_all() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
 
     let seq1 = manager.allocate_sequence();
     let seq2 = manager.allocate_sequence();
@@ -320,7 +323,7 @@ fn test_quantization_disabled() {
         enable_quantization: false,
         ..test_config()
     };
-    let manager = KvCacheManager::new(config);
+    let manager = KvCacheManager::new(config).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let keys: Vec<f32> = (0..128).map(|i| i as f32 * 0.1).collect();
@@ -345,7 +348,7 @@ fn test_quantization_disabled() {
 
 #[test]
 fn test_large_sequence() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     // Write 256 positions
@@ -383,7 +386,7 @@ fn test_large_sequence() {
 
 #[test]
 fn test_stats_tracking() {
-    let manager = KvCacheManager::new(test_config());
+    let manager = KvCacheManager::new(test_config()).unwrap();
     let seq_id = manager.allocate_sequence();
 
     let keys = vec![1.0f32; 128];
@@ -396,3 +399,187 @@ fn test_stats_tracking() {
     let stats = manager.stats();
     assert!(stats.memory_bytes_used > 0 || manager.memory_usage() > 0);
 }
+
+#[test]
+fn test_stats_counters_match_operations() {
+    let page_bytes = 16 * 128 * 2 * 4;
+    let manager = KvCacheManager::new(test_config()).unwrap();
+    let seq_id = manager.allocate_sequence();
+
+    let keys = vec![1.0f32; 128];
+    let values = vec![2.0f32; 128];
+    // 20 tokens span two pages
+    for _ in 0..20 {
+        manager.append_kv(seq_id, &keys, &values).unwrap();
+    }
+
+    let mut k_out = vec![0.0f32; 128];
+    let mut v_out = vec![0.0f32; 128];
+    for pos in [0, 5, 19] {
+        manager.read_kv(seq_id, pos, &mut k_out, &mut v_out).unwrap();
+    }
+
+    let stats = manager.stats();
+    assert_eq!(stats.total_pages_allocated, 2);
+    assert_eq!(stats.current_pages_in_use, 2);
+    assert_eq!(stats.memory_bytes_used, 2 * page_bytes);
+    assert_eq!(stats.cache_hits, 3);
+    assert_eq!(stats.cache_misses, 0);
+    assert_eq!(stats.hit_rate(), 1.0);
+
+    manager.free_sequence(seq_id).unwrap();
+    let stats = manager.stats();
+    assert_eq!(stats.total_pages_freed, 2);
+    assert_eq!(stats.current_pages_in_use, 0);
+    assert_eq!(stats.memory_bytes_used, 0);
+    assert_eq!(stats.peak_memory_bytes, 2 * page_bytes);
+
+    // Without the quantized store every read falls back to the page table
+    let unquantized = KvCacheManager::new(KvCacheConfig {
+        enable_quantization: false,
+        ..test_config()
+    })
+        .unwrap();
+    let seq_id = unquantized.allocate_sequence();
+    unquantized.append_kv(seq_id, &keys, &values).unwrap();
+    unquantized.read_kv(seq_id, 0, &mut k_out, &mut v_out).unwrap();
+    let stats = unquantized.stats();
+    assert_eq!((stats.cache_hits, stats.cache_misses), (0, 1));
+    assert_eq!(stats.hit_rate(), 0.0);
+}
+
+// ============================================================================
+// Grouped-Query Attention
+// ============================================================================
+
+/// Llama-style layout: 32 query heads of 128 dims.
+fn llama_config(num_kv_heads: usize) -> KvCacheConfig {
+    KvCacheConfig {
+        hidden_dim: 4096,
+        max_pages: 8,
+        max_seq_len: 64,
+        num_heads: 32,
+        num_kv_heads,
+        head_dim: 128,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_gqa_uses_quarter_of_kv_memory_and_reads_back() {
+    let dense = KvCacheManager::new(llama_config(32)).unwrap();
+    let gqa = KvCacheManager::new(llama_config(8)).unwrap();
+    let (dense_seq, gqa_seq) = (dense.allocate_sequence(), gqa.allocate_sequence());
+
+    let kv = |token: usize, width: usize, offset: f32| -> Vec<f32> {
+        (0..width)
+            .map(|i| ((token * 31 + i) % 97) as f32 / 97.0 - 0.5 + offset)
+            .collect()
+    };
+    for token in 0..20 {
+        dense
+            .append_kv(dense_seq, &kv(token, 4096, 0.0), &kv(token, 4096, 0.25))
+            .unwrap();
+        gqa.append_kv(gqa_seq, &kv(token, 1024, 0.0), &kv(token, 1024, 0.25))
+            .unwrap();
+    }
+
+    assert_eq!(dense.memory_usage(), 4 * gqa.memory_usage());
+    assert_eq!(dense.stats().memory_bytes_used, 4 * gqa.stats().memory_bytes_used);
+
+    let (mut keys, mut values) = (vec![0.0f32; 1024], vec![0.0f32; 1024]);
+    for token in [0, 7, 19] {
+        gqa.read_kv(gqa_seq, token, &mut keys, &mut values).unwrap();
+        let close = |got: &[f32], want: &[f32]| {
+            got.iter().zip(want).all(|(g, w)| (g - w).abs() < 0.01)
+        };
+        assert!(close(&keys, &kv(token, 1024, 0.0)), "keys at {}", token);
+        assert!(close(&values, &kv(token, 1024, 0.25)), "values at {}", token);
+    }
+}
+
+#[test]
+fn test_gqa_attention_broadcasts_kv_heads_across_query_groups() {
+    let dense = KvCacheManager::new(llama_config(32)).unwrap();
+    let gqa = KvCacheManager::new(llama_config(8)).unwrap();
+    let (dense_seq, gqa_seq) = (dense.allocate_sequence(), gqa.allocate_sequence());
+
+    for token in 0..5 {
+        let kv_heads: Vec<f32> = (0..1024)
+            .map(|i| ((token * 7 + i) % 13) as f32 / 13.0)
+            .collect();
+        // The dense cache stores each KV head once per query head in its group
+        let expanded: Vec<f32> = kv_heads
+            .chunks(128)
+            .flat_map(|head| std::iter::repeat_n(head, 4).flatten().copied())
+            .collect();
+        dense.append_kv(dense_seq, &expanded, &expanded).unwrap();
+        gqa.append_kv(gqa_seq, &kv_heads, &kv_heads).unwrap();
+    }
+
+    let query: Vec<f32> = (0..4096).map(|i| (i % 5) as f32 - 2.0).collect();
+    let (mut dense_scores, mut gqa_scores) = (vec![0.0f32; 5], vec![0.0f32; 5]);
+    dense.attention_scores(dense_seq, &query, &mut dense_scores).unwrap();
+    gqa.attention_scores(gqa_seq, &query, &mut gqa_scores).unwrap();
+
+    for (d, g) in dense_scores.iter().zip(&gqa_scores) {
+        assert!((d - g).abs() <= 1e-3 * d.abs().max(1.0), "{} vs {}", d, g);
+    }
+}
+
+/// Model whose metadata records 32 query heads over 8 KV heads.
+struct GqaModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for GqaModel {
+    fn model_id(&self) -> &str {
+        "gqa-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        _config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        Err(gg_core::engine::InferenceError::ExecutionFailed("metadata only".into()))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn attention_heads(&self) -> Option<gg_core::engine::AttentionHeads> {
+        Some(gg_core::engine::AttentionHeads {
+            heads: 32,
+            kv_heads: 8,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_kv_heads_taken_from_model_metadata() {
+    let config = llama_config(32).with_model_heads(&GqaModel);
+    assert_eq!((config.num_heads, config.num_kv_heads), (32, 8));
+    assert_eq!(config.kv_dim(), 1024);
+    let manager = KvCacheManager::new(config).unwrap();
+    let seq_id = manager.allocate_sequence();
+    manager.append_kv(seq_id, &[0.5; 1024], &[0.5; 1024]).unwrap();
+
+    let uneven = KvCacheConfig {
+        num_kv_heads: 5,
+        ..llama_config(32)
+    };
+    assert!(KvCacheManager::new(uneven).is_err());
+}
@@ -63,6 +63,26 @@ fn limits_reject_exceeds_concurrent() {
     ));
 }
 
+#[test]
+fn limits_set_config_applies_to_next_acquire() {
+    let config = ResourceLimitsConfig {
+        max_memory_per_call: 1000,
+        max_total_memory: 10000,
+        max_concurrent: 1,
+    };
+    let limits = ResourceLimits::new(config.clone());
+    let _held = limits.try_acquire(100).unwrap();
+    assert!(limits.try_acquire(100).is_err());
+
+    limits.set_config(ResourceLimitsConfig {
+        max_concurrent: 2,
+        ..config
+    });
+
+    assert_eq!(limits.config().max_concurrent, 2);
+    assert!(limits.try_acquire(100).is_ok());
+}
+
 #[test]
 fn limits_release_on_drop() {
     let config = ResourceLimitsConfig {
@@ -128,3 +148,41 @@ fn limits_default_config_is_reasonable() {
     assert!(config.max_total_memory >= config.max_memory_per_call);
     assert!(config.max_concurrent >= 1);
 }
+
+/// Write a stub `/proc/meminfo` reporting `available_kb` of MemAvailable.
+#[cfg(target_os = "linux")]
+fn stub_meminfo(name: &str, available_kb: u64) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("gg-core-meminfo-{}-{}", name, std::process::id()));
+    let contents = format!(
+        "MemTotal:       16384000 kB\nMemFree:          100000 kB\nMemAvailable:   {:>8} kB\n",
+        available_kb
+    );
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn memory_floor_rejects_below_available() {
+    use gg_core::memory::MemoryFloorConfig;
+
+    let low = MemoryFloorConfig {
+        min_available_bytes: Some(512 * 1024 * 1024),
+        meminfo_path: stub_meminfo("low", 256 * 1024),
+    };
+    let result = low.check();
+    assert!(matches!(
+        result,
+        Err(InferenceError::MemoryPressure { available, floor })
+            if available == 256 * 1024 * 1024 && floor == 512 * 1024 * 1024
+    ));
+
+    let ample = MemoryFloorConfig {
+        min_available_bytes: Some(512 * 1024 * 1024),
+        meminfo_path: stub_meminfo("ample", 1024 * 1024),
+    };
+    assert!(ample.check().is_ok());
+
+    let _ = std::fs::remove_file(low.meminfo_path);
+    let _ = std::fs::remove_file(ample.meminfo_path);
+}
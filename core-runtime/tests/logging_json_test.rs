@@ -0,0 +1,34 @@
+//! `GG_CORE_LOG_FORMAT=json` produces newline-delimited JSON records.
+//!
+//! Installs the global subscriber, so this binary holds a single test.
+
+use gg_core::telemetry::{init_logging, LogConfig, LogFormat};
+
+#[test]
+fn json_format_writes_parseable_records() {
+    let path = std::env::temp_dir().join(format!("gg-core-log-json-{}.log", std::process::id()));
+
+    let config = LogConfig {
+        format: "json".parse().unwrap(),
+        output_path: Some(path.clone()),
+        ..Default::default()
+    };
+    assert_eq!(config.format, LogFormat::Json);
+    init_logging(&config).unwrap();
+
+    let span = tracing::info_span!("inference", model_id = "phi-3");
+    span.in_scope(|| tracing::info!(tokens = 42, "generation finished"));
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let line = contents.lines().find(|l| l.contains("generation finished")).unwrap();
+    let record: serde_json::Value = serde_json::from_str(line).unwrap();
+
+    assert!(record["timestamp"].is_string());
+    assert_eq!(record["level"], "INFO");
+    assert_eq!(record["target"], "logging_json_test");
+    assert_eq!(record["fields"]["message"], "generation finished");
+    assert_eq!(record["fields"]["tokens"], 42);
+    assert_eq!(record["span"]["name"], "inference");
+    assert_eq!(record["span"]["model_id"], "phi-3");
+}
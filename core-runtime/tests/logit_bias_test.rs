@@ -0,0 +1,89 @@
+//! Per-request logit bias reaches the sampler.
+
+mod common;
+
+use common::infer_with_params;
+use gg_core::engine::InferenceParams;
+
+/// Greedy decoder over fixed logits that favour EOS (token 2); stops at EOS.
+struct EosFavouringModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for EosFavouringModel {
+    fn model_id(&self) -> &str {
+        "eos-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        use gg_core::engine::sampling;
+        const EOS: u32 = 2;
+        let mut text = String::new();
+        let mut finish_reason = gg_core::engine::FinishReason::MaxTokens;
+        for _ in 0..config.max_tokens.unwrap_or(1) {
+            let mut logits = vec![0.0, 1.0, 5.0, 2.0];
+            sampling::apply_logit_bias(&mut logits, &config.logit_bias);
+            let token = sampling::sample_top_k_top_p(&logits, 1, 1.0, 0.0, 0, 0.0).unwrap();
+            if token == EOS {
+                finish_reason = gg_core::engine::FinishReason::Stop;
+                break;
+            }
+            text.push_str(&token.to_string());
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            tokens_generated: text.len() as u32,
+            text,
+            finish_reason,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn logit_bias_ban_on_eos_forces_max_tokens() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "eos-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(EosFavouringModel),
+        )
+        .await;
+
+    let unbiased = infer_with_params(&runtime, "eos-model", InferenceParams::default()).await;
+    assert_eq!(unbiased.tokens_generated, 0, "{:?}", unbiased.error);
+
+    let params = InferenceParams {
+        max_tokens: 5,
+        logit_bias: std::collections::HashMap::from([(2, f32::NEG_INFINITY)]),
+        ..Default::default()
+    };
+    let response = infer_with_params(&runtime, "eos-model", params).await;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.tokens_generated, 5);
+    assert_eq!(response.output, "33333");
+}
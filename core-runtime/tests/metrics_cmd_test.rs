@@ -0,0 +1,55 @@
+//! `metrics --prometheus` and `metrics --json` against an in-process server.
+
+#![cfg(unix)]
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use gg_core::cli::{fetch_metrics, run_metrics, MetricsFormat};
+use gg_core::ipc::server::run_server;
+use gg_core::telemetry::MetricsSnapshot;
+use gg_core::{Runtime, RuntimeConfig};
+use tempfile::TempDir;
+use tokio::sync::watch;
+
+#[tokio::test]
+async fn prometheus_and_json_outputs() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Runtime::new(RuntimeConfig {
+        base_path: dir.path().to_path_buf(),
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let connections = Arc::clone(&runtime.connections);
+    let handler = Arc::new(runtime.ipc_handler);
+
+    let socket = dir.path().join("core.sock").to_string_lossy().into_owned();
+    let (shutdown, rx) = watch::channel(false);
+    tokio::spawn(run_server(socket.clone(), handler, connections, rx));
+    while !Path::new(&socket).exists() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let text = fetch_metrics(&socket, MetricsFormat::Prometheus)
+        .await
+        .unwrap();
+    assert!(!text.is_empty());
+    assert!(text.contains("# TYPE "), "{}", text);
+
+    let json = fetch_metrics(&socket, MetricsFormat::Json).await.unwrap();
+    assert!(!json.is_empty());
+    let snapshot: MetricsSnapshot = serde_json::from_str(&json).unwrap();
+    assert!(!snapshot.gauges.is_empty() || !snapshot.counters.is_empty());
+
+    assert_eq!(run_metrics(&socket, MetricsFormat::Prometheus).await, 0);
+    let _ = shutdown.send(true);
+    assert_eq!(
+        run_metrics(
+            &dir.path().join("absent.sock").to_string_lossy(),
+            MetricsFormat::Json
+        )
+        .await,
+        3
+    );
+}
@@ -1,7 +1,10 @@
 //! Tests for metrics export via IPC.
 
 use gg_core::ipc::{decode_message, encode_message, IpcMessage, MetricsSnapshot};
-use gg_core::telemetry::{HistogramSummary, MetricsStore};
+use gg_core::telemetry::{
+    encode_prometheus, BucketError, HistogramBuckets, HistogramSummary, MetricsStore,
+    LATENCY_HISTOGRAM, QUEUE_WAIT_HISTOGRAM, REQUEST_LATENCY_HISTOGRAM, TTFT_HISTOGRAM,
+};
 
 // ============================================================================
 // MetricsStore Tests
@@ -90,6 +93,32 @@ fn test_histogram_min_max_tracking() {
     assert_eq!(hist.max, 200.0);
 }
 
+#[test]
+fn test_request_latency_percentiles() {
+    let store = MetricsStore::new();
+    assert_eq!(store.snapshot().p99_ms, 0.0);
+
+    // 90 requests at 10ms, 9 at 100ms, 1 at 2000ms
+    for (latency, n) in [(10.0, 90), (100.0, 9), (2000.0, 1)] {
+        for _ in 0..n {
+            store.record_histogram(REQUEST_LATENCY_HISTOGRAM, latency);
+        }
+    }
+
+    // Percentile buckets are ~12% wide
+    let snapshot = store.snapshot();
+    assert!((10.0..=11.3).contains(&snapshot.p50_ms), "{}", snapshot.p50_ms);
+    assert!((10.0..=11.3).contains(&snapshot.p90_ms), "{}", snapshot.p90_ms);
+    assert!((100.0..=113.0).contains(&snapshot.p99_ms), "{}", snapshot.p99_ms);
+
+    let text = encode_prometheus(&snapshot);
+    assert!(text.contains("# TYPE core_request_latency_ms summary"));
+    assert!(text.contains(&format!(
+        "core_request_latency_ms{{quantile=\"0.99\"}} {}",
+        snapshot.p99_ms
+    )));
+}
+
 #[test]
 fn test_empty_snapshot() {
     let store = MetricsStore::new();
@@ -149,6 +178,9 @@ fn test_metrics_response_roundtrip() {
             sum: 500.0,
             min: 1.0,
             max: 50.0,
+            p50_ms: 9.0,
+            p90_ms: 40.0,
+            p99_ms: 50.0,
         },
     );
 
@@ -157,6 +189,9 @@ fn test_metrics_response_roundtrip() {
         gauges,
         histograms,
         bucketed_histograms: std::collections::HashMap::new(),
+        p50_ms: 0.0,
+        p90_ms: 0.0,
+        p99_ms: 0.0,
     };
     let message = IpcMessage::MetricsResponse(snapshot);
 
@@ -173,6 +208,7 @@ fn test_metrics_response_roundtrip() {
             assert_eq!(hist.sum, 500.0);
             assert_eq!(hist.min, 1.0);
             assert_eq!(hist.max, 50.0);
+            assert_eq!(hist.p90_ms, 40.0);
         }
         _ => panic!("Expected MetricsResponse message"),
     }
@@ -185,6 +221,9 @@ fn test_metrics_response_empty_snapshot() {
         gauges: std::collections::HashMap::new(),
         histograms: std::collections::HashMap::new(),
         bucketed_histograms: std::collections::HashMap::new(),
+        p50_ms: 0.0,
+        p90_ms: 0.0,
+        p99_ms: 0.0,
     };
     let message = IpcMessage::MetricsResponse(snapshot);
 
@@ -200,3 +239,72 @@ fn test_metrics_response_empty_snapshot() {
         _ => panic!("Expected MetricsResponse message"),
     }
 }
+
+// ============================================================================
+// Configured Histogram Buckets
+// ============================================================================
+
+async fn metrics_over_ipc(runtime: &gg_core::Runtime, request: IpcMessage) -> IpcMessage {
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&request).unwrap(), None)
+        .await
+        .unwrap();
+    decode_message(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_configured_buckets_bin_samples_in_json_and_prometheus() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        histogram_buckets: HistogramBuckets {
+            latency_ms: vec![100.0, 1000.0, 10000.0],
+            ttft_ms: vec![0.1, 0.5],
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let store = &runtime.metrics_store;
+    for latency in [50.0, 700.0, 800.0, 4000.0, 60000.0] {
+        store.record_bucketed(LATENCY_HISTOGRAM, latency);
+    }
+    store.record_bucketed(TTFT_HISTOGRAM, 0.05);
+
+    let snapshot = match metrics_over_ipc(&runtime, IpcMessage::MetricsRequest).await {
+        IpcMessage::MetricsResponse(snapshot) => snapshot,
+        other => panic!("Expected MetricsResponse, got {:?}", other),
+    };
+    let latency = &snapshot.bucketed_histograms[LATENCY_HISTOGRAM];
+    assert_eq!(latency.boundaries, vec![100.0, 1000.0, 10000.0]);
+    assert_eq!(latency.bucket_counts, vec![1, 2, 1, 1]);
+    assert_eq!(snapshot.bucketed_histograms[TTFT_HISTOGRAM].bucket_counts, vec![1, 0, 0]);
+
+    let text = match metrics_over_ipc(&runtime, IpcMessage::PrometheusMetricsRequest).await {
+        IpcMessage::PrometheusMetricsResponse { text } => text,
+        other => panic!("Expected PrometheusMetricsResponse, got {:?}", other),
+    };
+    assert!(text.contains("# TYPE core_latency_ms histogram"));
+    assert!(text.contains("core_latency_ms_bucket{le=\"100\"} 1"));
+    assert!(text.contains("core_latency_ms_bucket{le=\"1000\"} 3"));
+    assert!(text.contains("core_latency_ms_bucket{le=\"10000\"} 4"));
+    assert!(text.contains("core_latency_ms_bucket{le=\"+Inf\"} 5"));
+    assert!(text.contains("core_ttft_ms_bucket{le=\"0.1\"} 1"));
+}
+
+#[test]
+fn test_invalid_buckets_fall_back_to_defaults() {
+    let buckets = HistogramBuckets {
+        queue_wait_ms: vec![5.0, -1.0],
+        ..Default::default()
+    };
+    assert!(matches!(buckets.validate(), Err(BucketError::NotPositive { .. })));
+
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        histogram_buckets: buckets,
+        ..Default::default()
+    });
+    let snapshot = runtime.metrics_store.snapshot();
+    assert_eq!(
+        snapshot.bucketed_histograms[QUEUE_WAIT_HISTOGRAM].boundaries,
+        HistogramBuckets::default().queue_wait_ms
+    );
+}
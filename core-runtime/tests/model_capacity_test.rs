@@ -0,0 +1,52 @@
+//! A running request holds one of its model's registry capacity slots.
+
+mod common;
+
+use common::{send_inference, HeldModel};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{InferenceRequest, RequestId};
+
+#[tokio::test]
+async fn running_request_counts_against_model_capacity() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let metadata = gg_core::models::ModelMetadata {
+        name: "held-model".into(),
+        size_bytes: 1024,
+    };
+    let handle = runtime.model_registry.register(metadata, 1024).await.unwrap();
+    let model = std::sync::Arc::new(HeldModel {
+        runs: Default::default(),
+        permits: tokio::sync::Semaphore::new(0),
+    });
+    runtime
+        .inference_engine
+        .register_model("held-model".into(), handle, model.clone())
+        .await;
+    let idle = runtime.model_registry.free_capacity(handle).await.unwrap();
+    let request = InferenceRequest {
+        request_id: RequestId(1),
+        model_id: "held-model".into(),
+        prompt: "Summarize the report".into(),
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+
+    let check = async {
+        while model.runs.load(std::sync::atomic::Ordering::SeqCst) == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        let running = runtime.model_registry.free_capacity(handle).await.unwrap();
+        model.permits.add_permits(1);
+        running
+    };
+    let (response, running) = tokio::join!(send_inference(&runtime, request), check);
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(running, idle - 1);
+    assert_eq!(runtime.model_registry.free_capacity(handle).await, Some(idle));
+}
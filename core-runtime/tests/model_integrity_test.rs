@@ -0,0 +1,138 @@
+//! Manifest hash verification when mapping a model.
+
+use std::time::Duration;
+
+use gg_core::models::{
+    LoadError, ModelArchitecture, ModelCapability, ModelLoader, ModelManifest,
+};
+use gg_core::security::audit::{audit_logger, init_audit_logger, AuditConfig};
+use gg_core::security::AuditCategory;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+const MODEL_BYTES: &[u8] = b"GGUF model weights under test";
+
+fn manifest(model_id: &str, sha256: Option<String>) -> ModelManifest {
+    ModelManifest {
+        model_id: model_id.to_string(),
+        name: "Integrity Test".to_string(),
+        version: "1.0.0".to_string(),
+        capabilities: vec![ModelCapability::TextGeneration],
+        sha256,
+        size_bytes: MODEL_BYTES.len() as u64,
+        architecture: ModelArchitecture::Gguf,
+        license: "MIT".to_string(),
+        chat_template: None,
+    }
+}
+
+fn loader_with(file: &str, bytes: &[u8]) -> (TempDir, ModelLoader) {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("models")).unwrap();
+    std::fs::write(dir.path().join("models").join(file), bytes).unwrap();
+    let loader = ModelLoader::new(dir.path().to_path_buf());
+    (dir, loader)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Wait for the spawned audit write for `model_id` and return its outcome.
+async fn audited_outcome(model_id: &str) -> bool {
+    let logger = audit_logger().unwrap();
+    for _ in 0..100 {
+        let events = logger.get_events_by_category(AuditCategory::ModelOperation).await;
+        if let Some(event) = events
+            .iter()
+            .find(|e| e.resource.as_deref() == Some(model_id))
+        {
+            assert_eq!(event.event_type, "model_integrity_check");
+            return event.success;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("no audit event for {model_id}");
+}
+
+#[tokio::test]
+async fn untampered_model_loads_and_is_audited() {
+    init_audit_logger(AuditConfig { log_to_stdout: false, ..Default::default() });
+    let (_dir, loader) = loader_with("intact.gguf", MODEL_BYTES);
+    let path = loader.validate_path("models/intact.gguf").unwrap();
+
+    let mapped = loader
+        .load_verified(&path, &manifest("intact", Some(sha256_hex(MODEL_BYTES))))
+        .unwrap();
+
+    assert_eq!(mapped.as_bytes(), MODEL_BYTES);
+    assert!(audited_outcome("intact").await);
+}
+
+#[tokio::test]
+async fn tampered_model_is_rejected_and_audited() {
+    init_audit_logger(AuditConfig { log_to_stdout: false, ..Default::default() });
+    let mut tampered = MODEL_BYTES.to_vec();
+    tampered[0] ^= 0xff;
+    let (_dir, loader) = loader_with("tampered.gguf", &tampered);
+    let path = loader.validate_path("models/tampered.gguf").unwrap();
+    let expected = sha256_hex(MODEL_BYTES);
+
+    let result = loader.load_verified(&path, &manifest("tampered", Some(expected.clone())));
+
+    match result {
+        Err(LoadError::HashMismatch { expected: e, actual }) => {
+            assert_eq!(e, expected);
+            assert_eq!(actual, sha256_hex(&tampered));
+        }
+        other => panic!("expected HashMismatch, got {:?}", other.err()),
+    }
+    assert!(!audited_outcome("tampered").await);
+}
+
+#[test]
+fn uppercase_manifest_hash_matches() {
+    let (_dir, loader) = loader_with("upper.gguf", MODEL_BYTES);
+    let path = loader.validate_path("models/upper.gguf").unwrap();
+    let expected = sha256_hex(MODEL_BYTES).to_uppercase();
+
+    assert!(loader.load_verified(&path, &manifest("upper", Some(expected))).is_ok());
+}
+
+#[test]
+fn manifest_without_hash_loads_unchecked() {
+    let (_dir, loader) = loader_with("nohash.gguf", MODEL_BYTES);
+    let path = loader.validate_path("models/nohash.gguf").unwrap();
+
+    assert!(loader.load_verified(&path, &manifest("nohash", None)).is_ok());
+}
+
+#[test]
+fn checks_outside_a_runtime_are_audited() {
+    init_audit_logger(AuditConfig { log_to_stdout: false, ..Default::default() });
+    let (_dir, loader) = loader_with("sync.gguf", MODEL_BYTES);
+    let path = loader.validate_path("models/sync.gguf").unwrap();
+
+    loader
+        .load_verified(&path, &manifest("sync", Some(sha256_hex(MODEL_BYTES))))
+        .unwrap();
+
+    let events = futures::executor::block_on(
+        audit_logger().unwrap().get_events_by_category(AuditCategory::ModelOperation),
+    );
+    assert!(events.iter().any(|e| e.resource.as_deref() == Some("sync") && e.success));
+}
+
+#[test]
+fn sidecar_manifest_is_read_beside_the_model() {
+    let (dir, loader) = loader_with("side.gguf", MODEL_BYTES);
+    let path = loader.validate_path("models/side.gguf").unwrap();
+    assert!(loader.read_manifest(&path).unwrap().is_none());
+
+    let json = serde_json::to_string(&manifest("side", Some(sha256_hex(MODEL_BYTES)))).unwrap();
+    std::fs::write(dir.path().join("models").join("side.manifest.json"), json).unwrap();
+
+    let read = loader.read_manifest(&path).unwrap().unwrap();
+    assert_eq!(read.model_id, "side");
+    assert!(loader.verify_integrity(&path, &read).is_ok());
+}
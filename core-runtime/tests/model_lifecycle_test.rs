@@ -0,0 +1,106 @@
+//! Reloading a served model's chat template or tokenizer in place.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::{handshake, send, MockModel, TEST_TOKEN};
+use gg_core::engine::{
+    ByteLevelBpe, ChatMessage, ChatRole, ChatTemplate, Detokenizer, InferenceParams,
+};
+use gg_core::ipc::protocol::{IpcMessage, RequestId};
+use gg_core::ipc::{ChatRequest, SessionToken};
+use gg_core::models::{LifecycleError, ModelHandle, TokenizerSource};
+use gg_core::{Runtime, RuntimeConfig};
+
+fn handle() -> ModelHandle {
+    ModelHandle::new(1)
+}
+
+async fn runtime_serving(model: Arc<MockModel>) -> Runtime {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        default_model: Some("chat".into()),
+        ..Default::default()
+    });
+    runtime.inference_engine.register_model("chat".into(), handle(), model).await;
+    runtime
+}
+
+async fn chat(runtime: &Runtime, session: &SessionToken) -> String {
+    let request = IpcMessage::ChatRequest(ChatRequest {
+        request_id: RequestId(1),
+        model_id: String::new(),
+        messages: vec![
+            ChatMessage { role: ChatRole::System, content: "Be brief.".into() },
+            ChatMessage { role: ChatRole::User, content: "Hi".into() },
+        ],
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    });
+    match send(runtime, request, Some(session)).await {
+        IpcMessage::InferenceResponse(response) => {
+            assert!(response.error.is_none(), "{:?}", response.error);
+            response.output
+        }
+        other => panic!("Expected InferenceResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn reloaded_template_renders_next_chat_without_reloading_weights() {
+    let model = Arc::new(MockModel::new("chat").echoing_prompt());
+    let runtime = runtime_serving(Arc::clone(&model)).await;
+    let session = handshake(&runtime).await;
+    assert!(!chat(&runtime, &session).await.contains("[user]"));
+
+    let template = ChatTemplate::from_jinja(
+        "{% for message in messages %}[{{ message.role }}]{{ message.content }}\n\
+         {% endfor %}[assistant]",
+    )
+    .unwrap();
+    runtime.model_lifecycle.reload_template(handle(), template).await.unwrap();
+
+    assert_eq!(chat(&runtime, &session).await, "[system]Be brief.\n[user]Hi\n[assistant]");
+    // The engine still serves the very same weights under the same handle
+    assert_eq!(Arc::strong_count(&model), 2);
+    assert_eq!(runtime.inference_engine.get_handle("chat").await, Some(handle()));
+}
+
+#[tokio::test]
+async fn reloaded_tokenizer_decodes_next_tokens() {
+    let runtime = runtime_serving(Arc::new(MockModel::new("chat"))).await;
+    let loads = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&loads);
+    let source: Arc<dyn TokenizerSource> = Arc::new(move || {
+        let piece = match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => "broken",
+            _ => "fixed",
+        };
+        Ok(Arc::new(ByteLevelBpe::new(vec![piece.to_string()])) as Arc<dyn Detokenizer>)
+    });
+    runtime.model_lifecycle.attach(handle(), source).await.unwrap();
+    let decode = || runtime.inference_engine.detokenize_bytes("chat", &[0]);
+    assert_eq!(decode().await.unwrap(), b"broken");
+
+    runtime.model_lifecycle.reload_tokenizer(handle()).await.unwrap();
+
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+    assert_eq!(decode().await.unwrap(), b"fixed");
+}
+
+#[tokio::test]
+async fn reload_requires_a_served_model() {
+    let runtime = runtime_serving(Arc::new(MockModel::new("chat"))).await;
+    let missing = ModelHandle::new(9);
+
+    let reloaded = runtime.model_lifecycle.reload_template(missing, ChatTemplate::default());
+    assert!(matches!(reloaded.await, Err(LifecycleError::ModelNotFound(h)) if h == missing));
+    assert!(matches!(
+        runtime.model_lifecycle.reload_tokenizer(handle()).await,
+        Err(LifecycleError::AssetsNotAttached(_))
+    ));
+}
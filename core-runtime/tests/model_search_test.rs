@@ -0,0 +1,155 @@
+//! Stable ordering and pagination of registry searches.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use gg_core::models::{
+    ModelArchitecture, ModelCapability, ModelMetadata, ModelQuery, ModelRegistry,
+    ModelSearchResult, ModelVersion, PersistedModel, VersionHistory,
+};
+use tempfile::TempDir;
+
+fn entry(base: &Path, model_id: &str, architecture: ModelArchitecture) -> PersistedModel {
+    let path = base.join(format!("{model_id}.bin"));
+    std::fs::write(&path, b"weights").unwrap();
+    PersistedModel {
+        model_id: model_id.to_string(),
+        path,
+        version: ModelVersion::new(1, 0, 0),
+        capabilities: vec![ModelCapability::TextGeneration],
+        architecture,
+        auto_load: false,
+        history: VersionHistory::new(),
+        handle_id: 0,
+        size_bytes: 7,
+        memory_bytes: 0,
+        format: "gguf".to_string(),
+    }
+}
+
+/// Registry whose names tie on score in several places, including two
+/// entries with the same name.
+async fn registry(base: &Path) -> ModelRegistry {
+    let registry = ModelRegistry::new();
+    for name in [
+        "phi", "tiny-phi", "phi-3-mini", "phi-2", "llama", "big-phi", "phi-3-mini", "mistral",
+    ] {
+        registry
+            .register_persisted(entry(base, name, ModelArchitecture::Gguf), 0)
+            .await
+            .unwrap();
+    }
+    registry
+        .register_persisted(entry(base, "phi-onnx", ModelArchitecture::Onnx), 0)
+        .await
+        .unwrap();
+    registry
+}
+
+fn ids(results: &[ModelSearchResult]) -> Vec<&str> {
+    results.iter().map(|r| r.model_id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn identical_searches_serialize_identically() {
+    let dir = TempDir::new().unwrap();
+    let registry = registry(dir.path()).await;
+    let query = ModelQuery::builder().name_contains("phi").build();
+
+    let first = serde_json::to_vec(&registry.search(&query).await).unwrap();
+    let second = serde_json::to_vec(&registry.search(&query).await).unwrap();
+    assert_eq!(first, second);
+
+    // A registry rebuilt from a snapshot hashes its entries differently but
+    // keeps handles, so it must give the same bytes too
+    let restored = ModelRegistry::from_state(64, &registry.snapshot().await);
+    let third = serde_json::to_vec(&restored.search(&query).await).unwrap();
+    assert_eq!(first, third);
+}
+
+#[tokio::test]
+async fn results_sorted_by_score_then_name_then_handle() {
+    let dir = TempDir::new().unwrap();
+    let registry = registry(dir.path()).await;
+
+    let results = registry
+        .search(&ModelQuery::builder().name_contains("phi").build())
+        .await;
+
+    assert_eq!(
+        ids(&results),
+        ["phi", "phi-2", "phi-3-mini", "phi-3-mini", "phi-onnx", "big-phi", "tiny-phi"]
+    );
+    assert!(results[2].handle_id < results[3].handle_id);
+    assert_eq!(results[0].score, 3);
+}
+
+#[tokio::test]
+async fn pages_cover_every_result_exactly_once() {
+    let dir = TempDir::new().unwrap();
+    let registry = registry(dir.path()).await;
+    let all = registry.search(&ModelQuery::new()).await;
+    assert_eq!(all.len(), 9);
+
+    let mut paged = Vec::new();
+    for offset in (0..all.len() + 3).step_by(3) {
+        let page = registry
+            .search(&ModelQuery::builder().offset(offset).limit(3).build())
+            .await;
+        assert!(page.len() <= 3);
+        paged.extend(page);
+    }
+
+    assert_eq!(paged, all);
+    let handles: HashSet<u64> = paged.iter().map(|r| r.handle_id).collect();
+    assert_eq!(handles.len(), all.len());
+}
+
+#[tokio::test]
+async fn filters_apply_before_pagination() {
+    let dir = TempDir::new().unwrap();
+    let registry = registry(dir.path()).await;
+
+    let onnx = registry
+        .search(
+            &ModelQuery::builder()
+                .architecture(ModelArchitecture::Onnx)
+                .limit(5)
+                .build(),
+        )
+        .await;
+
+    assert_eq!(ids(&onnx), ["phi-onnx"]);
+    assert!(onnx[0].is_loaded);
+}
+
+#[tokio::test]
+async fn models_without_manifest_entry_are_searched() {
+    let dir = TempDir::new().unwrap();
+    let registry = registry(dir.path()).await;
+    let metadata = ModelMetadata {
+        name: "phi-local".to_string(),
+        size_bytes: 42,
+    };
+    let handle = registry
+        .register_with_format(metadata, 0, "onnx".to_string())
+        .await
+        .unwrap();
+
+    let phi = registry
+        .search(&ModelQuery::builder().name_contains("phi-local").build())
+        .await;
+    assert_eq!(ids(&phi), ["phi-local"]);
+    assert_eq!(phi[0].handle_id, handle.id());
+    assert_eq!(phi[0].size_bytes, 42);
+    assert_eq!(phi[0].version, ModelVersion::new(0, 0, 0));
+
+    let onnx = registry
+        .search(&ModelQuery::builder().architecture(ModelArchitecture::Onnx).build())
+        .await;
+    assert_eq!(ids(&onnx), ["phi-local", "phi-onnx"]);
+    let generators = registry
+        .search(&ModelQuery::builder().capability(ModelCapability::TextGeneration).build())
+        .await;
+    assert!(!ids(&generators).contains(&"phi-local"));
+}
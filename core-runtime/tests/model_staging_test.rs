@@ -0,0 +1,76 @@
+//! Staged model promotion from `temp/` into `models/`.
+
+use gg_core::models::{LoadError, ModelLoader};
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+const MODEL_BYTES: &[u8] = b"GGUF staged model weights";
+
+fn staged_loader() -> (TempDir, ModelLoader) {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("temp")).unwrap();
+    std::fs::create_dir_all(dir.path().join("models")).unwrap();
+    std::fs::write(dir.path().join("temp/download.part"), MODEL_BYTES).unwrap();
+    let loader = ModelLoader::new(dir.path().to_path_buf());
+    (dir, loader)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[test]
+fn promotes_verified_file_into_models() {
+    let (dir, loader) = staged_loader();
+
+    let promoted = loader
+        .stage_and_promote("download.part", "phi.gguf", &sha256_hex(MODEL_BYTES))
+        .unwrap();
+
+    assert!(promoted.as_path().ends_with("models/phi.gguf"));
+    assert_eq!(
+        std::fs::read(dir.path().join("models/phi.gguf")).unwrap(),
+        MODEL_BYTES
+    );
+    assert!(!dir.path().join("temp/download.part").exists());
+}
+
+#[test]
+fn checksum_mismatch_leaves_models_untouched() {
+    let (dir, loader) = staged_loader();
+
+    let result = loader.stage_and_promote("download.part", "phi.gguf", &sha256_hex(b"other"));
+
+    assert!(matches!(result, Err(LoadError::HashMismatch { .. })));
+    assert!(std::fs::read_dir(dir.path().join("models"))
+        .unwrap()
+        .next()
+        .is_none());
+    assert!(dir.path().join("temp/download.part").exists());
+}
+
+#[test]
+fn refuses_promotion_while_load_in_progress() {
+    let (dir, loader) = staged_loader();
+
+    let _loading = loader.begin_load("phi.gguf").unwrap();
+    let result = loader.stage_and_promote("download.part", "phi.gguf", &sha256_hex(MODEL_BYTES));
+
+    assert!(matches!(result, Err(LoadError::LoadInProgress(_))));
+    assert!(!dir.path().join("models/phi.gguf").exists());
+}
+
+#[test]
+fn rejects_paths_outside_staging_and_models() {
+    let (_dir, loader) = staged_loader();
+    let hash = sha256_hex(MODEL_BYTES);
+
+    assert!(matches!(
+        loader.stage_and_promote("download.part", "../escape.gguf", &hash),
+        Err(LoadError::PathNotAllowed(_))
+    ));
+    assert!(matches!(
+        loader.stage_and_promote("../temp/../models", "phi.gguf", &hash),
+        Err(LoadError::PathNotAllowed(_))
+    ));
+}
@@ -0,0 +1,95 @@
+//! Per-model stats over IPC after routing requests through a mock model.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{handshake, infer, send, MockModel, TEST_TOKEN};
+use gg_core::ipc::protocol::IpcMessage;
+use gg_core::ipc::ModelStats;
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+
+const MODEL: &str = "mock-model";
+const INFERENCE_TIME: Duration = Duration::from_millis(50);
+const MEMORY_BYTES: usize = 4096;
+
+async fn runtime_with_mock_model() -> Arc<Runtime> {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        ..Default::default()
+    });
+    let model = MockModel::new(MODEL)
+        .taking(INFERENCE_TIME)
+        .using_memory(MEMORY_BYTES);
+    runtime
+        .inference_engine
+        .register_model(MODEL.into(), ModelHandle::new(1), Arc::new(model))
+        .await;
+    Arc::new(runtime)
+}
+
+async fn stats(runtime: &Runtime, model_id: &str) -> IpcMessage {
+    let message = IpcMessage::ModelStatsRequest {
+        model_id: model_id.into(),
+    };
+    send(runtime, message, None).await
+}
+
+async fn model_stats(runtime: &Runtime) -> ModelStats {
+    match stats(runtime, MODEL).await {
+        IpcMessage::ModelStatsResponse(stats) => stats,
+        other => panic!("Expected ModelStatsResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn stats_count_completed_and_in_flight_requests() {
+    let runtime = runtime_with_mock_model().await;
+    let session = handshake(&runtime).await;
+
+    let before = model_stats(&runtime).await;
+    assert_eq!(before.model_id, MODEL);
+    assert_eq!(before.total_requests, 0);
+    assert_eq!(before.in_flight, 0);
+    assert_eq!(before.memory_bytes, MEMORY_BYTES as u64);
+
+    let in_flight = tokio::spawn({
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        async move { infer(&runtime, &session, MODEL, 1).await }
+    });
+    while runtime.ipc_handler.active_requests().is_empty() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let during = model_stats(&runtime).await;
+    assert_eq!(during.in_flight, 1);
+    assert_eq!(during.total_requests, 0);
+
+    let first = in_flight.await.unwrap();
+    assert!(first.error.is_none(), "{:?}", first.error);
+    let second = infer(&runtime, &session, MODEL, 2).await;
+    assert!(second.error.is_none(), "{:?}", second.error);
+
+    let after = model_stats(&runtime).await;
+    assert_eq!(after.in_flight, 0);
+    assert_eq!(after.total_requests, 2);
+    assert!(
+        after.avg_latency_ms >= INFERENCE_TIME.as_millis() as f64,
+        "{}",
+        after.avg_latency_ms
+    );
+}
+
+#[tokio::test]
+async fn stats_for_unknown_model_is_an_error() {
+    let runtime = runtime_with_mock_model().await;
+    match stats(&runtime, "missing").await {
+        IpcMessage::Error { code, message } => {
+            assert_eq!(code, 404);
+            assert_eq!(message, "Model not loaded: missing");
+        }
+        other => panic!("Expected Error, got {:?}", other),
+    }
+}
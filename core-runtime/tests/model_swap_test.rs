@@ -0,0 +1,174 @@
+//! Hot-swapping a model over IPC while requests keep arriving.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{handshake, infer, send, MockModel, TEST_TOKEN};
+use gg_core::engine::GgufModel;
+use gg_core::ipc::protocol::{encode_message, IpcMessage};
+use gg_core::ipc::SwapModelResponse;
+use gg_core::models::{ModelHandle, ModelMetadata};
+use gg_core::{Runtime, RuntimeConfig};
+use tempfile::TempDir;
+use tokio::sync::Semaphore;
+
+const MODEL: &str = "mock-model";
+const INFERENCE_TIME: Duration = Duration::from_millis(50);
+
+/// Model answering every request with `tag` after `inference_time`.
+fn tagged(tag: &str, inference_time: Duration) -> MockModel {
+    MockModel::new(MODEL).answering(tag, 1).taking(inference_time)
+}
+
+/// Runtime serving `old`, whose swap factory builds a model tagged "new"
+/// from `models/replacement.gguf`.
+async fn runtime_with_old_model(old: MockModel) -> (Arc<Runtime>, ModelHandle, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("models").join("replacement.gguf");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, b"GGUF\0\0\0\0").unwrap();
+
+    let mut runtime = Runtime::new(RuntimeConfig {
+        base_path: dir.path().to_path_buf(),
+        auth_token: TEST_TOKEN.into(),
+        ..Default::default()
+    });
+    runtime.ipc_handler = runtime.ipc_handler.with_model_factory(Arc::new(|_, _| {
+        Ok(Arc::new(tagged("new", INFERENCE_TIME)) as Arc<dyn GgufModel>)
+    }));
+
+    let metadata = ModelMetadata { name: MODEL.into(), size_bytes: 8 };
+    let handle = runtime.model_registry.register(metadata, 0).await.unwrap();
+    runtime
+        .inference_engine
+        .register_model(MODEL.into(), handle, Arc::new(old))
+        .await;
+    (Arc::new(runtime), handle, dir)
+}
+
+fn swap(model_id: &str) -> IpcMessage {
+    IpcMessage::SwapModel {
+        model_id: model_id.into(),
+        new_path: "models/replacement.gguf".into(),
+    }
+}
+
+#[tokio::test]
+async fn requests_during_swap_all_succeed_on_one_handle() {
+    let old = tagged("old", INFERENCE_TIME);
+    let (runtime, old_handle, _dir) = runtime_with_old_model(old).await;
+    let session = handshake(&runtime).await;
+
+    let spawn_request = |request_id| {
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        tokio::spawn(async move { infer(&runtime, &session, MODEL, request_id).await })
+    };
+
+    // The swap has to wait for this one to drain
+    let mut requests = vec![spawn_request(1)];
+    while runtime.ipc_handler.active_requests().is_empty() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let swapping = tokio::spawn({
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        async move { send(&runtime, swap(MODEL), Some(&session)).await }
+    });
+    for request_id in 2..=8 {
+        requests.push(spawn_request(request_id));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let swapped: SwapModelResponse = match swapping.await.unwrap() {
+        IpcMessage::SwapModelResponse(response) => response,
+        other => panic!("Expected SwapModelResponse, got {:?}", other),
+    };
+    assert_eq!(swapped.model_id, MODEL);
+    assert_eq!(swapped.old_handle_id, old_handle.id());
+    assert_ne!(swapped.new_handle_id, old_handle.id());
+
+    let mut served = Vec::new();
+    for request in requests {
+        let response = request.await.unwrap();
+        assert!(response.error.is_none(), "{:?}", response.error);
+        served.push(response.output);
+    }
+    assert!(
+        served.iter().all(|tag| tag == "old" || tag == "new"),
+        "{:?}",
+        served
+    );
+    assert_eq!(served[0], "old");
+
+    // Only the new handle is left, and it serves everything from now on
+    let new_handle = ModelHandle::new(swapped.new_handle_id);
+    assert_eq!(runtime.inference_engine.get_handle(MODEL).await, Some(new_handle));
+    assert!(runtime.model_registry.contains(new_handle).await);
+    assert!(!runtime.model_registry.contains(old_handle).await);
+    assert_eq!(infer(&runtime, &session, MODEL, 9).await.output, "new");
+}
+
+#[tokio::test]
+async fn requests_during_drain_land_on_new_model() {
+    // The old model holds its request until the gate opens
+    let gate = Arc::new(Semaphore::new(0));
+    let old = tagged("old", Duration::ZERO).gated_by(Arc::clone(&gate));
+    let (runtime, old_handle, _dir) = runtime_with_old_model(old).await;
+    let session = handshake(&runtime).await;
+
+    let slow = tokio::spawn({
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        async move { infer(&runtime, &session, MODEL, 1).await }
+    });
+    while runtime.ipc_handler.active_requests().is_empty() {
+        tokio::task::yield_now().await;
+    }
+    let swapping = tokio::spawn({
+        let runtime = Arc::clone(&runtime);
+        let session = session.clone();
+        async move { send(&runtime, swap(MODEL), Some(&session)).await }
+    });
+    // The engine is repointed without waiting for the held request
+    let repointed = async {
+        while runtime.inference_engine.get_handle(MODEL).await == Some(old_handle) {
+            tokio::task::yield_now().await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), repointed)
+        .await
+        .expect("swap blocked on the in-flight request");
+
+    // The old model is still draining the held request
+    assert_eq!(infer(&runtime, &session, MODEL, 2).await.output, "new");
+    assert!(!slow.is_finished());
+    assert!(!swapping.is_finished());
+
+    gate.add_permits(1);
+    assert!(matches!(
+        swapping.await.unwrap(),
+        IpcMessage::SwapModelResponse(_)
+    ));
+    assert_eq!(slow.await.unwrap().output, "old");
+    assert!(!runtime.model_registry.contains(old_handle).await);
+}
+
+#[tokio::test]
+async fn swap_requires_auth_and_known_model() {
+    let (runtime, _, _dir) = runtime_with_old_model(tagged("old", INFERENCE_TIME)).await;
+    let result = runtime
+        .ipc_handler
+        .process(&encode_message(&swap(MODEL)).unwrap(), None)
+        .await;
+    assert!(result.is_err());
+
+    let session = handshake(&runtime).await;
+    assert!(matches!(
+        send(&runtime, swap("missing"), Some(&session)).await,
+        IpcMessage::Error { code: 404, .. }
+    ));
+    assert_eq!(infer(&runtime, &session, MODEL, 1).await.output, "old");
+}
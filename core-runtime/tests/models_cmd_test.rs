@@ -0,0 +1,219 @@
+//! `models load`, `models info` and `models unload` against an in-process server.
+
+#![cfg(unix)]
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use gg_core::cli::{run_info, run_load, run_unload, CliError, CliIpcClient};
+use gg_core::engine::{
+    ChatMessage, ChatRole, GgufModel, InferenceCapability, InferenceConfig, InferenceEngine,
+    InferenceError, InferenceInput, InferenceOutput,
+};
+use gg_core::ipc::server::run_server;
+use gg_core::models::ModelRegistry;
+use gg_core::{Runtime, RuntimeConfig};
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+use tokio::sync::watch;
+
+const TOKEN: &str = "test-token";
+
+/// Weights built by the test server's model factory.
+struct LoadedModel(String);
+
+#[async_trait::async_trait]
+impl GgufModel for LoadedModel {
+    fn model_id(&self) -> &str {
+        &self.0
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        16
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        Err(InferenceError::ModelError("not used".into()))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+struct TestServer {
+    socket: String,
+    registry: Arc<ModelRegistry>,
+    engine: Arc<InferenceEngine>,
+    shutdown: watch::Sender<bool>,
+    dir: TempDir,
+}
+
+impl TestServer {
+    /// Serve a runtime whose `models/` holds `<name>.gguf` for each name.
+    /// Loading `broken` fails to build weights.
+    async fn start(models: &[&str]) -> Self {
+        let dir = TempDir::new().unwrap();
+        for name in models {
+            let path = dir.path().join("models").join(format!("{name}.gguf"));
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"GGUF\0\0\0\0").unwrap();
+        }
+        let mut runtime = Runtime::new(RuntimeConfig {
+            base_path: dir.path().to_path_buf(),
+            auth_token: TOKEN.into(),
+            ..Default::default()
+        });
+        let factory = |_: &Path, model_id: &str| {
+            if model_id == "broken" {
+                return Err(InferenceError::ModelError("corrupt weights".into()));
+            }
+            Ok(Arc::new(LoadedModel(model_id.to_string())) as Arc<dyn GgufModel>)
+        };
+        runtime.ipc_handler = runtime.ipc_handler.with_model_factory(Arc::new(factory));
+        let registry = Arc::clone(&runtime.model_registry);
+        let engine = Arc::clone(&runtime.inference_engine);
+        let connections = Arc::clone(&runtime.connections);
+        let handler = Arc::new(runtime.ipc_handler);
+
+        let socket = dir.path().join("core.sock").to_string_lossy().into_owned();
+        let (shutdown, rx) = watch::channel(false);
+        tokio::spawn(run_server(socket.clone(), handler, connections, rx));
+        while !Path::new(&socket).exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        Self { socket, registry, engine, shutdown, dir }
+    }
+
+    fn client(&self) -> CliIpcClient {
+        CliIpcClient::new(self.socket.clone())
+    }
+
+    /// Ship `<name>.gguf` with a manifest claiming `sha256`.
+    fn write_manifest(&self, name: &str, sha256: &str, chat_template: Option<&str>) {
+        let manifest = serde_json::json!({
+            "model_id": name,
+            "name": name,
+            "version": "1.0.0",
+            "capabilities": ["text_generation"],
+            "sha256": sha256,
+            "size_bytes": 8,
+            "architecture": "gguf",
+            "license": "MIT",
+            "chat_template": chat_template,
+        });
+        let path = self.dir.path().join("models").join(format!("{name}.manifest.json"));
+        std::fs::write(path, manifest.to_string()).unwrap();
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
+#[tokio::test]
+async fn load_info_unload_round_trip() {
+    let server = TestServer::start(&["tiny"]).await;
+
+    assert_eq!(run_load(&server.socket, TOKEN, "tiny").await, 0);
+    assert_eq!(server.registry.count().await, 1);
+    assert!(server.engine.get_handle("tiny").await.is_some());
+
+    assert_eq!(run_info(&server.socket, "tiny").await, 0);
+    let info = server.client().model_info("tiny").await.unwrap();
+    assert_eq!(info.name, "tiny");
+    assert_eq!(info.format, "gguf");
+    assert_eq!(info.size_bytes, 8);
+    assert_eq!(info.state, "ready");
+
+    assert_eq!(run_unload(&server.socket, TOKEN, "tiny").await, 0);
+    assert_eq!(server.registry.count().await, 0);
+    assert!(server.engine.get_handle("tiny").await.is_none());
+    assert_eq!(run_info(&server.socket, "tiny").await, 1);
+}
+
+#[tokio::test]
+async fn failed_weight_load_registers_nothing() {
+    let server = TestServer::start(&["broken"]).await;
+
+    let error = server.client().load_model(TOKEN, "broken").await.unwrap_err();
+    assert!(error.to_string().contains("corrupt weights"), "{}", error);
+    assert_eq!(server.registry.count().await, 0);
+    assert!(server.engine.get_handle("broken").await.is_none());
+}
+
+#[tokio::test]
+async fn unload_of_unknown_model_fails_readably() {
+    let server = TestServer::start(&[]).await;
+
+    assert_eq!(run_unload(&server.socket, TOKEN, "ghost").await, 1);
+    match server.client().unload_model(TOKEN, "ghost").await {
+        Err(CliError::Protocol(message)) => assert_eq!(message, "Model not loaded: ghost"),
+        other => panic!("Expected a protocol error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn load_rejects_missing_files_duplicates_and_bad_tokens() {
+    let server = TestServer::start(&["tiny"]).await;
+    let client = server.client();
+
+    let missing = client.load_model(TOKEN, "absent").await.unwrap_err();
+    assert!(missing.to_string().contains("not found"), "{}", missing);
+
+    let loaded = client.load_model(TOKEN, "tiny").await.unwrap();
+    let duplicate = client.load_model(TOKEN, "tiny").await.unwrap_err();
+    assert!(duplicate.to_string().contains("already loaded"), "{}", duplicate);
+    assert_eq!(server.registry.count().await, 1);
+
+    assert_eq!(run_unload(&server.socket, "wrong-token", "tiny").await, 1);
+    let info = client.model_info("tiny").await.unwrap();
+    assert_eq!(info.handle_id, loaded.handle_id);
+    assert_eq!(info.memory_bytes, 16);
+}
+
+#[tokio::test]
+async fn load_checks_the_file_against_its_manifest_hash() {
+    let server = TestServer::start(&["intact", "tampered"]).await;
+    let client = server.client();
+    server.write_manifest("intact", &hex::encode(Sha256::digest(b"GGUF\0\0\0\0")), None);
+    let forged = hex::encode(Sha256::digest(b"original weights"));
+    server.write_manifest("tampered", &forged, None);
+
+    client.load_model(TOKEN, "intact").await.unwrap();
+    let refused = client.load_model(TOKEN, "tampered").await.unwrap_err();
+
+    assert!(refused.to_string().contains("Hash mismatch"), "{}", refused);
+    assert!(server.engine.get_handle("tampered").await.is_none());
+    assert_eq!(server.registry.count().await, 1);
+}
+
+#[tokio::test]
+async fn load_applies_the_manifest_chat_template() {
+    let server = TestServer::start(&["chatml"]).await;
+    let template = "{% for message in messages %}<|im_start|>{{ message.role }}\n\
+        {{ message.content }}<|im_end|>\n{% endfor %}<|im_start|>assistant\n";
+    let sha256 = hex::encode(Sha256::digest(b"GGUF\0\0\0\0"));
+    server.write_manifest("chatml", &sha256, Some(template));
+
+    server.client().load_model(TOKEN, "chatml").await.unwrap();
+
+    let messages = [ChatMessage { role: ChatRole::User, content: "Hi".into() }];
+    let prompt = server.engine.render_chat("chatml", &messages).await.unwrap();
+    assert_eq!(prompt, "<|im_start|>user\nHi<|im_end|>\n<|im_start|>assistant\n");
+}
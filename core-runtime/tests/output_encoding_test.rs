@@ -0,0 +1,38 @@
+//! `OutputEncoding::Bytes` returns model output that is not valid UTF-8
+//! exactly; the default encoding replaces it lossily.
+
+mod common;
+
+use common::{infer_once, infer_with_params, InvalidUtf8Model, INVALID_UTF8};
+use gg_core::engine::{InferenceParams, OutputEncoding};
+
+#[tokio::test]
+async fn bytes_encoding_returns_exact_model_output() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "invalid-utf8-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(InvalidUtf8Model),
+        )
+        .await;
+
+    let bytes_params = InferenceParams {
+        output_encoding: OutputEncoding::Bytes,
+        ..Default::default()
+    };
+    let response = infer_with_params(&runtime, "invalid-utf8-model", bytes_params).await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert!(response.output.is_empty());
+    let decoded = response.decode_output_bytes().expect("output_bytes set").unwrap();
+    assert_eq!(decoded, INVALID_UTF8);
+
+    let response = infer_once(&runtime, "invalid-utf8-model").await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert!(response.output_bytes.is_none());
+    assert_eq!(response.output, "f\u{FFFD}\u{FFFD}o");
+}
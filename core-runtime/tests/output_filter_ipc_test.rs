@@ -0,0 +1,58 @@
+//! Output filtering of inference responses served over IPC.
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{handshake, MockModel, TEST_TOKEN};
+use gg_core::engine::FilterConfig;
+use gg_core::ipc::protocol::InferenceResponse;
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+
+const MODEL: &str = "fixed-model";
+
+async fn runtime(output_filter: FilterConfig) -> Runtime {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        output_filter,
+        ..Default::default()
+    });
+    let model = MockModel::new(MODEL).answering("the secret plan is ready", 5);
+    runtime
+        .inference_engine
+        .register_model(MODEL.into(), ModelHandle::new(1), Arc::new(model))
+        .await;
+    runtime
+}
+
+async fn infer(runtime: &Runtime) -> InferenceResponse {
+    let session = handshake(runtime).await;
+    common::infer(runtime, &session, MODEL, 1).await
+}
+
+#[tokio::test]
+async fn unfiltered_output_is_returned_unchanged() {
+    let response = infer(&runtime(FilterConfig::default()).await).await;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.output, "the secret plan is ready");
+    assert!(response.finished);
+}
+
+#[tokio::test]
+async fn blocklisted_output_is_replaced_and_cut_to_the_byte_limit() {
+    let runtime = runtime(FilterConfig {
+        blocklist: vec!["secret".into()],
+        max_output_bytes: Some(16),
+        replacement: "***".into(),
+        ..Default::default()
+    })
+    .await;
+
+    let response = infer(&runtime).await;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.output, "the *** plan is ");
+    assert!(!response.finished, "a cut generation is not finished");
+}
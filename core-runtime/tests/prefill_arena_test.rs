@@ -0,0 +1,131 @@
+//! Arena-backed prefill scratch.
+//!
+//! Counts heap allocations on the test thread to show a warm arena pool
+//! serves repeated prefills without allocating scratch.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+
+use gg_core::engine::prefill::{PrefillConfig, PrefillExecutor};
+use gg_core::memory::paged::PageTable;
+use gg_core::memory::ArenaPool;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    // try_with: the slot may be gone while the thread shuts down
+    let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Heap allocations made on this thread while running `f`.
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+const HIDDEN_DIM: usize = 64;
+
+fn executor(use_arena: bool, pool: &Arc<ArenaPool>) -> PrefillExecutor {
+    PrefillExecutor::new(PrefillConfig {
+        chunk_size: 64,
+        hidden_dim: HIDDEN_DIM,
+        vocab_size: Some(1000),
+        use_arena,
+    })
+    .with_arena_pool(Arc::clone(pool))
+}
+
+#[test]
+fn warm_arena_prefills_without_scratch_allocations() {
+    let pool = Arc::new(ArenaPool::new(1 << 20, 1));
+    let executor = executor(true, &pool);
+    let prompt: Vec<u32> = (0..256).collect();
+    // Reusing the page table keeps KV pages out of the count
+    let mut page_table = PageTable::new(HIDDEN_DIM, 32);
+
+    // Warm up: creates the arena and the KV pages
+    executor.execute(&prompt, &mut page_table).unwrap();
+    assert_eq!(pool.available(), 1);
+
+    let allocations = allocations_during(|| {
+        for _ in 0..1_000 {
+            let result = executor.execute(&prompt, &mut page_table).unwrap();
+            assert_eq!(result.kv_len, 256);
+        }
+    });
+    assert_eq!(allocations, 0);
+    // The one arena went back to the pool after every request
+    assert_eq!(pool.available(), 1);
+}
+
+#[test]
+fn heap_scratch_allocates_per_request_when_arena_disabled() {
+    let pool = Arc::new(ArenaPool::new(1 << 20, 1));
+    let executor = executor(false, &pool);
+    let prompt: Vec<u32> = (0..256).collect();
+    let mut page_table = PageTable::new(HIDDEN_DIM, 32);
+    executor.execute(&prompt, &mut page_table).unwrap();
+
+    let allocations = allocations_during(|| {
+        for _ in 0..100 {
+            executor.execute(&prompt, &mut page_table).unwrap();
+        }
+    });
+    assert!(allocations >= 100, "{allocations} allocations");
+    assert_eq!(pool.available(), 0, "pool untouched without use_arena");
+}
+
+#[test]
+fn undersized_arena_falls_back_to_heap() {
+    let pool = Arc::new(ArenaPool::new(64, 1));
+    let executor = executor(true, &pool);
+    let mut page_table = PageTable::new(HIDDEN_DIM, 32);
+
+    let result = executor.execute(&[1, 2, 3], &mut page_table).unwrap();
+    assert_eq!(result.kv_len, 3);
+    assert!(page_table.get(2).is_some());
+    assert_eq!(pool.available(), 1);
+}
+
+#[test]
+fn leased_arena_returns_to_pool_when_user_panics() {
+    let pool = ArenaPool::new(1024, 1);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _arena = pool.lease();
+        panic!("scratch user failed");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(pool.available(), 1);
+}
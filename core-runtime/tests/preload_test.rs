@@ -11,10 +11,11 @@ fn test_manifest() -> ModelManifest {
         name: "Test Model".to_string(),
         version: "1.0.0".to_string(),
         capabilities: vec![ModelCapability::TextGeneration],
-        sha256: "a".repeat(64),
+        sha256: Some("a".repeat(64)),
         size_bytes: 1024,
         architecture: ModelArchitecture::Gguf,
         license: "MIT".to_string(),
+        chat_template: None,
     }
 }
 
@@ -83,8 +84,76 @@ async fn test_preload_invalid_manifest_sha256() {
     let preloader = ModelPreloader::new(registry.clone());
 
     let mut manifest = test_manifest();
-    manifest.sha256 = "invalid".to_string();
+    manifest.sha256 = Some("invalid".to_string());
 
     let result = preloader.preload(manifest).await;
     assert!(matches!(result, Err(PreloadError::ManifestInvalid(_))));
 }
+
+#[tokio::test]
+async fn test_preload_from_manifest_bounds_concurrent_loads() {
+    use gg_core::models::PreloadConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    let registry = Arc::new(ModelRegistry::new());
+    let preloader = ModelPreloader::new(registry.clone())
+        .with_config(PreloadConfig { max_concurrent_loads: 2 });
+
+    let manifests: Vec<_> = (0..6)
+        .map(|i| ModelManifest {
+            model_id: format!("model-{i}"),
+            ..test_manifest()
+        })
+        .collect();
+
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (active_in, peak_in) = (active.clone(), peak.clone());
+    let results = preloader
+        .preload_from_manifest(manifests, move |_manifest| {
+            let active = active_in.clone();
+            let peak = peak_in.clone();
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+        .await;
+
+    assert_eq!(results.len(), 6);
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(registry.count().await, 6);
+
+    let progress = preloader.progress();
+    assert_eq!((progress.total, progress.completed, progress.failed), (6, 6, 0));
+    assert_eq!(progress.in_flight, 0);
+    assert!(progress.is_done());
+}
+
+#[tokio::test]
+async fn test_preload_from_manifest_reports_failures_in_order() {
+    let registry = Arc::new(ModelRegistry::new());
+    let preloader = ModelPreloader::new(registry.clone());
+
+    let mut bad = test_manifest();
+    bad.model_id = "broken".to_string();
+    let manifests = vec![test_manifest(), bad];
+
+    let results = preloader
+        .preload_from_manifest(manifests, |manifest| async move {
+            if manifest.model_id == "broken" {
+                return Err(PreloadError::LoadFailed("corrupt weights".into()));
+            }
+            Ok(())
+        })
+        .await;
+
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(PreloadError::LoadFailed(_))));
+    assert_eq!(preloader.progress().failed, 1);
+}
@@ -1,5 +1,7 @@
 //! Tests for LRU prompt cache.
 
+use gg_core::engine::prefill::{PrefillConfig, PrefillExecutor};
+use gg_core::memory::paged::PageTable;
 use gg_core::memory::prompt_cache::PromptCache;
 
 #[test]
@@ -131,3 +133,63 @@ fn cache_memory_bytes_tracking() {
 
     assert_eq!(cache.memory_bytes(), 300);
 }
+
+#[test]
+fn longest_prefix_match_verifies_tokens() {
+    let mut cache = PromptCache::new(10);
+    cache.insert(&[1, 2], vec![0; 8], 2);
+    cache.insert(&[1, 2, 3, 4], vec![0; 16], 4);
+
+    let (len, entry) = cache.longest_prefix_match(&[1, 2, 3, 9]).unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(entry.seq_len(), 2);
+    assert!(cache.longest_prefix_match(&[2, 1]).is_none());
+    assert_ne!(PromptCache::rolling_hash(&[0]), PromptCache::rolling_hash(&[]));
+}
+
+fn chunked_executor() -> PrefillExecutor {
+    PrefillExecutor::new(PrefillConfig {
+        chunk_size: 10,
+        hidden_dim: 8,
+        vocab_size: Some(1000),
+        use_arena: false,
+    })
+}
+
+#[test]
+fn repeated_prefix_only_prefills_novel_suffix() {
+    let executor = chunked_executor();
+    let mut cache = PromptCache::new(4);
+    let system_prompt: Vec<u32> = (0..500).map(|i| i % 1000).collect();
+
+    let first = executor
+        .execute_with_cache(&system_prompt, &mut PageTable::new(8, 64), &mut cache)
+        .unwrap();
+    assert_eq!(first.reused_tokens, 0);
+    assert_eq!(first.chunks_processed, 50);
+
+    let mut turn: Vec<u32> = system_prompt.clone();
+    turn.extend(900..920);
+    let mut page_table = PageTable::new(8, 64);
+    let second = executor.execute_with_cache(&turn, &mut page_table, &mut cache).unwrap();
+    assert_eq!(second.reused_tokens, 500);
+    assert_eq!(second.chunks_processed, 2, "only the 20 new tokens are prefilled");
+    assert_eq!(second.kv_len, 520);
+    assert!(page_table.get(0).is_some() && page_table.get(519).is_some());
+}
+
+#[test]
+fn fully_cached_prompt_still_prefills_last_token() {
+    let executor = chunked_executor();
+    let mut cache = PromptCache::new(4);
+    let prompt = [5u32, 6, 7, 8];
+
+    executor
+        .execute_with_cache(&prompt, &mut PageTable::new(8, 4), &mut cache)
+        .unwrap();
+    let again = executor
+        .execute_with_cache(&prompt, &mut PageTable::new(8, 4), &mut cache)
+        .unwrap();
+    assert_eq!(again.reused_tokens, 3);
+    assert_eq!(again.chunks_processed, 1);
+}
@@ -1,9 +1,16 @@
 //! Tests for protocol version negotiation.
 
 use gg_core::ipc::{
-    decode_message, encode_message, IpcMessage, ProtocolVersion,
+    decode_message, encode_message, HandlerError, IpcMessage, ProtocolError, ProtocolVersion,
 };
 
+fn runtime() -> gg_core::Runtime {
+    gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "secret123".into(),
+        ..Default::default()
+    })
+}
+
 #[test]
 fn handshake_v1_default_when_not_specified() {
     // Legacy client sends handshake without protocol_version
@@ -11,7 +18,7 @@ fn handshake_v1_default_when_not_specified() {
     let message = decode_message(legacy_json.as_bytes()).unwrap();
 
     match message {
-        IpcMessage::Handshake { token, protocol_version } => {
+        IpcMessage::Handshake { token, protocol_version, .. } => {
             assert_eq!(token, "secret123");
             assert_eq!(protocol_version, None);
         }
@@ -24,13 +31,15 @@ fn handshake_v1_explicit() {
     let message = IpcMessage::Handshake {
         token: "secret123".to_string(),
         protocol_version: Some(ProtocolVersion::V1),
+        compression: None,
+        strict_version: false,
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::Handshake { token, protocol_version } => {
+        IpcMessage::Handshake { token, protocol_version, .. } => {
             assert_eq!(token, "secret123");
             assert_eq!(protocol_version, Some(ProtocolVersion::V1));
         }
@@ -43,13 +52,15 @@ fn handshake_v2_request() {
     let message = IpcMessage::Handshake {
         token: "secret123".to_string(),
         protocol_version: Some(ProtocolVersion::V2),
+        compression: None,
+        strict_version: false,
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::Handshake { token, protocol_version } => {
+        IpcMessage::Handshake { token, protocol_version, .. } => {
             assert_eq!(token, "secret123");
             assert_eq!(protocol_version, Some(ProtocolVersion::V2));
         }
@@ -62,13 +73,15 @@ fn handshake_ack_includes_negotiated_version() {
     let message = IpcMessage::HandshakeAck {
         session_id: "session-abc".to_string(),
         protocol_version: ProtocolVersion::V1,
+        compression: Default::default(),
+        downgrade_reason: None,
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-abc");
             assert_eq!(protocol_version, ProtocolVersion::V1);
         }
@@ -81,13 +94,15 @@ fn handshake_ack_v2_negotiated() {
     let message = IpcMessage::HandshakeAck {
         session_id: "session-xyz".to_string(),
         protocol_version: ProtocolVersion::V2,
+        compression: Default::default(),
+        downgrade_reason: None,
     };
 
     let encoded = encode_message(&message).unwrap();
     let decoded = decode_message(&encoded).unwrap();
 
     match decoded {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-xyz");
             assert_eq!(protocol_version, ProtocolVersion::V2);
         }
@@ -102,7 +117,7 @@ fn legacy_handshake_ack_defaults_to_v1() {
     let message = decode_message(legacy_json.as_bytes()).unwrap();
 
     match message {
-        IpcMessage::HandshakeAck { session_id, protocol_version } => {
+        IpcMessage::HandshakeAck { session_id, protocol_version, .. } => {
             assert_eq!(session_id, "session-old");
             assert_eq!(protocol_version, ProtocolVersion::V1); // Default
         }
@@ -114,3 +129,79 @@ fn legacy_handshake_ack_defaults_to_v1() {
 fn protocol_version_default_is_v1() {
     assert_eq!(ProtocolVersion::default(), ProtocolVersion::V1);
 }
+
+#[tokio::test]
+async fn unknown_version_is_downgraded_with_reason() {
+    let runtime = runtime();
+    let handshake = r#"{"type":"handshake","token":"secret123","protocol_version":"V9"}"#;
+    let (bytes, session) = runtime
+        .ipc_handler
+        .process(handshake.as_bytes(), None)
+        .await
+        .unwrap();
+    assert!(session.is_some());
+
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::HandshakeAck {
+            protocol_version,
+            downgrade_reason,
+            ..
+        } => {
+            assert_eq!(protocol_version, ProtocolVersion::V1);
+            let reason = downgrade_reason.expect("downgrade should carry a reason");
+            assert!(reason.contains("not supported"), "{}", reason);
+        }
+        other => panic!("Expected HandshakeAck, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn supported_version_has_no_downgrade_reason() {
+    let runtime = runtime();
+    let handshake =
+        r#"{"type":"handshake","token":"secret123","protocol_version":"V2","strict_version":true}"#;
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(handshake.as_bytes(), None)
+        .await
+        .unwrap();
+
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::HandshakeAck {
+            protocol_version,
+            downgrade_reason,
+            ..
+        } => {
+            assert_eq!(protocol_version, ProtocolVersion::V2);
+            assert!(downgrade_reason.is_none());
+        }
+        other => panic!("Expected HandshakeAck, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn strict_client_rejects_bogus_version() {
+    let runtime = runtime();
+    let handshake =
+        r#"{"type":"handshake","token":"secret123","protocol_version":"V9","strict_version":true}"#;
+    let err = runtime
+        .ipc_handler
+        .process(handshake.as_bytes(), None)
+        .await
+        .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("Unsupported protocol version"), "{}", message);
+    assert!(message.contains("V1 to V2"), "{}", message);
+    match err {
+        HandlerError::Protocol(ProtocolError::UnsupportedProtocolVersion {
+            requested,
+            min,
+            max,
+        }) => {
+            assert_eq!(requested, ProtocolVersion::Unknown);
+            assert_eq!((min, max), (ProtocolVersion::V1, ProtocolVersion::V2));
+        }
+        other => panic!("Expected UnsupportedProtocolVersion, got {:?}", other),
+    }
+}
@@ -0,0 +1,211 @@
+//! Readiness and model pings: loaded models must answer a real step, and
+//! startup gates hold traffic until preload and warmup finish.
+
+mod common;
+
+use common::{infer_once, infer_with_params, is_ready, ping, EchoModel, FailingModel, HeldModel};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{decode_message, encode_message, IpcMessage};
+
+#[tokio::test]
+async fn ping_model_succeeds_for_registered_and_fails_for_unknown() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    runtime
+        .inference_engine
+        .register_model(
+            "echo-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(EchoModel),
+        )
+        .await;
+
+    let response = ping(&runtime, "echo-model").await;
+    assert!(response.ok);
+    assert!(response.error.is_none());
+
+    let response = ping(&runtime, "missing-model").await;
+    assert!(!response.ok);
+    assert!(response.error.unwrap().contains("missing-model"));
+}
+
+#[tokio::test]
+async fn readiness_requires_loaded_models_to_ping() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    let metadata = gg_core::models::ModelMetadata {
+        name: "echo-model".into(),
+        size_bytes: 1024,
+    };
+    let handle = runtime.model_registry.register(metadata, 1024).await.unwrap();
+    runtime
+        .inference_engine
+        .register_model("echo-model".into(), handle, std::sync::Arc::new(EchoModel))
+        .await;
+    assert!(is_ready(&runtime).await);
+
+    runtime
+        .inference_engine
+        .register_model(
+            "failing-model".into(),
+            gg_core::models::ModelHandle::new(99),
+            std::sync::Arc::new(FailingModel(|| {
+                gg_core::engine::InferenceError::ModelError("weights corrupt".into())
+            })),
+        )
+        .await;
+    assert!(!is_ready(&runtime).await);
+}
+
+#[tokio::test]
+async fn repeated_probes_step_the_model_once() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig::default());
+    let model = std::sync::Arc::new(HeldModel {
+        runs: Default::default(),
+        permits: tokio::sync::Semaphore::new(10),
+    });
+    let metadata = gg_core::models::ModelMetadata {
+        name: "held-model".into(),
+        size_bytes: 1024,
+    };
+    let handle = runtime.model_registry.register(metadata, 1024).await.unwrap();
+    runtime
+        .inference_engine
+        .register_model("held-model".into(), handle, model.clone())
+        .await;
+
+    for _ in 0..3 {
+        assert!(is_ready(&runtime).await);
+        assert!(ping(&runtime, "held-model").await.ok);
+    }
+    assert_eq!(model.runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn failing_traffic_ages_last_success_and_flips_readiness() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        health: gg_core::health::HealthConfig {
+            max_success_age: Some(std::time::Duration::ZERO),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "failing-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(FailingModel(|| {
+                gg_core::engine::InferenceError::ModelError("weights corrupt".into())
+            })),
+        )
+        .await;
+
+    infer_once(&runtime, "failing-model").await;
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+    let request = IpcMessage::HealthCheck {
+        check_type: gg_core::ipc::HealthCheckType::Readiness,
+    };
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&request).unwrap(), None)
+        .await
+        .unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::HealthResponse(response) => {
+            assert!(!response.ok);
+            assert_eq!(
+                response.reason,
+                Some(gg_core::health::NotReadyReason::NoRecentSuccess)
+            );
+        }
+        other => panic!("Expected HealthResponse, got {:?}", other),
+    }
+
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&IpcMessage::MetricsRequest).unwrap(), None)
+        .await
+        .unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::MetricsResponse(snapshot) => {
+            assert!(snapshot.gauges["core_last_successful_inference_age_seconds"] > 0.0);
+        }
+        other => panic!("Expected MetricsResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn startup_gate_answers_warming_up_until_preload_completes() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        health: gg_core::health::HealthConfig {
+            startup_gate: gg_core::health::StartupGate::WarmingUp,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let one_token = InferenceParams {
+        max_tokens: 1,
+        ..Default::default()
+    };
+
+    // Before preload the model is unknown, but callers are told to wait
+    let response = infer_with_params(&runtime, "echo-model", one_token.clone()).await;
+    assert_eq!(response.error_code, Some(503));
+    assert!(response.error.unwrap().contains("warming up"));
+    assert!(!is_ready(&runtime).await);
+
+    // Preload finishes
+    runtime
+        .inference_engine
+        .register_model(
+            "echo-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(EchoModel),
+        )
+        .await;
+    runtime.health.mark_startup_complete();
+
+    let response = infer_with_params(&runtime, "echo-model", one_token).await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_eq!(response.output, "a");
+    assert!(is_ready(&runtime).await);
+}
+
+#[tokio::test]
+async fn delay_bind_gate_opens_once_startup_warmup_finishes() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        health: gg_core::health::HealthConfig {
+            startup_gate: gg_core::health::StartupGate::DelayBind,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let model = std::sync::Arc::new(HeldModel {
+        runs: Default::default(),
+        permits: tokio::sync::Semaphore::new(0),
+    });
+    runtime
+        .inference_engine
+        .register_model("held-model".into(), gg_core::models::ModelHandle::new(1), model.clone())
+        .await;
+
+    // The server waits on this before binding its socket
+    let startup = runtime.spawn_startup();
+    let bind = runtime.health.wait_startup_complete();
+    tokio::pin!(bind);
+    assert!(tokio::time::timeout(std::time::Duration::from_millis(50), &mut bind)
+        .await
+        .is_err());
+    assert_eq!(model.runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // Warmup finishing opens the gate
+    model.permits.add_permits(1);
+    tokio::time::timeout(std::time::Duration::from_secs(5), bind)
+        .await
+        .expect("startup should open the gate");
+    startup.await.unwrap();
+    assert!(!runtime.health.is_starting_up());
+}
@@ -0,0 +1,232 @@
+//! Persisting the model registry and restoring it on runtime startup.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use gg_core::engine::{
+    GgufModel, InferenceCapability, InferenceConfig, InferenceError, InferenceInput,
+    InferenceOutput, ModelFactory,
+};
+use gg_core::models::{
+    LoadedModelState, ModelArchitecture, ModelCapability, ModelHandle, ModelMetadata,
+    ModelVersion, PersistedModel, SmartLoader, SmartLoaderConfig, VersionHistory,
+};
+use gg_core::{Runtime, RuntimeConfig};
+use tempfile::TempDir;
+
+fn runtime(base: &Path) -> Runtime {
+    Runtime::new(RuntimeConfig {
+        base_path: base.to_path_buf(),
+        restore_registry: true,
+        ..Default::default()
+    })
+}
+
+/// Weights built by the smart loader's factory in these tests.
+struct StubModel(String);
+
+#[async_trait::async_trait]
+impl GgufModel for StubModel {
+    fn model_id(&self) -> &str {
+        &self.0
+    }
+
+    fn capabilities(&self) -> &[InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        16
+    }
+
+    async fn infer(
+        &self,
+        _input: &InferenceInput,
+        _config: &InferenceConfig,
+    ) -> Result<InferenceOutput, InferenceError> {
+        Err(InferenceError::ModelError("not used".into()))
+    }
+
+    async fn unload(&mut self) -> Result<(), InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn entry(base: &Path, model_id: &str, size_bytes: u64) -> PersistedModel {
+    let path = base.join("models").join(format!("{model_id}.gguf"));
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, b"GGUF").unwrap();
+    PersistedModel {
+        model_id: model_id.to_string(),
+        path,
+        version: ModelVersion::new(1, 0, 0),
+        capabilities: vec![ModelCapability::TextGeneration],
+        architecture: ModelArchitecture::Gguf,
+        auto_load: true,
+        history: VersionHistory::new(),
+        handle_id: 0,
+        size_bytes,
+        memory_bytes: 0,
+        format: "gguf".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn registry_round_trips_handles_and_metadata() {
+    let dir = TempDir::new().unwrap();
+    let first = runtime(dir.path());
+    let alpha = first
+        .model_registry
+        .register_persisted(entry(dir.path(), "alpha", 1000), 4096)
+        .await
+        .unwrap();
+    let beta = first
+        .model_registry
+        .register_persisted(entry(dir.path(), "beta", 2000), 8192)
+        .await
+        .unwrap();
+    first.persist_registry().await.unwrap();
+    assert!(dir.path().join("cache/registry.json").exists());
+    assert!(!dir.path().join("cache/registry.json.tmp").exists());
+
+    let second = runtime(dir.path());
+    let registry = &second.model_registry;
+    assert_eq!(registry.count().await, 2);
+    for (handle, name, size, memory) in [(alpha, "alpha", 1000, 4096), (beta, "beta", 2000, 8192)] {
+        let metadata = registry.get_metadata(handle).await.unwrap();
+        assert_eq!(metadata.name, name);
+        assert_eq!(metadata.size_bytes, size);
+        let info = registry
+            .list_models()
+            .await
+            .into_iter()
+            .find(|m| m.handle_id == handle.id())
+            .unwrap();
+        assert_eq!(info.memory_bytes, memory);
+        assert_eq!(info.format, "gguf");
+        assert_eq!(info.state, LoadedModelState::Unloaded);
+    }
+
+    // New registrations never reuse a restored handle
+    let gamma = registry
+        .register_persisted(entry(dir.path(), "gamma", 3000), 1)
+        .await
+        .unwrap();
+    assert!(gamma != alpha && gamma != beta);
+}
+
+#[tokio::test]
+async fn missing_model_files_are_skipped_on_restore() {
+    let dir = TempDir::new().unwrap();
+    let first = runtime(dir.path());
+    let kept = first
+        .model_registry
+        .register_persisted(entry(dir.path(), "kept", 10), 1)
+        .await
+        .unwrap();
+    let gone = entry(dir.path(), "gone", 20);
+    std::fs::remove_file(&gone.path).unwrap();
+    first
+        .model_registry
+        .register_persisted(gone, 1)
+        .await
+        .unwrap();
+    first.persist_registry().await.unwrap();
+
+    let second = runtime(dir.path());
+    assert_eq!(second.model_registry.count().await, 1);
+    assert!(second.model_registry.contains(kept).await);
+    assert!(
+        !second
+            .model_registry
+            .contains(ModelHandle::new(kept.id() + 1))
+            .await
+    );
+}
+
+#[tokio::test]
+async fn restore_is_off_by_default() {
+    let dir = TempDir::new().unwrap();
+    let first = runtime(dir.path());
+    first
+        .model_registry
+        .register_persisted(entry(dir.path(), "alpha", 1), 1)
+        .await
+        .unwrap();
+    first.persist_registry().await.unwrap();
+
+    let second = Runtime::new(RuntimeConfig {
+        base_path: dir.path().to_path_buf(),
+        ..Default::default()
+    });
+    assert_eq!(second.model_registry.count().await, 0);
+}
+
+#[tokio::test]
+async fn models_registered_without_a_manifest_are_persisted() {
+    let dir = TempDir::new().unwrap();
+    let first = runtime(dir.path());
+    let path = entry(dir.path(), "loaded", 0).path;
+    let metadata = |name: &str| ModelMetadata { name: name.into(), size_bytes: 64 };
+    let loaded = first
+        .model_registry
+        .register_file(metadata("loaded"), 128, "gguf".into(), path.clone())
+        .await
+        .unwrap();
+    let pathless = first.model_registry.register(metadata("pathless"), 0).await.unwrap();
+    first.persist_registry().await.unwrap();
+
+    let second = runtime(dir.path());
+    let models = second.model_registry.list_models().await;
+    assert_eq!(models.len(), 2);
+    let restored = models.iter().find(|m| m.name == "loaded").unwrap();
+    assert_eq!(restored.handle_id, loaded.id());
+    assert_eq!((restored.size_bytes, restored.memory_bytes), (64, 128));
+    assert!(models.iter().any(|m| m.handle_id == pathless.id()));
+    assert!(models.iter().all(|m| m.state == LoadedModelState::Unloaded));
+
+    // The model file going away drops it from the next restore
+    second.persist_registry().await.unwrap();
+    std::fs::remove_file(path).unwrap();
+    let third = runtime(dir.path());
+    assert!(!third.model_registry.contains(loaded).await);
+    assert!(third.model_registry.contains(pathless).await);
+}
+
+#[tokio::test]
+async fn startup_preloads_auto_load_models_through_the_smart_loader() {
+    let dir = TempDir::new().unwrap();
+    let first = runtime(dir.path());
+    let registry = &first.model_registry;
+    registry.register_persisted(entry(dir.path(), "alpha", 4), 1).await.unwrap();
+    let manual = PersistedModel { auto_load: false, ..entry(dir.path(), "beta", 4) };
+    registry.register_persisted(manual, 1).await.unwrap();
+    first.persist_registry().await.unwrap();
+
+    let mut second = runtime(dir.path());
+    let factory: ModelFactory = Arc::new(|_: &Path, model_id: &str| {
+        Ok(Arc::new(StubModel(model_id.to_string())) as Arc<dyn GgufModel>)
+    });
+    second.smart_loader = Arc::new(SmartLoader::serving(
+        SmartLoaderConfig::default(),
+        Arc::clone(&second.model_loader),
+        factory,
+        Arc::clone(&second.model_registry),
+        Arc::clone(&second.inference_engine),
+        Arc::clone(&second.health),
+    ));
+    second.spawn_startup().await.unwrap();
+
+    assert!(second.inference_engine.has_model("alpha").await);
+    assert!(!second.inference_engine.has_model("beta").await);
+    let models = second.model_registry.list_models().await;
+    assert_eq!(models.len(), 2);
+    let state = |name: &str| models.iter().find(|m| m.name == name).unwrap().state;
+    assert_eq!(state("alpha"), LoadedModelState::Ready);
+    assert_eq!(state("beta"), LoadedModelState::Unloaded);
+    assert_eq!(second.smart_loader.status().await.loaded_count, 1);
+}
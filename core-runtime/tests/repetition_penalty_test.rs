@@ -0,0 +1,114 @@
+//! Repetition penalty and no-repeat n-grams steer greedy decoding away from
+//! tokens already in the history.
+
+mod common;
+
+use common::infer_with_params;
+use gg_core::engine::InferenceParams;
+
+/// Greedy decoder over eight fixed, closely spaced logits that always prefer
+/// token 0, with the prompt's bytes (mod 4) seeding the repetition history.
+struct RepeatingModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for RepeatingModel {
+    fn model_id(&self) -> &str {
+        "repeating-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        use gg_core::engine::sampling;
+        let gg_core::engine::InferenceInput::Text(prompt) = input else {
+            panic!("expected text input");
+        };
+        let mut history: Vec<u32> = prompt.bytes().map(|b| u32::from(b) % 4).collect();
+        let mut text = String::new();
+        for _ in 0..config.max_tokens.unwrap_or(1) {
+            let mut logits: Vec<f32> = (0..8).map(|i| 2.0 - 0.05 * i as f32).collect();
+            sampling::apply_logit_bias(&mut logits, &config.logit_bias);
+            sampling::ban_tokens(
+                &mut logits,
+                &sampling::banned_ngram_tokens(&history, config.no_repeat_ngram_size),
+            );
+            sampling::apply_repetition_penalty(
+                &mut logits,
+                &history,
+                config.repetition_penalty,
+                config.history_window,
+            );
+            let token = sampling::sample_top_k_top_p(&logits, 1, 1.0, 0.0, 0, 0.0).unwrap();
+            history.push(token);
+            text.push_str(&token.to_string());
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            tokens_generated: text.len() as u32,
+            text,
+            finish_reason: gg_core::engine::FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Tokens in `output` equal to the token just before them.
+fn adjacent_repeats(output: &str) -> usize {
+    output.as_bytes().windows(2).filter(|pair| pair[0] == pair[1]).count()
+}
+
+#[tokio::test]
+async fn repetition_penalty_reduces_repeated_tokens() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "repeating-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(RepeatingModel),
+        )
+        .await;
+    let with = |repetition_penalty, no_repeat_ngram_size| InferenceParams {
+        max_tokens: 8,
+        repetition_penalty,
+        no_repeat_ngram_size,
+        ..Default::default()
+    };
+
+    let plain = infer_with_params(&runtime, "repeating-model", with(1.0, 0)).await;
+    let penalized = infer_with_params(&runtime, "repeating-model", with(1.3, 0)).await;
+    let ngram = infer_with_params(&runtime, "repeating-model", with(1.0, 2)).await;
+
+    assert_eq!(plain.output, "00000000");
+    assert!(
+        adjacent_repeats(&penalized.output) < adjacent_repeats(&plain.output),
+        "{} vs {}",
+        penalized.output,
+        plain.output
+    );
+    // Prompt "Hello" seeds history with "0 1 0 0 3": no bigram may recur
+    let grams: Vec<&[u8]> = ngram.output.as_bytes().windows(2).collect();
+    let unique: std::collections::HashSet<_> = grams.iter().collect();
+    assert_eq!(grams.len(), unique.len(), "{}", ngram.output);
+    assert!(!ngram.output.contains("00"), "{}", ngram.output);
+}
@@ -0,0 +1,150 @@
+//! Operator control over requests in flight: listing and cancelling them.
+
+mod common;
+
+use common::{handshake, infer_once};
+use gg_core::ipc::protocol::{decode_message, encode_message, IpcMessage, RequestId};
+
+/// Never finishes generating; only cancellation ends a request.
+struct StallingModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for StallingModel {
+    fn model_id(&self) -> &str {
+        "stalling-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        _config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        std::future::pending().await
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn cancel_all_fails_every_pending_request() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "stalling-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(StallingModel),
+        )
+        .await;
+    let handler = &runtime.ipc_handler;
+    let cancel_all = encode_message(&IpcMessage::CancelAllRequest).unwrap();
+
+    // Unauthenticated callers cannot drain the queue
+    assert!(matches!(
+        handler.process(&cancel_all, None).await,
+        Err(gg_core::ipc::HandlerError::NotAuthenticated)
+    ));
+
+    let callers = async {
+        tokio::join!(
+            infer_once(&runtime, "stalling-model"),
+            infer_once(&runtime, "stalling-model"),
+            infer_once(&runtime, "stalling-model"),
+        )
+    };
+    let operator = async {
+        while runtime.request_queue.len().await < 3 {
+            tokio::task::yield_now().await;
+        }
+        let session = handshake(&runtime).await;
+        let (bytes, _) = handler.process(&cancel_all, Some(&session)).await.unwrap();
+        decode_message(&bytes).unwrap()
+    };
+    let ((a, b, c), response) = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        async { tokio::join!(callers, operator) },
+    )
+    .await
+    .expect("cancel-all should release every caller");
+
+    assert!(matches!(response, IpcMessage::CancelAllResponse { cancelled: 3 }));
+    for response in [a, b, c] {
+        assert_eq!(response.error.as_deref(), Some("request cancelled"));
+    }
+    assert!(runtime.request_queue.is_empty().await);
+}
+
+#[tokio::test]
+async fn active_requests_lists_running_request_with_advancing_age() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "stalling-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(StallingModel),
+        )
+        .await;
+    let handler = &runtime.ipc_handler;
+    let list = encode_message(&IpcMessage::ActiveRequestsRequest).unwrap();
+    assert!(matches!(
+        handler.process(&list, None).await,
+        Err(gg_core::ipc::HandlerError::NotAuthenticated)
+    ));
+
+    let session = handshake(&runtime).await;
+    let active = || async {
+        let (bytes, _) = handler.process(&list, Some(&session)).await.unwrap();
+        match decode_message(&bytes).unwrap() {
+            IpcMessage::ActiveRequestsResponse { requests } => requests,
+            other => panic!("Expected ActiveRequestsResponse, got {:?}", other),
+        }
+    };
+
+    let operator = async {
+        while runtime.ipc_handler.active_requests().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let first = active().await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        (first, active().await)
+    };
+    let (first, second) = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        tokio::select! {
+            _ = infer_once(&runtime, "stalling-model") => panic!("stalling request finished"),
+            listed = operator => listed,
+        }
+    })
+    .await
+    .expect("Request should appear in the active list");
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].request_id, RequestId(1));
+    assert_eq!(first[0].model_id, "stalling-model");
+    assert_eq!(first[0].phase, gg_core::ipc::RequestPhase::Prefill);
+    assert_eq!(first[0].session_prefix.as_ref().map(String::len), Some(8));
+    assert!(second[0].age_ms >= first[0].age_ms + 50);
+
+    // Dropping the request's future removes it from the list
+    assert!(active().await.is_empty());
+}
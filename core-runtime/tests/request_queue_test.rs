@@ -0,0 +1,115 @@
+//! Requests from every origin share the runtime's request queue, which
+//! orders them by the priority clients ask for, up to a configured cap.
+
+mod common;
+
+use common::{infer_once, send_inference};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{InferenceRequest, RequestId};
+use gg_core::scheduler::Priority;
+
+#[tokio::test]
+async fn ffi_and_ipc_requests_share_queue_without_id_collisions() {
+    use gg_core::scheduler::RequestOrigin;
+
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    // Same enqueue core_infer performs for FFI callers
+    let (ffi_id, _) = runtime
+        .request_queue
+        .enqueue_from(
+            RequestOrigin::Ffi,
+            None,
+            "unloaded-model".into(),
+            String::new(),
+            InferenceParams::default(),
+            Priority::Normal,
+        )
+        .await
+        .unwrap();
+    infer_once(&runtime, "unloaded-model").await;
+
+    let first = runtime.request_queue.dequeue().await.unwrap();
+    let second = runtime.request_queue.dequeue().await.unwrap();
+    assert_eq!((first.id, first.origin), (ffi_id, RequestOrigin::Ffi));
+    assert_eq!(second.origin, RequestOrigin::Ipc);
+    assert_ne!(first.id, second.id);
+}
+
+/// Queue an IPC request for an unloaded model (it stays pending) and
+/// return the order the queue hands requests out in, by prompt.
+async fn enqueue_prompts(
+    runtime: &gg_core::Runtime,
+    requests: &[(&str, Option<Priority>)],
+) -> Vec<String> {
+    for (prompt, priority) in requests {
+        send_inference(
+            runtime,
+            InferenceRequest {
+                request_id: RequestId(1),
+                model_id: "unloaded-model".into(),
+                prompt: prompt.to_string(),
+                parameters: InferenceParams::default(),
+                client_metadata: None,
+                priority: *priority,
+                client_id: None,
+            },
+        )
+        .await;
+    }
+    let mut order = Vec::new();
+    while let Some(request) = runtime.request_queue.dequeue().await {
+        order.push(request.prompt);
+    }
+    order
+}
+
+#[tokio::test]
+async fn client_requested_priority_is_dequeued_first() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let order = enqueue_prompts(
+        &runtime,
+        &[
+            ("normal-0", None),
+            ("normal-1", Some(Priority::Normal)),
+            ("low", Some(Priority::Low)),
+            ("normal-2", None),
+            ("high", Some(Priority::High)),
+        ],
+    )
+    .await;
+    assert_eq!(order, ["high", "normal-0", "normal-1", "normal-2", "low"]);
+}
+
+#[tokio::test]
+async fn client_priority_above_cap_is_silently_capped() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        max_client_priority: Priority::Normal,
+        ..Default::default()
+    });
+    let order = enqueue_prompts(
+        &runtime,
+        &[("normal-0", None), ("normal-1", None), ("high", Some(Priority::High))],
+    )
+    .await;
+    // Capped to Normal, so it waits its turn behind earlier requests
+    assert_eq!(order, ["normal-0", "normal-1", "high"]);
+
+    // Critical is reserved for the server even under the default cap
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let order = enqueue_prompts(
+        &runtime,
+        &[("high", Some(Priority::High)), ("critical", Some(Priority::Critical))],
+    )
+    .await;
+    assert_eq!(order, ["high", "critical"]);
+}
@@ -0,0 +1,54 @@
+//! `SpansRequest` exports the tracing spans recorded for one request.
+
+mod common;
+
+use common::{handshake, runtime_with_failing_model};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
+};
+
+#[tokio::test]
+async fn spans_request_filters_by_request_id() {
+    let runtime = runtime_with_failing_model(|| {
+        gg_core::engine::InferenceError::ModelError("boom".into())
+    })
+    .await;
+    let handler = &runtime.ipc_handler;
+    let session = handshake(&runtime).await;
+
+    for id in [41, 42] {
+        let request = InferenceRequest {
+            request_id: RequestId(id),
+            model_id: "failing-model".to_string(),
+            prompt: "Hello".to_string(),
+            parameters: InferenceParams::default(),
+            client_metadata: None,
+            priority: None,
+            client_id: None,
+        };
+        let message = encode_message(&IpcMessage::InferenceRequest(request)).unwrap();
+        handler.process(&message, Some(&session)).await.unwrap();
+    }
+
+    let request = IpcMessage::SpansRequest {
+        max_count: 100,
+        request_id: Some(RequestId(42)),
+    };
+    let (bytes, _) = handler
+        .process(&encode_message(&request).unwrap(), None)
+        .await
+        .unwrap();
+    let spans = match decode_message(&bytes).unwrap() {
+        IpcMessage::SpansResponse { spans } => spans,
+        other => panic!("Expected SpansResponse, got {:?}", other),
+    };
+
+    let mut names: Vec<&str> = spans.iter().map(|s| s.name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["generate", "inference", "queue"]);
+    assert!(spans.iter().all(|s| s.request_id() == Some(42)));
+    assert!(spans.iter().all(|s| s.trace_id == spans[0].trace_id));
+    // Both requests' spans remain buffered for unfiltered export
+    assert_eq!(runtime.span_collector.len(), 6);
+}
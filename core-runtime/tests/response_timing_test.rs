@@ -0,0 +1,128 @@
+//! Responses break their latency down into queue wait, prefill and decode.
+
+mod common;
+
+use common::{infer_once, send_inference};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{InferenceRequest, RequestId};
+use gg_core::scheduler::RequestQueueConfig;
+
+/// Spends `PREFILL` before marking the decode signal, then `DECODE` after.
+struct PhasedModel;
+
+const PREFILL: std::time::Duration = std::time::Duration::from_millis(30);
+const DECODE: std::time::Duration = std::time::Duration::from_millis(40);
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for PhasedModel {
+    fn model_id(&self) -> &str {
+        "phased-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        tokio::time::sleep(PREFILL).await;
+        config.decode_signal.mark();
+        tokio::time::sleep(DECODE).await;
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            text: "done".into(),
+            tokens_generated: 1,
+            finish_reason: gg_core::engine::FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn response_timing_breakdown_sums_to_wall_time() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "phased-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(PhasedModel),
+        )
+        .await;
+
+    let started = std::time::Instant::now();
+    let response = infer_once(&runtime, "phased-model").await;
+    let wall_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    let queue = response.queue_wait_ms.expect("queue_wait_ms");
+    let prefill = response.prefill_ms.expect("prefill_ms");
+    let decode = response.decode_ms.expect("decode_ms");
+    assert!(prefill >= PREFILL.as_millis() as f64, "prefill {prefill}");
+    assert!(decode >= DECODE.as_millis() as f64, "decode {decode}");
+
+    // Only handshake and message handling fall outside the three phases
+    let total = queue + prefill + decode;
+    assert!(total <= wall_ms, "{total} > {wall_ms}");
+    assert!(total >= wall_ms * 0.8, "{total} vs {wall_ms}");
+}
+
+#[tokio::test]
+async fn queue_wait_lasts_until_the_request_runs() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        request_queue: RequestQueueConfig {
+            max_pending: 1,
+            enqueue_timeout: Some(std::time::Duration::from_secs(5)),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "phased-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(PhasedModel),
+        )
+        .await;
+    let request = |id: u64, prompt: &str| InferenceRequest {
+        request_id: RequestId(id),
+        model_id: "phased-model".into(),
+        prompt: prompt.into(),
+        parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+
+    let (first, second) = tokio::join!(send_inference(&runtime, request(1, "first")), async {
+        // Arrive while the first request holds the only queue slot
+        tokio::time::sleep(PREFILL / 3).await;
+        send_inference(&runtime, request(2, "second")).await
+    });
+
+    assert!(first.error.is_none(), "{:?}", first.error);
+    assert!(second.error.is_none(), "{:?}", second.error);
+    let waited = second.queue_wait_ms.expect("queue_wait_ms");
+    let remaining = (PREFILL + DECODE - PREFILL / 3).as_millis() as f64;
+    assert!(waited >= remaining * 0.8, "waited {waited}ms, expected about {remaining}ms");
+    assert!(first.queue_wait_ms.expect("queue_wait_ms") < waited);
+}
@@ -0,0 +1,143 @@
+//! Safety valves that stop a generation the model itself would not end.
+
+use gg_core::engine::InferenceParams;
+
+/// Burns CPU on every decode step, checking the request's CPU budget.
+struct SpinningModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for SpinningModel {
+    fn model_id(&self) -> &str {
+        "spinning-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        let budget = gg_core::engine::CpuBudget::start(config.max_cpu_ms);
+        let steps = config.max_tokens.unwrap_or(0);
+        for _ in 0..steps {
+            budget.check()?;
+            let step = std::time::Instant::now();
+            while step.elapsed() < std::time::Duration::from_millis(2) {
+                std::hint::spin_loop();
+            }
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            text: String::new(),
+            tokens_generated: steps,
+            finish_reason: gg_core::engine::FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn cpu_cap_terminates_runaway_request() {
+    let engine = gg_core::engine::InferenceEngine::new(4096);
+    engine
+        .register_model(
+            "spinning-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(SpinningModel),
+        )
+        .await;
+    let params = InferenceParams {
+        max_tokens: 10_000,
+        max_cpu_ms: Some(30),
+        ..Default::default()
+    };
+
+    let start = std::time::Instant::now();
+    let err = engine.run("spinning-model", "spin", &params).await.unwrap_err();
+
+    assert_eq!(err.category(), gg_core::engine::ErrorCategory::Infra);
+    assert!(err.to_string().contains("CPU limit exceeded"), "{}", err);
+    // 10k steps at 2ms would take 20s; the cap stops it after a few dozen
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+}
+
+/// Never emits EOS; only the decode-step valve can stop it.
+struct EndlessModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for EndlessModel {
+    fn model_id(&self) -> &str {
+        "endless-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        let mut valve = gg_core::engine::DecodeValve::new(config.absolute_max_decode_steps);
+        let max_tokens = config.max_tokens.unwrap_or(u32::MAX);
+        for _ in 0..max_tokens {
+            valve.step()?;
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            text: String::new(),
+            tokens_generated: valve.steps(),
+            finish_reason: gg_core::engine::FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn absolute_decode_step_valve_stops_endless_generation() {
+    let engine = gg_core::engine::InferenceEngine::new(4096).with_absolute_max_decode_steps(100);
+    engine
+        .register_model(
+            "endless-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(EndlessModel),
+        )
+        .await;
+    let params = InferenceParams {
+        max_tokens: 1_000_000_000,
+        timeout_ms: None,
+        ..Default::default()
+    };
+
+    let err = engine.run("endless-model", "go", &params).await.unwrap_err();
+
+    assert_eq!(err.category(), gg_core::engine::ErrorCategory::Model);
+    assert!(err.to_string().contains("Decode step safety limit reached: 100"), "{}", err);
+}
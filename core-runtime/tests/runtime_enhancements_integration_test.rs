@@ -125,7 +125,7 @@ fn test_dedup_across_sessions() {
 
 #[test]
 fn test_connection_limit_enforced() {
-    let config = ConnectionConfig { max_connections: 2 };
+    let config = ConnectionConfig { max_connections: 2, ..Default::default() };
     let pool = ConnectionPool::new(config);
 
     // Acquire up to limit
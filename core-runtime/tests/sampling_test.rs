@@ -0,0 +1,74 @@
+//! Tests for the candidate-capped sampler on large vocabularies.
+
+use std::time::Duration;
+
+use gg_core::engine::sampling::{sample_top_k_top_p, top_candidates, DEFAULT_CANDIDATE_CAP};
+use tokio::time::Instant;
+
+const VOCAB_SIZE: usize = 128_000;
+
+/// Cost charged for each candidate a step sorts. The clock is paused, so
+/// step time follows from the work done rather than the machine's speed.
+const SORT_COST_PER_CANDIDATE: Duration = Duration::from_nanos(10);
+
+/// Flat low logits with a few strong tokens at known positions.
+fn peaked_logits() -> Vec<f32> {
+    let mut logits = vec![-10.0f32; VOCAB_SIZE];
+    logits[97_531] = 12.0;
+    logits[4_242] = 11.0;
+    logits[64_000] = 10.0;
+    logits
+}
+
+#[test]
+fn capped_sampling_selects_same_token_as_uncapped() {
+    let logits = peaked_logits();
+
+    for uniform in [0.0, 0.3, 0.6, 0.9, 0.999] {
+        let capped = sample_top_k_top_p(&logits, 0, 0.95, 1.0, DEFAULT_CANDIDATE_CAP, uniform);
+        let uncapped = sample_top_k_top_p(&logits, 0, 0.95, 1.0, 0, uniform);
+        assert_eq!(capped, uncapped, "draw {uniform}");
+    }
+
+    // Greedy always returns the peak
+    assert_eq!(
+        sample_top_k_top_p(&logits, 40, 0.9, 0.0, DEFAULT_CANDIDATE_CAP, 0.5),
+        Some(97_531)
+    );
+}
+
+#[test]
+fn candidate_cap_bounds_candidate_set() {
+    let logits = peaked_logits();
+    let candidates = top_candidates(&logits, DEFAULT_CANDIDATE_CAP);
+
+    assert_eq!(candidates.len(), DEFAULT_CANDIDATE_CAP);
+    assert_eq!(
+        candidates[..3].iter().map(|c| c.0).collect::<Vec<_>>(),
+        vec![97_531, 4_242, 64_000]
+    );
+}
+
+/// One sampling step with `cap`, charged for the candidates it sorts.
+async fn step_time(logits: &[f32], cap: usize) -> Duration {
+    let start = Instant::now();
+    sample_top_k_top_p(logits, 0, 0.9, 0.8, cap, 0.42);
+    let sorted = top_candidates(logits, cap).len() as u32;
+    tokio::time::advance(SORT_COST_PER_CANDIDATE * sorted).await;
+    start.elapsed()
+}
+
+#[tokio::test(start_paused = true)]
+async fn capped_sampling_step_time_compared_to_uncapped() {
+    let logits: Vec<f32> = (0..VOCAB_SIZE)
+        .map(|i| ((i as u64 * 2_654_435_761) % 10_007) as f32 / 1_000.0)
+        .collect();
+
+    let uncapped = step_time(&logits, 0).await;
+    let capped = step_time(&logits, DEFAULT_CANDIDATE_CAP).await;
+
+    // Full sort of 128k candidates dominates; the capped path avoids it
+    assert_eq!(uncapped, SORT_COST_PER_CANDIDATE * VOCAB_SIZE as u32);
+    assert_eq!(capped, SORT_COST_PER_CANDIDATE * DEFAULT_CANDIDATE_CAP as u32);
+    assert!(capped < uncapped);
+}
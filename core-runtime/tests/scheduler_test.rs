@@ -2,9 +2,12 @@
 
 use gg_core::engine::InferenceParams;
 use gg_core::scheduler::{
-    BatchConfig, BatchProcessor, Priority, PriorityQueue, RequestQueue,
+    BatchConfig, BatchProcessor, ModelAffinity, Priority, PriorityCaps, PriorityQueue,
+    QueueError, QueueFairness, RequestIdAllocator, RequestOrigin, RequestQueue,
     RequestQueueConfig, ThreadPoolConfig,
 };
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[test]
 fn priority_queue_orders_by_priority() {
@@ -46,14 +49,306 @@ async fn request_queue_enqueue_dequeue() {
         .await
         .unwrap();
 
-    assert_eq!(id, 1);
+    assert!(id >= 1);
     assert_eq!(position, 0);
 
     let request = queue.dequeue().await.unwrap();
-    assert_eq!(request.id, 1);
+    assert_eq!(request.id, id);
     assert_eq!(request.model_id, "model");
 }
 
+#[test]
+fn priority_queue_pop_matching_preserves_order_of_skipped() {
+    let mut queue: PriorityQueue<&str> = PriorityQueue::new();
+
+    queue.push("x1", Priority::Normal);
+    queue.push("y1", Priority::Normal);
+    queue.push("x2", Priority::Normal);
+
+    assert_eq!(queue.pop_matching(|item| item.starts_with('y')), Some("y1"));
+    assert_eq!(queue.pop_matching(|item| item.starts_with('y')), None);
+    assert_eq!(queue.pop(), Some("x1"));
+    assert_eq!(queue.pop(), Some("x2"));
+}
+
+#[tokio::test]
+async fn request_queue_routes_models_to_bound_workers() {
+    let queue = Arc::new(RequestQueue::new(RequestQueueConfig::default()));
+    for i in 0..6 {
+        let model = if i % 2 == 0 { "model-x" } else { "model-y" };
+        queue
+            .enqueue(
+                model.to_string(),
+                format!("prompt {}", i),
+                InferenceParams::default(),
+                Priority::Normal,
+            )
+            .await
+            .unwrap();
+    }
+
+    let spawn_worker = |affinity: ModelAffinity| {
+        let queue = Arc::clone(&queue);
+        tokio::spawn(async move {
+            let mut served = Vec::new();
+            while let Some(request) = queue.dequeue_for(&affinity).await {
+                served.push(request.model_id);
+            }
+            served
+        })
+    };
+    let worker_a = spawn_worker(ModelAffinity::only(["model-x"]));
+    let worker_b = spawn_worker(ModelAffinity::only(["model-y"]));
+
+    let served_a = worker_a.await.unwrap();
+    let served_b = worker_b.await.unwrap();
+
+    assert_eq!(served_a, vec!["model-x"; 3]);
+    assert_eq!(served_b, vec!["model-y"; 3]);
+    assert!(queue.is_empty().await);
+}
+
+#[tokio::test]
+async fn request_queue_unbound_affinity_serves_any_model() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+    queue
+        .enqueue("model-z".into(), "p".into(), InferenceParams::default(), Priority::Normal)
+        .await
+        .unwrap();
+
+    assert!(queue.dequeue_for(&ModelAffinity::only(["model-x"])).await.is_none());
+    assert_eq!(queue.len().await, 1);
+    let request = queue.dequeue_for(&ModelAffinity::Any).await.unwrap();
+    assert_eq!(request.model_id, "model-z");
+}
+
+async fn enqueue_sessions(queue: &RequestQueue, sessions: &[&str], per_session: usize) {
+    for session in sessions {
+        for i in 0..per_session {
+            queue
+                .enqueue_for_session(
+                    Some(session.to_string()),
+                    "model".to_string(),
+                    format!("{} {}", session, i),
+                    InferenceParams::default(),
+                    Priority::Normal,
+                )
+                .await
+                .unwrap();
+        }
+    }
+}
+
+async fn drain_sessions(queue: &RequestQueue) -> Vec<String> {
+    let mut order = Vec::new();
+    while let Some(request) = queue.dequeue().await {
+        order.push(request.session.unwrap());
+    }
+    order
+}
+
+#[tokio::test]
+async fn request_queue_round_robin_interleaves_sessions() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        fairness: QueueFairness::SessionRoundRobin,
+        ..Default::default()
+    });
+    enqueue_sessions(&queue, &["a", "b"], 3).await;
+
+    assert_eq!(drain_sessions(&queue).await, ["a", "b", "a", "b", "a", "b"]);
+}
+
+#[tokio::test]
+async fn request_queue_fifo_drains_first_session_first() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+    enqueue_sessions(&queue, &["a", "b"], 3).await;
+
+    assert_eq!(drain_sessions(&queue).await, ["a", "a", "a", "b", "b", "b"]);
+}
+
+#[tokio::test]
+async fn request_queue_round_robin_respects_priority() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        fairness: QueueFairness::SessionRoundRobin,
+        ..Default::default()
+    });
+    enqueue_sessions(&queue, &["a"], 2).await;
+    queue
+        .enqueue_for_session(
+            Some("b".into()),
+            "model".into(),
+            "urgent".into(),
+            InferenceParams::default(),
+            Priority::High,
+        )
+        .await
+        .unwrap();
+
+    let first = queue.dequeue().await.unwrap();
+    assert_eq!(first.prompt, "urgent");
+    assert_eq!(drain_sessions(&queue).await, ["a", "a"]);
+}
+
+#[tokio::test]
+async fn request_queue_low_cap_reserves_high_capacity() {
+    let config = RequestQueueConfig {
+        max_pending: 10,
+        priority_caps: PriorityCaps { low: Some(3), ..Default::default() },
+        ..Default::default()
+    };
+    let queue = RequestQueue::new(config);
+    let enqueue = |priority| {
+        queue.enqueue("model".into(), "p".into(), InferenceParams::default(), priority)
+    };
+
+    for _ in 0..3 {
+        enqueue(Priority::Low).await.unwrap();
+    }
+    assert!(matches!(
+        enqueue(Priority::Low).await,
+        Err(QueueError::PriorityCapReached(Priority::Low))
+    ));
+
+    // Total capacity remains, so higher priorities are still admitted
+    let (_, position) = enqueue(Priority::High).await.unwrap();
+    assert_eq!(position, 3);
+}
+
+fn waiting_queue(max_pending: usize, timeout_ms: u64) -> Arc<RequestQueue> {
+    Arc::new(RequestQueue::new(RequestQueueConfig {
+        max_pending,
+        enqueue_timeout: Some(std::time::Duration::from_millis(timeout_ms)),
+        ..Default::default()
+    }))
+}
+
+async fn enqueue_prompt(queue: &RequestQueue, prompt: &str) -> Result<(u64, usize), QueueError> {
+    queue
+        .enqueue("model".into(), prompt.into(), InferenceParams::default(), Priority::Normal)
+        .await
+}
+
+#[tokio::test]
+async fn request_queue_full_enqueue_waits_for_freed_slot() {
+    let queue = waiting_queue(1, 5_000);
+    enqueue_prompt(&queue, "running").await.unwrap();
+
+    let waiter = tokio::spawn({
+        let queue = Arc::clone(&queue);
+        async move { enqueue_prompt(&queue, "waiting").await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(!waiter.is_finished());
+
+    assert_eq!(queue.dequeue().await.unwrap().prompt, "running");
+    waiter.await.unwrap().unwrap();
+    assert_eq!(queue.dequeue().await.unwrap().prompt, "waiting");
+}
+
+#[tokio::test]
+async fn request_queue_full_enqueue_times_out_cleanly() {
+    let queue = waiting_queue(1, 50);
+    enqueue_prompt(&queue, "running").await.unwrap();
+
+    let started = std::time::Instant::now();
+    let result = enqueue_prompt(&queue, "rejected").await;
+    assert!(matches!(result, Err(QueueError::EnqueueTimeout(_))));
+    assert!(started.elapsed() >= std::time::Duration::from_millis(50));
+    assert_eq!(queue.len().await, 1);
+
+    // The timed-out waiter holds no slot once one frees up
+    queue.dequeue().await.unwrap();
+    enqueue_prompt(&queue, "next").await.unwrap();
+    assert!(matches!(enqueue_prompt(&queue, "over").await, Err(QueueError::EnqueueTimeout(_))));
+}
+
+#[tokio::test]
+async fn request_queue_complete_returns_slot() {
+    let queue = waiting_queue(1, 50);
+    let (id, _) = enqueue_prompt(&queue, "finished").await.unwrap();
+
+    assert!(queue.complete(id).await);
+    assert!(!queue.complete(id).await);
+    assert!(queue.is_empty().await);
+    enqueue_prompt(&queue, "next").await.unwrap();
+    assert!(matches!(enqueue_prompt(&queue, "over").await, Err(QueueError::EnqueueTimeout(_))));
+}
+
+#[tokio::test]
+async fn request_queue_waiters_admitted_in_arrival_order() {
+    let queue = waiting_queue(1, 5_000);
+    enqueue_prompt(&queue, "running").await.unwrap();
+
+    let mut waiters = Vec::new();
+    for prompt in ["first", "second", "third"] {
+        let queue = Arc::clone(&queue);
+        waiters.push(tokio::spawn(async move { enqueue_prompt(&queue, prompt).await }));
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let mut served = Vec::new();
+    for _ in 0..4 {
+        let request = loop {
+            if let Some(request) = queue.dequeue().await {
+                break request;
+            }
+            tokio::task::yield_now().await;
+        };
+        served.push(request.prompt);
+    }
+    assert_eq!(served, ["running", "first", "second", "third"]);
+    for waiter in waiters {
+        waiter.await.unwrap().unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn request_ids_unique_across_ffi_and_ipc_origins() {
+    let config = RequestQueueConfig {
+        max_pending: 1000,
+        ..Default::default()
+    };
+    let queue = Arc::new(RequestQueue::new(config));
+
+    let tasks: Vec<_> = (0..100)
+        .map(|i| {
+            let origin = if i % 2 == 0 { RequestOrigin::Ffi } else { RequestOrigin::Ipc };
+            let queue = Arc::clone(&queue);
+            tokio::spawn(async move {
+                let (id, _) = queue
+                    .enqueue_from(
+                        origin,
+                        None,
+                        "model".into(),
+                        format!("prompt {}", i),
+                        InferenceParams::default(),
+                        Priority::Normal,
+                    )
+                    .await
+                    .unwrap();
+                (id, origin)
+            })
+        })
+        .collect();
+    let cli_id = RequestIdAllocator::global().allocate(RequestOrigin::Cli);
+
+    let mut issued = HashMap::new();
+    for task in tasks {
+        let (id, origin) = task.await.unwrap();
+        assert!(issued.insert(id, origin).is_none(), "duplicate request id {}", id);
+    }
+    assert_eq!(issued.len(), 100);
+    assert!(!issued.contains_key(&cli_id));
+
+    let mut by_origin: HashMap<RequestOrigin, usize> = HashMap::new();
+    while let Some(request) = queue.dequeue().await {
+        assert_eq!(request.origin, issued[&request.id]);
+        *by_origin.entry(request.origin).or_default() += 1;
+    }
+    assert_eq!(by_origin[&RequestOrigin::Ffi], 50);
+    assert_eq!(by_origin[&RequestOrigin::Ipc], 50);
+}
+
 #[test]
 fn batch_processor_respects_size_limit() {
     let config = BatchConfig {
@@ -156,3 +451,72 @@ fn thread_pool_config_stack_size_reasonable() {
     // Inference stack should be at least 4MB
     assert!(inference_config.stack_size >= 4 * 1024 * 1024);
 }
+
+#[tokio::test]
+async fn test_set_max_pending_grows_and_shrinks_without_evicting() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        max_pending: 1,
+        ..Default::default()
+    });
+    let params = InferenceParams::default();
+    let enqueue = || queue.enqueue("m".into(), "p".into(), params.clone(), Priority::Normal);
+
+    enqueue().await.unwrap();
+    assert!(matches!(enqueue().await, Err(QueueError::QueueFull)));
+
+    queue.set_max_pending(3);
+    enqueue().await.unwrap();
+    enqueue().await.unwrap();
+    assert_eq!(queue.len().await, 3);
+
+    // Shrinking keeps queued requests; space frees only below the new cap
+    queue.set_max_pending(1);
+    assert_eq!(queue.len().await, 3);
+    assert!(matches!(enqueue().await, Err(QueueError::QueueFull)));
+    queue.dequeue().await.unwrap();
+    queue.dequeue().await.unwrap();
+    assert!(matches!(enqueue().await, Err(QueueError::QueueFull)));
+    queue.dequeue().await.unwrap();
+    enqueue().await.unwrap();
+    assert!(matches!(enqueue().await, Err(QueueError::QueueFull)));
+}
+
+#[tokio::test]
+async fn test_set_max_pending_clamps_to_semaphore_limit() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+
+    queue.set_max_pending(usize::MAX);
+
+    assert_eq!(queue.max_pending(), tokio::sync::Semaphore::MAX_PERMITS);
+    queue
+        .enqueue("m".into(), "p".into(), InferenceParams::default(), Priority::Normal)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_new_clamps_max_pending_to_semaphore_limit() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        max_pending: usize::MAX,
+        ..Default::default()
+    });
+
+    assert_eq!(queue.max_pending(), tokio::sync::Semaphore::MAX_PERMITS);
+}
+
+#[tokio::test]
+async fn test_complete_returns_the_slot() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        max_pending: 1,
+        ..Default::default()
+    });
+    let params = InferenceParams::default();
+    let enqueue = || queue.enqueue("m".into(), "p".into(), params.clone(), Priority::Normal);
+
+    let (id, _) = enqueue().await.unwrap();
+    assert!(matches!(enqueue().await, Err(QueueError::QueueFull)));
+
+    assert!(queue.complete(id).await);
+    assert!(!queue.complete(id).await);
+    enqueue().await.unwrap();
+}
@@ -96,6 +96,9 @@ fn inference_request_requires_model_id() {
         model_id: String::new(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let result = request.validate();
     assert!(result.is_err());
@@ -109,6 +112,9 @@ fn inference_request_requires_prompt() {
         model_id: "test-model".to_string(),
         prompt: String::new(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let result = request.validate();
     assert!(result.is_err());
@@ -122,6 +128,9 @@ fn valid_inference_request_passes() {
         model_id: "test-model".to_string(),
         prompt: "Hello, world!".to_string(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let result = request.validate();
     assert!(result.is_ok());
@@ -151,6 +160,8 @@ fn handshake_message_encoding() {
     let msg = IpcMessage::Handshake {
         token: "test-token".to_string(),
         protocol_version: None,
+        compression: None,
+        strict_version: false,
     };
     let encoded = encode_message(&msg).unwrap();
     let decoded = decode_message(&encoded).unwrap();
@@ -166,6 +177,9 @@ fn large_prompt() {
         model_id: "test".to_string(),
         prompt: large_prompt.clone(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
@@ -202,6 +216,9 @@ fn unicode_model_id() {
         model_id: "test-model-\u{4e2d}\u{6587}".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg);
@@ -245,6 +262,9 @@ fn large_request_id() {
         model_id: "test".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
@@ -264,6 +284,9 @@ fn zero_request_id() {
         model_id: "test".to_string(),
         prompt: "test prompt".to_string(),
         parameters: InferenceParams::default(),
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
     let msg = IpcMessage::InferenceRequest(request);
     let encoded = encode_message(&msg).unwrap();
@@ -10,6 +10,7 @@ fn create_filter_with_blocklist(words: Vec<&str>) -> OutputFilter {
         blocklist: words.into_iter().map(String::from).collect(),
         regex_patterns: vec![],
         max_output_chars: 0,
+        max_output_bytes: None,
         replacement: "[BLOCKED]".to_string(),
     };
     OutputFilter::new(config).expect("valid filter config")
@@ -20,6 +21,7 @@ fn create_filter_with_regex(patterns: Vec<&str>) -> OutputFilter {
         blocklist: vec![],
         regex_patterns: patterns.into_iter().map(String::from).collect(),
         max_output_chars: 0,
+        max_output_bytes: None,
         replacement: "[BLOCKED]".to_string(),
     };
     OutputFilter::new(config).expect("valid filter config")
@@ -64,6 +66,7 @@ fn filter_length_truncation() {
         blocklist: vec![],
         regex_patterns: vec![],
         max_output_chars: 10,
+        max_output_bytes: None,
         replacement: "[BLOCKED]".to_string(),
     };
     let filter = OutputFilter::new(config).expect("valid config");
@@ -104,6 +107,7 @@ fn filter_regex_not_catastrophic_backtracking() {
         blocklist: vec![],
         regex_patterns: vec![r"(a+)+b".to_string()], // Known ReDoS pattern
         max_output_chars: 0,
+        max_output_bytes: None,
         replacement: "[BLOCKED]".to_string(),
     };
     let filter = OutputFilter::new(config).expect("valid config");
@@ -131,6 +135,7 @@ fn filter_invalid_regex_rejected() {
         blocklist: vec![],
         regex_patterns: vec!["[invalid".to_string()], // Unclosed bracket
         max_output_chars: 0,
+        max_output_bytes: None,
         replacement: "[BLOCKED]".to_string(),
     };
 
@@ -11,10 +11,11 @@ fn create_test_manifest(sha256: &str) -> ModelManifest {
         name: "Test Model".to_string(),
         version: "1.0.0".to_string(),
         capabilities: vec![ModelCapability::TextClassification],
-        sha256: sha256.to_string(),
+        sha256: Some(sha256.to_string()),
         size_bytes: 1024,
         architecture: ModelArchitecture::Onnx,
         license: "MIT".to_string(),
+        chat_template: None,
     }
 }
 
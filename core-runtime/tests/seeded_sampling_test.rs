@@ -0,0 +1,90 @@
+//! A request's seed makes sampled output reproducible.
+
+mod common;
+
+use common::infer_with_params;
+use gg_core::engine::InferenceParams;
+
+/// Samples 32 tokens at temperature 1.0 from fixed, nearly flat logits with
+/// a `DecodeExecutor` seeded from the request.
+struct SampledModel;
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for SampledModel {
+    fn model_id(&self) -> &str {
+        "sampled-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        use gg_core::engine::{DecodeConfig, DecodeExecutor};
+        let mut executor = DecodeExecutor::new(DecodeConfig {
+            seed: config.seed,
+            eos_token: u32::MAX,
+            ..Default::default()
+        });
+        let mut text = String::new();
+        for _ in 0..config.max_tokens.unwrap_or(1) {
+            let mut logits: Vec<f32> = (0..16).map(|i| 1.0 - 0.01 * i as f32).collect();
+            let token = executor.sample(&mut logits, config).unwrap();
+            text.push_str(&format!("{token} "));
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(gg_core::engine::GenerationResult {
+            tokens_generated: config.max_tokens.unwrap_or(1),
+            text,
+            finish_reason: gg_core::engine::FinishReason::MaxTokens,
+            raw_bytes: None,
+        }))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn same_seed_reproduces_output_and_different_seeds_diverge() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "sampled-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(SampledModel),
+        )
+        .await;
+
+    let seeded = |seed| InferenceParams {
+        max_tokens: 32,
+        temperature: 1.0,
+        top_p: 1.0,
+        top_k: 0,
+        seed: Some(seed),
+        ..Default::default()
+    };
+    let first = infer_with_params(&runtime, "sampled-model", seeded(7)).await;
+    let second = infer_with_params(&runtime, "sampled-model", seeded(7)).await;
+    let other = infer_with_params(&runtime, "sampled-model", seeded(8)).await;
+
+    assert!(first.error.is_none(), "{:?}", first.error);
+    assert_eq!(first.output.as_bytes(), second.output.as_bytes());
+    assert_ne!(first.output, other.output);
+}
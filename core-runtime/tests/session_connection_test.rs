@@ -0,0 +1,73 @@
+//! Sessions count as connected while a server connection holds them, so the
+//! evict-oldest-idle session policy never drops a session in use.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::TEST_TOKEN;
+use gg_core::ipc::protocol::{encode_message, IpcMessage};
+use gg_core::ipc::{
+    ConnectionConfig, ConnectionPool, IpcHandler, SessionLimitConfig, SessionLimitPolicy,
+};
+use gg_core::{Runtime, RuntimeConfig};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+fn handler_with_one_session() -> Arc<IpcHandler> {
+    let runtime = Runtime::new(RuntimeConfig {
+        auth_token: TEST_TOKEN.into(),
+        session_limit: SessionLimitConfig {
+            max_active_sessions: Some(1),
+            policy: SessionLimitPolicy::EvictOldestIdle,
+        },
+        ..Default::default()
+    });
+    Arc::new(runtime.ipc_handler)
+}
+
+fn handshake_bytes() -> Vec<u8> {
+    encode_message(&IpcMessage::Handshake {
+        token: TEST_TOKEN.into(),
+        protocol_version: None,
+        compression: None,
+        strict_version: false,
+    })
+    .unwrap()
+}
+
+/// Handshake over a served in-memory connection; returns the client end.
+async fn connect(handler: &Arc<IpcHandler>) -> (DuplexStream, tokio::task::JoinHandle<()>) {
+    let pool = Arc::new(ConnectionPool::new(ConnectionConfig::default()));
+    let guard = pool.try_acquire_owned().unwrap();
+    let (mut client, server) = tokio::io::duplex(64 * 1024);
+    let task = tokio::spawn(gg_core::ipc::server::handle_connection(
+        server,
+        Arc::clone(handler),
+        guard,
+    ));
+    let frame = handshake_bytes();
+    client.write_all(&(frame.len() as u32).to_le_bytes()).await.unwrap();
+    client.write_all(&frame).await.unwrap();
+    let mut len = [0u8; 4];
+    client.read_exact(&mut len).await.unwrap();
+    let mut ack = vec![0u8; u32::from_le_bytes(len) as usize];
+    client.read_exact(&mut ack).await.unwrap();
+    assert!(String::from_utf8_lossy(&ack).contains("handshake_ack"));
+    (client, task)
+}
+
+#[tokio::test]
+async fn connected_session_is_not_evicted_for_a_new_one() {
+    let handler = handler_with_one_session();
+    let (client, task) = connect(&handler).await;
+
+    let (_, session) = handler.process(&handshake_bytes(), None).await.unwrap();
+    assert!(session.is_none(), "the connected session was evicted");
+
+    // Once the client hangs up its session is idle and may be evicted
+    drop(client);
+    tokio::time::timeout(Duration::from_secs(2), task).await.unwrap().unwrap();
+    let (_, session) = handler.process(&handshake_bytes(), None).await.unwrap();
+    assert!(session.is_some());
+}
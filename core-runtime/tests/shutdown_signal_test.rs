@@ -0,0 +1,50 @@
+//! Tests for SIGTERM-triggered graceful shutdown.
+//!
+//! Kept in its own test binary because it signals the whole process.
+
+#![cfg(unix)]
+
+use gg_core::shutdown::{
+    ShutdownCoordinator, ShutdownResult, ShutdownSignal, ShutdownSignals, ShutdownState,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_sigterm_initiates_drain_and_completes() {
+    let coordinator = Arc::new(ShutdownCoordinator::new());
+    let guard = coordinator.track().unwrap();
+    let mut signals = ShutdownSignals::new().unwrap();
+
+    let server_coordinator = Arc::clone(&coordinator);
+    let server = tokio::spawn(async move {
+        let signal = signals.recv().await;
+        let result = server_coordinator.initiate(Duration::from_secs(5)).await;
+        (signal, result)
+    });
+
+    // SAFETY: a SIGTERM handler is installed above, so this does not kill the process
+    unsafe {
+        libc::kill(libc::getpid(), libc::SIGTERM);
+    }
+
+    // Draining starts while the request is still in flight
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while coordinator.state().await != ShutdownState::Draining {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("SIGTERM should start draining");
+    assert!(!coordinator.is_accepting());
+
+    drop(guard);
+
+    let (signal, result) = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("shutdown should finish within the timeout")
+        .unwrap();
+    assert_eq!(signal, ShutdownSignal::Terminate);
+    assert_eq!(result, ShutdownResult::Complete);
+    assert_eq!(coordinator.state().await, ShutdownState::Stopped);
+}
@@ -0,0 +1,109 @@
+//! SmartLoader backed by the runtime's engine and registry: auto-unloads
+//! release the model everywhere it was attached.
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::MockModel;
+use gg_core::engine::{GgufModel, ModelFactory};
+use gg_core::models::smart_loader::{
+    ModelTier, SmartLoader, SmartLoaderConfig, SmartLoaderError,
+};
+use gg_core::models::ModelHandle;
+use gg_core::{Runtime, RuntimeConfig};
+use tempfile::TempDir;
+
+fn model_file(base: &Path, model_id: &str) -> PathBuf {
+    let path = base.join("models").join(format!("{model_id}.gguf"));
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, b"GGUF").unwrap();
+    path
+}
+
+fn serving_loader(runtime: &Runtime, max_resident_models: usize) -> SmartLoader {
+    let factory: ModelFactory = Arc::new(|_: &Path, model_id: &str| {
+        Ok(Arc::new(MockModel::new(model_id)) as Arc<dyn GgufModel>)
+    });
+    SmartLoader::serving(
+        SmartLoaderConfig {
+            max_resident_models: Some(max_resident_models),
+            enable_prediction: false,
+            ..Default::default()
+        },
+        Arc::clone(&runtime.model_loader),
+        factory,
+        Arc::clone(&runtime.model_registry),
+        Arc::clone(&runtime.inference_engine),
+        Arc::clone(&runtime.health),
+    )
+}
+
+async fn registered_names(runtime: &Runtime) -> Vec<String> {
+    let mut names: Vec<_> =
+        runtime.model_registry.list_models().await.into_iter().map(|m| m.name).collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn auto_unload_detaches_victim_from_engine_and_registry() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Runtime::new(RuntimeConfig {
+        base_path: dir.path().to_path_buf(),
+        ..Default::default()
+    });
+    let loader = serving_loader(&runtime, 1);
+    for model_id in ["a", "b"] {
+        let path = model_file(dir.path(), model_id);
+        loader.register(model_id.to_string(), path, ModelTier::Balanced).await.unwrap();
+    }
+
+    loader.get("a").await.unwrap();
+    loader.get("b").await.unwrap();
+    assert_eq!(loader.metrics().await.auto_unloads, 1);
+    assert!(!runtime.inference_engine.has_model("a").await);
+    assert!(runtime.inference_engine.has_model("b").await);
+    assert_eq!(registered_names(&runtime).await, vec!["b".to_string()]);
+
+    // Reloading registers the evicted model once, not alongside a stale record
+    loader.get("a").await.unwrap();
+    assert_eq!(loader.metrics().await.reloads, 1);
+    assert!(runtime.inference_engine.has_model("a").await);
+    assert!(!runtime.inference_engine.has_model("b").await);
+    assert_eq!(registered_names(&runtime).await, vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn explicit_unload_detaches_model_from_engine_and_registry() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Runtime::new(RuntimeConfig {
+        base_path: dir.path().to_path_buf(),
+        ..Default::default()
+    });
+    let loader = serving_loader(&runtime, 2);
+    let path = model_file(dir.path(), "a");
+    loader.register("a".to_string(), path, ModelTier::Balanced).await.unwrap();
+
+    loader.get("a").await.unwrap();
+    loader.unload("a").await;
+    assert!(!runtime.inference_engine.has_model("a").await);
+    assert!(registered_names(&runtime).await.is_empty());
+}
+
+#[tokio::test]
+async fn resident_cap_refused_without_an_unload_callback() {
+    let dir = TempDir::new().unwrap();
+    let mut loader = SmartLoader::new(SmartLoaderConfig {
+        max_resident_models: Some(1),
+        ..Default::default()
+    });
+    loader.set_load_callback(Box::new(|_, _| Box::pin(async { Ok(ModelHandle::new(7)) })));
+    let path = model_file(dir.path(), "a");
+    loader.register("a".to_string(), path, ModelTier::Balanced).await.unwrap();
+
+    let err = loader.get("a").await.unwrap_err();
+    assert!(matches!(err, SmartLoaderError::NoUnloadCallback(id) if id == "a"));
+    assert_eq!(loader.status().await.loaded_count, 0);
+}
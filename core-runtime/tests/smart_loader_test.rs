@@ -0,0 +1,287 @@
+//! SmartLoader: lazy loading, hints, resident cap, pinning and warmup.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use gg_core::health::{HealthChecker, NotReadyReason};
+use gg_core::models::smart_loader::{
+    LoadHint, ModelTier, SmartLoader, SmartLoaderConfig, SmartLoaderError, SmartLoaderStatus,
+};
+use gg_core::shutdown::ShutdownState;
+use tempfile::NamedTempFile;
+use tokio::sync::Notify;
+
+fn create_test_model(size: usize) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&vec![0u8; size]).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+#[tokio::test]
+async fn test_register_zero_overhead() {
+    let loader = SmartLoader::new(SmartLoaderConfig::default());
+    let file = create_test_model(1_000_000);
+
+    loader
+        .register("test".to_string(), file.path().to_path_buf(), ModelTier::Light)
+        .await
+        .unwrap();
+
+    let status = loader.status().await;
+    assert_eq!(status.registered_count, 1);
+    assert_eq!(status.loaded_count, 0);
+    assert_eq!(status.total_loaded_bytes, 0);
+}
+
+#[tokio::test]
+async fn test_semantic_hint_quick_query() {
+    let loader = SmartLoader::new(SmartLoaderConfig::default());
+
+    let light = create_test_model(100_000);
+    let quality = create_test_model(200_000);
+
+    loader
+        .register("light".to_string(), light.path().to_path_buf(), ModelTier::Light)
+        .await.unwrap();
+    loader
+        .register("quality".to_string(), quality.path().to_path_buf(), ModelTier::Quality)
+        .await.unwrap();
+
+    // Hint: quick query
+    loader.hint(LoadHint::QuickQuery).await;
+
+    // Should predict light model
+    let status = loader.status().await;
+    assert_eq!(status.predicted_next, Some("light".to_string()));
+}
+
+#[tokio::test]
+async fn test_cache_hit_fast() {
+    let loader = SmartLoader::new(SmartLoaderConfig::default());
+    let file = create_test_model(100_000);
+
+    loader
+        .register("test".to_string(), file.path().to_path_buf(), ModelTier::Balanced)
+        .await.unwrap();
+
+    // First load (cold)
+    let start = Instant::now();
+    loader.get("test").await.unwrap();
+    let cold_time = start.elapsed();
+
+    // Second load (cache hit)
+    let start = Instant::now();
+    loader.get("test").await.unwrap();
+    let warm_time = start.elapsed();
+
+    println!("Cold: {:?}, Warm: {:?}", cold_time, warm_time);
+
+    let metrics = loader.metrics().await;
+    assert_eq!(metrics.total_loads, 1);
+    assert_eq!(metrics.cache_hits, 1);
+}
+
+#[tokio::test]
+async fn test_tier_based_hints() {
+    let loader = SmartLoader::new(SmartLoaderConfig::default());
+
+    let light = create_test_model(100);
+    let balanced = create_test_model(100);
+    let quality = create_test_model(100);
+
+    for (id, file, tier) in [
+        ("l", &light, ModelTier::Light),
+        ("b", &balanced, ModelTier::Balanced),
+        ("q", &quality, ModelTier::Quality),
+    ] {
+        loader.register(id.to_string(), file.path().to_path_buf(), tier).await.unwrap();
+    }
+
+    // Different hints should predict different models
+    loader.hint(LoadHint::QuickQuery).await;
+    assert_eq!(loader.status().await.predicted_next, Some("l".to_string()));
+
+    loader.hint(LoadHint::ComplexTask).await;
+    assert_eq!(loader.status().await.predicted_next, Some("q".to_string()));
+
+    loader.hint(LoadHint::PreferModel { tier: ModelTier::Balanced }).await;
+    assert_eq!(loader.status().await.predicted_next, Some("b".to_string()));
+}
+
+fn capped_loader(max: usize) -> SmartLoader {
+    SmartLoader::new(SmartLoaderConfig {
+        max_resident_models: Some(max),
+        enable_prediction: false,
+        ..Default::default()
+    })
+}
+
+fn loaded_ids(status: &SmartLoaderStatus) -> Vec<String> {
+    status.loaded_models.iter().map(|(id, _)| id.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_resident_cap_evicts_lru_and_reloads_on_demand() {
+    let loader = capped_loader(1);
+    let a = create_test_model(100);
+    let b = create_test_model(100);
+    loader.register("a".to_string(), a.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    loader.register("b".to_string(), b.path().to_path_buf(), ModelTier::Quality).await.unwrap();
+
+    loader.get("a").await.unwrap();
+    loader.get("b").await.unwrap();
+    assert_eq!(loaded_ids(&loader.status().await), vec!["b".to_string()]);
+    assert_eq!(loader.metrics().await.auto_unloads, 1);
+    assert_eq!(loader.metrics().await.reloads, 0);
+
+    // Requesting the evicted model reloads it and evicts the other
+    loader.get("a").await.unwrap();
+    assert_eq!(loaded_ids(&loader.status().await), vec!["a".to_string()]);
+    let metrics = loader.metrics().await;
+    assert_eq!(metrics.auto_unloads, 2);
+    assert_eq!(metrics.reloads, 1);
+}
+
+#[tokio::test]
+async fn test_auto_unload_waits_for_in_flight_requests() {
+    let loader = Arc::new(capped_loader(1));
+    let a = create_test_model(100);
+    let b = create_test_model(100);
+    loader.register("a".to_string(), a.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    loader.register("b".to_string(), b.path().to_path_buf(), ModelTier::Quality).await.unwrap();
+
+    let pinned = loader.acquire("a").await.unwrap();
+    let loading = {
+        let loader = Arc::clone(&loader);
+        tokio::spawn(async move { loader.get("b").await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!loading.is_finished(), "eviction must wait for the pinned request");
+    assert_eq!(loader.metrics().await.auto_unloads, 0);
+
+    drop(pinned);
+    loading.await.unwrap().unwrap();
+    assert_eq!(loaded_ids(&loader.status().await), vec!["b".to_string()]);
+}
+
+#[tokio::test]
+async fn test_drain_timeout_keeps_model_resident() {
+    let loader = SmartLoader::new(SmartLoaderConfig {
+        max_resident_models: Some(1),
+        drain_timeout: Duration::from_millis(20),
+        ..Default::default()
+    });
+    let a = create_test_model(100);
+    let b = create_test_model(100);
+    loader.register("a".to_string(), a.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    loader.register("b".to_string(), b.path().to_path_buf(), ModelTier::Quality).await.unwrap();
+
+    let _pinned = loader.acquire("a").await.unwrap();
+    assert!(matches!(loader.get("b").await, Err(SmartLoaderError::DrainTimeout(_))));
+    assert_eq!(loaded_ids(&loader.status().await), vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn test_pinned_model_survives_eviction_pressure() {
+    let loader = capped_loader(2);
+    let files: Vec<_> = (0..3).map(|_| create_test_model(100)).collect();
+    for (id, file) in ["classifier", "a", "b"].iter().zip(&files) {
+        loader.register(id.to_string(), file.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    }
+    loader.set_pinned("classifier", true).await.unwrap();
+
+    // classifier is least recently used every time, yet only a/b are evicted
+    loader.get("classifier").await.unwrap();
+    loader.get("a").await.unwrap();
+    loader.get("b").await.unwrap();
+    loader.get("a").await.unwrap();
+
+    let mut loaded = loaded_ids(&loader.status().await);
+    loaded.sort();
+    assert_eq!(loaded, vec!["a".to_string(), "classifier".to_string()]);
+    assert_eq!(loader.metrics().await.auto_unloads, 2);
+}
+
+#[tokio::test]
+async fn test_load_fails_when_all_resident_slots_pinned() {
+    let loader = capped_loader(1);
+    let a = create_test_model(100);
+    let b = create_test_model(100);
+    loader.register("a".to_string(), a.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    loader.register("b".to_string(), b.path().to_path_buf(), ModelTier::Quality).await.unwrap();
+    loader.set_pinned("a", true).await.unwrap();
+
+    loader.get("a").await.unwrap();
+    let err = loader.get("b").await.unwrap_err();
+    assert!(matches!(err, SmartLoaderError::AllSlotsPinned(ref id) if id == "b"));
+    assert!(err.to_string().contains("all resident slots pinned"));
+    assert_eq!(loaded_ids(&loader.status().await), vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn test_readiness_waits_for_auto_warmup() {
+    let health = Arc::new(HealthChecker::default());
+    let forward_pass = Arc::new(Notify::new());
+    let warmed_tokens = Arc::new(AtomicUsize::new(0));
+
+    let mut loader = SmartLoader::new(SmartLoaderConfig {
+        warmup_tokens: 8,
+        ..Default::default()
+    });
+    loader.set_health_checker(Arc::clone(&health));
+    {
+        let forward_pass = Arc::clone(&forward_pass);
+        let warmed_tokens = Arc::clone(&warmed_tokens);
+        loader.set_warmup_callback(Box::new(move |_handle, tokens| {
+            let forward_pass = Arc::clone(&forward_pass);
+            let warmed_tokens = Arc::clone(&warmed_tokens);
+            Box::pin(async move {
+                // Slow first forward pass, released by the test
+                forward_pass.notified().await;
+                warmed_tokens.store(tokens, Ordering::SeqCst);
+                Ok(())
+            })
+        }));
+    }
+    let file = create_test_model(100);
+    loader.register("m".to_string(), file.path().to_path_buf(), ModelTier::Light).await.unwrap();
+    let loader = Arc::new(loader);
+    let not_ready = || health.not_ready_reason(ShutdownState::Running, 1, 0);
+    assert_eq!(not_ready(), None);
+
+    let loading = {
+        let loader = Arc::clone(&loader);
+        tokio::spawn(async move { loader.get("m").await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!loading.is_finished(), "get waits for the warmup");
+    assert_eq!(not_ready(), Some(NotReadyReason::ModelsLoading));
+    assert_eq!(loader.status().await.loaded_count, 0, "not available before warm");
+
+    forward_pass.notify_one();
+    loading.await.unwrap().unwrap();
+    assert_eq!(warmed_tokens.load(Ordering::SeqCst), 8);
+    assert_eq!(not_ready(), None);
+    assert_eq!(loader.status().await.loaded_count, 1);
+}
+
+#[tokio::test]
+async fn test_failed_warmup_still_loads_model() {
+    let health = Arc::new(HealthChecker::default());
+    let mut loader = SmartLoader::new(SmartLoaderConfig {
+        warmup_tokens: 4,
+        ..Default::default()
+    });
+    loader.set_health_checker(Arc::clone(&health));
+    loader.set_warmup_callback(Box::new(|_, _| Box::pin(async { Err("no KV".to_string()) })));
+    let file = create_test_model(100);
+    loader.register("m".to_string(), file.path().to_path_buf(), ModelTier::Light).await.unwrap();
+
+    loader.get("m").await.unwrap();
+    assert_eq!(loader.status().await.loaded_count, 1);
+    assert_eq!(health.warmups_pending(), 0);
+}
@@ -0,0 +1,100 @@
+//! Stop sequences end generation and trim the stop string from the output,
+//! even when it spans several tokens.
+
+mod common;
+
+use common::infer_with_params;
+use gg_core::engine::InferenceParams;
+
+/// Replays fixed token pieces through a `DecodeExecutor` honouring the
+/// request's stop sequences.
+struct ScriptedModel;
+
+const SCRIPT: &[&str] = &["Sure", ".\n", "\nUs", "er:", " more", "###", " tail"];
+
+#[async_trait::async_trait]
+impl gg_core::engine::GgufModel for ScriptedModel {
+    fn model_id(&self) -> &str {
+        "scripted-model"
+    }
+
+    fn capabilities(&self) -> &[gg_core::engine::InferenceCapability] {
+        &[]
+    }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    async fn infer(
+        &self,
+        _input: &gg_core::engine::InferenceInput,
+        config: &gg_core::engine::InferenceConfig,
+    ) -> Result<gg_core::engine::InferenceOutput, gg_core::engine::InferenceError> {
+        use gg_core::engine::{DecodeConfig, DecodeExecutor, FinishReason};
+        let mut executor = DecodeExecutor::new(DecodeConfig {
+            eos_token: u32::MAX,
+            ..Default::default()
+        })
+        .with_stop_sequences(&config.stop_sequences);
+        let mut finish_reason = FinishReason::MaxTokens;
+        for (token, piece) in SCRIPT.iter().enumerate() {
+            let step = executor.accept(token as u32, piece.as_bytes())?;
+            if let Some(reason) = step.finish_reason {
+                finish_reason = reason;
+                break;
+            }
+        }
+        Ok(gg_core::engine::InferenceOutput::Generation(
+            gg_core::engine::GenerationResult::from_bytes(
+                executor.output().to_vec(),
+                executor.tokens_generated() as u32,
+                finish_reason,
+            ),
+        ))
+    }
+
+    async fn unload(&mut self) -> Result<(), gg_core::engine::InferenceError> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn stop_sequences_trim_output_and_token_count() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    runtime
+        .inference_engine
+        .register_model(
+            "scripted-model".into(),
+            gg_core::models::ModelHandle::new(1),
+            std::sync::Arc::new(ScriptedModel),
+        )
+        .await;
+    let with_stops = |stops: &[&str]| InferenceParams {
+        stop_sequences: stops.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    };
+
+    let unstopped = infer_with_params(&runtime, "scripted-model", with_stops(&[])).await;
+    assert_eq!(unstopped.output, SCRIPT.concat());
+    assert_eq!(unstopped.tokens_generated, SCRIPT.len());
+
+    // Stop string produced by a single token
+    let single = infer_with_params(&runtime, "scripted-model", with_stops(&["###"])).await;
+    assert!(single.error.is_none(), "{:?}", single.error);
+    assert_eq!(single.output, "Sure.\n\nUser: more");
+    assert_eq!(single.tokens_generated, 5);
+
+    // Stop string straddling three tokens, starting mid-token
+    let straddling =
+        infer_with_params(&runtime, "scripted-model", with_stops(&["###", "\n\nUser:"])).await;
+    assert_eq!(straddling.output, "Sure.");
+    assert_eq!(straddling.tokens_generated, 2);
+}
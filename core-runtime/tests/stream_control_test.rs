@@ -0,0 +1,158 @@
+//! Control over open inference streams: cancelling one by request ID and
+//! limiting how many a session holds at once.
+
+mod common;
+
+use common::{handshake, GatedSender, RecordingSender};
+use gg_core::engine::InferenceParams;
+use gg_core::ipc::protocol::{
+    decode_message, encode_message, InferenceRequest, IpcMessage, RequestId,
+};
+
+/// Send `CancelRequest` for `request_id` on `session`; whether it cancelled.
+async fn cancel_request(
+    runtime: &gg_core::Runtime,
+    session: &gg_core::ipc::SessionToken,
+    request_id: u64,
+) -> bool {
+    let message = IpcMessage::CancelRequest {
+        request_id: RequestId(request_id),
+    };
+    let (bytes, _) = runtime
+        .ipc_handler
+        .process(&encode_message(&message).unwrap(), Some(session))
+        .await
+        .unwrap();
+    match decode_message(&bytes).unwrap() {
+        IpcMessage::CancelResponse { cancelled, .. } => cancelled,
+        other => panic!("Expected CancelResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn cancel_request_reaches_running_stream_and_is_noop_after() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        ..Default::default()
+    });
+    let handler = &runtime.ipc_handler;
+    let session = handshake(&runtime).await;
+    let intruder = handshake(&runtime).await;
+
+    // The stream stays open while its (error) frame is held by the sender
+    let request = InferenceRequest {
+        request_id: RequestId(42),
+        model_id: "any-model".into(),
+        prompt: String::new(),
+        parameters: InferenceParams {
+            stream: true,
+            ..Default::default()
+        },
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+    let sender = GatedSender(tokio::sync::Notify::new());
+    let token = tokio_util::sync::CancellationToken::new();
+    let stream = handler.process_streaming(request, &session, &sender, token.clone());
+    let operator = async {
+        tokio::task::yield_now().await;
+        // Another session reusing the request ID cannot touch the stream
+        assert!(!cancel_request(&runtime, &intruder, 42).await);
+        let cancelled = cancel_request(&runtime, &session, 42).await;
+        sender.0.notify_one();
+        cancelled
+    };
+    let (streamed, cancelled) = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        tokio::join!(stream, operator)
+    })
+    .await
+    .unwrap();
+    streamed.unwrap();
+    assert!(cancelled);
+    assert!(token.is_cancelled());
+
+    // Once the stream has completed, cancelling it again does nothing
+    assert!(!cancel_request(&runtime, &session, 42).await);
+}
+
+#[tokio::test]
+async fn streams_beyond_session_limit_rejected_other_sessions_unaffected() {
+    let runtime = gg_core::Runtime::new(gg_core::RuntimeConfig {
+        auth_token: "test-token".into(),
+        max_streams_per_session: 2,
+        ..Default::default()
+    });
+    let handler = &runtime.ipc_handler;
+    let (busy, other) = (handshake(&runtime).await, handshake(&runtime).await);
+    let stream_request = |id| InferenceRequest {
+        request_id: RequestId(id),
+        model_id: "any-model".into(),
+        prompt: String::new(),
+        parameters: InferenceParams {
+            stream: true,
+            ..Default::default()
+        },
+        client_metadata: None,
+        priority: None,
+        client_id: None,
+    };
+    let token = tokio_util::sync::CancellationToken::new;
+
+    // Two streams held open on `busy` by senders that never release
+    let held = GatedSender(tokio::sync::Notify::new());
+    let first = handler.process_streaming(stream_request(1), &busy, &held, token());
+    let second = handler.process_streaming(stream_request(2), &busy, &held, token());
+    let checks = async {
+        tokio::task::yield_now().await;
+
+        let rejected = RecordingSender::default();
+        handler
+            .process_streaming(stream_request(3), &busy, &rejected, token())
+            .await
+            .unwrap();
+        let error = rejected.final_error().expect("third stream should be rejected");
+        assert!(error.contains("too many concurrent streams"), "got: {}", error);
+
+        // Another session still gets through the limit check
+        let unaffected = RecordingSender::default();
+        handler
+            .process_streaming(stream_request(4), &other, &unaffected, token())
+            .await
+            .unwrap();
+        let error = unaffected.final_error().unwrap_or_default();
+        assert!(!error.contains("too many concurrent streams"), "got: {}", error);
+    };
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        tokio::select! {
+            _ = first => panic!("held stream finished early"),
+            _ = second => panic!("held stream finished early"),
+            _ = checks => {}
+        }
+    })
+    .await
+    .unwrap();
+
+    // Dropping the held streams frees their slots
+    let retried = RecordingSender::default();
+    handler
+        .process_streaming(stream_request(5), &busy, &retried, token())
+        .await
+        .unwrap();
+    let error = retried.final_error().unwrap_or_default();
+    assert!(!error.contains("too many concurrent streams"), "got: {}", error);
+}
+
+#[test]
+fn zero_streams_per_session_rejected_at_validation() {
+    let config = gg_core::RuntimeConfig {
+        max_streams_per_session: 0,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        config.validate(),
+        Err(gg_core::ipc::HandlerConfigError::ZeroStreamsPerSession)
+    );
+    assert!(gg_core::RuntimeConfig::default().validate().is_ok());
+}
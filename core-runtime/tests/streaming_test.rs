@@ -1,8 +1,15 @@
 //! Tests for streaming response functionality.
 
-use gg_core::engine::InferenceParams;
+mod common;
+
+use std::sync::Arc;
+
+use common::MockModel;
+use gg_core::engine::{ByteLevelBpe, InferenceEngine, InferenceParams, StreamTextDecoder};
+use gg_core::models::ModelHandle;
 use gg_core::ipc::{
     decode_message, encode_message, InferenceRequest, IpcMessage, RequestId, StreamChunk,
+    StreamCoalesceConfig, StreamCoalescer,
 };
 
 // =============================================================================
@@ -132,6 +139,9 @@ fn test_inference_request_with_stream_flag() {
         model_id: "test-model".into(),
         prompt: "test prompt for streaming".into(),
         parameters: params,
+        client_metadata: None,
+        priority: None,
+        client_id: None,
     };
 
     let message = IpcMessage::InferenceRequest(request);
@@ -145,3 +155,74 @@ fn test_inference_request_with_stream_flag() {
         _ => panic!("Expected InferenceRequest message"),
     }
 }
+
+// =============================================================================
+// Token Coalescing Tests
+// =============================================================================
+
+/// Feed tokens through a coalescer and return the encoded wire frames.
+fn stream_frames(config: StreamCoalesceConfig, tokens: &[u32]) -> Vec<Vec<u8>> {
+    let mut coalescer = StreamCoalescer::new(RequestId(9), config);
+    tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &token)| coalescer.push(token, i + 1 == tokens.len(), None))
+        .map(|frame| encode_message(&frame).unwrap())
+        .collect()
+}
+
+/// Decode frames back into (tokens, final flag of last frame).
+fn received_tokens(frames: &[Vec<u8>]) -> (Vec<u32>, bool) {
+    let mut tokens = Vec::new();
+    let mut is_final = false;
+    for frame in frames {
+        match decode_message(frame).unwrap() {
+            IpcMessage::StreamChunk(chunk) => {
+                tokens.push(chunk.token);
+                is_final = chunk.is_final;
+            }
+            IpcMessage::StreamBatch(batch) => {
+                tokens.extend(batch.tokens);
+                is_final = batch.is_final;
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+    (tokens, is_final)
+}
+
+#[test]
+fn test_coalescing_reduces_frames_and_preserves_order() {
+    let tokens: Vec<u32> = (100..110).collect();
+
+    let per_token = stream_frames(StreamCoalesceConfig::default(), &tokens);
+    let coalesced = stream_frames(
+        StreamCoalesceConfig { max_tokens: 4, max_interval_ms: 60_000 },
+        &tokens,
+    );
+
+    assert_eq!(per_token.len(), 10);
+    assert_eq!(coalesced.len(), 3);
+    assert_eq!(received_tokens(&per_token), (tokens.clone(), true));
+    assert_eq!(received_tokens(&coalesced), (tokens, true));
+}
+
+#[tokio::test]
+async fn test_stream_text_uses_plugged_in_detokenizer() {
+    // GPT-2 pieces for " Hello world,\n how"
+    let pieces = ["ĠHello", "Ġworld", ",", "Ċ", "Ġhow"];
+    let engine = InferenceEngine::new(4096);
+    let model = Arc::new(MockModel::new("bpe"));
+    engine.register_model("bpe".into(), ModelHandle::new(1), model).await;
+    let bpe = ByteLevelBpe::new(pieces.iter().map(|p| p.to_string()).collect());
+    engine.set_detokenizer("bpe", Arc::new(bpe)).await;
+
+    let mut decoder = StreamTextDecoder::default();
+    let mut text = String::new();
+    for token in 0..pieces.len() as u32 {
+        let is_final = token + 1 == pieces.len() as u32;
+        text += &decoder.push(&engine, "bpe", &[token], is_final).await.unwrap();
+    }
+
+    assert_eq!(text, " Hello world,\n how");
+}
@@ -13,10 +13,11 @@ fn test_manifest(model_id: &str) -> ModelManifest {
         name: format!("{} Model", model_id),
         version: "1.0.0".to_string(),
         capabilities: vec![ModelCapability::TextGeneration],
-        sha256: "a".repeat(64),
+        sha256: Some("a".repeat(64)),
         size_bytes: 1024,
         architecture: ModelArchitecture::Gguf,
         license: "MIT".to_string(),
+        chat_template: None,
     }
 }
 
@@ -46,7 +47,8 @@ async fn test_zero_downtime_swap() {
             },
             1024,
         )
-        .await;
+        .await
+        .unwrap();
     router.add_route("test-model", old_handle).await.unwrap();
 
     assert!(router.resolve("test-model").await == Some(old_handle));
@@ -86,7 +88,8 @@ async fn test_swap_with_drain_timeout() {
             },
             1024,
         )
-        .await;
+        .await
+        .unwrap();
     router.add_route("test-model", old_handle).await.unwrap();
 
     // Simulate in-flight request that won't complete
@@ -131,7 +134,8 @@ async fn test_swap_preload_failure_rollback() {
             },
             1024,
         )
-        .await;
+        .await
+        .unwrap();
     router.add_route("test-model", old_handle).await.unwrap();
 
     // Try swap with invalid manifest
@@ -162,7 +166,8 @@ async fn test_concurrent_swap_rejected() {
             },
             1024,
         )
-        .await;
+        .await
+        .unwrap();
     router.add_route("test-model", old_handle).await.unwrap();
 
     // Start a swap that will be blocked by in-flight request
@@ -213,7 +218,8 @@ async fn test_swap_manager_is_idle_after_completion() {
             },
             1024,
         )
-        .await;
+        .await
+        .unwrap();
     router.add_route("test-model", old_handle).await.unwrap();
 
     assert!(manager.is_idle().await);
@@ -4,7 +4,7 @@ use std::time::{Duration, Instant};
 
 use gg_core::engine::InferenceParams;
 use gg_core::ipc::{decode_message, encode_message, IpcMessage, RequestId};
-use gg_core::scheduler::{Priority, RequestQueue, RequestQueueConfig};
+use gg_core::scheduler::{Priority, QueueError, RequestOrigin, RequestQueue, RequestQueueConfig};
 
 #[test]
 fn test_request_with_timeout() {
@@ -180,3 +180,67 @@ async fn test_expired_request_skipped_on_dequeue() {
     let request = queue.dequeue().await.unwrap();
     assert_eq!(request.id, id2);
 }
+
+#[tokio::test]
+async fn test_cancel_all_notifies_every_pending_caller() {
+    let queue = RequestQueue::new(RequestQueueConfig::default());
+    let mut tickets = Vec::new();
+    for priority in [Priority::Low, Priority::Normal, Priority::High] {
+        let ticket = queue
+            .enqueue_tracked(
+                RequestOrigin::Ipc,
+                None,
+                "model".to_string(),
+                "prompt".to_string(),
+                InferenceParams::default(),
+                priority,
+            )
+            .await
+            .unwrap();
+        tickets.push(ticket);
+    }
+
+    assert_eq!(queue.cancel_all().await, 3);
+    assert!(queue.is_empty().await);
+    for ticket in &tickets {
+        assert!(ticket.is_cancelled());
+        assert!(matches!(ticket.cancelled().await, QueueError::Cancelled));
+    }
+}
+
+#[tokio::test]
+async fn test_cancel_all_removes_finished_entries_without_counting_them() {
+    let queue = RequestQueue::new(RequestQueueConfig {
+        max_pending: 3,
+        ..Default::default()
+    });
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let (id, _) = queue
+            .enqueue(
+                "model".to_string(),
+                "prompt".to_string(),
+                InferenceParams::default(),
+                Priority::Normal,
+            )
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+    assert!(queue.cancel(ids[0]).await);
+
+    assert_eq!(queue.cancel_all().await, 2);
+    assert!(queue.is_empty().await);
+    // Every slot came back, including the already-cancelled entry's
+    for _ in 0..3 {
+        queue
+            .enqueue(
+                "model".to_string(),
+                "prompt".to_string(),
+                InferenceParams::default(),
+                Priority::Normal,
+            )
+            .await
+            .unwrap();
+    }
+}